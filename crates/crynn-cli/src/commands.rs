@@ -0,0 +1,163 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use crynn_storage::{StorageManager, Visit};
+
+use crate::error::CliError;
+
+const BOOKMARK_PREFIX: &str = "bookmark:";
+
+pub fn fetch(url: &str) -> Result<(), CliError> {
+    Err(CliError::NotImplemented {
+        command: format!("fetch {url}"),
+        subsystem: "NetworkManager".to_string(),
+    })
+}
+
+pub fn bookmarks_export(storage: &StorageManager, path: &Path) -> Result<(), CliError> {
+    let db = storage.database("storage").expect("storage database always present");
+    let bookmarks: BTreeMap<String, serde_json::Value> = db
+        .iter()?
+        .filter_map(|(key, value)| key.strip_prefix(BOOKMARK_PREFIX).map(|id| (id.to_string(), value.clone())))
+        .collect();
+    fs::write(path, serde_json::to_vec_pretty(&bookmarks)?)?;
+    Ok(())
+}
+
+pub fn bookmarks_import(storage: &mut StorageManager, path: &Path) -> Result<(), CliError> {
+    let bytes = fs::read(path)?;
+    let bookmarks: BTreeMap<String, serde_json::Value> = serde_json::from_slice(&bytes)?;
+
+    let db = storage.database_mut("storage").expect("storage database always present");
+    for (id, value) in bookmarks {
+        db.set(format!("{BOOKMARK_PREFIX}{id}"), value)?;
+    }
+    db.save()?;
+    Ok(())
+}
+
+pub fn history_search(storage: &StorageManager, query: &str) -> Result<Vec<Visit>, CliError> {
+    let needle = query.to_lowercase();
+
+    let mut matches: Vec<Visit> = crynn_storage::visits(storage)?
+        .into_iter()
+        .filter(|visit| visit.title.to_lowercase().contains(&needle) || visit.url.to_lowercase().contains(&needle))
+        .collect();
+    matches.sort_by(|a, b| a.id.cmp(&b.id));
+    Ok(matches)
+}
+
+pub fn email_sync() -> Result<(), CliError> {
+    Err(CliError::NotImplemented {
+        command: "email sync".to_string(),
+        subsystem: "crynn-email".to_string(),
+    })
+}
+
+/// Manual counterpart to whatever interval a configured sync would run
+/// on. `crynn-sync` has a real `WebDavTransport` now, but nothing in
+/// this tree yet has a WebDAV URL or passphrase to hand it.
+pub fn sync_now() -> Result<(), CliError> {
+    Err(CliError::NotImplemented {
+        command: "sync now".to_string(),
+        subsystem: "crynn-sync".to_string(),
+    })
+}
+
+pub fn vpn_connect(provider: &str, location: &str) -> Result<(), CliError> {
+    Err(CliError::NotImplemented {
+        command: format!("vpn connect {provider} {location}"),
+        subsystem: "crynn-vpn".to_string(),
+    })
+}
+
+pub fn profile_memory_report() -> String {
+    let registry = crynn_engine::TabRegistry::new();
+    let snapshot = registry.profiler_snapshot();
+
+    let mut report = format!("total: {} bytes\n", snapshot.total_memory_bytes());
+    for component in &snapshot.components {
+        report.push_str(&format!("  {:<10} {} bytes\n", component.label, component.memory_bytes));
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("crynn-cli-test-{}-{name}", std::process::id()))
+    }
+
+    #[test]
+    fn bookmarks_round_trip_through_export_and_import() {
+        let data_dir = temp_dir("bookmarks-data");
+        let export_path = temp_dir("bookmarks-export.json");
+
+        let mut storage = StorageManager::open(&data_dir, None).unwrap();
+        storage
+            .database_mut("storage")
+            .unwrap()
+            .set("bookmark:1", serde_json::json!({"url": "https://example.com"}))
+            .unwrap();
+
+        bookmarks_export(&storage, &export_path).unwrap();
+
+        let mut fresh = StorageManager::open(temp_dir("bookmarks-fresh"), None).unwrap();
+        bookmarks_import(&mut fresh, &export_path).unwrap();
+
+        assert_eq!(
+            fresh.database("storage").unwrap().get("bookmark:1").unwrap().unwrap()["url"],
+            "https://example.com"
+        );
+
+        let _ = fs::remove_dir_all(&data_dir);
+        let _ = fs::remove_file(&export_path);
+        let _ = fs::remove_dir_all(temp_dir("bookmarks-fresh"));
+    }
+
+    #[test]
+    fn history_search_matches_case_insensitively_on_title_or_url() {
+        let data_dir = temp_dir("history-data");
+        let mut storage = StorageManager::open(&data_dir, None).unwrap();
+        crynn_storage::record_visit(
+            &mut storage,
+            &Visit {
+                id: "1".to_string(),
+                url: "https://rust-lang.org".to_string(),
+                title: "Rust Programming Language".to_string(),
+                visit_type: crynn_storage::VisitType::Typed,
+                at: 1,
+                from_visit: None,
+            },
+        )
+        .unwrap();
+        crynn_storage::record_visit(
+            &mut storage,
+            &Visit {
+                id: "2".to_string(),
+                url: "https://example.com/recipes".to_string(),
+                title: "Cooking recipes".to_string(),
+                visit_type: crynn_storage::VisitType::Link,
+                at: 2,
+                from_visit: None,
+            },
+        )
+        .unwrap();
+
+        let results = history_search(&storage, "rust").unwrap();
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].id, "1");
+
+        let _ = fs::remove_dir_all(&data_dir);
+    }
+
+    #[test]
+    fn fetch_reports_not_implemented() {
+        let err = fetch("https://example.com").unwrap_err();
+        assert!(matches!(err, CliError::NotImplemented { .. }));
+    }
+}
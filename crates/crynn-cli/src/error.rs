@@ -0,0 +1,17 @@
+/// Errors surfaced at the CLI boundary. Subsystem crates keep their own
+/// typed errors (see `crynn-error`); this just adds the one case specific
+/// to a headless binary: a command whose backend hasn't landed yet.
+#[derive(Debug, thiserror::Error)]
+pub enum CliError {
+    #[error(transparent)]
+    Storage(#[from] crynn_error::StorageError),
+
+    #[error("`crynn {command}` isn't wired up yet: {subsystem} hasn't landed in this tree")]
+    NotImplemented { command: String, subsystem: String },
+
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+}
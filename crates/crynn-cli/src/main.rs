@@ -0,0 +1,149 @@
+//! Headless entry point exposing the backend crates without the GUI
+//! shell: scripting, and exercising the backends in isolation during
+//! development.
+
+mod commands;
+mod error;
+mod paths;
+
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use clap::{Parser, Subcommand};
+
+use error::CliError;
+
+#[derive(Parser)]
+#[command(name = "crynn", about = "Headless CLI for the Crynn backends")]
+struct Cli {
+    /// Profile data directory. Defaults to the platform data dir.
+    #[arg(long, global = true)]
+    data_dir: Option<PathBuf>,
+
+    /// Master password for the local databases, if one is set.
+    #[arg(long, global = true)]
+    password: Option<String>,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Fetch a URL through the network stack's cache and cookie jar.
+    Fetch { url: String },
+    /// Manage bookmarks.
+    Bookmarks {
+        #[command(subcommand)]
+        action: BookmarksAction,
+    },
+    /// Query browsing history.
+    History {
+        #[command(subcommand)]
+        action: HistoryAction,
+    },
+    /// Email account operations.
+    Email {
+        #[command(subcommand)]
+        action: EmailAction,
+    },
+    /// VPN operations.
+    Vpn {
+        #[command(subcommand)]
+        action: VpnAction,
+    },
+    /// Bookmark/history sync operations.
+    Sync {
+        #[command(subcommand)]
+        action: SyncAction,
+    },
+    /// Profile-wide diagnostics.
+    Profile {
+        #[command(subcommand)]
+        action: ProfileAction,
+    },
+}
+
+#[derive(Subcommand)]
+enum BookmarksAction {
+    Export { path: PathBuf },
+    Import { path: PathBuf },
+}
+
+#[derive(Subcommand)]
+enum HistoryAction {
+    Search { query: String },
+}
+
+#[derive(Subcommand)]
+enum EmailAction {
+    Sync,
+}
+
+#[derive(Subcommand)]
+enum VpnAction {
+    Connect { provider: String, location: String },
+}
+
+#[derive(Subcommand)]
+enum SyncAction {
+    Now,
+}
+
+#[derive(Subcommand)]
+enum ProfileAction {
+    MemoryReport,
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    match run(cli) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(cli: Cli) -> Result<(), CliError> {
+    let data_dir = cli.data_dir.unwrap_or_else(paths::default_data_dir);
+    let password = cli.password.as_deref();
+
+    match cli.command {
+        Command::Fetch { url } => commands::fetch(&url),
+        Command::Bookmarks { action } => {
+            let mut storage = crynn_storage::StorageManager::open(&data_dir, password)?;
+            match action {
+                BookmarksAction::Export { path } => commands::bookmarks_export(&storage, &path),
+                BookmarksAction::Import { path } => commands::bookmarks_import(&mut storage, &path),
+            }
+        }
+        Command::History { action } => {
+            let storage = crynn_storage::StorageManager::open(&data_dir, password)?;
+            match action {
+                HistoryAction::Search { query } => {
+                    for visit in commands::history_search(&storage, &query)? {
+                        println!("{}: {} ({})", visit.id, visit.title, visit.url);
+                    }
+                    Ok(())
+                }
+            }
+        }
+        Command::Email { action } => match action {
+            EmailAction::Sync => commands::email_sync(),
+        },
+        Command::Vpn { action } => match action {
+            VpnAction::Connect { provider, location } => commands::vpn_connect(&provider, &location),
+        },
+        Command::Sync { action } => match action {
+            SyncAction::Now => commands::sync_now(),
+        },
+        Command::Profile { action } => match action {
+            ProfileAction::MemoryReport => {
+                print!("{}", commands::profile_memory_report());
+                Ok(())
+            }
+        },
+    }
+}
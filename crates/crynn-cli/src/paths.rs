@@ -0,0 +1,17 @@
+use std::path::{Path, PathBuf};
+
+/// Default profile data directory when `--data-dir` isn't given.
+pub fn default_data_dir() -> PathBuf {
+    dirs_fallback()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("crynn")
+}
+
+/// Minimal stand-in for a platform data-dir lookup until the shared config
+/// crate provides one; mirrors `crynn-shell`'s own fallback for the zoom
+/// store.
+fn dirs_fallback() -> Option<PathBuf> {
+    std::env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| Path::new(&home).join(".local/share")))
+}
@@ -0,0 +1,456 @@
+//! A single layered configuration shared by every subsystem and by both
+//! shells (the egui shell and, eventually, the Tauri frontend), instead of
+//! each one keeping its own config file and defaults.
+//!
+//! Layers apply in order, each overriding the last: built-in defaults, the
+//! config file, environment variables, then runtime overrides set by code
+//! (e.g. a settings dialog). [`ConfigManager::reload`] re-applies the file
+//! and env layers without losing runtime overrides.
+//!
+//! [`ConfigManager::reload_if_changed`] is [`ConfigManager::reload`]'s
+//! polling counterpart, for a caller watching the config file for edits
+//! made outside the shell (another window, or a text editor): it only
+//! reloads — and only notifies listeners — when the file's modified
+//! time has actually moved since the last reload. Listeners receive a
+//! [`ChangedSections`] alongside the new [`Config`] either way, so a
+//! listener pushing a setting into a live subsystem (the user agent,
+//! the search client, a cache budget) can skip sections that didn't
+//! change instead of re-applying all of them on every reload.
+
+mod sections;
+
+use std::env;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::SystemTime;
+
+use crynn_error::ConfigError;
+use serde::{Deserialize, Serialize};
+
+pub use sections::{
+    ContentConfig, CookiesConfig, EmailConfig, GesturesConfig, GlobalHotkeysConfig, LocaleConfig, LoggingConfig, MetricsConfig, NetworkConfig,
+    OnboardingConfig, PowerConfig, PrivacyConfig, SearchConfig, StorageConfig, Theme, TrackingConfig, TranslationConfig, UpdatesConfig, VpnConfig,
+};
+
+/// The full, typed configuration tree. Every section has `#[serde(default)]`
+/// fields so a config file only needs to mention what it overrides.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    pub storage: StorageConfig,
+    pub network: NetworkConfig,
+    pub vpn: VpnConfig,
+    pub email: EmailConfig,
+    pub logging: LoggingConfig,
+    pub tracking: TrackingConfig,
+    pub cookies: CookiesConfig,
+    pub locale: LocaleConfig,
+    pub metrics: MetricsConfig,
+    pub search: SearchConfig,
+    pub privacy: PrivacyConfig,
+    pub power: PowerConfig,
+    pub updates: UpdatesConfig,
+    pub content: ContentConfig,
+    pub global_hotkeys: GlobalHotkeysConfig,
+    pub gestures: GesturesConfig,
+    pub translation: TranslationConfig,
+    pub onboarding: OnboardingConfig,
+}
+
+type ChangeListener = Box<dyn Fn(&Config, ChangedSections)>;
+type RuntimeOverride = Box<dyn Fn(&mut Config)>;
+
+/// Which top-level [`Config`] sections differ between the [`Config`]
+/// [`ConfigManager`] notified listeners with last and the one it just
+/// rebuilt. Lets a listener that only cares about, say, `search` skip
+/// doing anything on a reload that only touched `power`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ChangedSections {
+    pub storage: bool,
+    pub network: bool,
+    pub vpn: bool,
+    pub email: bool,
+    pub logging: bool,
+    pub tracking: bool,
+    pub cookies: bool,
+    pub locale: bool,
+    pub metrics: bool,
+    pub search: bool,
+    pub privacy: bool,
+    pub power: bool,
+    pub updates: bool,
+    pub content: bool,
+    pub global_hotkeys: bool,
+    pub translation: bool,
+    pub gestures: bool,
+    pub onboarding: bool,
+}
+
+impl ChangedSections {
+    fn diff(old: &Config, new: &Config) -> Self {
+        Self {
+            storage: old.storage != new.storage,
+            network: old.network != new.network,
+            vpn: old.vpn != new.vpn,
+            email: old.email != new.email,
+            logging: old.logging != new.logging,
+            tracking: old.tracking != new.tracking,
+            cookies: old.cookies != new.cookies,
+            locale: old.locale != new.locale,
+            metrics: old.metrics != new.metrics,
+            search: old.search != new.search,
+            privacy: old.privacy != new.privacy,
+            power: old.power != new.power,
+            updates: old.updates != new.updates,
+            content: old.content != new.content,
+            global_hotkeys: old.global_hotkeys != new.global_hotkeys,
+            translation: old.translation != new.translation,
+            gestures: old.gestures != new.gestures,
+            onboarding: old.onboarding != new.onboarding,
+        }
+    }
+
+    /// Whether any section changed at all, for a caller deciding whether
+    /// a reload is even worth logging.
+    pub fn any(&self) -> bool {
+        *self != Self::default()
+    }
+}
+
+/// Owns the layered [`Config`], reloading it from disk/env and notifying
+/// subscribers when it changes.
+pub struct ConfigManager {
+    path: Option<PathBuf>,
+    base: Config,
+    config: Config,
+    overrides: Vec<RuntimeOverride>,
+    listeners: Vec<ChangeListener>,
+    /// The config file's modified time as of the last successful
+    /// [`Self::reload`]/[`Self::reload_if_changed`], for the latter to
+    /// detect an edit without re-parsing the file on every poll.
+    last_modified: Option<SystemTime>,
+}
+
+impl ConfigManager {
+    /// Loads defaults, then the file at `path` (if it exists), then
+    /// environment variables.
+    pub fn load(path: impl Into<PathBuf>) -> Result<Self, ConfigError> {
+        let path = path.into();
+        let base = build_base(Some(&path))?;
+        Ok(Self {
+            last_modified: file_modified(&path),
+            path: Some(path),
+            config: base.clone(),
+            base,
+            overrides: Vec::new(),
+            listeners: Vec::new(),
+        })
+    }
+
+    /// Defaults plus environment variables only, no config file.
+    pub fn without_file() -> Result<Self, ConfigError> {
+        let base = build_base(None)?;
+        Ok(Self {
+            path: None,
+            config: base.clone(),
+            base,
+            overrides: Vec::new(),
+            listeners: Vec::new(),
+            last_modified: None,
+        })
+    }
+
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    pub fn on_change(&mut self, listener: impl Fn(&Config, ChangedSections) + 'static) {
+        self.listeners.push(Box::new(listener));
+    }
+
+    fn notify(&self, changed: ChangedSections) {
+        for listener in &self.listeners {
+            listener(&self.config, changed);
+        }
+    }
+
+    /// Applies a runtime override on top of the current base layers. Kept
+    /// around so a later [`ConfigManager::reload`] can re-apply it after
+    /// refreshing the file/env layers underneath.
+    pub fn set_override(&mut self, f: impl Fn(&mut Config) + 'static) {
+        self.overrides.push(Box::new(f));
+        let previous = self.config.clone();
+        self.rebuild();
+        self.notify(ChangedSections::diff(&previous, &self.config));
+    }
+
+    /// Re-reads the config file and environment, then re-applies every
+    /// runtime override on top, and notifies listeners of the result.
+    #[tracing::instrument(skip(self))]
+    pub fn reload(&mut self) -> Result<(), ConfigError> {
+        self.base = build_base(self.path.as_deref())?;
+        self.last_modified = self.path.as_deref().and_then(file_modified);
+        let previous = self.config.clone();
+        self.rebuild();
+        self.notify(ChangedSections::diff(&previous, &self.config));
+        tracing::info!("config reloaded");
+        Ok(())
+    }
+
+    /// [`Self::reload`], but only if the config file's modified time has
+    /// moved since the last reload — the poll a caller watching the file
+    /// for edits made outside this process (another window, or a text
+    /// editor) runs on a timer instead of re-parsing the file every
+    /// tick. Always reloads on the very first call if the file's
+    /// modified time couldn't be determined at load time, since there's
+    /// nothing to compare against yet. Returns whether a reload
+    /// happened.
+    pub fn reload_if_changed(&mut self) -> Result<bool, ConfigError> {
+        let Some(path) = self.path.clone() else {
+            return Ok(false);
+        };
+        let modified = file_modified(&path);
+        if modified.is_some() && modified == self.last_modified {
+            return Ok(false);
+        }
+        self.reload()?;
+        Ok(true)
+    }
+
+    fn rebuild(&mut self) {
+        let mut config = self.base.clone();
+        for over in &self.overrides {
+            over(&mut config);
+        }
+        self.config = config;
+    }
+}
+
+fn file_modified(path: &std::path::Path) -> Option<SystemTime> {
+    fs::metadata(path).ok()?.modified().ok()
+}
+
+fn build_base(path: Option<&std::path::Path>) -> Result<Config, ConfigError> {
+    let mut config = Config::default();
+
+    if let Some(path) = path {
+        match fs::read_to_string(path) {
+            Ok(contents) => {
+                config = toml::from_str(&contents).map_err(|e| ConfigError::InvalidToml {
+                    path: path.display().to_string(),
+                    reason: e.to_string(),
+                })?;
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e.into()),
+        }
+    }
+
+    apply_env(&mut config);
+    Ok(config)
+}
+
+fn env_var<T: FromStr>(key: &str) -> Option<T> {
+    env::var(key).ok().and_then(|v| v.parse().ok())
+}
+
+/// Known environment overrides, one per config field. Kept as an explicit
+/// list rather than reflection so it's obvious at a glance which vars the
+/// shell respects.
+fn apply_env(config: &mut Config) {
+    if let Some(v) = env_var("CRYNN_STORAGE_DATA_DIR") {
+        config.storage.data_dir = v;
+    }
+    if let Some(v) = env_var("CRYNN_STORAGE_MAX_CACHE_BYTES") {
+        config.storage.max_cache_bytes = v;
+    }
+    if let Some(v) = env_var("CRYNN_NETWORK_CONNECT_TIMEOUT_MS") {
+        config.network.connect_timeout_ms = v;
+    }
+    if let Some(v) = env_var("CRYNN_NETWORK_READ_TIMEOUT_MS") {
+        config.network.read_timeout_ms = v;
+    }
+    if let Some(v) = env_var("CRYNN_NETWORK_REQUEST_TIMEOUT_MS") {
+        config.network.request_timeout_ms = v;
+    }
+    if let Some(v) = env_var("CRYNN_NETWORK_MAX_CONNECTIONS_PER_HOST") {
+        config.network.max_connections_per_host = v;
+    }
+    if let Some(v) = env_var("CRYNN_VPN_AUTO_CONNECT") {
+        config.vpn.auto_connect = v;
+    }
+    if let Some(v) = env_var("CRYNN_VPN_PREFERRED_REGION") {
+        config.vpn.preferred_region = v;
+    }
+    if let Some(v) = env_var("CRYNN_EMAIL_SYNC_INTERVAL_SECS") {
+        config.email.sync_interval_secs = v;
+    }
+    if let Some(v) = env_var("CRYNN_EMAIL_FETCH_IMAGES") {
+        config.email.fetch_images = v;
+    }
+    if let Some(v) = env_var("CRYNN_LOG_LEVEL") {
+        config.logging.level = v;
+    }
+    if let Some(v) = env_var("CRYNN_TRACKING_ENABLED") {
+        config.tracking.enabled = v;
+    }
+    if let Some(v) = env_var("CRYNN_TRACKING_STRICTNESS") {
+        config.tracking.strictness = v;
+    }
+    if let Some(v) = env_var("CRYNN_COOKIES_POLICY") {
+        config.cookies.policy = v;
+    }
+    if let Some(v) = env_var("CRYNN_LOCALE") {
+        config.locale.locale = v;
+    }
+    if let Some(v) = env_var("CRYNN_METRICS_ENABLED") {
+        config.metrics.enabled = v;
+    }
+    if let Some(v) = env_var("CRYNN_PRIVACY_RESIST_FINGERPRINTING") {
+        config.privacy.resist_fingerprinting = v;
+    }
+    if let Some(v) = env_var("CRYNN_PRIVACY_CLIPBOARD_URL_DETECTION_ENABLED") {
+        config.privacy.clipboard_url_detection_enabled = v;
+    }
+    if let Some(v) = env_var("CRYNN_POWER_DEFAULT_CACHE_BATCH_SIZE") {
+        config.power.default_cache_batch_size = v;
+    }
+    if let Some(v) = env_var("CRYNN_POWER_CONSTRAINED_CACHE_BATCH_SIZE") {
+        config.power.constrained_cache_batch_size = v;
+    }
+    if let Some(v) = env_var("CRYNN_UPDATES_CHANNEL") {
+        config.updates.channel = v;
+    }
+    if let Some(v) = env_var("CRYNN_UPDATES_AUTO_CHECK") {
+        config.updates.auto_check = v;
+    }
+    if let Some(v) = env_var("CRYNN_UPDATES_WIFI_ONLY") {
+        config.updates.wifi_only = v;
+    }
+    if let Some(v) = env_var("CRYNN_CONTENT_IMAGES_ENABLED") {
+        config.content.images_enabled = v;
+    }
+    if let Some(v) = env_var("CRYNN_CONTENT_POPUPS_ENABLED") {
+        config.content.popups_enabled = v;
+    }
+    if let Some(v) = env_var("CRYNN_CONTENT_SPELLCHECK_ENABLED") {
+        config.content.spellcheck_enabled = v;
+    }
+    if let Some(v) = env_var("CRYNN_TRANSLATION_ENABLED") {
+        config.translation.enabled = v;
+    }
+    if let Some(v) = env_var("CRYNN_TRANSLATION_ENDPOINT") {
+        config.translation.endpoint = v;
+    }
+    if let Some(v) = env_var("CRYNN_TRANSLATION_TARGET_LANGUAGE") {
+        config.translation.target_language = v;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("crynn-config-test-{}-{name}", std::process::id()))
+    }
+
+    #[test]
+    fn file_layer_overrides_defaults() {
+        let path = temp_path("file-layer.toml");
+        fs::write(&path, "[vpn]\nauto_connect = true\n").unwrap();
+
+        let manager = ConfigManager::load(&path).unwrap();
+
+        assert!(manager.config().vpn.auto_connect);
+        assert_eq!(manager.config().vpn.preferred_region, "auto");
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn runtime_override_survives_reload() {
+        let path = temp_path("override-survives.toml");
+        fs::write(&path, "[network]\nrequest_timeout_ms = 1000\n").unwrap();
+
+        let mut manager = ConfigManager::load(&path).unwrap();
+        manager.set_override(|c| c.network.max_connections_per_host = 42);
+        assert_eq!(manager.config().network.max_connections_per_host, 42);
+
+        manager.reload().unwrap();
+
+        assert_eq!(manager.config().network.max_connections_per_host, 42);
+        assert_eq!(manager.config().network.request_timeout_ms, 1000);
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn change_listeners_fire_on_override_and_reload() {
+        let path = temp_path("listeners.toml");
+        let _ = fs::remove_file(&path);
+
+        let mut manager = ConfigManager::load(&path).unwrap();
+        let calls = Arc::new(AtomicUsize::new(0));
+        let calls_clone = calls.clone();
+        manager.on_change(move |_, _| {
+            calls_clone.fetch_add(1, Ordering::SeqCst);
+        });
+
+        manager.set_override(|c| c.email.fetch_images = true);
+        manager.reload().unwrap();
+
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn change_listeners_report_only_the_sections_that_changed() {
+        let path = temp_path("changed-sections.toml");
+        let _ = fs::remove_file(&path);
+
+        let mut manager = ConfigManager::load(&path).unwrap();
+        let seen = Arc::new(std::sync::Mutex::new(ChangedSections::default()));
+        let seen_clone = seen.clone();
+        manager.on_change(move |_, changed| *seen_clone.lock().unwrap() = changed);
+
+        manager.set_override(|c| c.email.fetch_images = true);
+
+        let changed = *seen.lock().unwrap();
+        assert!(changed.email);
+        assert!(!changed.network);
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn reload_if_changed_skips_reloading_when_the_file_is_untouched() {
+        let path = temp_path("reload-if-changed-untouched.toml");
+        fs::write(&path, "[network]\nrequest_timeout_ms = 1000\n").unwrap();
+
+        let mut manager = ConfigManager::load(&path).unwrap();
+        assert!(!manager.reload_if_changed().unwrap());
+
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn reload_if_changed_picks_up_an_edit_made_after_load() {
+        let path = temp_path("reload-if-changed-edited.toml");
+        fs::write(&path, "[network]\nrequest_timeout_ms = 1000\n").unwrap();
+
+        let mut manager = ConfigManager::load(&path).unwrap();
+
+        // Nudge the modified time forward so the poll sees a change even
+        // on filesystems with coarse mtime resolution.
+        let future = SystemTime::now() + std::time::Duration::from_secs(2);
+        fs::write(&path, "[network]\nrequest_timeout_ms = 2000\n").unwrap();
+        let file = fs::File::open(&path).unwrap();
+        let _ = file.set_modified(future);
+
+        assert!(manager.reload_if_changed().unwrap());
+        assert_eq!(manager.config().network.request_timeout_ms, 2000);
+
+        let _ = fs::remove_file(&path);
+    }
+}
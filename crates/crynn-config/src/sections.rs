@@ -0,0 +1,368 @@
+use serde::{Deserialize, Serialize};
+
+/// Where local databases and caches live on disk.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StorageConfig {
+    pub data_dir: String,
+    pub max_cache_bytes: u64,
+}
+
+impl Default for StorageConfig {
+    fn default() -> Self {
+        Self {
+            data_dir: ".".to_string(),
+            max_cache_bytes: 512 * 1024 * 1024,
+        }
+    }
+}
+
+/// Networking defaults: per-phase timeouts and the shared connection pool
+/// size. `request_timeout_ms` is the hard ceiling on a request as a
+/// whole; `connect_timeout_ms` and `read_timeout_ms` bound the connect
+/// and each-read phases separately, so a slow-to-connect server and a
+/// server that connects fine but trickles bytes fail for different,
+/// more specific reasons instead of both just hitting one end-to-end
+/// clock. See `crynn_network::PhaseTimeouts`, which these three fields
+/// feed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NetworkConfig {
+    pub connect_timeout_ms: u64,
+    pub read_timeout_ms: u64,
+    pub request_timeout_ms: u64,
+    pub max_connections_per_host: u32,
+}
+
+impl Default for NetworkConfig {
+    fn default() -> Self {
+        Self {
+            connect_timeout_ms: 10_000,
+            read_timeout_ms: 30_000,
+            request_timeout_ms: 30_000,
+            max_connections_per_host: 6,
+        }
+    }
+}
+
+/// VPN client defaults.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct VpnConfig {
+    pub auto_connect: bool,
+    pub preferred_region: String,
+}
+
+impl Default for VpnConfig {
+    fn default() -> Self {
+        Self {
+            auto_connect: false,
+            preferred_region: "auto".to_string(),
+        }
+    }
+}
+
+/// Controls the shared `tracing` setup in `crynn-log`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LoggingConfig {
+    pub level: String,
+}
+
+impl Default for LoggingConfig {
+    fn default() -> Self {
+        Self {
+            level: "info".to_string(),
+        }
+    }
+}
+
+/// The UI language, used by `crynn-i18n` to pick which bundled message
+/// resources to load.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LocaleConfig {
+    pub locale: String,
+}
+
+impl Default for LocaleConfig {
+    fn default() -> Self {
+        Self {
+            locale: "en-US".to_string(),
+        }
+    }
+}
+
+/// Tracking-protection defaults. `strictness` is a plain string rather
+/// than an enum so this crate doesn't need to depend on
+/// `crynn-tracking-protection` just to hold a setting; callers parse it
+/// with `StrictnessLevel::from_str`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TrackingConfig {
+    pub enabled: bool,
+    pub strictness: String,
+}
+
+impl Default for TrackingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            strictness: "standard".to_string(),
+        }
+    }
+}
+
+/// Cookie handling defaults. `policy` is a plain string, the same way
+/// [`TrackingConfig::strictness`] is, so this crate doesn't need to
+/// depend on `crynn-cookies` just to hold a setting; callers parse it
+/// with `CookiePolicy::from_str`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CookiesConfig {
+    pub policy: String,
+}
+
+impl Default for CookiesConfig {
+    fn default() -> Self {
+        Self {
+            policy: "block_third_party".to_string(),
+        }
+    }
+}
+
+/// Local telemetry defaults. Disabled unless the user opts in; `crynn-metrics`
+/// enforces the same thing structurally by dropping recorded events while
+/// its store is disabled, so this flag stays the single source of truth.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MetricsConfig {
+    pub enabled: bool,
+}
+
+/// The omnibox's remote search-suggestions defaults. `suggest_url` is an
+/// OpenSearch-style suggestions endpoint with `{}` standing in for the
+/// URL-encoded query.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SearchConfig {
+    pub suggestions_enabled: bool,
+    pub suggest_url: String,
+}
+
+impl Default for SearchConfig {
+    fn default() -> Self {
+        Self {
+            suggestions_enabled: true,
+            suggest_url: "https://search.crynn.example/suggest?q={}".to_string(),
+        }
+    }
+}
+
+/// Email client defaults.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EmailConfig {
+    pub sync_interval_secs: u64,
+    pub fetch_images: bool,
+}
+
+impl Default for EmailConfig {
+    fn default() -> Self {
+        Self {
+            sync_interval_secs: 300,
+            fetch_images: false,
+        }
+    }
+}
+
+/// Fingerprinting-resistance defaults. `enabled` is the global toggle;
+/// a site the user has exempted overrides it through
+/// `crynn-engine`'s `SitePrefStore`, the same seam `crynn-shell`'s
+/// settings panel already edits `privacy.resist_fingerprinting` through.
+///
+/// `clipboard_url_detection_enabled` is a separate opt-in: off by
+/// default because polling the clipboard for every copy is itself a
+/// thing a privacy-conscious default shouldn't do silently, unlike
+/// `resist_fingerprinting` which defaults to protecting the user more.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PrivacyConfig {
+    pub resist_fingerprinting: bool,
+    pub clipboard_url_detection_enabled: bool,
+}
+
+/// Global defaults for the per-site content-settings panel: whether
+/// images load and whether a script-initiated `window.open` succeeds,
+/// absent a per-site override. A site the user has customized overrides
+/// these through `crynn-engine`'s `SitePrefStore`, the same seam
+/// [`PrivacyConfig`] already defers to for `resist_fingerprinting`.
+///
+/// `spellcheck_enabled` rides along here rather than getting its own
+/// section: like `images_enabled`/`popups_enabled` it's a content-editing
+/// default (`crynn-engine`'s `PREF_SPELLCHECK_ENABLED`), just one with no
+/// per-site override, since misspellings aren't a per-origin concern the
+/// way images or popups are.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ContentConfig {
+    pub images_enabled: bool,
+    pub popups_enabled: bool,
+    pub spellcheck_enabled: bool,
+}
+
+impl Default for ContentConfig {
+    fn default() -> Self {
+        Self {
+            images_enabled: true,
+            popups_enabled: false,
+            spellcheck_enabled: true,
+        }
+    }
+}
+
+/// Policy knobs for `crynn-shell`'s system-conditions broadcaster: how
+/// aggressively it batches cache writes while on battery, on a metered
+/// connection, or under memory pressure, versus its unconstrained
+/// default.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PowerConfig {
+    pub default_cache_batch_size: u32,
+    pub constrained_cache_batch_size: u32,
+}
+
+impl Default for PowerConfig {
+    fn default() -> Self {
+        Self {
+            default_cache_batch_size: 1,
+            constrained_cache_batch_size: 32,
+        }
+    }
+}
+
+/// Self-update defaults. `channel` is a plain string, the same way
+/// [`TrackingConfig::strictness`] is, so this crate doesn't need to
+/// depend on `crynn-update` just to hold a setting; callers parse it
+/// with `Channel::from_str`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UpdatesConfig {
+    pub channel: String,
+    pub auto_check: bool,
+    pub wifi_only: bool,
+}
+
+impl Default for UpdatesConfig {
+    fn default() -> Self {
+        Self {
+            channel: "stable".to_string(),
+            auto_check: true,
+            wifi_only: true,
+        }
+    }
+}
+
+/// Automatic page-translation defaults. `endpoint` is a self-hosted
+/// LibreTranslate-compatible server's `/translate` URL; `crynn-network`'s
+/// `TranslationClient` is what actually builds requests against it, the
+/// same split [`SearchConfig::suggest_url`] has from `SuggestionsClient`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TranslationConfig {
+    pub enabled: bool,
+    pub endpoint: String,
+    pub target_language: String,
+}
+
+impl Default for TranslationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            endpoint: "https://translate.crynn.example/translate".to_string(),
+            target_language: "en".to_string(),
+        }
+    }
+}
+
+/// OS-level global shortcuts, keyed by the same action id
+/// [`crate::Config`]'s consumers already use for in-app keybindings and
+/// the command palette — `bindings` maps an action id to the chord
+/// string (e.g. `"Ctrl+Alt+V"`) that should trigger it even when no
+/// Crynn window has focus. `crynn-shell`'s `global_hotkeys` module
+/// detects two action ids sharing a chord before registering anything
+/// with the platform.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GlobalHotkeysConfig {
+    pub bindings: std::collections::HashMap<String, String>,
+}
+
+impl Default for GlobalHotkeysConfig {
+    fn default() -> Self {
+        Self {
+            bindings: [
+                ("view.about-vpn".to_string(), "Ctrl+Alt+V".to_string()),
+                ("window.new-private".to_string(), "Ctrl+Shift+N".to_string()),
+                ("view.toggle-command-palette".to_string(), "Ctrl+Shift+Space".to_string()),
+            ]
+            .into_iter()
+            .collect(),
+        }
+    }
+}
+
+/// Mouse gestures, keyed by the same action id [`GlobalHotkeysConfig`]
+/// uses — `bindings` maps an action id to a gesture spec string
+/// `crynn-shell`'s `gestures` module parses: `"drag:left,down"` for a
+/// right-button drag through that sequence of strokes, or
+/// `"rocker:left,right"` for a rocker gesture (hold the first button,
+/// click the second).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GesturesConfig {
+    pub bindings: std::collections::HashMap<String, String>,
+}
+
+impl Default for GesturesConfig {
+    fn default() -> Self {
+        Self {
+            bindings: [
+                ("navigation.back".to_string(), "drag:left".to_string()),
+                ("navigation.new-tab".to_string(), "drag:down".to_string()),
+                ("navigation.forward".to_string(), "rocker:left,right".to_string()),
+            ]
+            .into_iter()
+            .collect(),
+        }
+    }
+}
+
+/// The shell chrome's color scheme, chosen during first-run onboarding
+/// or later from settings. `System` follows the OS's own light/dark
+/// setting rather than pinning one outright.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Theme {
+    Light,
+    Dark,
+    #[default]
+    System,
+}
+
+/// First-run wizard state: whether it's been completed, and the choices
+/// it recorded along the way, so restarting Crynn doesn't ask again and
+/// both frontends read the same answers. `crynn-shell`'s `onboarding`
+/// module drives the wizard itself; this section is only where it lands.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OnboardingConfig {
+    pub completed: bool,
+    pub default_search_engine: String,
+    pub theme: Theme,
+}
+
+impl Default for OnboardingConfig {
+    fn default() -> Self {
+        Self { completed: false, default_search_engine: "crynn".to_string(), theme: Theme::default() }
+    }
+}
@@ -0,0 +1,22 @@
+/// A single stored cookie. Expiry, `Secure`/`HttpOnly` flags, and the
+/// rest of RFC 6265 aren't modeled yet — this is the jar a future real
+/// cookie-setting path (there's no network transport to drive one today)
+/// has to fill in, keyed the same way it will be.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cookie {
+    pub domain: String,
+    pub name: String,
+    pub value: String,
+}
+
+/// Whether a cookie access happened in a first-party context (the page's
+/// own site) or a third-party one (an embedded site setting or reading a
+/// cookie scoped to a domain other than the top-level page's). Callers
+/// decide which applies by comparing hosts the same way
+/// `crynn-tracking-protection` classifies requests — this crate just
+/// records the answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CookieParty {
+    First,
+    Third,
+}
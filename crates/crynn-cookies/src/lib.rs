@@ -0,0 +1,25 @@
+//! Cookie jar plus a lightweight access audit log: every
+//! [`CookieManager::set`]/[`CookieManager::get`] records which site set or
+//! read a cookie and whether that happened in a first- or third-party
+//! context, the same first/third-party distinction
+//! `crynn-tracking-protection`'s classifier draws for trackers. The audit
+//! log is what [`CookieManager::cookies_for_site`] replays to answer "what
+//! does this site's jar look like and how has it been used" for the
+//! shell's per-site cookie panel.
+//!
+//! Deciding *whether* a site is allowed to set cookies at all is a
+//! permission decision, not a cookie-jar one — the panel's block control
+//! is `crynn-permissions`'s `PermissionKind::Cookies`. [`CookiePolicy`] is
+//! a coarser, global decision about third-party cookies specifically:
+//! [`CookieManager::request_set`]/[`CookieManager::request_get`] are the
+//! enforced path a real request would go through, working out first- vs
+//! third-party from the top-level site and consulting the policy (or a
+//! per-site exception) before touching the jar.
+
+mod cookie;
+mod manager;
+mod policy;
+
+pub use cookie::{Cookie, CookieParty};
+pub use manager::{CookieManager, CookieSummary};
+pub use policy::CookiePolicy;
@@ -0,0 +1,380 @@
+use std::collections::HashMap;
+
+use crate::cookie::{Cookie, CookieParty};
+use crate::policy::CookiePolicy;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AccessKind {
+    Set,
+    Read,
+}
+
+/// One audit entry: `domain` set or read cookie `name` in a first- or
+/// third-party context at time `at`. `at` is supplied by the caller
+/// rather than taken from the clock, the same way the rest of this
+/// workspace threads timestamps through instead of reaching for
+/// `Instant::now()` internally.
+#[derive(Debug, Clone)]
+struct AuditEntry {
+    domain: String,
+    name: String,
+    party: CookieParty,
+    kind: AccessKind,
+    at: u64,
+}
+
+/// Per-cookie counts and last access, as shown in a site's row of the
+/// cookie panel.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CookieSummary {
+    pub name: String,
+    pub party: CookieParty,
+    pub set_count: u32,
+    pub read_count: u32,
+    pub last_access: u64,
+}
+
+/// Owns the cookie jar and a lightweight access log. The log is the
+/// source of truth for [`CookieManager::cookies_for_site`]'s counts and
+/// last-access data; the jar itself only tracks current values, so
+/// deleting a cookie doesn't erase the history of how it was used.
+///
+/// [`Self::request_set`]/[`Self::request_get`] are the enforced request
+/// path: they work out first- vs third-party from the top-level site
+/// making the request and consult [`Self::policy`] (or a per-site
+/// exception) before touching the jar, tallying anything blocked in
+/// [`Self::blocked_count`]. [`Self::set`]/[`Self::get`] stay unenforced —
+/// callers that already know the party and have made their own policy
+/// decision (tests, the cookie panel's own edits) use those directly.
+#[derive(Debug, Default)]
+pub struct CookieManager {
+    cookies: HashMap<(String, String), Cookie>,
+    audit: Vec<AuditEntry>,
+    policy: CookiePolicy,
+    exceptions: HashMap<String, CookiePolicy>,
+    blocked_counts: HashMap<String, u32>,
+}
+
+impl CookieManager {
+    pub fn new(policy: CookiePolicy) -> Self {
+        Self { policy, ..Self::default() }
+    }
+
+    pub fn policy(&self) -> CookiePolicy {
+        self.policy
+    }
+
+    pub fn set_policy(&mut self, policy: CookiePolicy) {
+        self.policy = policy;
+    }
+
+    /// Overrides the policy for `top_level_site` only, e.g. a user
+    /// allowing third-party cookies on a site that breaks without them.
+    pub fn set_exception(&mut self, top_level_site: impl Into<String>, policy: CookiePolicy) {
+        self.exceptions.insert(top_level_site.into(), policy);
+    }
+
+    pub fn clear_exception(&mut self, top_level_site: &str) {
+        self.exceptions.remove(top_level_site);
+    }
+
+    fn effective_policy(&self, top_level_site: &str) -> CookiePolicy {
+        self.exceptions.get(top_level_site).copied().unwrap_or(self.policy)
+    }
+
+    /// Enforced request path for setting a cookie: works out whether
+    /// `domain` is first- or third-party relative to `top_level_site`,
+    /// and only stores it if the effective policy allows that party.
+    /// Returns whether it was allowed; a blocked request bumps
+    /// `top_level_site`'s [`Self::blocked_count`] instead of storing
+    /// anything.
+    pub fn request_set(
+        &mut self,
+        top_level_site: &str,
+        domain: impl Into<String>,
+        name: impl Into<String>,
+        value: impl Into<String>,
+        at: u64,
+    ) -> bool {
+        let domain = domain.into();
+        let party = party_of(top_level_site, &domain);
+        if !self.effective_policy(top_level_site).allows(party) {
+            *self.blocked_counts.entry(top_level_site.to_string()).or_insert(0) += 1;
+            return false;
+        }
+        self.set(domain, name, value, party, at);
+        true
+    }
+
+    /// Enforced request path for reading a cookie back, e.g. before
+    /// attaching it to an outgoing request. Mirrors [`Self::request_set`]:
+    /// blocked reads are tallied rather than returned.
+    pub fn request_get(&mut self, top_level_site: &str, domain: &str, name: &str, at: u64) -> Option<&str> {
+        let party = party_of(top_level_site, domain);
+        if !self.effective_policy(top_level_site).allows(party) {
+            *self.blocked_counts.entry(top_level_site.to_string()).or_insert(0) += 1;
+            return None;
+        }
+        self.get(domain, name, party, at)
+    }
+
+    /// How many `request_set`/`request_get` calls `top_level_site` has
+    /// had blocked since the last [`Self::reset_page`].
+    pub fn blocked_count(&self, top_level_site: &str) -> u32 {
+        self.blocked_counts.get(top_level_site).copied().unwrap_or(0)
+    }
+
+    /// Clears `top_level_site`'s blocked count, e.g. when it navigates to
+    /// a new page, so the count reported there reflects only the current
+    /// page load.
+    pub fn reset_page(&mut self, top_level_site: &str) {
+        self.blocked_counts.remove(top_level_site);
+    }
+
+    /// Stores a cookie and records the set in the audit log.
+    pub fn set(
+        &mut self,
+        domain: impl Into<String>,
+        name: impl Into<String>,
+        value: impl Into<String>,
+        party: CookieParty,
+        at: u64,
+    ) {
+        let domain = domain.into();
+        let name = name.into();
+        self.audit.push(AuditEntry {
+            domain: domain.clone(),
+            name: name.clone(),
+            party,
+            kind: AccessKind::Set,
+            at,
+        });
+        self.cookies.insert((domain.clone(), name.clone()), Cookie { domain, name, value: value.into() });
+    }
+
+    /// Reads back a stored cookie's value, recording the read in the
+    /// audit log. Returns `None` without recording anything if `domain`
+    /// has no cookie named `name`.
+    pub fn get(&mut self, domain: &str, name: &str, party: CookieParty, at: u64) -> Option<&str> {
+        let cookie = self.cookies.get(&(domain.to_string(), name.to_string()))?;
+        self.audit.push(AuditEntry {
+            domain: domain.to_string(),
+            name: name.to_string(),
+            party,
+            kind: AccessKind::Read,
+            at,
+        });
+        Some(cookie.value.as_str())
+    }
+
+    /// Deletes a single cookie, e.g. the panel's per-row delete button.
+    /// Leaves the audit log intact — past access history stays visible
+    /// even after the cookie itself is gone.
+    pub fn delete(&mut self, domain: &str, name: &str) {
+        self.cookies.remove(&(domain.to_string(), name.to_string()));
+    }
+
+    /// Deletes every cookie for `domain`, e.g. the panel's "clear all"
+    /// button.
+    pub fn clear_site(&mut self, domain: &str) {
+        self.cookies.retain(|(d, _), _| d != domain);
+    }
+
+    /// Every cookie name `domain` has been seen setting or reading, with
+    /// access counts and the most recent access time, for the shell's
+    /// per-site cookie panel. Sorted by name for a stable display order.
+    pub fn cookies_for_site(&self, domain: &str) -> Vec<CookieSummary> {
+        let mut summaries: HashMap<&str, CookieSummary> = HashMap::new();
+        for entry in self.audit.iter().filter(|e| e.domain == domain) {
+            let summary = summaries.entry(entry.name.as_str()).or_insert_with(|| CookieSummary {
+                name: entry.name.clone(),
+                party: entry.party,
+                set_count: 0,
+                read_count: 0,
+                last_access: 0,
+            });
+            match entry.kind {
+                AccessKind::Set => summary.set_count += 1,
+                AccessKind::Read => summary.read_count += 1,
+            }
+            if entry.at >= summary.last_access {
+                summary.last_access = entry.at;
+                summary.party = entry.party;
+            }
+        }
+        let mut out: Vec<CookieSummary> = summaries.into_values().collect();
+        out.sort_by(|a, b| a.name.cmp(&b.name));
+        out
+    }
+
+    /// How many cookies `domain` currently has set, for a quick count
+    /// (e.g. the page info popover) without building the full summary
+    /// list.
+    pub fn count_for_site(&self, domain: &str) -> usize {
+        self.cookies.keys().filter(|(d, _)| d == domain).count()
+    }
+
+    /// Every distinct domain with at least one cookie currently set,
+    /// sorted for a stable order — for the retention scheduler's
+    /// "cookies from sites not visited in N days" sweep, which needs to
+    /// walk every site rather than look one up.
+    pub fn sites(&self) -> Vec<&str> {
+        let mut domains: Vec<&str> = self.cookies.keys().map(|(domain, _)| domain.as_str()).collect();
+        domains.sort_unstable();
+        domains.dedup();
+        domains
+    }
+}
+
+/// Whether `domain` is first-party relative to `top_level_site`: the same
+/// or either one a subdomain of the other, the host-suffix match
+/// `crynn-tracking-protection`'s classifier already uses for its own
+/// domain/subdomain matching.
+fn party_of(top_level_site: &str, domain: &str) -> CookieParty {
+    let domain = domain.trim_start_matches('.');
+    let is_first_party = domain == top_level_site
+        || top_level_site.ends_with(&format!(".{domain}"))
+        || domain.ends_with(&format!(".{top_level_site}"));
+    if is_first_party {
+        CookieParty::First
+    } else {
+        CookieParty::Third
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn set_then_get_returns_the_stored_value() {
+        let mut manager = CookieManager::default();
+        manager.set("example.com", "session", "abc123", CookieParty::First, 1);
+        assert_eq!(manager.get("example.com", "session", CookieParty::First, 2), Some("abc123"));
+    }
+
+    #[test]
+    fn get_on_an_unset_cookie_returns_none_and_does_not_record_an_access() {
+        let mut manager = CookieManager::default();
+        assert_eq!(manager.get("example.com", "session", CookieParty::First, 1), None);
+        assert!(manager.cookies_for_site("example.com").is_empty());
+    }
+
+    #[test]
+    fn cookies_for_site_counts_sets_and_reads_separately() {
+        let mut manager = CookieManager::default();
+        manager.set("example.com", "session", "abc123", CookieParty::First, 1);
+        manager.get("example.com", "session", CookieParty::First, 2);
+        manager.get("example.com", "session", CookieParty::First, 3);
+
+        let summaries = manager.cookies_for_site("example.com");
+        assert_eq!(summaries.len(), 1);
+        assert_eq!(summaries[0].set_count, 1);
+        assert_eq!(summaries[0].read_count, 2);
+        assert_eq!(summaries[0].last_access, 3);
+    }
+
+    #[test]
+    fn cookies_for_site_is_scoped_per_domain() {
+        let mut manager = CookieManager::default();
+        manager.set("example.com", "session", "abc", CookieParty::First, 1);
+        manager.set("ads.example.org", "tracker", "xyz", CookieParty::Third, 1);
+        assert_eq!(manager.cookies_for_site("example.com").len(), 1);
+        assert_eq!(manager.cookies_for_site("ads.example.org").len(), 1);
+        assert!(manager.cookies_for_site("other.com").is_empty());
+    }
+
+    #[test]
+    fn delete_removes_the_cookie_but_keeps_its_audit_history() {
+        let mut manager = CookieManager::default();
+        manager.set("example.com", "session", "abc", CookieParty::First, 1);
+        manager.delete("example.com", "session");
+        assert_eq!(manager.count_for_site("example.com"), 0);
+        assert_eq!(manager.cookies_for_site("example.com").len(), 1);
+    }
+
+    #[test]
+    fn sites_lists_each_distinct_domain_with_cookies_once() {
+        let mut manager = CookieManager::default();
+        manager.set("example.com", "session", "abc", CookieParty::First, 1);
+        manager.set("example.com", "theme", "dark", CookieParty::First, 1);
+        manager.set("other.com", "session", "def", CookieParty::First, 1);
+        assert_eq!(manager.sites(), vec!["example.com", "other.com"]);
+    }
+
+    #[test]
+    fn clear_site_removes_every_cookie_for_that_domain_only() {
+        let mut manager = CookieManager::default();
+        manager.set("example.com", "session", "abc", CookieParty::First, 1);
+        manager.set("example.com", "theme", "dark", CookieParty::First, 1);
+        manager.set("other.com", "session", "def", CookieParty::First, 1);
+        manager.clear_site("example.com");
+        assert_eq!(manager.count_for_site("example.com"), 0);
+        assert_eq!(manager.count_for_site("other.com"), 1);
+    }
+
+    #[test]
+    fn most_recent_access_party_wins_when_it_changes() {
+        let mut manager = CookieManager::default();
+        manager.set("example.com", "session", "abc", CookieParty::Third, 1);
+        manager.get("example.com", "session", CookieParty::First, 2);
+        let summaries = manager.cookies_for_site("example.com");
+        assert_eq!(summaries[0].party, CookieParty::First);
+    }
+
+    #[test]
+    fn party_of_treats_subdomains_as_first_party() {
+        assert_eq!(party_of("example.com", "example.com"), CookieParty::First);
+        assert_eq!(party_of("example.com", "login.example.com"), CookieParty::First);
+        assert_eq!(party_of("app.example.com", "example.com"), CookieParty::First);
+        assert_eq!(party_of("example.com", "ads.example.org"), CookieParty::Third);
+    }
+
+    #[test]
+    fn default_policy_blocks_third_party_requests() {
+        let mut manager = CookieManager::default();
+        assert!(!manager.request_set("example.com", "ads.example.org", "tracker", "xyz", 1));
+        assert_eq!(manager.blocked_count("example.com"), 1);
+        assert_eq!(manager.count_for_site("ads.example.org"), 0);
+    }
+
+    #[test]
+    fn default_policy_allows_first_party_requests() {
+        let mut manager = CookieManager::default();
+        assert!(manager.request_set("example.com", "example.com", "session", "abc", 1));
+        assert_eq!(manager.blocked_count("example.com"), 0);
+        assert_eq!(manager.count_for_site("example.com"), 1);
+    }
+
+    #[test]
+    fn allow_all_policy_permits_third_party_requests() {
+        let mut manager = CookieManager::new(CookiePolicy::AllowAll);
+        assert!(manager.request_set("example.com", "ads.example.org", "tracker", "xyz", 1));
+    }
+
+    #[test]
+    fn per_site_exception_overrides_the_global_policy() {
+        let mut manager = CookieManager::default();
+        manager.set_exception("example.com", CookiePolicy::AllowAll);
+        assert!(manager.request_set("example.com", "ads.example.org", "tracker", "xyz", 1));
+
+        manager.clear_exception("example.com");
+        assert!(!manager.request_set("example.com", "ads.example.org", "tracker", "xyz", 2));
+    }
+
+    #[test]
+    fn reset_page_clears_the_blocked_count() {
+        let mut manager = CookieManager::default();
+        manager.request_set("example.com", "ads.example.org", "tracker", "xyz", 1);
+        manager.reset_page("example.com");
+        assert_eq!(manager.blocked_count("example.com"), 0);
+    }
+
+    #[test]
+    fn blocked_request_get_does_not_leak_the_cookie_value() {
+        let mut manager = CookieManager::default();
+        manager.set("ads.example.org", "tracker", "xyz", CookieParty::Third, 1);
+        assert_eq!(manager.request_get("example.com", "ads.example.org", "tracker", 2), None);
+        assert_eq!(manager.blocked_count("example.com"), 1);
+    }
+}
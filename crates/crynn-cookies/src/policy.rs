@@ -0,0 +1,71 @@
+use std::str::FromStr;
+
+use crate::cookie::CookieParty;
+
+/// How the cookie jar's request path treats cookies by party. Mirrors the
+/// allow/block split `crynn-tracking-protection::StrictnessLevel` exposes
+/// for trackers, but cookies only have the one axis (party) to key off
+/// of rather than a set of categories.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CookiePolicy {
+    AllowAll,
+    #[default]
+    BlockThirdParty,
+    BlockAll,
+}
+
+impl CookiePolicy {
+    /// Whether a cookie access of party `party` is allowed under this
+    /// policy, ignoring any per-site exception — [`crate::CookieManager`]
+    /// checks those separately.
+    pub fn allows(&self, party: CookieParty) -> bool {
+        match self {
+            Self::AllowAll => true,
+            Self::BlockThirdParty => party == CookieParty::First,
+            Self::BlockAll => false,
+        }
+    }
+}
+
+impl FromStr for CookiePolicy {
+    type Err = String;
+
+    /// Parses the `cookies.policy` config value.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "allow_all" => Ok(Self::AllowAll),
+            "block_third_party" => Ok(Self::BlockThirdParty),
+            "block_all" => Ok(Self::BlockAll),
+            other => Err(format!("unknown cookie policy: {other}")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn allow_all_allows_both_parties() {
+        assert!(CookiePolicy::AllowAll.allows(CookieParty::First));
+        assert!(CookiePolicy::AllowAll.allows(CookieParty::Third));
+    }
+
+    #[test]
+    fn block_third_party_only_blocks_third_party() {
+        assert!(CookiePolicy::BlockThirdParty.allows(CookieParty::First));
+        assert!(!CookiePolicy::BlockThirdParty.allows(CookieParty::Third));
+    }
+
+    #[test]
+    fn block_all_blocks_both_parties() {
+        assert!(!CookiePolicy::BlockAll.allows(CookieParty::First));
+        assert!(!CookiePolicy::BlockAll.allows(CookieParty::Third));
+    }
+
+    #[test]
+    fn from_str_rejects_unknown_policies() {
+        assert_eq!(CookiePolicy::from_str("allow_all"), Ok(CookiePolicy::AllowAll));
+        assert!(CookiePolicy::from_str("off").is_err());
+    }
+}
@@ -0,0 +1,90 @@
+use std::panic::PanicHookInfo;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::report::CrashReport;
+
+/// Whatever the caller can report about running subsystems and recent
+/// user actions at the moment of a crash. Implementors read their own
+/// live state rather than this trait snapshotting anything ahead of
+/// time, since a panic can happen long after the provider was handed to
+/// [`install_panic_hook`].
+pub trait CrashContextProvider: Send + Sync {
+    /// One `(subsystem, state)` pair per thing worth knowing, e.g.
+    /// `("network", "3 open connections")`.
+    fn subsystem_states(&self) -> Vec<(String, String)>;
+
+    /// Recent user-driven actions, oldest first, for "what was the user
+    /// doing right before this" context a bare backtrace can't give.
+    fn last_actions(&self) -> Vec<String>;
+}
+
+/// Installs a panic hook that turns every panic in this process into a
+/// [`CrashReport`] and hands it to `on_report`, then falls through to
+/// whatever hook was already installed so default panic output to
+/// stderr still happens. Meant to be called once, as early as possible,
+/// in both the shell process and any helper process that can panic on
+/// its own thread.
+pub fn install_panic_hook<P>(component: &'static str, context: Arc<P>, on_report: impl Fn(CrashReport) + Send + Sync + 'static)
+where
+    P: CrashContextProvider + 'static,
+{
+    let previous = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info: &PanicHookInfo<'_>| {
+        let report = CrashReport {
+            id: 0,
+            occurred_at: now_unix(),
+            component: component.to_string(),
+            message: info.to_string(),
+            backtrace: std::backtrace::Backtrace::force_capture().to_string(),
+            subsystem_states: context.subsystem_states(),
+            last_actions: context.last_actions(),
+        };
+        on_report(report);
+        previous(info);
+    }));
+}
+
+fn now_unix() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Mutex, OnceLock};
+
+    struct FixedContext;
+
+    impl CrashContextProvider for FixedContext {
+        fn subsystem_states(&self) -> Vec<(String, String)> {
+            vec![("engine".to_string(), "3 tabs open".to_string())]
+        }
+
+        fn last_actions(&self) -> Vec<String> {
+            vec!["clicked reload".to_string()]
+        }
+    }
+
+    fn reports() -> &'static Mutex<Vec<CrashReport>> {
+        static REPORTS: OnceLock<Mutex<Vec<CrashReport>>> = OnceLock::new();
+        REPORTS.get_or_init(|| Mutex::new(Vec::new()))
+    }
+
+    #[test]
+    fn panic_hook_captures_context_and_message() {
+        install_panic_hook("test-component", Arc::new(FixedContext), |report| {
+            reports().lock().expect("reports mutex poisoned").push(report);
+        });
+
+        let result = std::panic::catch_unwind(|| panic!("boom"));
+        assert!(result.is_err());
+
+        let captured = reports().lock().expect("reports mutex poisoned");
+        let report = captured.last().expect("hook should have recorded a report");
+        assert_eq!(report.component, "test-component");
+        assert!(report.message.contains("boom"));
+        assert_eq!(report.subsystem_states, vec![("engine".to_string(), "3 tabs open".to_string())]);
+        assert_eq!(report.last_actions, vec!["clicked reload".to_string()]);
+    }
+}
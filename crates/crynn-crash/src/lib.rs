@@ -0,0 +1,16 @@
+//! Crash reporting with nowhere to send a crash to but this device.
+//!
+//! [`install_panic_hook`] turns a Rust panic into a structured
+//! [`CrashReport`] and hands it to the caller's own persistence; this
+//! crate doesn't decide where reports live or how they leave the
+//! machine, because by design there is no "leave the machine" path —
+//! [`CrashStore::export`] is the only way a report goes anywhere, and
+//! that's always a user-initiated file write, never a network call.
+
+mod hook;
+mod report;
+mod store;
+
+pub use hook::{install_panic_hook, CrashContextProvider};
+pub use report::CrashReport;
+pub use store::CrashStore;
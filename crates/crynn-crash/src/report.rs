@@ -0,0 +1,17 @@
+use serde::{Deserialize, Serialize};
+
+/// One captured crash: what the panic hook saw, plus whatever subsystem
+/// context and recent-action history the caller had on hand to attach.
+/// `id` is assigned by [`crate::CrashStore::record`], not by whoever
+/// builds the report, so it stays unique across a store's whole history
+/// rather than colliding on two crashes in the same second.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CrashReport {
+    pub id: u64,
+    pub occurred_at: u64,
+    pub component: String,
+    pub message: String,
+    pub backtrace: String,
+    pub subsystem_states: Vec<(String, String)>,
+    pub last_actions: Vec<String>,
+}
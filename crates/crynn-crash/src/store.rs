@@ -0,0 +1,126 @@
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use crate::report::CrashReport;
+
+/// Local log of crash reports, persisted as a single JSON file. Nothing
+/// in this crate uploads a report anywhere; [`CrashStore::export`] is
+/// the only way one leaves this file, and it only runs when the user
+/// clicks the `about:crashes` export button.
+#[derive(Debug, Default)]
+pub struct CrashStore {
+    reports: Vec<CrashReport>,
+    path: Option<PathBuf>,
+}
+
+impl CrashStore {
+    /// Loads reports from `path` if it exists, otherwise starts empty.
+    /// The store remembers `path` so later [`CrashStore::save`] calls
+    /// don't need to repeat it.
+    pub fn load(path: impl Into<PathBuf>) -> io::Result<Self> {
+        let path = path.into();
+        let reports = match fs::read_to_string(&path) {
+            Ok(contents) => serde_json::from_str(&contents)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => return Err(e),
+        };
+        Ok(Self { reports, path: Some(path) })
+    }
+
+    /// Assigns `report` the next id and appends it.
+    pub fn record(&mut self, mut report: CrashReport) {
+        report.id = self.reports.last().map_or(1, |last| last.id + 1);
+        self.reports.push(report);
+    }
+
+    pub fn reports(&self) -> &[CrashReport] {
+        &self.reports
+    }
+
+    pub fn len(&self) -> usize {
+        self.reports.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.reports.is_empty()
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let contents = serde_json::to_string_pretty(&self.reports)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        fs::write(path, contents)
+    }
+
+    /// Writes every recorded report to `path` as pretty JSON, for the
+    /// user to attach to a bug report or send wherever they choose.
+    pub fn export(&self, path: impl AsRef<std::path::Path>) -> io::Result<()> {
+        let contents = serde_json::to_string_pretty(&self.reports)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        fs::write(path, contents)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(component: &str) -> CrashReport {
+        CrashReport {
+            id: 0,
+            occurred_at: 1,
+            component: component.to_string(),
+            message: "boom".to_string(),
+            backtrace: "at foo.rs:1".to_string(),
+            subsystem_states: Vec::new(),
+            last_actions: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn record_assigns_increasing_ids() {
+        let mut store = CrashStore::default();
+        store.record(sample("engine"));
+        store.record(sample("network"));
+        let ids: Vec<u64> = store.reports().iter().map(|r| r.id).collect();
+        assert_eq!(ids, vec![1, 2]);
+    }
+
+    #[test]
+    fn save_and_load_round_trip() {
+        let dir = std::env::temp_dir().join(format!("crynn-crash-test-{}", std::process::id()));
+        let path = dir.join("crashes.json");
+        let mut store = CrashStore::load(&path).unwrap();
+        store.record(sample("engine"));
+        store.save().unwrap();
+
+        let reloaded = CrashStore::load(&path).unwrap();
+        assert_eq!(reloaded.len(), 1);
+        assert_eq!(reloaded.reports()[0].component, "engine");
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn export_writes_every_report_as_json() {
+        let dir = std::env::temp_dir().join(format!("crynn-crash-export-test-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let export_path = dir.join("export.json");
+
+        let mut store = CrashStore::default();
+        store.record(sample("engine"));
+        store.export(&export_path).unwrap();
+
+        let contents = fs::read_to_string(&export_path).unwrap();
+        assert!(contents.contains("\"engine\""));
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,241 @@
+use std::fs::{self, File};
+use std::io::{self, Read, Write};
+use std::path::Path;
+
+use crate::message::EmailBody;
+
+/// Output format for [`export_folder`]. Only [`MailExportFormat::Mbox`]
+/// round-trips through [`import_mbox`] — [`MailExportFormat::Eml`] is for
+/// handing messages to another client that reads one file per message,
+/// not for reading back into this one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MailExportFormat {
+    Mbox,
+    Eml,
+}
+
+/// Writes every message in `messages` to `path` in `format`: a single
+/// concatenated mbox file for [`MailExportFormat::Mbox`], or `path` as a
+/// directory with one `.eml` file per message for [`MailExportFormat::Eml`].
+///
+/// Carries over headers and the plain-text body; attachments and the
+/// HTML part aren't re-encoded into the exported bytes — this crate has
+/// no MIME multipart writer yet, the same gap [`crate::AuthPipeline`]-
+/// style contracts elsewhere in this workspace leave for a later
+/// implementation rather than papering over with something half right.
+pub fn export_folder(messages: &[EmailBody], format: MailExportFormat, path: &Path) -> io::Result<()> {
+    match format {
+        MailExportFormat::Mbox => {
+            let mut file = File::create(path)?;
+            for message in messages {
+                writeln!(file, "From {} {}", message.from, message.date)?;
+                write!(file, "{}", serialize_headers(message))?;
+                writeln!(file)?;
+                for line in message.body_text.lines() {
+                    writeln!(file, "{}", escape_mbox_line(line))?;
+                }
+                writeln!(file)?;
+            }
+            Ok(())
+        }
+        MailExportFormat::Eml => {
+            fs::create_dir_all(path)?;
+            for message in messages {
+                let mut file = File::create(path.join(eml_filename(message)))?;
+                write!(file, "{}", serialize_headers(message))?;
+                writeln!(file)?;
+                write!(file, "{}", message.body_text)?;
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Re-imports messages previously written by [`export_folder`] with
+/// [`MailExportFormat::Mbox`]. Since the export never carries attachments
+/// or the HTML part, neither does the round trip — every returned
+/// [`EmailBody::attachments`] is empty and [`EmailBody::html_body`] is
+/// `None`.
+pub fn import_mbox(path: &Path) -> io::Result<Vec<EmailBody>> {
+    let mut contents = String::new();
+    File::open(path)?.read_to_string(&mut contents)?;
+
+    let mut messages = Vec::new();
+    let mut block: Vec<&str> = Vec::new();
+    for line in contents.lines() {
+        if line.starts_with("From ") && !block.is_empty() {
+            messages.push(parse_block(&block));
+            block.clear();
+        }
+        block.push(line);
+    }
+    if !block.is_empty() {
+        messages.push(parse_block(&block));
+    }
+    Ok(messages)
+}
+
+fn serialize_headers(message: &EmailBody) -> String {
+    format!(
+        "Message-Id: {}\nFrom: {}\nTo: {}\nCc: {}\nSubject: {}\nDate: {}\nReferences: {}\n",
+        message.message_id,
+        message.from,
+        message.to.join(", "),
+        message.cc.join(", "),
+        message.subject,
+        message.date,
+        message.references.join(" "),
+    )
+}
+
+fn eml_filename(message: &EmailBody) -> String {
+    let sanitized: String = message.message_id.chars().filter(|c| c.is_ascii_alphanumeric()).collect();
+    format!("{sanitized}.eml")
+}
+
+/// mbox-style escaping: a body line that itself starts with `"From "`
+/// gets an extra `>` prefixed, so [`import_mbox`] can tell it apart from
+/// a real message boundary.
+fn escape_mbox_line(line: &str) -> String {
+    if line.starts_with("From ") {
+        format!(">{line}")
+    } else {
+        line.to_string()
+    }
+}
+
+fn unescape_mbox_line(line: &str) -> &str {
+    line.strip_prefix('>').filter(|rest| rest.starts_with("From ")).unwrap_or(line)
+}
+
+fn parse_block(block: &[&str]) -> EmailBody {
+    let mut message_id = String::new();
+    let mut from = String::new();
+    let mut to = Vec::new();
+    let mut cc = Vec::new();
+    let mut subject = String::new();
+    let mut date = String::new();
+    let mut references = Vec::new();
+
+    let mut in_headers = true;
+    let mut body_lines: Vec<&str> = Vec::new();
+    for line in &block[1..] {
+        if in_headers {
+            if line.is_empty() {
+                in_headers = false;
+            } else if let Some(value) = line.strip_prefix("Message-Id: ") {
+                message_id = value.to_string();
+            } else if let Some(value) = line.strip_prefix("From: ") {
+                from = value.to_string();
+            } else if let Some(value) = line.strip_prefix("To: ") {
+                to = split_addresses(value);
+            } else if let Some(value) = line.strip_prefix("Cc: ") {
+                cc = split_addresses(value);
+            } else if let Some(value) = line.strip_prefix("Subject: ") {
+                subject = value.to_string();
+            } else if let Some(value) = line.strip_prefix("Date: ") {
+                date = value.to_string();
+            } else if let Some(value) = line.strip_prefix("References: ") {
+                references = value.split(' ').filter(|s| !s.is_empty()).map(String::from).collect();
+            }
+        } else {
+            body_lines.push(unescape_mbox_line(line));
+        }
+    }
+    while body_lines.last() == Some(&"") {
+        body_lines.pop();
+    }
+
+    EmailBody { message_id, from, to, cc, subject, body_text: body_lines.join("\n"), html_body: None, date, attachments: Vec::new(), references }
+}
+
+fn split_addresses(value: &str) -> Vec<String> {
+    if value.is_empty() {
+        Vec::new()
+    } else {
+        value.split(", ").map(String::from).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(message_id: &str, subject: &str, body_text: &str) -> EmailBody {
+        EmailBody {
+            message_id: message_id.to_string(),
+            from: "alice@example.com".to_string(),
+            to: vec!["bob@example.com".to_string(), "carol@example.com".to_string()],
+            cc: vec!["dave@example.com".to_string()],
+            subject: subject.to_string(),
+            body_text: body_text.to_string(),
+            html_body: Some("<p>ignored on export</p>".to_string()),
+            date: "Mon, 1 Jan 2024 09:00:00 +0000".to_string(),
+            attachments: Vec::new(),
+            references: vec!["<msg-0@example.com>".to_string()],
+        }
+    }
+
+    fn temp_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("crynn-email-backup-test-{}-{name}", std::process::id()))
+    }
+
+    #[test]
+    fn mbox_export_round_trips_through_import() {
+        let path = temp_path("mbox-round-trip");
+        let messages = vec![sample("<msg-1@example.com>", "Lunch?", "Free Thursday?"), sample("<msg-2@example.com>", "Re: Lunch?", "Yes!")];
+
+        export_folder(&messages, MailExportFormat::Mbox, &path).unwrap();
+        let imported = import_mbox(&path).unwrap();
+
+        assert_eq!(imported.len(), 2);
+        assert_eq!(imported[0].message_id, "<msg-1@example.com>");
+        assert_eq!(imported[0].subject, "Lunch?");
+        assert_eq!(imported[0].body_text, "Free Thursday?");
+        assert_eq!(imported[0].to, vec!["bob@example.com".to_string(), "carol@example.com".to_string()]);
+        assert_eq!(imported[1].body_text, "Yes!");
+
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn a_body_line_starting_with_from_is_escaped_and_restored() {
+        let path = temp_path("from-line");
+        let messages = vec![sample("<msg-1@example.com>", "Heads up", "From now on let's meet on Fridays.")];
+
+        export_folder(&messages, MailExportFormat::Mbox, &path).unwrap();
+        let contents = fs::read_to_string(&path).unwrap();
+        assert!(contents.contains(">From now on let's meet on Fridays."));
+
+        let imported = import_mbox(&path).unwrap();
+        assert_eq!(imported[0].body_text, "From now on let's meet on Fridays.");
+
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn mbox_export_never_carries_over_attachments_or_html() {
+        let path = temp_path("no-attachments");
+        export_folder(&[sample("<msg-1@example.com>", "Lunch?", "Free Thursday?")], MailExportFormat::Mbox, &path).unwrap();
+
+        let imported = import_mbox(&path).unwrap();
+        assert!(imported[0].attachments.is_empty());
+        assert!(imported[0].html_body.is_none());
+
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn eml_export_writes_one_file_per_message() {
+        let dir = temp_path("eml-dir");
+        let messages = vec![sample("<msg-1@example.com>", "Lunch?", "Free Thursday?"), sample("<msg-2@example.com>", "Re: Lunch?", "Yes!")];
+
+        export_folder(&messages, MailExportFormat::Eml, &dir).unwrap();
+
+        let contents = fs::read_to_string(dir.join("msg1examplecom.eml")).unwrap();
+        assert!(contents.contains("Subject: Lunch?"));
+        assert!(contents.contains("Free Thursday?"));
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,110 @@
+use std::collections::HashMap;
+
+/// A naive-Bayes spam classifier trained on cached message headers and
+/// bodies. Laplace-smoothed word frequencies rather than anything more
+/// sophisticated — good enough to flag the obvious cases locally,
+/// without needing a model the rest of this crate would have to ship or
+/// download.
+#[derive(Debug, Default)]
+pub struct NaiveBayesClassifier {
+    spam_word_counts: HashMap<String, u32>,
+    ham_word_counts: HashMap<String, u32>,
+    spam_messages: u32,
+    ham_messages: u32,
+}
+
+impl NaiveBayesClassifier {
+    /// Trains on `text` (a header plus body, or however much the caller
+    /// has cached) as an example of spam or ham.
+    pub fn train(&mut self, text: &str, is_spam: bool) {
+        let counts = if is_spam { &mut self.spam_word_counts } else { &mut self.ham_word_counts };
+        for word in tokenize(text) {
+            *counts.entry(word).or_insert(0) += 1;
+        }
+        if is_spam {
+            self.spam_messages += 1;
+        } else {
+            self.ham_messages += 1;
+        }
+    }
+
+    /// Estimated probability that `text` is spam, in `[0, 1]`. `0.5`
+    /// with nothing trained yet — no evidence either way.
+    pub fn spam_probability(&self, text: &str) -> f64 {
+        if self.spam_messages == 0 || self.ham_messages == 0 {
+            return 0.5;
+        }
+
+        let total_messages = f64::from(self.spam_messages + self.ham_messages);
+        let mut log_spam = (f64::from(self.spam_messages) / total_messages).ln();
+        let mut log_ham = (f64::from(self.ham_messages) / total_messages).ln();
+
+        let spam_vocab = self.spam_word_counts.len() as f64;
+        let ham_vocab = self.ham_word_counts.len() as f64;
+        let spam_total: f64 = self.spam_word_counts.values().map(|&c| f64::from(c)).sum();
+        let ham_total: f64 = self.ham_word_counts.values().map(|&c| f64::from(c)).sum();
+
+        for word in tokenize(text) {
+            let spam_count = f64::from(*self.spam_word_counts.get(&word).unwrap_or(&0));
+            let ham_count = f64::from(*self.ham_word_counts.get(&word).unwrap_or(&0));
+            log_spam += ((spam_count + 1.0) / (spam_total + spam_vocab)).ln();
+            log_ham += ((ham_count + 1.0) / (ham_total + ham_vocab)).ln();
+        }
+
+        // log-sum-exp to turn the two log-likelihoods back into a
+        // normalized probability without overflowing.
+        let max = log_spam.max(log_ham);
+        let spam_likelihood = (log_spam - max).exp();
+        let ham_likelihood = (log_ham - max).exp();
+        spam_likelihood / (spam_likelihood + ham_likelihood)
+    }
+
+    /// Whether `text` clears `threshold`'s spam probability, e.g. `0.9`
+    /// for "only flag the confident cases".
+    pub fn is_spam(&self, text: &str, threshold: f64) -> bool {
+        self.spam_probability(text) >= threshold
+    }
+}
+
+fn tokenize(text: &str) -> Vec<String> {
+    text.to_lowercase().split(|c: char| !c.is_alphanumeric()).filter(|w| !w.is_empty()).map(str::to_string).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn an_untrained_classifier_is_undecided() {
+        let classifier = NaiveBayesClassifier::default();
+        assert_eq!(classifier.spam_probability("anything"), 0.5);
+    }
+
+    #[test]
+    fn learns_to_flag_words_seen_mostly_in_spam() {
+        let mut classifier = NaiveBayesClassifier::default();
+        for _ in 0..20 {
+            classifier.train("free viagra cheap pills buy now", true);
+        }
+        for _ in 0..20 {
+            classifier.train("meeting notes attached from yesterday", false);
+        }
+
+        assert!(classifier.spam_probability("free viagra cheap pills") > 0.9);
+        assert!(classifier.spam_probability("meeting notes attached") < 0.1);
+    }
+
+    #[test]
+    fn is_spam_applies_the_given_threshold() {
+        let mut classifier = NaiveBayesClassifier::default();
+        for _ in 0..20 {
+            classifier.train("free viagra cheap pills buy now", true);
+        }
+        for _ in 0..20 {
+            classifier.train("meeting notes attached from yesterday", false);
+        }
+
+        assert!(classifier.is_spam("free viagra cheap pills", 0.9));
+        assert!(!classifier.is_spam("meeting notes attached", 0.9));
+    }
+}
@@ -0,0 +1,300 @@
+use crynn_network::BodySource;
+use crynn_spellcheck::{Misspelling, SpellChecker};
+
+use crate::identity::Identity;
+use crate::message::{Attachment, EmailBody};
+
+/// Separates a signature from the rest of the body, per the
+/// conventional plain-text mail signature delimiter (a lone `"-- "`
+/// line) that most clients strip on quoting.
+const SIGNATURE_DELIMITER: &str = "\n\n-- \n";
+
+/// A message being drafted: a reply, a forward, or a message started
+/// from scratch (plain [`ComposeEmail::new`]). `from`/`envelope_sender`
+/// start empty until [`ComposeEmail::with_identity`] picks which of the
+/// user's identities to send as; the SMTP path uses `envelope_sender`
+/// for `MAIL FROM` and `from` verbatim as the `From` header, same as
+/// real mail clients keep the two separate for when they differ.
+#[derive(Default)]
+pub struct ComposeEmail {
+    pub from: String,
+    pub envelope_sender: String,
+    pub to: Vec<String>,
+    pub cc: Vec<String>,
+    pub bcc: Vec<String>,
+    pub subject: String,
+    pub body_text: String,
+    pub attachments: Vec<Attachment>,
+    pub in_reply_to: Option<String>,
+    pub references: Vec<String>,
+}
+
+impl ComposeEmail {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_cc(mut self, cc: impl Into<String>) -> Self {
+        self.cc.push(cc.into());
+        self
+    }
+
+    pub fn with_bcc(mut self, bcc: impl Into<String>) -> Self {
+        self.bcc.push(bcc.into());
+        self
+    }
+
+    pub fn with_attachment(mut self, filename: impl Into<String>, content_type: impl Into<String>, body: BodySource) -> Self {
+        self.attachments.push(Attachment { filename: filename.into(), content_type: content_type.into(), body, content_id: None });
+        self
+    }
+
+    /// Selects which identity this draft sends as: sets the `From`
+    /// header and envelope sender from `identity`, and appends its
+    /// signature to the body. Call after building the rest of the
+    /// draft (`reply_to`, `forward`, ...) so the signature lands after
+    /// the quoted text rather than in the middle of it.
+    pub fn with_identity(mut self, identity: &Identity) -> Self {
+        self.from = identity.from_header();
+        self.envelope_sender = identity.address.clone();
+        if !identity.signature.is_empty() {
+            self.body_text.push_str(SIGNATURE_DELIMITER);
+            self.body_text.push_str(&identity.signature);
+        }
+        self
+    }
+
+    /// Replies to `original`'s sender only: quoted body with an
+    /// attribution line, `Re:` subject, and threading headers carried
+    /// over so the reply lands in the same thread. No attachments —
+    /// replying doesn't re-send what was already received.
+    pub fn reply_to(original: &EmailBody) -> Self {
+        Self {
+            from: String::new(),
+            envelope_sender: String::new(),
+            to: vec![original.from.clone()],
+            cc: Vec::new(),
+            bcc: Vec::new(),
+            subject: reply_subject(&original.subject),
+            body_text: quote_body(original),
+            attachments: Vec::new(),
+            in_reply_to: Some(original.message_id.clone()),
+            references: threaded_references(original),
+        }
+    }
+
+    /// Like [`ComposeEmail::reply_to`], but also addresses everyone else
+    /// who received the original message. `own_address` is left out of
+    /// the carried-over recipients so a reply-all doesn't send the
+    /// user a copy of their own message.
+    pub fn reply_all_to(original: &EmailBody, own_address: &str) -> Self {
+        let mut reply = Self::reply_to(original);
+        reply.cc = original
+            .to
+            .iter()
+            .chain(original.cc.iter())
+            .filter(|address| address.as_str() != own_address && address.as_str() != original.from)
+            .cloned()
+            .collect();
+        reply
+    }
+
+    /// Forwards `original`: quoted body with attribution, `Fwd:`
+    /// subject, and its attachments carried over unchanged. Starts a
+    /// new thread rather than continuing the original one — no
+    /// `in_reply_to`/`references` — since the recipient wasn't part of
+    /// that thread.
+    ///
+    /// Takes `original` by value rather than by reference: an
+    /// attachment's [`BodySource`] may stream from an arbitrary reader,
+    /// which can't be cloned, so carrying it over means moving it.
+    /// Spell-checks [`Self::body_text`] against `checker`'s dictionary
+    /// for `locale`, for the compose view to underline as the user
+    /// types. Doesn't touch `subject`, `to`, or any other field — those
+    /// aren't prose a dictionary has an opinion on.
+    pub fn check_spelling(&self, checker: &SpellChecker, locale: &str) -> Vec<Misspelling> {
+        checker.check(&self.body_text, locale)
+    }
+
+    pub fn forward(original: EmailBody) -> Self {
+        Self {
+            from: String::new(),
+            envelope_sender: String::new(),
+            to: Vec::new(),
+            cc: Vec::new(),
+            bcc: Vec::new(),
+            subject: forward_subject(&original.subject),
+            body_text: quote_body(&original),
+            attachments: original.attachments,
+            in_reply_to: None,
+            references: Vec::new(),
+        }
+    }
+}
+
+fn reply_subject(subject: &str) -> String {
+    if subject.to_ascii_lowercase().starts_with("re:") {
+        subject.to_string()
+    } else {
+        format!("Re: {subject}")
+    }
+}
+
+fn forward_subject(subject: &str) -> String {
+    if subject.to_ascii_lowercase().starts_with("fwd:") {
+        subject.to_string()
+    } else {
+        format!("Fwd: {subject}")
+    }
+}
+
+/// Builds the `References` header for a reply: every ancestor the
+/// original message already carried, plus the original itself, oldest
+/// first — the order mail clients thread on.
+fn threaded_references(original: &EmailBody) -> Vec<String> {
+    let mut references = original.references.clone();
+    references.push(original.message_id.clone());
+    references
+}
+
+/// Quotes `original`'s body under a standard attribution line, each
+/// line of the original (already-quoted lines included) prefixed with
+/// another `"> "` — the same nesting convention plain-text mail clients
+/// use for a reply chain.
+fn quote_body(original: &EmailBody) -> String {
+    let attribution = format!("On {}, {} wrote:", original.date, original.from);
+    let quoted = original.body_text.lines().map(|line| format!("> {line}")).collect::<Vec<_>>().join("\n");
+    format!("\n\n{attribution}\n{quoted}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> EmailBody {
+        EmailBody {
+            message_id: "<msg-1@example.com>".to_string(),
+            from: "alice@example.com".to_string(),
+            to: vec!["bob@example.com".to_string(), "carol@example.com".to_string()],
+            cc: vec!["dave@example.com".to_string()],
+            subject: "Lunch?".to_string(),
+            body_text: "Free Thursday?".to_string(),
+            html_body: None,
+            date: "Mon, 1 Jan 2024 09:00:00 +0000".to_string(),
+            attachments: Vec::new(),
+            references: vec!["<msg-0@example.com>".to_string()],
+        }
+    }
+
+    #[test]
+    fn reply_to_addresses_only_the_sender() {
+        let reply = ComposeEmail::reply_to(&sample());
+        assert_eq!(reply.to, vec!["alice@example.com".to_string()]);
+        assert!(reply.cc.is_empty());
+    }
+
+    #[test]
+    fn reply_to_prefixes_the_subject_once() {
+        let reply = ComposeEmail::reply_to(&sample());
+        assert_eq!(reply.subject, "Re: Lunch?");
+
+        let mut already_replied = sample();
+        already_replied.subject = "Re: Lunch?".to_string();
+        assert_eq!(ComposeEmail::reply_to(&already_replied).subject, "Re: Lunch?");
+    }
+
+    #[test]
+    fn reply_to_quotes_the_body_with_an_attribution_line() {
+        let reply = ComposeEmail::reply_to(&sample());
+        assert!(reply.body_text.contains("On Mon, 1 Jan 2024 09:00:00 +0000, alice@example.com wrote:"));
+        assert!(reply.body_text.contains("> Free Thursday?"));
+    }
+
+    #[test]
+    fn reply_to_carries_over_threading_headers() {
+        let reply = ComposeEmail::reply_to(&sample());
+        assert_eq!(reply.in_reply_to, Some("<msg-1@example.com>".to_string()));
+        assert_eq!(reply.references, vec!["<msg-0@example.com>".to_string(), "<msg-1@example.com>".to_string()]);
+    }
+
+    #[test]
+    fn reply_all_addresses_every_other_recipient_but_not_self() {
+        let reply = ComposeEmail::reply_all_to(&sample(), "carol@example.com");
+        assert_eq!(reply.to, vec!["alice@example.com".to_string()]);
+        assert_eq!(reply.cc, vec!["bob@example.com".to_string(), "dave@example.com".to_string()]);
+    }
+
+    #[test]
+    fn forward_carries_over_attachments_and_starts_a_new_thread() {
+        let mut original = sample();
+        original.attachments = vec![Attachment {
+            filename: "agenda.pdf".to_string(),
+            content_type: "application/pdf".to_string(),
+            body: BodySource::Bytes(vec![1, 2, 3]),
+            content_id: None,
+        }];
+
+        let forwarded = ComposeEmail::forward(original);
+
+        assert_eq!(forwarded.subject, "Fwd: Lunch?");
+        assert_eq!(forwarded.attachments.len(), 1);
+        assert_eq!(forwarded.attachments[0].filename, "agenda.pdf");
+        assert!(forwarded.in_reply_to.is_none());
+        assert!(forwarded.references.is_empty());
+    }
+
+    #[test]
+    fn with_attachment_builds_up_a_drafted_attachment_list() {
+        let draft = ComposeEmail::new().with_attachment("notes.txt", "text/plain", BodySource::Bytes(vec![b'h', b'i']));
+        assert_eq!(draft.attachments.len(), 1);
+        assert_eq!(draft.attachments[0].content_type, "text/plain");
+    }
+
+    #[test]
+    fn with_identity_sets_the_from_header_and_envelope_sender() {
+        let identity = Identity {
+            display_name: "Alice".to_string(),
+            address: "alice@example.com".to_string(),
+            signature: String::new(),
+            default_sent_folder: "Sent".to_string(),
+        };
+        let draft = ComposeEmail::new().with_identity(&identity);
+        assert_eq!(draft.from, "Alice <alice@example.com>");
+        assert_eq!(draft.envelope_sender, "alice@example.com");
+    }
+
+    #[test]
+    fn with_identity_appends_the_signature_after_the_quoted_reply() {
+        let identity = Identity {
+            display_name: "Alice".to_string(),
+            address: "alice@example.com".to_string(),
+            signature: "Alice\nSent from Crynn".to_string(),
+            default_sent_folder: "Sent".to_string(),
+        };
+        let draft = ComposeEmail::reply_to(&sample()).with_identity(&identity);
+        assert!(draft.body_text.ends_with("-- \nAlice\nSent from Crynn"));
+        assert!(draft.body_text.contains("> Free Thursday?"));
+    }
+
+    #[test]
+    fn check_spelling_flags_words_outside_the_loaded_dictionary() {
+        let mut checker = SpellChecker::new();
+        let mut fetcher = crynn_spellcheck::EmptyDictionary;
+        checker.load_locale(&mut fetcher, "en-US").unwrap();
+
+        let mut draft = ComposeEmail::new();
+        draft.body_text = "wrold".to_string();
+        let misspellings = draft.check_spelling(&checker, "en-US");
+
+        assert_eq!(misspellings.len(), 1);
+        assert_eq!(misspellings[0].word, "wrold");
+    }
+
+    #[test]
+    fn with_identity_skips_the_signature_when_there_is_none() {
+        let identity =
+            Identity { display_name: "Alice".to_string(), address: "alice@example.com".to_string(), signature: String::new(), default_sent_folder: "Sent".to_string() };
+        let draft = ComposeEmail::new().with_identity(&identity);
+        assert!(!draft.body_text.contains("-- "));
+    }
+}
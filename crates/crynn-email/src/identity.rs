@@ -0,0 +1,164 @@
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+/// One account's outgoing-mail identity: the name and address that go in
+/// the `From` header, the signature new messages get, and which folder
+/// a sent copy is filed under. An account with more than one address
+/// (an alias, a shared mailbox) has one [`Identity`] per address.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Identity {
+    pub display_name: String,
+    pub address: String,
+    pub signature: String,
+    pub default_sent_folder: String,
+}
+
+impl Identity {
+    /// The `From` header value: `"Display Name <address>"`.
+    pub fn from_header(&self) -> String {
+        format!("{} <{}>", self.display_name, self.address)
+    }
+}
+
+/// Every identity configured across the user's accounts, persisted
+/// across restarts, with one of them marked default for new messages.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct IdentityStore {
+    identities: Vec<Identity>,
+    default_address: Option<String>,
+    #[serde(skip)]
+    path: Option<PathBuf>,
+}
+
+impl IdentityStore {
+    /// Loads identities from `path` if it exists, otherwise starts empty.
+    pub fn load(path: impl Into<PathBuf>) -> io::Result<Self> {
+        let path = path.into();
+        let mut store = match fs::read_to_string(&path) {
+            Ok(contents) => {
+                serde_json::from_str::<IdentityStore>(&contents).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => IdentityStore::default(),
+            Err(e) => return Err(e),
+        };
+        store.path = Some(path);
+        Ok(store)
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let contents = serde_json::to_string_pretty(self).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        fs::write(path, contents)
+    }
+
+    pub fn identities(&self) -> &[Identity] {
+        &self.identities
+    }
+
+    pub fn get(&self, address: &str) -> Option<&Identity> {
+        self.identities.iter().find(|i| i.address == address)
+    }
+
+    /// Adds `identity`, or replaces the existing one for the same
+    /// address. The first identity added becomes the default.
+    pub fn add(&mut self, identity: Identity) {
+        if self.default_address.is_none() {
+            self.default_address = Some(identity.address.clone());
+        }
+        self.identities.retain(|i| i.address != identity.address);
+        self.identities.push(identity);
+    }
+
+    pub fn remove(&mut self, address: &str) {
+        self.identities.retain(|i| i.address != address);
+        if self.default_address.as_deref() == Some(address) {
+            self.default_address = self.identities.first().map(|i| i.address.clone());
+        }
+    }
+
+    /// Marks `address` as the one compose starts with, if it's a known
+    /// identity.
+    pub fn set_default(&mut self, address: &str) {
+        if self.get(address).is_some() {
+            self.default_address = Some(address.to_string());
+        }
+    }
+
+    /// The identity compose should start with: the one marked default,
+    /// or the first one configured if none has been chosen explicitly.
+    pub fn default_identity(&self) -> Option<&Identity> {
+        self.default_address.as_deref().and_then(|address| self.get(address)).or_else(|| self.identities.first())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn identity(address: &str) -> Identity {
+        Identity {
+            display_name: "Alice".to_string(),
+            address: address.to_string(),
+            signature: "Sent from Crynn".to_string(),
+            default_sent_folder: "Sent".to_string(),
+        }
+    }
+
+    #[test]
+    fn from_header_combines_display_name_and_address() {
+        assert_eq!(identity("alice@example.com").from_header(), "Alice <alice@example.com>");
+    }
+
+    #[test]
+    fn the_first_identity_added_becomes_the_default() {
+        let mut store = IdentityStore::default();
+        store.add(identity("alice@example.com"));
+        store.add(identity("alice-work@example.com"));
+        assert_eq!(store.default_identity().unwrap().address, "alice@example.com");
+    }
+
+    #[test]
+    fn set_default_switches_which_identity_compose_starts_with() {
+        let mut store = IdentityStore::default();
+        store.add(identity("alice@example.com"));
+        store.add(identity("alice-work@example.com"));
+        store.set_default("alice-work@example.com");
+        assert_eq!(store.default_identity().unwrap().address, "alice-work@example.com");
+    }
+
+    #[test]
+    fn removing_the_default_falls_back_to_another_identity() {
+        let mut store = IdentityStore::default();
+        store.add(identity("alice@example.com"));
+        store.add(identity("alice-work@example.com"));
+        store.remove("alice@example.com");
+        assert_eq!(store.default_identity().unwrap().address, "alice-work@example.com");
+    }
+
+    #[test]
+    fn save_without_a_path_is_a_harmless_no_op() {
+        let store = IdentityStore::default();
+        assert!(store.save().is_ok());
+    }
+
+    #[test]
+    fn loading_back_a_saved_store_round_trips_its_identities() {
+        let path = std::env::temp_dir().join(format!("crynn-email-identity-test-{}.json", std::process::id()));
+        let mut store = IdentityStore::load(&path).unwrap();
+        store.add(identity("alice@example.com"));
+        store.save().unwrap();
+
+        let reloaded = IdentityStore::load(&path).unwrap();
+        assert_eq!(reloaded.default_identity().unwrap().address, "alice@example.com");
+
+        let _ = fs::remove_file(&path);
+    }
+}
@@ -0,0 +1,70 @@
+use crynn_network::BodySource;
+
+use crate::message::Attachment;
+
+/// Rewrites `cid:<content-id>` references in `html` to a source the
+/// shell's HTML renderer can load directly: a `data:` URI for an
+/// attachment held in memory, or the path it was streamed to disk at.
+/// A `cid:` with no matching attachment, or one streaming from a
+/// [`BodySource::Reader`]/[`BodySource::Multipart`] (reading either
+/// would consume it; resolving without consuming needs a seekable
+/// source this crate doesn't have), is left as-is.
+pub fn resolve_inline_images(html: &str, attachments: &[Attachment]) -> String {
+    let mut resolved = html.to_string();
+    for attachment in attachments {
+        let Some(content_id) = &attachment.content_id else { continue };
+        let Some(src) = inline_src(attachment) else { continue };
+        resolved = resolved.replace(&format!("cid:{content_id}"), &src);
+    }
+    resolved
+}
+
+fn inline_src(attachment: &Attachment) -> Option<String> {
+    match &attachment.body {
+        BodySource::Bytes(bytes) => {
+            use base64::engine::general_purpose::STANDARD;
+            use base64::Engine;
+            Some(format!("data:{};base64,{}", attachment.content_type, STANDARD.encode(bytes)))
+        }
+        BodySource::File(path) => Some(format!("file://{}", path.display())),
+        BodySource::Reader(_) | BodySource::Multipart(_) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn attachment(content_id: &str, body: BodySource) -> Attachment {
+        Attachment { filename: "image.png".to_string(), content_type: "image/png".to_string(), body, content_id: Some(content_id.to_string()) }
+    }
+
+    #[test]
+    fn a_cid_reference_to_an_in_memory_attachment_becomes_a_data_uri() {
+        let attachments = vec![attachment("logo", BodySource::Bytes(vec![1, 2, 3]))];
+        let resolved = resolve_inline_images(r#"<img src="cid:logo">"#, &attachments);
+        assert!(resolved.starts_with(r#"<img src="data:image/png;base64,"#));
+        assert!(!resolved.contains("cid:logo"));
+    }
+
+    #[test]
+    fn a_cid_reference_to_a_file_backed_attachment_becomes_a_file_url() {
+        let attachments = vec![attachment("logo", BodySource::File("/tmp/logo.png".into()))];
+        let resolved = resolve_inline_images(r#"<img src="cid:logo">"#, &attachments);
+        assert_eq!(resolved, r#"<img src="file:///tmp/logo.png">"#);
+    }
+
+    #[test]
+    fn an_unmatched_cid_is_left_alone() {
+        let resolved = resolve_inline_images(r#"<img src="cid:missing">"#, &[]);
+        assert_eq!(resolved, r#"<img src="cid:missing">"#);
+    }
+
+    #[test]
+    fn an_attachment_without_a_content_id_is_never_substituted() {
+        let attachments =
+            vec![Attachment { filename: "image.png".to_string(), content_type: "image/png".to_string(), body: BodySource::Bytes(vec![1]), content_id: None }];
+        let resolved = resolve_inline_images(r#"<img src="cid:logo">"#, &attachments);
+        assert_eq!(resolved, r#"<img src="cid:logo">"#);
+    }
+}
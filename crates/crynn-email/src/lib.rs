@@ -0,0 +1,96 @@
+//! Message and compose types for the email client: no IMAP/JMAP sync and
+//! no transport live here yet (that's [`crynn_network`]'s job once it has
+//! one), just the data this crate's callers need regardless of how a
+//! message arrived or is about to be sent.
+//!
+//! [`EmailBody`] is a received message; [`ComposeEmail`] is a draft.
+//! [`ComposeEmail::reply_to`] and [`ComposeEmail::reply_all_to`] build a
+//! quoted reply with an attribution line and the `In-Reply-To`/
+//! `References` headers carried over so it threads correctly;
+//! [`ComposeEmail::forward`] does the same for the subject and quoting
+//! but starts a new thread and carries the original's attachments
+//! instead of its recipients.
+//!
+//! [`Identity`] and [`IdentityStore`] are which address a draft sends
+//! as: [`ComposeEmail::with_identity`] sets the `From` header/envelope
+//! sender and appends the identity's signature, so an account with
+//! several addresses (aliases, a shared mailbox) picks one at compose
+//! time instead of always sending as the account's primary address.
+//!
+//! [`SpamFilter`] is junk-mail handling: [`SpamFilter::mark_as_spam`]
+//! and [`SpamFilter::not_spam`] drive a [`MailboxMover`] (the account's
+//! sync side, once one exists) to move the message and learn the
+//! sender into a local allow/block list, and
+//! [`SpamFilter::should_file_as_spam`] is what a sync run consults for
+//! mail the server's own spam folder didn't already flag — the allow/
+//! block lists first, then an optional [`NaiveBayesClassifier`] trained
+//! on cached headers/bodies.
+
+//!
+//! [`SyncScheduler`] decides when each folder is due for a sync:
+//! [`INBOX_FOLDER`] on a short interval, other folders on a longer one,
+//! backed off while the shell reports
+//! [`ConnectionConstraints::on_battery`]/`metered`.
+//! [`SyncScheduler::sync_now`] is the UI's bypass for an explicit
+//! "sync now", and [`SyncScheduler::status`] is the per-folder history
+//! the UI renders next to each folder's name.
+//!
+//! [`resolve_inline_images`] rewrites an [`EmailBody::html_body`]'s
+//! `cid:` references against its attachments before the shell renders
+//! it. [`preview`] is the attachment pane's "what is this" check: it
+//! pulls one attachment through an [`AttachmentSource`] (the account's
+//! sync side, once one exists) and classifies it into a [`Preview`] the
+//! shell picks a widget for without having to inspect the bytes itself.
+//!
+//! [`EmailBody::sanitized`] is what actually renders a fetched body:
+//! [`sanitize_html`] strips scripts/forms/event handlers outright,
+//! blocks remote image loading unless the sender is on a per-sender
+//! allow list, and rewrites links so the shell can confirm before
+//! following one from a received message, returning both the sanitized
+//! HTML and a [`SanitizedEmail::plain_text`] fallback.
+//!
+//! [`ComposeEmail::check_spelling`] runs the compose view's body text
+//! through an injected `crynn_spellcheck::SpellChecker` for a chosen
+//! locale; the checker itself — its dictionaries, personal word list,
+//! and language auto-detection — lives in [`crynn_spellcheck`], which
+//! this crate only calls into.
+//!
+//! [`setup_account`] is the guided account-setup wizard's backend:
+//! given an address and password it autoconfigures IMAP/SMTP hosts from
+//! the domain, asks an injected [`PortProbe`] which candidate port and
+//! [`SecurityMode`] actually responds, reports
+//! [`crynn_error::EmailError::OAuthRequired`] up front for providers
+//! that don't take a password at all, and asks a [`CredentialVerifier`]
+//! to confirm the password before returning a ready [`EmailConfig`].
+//!
+//! [`export_folder`] is local backup and migration: every message in a
+//! folder written as one mbox file or as individual `.eml` files, so a
+//! user can back up their mail independently of the server or move it
+//! into another client. [`import_mbox`] reads a previously exported
+//! mbox file back in. Neither touches attachments or the HTML part —
+//! this crate has no MIME multipart writer yet, so round-tripping is
+//! headers and the plain-text body only.
+
+mod backup;
+mod classifier;
+mod compose;
+mod identity;
+mod inline;
+mod message;
+mod preview;
+mod sanitize;
+mod scheduler;
+mod setup;
+mod spam;
+
+pub use backup::{export_folder, import_mbox, MailExportFormat};
+pub use classifier::NaiveBayesClassifier;
+pub use compose::ComposeEmail;
+pub use identity::{Identity, IdentityStore};
+pub use inline::resolve_inline_images;
+pub use message::{Attachment, EmailBody};
+pub use preview::{preview, AttachmentSource, Preview};
+pub use sanitize::{sanitize_html, SanitizedEmail};
+pub use scheduler::{ConnectionConstraints, SyncOutcome, SyncScheduler, SyncStatus, INBOX_FOLDER};
+pub use setup::{setup_account, AuthMethod, CredentialVerifier, EmailConfig, PortProbe, SecurityMode, ServerConfig};
+pub use spam::{MailboxMover, SpamFilter};
@@ -0,0 +1,55 @@
+use crynn_network::BodySource;
+
+use crate::sanitize::{sanitize_html, SanitizedEmail};
+
+/// A file attached to a message. Reuses [`BodySource`] for the same
+/// reason the network crate introduced it: forwarding a message with a
+/// large attachment shouldn't require loading it into memory just to
+/// carry it over to the new compose.
+pub struct Attachment {
+    pub filename: String,
+    pub content_type: String,
+    pub body: BodySource,
+    /// The MIME `Content-ID`, without the surrounding angle brackets,
+    /// for attachments referenced inline from the HTML body as
+    /// `cid:<id>` rather than shown in the attachment list — an inline
+    /// image, typically. `None` for a plain attachment.
+    pub content_id: Option<String>,
+}
+
+/// A received message, as the IMAP/JMAP sync side of this crate would
+/// hand it to the shell's compose window once one exists. `references`
+/// is every `Message-Id` in the thread up to and including this
+/// message's parent, oldest first, the way the `References` header is
+/// conventionally built.
+pub struct EmailBody {
+    pub message_id: String,
+    pub from: String,
+    pub to: Vec<String>,
+    pub cc: Vec<String>,
+    pub subject: String,
+    pub body_text: String,
+    /// The HTML part, if the message had one. [`crate::resolve_inline_images`]
+    /// rewrites its `cid:` references against [`EmailBody::attachments`]
+    /// before the shell renders it.
+    pub html_body: Option<String>,
+    /// RFC 2822 date string, kept as-is rather than parsed since nothing
+    /// here needs to do date arithmetic on it — just quote it back in
+    /// the attribution line.
+    pub date: String,
+    pub attachments: Vec<Attachment>,
+    pub references: Vec<String>,
+}
+
+impl EmailBody {
+    /// Runs [`EmailBody::html_body`] through [`sanitize_html`] for safe
+    /// rendering, falling back to [`EmailBody::body_text`] verbatim
+    /// (wrapped as the plain-text side, with no HTML to show) for a
+    /// message that never had an HTML part.
+    pub fn sanitized(&self, always_load_images_from: &[String]) -> SanitizedEmail {
+        match &self.html_body {
+            Some(html) => sanitize_html(html, &self.from, always_load_images_from),
+            None => SanitizedEmail { html: String::new(), plain_text: self.body_text.clone() },
+        }
+    }
+}
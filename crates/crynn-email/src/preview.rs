@@ -0,0 +1,159 @@
+use crynn_error::EmailError;
+
+use crate::message::Attachment;
+
+/// Fetches an attachment's bytes on demand, the way the account's sync
+/// side would once it exists: attachment previews shouldn't require
+/// downloading every attachment in a message up front, only the one the
+/// user clicked.
+pub trait AttachmentSource {
+    fn attachment(&mut self, uid: &str, index: usize) -> Result<Attachment, EmailError>;
+}
+
+/// What [`preview`] renders for an attachment, classified by its
+/// content type so the shell can pick a widget without inspecting the
+/// bytes itself.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Preview {
+    Image { content_type: String, bytes: Vec<u8> },
+    /// No PDF text extraction lives in this crate yet, so `snippet` is
+    /// always empty for now — the shell still gets a `Pdf` preview to
+    /// show a PDF-shaped placeholder rather than falling back to
+    /// `Unsupported`.
+    Pdf { snippet: String },
+    Text { snippet: String },
+    Unsupported { content_type: String },
+}
+
+/// How much of a text attachment's content to show before truncating.
+const TEXT_SNIPPET_LIMIT: usize = 500;
+
+/// Fetches attachment `index` of message `uid` from `source` and
+/// classifies it into a [`Preview`] by content type.
+pub fn preview(source: &mut dyn AttachmentSource, uid: &str, index: usize) -> Result<Preview, EmailError> {
+    let attachment = source.attachment(uid, index)?;
+    let bytes = match &attachment.body {
+        crynn_network::BodySource::Bytes(bytes) => bytes.clone(),
+        _ => Vec::new(),
+    };
+
+    if attachment.content_type.starts_with("image/") {
+        return Ok(Preview::Image { content_type: attachment.content_type, bytes });
+    }
+    if attachment.content_type == "application/pdf" {
+        return Ok(Preview::Pdf { snippet: String::new() });
+    }
+    if attachment.content_type.starts_with("text/") {
+        return Ok(Preview::Text { snippet: text_snippet(&bytes) });
+    }
+    Ok(Preview::Unsupported { content_type: attachment.content_type })
+}
+
+/// Sanitizes and truncates raw text-attachment bytes for display:
+/// invalid UTF-8 is replaced rather than rejected (a preview shouldn't
+/// fail outright over mildly malformed text), and control characters
+/// other than `\n`/`\t` are stripped since they'd otherwise do things
+/// like move the cursor around in the preview pane.
+fn text_snippet(bytes: &[u8]) -> String {
+    let text = String::from_utf8_lossy(bytes);
+    let sanitized: String = text.chars().filter(|c| !c.is_control() || *c == '\n' || *c == '\t').collect();
+    if sanitized.chars().count() > TEXT_SNIPPET_LIMIT {
+        sanitized.chars().take(TEXT_SNIPPET_LIMIT).collect()
+    } else {
+        sanitized
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crynn_network::BodySource;
+
+    struct FixedSource {
+        attachment: Option<Attachment>,
+    }
+
+    impl AttachmentSource for FixedSource {
+        fn attachment(&mut self, uid: &str, _index: usize) -> Result<Attachment, EmailError> {
+            self.attachment.take().ok_or_else(|| EmailError::AttachmentNotFound { id: uid.to_string() })
+        }
+    }
+
+    #[test]
+    fn an_image_attachment_previews_as_an_image() {
+        let mut source = FixedSource {
+            attachment: Some(Attachment {
+                filename: "photo.jpg".to_string(),
+                content_type: "image/jpeg".to_string(),
+                body: BodySource::Bytes(vec![1, 2, 3]),
+                content_id: None,
+            }),
+        };
+        let preview = preview(&mut source, "msg-1", 0).unwrap();
+        assert_eq!(preview, Preview::Image { content_type: "image/jpeg".to_string(), bytes: vec![1, 2, 3] });
+    }
+
+    #[test]
+    fn a_pdf_attachment_previews_with_an_empty_snippet() {
+        let mut source = FixedSource {
+            attachment: Some(Attachment {
+                filename: "doc.pdf".to_string(),
+                content_type: "application/pdf".to_string(),
+                body: BodySource::Bytes(vec![1, 2, 3]),
+                content_id: None,
+            }),
+        };
+        let preview = preview(&mut source, "msg-1", 0).unwrap();
+        assert_eq!(preview, Preview::Pdf { snippet: String::new() });
+    }
+
+    #[test]
+    fn a_text_attachment_previews_with_its_sanitized_content() {
+        let mut source = FixedSource {
+            attachment: Some(Attachment {
+                filename: "notes.txt".to_string(),
+                content_type: "text/plain".to_string(),
+                body: BodySource::Bytes(b"hello\x07world".to_vec()),
+                content_id: None,
+            }),
+        };
+        let preview = preview(&mut source, "msg-1", 0).unwrap();
+        assert_eq!(preview, Preview::Text { snippet: "helloworld".to_string() });
+    }
+
+    #[test]
+    fn a_long_text_attachment_is_truncated() {
+        let mut source = FixedSource {
+            attachment: Some(Attachment {
+                filename: "notes.txt".to_string(),
+                content_type: "text/plain".to_string(),
+                body: BodySource::Bytes(vec![b'a'; TEXT_SNIPPET_LIMIT + 50]),
+                content_id: None,
+            }),
+        };
+        let preview = preview(&mut source, "msg-1", 0).unwrap();
+        let Preview::Text { snippet } = preview else { panic!("expected a text preview") };
+        assert_eq!(snippet.len(), TEXT_SNIPPET_LIMIT);
+    }
+
+    #[test]
+    fn an_unrecognized_content_type_is_unsupported() {
+        let mut source = FixedSource {
+            attachment: Some(Attachment {
+                filename: "archive.zip".to_string(),
+                content_type: "application/zip".to_string(),
+                body: BodySource::Bytes(vec![1, 2, 3]),
+                content_id: None,
+            }),
+        };
+        let preview = preview(&mut source, "msg-1", 0).unwrap();
+        assert_eq!(preview, Preview::Unsupported { content_type: "application/zip".to_string() });
+    }
+
+    #[test]
+    fn a_missing_attachment_surfaces_the_sources_error() {
+        let mut source = FixedSource { attachment: None };
+        let result = preview(&mut source, "msg-1", 0);
+        assert!(matches!(result, Err(EmailError::AttachmentNotFound { id }) if id == "msg-1"));
+    }
+}
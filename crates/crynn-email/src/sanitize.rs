@@ -0,0 +1,411 @@
+/// Tags whose content is dropped outright rather than sanitized in
+/// place: a script has no sanitized form that's still a script, a
+/// form posting back to the sender's server is never something a
+/// received message should be able to do, `iframe`/`object`/`embed`
+/// embed active content this pipeline has no way to sanitize in turn,
+/// and `style`/`link`/`meta` can fetch remote resources or redirect the
+/// page (`<meta http-equiv="refresh">`) outside of the `src`/`href`
+/// rewriting below.
+const BLOCKED_TAGS: &[&str] = &["script", "form", "iframe", "object", "embed", "style", "link", "meta"];
+
+/// The `html` output's stand-in for a blocked remote image: kept as an
+/// `<img>` so the layout doesn't shift once the real image loads, with
+/// the original URL preserved in `data-crynn-blocked-src` for the "load
+/// images" action to read back.
+const BLOCKED_SRC_ATTR: &str = "data-crynn-blocked-src";
+
+/// The scheme [`sanitize_html`] rewrites `http(s)` links to, so the
+/// shell's link handler can show a "this will open an external site"
+/// confirmation before navigating instead of following it straight
+/// from a received message.
+const LINK_CONFIRM_SCHEME: &str = "crynn-confirm-link:";
+
+/// The result of running a fetched message body through
+/// [`sanitize_html`]: `html` for the rendered view, `plain_text` for
+/// anywhere a message needs to be shown or searched as text (the
+/// preview line, a notification, full-text search).
+#[derive(Debug, Clone, PartialEq)]
+pub struct SanitizedEmail {
+    pub html: String,
+    pub plain_text: String,
+}
+
+/// Sanitizes a fetched HTML body for safe rendering: strips
+/// [`BLOCKED_TAGS`] entirely along with `on*` event handler attributes,
+/// blocks remote `<img>` loading unless `sender` appears in
+/// `always_load_images_from`, and rewrites `<a>`/`<area>` links whose
+/// `href` [`needs_click_confirmation`] so the shell can ask before
+/// following one from a received message. `plain_text` is every
+/// remaining text node, for use anywhere a message needs to be shown or
+/// searched as text.
+pub fn sanitize_html(html: &str, sender: &str, always_load_images_from: &[String]) -> SanitizedEmail {
+    let without_blocked = strip_blocked_tags(html);
+    let load_images = always_load_images_from.iter().any(|allowed| allowed == sender);
+    let mut out = String::with_capacity(without_blocked.len());
+    let mut plain_text = String::new();
+    let mut chars = without_blocked.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c != '<' {
+            out.push(c);
+            plain_text.push(c);
+            continue;
+        }
+        let mut tag_source = String::from("<");
+        for next in chars.by_ref() {
+            tag_source.push(next);
+            if next == '>' {
+                break;
+            }
+        }
+        out.push_str(&sanitize_tag(&tag_source, load_images));
+    }
+
+    SanitizedEmail { html: out, plain_text: collapse_whitespace(&plain_text) }
+}
+
+/// Removes every `<tag ...>...</tag>` span (case-insensitive, tags from
+/// [`BLOCKED_TAGS`]) from `html`, content included.
+fn strip_blocked_tags(html: &str) -> String {
+    let mut result = html.to_string();
+    for tag in BLOCKED_TAGS {
+        loop {
+            let lower = result.to_ascii_lowercase();
+            let Some(open_start) = find_tag_open(&lower, tag) else { break };
+            let Some(open_end_rel) = lower[open_start..].find('>') else { break };
+            let open_end = open_start + open_end_rel + 1;
+            let close_tag = format!("</{tag}>");
+            let Some(close_start_rel) = lower[open_end..].find(&close_tag) else {
+                result.replace_range(open_start..open_end, "");
+                continue;
+            };
+            let close_end = open_end + close_start_rel + close_tag.len();
+            result.replace_range(open_start..close_end, "");
+        }
+    }
+    result
+}
+
+/// Finds the next `<tag` in `lower` (already-lowercased) whose name is
+/// exactly `tag` rather than merely starting with it: the character
+/// right after the name must end it (whitespace, `>`, or the `/` of a
+/// self-closing tag), so a custom element like `<stylesheet-widget>`
+/// isn't mistaken for `<style>`.
+fn find_tag_open(lower: &str, tag: &str) -> Option<usize> {
+    let needle = format!("<{tag}");
+    let mut search_from = 0;
+    while let Some(rel) = lower[search_from..].find(&needle) {
+        let start = search_from + rel;
+        let after = start + needle.len();
+        match lower[after..].chars().next() {
+            Some(c) if c.is_whitespace() || c == '>' || c == '/' => return Some(start),
+            None => return Some(start),
+            _ => search_from = start + 1,
+        }
+    }
+    None
+}
+
+/// Rewrites one already-extracted `<...>` tag: drops `on*` and `style`
+/// attributes, blocks or rewrites `src`/`href` per [`sanitize_html`]'s
+/// rules, and leaves everything else untouched.
+fn sanitize_tag(tag_source: &str, load_images: bool) -> String {
+    let inner = tag_source.trim_start_matches('<').trim_end_matches('>');
+    let is_closing = inner.starts_with('/');
+    if is_closing {
+        return tag_source.to_string();
+    }
+
+    let mut parts = tokenize_tag(inner);
+    let Some(name) = parts.first().map(|(n, _)| n.to_ascii_lowercase()) else {
+        return tag_source.to_string();
+    };
+    let self_closing = inner.trim_end().ends_with('/');
+
+    let mut rendered = format!("<{}", parts.remove(0).0);
+    for (attr_name, attr_value) in parts {
+        let lower_name = attr_name.to_ascii_lowercase();
+        if lower_name.starts_with("on") {
+            continue;
+        }
+        if lower_name == "style" {
+            // Inline styles can fetch remote resources (`url(...)`
+            // backgrounds) or trigger legacy CSS behaviors just as
+            // surely as a blocked `<style>` element or `<img src>`, so
+            // there's no sanitized form worth keeping — drop the whole
+            // attribute rather than trying to parse CSS here.
+            continue;
+        }
+        if name == "img" && lower_name == "src" && !load_images && is_remote_url(&attr_value) {
+            rendered.push_str(&format!(" {BLOCKED_SRC_ATTR}=\"{attr_value}\""));
+            continue;
+        }
+        if (name == "a" || name == "area") && lower_name == "href" && needs_click_confirmation(&attr_value) {
+            rendered.push_str(&format!(" href=\"{LINK_CONFIRM_SCHEME}{attr_value}\""));
+            continue;
+        }
+        if attr_value.is_empty() && !attr_name.contains('=') {
+            rendered.push_str(&format!(" {attr_name}"));
+        } else {
+            rendered.push_str(&format!(" {attr_name}=\"{attr_value}\""));
+        }
+    }
+    if self_closing {
+        rendered.push_str(" />");
+    } else {
+        rendered.push('>');
+    }
+    rendered
+}
+
+/// Splits a tag's inner text (name and attributes, `<`/`>` already
+/// stripped) into `(name_or_attr_name, value)` pairs. The first element
+/// is always the tag name with an empty value.
+fn tokenize_tag(inner: &str) -> Vec<(String, String)> {
+    let mut tokens = Vec::new();
+    let mut chars = inner.chars().peekable();
+    let mut first = true;
+
+    loop {
+        while chars.peek().is_some_and(|c| c.is_whitespace() || *c == '/') {
+            chars.next();
+        }
+        let mut name = String::new();
+        while chars.peek().is_some_and(|c| !c.is_whitespace() && *c != '=' && *c != '/') {
+            name.push(chars.next().unwrap());
+        }
+        if name.is_empty() {
+            break;
+        }
+        if first {
+            tokens.push((name, String::new()));
+            first = false;
+            continue;
+        }
+        while chars.peek().is_some_and(|c| c.is_whitespace()) {
+            chars.next();
+        }
+        let mut value = String::new();
+        if chars.peek() == Some(&'=') {
+            chars.next();
+            while chars.peek().is_some_and(|c| c.is_whitespace()) {
+                chars.next();
+            }
+            if matches!(chars.peek(), Some('"') | Some('\'')) {
+                let quote = chars.next().unwrap();
+                for c in chars.by_ref() {
+                    if c == quote {
+                        break;
+                    }
+                    value.push(c);
+                }
+            } else {
+                while chars.peek().is_some_and(|c| !c.is_whitespace()) {
+                    value.push(chars.next().unwrap());
+                }
+            }
+        }
+        tokens.push((name, value));
+    }
+    tokens
+}
+
+fn is_remote_url(value: &str) -> bool {
+    let lower = value.to_ascii_lowercase();
+    lower.starts_with("http://") || lower.starts_with("https://")
+}
+
+/// Whether an `<a>`/`<area>` `href` needs the [`LINK_CONFIRM_SCHEME`]
+/// click-confirmation rewrite: not just `http(s)` links, but any scheme
+/// that can run script or act without the user choosing to follow it —
+/// `javascript:`/`vbscript:` execute outright, and `data:` can carry an
+/// `text/html` payload that does the same once navigated to. Unlike
+/// [`is_remote_url`], `data:` is covered here even though it's left
+/// alone on `<img src>`, where it's just inline image bytes rather than
+/// something that can run.
+///
+/// Matches against a decoded copy of `value` rather than the raw
+/// attribute text: a real HTML parser resolves character references
+/// (`&#106;` is `j`) and drops embedded tabs/newlines before it ever
+/// looks at the scheme, so `&#106;avascript:` and `java&#09;script:`
+/// are both just `javascript:` by the time a browser follows the link.
+fn needs_click_confirmation(value: &str) -> bool {
+    let decoded = strip_url_control_chars(&decode_character_references(value));
+    let lower = decoded.trim().to_ascii_lowercase();
+    is_remote_url(&lower) || lower.starts_with("javascript:") || lower.starts_with("vbscript:") || lower.starts_with("data:")
+}
+
+/// Decodes decimal (`&#106;`) and hexadecimal (`&#x6a;`) numeric
+/// character references, with or without the trailing `;` (real parsers
+/// accept both). Named references (`&amp;`) aren't handled — they don't
+/// appear in the scheme of a URL attacker-controlled enough to matter
+/// here.
+fn decode_character_references(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    let mut chars = value.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '&' || chars.peek() != Some(&'#') {
+            out.push(c);
+            continue;
+        }
+        let mut lookahead = chars.clone();
+        lookahead.next(); // the '#'
+        let hex = matches!(lookahead.peek(), Some('x') | Some('X'));
+        if hex {
+            lookahead.next();
+        }
+        let mut digits = String::new();
+        while lookahead.peek().is_some_and(|d| if hex { d.is_ascii_hexdigit() } else { d.is_ascii_digit() }) {
+            digits.push(lookahead.next().unwrap());
+        }
+        let Some(code_point) = u32::from_str_radix(&digits, if hex { 16 } else { 10 }).ok().and_then(char::from_u32) else {
+            out.push(c);
+            continue;
+        };
+        if lookahead.peek() == Some(&';') {
+            lookahead.next();
+        }
+        out.push(code_point);
+        chars = lookahead;
+    }
+    out
+}
+
+/// Removes ASCII tab, newline, and carriage return — browsers strip
+/// these from a URL before resolving its scheme, so a scheme split
+/// across them (`java\tscript:`) still resolves to `javascript:`.
+fn strip_url_control_chars(value: &str) -> String {
+    value.chars().filter(|c| !matches!(c, '\t' | '\n' | '\r')).collect()
+}
+
+/// Collapses runs of whitespace (including the newlines left behind by
+/// stripped block-level tags) down to single spaces, trimming the
+/// result — the same normalization a plain-text fallback needs
+/// regardless of how the original markup was indented.
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn script_and_form_elements_are_removed_entirely() {
+        let sanitized = sanitize_html(
+            r#"<p>Hi</p><script>alert('x')</script><form action="https://evil.example"><input></form><p>Bye</p>"#,
+            "alice@example.com",
+            &[],
+        );
+        assert!(!sanitized.html.contains("script"));
+        assert!(!sanitized.html.contains("form"));
+        assert!(!sanitized.html.contains("alert"));
+        assert_eq!(sanitized.plain_text, "HiBye");
+    }
+
+    #[test]
+    fn event_handler_attributes_are_dropped() {
+        let sanitized = sanitize_html(r#"<img src="logo.png" onerror="steal()">"#, "alice@example.com", &[]);
+        assert!(!sanitized.html.contains("onerror"));
+        assert!(!sanitized.html.contains("steal"));
+    }
+
+    #[test]
+    fn remote_images_are_blocked_by_default() {
+        let sanitized = sanitize_html(r#"<img src="https://tracker.example/pixel.gif">"#, "alice@example.com", &[]);
+        assert!(!sanitized.html.contains(r#"<img src="#));
+        assert!(sanitized.html.contains(r#"data-crynn-blocked-src="https://tracker.example/pixel.gif""#));
+    }
+
+    #[test]
+    fn remote_images_load_for_an_allowed_sender() {
+        let sanitized =
+            sanitize_html(r#"<img src="https://cdn.example/photo.jpg">"#, "alice@example.com", &["alice@example.com".to_string()]);
+        assert!(sanitized.html.contains(r#"src="https://cdn.example/photo.jpg""#));
+        assert!(!sanitized.html.contains(BLOCKED_SRC_ATTR));
+    }
+
+    #[test]
+    fn a_data_uri_image_is_never_blocked() {
+        let sanitized = sanitize_html(r#"<img src="data:image/png;base64,AAAA">"#, "alice@example.com", &[]);
+        assert!(sanitized.html.contains(r#"src="data:image/png;base64,AAAA""#));
+    }
+
+    #[test]
+    fn remote_links_are_rewritten_for_click_confirmation() {
+        let sanitized = sanitize_html(r#"<a href="https://example.com/offer">Click</a>"#, "alice@example.com", &[]);
+        assert!(sanitized.html.contains(r#"href="crynn-confirm-link:https://example.com/offer""#));
+    }
+
+    #[test]
+    fn javascript_and_vbscript_and_data_hrefs_are_rewritten_for_click_confirmation() {
+        let sanitized = sanitize_html(
+            r#"<a href="javascript:alert(1)">A</a><a href="vbscript:msgbox(1)">B</a><a href="data:text/html,x">C</a>"#,
+            "alice@example.com",
+            &[],
+        );
+        assert!(sanitized.html.contains(r#"href="crynn-confirm-link:javascript:alert(1)""#));
+        assert!(sanitized.html.contains(r#"href="crynn-confirm-link:vbscript:msgbox(1)""#));
+        assert!(sanitized.html.contains(r#"href="crynn-confirm-link:data:text/html,x""#));
+    }
+
+    #[test]
+    fn active_content_and_resource_fetching_elements_are_removed_entirely() {
+        let sanitized = sanitize_html(
+            concat!(
+                r#"<iframe src="javascript:alert(1)"></iframe>"#,
+                r#"<object data="evil.swf"></object>"#,
+                r#"<embed src="evil.swf">"#,
+                r#"<style>body{background:url(https://tracker.example/pixel.gif)}</style>"#,
+                r#"<link rel="stylesheet" href="https://tracker.example/style.css">"#,
+                r#"<meta http-equiv="refresh" content="0;url=https://evil.example">"#,
+                "<p>Bye</p>",
+            ),
+            "alice@example.com",
+            &[],
+        );
+        assert!(!sanitized.html.contains("iframe"));
+        assert!(!sanitized.html.contains("object"));
+        assert!(!sanitized.html.contains("embed"));
+        assert!(!sanitized.html.contains("style"));
+        assert!(!sanitized.html.contains("link"));
+        assert!(!sanitized.html.contains("meta"));
+        assert!(!sanitized.html.contains("evil.example"));
+        assert_eq!(sanitized.plain_text, "Bye");
+    }
+
+    #[test]
+    fn style_attributes_are_dropped() {
+        let sanitized = sanitize_html(
+            r#"<div style="background:url(https://tracker.example/pixel.gif)">hi</div>"#,
+            "alice@example.com",
+            &[],
+        );
+        assert!(!sanitized.html.contains("style"));
+        assert!(!sanitized.html.contains("tracker.example"));
+    }
+
+    #[test]
+    fn character_referenced_and_tab_split_javascript_schemes_are_rewritten() {
+        let sanitized = sanitize_html(
+            r#"<a href="&#106;avascript:alert(1)">A</a><a href="java&#09;script:alert(1)">B</a>"#,
+            "alice@example.com",
+            &[],
+        );
+        assert!(sanitized.html.contains(&format!("href=\"{LINK_CONFIRM_SCHEME}&#106;avascript:alert(1)\"")));
+        assert!(sanitized.html.contains(&format!("href=\"{LINK_CONFIRM_SCHEME}java&#09;script:alert(1)\"")));
+    }
+
+    #[test]
+    fn a_custom_element_merely_starting_with_a_blocked_tag_name_is_left_alone() {
+        let sanitized = sanitize_html(r#"<stylesheet-widget>bye</stylesheet-widget>"#, "alice@example.com", &[]);
+        assert!(sanitized.html.contains("stylesheet-widget"));
+        assert_eq!(sanitized.plain_text, "bye");
+    }
+
+    #[test]
+    fn plain_text_strips_all_markup() {
+        let sanitized = sanitize_html(r#"<p>Hello <b>world</b></p>"#, "alice@example.com", &[]);
+        assert_eq!(sanitized.plain_text, "Hello world");
+    }
+}
@@ -0,0 +1,168 @@
+use std::collections::HashMap;
+
+/// The special-cased folder that syncs far more often than everything
+/// else — new mail shows up there first, so it's the one users notice
+/// lag on.
+pub const INBOX_FOLDER: &str = "INBOX";
+
+const INBOX_INTERVAL_SECS: u64 = 60;
+const DEFAULT_FOLDER_INTERVAL_SECS: u64 = 900;
+
+/// How much longer to wait between syncs while the shell reports these
+/// constraints. Metered wins over battery when both apply — burning
+/// data costs money, burning battery doesn't run out a plan.
+const BATTERY_BACKOFF_MULTIPLIER: u64 = 2;
+const METERED_BACKOFF_MULTIPLIER: u64 = 4;
+
+/// Power/connection state the shell reports in, so the scheduler backs
+/// off without needing to know anything about batteries or network
+/// interfaces itself.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ConnectionConstraints {
+    pub on_battery: bool,
+    pub metered: bool,
+}
+
+impl ConnectionConstraints {
+    fn backoff_multiplier(&self) -> u64 {
+        if self.metered {
+            METERED_BACKOFF_MULTIPLIER
+        } else if self.on_battery {
+            BATTERY_BACKOFF_MULTIPLIER
+        } else {
+            1
+        }
+    }
+}
+
+/// How a folder's last sync attempt went, for the UI to show next to
+/// its name.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SyncOutcome {
+    Success,
+    Failed { reason: String },
+}
+
+/// A folder's sync history, as the UI would render it: "Synced 2
+/// minutes ago" or "Failed: connection refused".
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SyncStatus {
+    pub last_synced_at: Option<u64>,
+    pub last_outcome: Option<SyncOutcome>,
+}
+
+/// Decides when each folder is due for a sync: [`INBOX_FOLDER`] on a
+/// short interval, every other folder on a longer one by default,
+/// either overridable per folder with [`SyncScheduler::set_interval`].
+/// Doesn't talk to the server itself — the caller still drives the
+/// actual IMAP session — this is just the timing and status-tracking
+/// contract it syncs against.
+#[derive(Debug, Default)]
+pub struct SyncScheduler {
+    interval_overrides: HashMap<String, u64>,
+    status: HashMap<String, SyncStatus>,
+}
+
+impl SyncScheduler {
+    pub fn set_interval(&mut self, folder: impl Into<String>, interval_secs: u64) {
+        self.interval_overrides.insert(folder.into(), interval_secs);
+    }
+
+    /// The folder's configured interval, before backoff: an override if
+    /// one was set, otherwise [`INBOX_FOLDER`]'s short default or the
+    /// longer default for everything else.
+    pub fn interval_for(&self, folder: &str) -> u64 {
+        self.interval_overrides.get(folder).copied().unwrap_or(if folder == INBOX_FOLDER {
+            INBOX_INTERVAL_SECS
+        } else {
+            DEFAULT_FOLDER_INTERVAL_SECS
+        })
+    }
+
+    pub fn status(&self, folder: &str) -> SyncStatus {
+        self.status.get(folder).cloned().unwrap_or_default()
+    }
+
+    /// Records the result of a sync attempt, whether it ran on schedule
+    /// or was triggered by [`SyncScheduler::sync_now`].
+    pub fn record_result(&mut self, folder: &str, now: u64, outcome: SyncOutcome) {
+        self.status.insert(folder.to_string(), SyncStatus { last_synced_at: Some(now), last_outcome: Some(outcome) });
+    }
+
+    /// Whether `folder` is due for a scheduled sync: never synced, or
+    /// its interval (backed off for `constraints`) has elapsed since
+    /// the last attempt.
+    pub fn is_due(&self, folder: &str, now: u64, constraints: ConnectionConstraints) -> bool {
+        let Some(last_synced_at) = self.status(folder).last_synced_at else {
+            return true;
+        };
+        let interval = self.interval_for(folder) * constraints.backoff_multiplier();
+        now.saturating_sub(last_synced_at) >= interval
+    }
+
+    /// The UI's "sync now" button: always allowed, bypassing the
+    /// interval and backoff entirely, since the user explicitly asked
+    /// for this folder to sync right away.
+    pub fn sync_now(&self, _folder: &str) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inbox_has_a_shorter_default_interval_than_other_folders() {
+        let scheduler = SyncScheduler::default();
+        assert!(scheduler.interval_for(INBOX_FOLDER) < scheduler.interval_for("Archive"));
+    }
+
+    #[test]
+    fn an_override_replaces_the_default_interval() {
+        let mut scheduler = SyncScheduler::default();
+        scheduler.set_interval("Archive", 30);
+        assert_eq!(scheduler.interval_for("Archive"), 30);
+    }
+
+    #[test]
+    fn a_never_synced_folder_is_always_due() {
+        let scheduler = SyncScheduler::default();
+        assert!(scheduler.is_due(INBOX_FOLDER, 1_000, ConnectionConstraints::default()));
+    }
+
+    #[test]
+    fn a_folder_is_not_due_until_its_interval_elapses() {
+        let mut scheduler = SyncScheduler::default();
+        scheduler.record_result(INBOX_FOLDER, 1_000, SyncOutcome::Success);
+
+        assert!(!scheduler.is_due(INBOX_FOLDER, 1_030, ConnectionConstraints::default()));
+        assert!(scheduler.is_due(INBOX_FOLDER, 1_060, ConnectionConstraints::default()));
+    }
+
+    #[test]
+    fn metered_connections_back_off_more_than_being_on_battery() {
+        let mut scheduler = SyncScheduler::default();
+        scheduler.record_result(INBOX_FOLDER, 1_000, SyncOutcome::Success);
+
+        assert!(!scheduler.is_due(INBOX_FOLDER, 1_060, ConnectionConstraints { on_battery: true, metered: false }));
+        assert!(!scheduler.is_due(INBOX_FOLDER, 1_060, ConnectionConstraints { on_battery: false, metered: true }));
+        assert!(scheduler.is_due(INBOX_FOLDER, 1_060 + INBOX_INTERVAL_SECS, ConnectionConstraints { on_battery: true, metered: false }));
+    }
+
+    #[test]
+    fn record_result_updates_the_status_the_ui_reads() {
+        let mut scheduler = SyncScheduler::default();
+        scheduler.record_result(INBOX_FOLDER, 1_000, SyncOutcome::Failed { reason: "timed out".to_string() });
+
+        let status = scheduler.status(INBOX_FOLDER);
+        assert_eq!(status.last_synced_at, Some(1_000));
+        assert_eq!(status.last_outcome, Some(SyncOutcome::Failed { reason: "timed out".to_string() }));
+    }
+
+    #[test]
+    fn sync_now_is_always_allowed() {
+        let scheduler = SyncScheduler::default();
+        assert!(scheduler.sync_now(INBOX_FOLDER));
+    }
+}
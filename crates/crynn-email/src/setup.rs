@@ -0,0 +1,182 @@
+use serde::{Deserialize, Serialize};
+
+use crynn_error::EmailError;
+
+/// Which encryption wraps an IMAP/SMTP connection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SecurityMode {
+    Tls,
+    StartTls,
+    None,
+}
+
+/// How to reach one server: host, port, and [`SecurityMode`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ServerConfig {
+    pub host: String,
+    pub port: u16,
+    pub security: SecurityMode,
+}
+
+/// How this account authenticates once its servers are known.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum AuthMethod {
+    Password,
+    OAuth { provider: String },
+}
+
+/// A fully probed and verified account, ready to hand to this crate's
+/// sync side once one exists.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EmailConfig {
+    pub address: String,
+    pub imap: ServerConfig,
+    pub smtp: ServerConfig,
+    pub auth: AuthMethod,
+}
+
+/// Whether `host:port` accepts a connection under `security`. No real
+/// socket I/O happens in this crate — the same split as
+/// `crynn_network::SuggestionsTransport` — so [`setup_account`] takes a
+/// probe; a fake for tests, eventually a real one once this crate has
+/// a transport.
+pub trait PortProbe {
+    fn probe(&mut self, host: &str, port: u16, security: SecurityMode) -> bool;
+}
+
+/// Whether `password` authenticates `address` against `imap`. Same
+/// "decision, not the I/O" split as [`PortProbe`].
+pub trait CredentialVerifier {
+    fn verify(&mut self, address: &str, password: &str, imap: &ServerConfig) -> bool;
+}
+
+const IMAP_CANDIDATES: [(u16, SecurityMode); 2] = [(993, SecurityMode::Tls), (143, SecurityMode::StartTls)];
+const SMTP_CANDIDATES: [(u16, SecurityMode); 3] =
+    [(587, SecurityMode::StartTls), (465, SecurityMode::Tls), (25, SecurityMode::None)];
+
+/// Providers that require signing in through their own OAuth flow
+/// rather than a password, keyed by the address's domain.
+fn oauth_provider(domain: &str) -> Option<&'static str> {
+    match domain {
+        "gmail.com" | "googlemail.com" => Some("google"),
+        "outlook.com" | "hotmail.com" | "live.com" => Some("microsoft"),
+        _ => None,
+    }
+}
+
+/// Tries each candidate port/security combination against `host` in
+/// order, returning the first one [`PortProbe::probe`] accepts.
+fn probe_server(probe: &mut dyn PortProbe, host: &str, candidates: &[(u16, SecurityMode)]) -> Option<ServerConfig> {
+    candidates
+        .iter()
+        .find(|(port, security)| probe.probe(host, *port, *security))
+        .map(|(port, security)| ServerConfig { host: host.to_string(), port: *port, security: *security })
+}
+
+/// Guided account setup: given `address`/`password`, autoconfigures the
+/// IMAP/SMTP hosts from the address's domain, probes candidate ports
+/// and security modes to find ones that actually respond, detects
+/// providers that require OAuth before a password will work, and
+/// verifies the credentials against the server it found — returning a
+/// ready [`EmailConfig`] rather than guessed settings no one confirmed.
+pub fn setup_account(
+    address: &str,
+    password: &str,
+    probe: &mut dyn PortProbe,
+    verify: &mut dyn CredentialVerifier,
+) -> Result<EmailConfig, EmailError> {
+    let domain = address.split('@').nth(1).unwrap_or(address);
+
+    if let Some(provider) = oauth_provider(domain) {
+        return Err(EmailError::OAuthRequired { provider: provider.to_string() });
+    }
+
+    let imap_host = format!("imap.{domain}");
+    let smtp_host = format!("smtp.{domain}");
+
+    let imap = probe_server(probe, &imap_host, &IMAP_CANDIDATES)
+        .ok_or_else(|| EmailError::ServerUnreachable { host: imap_host.clone() })?;
+    let smtp = probe_server(probe, &smtp_host, &SMTP_CANDIDATES)
+        .ok_or_else(|| EmailError::ServerUnreachable { host: smtp_host.clone() })?;
+
+    if !verify.verify(address, password, &imap) {
+        return Err(EmailError::CredentialsRejected { account: address.to_string() });
+    }
+
+    Ok(EmailConfig { address: address.to_string(), imap, smtp, auth: AuthMethod::Password })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Accepts the first security mode tried for every host, like a
+    /// well-behaved server that speaks whatever it's asked on its
+    /// standard port.
+    struct AcceptFirst;
+
+    impl PortProbe for AcceptFirst {
+        fn probe(&mut self, _host: &str, port: u16, _security: SecurityMode) -> bool {
+            port == IMAP_CANDIDATES[0].0 || port == SMTP_CANDIDATES[0].0
+        }
+    }
+
+    struct AcceptPassword;
+
+    impl CredentialVerifier for AcceptPassword {
+        fn verify(&mut self, _address: &str, password: &str, _imap: &ServerConfig) -> bool {
+            password == "correct"
+        }
+    }
+
+    #[test]
+    fn setup_account_finds_servers_and_verifies_a_correct_password() {
+        let config = setup_account("alice@example.com", "correct", &mut AcceptFirst, &mut AcceptPassword).unwrap();
+
+        assert_eq!(config.imap, ServerConfig { host: "imap.example.com".to_string(), port: 993, security: SecurityMode::Tls });
+        assert_eq!(
+            config.smtp,
+            ServerConfig { host: "smtp.example.com".to_string(), port: 587, security: SecurityMode::StartTls }
+        );
+        assert_eq!(config.auth, AuthMethod::Password);
+    }
+
+    #[test]
+    fn setup_account_rejects_the_wrong_password() {
+        let err = setup_account("alice@example.com", "wrong", &mut AcceptFirst, &mut AcceptPassword).unwrap_err();
+        assert!(matches!(err, EmailError::CredentialsRejected { account } if account == "alice@example.com"));
+    }
+
+    #[test]
+    fn setup_account_reports_oauth_required_for_known_providers_before_probing() {
+        let err = setup_account("alice@gmail.com", "anything", &mut AcceptFirst, &mut AcceptPassword).unwrap_err();
+        assert!(matches!(err, EmailError::OAuthRequired { provider } if provider == "google"));
+    }
+
+    #[test]
+    fn setup_account_reports_an_unreachable_server_when_no_candidate_responds() {
+        struct RejectEverything;
+        impl PortProbe for RejectEverything {
+            fn probe(&mut self, _host: &str, _port: u16, _security: SecurityMode) -> bool {
+                false
+            }
+        }
+
+        let err = setup_account("alice@example.com", "correct", &mut RejectEverything, &mut AcceptPassword).unwrap_err();
+        assert!(matches!(err, EmailError::ServerUnreachable { host } if host == "imap.example.com"));
+    }
+
+    #[test]
+    fn probe_server_falls_back_to_the_next_candidate() {
+        struct OnlyStartTls;
+        impl PortProbe for OnlyStartTls {
+            fn probe(&mut self, _host: &str, port: u16, security: SecurityMode) -> bool {
+                port == 143 && security == SecurityMode::StartTls
+            }
+        }
+
+        let config = probe_server(&mut OnlyStartTls, "imap.example.com", &IMAP_CANDIDATES).unwrap();
+        assert_eq!(config.port, 143);
+        assert_eq!(config.security, SecurityMode::StartTls);
+    }
+}
@@ -0,0 +1,154 @@
+use std::collections::HashSet;
+
+use crynn_error::EmailError;
+
+use crate::classifier::NaiveBayesClassifier;
+
+/// Moves a message between folders on the account's sync side. A real
+/// implementation is an IMAP `MOVE`/`COPY`+`EXPUNGE`; until this crate
+/// has one, [`SpamFilter`] drives this trait the same way
+/// [`crate::compose`] would drive an SMTP sender once it exists.
+pub trait MailboxMover {
+    fn move_message(&mut self, uid: &str, to_folder: &str) -> Result<(), EmailError>;
+}
+
+/// Junk-mail handling for one account: the server's own spam folder
+/// name, the local allow/block lists learned from the user's
+/// mark-as-spam actions, and an optional [`NaiveBayesClassifier`] over
+/// cached headers/bodies for messages the server didn't already flag.
+#[derive(Default)]
+pub struct SpamFilter {
+    junk_folder: String,
+    inbox_folder: String,
+    allowed: HashSet<String>,
+    blocked: HashSet<String>,
+    classifier: Option<NaiveBayesClassifier>,
+}
+
+impl SpamFilter {
+    pub fn new(junk_folder: impl Into<String>, inbox_folder: impl Into<String>) -> Self {
+        Self { junk_folder: junk_folder.into(), inbox_folder: inbox_folder.into(), allowed: HashSet::new(), blocked: HashSet::new(), classifier: None }
+    }
+
+    /// Trains and enables the naive-Bayes classifier for messages the
+    /// server's own spam folder and the allow/block lists don't already
+    /// decide. Safe to call again later with more examples — replaces
+    /// whichever classifier was there before.
+    pub fn set_classifier(&mut self, classifier: NaiveBayesClassifier) {
+        self.classifier = Some(classifier);
+    }
+
+    pub fn is_allowed(&self, address: &str) -> bool {
+        self.allowed.contains(address)
+    }
+
+    pub fn is_blocked(&self, address: &str) -> bool {
+        self.blocked.contains(address)
+    }
+
+    /// Moves `uid` to the junk folder and learns `from_address` as
+    /// blocked, so future sync runs file it as spam without waiting on
+    /// the classifier.
+    pub fn mark_as_spam(&mut self, mover: &mut dyn MailboxMover, uid: &str, from_address: &str) -> Result<(), EmailError> {
+        self.blocked.insert(from_address.to_string());
+        self.allowed.remove(from_address);
+        mover.move_message(uid, &self.junk_folder)
+    }
+
+    /// Moves `uid` back to the inbox and learns `from_address` as
+    /// allowed, overriding both the block list and the classifier for
+    /// future messages from it.
+    pub fn not_spam(&mut self, mover: &mut dyn MailboxMover, uid: &str, from_address: &str) -> Result<(), EmailError> {
+        self.allowed.insert(from_address.to_string());
+        self.blocked.remove(from_address);
+        mover.move_message(uid, &self.inbox_folder)
+    }
+
+    /// Whether a message from `from_address` with `content` (its
+    /// cached header/body text) should be treated as spam during sync:
+    /// the allow list always wins, then the block list, then the
+    /// classifier if one is trained — a server spam-folder flag should
+    /// be applied by the caller before this even gets asked, since this
+    /// filter has no way to see that flag itself.
+    pub fn should_file_as_spam(&self, from_address: &str, content: &str) -> bool {
+        if self.is_allowed(from_address) {
+            return false;
+        }
+        if self.is_blocked(from_address) {
+            return true;
+        }
+        self.classifier.as_ref().map(|c| c.is_spam(content, 0.9)).unwrap_or(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default)]
+    struct RecordingMover {
+        moves: Vec<(String, String)>,
+    }
+
+    impl MailboxMover for RecordingMover {
+        fn move_message(&mut self, uid: &str, to_folder: &str) -> Result<(), EmailError> {
+            self.moves.push((uid.to_string(), to_folder.to_string()));
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn mark_as_spam_moves_the_message_and_blocks_the_sender() {
+        let mut filter = SpamFilter::new("Junk", "Inbox");
+        let mut mover = RecordingMover::default();
+
+        filter.mark_as_spam(&mut mover, "uid-1", "spammer@example.com").unwrap();
+
+        assert_eq!(mover.moves, vec![("uid-1".to_string(), "Junk".to_string())]);
+        assert!(filter.is_blocked("spammer@example.com"));
+    }
+
+    #[test]
+    fn not_spam_moves_the_message_back_and_allows_the_sender() {
+        let mut filter = SpamFilter::new("Junk", "Inbox");
+        let mut mover = RecordingMover::default();
+        filter.mark_as_spam(&mut mover, "uid-1", "friend@example.com").unwrap();
+
+        filter.not_spam(&mut mover, "uid-1", "friend@example.com").unwrap();
+
+        assert_eq!(mover.moves[1], ("uid-1".to_string(), "Inbox".to_string()));
+        assert!(filter.is_allowed("friend@example.com"));
+        assert!(!filter.is_blocked("friend@example.com"));
+    }
+
+    #[test]
+    fn should_file_as_spam_honors_the_allow_list_over_the_block_list() {
+        let mut filter = SpamFilter::new("Junk", "Inbox");
+        filter.allowed.insert("both@example.com".to_string());
+        filter.blocked.insert("both@example.com".to_string());
+
+        assert!(!filter.should_file_as_spam("both@example.com", "anything"));
+    }
+
+    #[test]
+    fn should_file_as_spam_falls_back_to_the_classifier_when_trained() {
+        let mut filter = SpamFilter::new("Junk", "Inbox");
+        let mut classifier = NaiveBayesClassifier::default();
+        for _ in 0..20 {
+            classifier.train("free viagra cheap pills buy now", true);
+        }
+        for _ in 0..20 {
+            classifier.train("meeting notes attached from yesterday", false);
+        }
+        filter.set_classifier(classifier);
+
+        assert!(filter.should_file_as_spam("unknown@example.com", "free viagra cheap pills"));
+        assert!(!filter.should_file_as_spam("unknown@example.com", "meeting notes attached"));
+    }
+
+    #[test]
+    fn should_file_as_spam_defaults_to_false_without_a_classifier_or_a_listing() {
+        let filter = SpamFilter::new("Junk", "Inbox");
+        assert!(!filter.should_file_as_spam("unknown@example.com", "anything"));
+    }
+}
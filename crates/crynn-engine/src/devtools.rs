@@ -0,0 +1,115 @@
+use crate::tab::TabId;
+use crate::TabRegistry;
+
+/// How the embedding engine exposes developer tools for a tab: a full
+/// devtools window wired over the remote protocol when Crynn is backed
+/// by real Firefox (BiDi/CDP), or a minimal remote-debugging toggle when
+/// it's bound directly to Gecko through the FFI layer instead. A real
+/// binding implements whichever method matches its mode and leaves the
+/// other as a no-op.
+pub trait DevtoolsLauncher {
+    /// Opens the full devtools window connected to `tab`.
+    fn open_devtools_window(&mut self, tab: TabId) -> std::io::Result<()>;
+
+    /// Flips remote debugging for `tab` on or off.
+    fn set_remote_debugging(&mut self, tab: TabId, enabled: bool) -> std::io::Result<()>;
+}
+
+/// Which of [`DevtoolsLauncher`]'s two methods a binding actually backs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DevtoolsMode {
+    /// A full devtools window over BiDi/CDP.
+    RemoteProtocol,
+    /// A remote-debugging on/off toggle.
+    FfiToggle,
+}
+
+/// Opens devtools for `tab`, recording the open state on the tab itself
+/// so the shell can render an "open" indicator without having to ask the
+/// launcher. Calling this on a tab that doesn't exist is a no-op, the
+/// same as [`TabRegistry::unload`] on a missing id.
+pub fn open_devtools(engine: &mut TabRegistry, tab: TabId, mode: DevtoolsMode, launcher: &mut dyn DevtoolsLauncher) -> std::io::Result<()> {
+    match mode {
+        DevtoolsMode::RemoteProtocol => launcher.open_devtools_window(tab)?,
+        DevtoolsMode::FfiToggle => launcher.set_remote_debugging(tab, true)?,
+    }
+    if let Some(tab) = engine.get_mut(tab) {
+        tab.set_devtools_open(true);
+    }
+    Ok(())
+}
+
+/// Closes devtools for `tab`. Only meaningful in [`DevtoolsMode::FfiToggle`]
+/// mode, since a BiDi/CDP devtools window is closed by the user from its
+/// own window rather than from here.
+pub fn close_devtools(engine: &mut TabRegistry, tab: TabId, mode: DevtoolsMode, launcher: &mut dyn DevtoolsLauncher) -> std::io::Result<()> {
+    if mode == DevtoolsMode::FfiToggle {
+        launcher.set_remote_debugging(tab, false)?;
+    }
+    if let Some(tab) = engine.get_mut(tab) {
+        tab.set_devtools_open(false);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default)]
+    struct RecordingLauncher {
+        opened_window: Vec<TabId>,
+        remote_debugging: Vec<(TabId, bool)>,
+    }
+
+    impl DevtoolsLauncher for RecordingLauncher {
+        fn open_devtools_window(&mut self, tab: TabId) -> std::io::Result<()> {
+            self.opened_window.push(tab);
+            Ok(())
+        }
+
+        fn set_remote_debugging(&mut self, tab: TabId, enabled: bool) -> std::io::Result<()> {
+            self.remote_debugging.push((tab, enabled));
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn remote_protocol_mode_opens_a_devtools_window_and_marks_the_tab() {
+        let mut engine = TabRegistry::new();
+        let tab = engine.open("https://example.com", "Example");
+        let mut launcher = RecordingLauncher::default();
+
+        open_devtools(&mut engine, tab, DevtoolsMode::RemoteProtocol, &mut launcher).unwrap();
+
+        assert_eq!(launcher.opened_window, vec![tab]);
+        assert!(launcher.remote_debugging.is_empty());
+        assert!(engine.get(tab).unwrap().is_devtools_open());
+    }
+
+    #[test]
+    fn ffi_toggle_mode_flips_remote_debugging_on_then_off() {
+        let mut engine = TabRegistry::new();
+        let tab = engine.open("https://example.com", "Example");
+        let mut launcher = RecordingLauncher::default();
+
+        open_devtools(&mut engine, tab, DevtoolsMode::FfiToggle, &mut launcher).unwrap();
+        assert_eq!(launcher.remote_debugging, vec![(tab, true)]);
+        assert!(engine.get(tab).unwrap().is_devtools_open());
+
+        close_devtools(&mut engine, tab, DevtoolsMode::FfiToggle, &mut launcher).unwrap();
+        assert_eq!(launcher.remote_debugging, vec![(tab, true), (tab, false)]);
+        assert!(!engine.get(tab).unwrap().is_devtools_open());
+    }
+
+    #[test]
+    fn opening_devtools_for_a_missing_tab_is_a_no_op() {
+        let mut engine = TabRegistry::new();
+        let tab = engine.open("https://example.com", "Example");
+        engine.close(tab);
+        let mut launcher = RecordingLauncher::default();
+
+        assert!(open_devtools(&mut engine, tab, DevtoolsMode::RemoteProtocol, &mut launcher).is_ok());
+        assert_eq!(launcher.opened_window, vec![tab]);
+    }
+}
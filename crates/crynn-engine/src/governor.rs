@@ -0,0 +1,230 @@
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
+
+use crate::profiler::{ComponentKind, ProfilerSnapshot};
+use crate::tab::{Tab, TabId};
+
+/// Memory and CPU ceilings a tab's content is expected to stay under.
+/// Exceeding either for long enough is what [`ResourceGovernor::evaluate`]
+/// throttles or suspends a tab for.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ResourceLimits {
+    pub max_memory_bytes: u64,
+    pub max_cpu_percent: f32,
+}
+
+impl Default for ResourceLimits {
+    fn default() -> Self {
+        Self { max_memory_bytes: DEFAULT_MAX_MEMORY_BYTES, max_cpu_percent: DEFAULT_MAX_CPU_PERCENT }
+    }
+}
+
+pub const DEFAULT_MAX_MEMORY_BYTES: u64 = 512 * 1024 * 1024;
+pub const DEFAULT_MAX_CPU_PERCENT: f32 = 80.0;
+/// How long a tab has to stay over [`ResourceLimits`] before
+/// [`ResourceGovernor::evaluate`] recommends throttling it — long enough
+/// that a heavy page's initial load doesn't get penalized for a brief
+/// spike.
+pub const DEFAULT_THROTTLE_AFTER: Duration = Duration::from_secs(30);
+/// How long over threshold escalates a verdict from
+/// [`GovernorAction::Throttle`] to [`GovernorAction::Suspend`].
+pub const DEFAULT_SUSPEND_AFTER: Duration = Duration::from_secs(120);
+
+/// What [`ResourceGovernor::evaluate`] recommends for a tab that's
+/// overshot its [`ResourceLimits`] for long enough — throttling first,
+/// escalating to suspending (the same discard
+/// [`crate::TabRegistry::unload`] performs for a manually unloaded tab)
+/// if it still hasn't come back down.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GovernorAction {
+    Throttle,
+    Suspend,
+}
+
+/// One tab [`ResourceGovernor::evaluate`] flagged, for the shell to carry
+/// out and tell the user about.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GovernorVerdict {
+    pub tab: TabId,
+    pub origin: String,
+    pub action: GovernorAction,
+}
+
+/// Watches each tab's [`ProfilerSnapshot`] entry against
+/// [`ResourceLimits`], escalating from [`GovernorAction::Throttle`] to
+/// [`GovernorAction::Suspend`] only once a tab has stayed over threshold
+/// continuously — real throttling of a page's main thread is engine-hook
+/// work this crate doesn't do, the same gap [`crate::EnginePrefs`] leaves
+/// for applying a preference; this crate only decides when to and hands
+/// the decision to whatever drives a real engine binding.
+#[derive(Debug)]
+pub struct ResourceGovernor {
+    limits: ResourceLimits,
+    throttle_after: Duration,
+    suspend_after: Duration,
+    breach_since: HashMap<TabId, Instant>,
+    whitelist: HashSet<String>,
+}
+
+impl ResourceGovernor {
+    pub fn new(limits: ResourceLimits, throttle_after: Duration, suspend_after: Duration) -> Self {
+        Self { limits, throttle_after, suspend_after, breach_since: HashMap::new(), whitelist: HashSet::new() }
+    }
+
+    /// Exempts `origin` from future verdicts — the shell notification's
+    /// "whitelist this site" response.
+    pub fn whitelist(&mut self, origin: impl Into<String>) {
+        self.whitelist.insert(origin.into());
+    }
+
+    pub fn is_whitelisted(&self, origin: &str) -> bool {
+        self.whitelist.contains(origin)
+    }
+
+    /// Checks every tab in `tabs` against `snapshot`'s per-tab metrics,
+    /// returning a verdict for each one that's been over threshold
+    /// continuously for at least `throttle_after` (or `suspend_after`,
+    /// which escalates the verdict to [`GovernorAction::Suspend`]). A tab
+    /// back under threshold, or whose origin is whitelisted, has its
+    /// breach timer cleared instead.
+    pub fn evaluate<'a>(&mut self, snapshot: &ProfilerSnapshot, tabs: impl Iterator<Item = &'a Tab>, now: Instant) -> Vec<GovernorVerdict> {
+        let mut verdicts = Vec::new();
+        for tab in tabs {
+            let origin = origin_of(tab.url());
+            if self.whitelist.contains(&origin) {
+                self.breach_since.remove(&tab.id());
+                continue;
+            }
+
+            let over_threshold = snapshot.components.iter().any(|component| {
+                matches!(component.kind, ComponentKind::Tab(id) if id == tab.id())
+                    && (component.memory_bytes > self.limits.max_memory_bytes || component.cpu_percent > self.limits.max_cpu_percent)
+            });
+            if !over_threshold {
+                self.breach_since.remove(&tab.id());
+                continue;
+            }
+
+            let started = *self.breach_since.entry(tab.id()).or_insert(now);
+            let sustained_for = now.duration_since(started);
+            let action = if sustained_for >= self.suspend_after {
+                GovernorAction::Suspend
+            } else if sustained_for >= self.throttle_after {
+                GovernorAction::Throttle
+            } else {
+                continue;
+            };
+            verdicts.push(GovernorVerdict { tab: tab.id(), origin, action });
+        }
+        verdicts
+    }
+}
+
+impl Default for ResourceGovernor {
+    fn default() -> Self {
+        Self::new(ResourceLimits::default(), DEFAULT_THROTTLE_AFTER, DEFAULT_SUSPEND_AFTER)
+    }
+}
+
+/// Same scheme-plus-host extraction `crynn_network::preheat`'s own
+/// `origin_of` duplicates rather than sharing across crates for one
+/// helper this small.
+fn origin_of(url: &str) -> String {
+    url.split_once("://").map(|(scheme, rest)| format!("{scheme}://{}", rest.split('/').next().unwrap_or(rest))).unwrap_or_else(|| url.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TabRegistry;
+
+    fn snapshot_with(tab: TabId, memory_bytes: u64, cpu_percent: f32) -> ProfilerSnapshot {
+        ProfilerSnapshot {
+            components: vec![crate::profiler::ComponentMetrics { kind: ComponentKind::Tab(tab), label: String::new(), memory_bytes, cpu_percent }],
+        }
+    }
+
+    #[test]
+    fn a_tab_under_threshold_produces_no_verdict() {
+        let mut registry = TabRegistry::new();
+        let id = registry.open("https://example.com", "Example");
+        let mut governor = ResourceGovernor::default();
+        let snapshot = snapshot_with(id, 10, 1.0);
+
+        let verdicts = governor.evaluate(&snapshot, registry.iter(), Instant::now());
+
+        assert!(verdicts.is_empty());
+    }
+
+    #[test]
+    fn a_brief_spike_is_not_enough_to_throttle() {
+        let mut registry = TabRegistry::new();
+        let id = registry.open("https://example.com", "Example");
+        let mut governor = ResourceGovernor::default();
+        let snapshot = snapshot_with(id, DEFAULT_MAX_MEMORY_BYTES + 1, 1.0);
+
+        let verdicts = governor.evaluate(&snapshot, registry.iter(), Instant::now());
+
+        assert!(verdicts.is_empty());
+    }
+
+    #[test]
+    fn a_sustained_breach_is_throttled() {
+        let mut registry = TabRegistry::new();
+        let id = registry.open("https://example.com", "Example");
+        let mut governor = ResourceGovernor::default();
+        let snapshot = snapshot_with(id, DEFAULT_MAX_MEMORY_BYTES + 1, 1.0);
+        let t0 = Instant::now();
+
+        governor.evaluate(&snapshot, registry.iter(), t0);
+        let verdicts = governor.evaluate(&snapshot, registry.iter(), t0 + DEFAULT_THROTTLE_AFTER);
+
+        assert_eq!(verdicts, vec![GovernorVerdict { tab: id, origin: "https://example.com".to_string(), action: GovernorAction::Throttle }]);
+    }
+
+    #[test]
+    fn a_much_longer_breach_escalates_to_suspend() {
+        let mut registry = TabRegistry::new();
+        let id = registry.open("https://example.com", "Example");
+        let mut governor = ResourceGovernor::default();
+        let snapshot = snapshot_with(id, DEFAULT_MAX_MEMORY_BYTES + 1, 1.0);
+        let t0 = Instant::now();
+
+        governor.evaluate(&snapshot, registry.iter(), t0);
+        let verdicts = governor.evaluate(&snapshot, registry.iter(), t0 + DEFAULT_SUSPEND_AFTER);
+
+        assert_eq!(verdicts, vec![GovernorVerdict { tab: id, origin: "https://example.com".to_string(), action: GovernorAction::Suspend }]);
+    }
+
+    #[test]
+    fn dropping_back_under_threshold_resets_the_breach_timer() {
+        let mut registry = TabRegistry::new();
+        let id = registry.open("https://example.com", "Example");
+        let mut governor = ResourceGovernor::default();
+        let over = snapshot_with(id, DEFAULT_MAX_MEMORY_BYTES + 1, 1.0);
+        let under = snapshot_with(id, 10, 1.0);
+        let t0 = Instant::now();
+
+        governor.evaluate(&over, registry.iter(), t0);
+        governor.evaluate(&under, registry.iter(), t0 + Duration::from_secs(1));
+        let verdicts = governor.evaluate(&over, registry.iter(), t0 + DEFAULT_THROTTLE_AFTER);
+
+        assert!(verdicts.is_empty());
+    }
+
+    #[test]
+    fn a_whitelisted_origin_is_never_flagged() {
+        let mut registry = TabRegistry::new();
+        let id = registry.open("https://example.com", "Example");
+        let mut governor = ResourceGovernor::default();
+        governor.whitelist("https://example.com");
+        let snapshot = snapshot_with(id, DEFAULT_MAX_MEMORY_BYTES + 1, 1.0);
+        let t0 = Instant::now();
+
+        governor.evaluate(&snapshot, registry.iter(), t0);
+        let verdicts = governor.evaluate(&snapshot, registry.iter(), t0 + DEFAULT_SUSPEND_AFTER);
+
+        assert!(verdicts.is_empty());
+        assert!(governor.is_whitelisted("https://example.com"));
+    }
+}
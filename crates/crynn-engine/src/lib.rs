@@ -0,0 +1,297 @@
+//! Engine abstraction shared by the shell: tabs, navigation state, the
+//! per-component profiler that backs the task-manager view, and the
+//! [`EnginePrefs`] seam a real engine's FFI wrapper implements so this
+//! crate's per-site preference decisions ([`SitePrefStore`]) reach it
+//! without this crate needing to know how.
+//!
+//! There is exactly one embedding path today: [`TabRegistry`] is the
+//! in-memory stand-in every shell feature is written against directly,
+//! with [`EnginePrefs`] as the only seam a real engine implements. No
+//! second, divergent backend exists yet to share a trait with — when one
+//! does, it's [`EnginePrefs`]'s seam that should grow, rather than
+//! introducing a parallel abstraction before there's a second
+//! implementation to justify it.
+//!
+//! [`ResourceGovernor`] reads the same per-tab [`ComponentMetrics`] the
+//! task-manager view does and decides when a tab's content has overshot
+//! its [`ResourceLimits`] for long enough to throttle or suspend, without
+//! an engine hook of its own to carry either out — [`GovernorVerdict`] is
+//! the decision, left for the shell to act on and the user to answer
+//! with a kill or a [`ResourceGovernor::whitelist`].
+
+mod devtools;
+mod governor;
+mod prefs;
+mod profiler;
+mod push;
+mod session;
+mod tab;
+mod tab_group;
+
+pub use devtools::{close_devtools, open_devtools, DevtoolsLauncher, DevtoolsMode};
+pub use governor::{
+    GovernorAction, GovernorVerdict, ResourceGovernor, ResourceLimits, DEFAULT_MAX_CPU_PERCENT, DEFAULT_MAX_MEMORY_BYTES,
+    DEFAULT_SUSPEND_AFTER, DEFAULT_THROTTLE_AFTER,
+};
+pub use prefs::{
+    autoplay_policy_pref, AutoplayPolicy, EnginePrefs, PrefValue, SitePrefStore, PREF_ALWAYS_TRANSLATE, PREF_AUTOPLAY_POLICY,
+    PREF_FORCE_DARK_MODE, PREF_GLOBAL_MUTE, PREF_IMAGES_ENABLED, PREF_JAVASCRIPT_ENABLED, PREF_MUTE_BACKGROUND_TABS,
+    PREF_POPUPS_ENABLED, PREF_RESIST_FINGERPRINTING, PREF_SPELLCHECK_ENABLED,
+};
+pub use profiler::{ComponentKind, ComponentMetrics, ProfilerSnapshot};
+pub use push::{PushInbox, PushMessage};
+pub use session::{GroupSnapshot, SessionSnapshot, TabSnapshot};
+pub use tab::{
+    RequestTimings, Tab, TabId, DEFAULT_SCROLL_Y, DEFAULT_TEXT_SIZE, DEFAULT_VOLUME, DEFAULT_ZOOM, MAX_VOLUME, MAX_ZOOM,
+    MIN_VOLUME, MIN_ZOOM,
+};
+pub use tab_group::{GroupColor, TabGroup, TabGroupId, TabGroupRegistry};
+
+use std::collections::BTreeMap;
+
+/// In-memory registry of open tabs. This is the engine-side source of truth
+/// that the shell's UI renders from; it does not itself talk to a real
+/// rendering engine, but stands in for one.
+#[derive(Debug, Default)]
+pub struct TabRegistry {
+    tabs: BTreeMap<TabId, Tab>,
+    next_id: u64,
+    push_inbox: PushInbox,
+    groups: TabGroupRegistry,
+}
+
+impl TabRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Opens a new tab and returns its id.
+    pub fn open(&mut self, url: impl Into<String>, title: impl Into<String>) -> TabId {
+        let id = TabId(self.next_id);
+        self.next_id += 1;
+        let url = url.into();
+        tracing::debug!(tab = id.0, %url, "opened tab");
+        self.tabs.insert(id, Tab::new(id, url, title));
+        id
+    }
+
+    pub fn get(&self, id: TabId) -> Option<&Tab> {
+        self.tabs.get(&id)
+    }
+
+    pub fn get_mut(&mut self, id: TabId) -> Option<&mut Tab> {
+        self.tabs.get_mut(&id)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Tab> {
+        self.tabs.values()
+    }
+
+    pub fn len(&self) -> usize {
+        self.tabs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tabs.is_empty()
+    }
+
+    /// Closes the tab outright, dropping its engine-side state.
+    pub fn close(&mut self, id: TabId) -> Option<Tab> {
+        tracing::debug!(tab = id.0, "closed tab");
+        self.tabs.remove(&id)
+    }
+
+    /// Which proxy a request from `id` should be routed through: the
+    /// tab's own override if it has one, otherwise its group's proxy,
+    /// otherwise [`crynn_network::ProxyConfig::Direct`] for a tab in no
+    /// group at all. `None` for a tab id that doesn't exist.
+    pub fn effective_proxy(&self, id: TabId) -> Option<crynn_network::ProxyConfig> {
+        let tab = self.tabs.get(&id)?;
+        if let Some(proxy) = tab.proxy_override() {
+            return Some(proxy.clone());
+        }
+        Some(tab.group_id().and_then(|group_id| self.groups.get(group_id)).map(|g| g.proxy().clone()).unwrap_or_default())
+    }
+
+    /// Discards the tab's rendering state while keeping it in the tab strip,
+    /// so it reloads from its last URL the next time it is activated.
+    pub fn unload(&mut self, id: TabId) -> bool {
+        match self.tabs.get_mut(&id) {
+            Some(tab) => {
+                tracing::debug!(tab = id.0, "unloaded tab");
+                tab.unload();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Builds a profiler snapshot across every open tab plus the shared
+    /// subsystems (network, storage, renderer), for the task-manager view.
+    pub fn profiler_snapshot(&self) -> ProfilerSnapshot {
+        ProfilerSnapshot::capture(self.tabs.values())
+    }
+
+    /// Entry point for the web-push bridge: the embedding engine calls
+    /// this when a subscribed site's push message arrives.
+    pub fn receive_push(&mut self, message: PushMessage) {
+        self.push_inbox.receive(message);
+    }
+
+    /// Polled by the shell once per frame to pick up any push messages
+    /// received since the last call.
+    pub fn drain_push_messages(&mut self) -> Vec<PushMessage> {
+        self.push_inbox.drain()
+    }
+
+    pub fn groups(&self) -> &TabGroupRegistry {
+        &self.groups
+    }
+
+    pub fn create_group(&mut self, name: impl Into<String>, color: GroupColor) -> TabGroupId {
+        self.groups.create(name, color)
+    }
+
+    /// Removes the group and clears it from every tab that belonged to it,
+    /// so no tab is left pointing at a group id that no longer exists.
+    pub fn remove_group(&mut self, id: TabGroupId) -> Option<TabGroup> {
+        let removed = self.groups.remove(id)?;
+        for tab in self.tabs.values_mut() {
+            if tab.group_id() == Some(id) {
+                tab.set_group(None);
+            }
+        }
+        Some(removed)
+    }
+
+    pub fn set_group_collapsed(&mut self, id: TabGroupId, collapsed: bool) {
+        if let Some(group) = self.groups.get_mut(id) {
+            group.set_collapsed(collapsed);
+        }
+    }
+
+    pub fn rename_group(&mut self, id: TabGroupId, name: impl Into<String>) {
+        if let Some(group) = self.groups.get_mut(id) {
+            group.set_name(name);
+        }
+    }
+
+    pub fn set_group_color(&mut self, id: TabGroupId, color: GroupColor) {
+        if let Some(group) = self.groups.get_mut(id) {
+            group.set_color(color);
+        }
+    }
+
+    /// Moves `tab_id` into `group_id`. A `group_id` that doesn't exist in
+    /// this registry is a no-op, same as closing a tab that's already
+    /// gone.
+    pub fn assign_tab_to_group(&mut self, tab_id: TabId, group_id: TabGroupId) {
+        if self.groups.get(group_id).is_none() {
+            return;
+        }
+        if let Some(tab) = self.tabs.get_mut(&tab_id) {
+            tab.set_group(Some(group_id));
+        }
+    }
+
+    pub fn remove_tab_from_group(&mut self, tab_id: TabId) {
+        if let Some(tab) = self.tabs.get_mut(&tab_id) {
+            tab.set_group(None);
+        }
+    }
+
+    /// Captures every open tab and group so the shell can persist them and
+    /// restore this registry's layout in a future session.
+    pub fn snapshot(&self) -> SessionSnapshot {
+        let groups: Vec<GroupSnapshot> = self
+            .groups
+            .iter()
+            .map(|g| GroupSnapshot { name: g.name().to_string(), color: g.color(), collapsed: g.is_collapsed() })
+            .collect();
+        let tabs: Vec<TabSnapshot> = self
+            .tabs
+            .values()
+            .map(|tab| TabSnapshot {
+                url: tab.url().to_string(),
+                title: tab.title().to_string(),
+                group: tab.group_id().and_then(|id| self.groups.get(id)).map(|g| g.name().to_string()),
+            })
+            .collect();
+        SessionSnapshot { tabs, groups }
+    }
+
+    /// Rebuilds a registry from a previously captured [`SessionSnapshot`],
+    /// recreating groups first so every tab can rejoin the one it was in
+    /// by name.
+    pub fn restore(snapshot: SessionSnapshot) -> Self {
+        let mut registry = Self::new();
+        for group in snapshot.groups {
+            let id = registry.create_group(group.name, group.color);
+            registry.set_group_collapsed(id, group.collapsed);
+        }
+        for tab in snapshot.tabs {
+            let id = registry.open(tab.url, tab.title);
+            if let Some(name) = tab.group.as_deref() {
+                if let Some(group_id) = registry.groups.find_by_name(name).map(|g| g.id()) {
+                    registry.assign_tab_to_group(id, group_id);
+                }
+            }
+        }
+        registry
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn open_close_round_trip() {
+        let mut reg = TabRegistry::new();
+        let id = reg.open("https://example.com", "Example");
+        assert_eq!(reg.len(), 1);
+        assert!(reg.get(id).is_some());
+        assert!(reg.close(id).is_some());
+        assert!(reg.is_empty());
+    }
+
+    #[test]
+    fn unload_marks_tab_discarded_without_closing_it() {
+        let mut reg = TabRegistry::new();
+        let id = reg.open("https://example.com", "Example");
+        assert!(reg.unload(id));
+        assert!(reg.get(id).unwrap().is_discarded());
+        assert_eq!(reg.len(), 1);
+    }
+
+    #[test]
+    fn removing_a_group_clears_it_from_member_tabs() {
+        let mut reg = TabRegistry::new();
+        let tab = reg.open("https://example.com", "Example");
+        let group = reg.create_group("Work", GroupColor::rgb(10, 20, 30));
+        reg.assign_tab_to_group(tab, group);
+        assert_eq!(reg.get(tab).unwrap().group_id(), Some(group));
+
+        reg.remove_group(group);
+
+        assert_eq!(reg.get(tab).unwrap().group_id(), None);
+    }
+
+    #[test]
+    fn snapshot_then_restore_rejoins_tabs_to_their_named_group() {
+        let mut reg = TabRegistry::new();
+        let group = reg.create_group("Work", GroupColor::rgb(10, 20, 30));
+        reg.set_group_collapsed(group, true);
+        let tab = reg.open("https://example.com", "Example");
+        reg.assign_tab_to_group(tab, group);
+
+        let restored = TabRegistry::restore(reg.snapshot());
+
+        assert_eq!(restored.len(), 1);
+        let restored_tab = restored.iter().next().unwrap();
+        let restored_group = restored.groups().iter().next().unwrap();
+        assert_eq!(restored_tab.group_id(), Some(restored_group.id()));
+        assert!(restored_group.is_collapsed());
+        assert_eq!(restored_group.name(), "Work");
+    }
+}
@@ -0,0 +1,261 @@
+use std::collections::HashMap;
+
+/// A value an engine preference can hold. Mirrors the two shapes Gecko's
+/// own preference service uses for the prefs this crate knows about —
+/// `Bool` for a simple toggle, `Str` for an enumerated setting like an
+/// autoplay policy where the values are named rather than boolean.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PrefValue {
+    Bool(bool),
+    Str(String),
+}
+
+/// Whether scripts run at all on a page. Per-tab because the shell's
+/// "disable JavaScript for this tab" developer tool only needs to affect
+/// the tab it was toggled from.
+pub const PREF_JAVASCRIPT_ENABLED: &str = "javascript.enabled";
+/// Gecko's umbrella anti-fingerprinting pref: reduced-precision timers,
+/// spoofed `navigator`/screen values, and the rest of what
+/// `resistFingerprinting` bundles together.
+pub const PREF_RESIST_FINGERPRINTING: &str = "privacy.resistFingerprinting";
+/// How autoplay is handled; see [`AutoplayPolicy`] for the values this
+/// crate's own code sets, though [`EnginePrefs::set_pref`] accepts any
+/// string an embedder's build supports.
+pub const PREF_AUTOPLAY_POLICY: &str = "media.autoplay.default";
+/// Whether images load on a page. Real Gecko's `permissions.default.image`
+/// is a three-way allow/block/block-cross-origin permission; this crate
+/// simplifies it to a toggle, the same simplification
+/// [`PREF_JAVASCRIPT_ENABLED`] already makes for script execution.
+pub const PREF_IMAGES_ENABLED: &str = "permissions.default.image";
+/// Whether a `window.open` not triggered by direct user input is allowed
+/// to succeed for a site. The popup blocker's allow-once/allow-always UI
+/// sets this per origin; it doesn't replace the engine's own heuristic
+/// for telling a user click from a script-initiated open.
+pub const PREF_POPUPS_ENABLED: &str = "dom.popups.enabled";
+/// Whether a page's styles are overridden with a color-inversion stylesheet
+/// so it renders dark regardless of what the page itself requests. Per-site
+/// because some pages already have their own correct dark theme, or invert
+/// badly (maps, photo galleries); see [`crate::SitePrefStore`] for how a
+/// site opts out while the shell theme is dark overall.
+pub const PREF_FORCE_DARK_MODE: &str = "layout.forceDarkMode";
+/// Whether text inputs and the email compose view run underline spelling
+/// through a `crynn_spellcheck::SpellChecker`. Matches Gecko's own
+/// `layout.spellcheckDefault` pref; unlike [`PREF_JAVASCRIPT_ENABLED`] and
+/// the rest of this file's per-origin toggles, it's an instance-global
+/// setting, not something a site can override through [`SitePrefStore`].
+pub const PREF_SPELLCHECK_ENABLED: &str = "layout.spellcheckDefault";
+/// Whether the translate bar's "always translate this site" checkbox is
+/// set for the current origin. Unlike [`PREF_RESIST_FINGERPRINTING`],
+/// there's no instance-global default this overrides — translation is
+/// always off until a site opts in, through this pref or a one-off
+/// translate-bar click that doesn't set it.
+pub const PREF_ALWAYS_TRANSLATE: &str = "translation.alwaysTranslate";
+/// Whether playback across the whole instance is silenced regardless of any
+/// per-tab volume or mute state — the global mute toggle's own pref, not
+/// scoped per origin the way [`PREF_AUTOPLAY_POLICY`] is.
+pub const PREF_GLOBAL_MUTE: &str = "media.globalMute";
+/// Whether a background tab (not the one currently focused) is
+/// automatically muted while it stays in the background. Instance-global
+/// like [`PREF_SPELLCHECK_ENABLED`], not something a single site opts out
+/// of through [`SitePrefStore`].
+pub const PREF_MUTE_BACKGROUND_TABS: &str = "media.muteBackgroundTabs";
+
+/// The autoplay values the per-site settings panel offers. Stored as
+/// [`PrefValue::Str`] since that's the shape Gecko's own
+/// `media.autoplay.default` pref takes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AutoplayPolicy {
+    Allowed,
+    BlockAudible,
+    BlockAll,
+}
+
+impl Default for AutoplayPolicy {
+    /// Matches Gecko's own shipping default: silent autoplay is harmless,
+    /// audible autoplay is the annoyance users complain about.
+    fn default() -> Self {
+        AutoplayPolicy::BlockAudible
+    }
+}
+
+impl AutoplayPolicy {
+    fn as_pref_str(&self) -> &'static str {
+        match self {
+            AutoplayPolicy::Allowed => "allowed",
+            AutoplayPolicy::BlockAudible => "block-audible",
+            AutoplayPolicy::BlockAll => "block-all",
+        }
+    }
+}
+
+/// The seam between this crate's preference decisions and the real
+/// engine: once an embedding engine exists, its FFI wrapper implements
+/// this by calling the equivalent of `gecko_set_pref`/`gecko_get_pref`
+/// across the boundary. Nothing upstream of this trait needs to know
+/// that's how it's implemented.
+pub trait EnginePrefs {
+    fn set_pref(&mut self, name: &str, value: PrefValue);
+    fn get_pref(&self, name: &str) -> Option<PrefValue>;
+}
+
+/// Per-origin preference overrides the per-site settings panel edits,
+/// applied onto an [`EnginePrefs`] sink whenever a tab navigates to that
+/// origin. Unlike engine prefs, which are instance-global knobs, these
+/// are scoped decisions this crate tracks so that leaving a site with
+/// JavaScript disabled doesn't leave every other tab that way too.
+#[derive(Debug, Default)]
+pub struct SitePrefStore {
+    overrides: HashMap<String, Vec<(String, PrefValue)>>,
+}
+
+impl SitePrefStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records `origin`'s override for `name`, replacing any previous
+    /// override of the same pref for that origin.
+    pub fn set(&mut self, origin: &str, name: &str, value: PrefValue) {
+        let entries = self.overrides.entry(origin.to_string()).or_default();
+        entries.retain(|(existing, _)| existing != name);
+        entries.push((name.to_string(), value));
+    }
+
+    /// Drops `origin`'s override for `name`, falling back to the
+    /// engine's own default the next time [`SitePrefStore::apply`] runs
+    /// for that origin.
+    pub fn clear(&mut self, origin: &str, name: &str) {
+        if let Some(entries) = self.overrides.get_mut(origin) {
+            entries.retain(|(existing, _)| existing != name);
+        }
+    }
+
+    /// Drops every override recorded for `origin`, e.g. when the user
+    /// forgets a site from history — the next navigation there applies
+    /// nothing but the engine's own defaults, the same as a site that
+    /// was never customized.
+    pub fn clear_origin(&mut self, origin: &str) {
+        self.overrides.remove(origin);
+    }
+
+    pub fn overrides_for(&self, origin: &str) -> &[(String, PrefValue)] {
+        self.overrides.get(origin).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// Resolves `name`'s bool override for `origin`, falling back to
+    /// `default` if there's no override or it isn't a [`PrefValue::Bool`].
+    /// The shared lookup behind every per-site on/off toggle in the
+    /// settings panel (JavaScript, images, popups, resist-fingerprinting).
+    pub fn bool_pref(&self, origin: &str, name: &str, default: bool) -> bool {
+        self.overrides_for(origin)
+            .iter()
+            .find(|(existing, _)| existing == name)
+            .map(|(_, value)| matches!(value, PrefValue::Bool(true)))
+            .unwrap_or(default)
+    }
+
+    /// Pushes every override recorded for `origin` onto `sink` — called
+    /// when a tab navigates to `origin`, so the engine picks up that
+    /// site's settings for the page it's about to load.
+    pub fn apply(&self, origin: &str, sink: &mut dyn EnginePrefs) {
+        for (name, value) in self.overrides_for(origin) {
+            sink.set_pref(name, value.clone());
+        }
+    }
+}
+
+pub fn autoplay_policy_pref(policy: AutoplayPolicy) -> PrefValue {
+    PrefValue::Str(policy.as_pref_str().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default)]
+    struct RecordingPrefs {
+        set: Vec<(String, PrefValue)>,
+    }
+
+    impl EnginePrefs for RecordingPrefs {
+        fn set_pref(&mut self, name: &str, value: PrefValue) {
+            self.set.push((name.to_string(), value));
+        }
+
+        fn get_pref(&self, name: &str) -> Option<PrefValue> {
+            self.set.iter().rev().find(|(existing, _)| existing == name).map(|(_, value)| value.clone())
+        }
+    }
+
+    #[test]
+    fn setting_a_site_override_replaces_a_previous_one_for_the_same_pref() {
+        let mut store = SitePrefStore::new();
+        store.set("https://example.com", PREF_JAVASCRIPT_ENABLED, PrefValue::Bool(false));
+        store.set("https://example.com", PREF_JAVASCRIPT_ENABLED, PrefValue::Bool(true));
+        assert_eq!(store.overrides_for("https://example.com"), &[(PREF_JAVASCRIPT_ENABLED.to_string(), PrefValue::Bool(true))]);
+    }
+
+    #[test]
+    fn overrides_are_scoped_per_origin() {
+        let mut store = SitePrefStore::new();
+        store.set("https://a.example.com", PREF_JAVASCRIPT_ENABLED, PrefValue::Bool(false));
+        assert!(store.overrides_for("https://b.example.com").is_empty());
+    }
+
+    #[test]
+    fn clearing_an_override_removes_only_that_pref() {
+        let mut store = SitePrefStore::new();
+        store.set("https://example.com", PREF_JAVASCRIPT_ENABLED, PrefValue::Bool(false));
+        store.set("https://example.com", PREF_RESIST_FINGERPRINTING, PrefValue::Bool(true));
+        store.clear("https://example.com", PREF_JAVASCRIPT_ENABLED);
+        assert_eq!(store.overrides_for("https://example.com"), &[(PREF_RESIST_FINGERPRINTING.to_string(), PrefValue::Bool(true))]);
+    }
+
+    #[test]
+    fn clear_origin_drops_every_override_for_that_origin_only() {
+        let mut store = SitePrefStore::new();
+        store.set("https://example.com", PREF_JAVASCRIPT_ENABLED, PrefValue::Bool(false));
+        store.set("https://example.com", PREF_RESIST_FINGERPRINTING, PrefValue::Bool(true));
+        store.set("https://other.example.com", PREF_JAVASCRIPT_ENABLED, PrefValue::Bool(false));
+
+        store.clear_origin("https://example.com");
+
+        assert!(store.overrides_for("https://example.com").is_empty());
+        assert!(!store.overrides_for("https://other.example.com").is_empty());
+    }
+
+    #[test]
+    fn apply_pushes_every_override_for_the_origin_onto_the_sink() {
+        let mut store = SitePrefStore::new();
+        store.set("https://example.com", PREF_JAVASCRIPT_ENABLED, PrefValue::Bool(false));
+        store.set("https://example.com", PREF_AUTOPLAY_POLICY, autoplay_policy_pref(AutoplayPolicy::BlockAll));
+
+        let mut sink = RecordingPrefs::default();
+        store.apply("https://example.com", &mut sink);
+
+        assert_eq!(sink.get_pref(PREF_JAVASCRIPT_ENABLED), Some(PrefValue::Bool(false)));
+        assert_eq!(sink.get_pref(PREF_AUTOPLAY_POLICY), Some(PrefValue::Str("block-all".to_string())));
+    }
+
+    #[test]
+    fn bool_pref_falls_back_to_the_default_without_an_override() {
+        let store = SitePrefStore::new();
+        assert!(store.bool_pref("https://example.com", PREF_IMAGES_ENABLED, true));
+        assert!(!store.bool_pref("https://example.com", PREF_POPUPS_ENABLED, false));
+    }
+
+    #[test]
+    fn bool_pref_reflects_a_recorded_override() {
+        let mut store = SitePrefStore::new();
+        store.set("https://example.com", PREF_IMAGES_ENABLED, PrefValue::Bool(false));
+        assert!(!store.bool_pref("https://example.com", PREF_IMAGES_ENABLED, true));
+    }
+
+    #[test]
+    fn apply_is_a_no_op_for_an_origin_with_no_overrides() {
+        let store = SitePrefStore::new();
+        let mut sink = RecordingPrefs::default();
+        store.apply("https://example.com", &mut sink);
+        assert!(sink.set.is_empty());
+    }
+}
@@ -0,0 +1,113 @@
+use crate::tab::Tab;
+use crate::tab::TabId;
+
+/// What a single profiler entry accounts for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ComponentKind {
+    Tab(TabId),
+    Network,
+    Storage,
+    Renderer,
+    /// An out-of-process helper (e.g. email, VPN) supervised by
+    /// `crynn-ipc`, named so the task manager can tell them apart.
+    Helper(String),
+}
+
+/// Memory and CPU usage for one component, as shown in the task-manager
+/// view (`about:performance`).
+#[derive(Debug, Clone)]
+pub struct ComponentMetrics {
+    pub kind: ComponentKind,
+    pub label: String,
+    pub memory_bytes: u64,
+    pub cpu_percent: f32,
+}
+
+/// A point-in-time readout across every tab and shared subsystem.
+///
+/// Real memory/CPU sampling belongs to the embedding engine; this crate
+/// only defines the shape subsystems report into and the shell renders
+/// from, plus a cheap built-in estimate so the view has something to show
+/// before a real profiler hook is wired up.
+#[derive(Debug, Clone, Default)]
+pub struct ProfilerSnapshot {
+    pub components: Vec<ComponentMetrics>,
+}
+
+impl ProfilerSnapshot {
+    pub(crate) fn capture<'a>(tabs: impl Iterator<Item = &'a Tab>) -> Self {
+        let mut components: Vec<ComponentMetrics> = tabs
+            .map(|tab| ComponentMetrics {
+                kind: ComponentKind::Tab(tab.id()),
+                label: tab.title().to_string(),
+                memory_bytes: estimate_tab_memory(tab),
+                cpu_percent: if tab.is_discarded() { 0.0 } else { 0.5 },
+            })
+            .collect();
+
+        components.push(ComponentMetrics {
+            kind: ComponentKind::Network,
+            label: "Network".to_string(),
+            memory_bytes: 0,
+            cpu_percent: 0.0,
+        });
+        components.push(ComponentMetrics {
+            kind: ComponentKind::Storage,
+            label: "Storage".to_string(),
+            memory_bytes: 0,
+            cpu_percent: 0.0,
+        });
+        components.push(ComponentMetrics {
+            kind: ComponentKind::Renderer,
+            label: "Renderer".to_string(),
+            memory_bytes: 0,
+            cpu_percent: 0.0,
+        });
+
+        Self { components }
+    }
+
+    pub fn total_memory_bytes(&self) -> u64 {
+        self.components.iter().map(|c| c.memory_bytes).sum()
+    }
+}
+
+/// Discarded tabs hold no rendering state; loaded ones are charged a base
+/// cost plus a per-character estimate for the page's in-memory DOM/text.
+fn estimate_tab_memory(tab: &Tab) -> u64 {
+    if tab.is_discarded() {
+        return 0;
+    }
+    const BASE_BYTES: u64 = 8 * 1024 * 1024;
+    BASE_BYTES + (tab.url().len() as u64) * 1024
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TabRegistry;
+
+    #[test]
+    fn discarded_tabs_report_zero_memory() {
+        let mut reg = TabRegistry::new();
+        let id = reg.open("https://example.com", "Example");
+        reg.unload(id);
+        let snapshot = reg.profiler_snapshot();
+        let tab_metrics = snapshot
+            .components
+            .iter()
+            .find(|c| matches!(c.kind, ComponentKind::Tab(t) if t == id))
+            .unwrap();
+        assert_eq!(tab_metrics.memory_bytes, 0);
+    }
+
+    #[test]
+    fn snapshot_always_includes_shared_subsystems() {
+        let reg = TabRegistry::new();
+        let snapshot = reg.profiler_snapshot();
+        assert!(snapshot
+            .components
+            .iter()
+            .any(|c| matches!(c.kind, ComponentKind::Network)));
+    }
+}
@@ -0,0 +1,53 @@
+use std::collections::VecDeque;
+
+/// A web push message delivered for a site's subscription, as the
+/// Firefox-backed engine would report it once a real push service
+/// connection exists. `origin` is whichever site subscribed; there may
+/// be no open tab for it when the message arrives.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PushMessage {
+    pub origin: String,
+    pub title: String,
+    pub body: String,
+}
+
+/// The engine-side half of the web-push bridge: [`PushInbox::receive`] is
+/// what the embedding engine calls into when a push message arrives;
+/// [`PushInbox::drain`] is what the shell polls once per frame to surface
+/// them (through the permissions system and the toast subsystem). Mirrors
+/// `crynn-shell`'s `EventBus` queue shape.
+#[derive(Debug, Default)]
+pub struct PushInbox {
+    queue: VecDeque<PushMessage>,
+}
+
+impl PushInbox {
+    pub fn receive(&mut self, message: PushMessage) {
+        tracing::debug!(origin = %message.origin, "received push message");
+        self.queue.push_back(message);
+    }
+
+    /// Removes and returns every message received since the last drain,
+    /// oldest first.
+    pub fn drain(&mut self) -> Vec<PushMessage> {
+        self.queue.drain(..).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn drain_returns_messages_in_receive_order_and_empties_the_queue() {
+        let mut inbox = PushInbox::default();
+        inbox.receive(PushMessage { origin: "https://a.example.com".to_string(), title: "A".to_string(), body: "first".to_string() });
+        inbox.receive(PushMessage { origin: "https://b.example.com".to_string(), title: "B".to_string(), body: "second".to_string() });
+
+        let drained = inbox.drain();
+        assert_eq!(drained.len(), 2);
+        assert_eq!(drained[0].body, "first");
+        assert_eq!(drained[1].body, "second");
+        assert!(inbox.drain().is_empty());
+    }
+}
@@ -0,0 +1,31 @@
+use serde::{Deserialize, Serialize};
+
+use crate::tab_group::GroupColor;
+
+/// One tab's worth of state worth restoring. `group` is the owning
+/// group's *name* rather than its id: ids are reassigned fresh on every
+/// restore (see [`crate::TabId`]'s own doc comment), so joining tabs back
+/// to groups by name avoids persisting ids that wouldn't mean anything
+/// across a restart anyway.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TabSnapshot {
+    pub url: String,
+    pub title: String,
+    pub group: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GroupSnapshot {
+    pub name: String,
+    pub color: GroupColor,
+    pub collapsed: bool,
+}
+
+/// Everything [`crate::TabRegistry::snapshot`] captures and
+/// [`crate::TabRegistry::restore`] rebuilds from, handed to the shell to
+/// persist however it persists other session-scoped state.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SessionSnapshot {
+    pub tabs: Vec<TabSnapshot>,
+    pub groups: Vec<GroupSnapshot>,
+}
@@ -0,0 +1,429 @@
+use crynn_network::ProxyConfig;
+
+use crate::tab_group::TabGroupId;
+
+/// Identifies a tab for the lifetime of the session. Not persisted across
+/// restarts; session-restore code maps these back to saved URLs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TabId(pub(crate) u64);
+
+/// Formats as the bare numeric id, for keying a string-keyed store (e.g.
+/// `crynn_network::NetworkManager::request_log`) that has no `TabId` of
+/// its own to key by.
+impl std::fmt::Display for TabId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Engine-side state for a single tab.
+#[derive(Debug, Clone)]
+pub struct Tab {
+    id: TabId,
+    url: String,
+    title: String,
+    discarded: bool,
+    /// Target URL of the link currently under the pointer, reported by the
+    /// engine's hover events. `None` when the pointer isn't over a link.
+    hovered_link: Option<String>,
+    /// The page's current text selection, reported by the engine the same
+    /// way [`Tab::hovered_link`] is. `None` when nothing is selected.
+    selected_text: Option<String>,
+    /// The page's detected content language (e.g. `"fr"`), reported by
+    /// the engine's language-detection pass the same way
+    /// [`Tab::hovered_link`] is reported from hover events. `None` until
+    /// detection finishes, or for a page short enough that it couldn't
+    /// confidently tell.
+    detected_language: Option<String>,
+    /// Set by [`Tab::request_select_all`], cleared by whatever drives the
+    /// engine once it has acted on the request — the same queue-and-drain
+    /// shape as [`crate::PushInbox`].
+    select_all_requested: bool,
+    /// Whether devtools are currently open for this tab, set by
+    /// [`crate::open_devtools`]/[`crate::close_devtools`].
+    devtools_open: bool,
+    timings: RequestTimings,
+    zoom: f32,
+    scroll_y: f32,
+    text_size: f32,
+    /// Which [`crate::TabGroup`] this tab belongs to, if any.
+    group_id: Option<TabGroupId>,
+    /// This tab's own proxy, overriding its group's — e.g. for testing
+    /// a region-specific version of a site without re-proxying every
+    /// other tab in the same container. `None` falls through to the
+    /// group's proxy (or [`ProxyConfig::Direct`] with no group); see
+    /// [`crate::TabRegistry::effective_proxy`].
+    proxy_override: Option<ProxyConfig>,
+    /// Whether the page currently has audio or video actively playing,
+    /// reported by the engine the same way [`Tab::hovered_link`] is
+    /// reported from hover events. Drives the speaker icon the tab strip
+    /// shows, and what [`crate::background_mute_targets`] mutes.
+    audio_playing: bool,
+    /// This tab's own mute state, independent of its volume — muting
+    /// silences playback without losing the level [`Tab::set_volume`]
+    /// should go back to once unmuted.
+    muted: bool,
+    volume: f32,
+}
+
+/// Zoom is clamped to the same range Gecko itself uses for `ZoomManager`.
+pub const MIN_ZOOM: f32 = 0.3;
+pub const MAX_ZOOM: f32 = 3.0;
+pub const DEFAULT_ZOOM: f32 = 1.0;
+pub const DEFAULT_SCROLL_Y: f32 = 0.0;
+pub const DEFAULT_TEXT_SIZE: f32 = 1.0;
+pub const MIN_VOLUME: f32 = 0.0;
+pub const MAX_VOLUME: f32 = 1.0;
+pub const DEFAULT_VOLUME: f32 = 1.0;
+
+/// Load timing for the page currently shown in a tab, as reported by the
+/// engine once navigation finishes. Feeds the status-bar page-info
+/// popover.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RequestTimings {
+    pub load_time_ms: Option<u64>,
+    pub content_size_bytes: u64,
+}
+
+impl Tab {
+    pub(crate) fn new(id: TabId, url: impl Into<String>, title: impl Into<String>) -> Self {
+        Self {
+            id,
+            url: url.into(),
+            title: title.into(),
+            discarded: false,
+            hovered_link: None,
+            selected_text: None,
+            detected_language: None,
+            select_all_requested: false,
+            devtools_open: false,
+            timings: RequestTimings::default(),
+            zoom: DEFAULT_ZOOM,
+            scroll_y: DEFAULT_SCROLL_Y,
+            text_size: DEFAULT_TEXT_SIZE,
+            group_id: None,
+            proxy_override: None,
+            audio_playing: false,
+            muted: false,
+            volume: DEFAULT_VOLUME,
+        }
+    }
+
+    pub fn zoom(&self) -> f32 {
+        self.zoom
+    }
+
+    /// Calls into the engine's zoom API, clamping to the supported range.
+    pub fn set_zoom(&mut self, level: f32) {
+        self.zoom = level.clamp(MIN_ZOOM, MAX_ZOOM);
+    }
+
+    pub fn scroll_y(&self) -> f32 {
+        self.scroll_y
+    }
+
+    /// Calls into the engine's scroll API. Reported back by the engine
+    /// as the page scrolls, the same as [`Tab::set_hovered_link`]; not
+    /// clamped here since the page's actual scrollable height isn't
+    /// something this crate knows without the engine telling it.
+    pub fn set_scroll_y(&mut self, y: f32) {
+        self.scroll_y = y;
+    }
+
+    pub fn text_size(&self) -> f32 {
+        self.text_size
+    }
+
+    /// Calls into the engine's minimum-font-size API, clamping to the
+    /// same range [`Tab::set_zoom`] uses — a text-size override is the
+    /// same kind of per-site readability knob zoom is, just scoped to
+    /// text rather than the whole page.
+    pub fn set_text_size(&mut self, scale: f32) {
+        self.text_size = scale.clamp(MIN_ZOOM, MAX_ZOOM);
+    }
+
+    /// Restores this tab's zoom, scroll position, and text size all at
+    /// once, e.g. from a `crynn_storage::ViewState` looked up on
+    /// navigation or session restore, calling into the engine's own
+    /// APIs for each the same way [`Tab::set_zoom`]/[`Tab::set_scroll_y`]/
+    /// [`Tab::set_text_size`] already do individually.
+    pub fn restore_view_state(&mut self, zoom: f32, scroll_y: f32, text_size: f32) {
+        self.set_zoom(zoom);
+        self.set_scroll_y(scroll_y);
+        self.set_text_size(text_size);
+    }
+
+    pub fn hovered_link(&self) -> Option<&str> {
+        self.hovered_link.as_deref()
+    }
+
+    /// Called from the engine's hover event when the pointer moves over or
+    /// off a link.
+    pub fn set_hovered_link(&mut self, url: Option<String>) {
+        self.hovered_link = url;
+    }
+
+    pub fn selected_text(&self) -> Option<&str> {
+        self.selected_text.as_deref()
+    }
+
+    /// Called from the engine's selection-changed event.
+    pub fn set_selected_text(&mut self, text: Option<String>) {
+        self.selected_text = text;
+    }
+
+    pub fn detected_language(&self) -> Option<&str> {
+        self.detected_language.as_deref()
+    }
+
+    /// Called from the engine's language-detection pass once it finishes
+    /// for the page currently loaded in this tab.
+    pub fn set_detected_language(&mut self, language: Option<String>) {
+        self.detected_language = language;
+    }
+
+    /// Queues a select-all request for whatever drives the engine to pick
+    /// up and act on, e.g. from a "Select All" context-menu entry.
+    pub fn request_select_all(&mut self) {
+        self.select_all_requested = true;
+    }
+
+    /// Drains the pending select-all request, if any: `true` once per
+    /// request, `false` otherwise.
+    pub fn take_select_all_request(&mut self) -> bool {
+        std::mem::take(&mut self.select_all_requested)
+    }
+
+    pub fn is_devtools_open(&self) -> bool {
+        self.devtools_open
+    }
+
+    pub(crate) fn set_devtools_open(&mut self, open: bool) {
+        self.devtools_open = open;
+    }
+
+    pub fn timings(&self) -> RequestTimings {
+        self.timings
+    }
+
+    /// Called once the engine finishes loading the page, recording how
+    /// long it took and how large the content was.
+    pub fn record_load(&mut self, load_time_ms: u64, content_size_bytes: u64) {
+        self.timings = RequestTimings {
+            load_time_ms: Some(load_time_ms),
+            content_size_bytes,
+        };
+    }
+
+    pub fn id(&self) -> TabId {
+        self.id
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    /// True once [`TabRegistry::unload`](crate::TabRegistry::unload) has
+    /// discarded this tab's rendering state.
+    pub fn is_discarded(&self) -> bool {
+        self.discarded
+    }
+
+    pub(crate) fn unload(&mut self) {
+        self.discarded = true;
+    }
+
+    pub fn group_id(&self) -> Option<TabGroupId> {
+        self.group_id
+    }
+
+    /// Moves this tab into `group_id`, or out of whatever group it was in
+    /// when passed `None`. Doesn't validate that the group still exists;
+    /// callers go through [`crate::TabRegistry::assign_tab_to_group`] for
+    /// that.
+    pub(crate) fn set_group(&mut self, group_id: Option<TabGroupId>) {
+        self.group_id = group_id;
+    }
+
+    pub fn proxy_override(&self) -> Option<&ProxyConfig> {
+        self.proxy_override.as_ref()
+    }
+
+    /// Sets this tab's own proxy, overriding its group's. Pass
+    /// [`ProxyConfig::Direct`] to force this tab off any proxy
+    /// regardless of its group, or [`Self::clear_proxy_override`] to
+    /// fall back to the group's proxy instead.
+    pub fn set_proxy_override(&mut self, proxy: ProxyConfig) {
+        self.proxy_override = Some(proxy);
+    }
+
+    pub fn clear_proxy_override(&mut self) {
+        self.proxy_override = None;
+    }
+
+    pub fn is_audio_playing(&self) -> bool {
+        self.audio_playing
+    }
+
+    /// Called from the engine's audio-playback-state event.
+    pub fn set_audio_playing(&mut self, playing: bool) {
+        self.audio_playing = playing;
+    }
+
+    pub fn is_muted(&self) -> bool {
+        self.muted
+    }
+
+    pub fn set_muted(&mut self, muted: bool) {
+        self.muted = muted;
+    }
+
+    pub fn volume(&self) -> f32 {
+        self.volume
+    }
+
+    /// Calls into the engine's volume API, clamping to the supported
+    /// range the same way [`Tab::set_zoom`] clamps zoom.
+    pub fn set_volume(&mut self, level: f32) {
+        self.volume = level.clamp(MIN_VOLUME, MAX_VOLUME);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hover_state_round_trips() {
+        let mut tab = Tab::new(TabId(0), "https://example.com", "Example");
+        assert!(tab.hovered_link().is_none());
+        tab.set_hovered_link(Some("https://example.com/about".to_string()));
+        assert_eq!(tab.hovered_link(), Some("https://example.com/about"));
+        tab.set_hovered_link(None);
+        assert!(tab.hovered_link().is_none());
+    }
+
+    #[test]
+    fn selection_state_round_trips() {
+        let mut tab = Tab::new(TabId(0), "https://example.com", "Example");
+        assert!(tab.selected_text().is_none());
+        tab.set_selected_text(Some("hello world".to_string()));
+        assert_eq!(tab.selected_text(), Some("hello world"));
+        tab.set_selected_text(None);
+        assert!(tab.selected_text().is_none());
+    }
+
+    #[test]
+    fn detected_language_round_trips() {
+        let mut tab = Tab::new(TabId(0), "https://example.com", "Example");
+        assert!(tab.detected_language().is_none());
+        tab.set_detected_language(Some("fr".to_string()));
+        assert_eq!(tab.detected_language(), Some("fr"));
+        tab.set_detected_language(None);
+        assert!(tab.detected_language().is_none());
+    }
+
+    #[test]
+    fn select_all_request_is_drained_once() {
+        let mut tab = Tab::new(TabId(0), "https://example.com", "Example");
+        assert!(!tab.take_select_all_request());
+        tab.request_select_all();
+        assert!(tab.take_select_all_request());
+        assert!(!tab.take_select_all_request());
+    }
+
+    #[test]
+    fn devtools_open_state_round_trips() {
+        let mut tab = Tab::new(TabId(0), "https://example.com", "Example");
+        assert!(!tab.is_devtools_open());
+        tab.set_devtools_open(true);
+        assert!(tab.is_devtools_open());
+        tab.set_devtools_open(false);
+        assert!(!tab.is_devtools_open());
+    }
+
+    #[test]
+    fn proxy_override_defaults_to_none_and_round_trips() {
+        let mut tab = Tab::new(TabId(0), "https://example.com", "Example");
+        assert!(tab.proxy_override().is_none());
+
+        let proxy = ProxyConfig::Http { host: "proxy.example.com".to_string(), port: 8080 };
+        tab.set_proxy_override(proxy.clone());
+        assert_eq!(tab.proxy_override(), Some(&proxy));
+
+        tab.clear_proxy_override();
+        assert!(tab.proxy_override().is_none());
+    }
+
+    #[test]
+    fn set_zoom_clamps_to_supported_range() {
+        let mut tab = Tab::new(TabId(0), "https://example.com", "Example");
+        tab.set_zoom(10.0);
+        assert_eq!(tab.zoom(), MAX_ZOOM);
+        tab.set_zoom(0.0);
+        assert_eq!(tab.zoom(), MIN_ZOOM);
+    }
+
+    #[test]
+    fn set_text_size_clamps_to_the_same_range_as_zoom() {
+        let mut tab = Tab::new(TabId(0), "https://example.com", "Example");
+        tab.set_text_size(10.0);
+        assert_eq!(tab.text_size(), MAX_ZOOM);
+        tab.set_text_size(0.0);
+        assert_eq!(tab.text_size(), MIN_ZOOM);
+    }
+
+    #[test]
+    fn scroll_position_round_trips_unclamped() {
+        let mut tab = Tab::new(TabId(0), "https://example.com", "Example");
+        assert_eq!(tab.scroll_y(), DEFAULT_SCROLL_Y);
+        tab.set_scroll_y(1200.0);
+        assert_eq!(tab.scroll_y(), 1200.0);
+    }
+
+    #[test]
+    fn restore_view_state_applies_zoom_scroll_and_text_size_together() {
+        let mut tab = Tab::new(TabId(0), "https://example.com", "Example");
+        tab.restore_view_state(1.5, 800.0, 1.2);
+        assert_eq!(tab.zoom(), 1.5);
+        assert_eq!(tab.scroll_y(), 800.0);
+        assert_eq!(tab.text_size(), 1.2);
+    }
+
+    #[test]
+    fn audio_playing_and_muted_state_round_trip() {
+        let mut tab = Tab::new(TabId(0), "https://example.com", "Example");
+        assert!(!tab.is_audio_playing());
+        assert!(!tab.is_muted());
+
+        tab.set_audio_playing(true);
+        tab.set_muted(true);
+
+        assert!(tab.is_audio_playing());
+        assert!(tab.is_muted());
+    }
+
+    #[test]
+    fn set_volume_clamps_to_supported_range() {
+        let mut tab = Tab::new(TabId(0), "https://example.com", "Example");
+        assert_eq!(tab.volume(), DEFAULT_VOLUME);
+        tab.set_volume(10.0);
+        assert_eq!(tab.volume(), MAX_VOLUME);
+        tab.set_volume(-1.0);
+        assert_eq!(tab.volume(), MIN_VOLUME);
+    }
+
+    #[test]
+    fn record_load_updates_timings() {
+        let mut tab = Tab::new(TabId(0), "https://example.com", "Example");
+        assert!(tab.timings().load_time_ms.is_none());
+        tab.record_load(120, 4096);
+        let timings = tab.timings();
+        assert_eq!(timings.load_time_ms, Some(120));
+        assert_eq!(timings.content_size_bytes, 4096);
+    }
+}
@@ -0,0 +1,12 @@
+/// Errors from `crynn-config`'s public API.
+#[derive(Debug, thiserror::Error)]
+pub enum ConfigError {
+    #[error("config file {path} is not valid TOML: {reason}")]
+    InvalidToml { path: String, reason: String },
+
+    #[error("unknown log level {level:?}")]
+    InvalidLogLevel { level: String },
+
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+}
@@ -0,0 +1,27 @@
+/// Errors from `crynn-email`'s public API.
+#[derive(Debug, thiserror::Error)]
+pub enum EmailError {
+    #[error("authentication expired for account {account}")]
+    AuthExpired { account: String },
+
+    #[error("IMAP server rejected the request: {reason}")]
+    ImapRejected { reason: String },
+
+    #[error("message {id} has no such attachment")]
+    AttachmentNotFound { id: String },
+
+    #[error("could not reach a mail server at {host}")]
+    ServerUnreachable { host: String },
+
+    #[error("account setup requires signing in via {provider}")]
+    OAuthRequired { provider: String },
+
+    #[error("credentials were rejected for {account}")]
+    CredentialsRejected { account: String },
+
+    #[error(transparent)]
+    Network(#[from] crate::NetworkError),
+
+    #[error(transparent)]
+    Storage(#[from] crate::StorageError),
+}
@@ -0,0 +1,6 @@
+/// Errors from `crynn-i18n`'s public API.
+#[derive(Debug, thiserror::Error)]
+pub enum I18nError {
+    #[error("locale {locale} has no bundled message resources")]
+    UnsupportedLocale { locale: String },
+}
@@ -0,0 +1,12 @@
+/// Errors from `crynn-ipc`'s public API.
+#[derive(Debug, thiserror::Error)]
+pub enum IpcError {
+    #[error("failed to spawn helper {helper}: {reason}")]
+    SpawnFailed { helper: String, reason: String },
+
+    #[error("failed to encode/decode an IPC message: {0}")]
+    Codec(String),
+
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+}
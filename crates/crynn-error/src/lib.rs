@@ -0,0 +1,57 @@
+//! Typed errors for each subsystem, so callers can match on "network
+//! timeout" vs. "database locked" vs. "auth expired" instead of inspecting
+//! an opaque `anyhow::Error` string. Subsystem crates return their own
+//! domain enum from their public APIs; [`CrynnError`] exists for code that
+//! needs to bubble errors up across domains (the CLI, the shell's top
+//! level) without flattening them into strings first.
+
+mod config;
+mod email;
+mod i18n;
+mod ipc;
+mod network;
+mod plugin;
+mod spellcheck;
+mod storage;
+mod sync;
+mod update;
+mod vpn;
+
+pub use config::ConfigError;
+pub use email::EmailError;
+pub use i18n::I18nError;
+pub use ipc::IpcError;
+pub use network::NetworkError;
+pub use plugin::PluginError;
+pub use spellcheck::SpellcheckError;
+pub use storage::StorageError;
+pub use sync::SyncError;
+pub use update::UpdateError;
+pub use vpn::VpnError;
+
+/// Umbrella error for call sites that aggregate across domains.
+#[derive(Debug, thiserror::Error)]
+pub enum CrynnError {
+    #[error(transparent)]
+    Network(#[from] NetworkError),
+    #[error(transparent)]
+    Storage(#[from] StorageError),
+    #[error(transparent)]
+    Email(#[from] EmailError),
+    #[error(transparent)]
+    Vpn(#[from] VpnError),
+    #[error(transparent)]
+    Config(#[from] ConfigError),
+    #[error(transparent)]
+    Sync(#[from] SyncError),
+    #[error(transparent)]
+    Plugin(#[from] PluginError),
+    #[error(transparent)]
+    Update(#[from] UpdateError),
+    #[error(transparent)]
+    I18n(#[from] I18nError),
+    #[error(transparent)]
+    Ipc(#[from] IpcError),
+    #[error(transparent)]
+    Spellcheck(#[from] SpellcheckError),
+}
@@ -0,0 +1,30 @@
+/// Errors from `crynn-network`'s public API.
+#[derive(Debug, thiserror::Error)]
+pub enum NetworkError {
+    #[error("request to {url} timed out after {elapsed_ms}ms")]
+    Timeout { url: String, elapsed_ms: u64 },
+
+    #[error("DNS resolution failed for {host}")]
+    DnsResolution { host: String },
+
+    #[error("TLS handshake with {host} failed: {reason}")]
+    TlsHandshake { host: String, reason: String },
+
+    #[error("connection refused by {host}")]
+    ConnectionRefused { host: String },
+
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
+    #[error("unsupported authentication scheme: {scheme}")]
+    UnsupportedAuthScheme { scheme: String },
+
+    #[error("no credential was provided for {realm} on {host}")]
+    AuthCredentialMissing { realm: String, host: String },
+
+    #[error("unsupported Content-Encoding: {encoding}")]
+    UnsupportedContentEncoding { encoding: String },
+
+    #[error("failed to decompress {encoding} response body: {reason}")]
+    Decompression { encoding: String, reason: String },
+}
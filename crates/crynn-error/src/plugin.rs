@@ -0,0 +1,9 @@
+/// Errors from `crynn-plugins`'s public API.
+#[derive(Debug, thiserror::Error)]
+pub enum PluginError {
+    #[error("plugin {plugin} does not have the {capability} capability")]
+    CapabilityNotGranted { plugin: String, capability: String },
+
+    #[error("plugin {plugin} crashed: {detail}")]
+    RuntimeFault { plugin: String, detail: String },
+}
@@ -0,0 +1,9 @@
+/// Errors from `crynn-spellcheck`'s public API.
+#[derive(Debug, thiserror::Error)]
+pub enum SpellcheckError {
+    #[error("no dictionary available for locale {locale}")]
+    LocaleUnavailable { locale: String },
+
+    #[error(transparent)]
+    Serialization(#[from] serde_json::Error),
+}
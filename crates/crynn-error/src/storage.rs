@@ -0,0 +1,24 @@
+/// Errors from `crynn-storage`'s public API.
+#[derive(Debug, thiserror::Error)]
+pub enum StorageError {
+    #[error("database {name} is locked by another process")]
+    DatabaseLocked { name: String },
+
+    #[error("record not found: {key}")]
+    NotFound { key: String },
+
+    #[error("{name} database is corrupt: {detail}")]
+    Corrupt { name: String, detail: String },
+
+    #[error("storage is locked; unlock with the master password")]
+    MasterPasswordRequired,
+
+    #[error("keyword {keyword} is already assigned to another bookmark")]
+    KeywordAlreadyAssigned { keyword: String },
+
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
+    #[error(transparent)]
+    Serialization(#[from] serde_json::Error),
+}
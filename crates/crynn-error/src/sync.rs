@@ -0,0 +1,22 @@
+/// Errors from `crynn-sync`'s public API.
+#[derive(Debug, thiserror::Error)]
+pub enum SyncError {
+    #[error("sync record {id} in collection {collection} is corrupt: {detail}")]
+    Corrupt {
+        collection: String,
+        id: String,
+        detail: String,
+    },
+
+    #[error("transport {transport} rejected the request: {reason}")]
+    TransportRejected { transport: String, reason: String },
+
+    #[error(transparent)]
+    Network(#[from] crate::NetworkError),
+
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
+    #[error(transparent)]
+    Serialization(#[from] serde_json::Error),
+}
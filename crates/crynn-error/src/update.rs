@@ -0,0 +1,24 @@
+/// Errors from `crynn-update`'s public API.
+#[derive(Debug, thiserror::Error)]
+pub enum UpdateError {
+    #[error("update manifest signature did not verify against the pinned key")]
+    SignatureInvalid,
+
+    #[error("downloaded package does not match the manifest's sha256 digest")]
+    DigestMismatch,
+
+    #[error("no manifest for channel {channel}")]
+    NoManifestForChannel { channel: String },
+
+    #[error("download deferred: on a metered connection and wifi_only is set")]
+    DeferredMetered,
+
+    #[error(transparent)]
+    Network(#[from] crate::NetworkError),
+
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
+    #[error(transparent)]
+    Serialization(#[from] serde_json::Error),
+}
@@ -0,0 +1,27 @@
+/// Errors from `crynn-vpn`'s public API.
+#[derive(Debug, thiserror::Error)]
+pub enum VpnError {
+    #[error("no server available in region {region}")]
+    NoServerInRegion { region: String },
+
+    #[error("handshake with VPN server {server} failed")]
+    HandshakeFailed { server: String },
+
+    #[error("VPN credentials were rejected")]
+    AuthRejected,
+
+    #[error("no VPN profile named {name}")]
+    ProfileNotFound { name: String },
+
+    #[error("no default profile is set for quick-connect")]
+    NoDefaultProfile,
+
+    #[error(transparent)]
+    Network(#[from] crate::NetworkError),
+
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
+    #[error(transparent)]
+    Serialization(#[from] serde_json::Error),
+}
@@ -0,0 +1,134 @@
+use std::collections::HashMap;
+
+use fluent_bundle::{FluentArgs, FluentBundle, FluentResource, FluentValue};
+use unic_langid::LanguageIdentifier;
+
+use crynn_error::I18nError;
+
+use crate::resources::{BUNDLED_RESOURCES, FALLBACK_LOCALE};
+
+/// Owns every bundled locale's [`FluentBundle`] and the currently active
+/// one, so switching languages at runtime is just repointing `current` —
+/// no reloading or re-parsing of `.ftl` resources involved.
+pub struct Catalog {
+    bundles: HashMap<String, FluentBundle<FluentResource>>,
+    current: String,
+}
+
+impl Catalog {
+    /// Builds a bundle for every resource in [`BUNDLED_RESOURCES`] and
+    /// activates `locale`, falling back to [`FALLBACK_LOCALE`] if it
+    /// isn't one of them.
+    pub fn new(locale: &str) -> Self {
+        let mut bundles = HashMap::new();
+        for (id, ftl) in BUNDLED_RESOURCES {
+            let langid: LanguageIdentifier = id.parse().expect("bundled locale ids are valid");
+            let resource = FluentResource::try_new(ftl.to_string())
+                .unwrap_or_else(|(_, errors)| panic!("bundled resource {id} failed to parse: {errors:?}"));
+            let mut bundle = FluentBundle::new(vec![langid]);
+            bundle.set_use_isolating(false);
+            bundle
+                .add_resource(resource)
+                .unwrap_or_else(|errors| panic!("bundled resource {id} has duplicate messages: {errors:?}"));
+            bundles.insert(id.to_string(), bundle);
+        }
+
+        let mut catalog = Self {
+            bundles,
+            current: FALLBACK_LOCALE.to_string(),
+        };
+        let _ = catalog.set_locale(locale);
+        catalog
+    }
+
+    /// Switches the active locale. Returns an error (and leaves the
+    /// current locale unchanged) if `locale` has no bundled resources.
+    pub fn set_locale(&mut self, locale: &str) -> Result<(), I18nError> {
+        if !self.bundles.contains_key(locale) {
+            return Err(I18nError::UnsupportedLocale {
+                locale: locale.to_string(),
+            });
+        }
+        self.current = locale.to_string();
+        Ok(())
+    }
+
+    pub fn current_locale(&self) -> &str {
+        &self.current
+    }
+
+    /// Looks up `id` with no arguments. Missing messages return the id
+    /// itself, wrapped in brackets, so a broken lookup is obviously wrong
+    /// on screen instead of rendering as empty text.
+    pub fn message(&self, id: &str) -> String {
+        self.message_with_args(id, None)
+    }
+
+    /// Looks up `id`, substituting `args` into its pattern.
+    pub fn message_with_args(&self, id: &str, args: Option<&FluentArgs>) -> String {
+        let bundle = self
+            .bundles
+            .get(&self.current)
+            .expect("current locale always has a bundle");
+        let Some(message) = bundle.get_message(id) else {
+            tracing::warn!(%id, locale = %self.current, "missing i18n message");
+            return format!("[{id}]");
+        };
+        let Some(pattern) = message.value() else {
+            return format!("[{id}]");
+        };
+        let mut errors = Vec::new();
+        let value = bundle.format_pattern(pattern, args, &mut errors);
+        for error in errors {
+            tracing::warn!(%id, locale = %self.current, %error, "error formatting i18n message");
+        }
+        value.into_owned()
+    }
+
+    /// Convenience for the common case of a single numeric argument, e.g.
+    /// `trackers-blocked = { $count } trackers blocked`.
+    pub fn message_with_count(&self, id: &str, count: u32) -> String {
+        let mut args = FluentArgs::new();
+        args.set("count", FluentValue::from(count));
+        self.message_with_args(id, Some(&args))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn defaults_to_the_fallback_locale_for_an_unsupported_request() {
+        let catalog = Catalog::new("xx-XX");
+        assert_eq!(catalog.current_locale(), FALLBACK_LOCALE);
+    }
+
+    #[test]
+    fn set_locale_switches_which_bundle_messages_come_from() {
+        let mut catalog = Catalog::new("en-US");
+        assert_eq!(catalog.message("page-info-tooltip"), "Page info");
+
+        catalog.set_locale("ar").unwrap();
+        assert_eq!(catalog.message("page-info-tooltip"), "معلومات الصفحة");
+    }
+
+    #[test]
+    fn set_locale_rejects_locales_with_no_bundled_resources() {
+        let mut catalog = Catalog::new("en-US");
+        assert!(catalog.set_locale("xx-XX").is_err());
+        assert_eq!(catalog.current_locale(), "en-US");
+    }
+
+    #[test]
+    fn missing_messages_render_as_their_id_rather_than_blank() {
+        let catalog = Catalog::new("en-US");
+        assert_eq!(catalog.message("no-such-message"), "[no-such-message]");
+    }
+
+    #[test]
+    fn message_with_count_substitutes_the_argument() {
+        let catalog = Catalog::new("en-US");
+        assert_eq!(catalog.message_with_count("trackers-blocked", 3), "3 trackers blocked on this page");
+    }
+}
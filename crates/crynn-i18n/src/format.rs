@@ -0,0 +1,120 @@
+/// Grouping and decimal separators for a locale's number formatting.
+/// Covers the locales this crate ships messages for plus a couple of
+/// other common conventions; unrecognized locales fall back to the
+/// en-US convention rather than failing.
+fn separators(locale: &str) -> (char, char) {
+    let primary = locale.split(['-', '_']).next().unwrap_or(locale);
+    match primary {
+        "fr" | "de" | "es" | "it" => ('.', ','),
+        _ => (',', '.'),
+    }
+}
+
+/// Formats `value` with locale-appropriate thousands grouping and decimal
+/// separator, rounded to `decimals` fractional digits.
+pub fn format_number(value: f64, locale: &str, decimals: usize) -> String {
+    let (group_sep, decimal_sep) = separators(locale);
+    let formatted = format!("{value:.decimals$}");
+    let (int_part, frac_part) = match formatted.split_once('.') {
+        Some((int_part, frac_part)) => (int_part, Some(frac_part)),
+        None => (formatted.as_str(), None),
+    };
+
+    let negative = int_part.starts_with('-');
+    let digits = if negative { &int_part[1..] } else { int_part };
+
+    let mut grouped = String::new();
+    for (i, digit) in digits.chars().rev().enumerate() {
+        if i > 0 && i % 3 == 0 {
+            grouped.push(group_sep);
+        }
+        grouped.push(digit);
+    }
+    let grouped: String = grouped.chars().rev().collect();
+
+    let mut result = if negative { format!("-{grouped}") } else { grouped };
+    if let Some(frac_part) = frac_part {
+        result.push(decimal_sep);
+        result.push_str(frac_part);
+    }
+    result
+}
+
+/// A calendar date and time of day, decomposed from a Unix timestamp.
+struct Civil {
+    year: i64,
+    month: u32,
+    day: u32,
+    hour: u32,
+    minute: u32,
+}
+
+/// Converts a Unix timestamp (seconds since the epoch, UTC) to a calendar
+/// date and time using Howard Hinnant's `civil_from_days` algorithm: pure
+/// integer arithmetic, correct for the whole proleptic Gregorian
+/// calendar, no external date/time crate required.
+fn civil_from_unix(unix_secs: i64) -> Civil {
+    let days = unix_secs.div_euclid(86_400);
+    let time_of_day = unix_secs.rem_euclid(86_400);
+
+    let z = days + 719_468;
+    let era = if z >= 0 { z } else { z - 146_096 } / 146_097;
+    let doe = (z - era * 146_097) as u64;
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let y = yoe as i64 + era * 400;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = if month <= 2 { y + 1 } else { y };
+
+    Civil {
+        year,
+        month,
+        day,
+        hour: (time_of_day / 3_600) as u32,
+        minute: ((time_of_day % 3_600) / 60) as u32,
+    }
+}
+
+/// Formats a Unix timestamp as a locale-appropriate date and 24-hour
+/// time. Only the field order varies by locale today (`en-US` uses
+/// month/day/year, everything else falls back to day/month/year); a
+/// locale that wants a different convention is a table entry away.
+pub fn format_date(unix_secs: i64, locale: &str) -> String {
+    let civil = civil_from_unix(unix_secs);
+    let date = if locale.eq_ignore_ascii_case("en-US") {
+        format!("{:02}/{:02}/{:04}", civil.month, civil.day, civil.year)
+    } else {
+        format!("{:02}/{:02}/{:04}", civil.day, civil.month, civil.year)
+    };
+    format!("{date} {:02}:{:02}", civil.hour, civil.minute)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_number_groups_thousands_with_the_us_convention() {
+        assert_eq!(format_number(1_234_567.5, "en-US", 1), "1,234,567.5");
+    }
+
+    #[test]
+    fn format_number_uses_the_french_convention() {
+        assert_eq!(format_number(1_234_567.0, "fr-FR", 0), "1.234.567");
+    }
+
+    #[test]
+    fn format_number_handles_negative_values() {
+        assert_eq!(format_number(-1234.0, "en-US", 0), "-1,234");
+    }
+
+    #[test]
+    fn format_date_round_trips_a_known_instant() {
+        // 2024-03-05 07:08:00 UTC.
+        let unix_secs = 1_709_622_480;
+        assert_eq!(format_date(unix_secs, "en-US"), "03/05/2024 07:08");
+        assert_eq!(format_date(unix_secs, "ar"), "05/03/2024 07:08");
+    }
+}
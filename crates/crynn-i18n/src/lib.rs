@@ -0,0 +1,14 @@
+//! Fluent-based i18n: per-locale message bundles, runtime language
+//! switching, and locale-aware date/number formatting for the shell and
+//! whichever views need it — today the shield and page-info popovers;
+//! history, downloads, and email views are natural next callers once
+//! those land.
+
+mod catalog;
+mod format;
+mod resources;
+mod rtl;
+
+pub use catalog::Catalog;
+pub use format::{format_date, format_number};
+pub use rtl::is_rtl;
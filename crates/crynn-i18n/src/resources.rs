@@ -0,0 +1,12 @@
+/// Bundled message resources, one `.ftl` file per supported locale. Adding
+/// a language is a matter of dropping a new file under `resources/` and
+/// adding it here; it doesn't need any code changes elsewhere in this
+/// crate.
+pub(crate) const BUNDLED_RESOURCES: &[(&str, &str)] = &[
+    ("en-US", include_str!("../resources/en-US.ftl")),
+    ("ar", include_str!("../resources/ar.ftl")),
+];
+
+/// Locale bundled resources fall back to when a requested message or
+/// locale isn't available.
+pub(crate) const FALLBACK_LOCALE: &str = "en-US";
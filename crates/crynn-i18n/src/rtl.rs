@@ -0,0 +1,29 @@
+/// Right-to-left script languages the shell needs to mirror its layout
+/// for. Matched on the primary language subtag, so `ar`, `ar-EG`, and
+/// `ar-SA` all count.
+const RTL_LANGUAGES: &[&str] = &["ar", "he", "fa", "ur"];
+
+/// Whether `locale`'s script reads right-to-left, for the shell to mirror
+/// its layout direction.
+pub fn is_rtl(locale: &str) -> bool {
+    let primary = locale.split(['-', '_']).next().unwrap_or(locale);
+    RTL_LANGUAGES.iter().any(|lang| lang.eq_ignore_ascii_case(primary))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recognizes_rtl_languages_regardless_of_region() {
+        assert!(is_rtl("ar"));
+        assert!(is_rtl("ar-EG"));
+        assert!(is_rtl("he-IL"));
+    }
+
+    #[test]
+    fn ltr_languages_are_not_rtl() {
+        assert!(!is_rtl("en-US"));
+        assert!(!is_rtl("fr"));
+    }
+}
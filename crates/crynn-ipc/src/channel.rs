@@ -0,0 +1,73 @@
+use std::io::{BufRead, BufReader, Write};
+use std::os::unix::net::UnixStream;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crynn_error::IpcError;
+
+/// A typed, newline-delimited JSON channel over a Unix domain socket.
+/// Good enough for the request/response traffic a helper process
+/// exchanges with the shell, without pulling in a binary framing
+/// format.
+pub struct Channel {
+    reader: BufReader<UnixStream>,
+    writer: UnixStream,
+}
+
+impl Channel {
+    pub fn new(stream: UnixStream) -> std::io::Result<Self> {
+        let reader = BufReader::new(stream.try_clone()?);
+        Ok(Self { reader, writer: stream })
+    }
+
+    pub fn send<T: Serialize>(&mut self, message: &T) -> Result<(), IpcError> {
+        let mut line = serde_json::to_string(message).map_err(|e| IpcError::Codec(e.to_string()))?;
+        line.push('\n');
+        self.writer.write_all(line.as_bytes())?;
+        Ok(())
+    }
+
+    /// Reads the next message, or `None` once the peer has closed its
+    /// end of the socket.
+    pub fn recv<T: DeserializeOwned>(&mut self) -> Result<Option<T>, IpcError> {
+        let mut line = String::new();
+        let bytes_read = self.reader.read_line(&mut line)?;
+        if bytes_read == 0 {
+            return Ok(None);
+        }
+        let value = serde_json::from_str(line.trim_end()).map_err(|e| IpcError::Codec(e.to_string()))?;
+        Ok(Some(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct Ping {
+        seq: u32,
+    }
+
+    #[test]
+    fn sent_messages_round_trip_to_the_other_end() {
+        let (a, b) = UnixStream::pair().unwrap();
+        let mut sender = Channel::new(a).unwrap();
+        let mut receiver = Channel::new(b).unwrap();
+
+        sender.send(&Ping { seq: 7 }).unwrap();
+        let received: Ping = receiver.recv().unwrap().unwrap();
+        assert_eq!(received, Ping { seq: 7 });
+    }
+
+    #[test]
+    fn recv_returns_none_once_the_peer_hangs_up() {
+        let (a, b) = UnixStream::pair().unwrap();
+        drop(a);
+        let mut receiver = Channel::new(b).unwrap();
+        let received: Option<Ping> = receiver.recv().unwrap();
+        assert!(received.is_none());
+    }
+}
@@ -0,0 +1,22 @@
+//! Out-of-process helpers, supervised by the shell: a typed IPC
+//! [`Channel`] over a Unix domain socket, and a [`Supervisor`] that
+//! spawns a helper's process, restarts it if it crashes, and samples its
+//! memory usage for the task manager.
+//!
+//! `crynn-email` and `crynn-vpn` are the two helpers this is meant for —
+//! running `EmailClient` and `VpnManager` out of process so a crash in
+//! either can't take down the shell and so their memory shows up as its
+//! own line in `about:performance`. Neither ships as a standalone binary
+//! yet; this crate defines the supervision contract a helper binary has
+//! to satisfy (accept a socket path argument, speak newline-delimited
+//! JSON on it) so wiring one up later doesn't touch this code.
+//!
+//! Socket-path IPC is Unix-specific; a Windows build would swap
+//! [`Channel`] for one backed by a named pipe behind the same
+//! send/recv API.
+
+mod channel;
+mod supervisor;
+
+pub use channel::Channel;
+pub use supervisor::Supervisor;
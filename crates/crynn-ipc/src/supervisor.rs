@@ -0,0 +1,150 @@
+use std::collections::HashMap;
+use std::process::{Child, Command};
+
+use crynn_error::IpcError;
+
+struct Helper {
+    program: String,
+    args: Vec<String>,
+    child: Option<Child>,
+    restarts: u32,
+}
+
+/// Spawns and supervises named helper processes: if one exits, the next
+/// [`Supervisor::poll`] respawns it with the same command and counts the
+/// restart, so a crashed helper comes back without the shell needing to
+/// notice anything beyond calling `poll` periodically.
+#[derive(Default)]
+pub struct Supervisor {
+    helpers: HashMap<String, Helper>,
+}
+
+impl Supervisor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Spawns `program` under `name`. Later restarts reuse `program` and
+    /// `args` exactly, so whatever socket path or account id the helper
+    /// needs should already be baked into `args`.
+    pub fn spawn(&mut self, name: impl Into<String>, program: impl Into<String>, args: Vec<String>) -> Result<(), IpcError> {
+        let name = name.into();
+        let program = program.into();
+        let child = Command::new(&program)
+            .args(&args)
+            .spawn()
+            .map_err(|e| IpcError::SpawnFailed { helper: name.clone(), reason: e.to_string() })?;
+        self.helpers.insert(
+            name,
+            Helper {
+                program,
+                args,
+                child: Some(child),
+                restarts: 0,
+            },
+        );
+        Ok(())
+    }
+
+    /// Checks every supervised helper and respawns any that have exited.
+    /// Call periodically (e.g. once per shell tick) so a crash is
+    /// noticed and recovered from promptly.
+    pub fn poll(&mut self) {
+        for (name, helper) in self.helpers.iter_mut() {
+            let exit_status = match &mut helper.child {
+                Some(child) => child.try_wait().ok().flatten(),
+                None => None,
+            };
+            let Some(status) = exit_status else { continue };
+
+            helper.child = None;
+            helper.restarts += 1;
+            tracing::warn!(
+                helper = %name,
+                exit_code = ?status.code(),
+                restarts = helper.restarts,
+                "helper process exited, restarting"
+            );
+            match Command::new(&helper.program).args(&helper.args).spawn() {
+                Ok(child) => helper.child = Some(child),
+                Err(e) => tracing::error!(helper = %name, error = %e, "failed to restart helper"),
+            }
+        }
+    }
+
+    pub fn restart_count(&self, name: &str) -> u32 {
+        self.helpers.get(name).map(|h| h.restarts).unwrap_or(0)
+    }
+
+    pub fn pid(&self, name: &str) -> Option<u32> {
+        self.helpers.get(name).and_then(|h| h.child.as_ref()).map(|c| c.id())
+    }
+
+    /// Best-effort resident memory for a supervised helper, read from
+    /// `/proc/<pid>/status` on Linux — the cheap option until a real
+    /// cross-platform profiler hook lands. Returns 0 if the helper isn't
+    /// running or the platform isn't Linux.
+    pub fn memory_bytes(&self, name: &str) -> u64 {
+        self.pid(name).and_then(read_proc_rss).unwrap_or(0)
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn read_proc_rss(pid: u32) -> Option<u64> {
+    let status = std::fs::read_to_string(format!("/proc/{pid}/status")).ok()?;
+    for line in status.lines() {
+        if let Some(rest) = line.strip_prefix("VmRSS:") {
+            let kb: u64 = rest.trim().trim_end_matches("kB").trim().parse().ok()?;
+            return Some(kb * 1024);
+        }
+    }
+    None
+}
+
+#[cfg(not(target_os = "linux"))]
+fn read_proc_rss(_pid: u32) -> Option<u64> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread::sleep;
+    use std::time::Duration;
+
+    #[test]
+    fn spawn_tracks_a_running_process() {
+        let mut supervisor = Supervisor::new();
+        supervisor.spawn("sleeper", "sleep", vec!["5".to_string()]).unwrap();
+        assert!(supervisor.pid("sleeper").is_some());
+        assert_eq!(supervisor.restart_count("sleeper"), 0);
+    }
+
+    #[test]
+    fn poll_restarts_a_helper_that_exited() {
+        let mut supervisor = Supervisor::new();
+        supervisor.spawn("flaky", "true", vec![]).unwrap();
+
+        // Give the short-lived process time to exit before polling.
+        sleep(Duration::from_millis(100));
+        supervisor.poll();
+
+        assert_eq!(supervisor.restart_count("flaky"), 1);
+        assert!(supervisor.pid("flaky").is_some());
+    }
+
+    #[test]
+    fn poll_is_a_no_op_for_a_still_running_helper() {
+        let mut supervisor = Supervisor::new();
+        supervisor.spawn("sleeper", "sleep", vec!["5".to_string()]).unwrap();
+        supervisor.poll();
+        assert_eq!(supervisor.restart_count("sleeper"), 0);
+    }
+
+    #[test]
+    fn spawn_reports_an_unknown_program_as_a_spawn_failure() {
+        let mut supervisor = Supervisor::new();
+        let err = supervisor.spawn("missing", "crynn-this-binary-does-not-exist", vec![]);
+        assert!(err.is_err());
+    }
+}
@@ -0,0 +1,139 @@
+//! Shared `tracing` setup for both shells: a rotating file writer under the
+//! profile's data dir, a reloadable log level driven by settings, and an
+//! in-memory ring buffer backing the `about:logs` viewer.
+
+use std::io;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+use tracing_appender::non_blocking::WorkerGuard;
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+use tracing_subscriber::filter::EnvFilter;
+use tracing_subscriber::fmt::MakeWriter;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::reload;
+
+const MAX_BUFFERED_LINES: usize = 2000;
+
+/// Recent log lines kept in memory for `about:logs`, oldest first.
+#[derive(Clone, Default)]
+pub struct LogBuffer(Arc<Mutex<Vec<String>>>);
+
+impl LogBuffer {
+    pub fn lines(&self) -> Vec<String> {
+        self.0.lock().expect("log buffer mutex poisoned").clone()
+    }
+
+    fn push(&self, line: String) {
+        let mut lines = self.0.lock().expect("log buffer mutex poisoned");
+        lines.push(line);
+        if lines.len() > MAX_BUFFERED_LINES {
+            let overflow = lines.len() - MAX_BUFFERED_LINES;
+            lines.drain(..overflow);
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct BufferWriter(LogBuffer);
+
+impl io::Write for BufferWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if let Ok(text) = std::str::from_utf8(buf) {
+            for line in text.lines() {
+                self.0.push(line.to_string());
+            }
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<'a> MakeWriter<'a> for LogBuffer {
+    type Writer = BufferWriter;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        BufferWriter(self.clone())
+    }
+}
+
+type FilterHandle = reload::Handle<EnvFilter, tracing_subscriber::Registry>;
+
+/// Keeps the things a running shell needs to adjust logging at runtime and
+/// to keep the file writer flushing on drop.
+pub struct LogHandle {
+    filter_handle: FilterHandle,
+    buffer: LogBuffer,
+    _file_guard: WorkerGuard,
+}
+
+impl LogHandle {
+    /// Changes the active log level without restarting the process, e.g.
+    /// when the user flips a setting from `info` to `debug`.
+    pub fn set_level(&self, level: &str) -> Result<(), String> {
+        let filter = EnvFilter::try_new(level).map_err(|e| e.to_string())?;
+        self.filter_handle
+            .reload(filter)
+            .map_err(|e| e.to_string())
+    }
+
+    /// Snapshot of recent log lines for the `about:logs` viewer.
+    pub fn recent_lines(&self) -> Vec<String> {
+        self.buffer.lines()
+    }
+}
+
+/// Initializes the global tracing subscriber: a daily-rotating file under
+/// `data_dir/logs`, plus the in-memory buffer the log viewer reads from.
+/// Must be called once, as early as possible in `main`.
+pub fn init(data_dir: &Path, default_level: &str) -> io::Result<LogHandle> {
+    let logs_dir = data_dir.join("logs");
+    std::fs::create_dir_all(&logs_dir)?;
+
+    let file_appender = RollingFileAppender::new(Rotation::DAILY, &logs_dir, "crynn.log");
+    let (non_blocking, file_guard) = tracing_appender::non_blocking(file_appender);
+
+    let buffer = LogBuffer::default();
+
+    let env_filter = EnvFilter::try_new(default_level)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+    let (filter_layer, filter_handle) = reload::Layer::new(env_filter);
+
+    let file_layer = tracing_subscriber::fmt::layer().with_writer(non_blocking);
+    let buffer_layer = tracing_subscriber::fmt::layer()
+        .with_ansi(false)
+        .with_writer(buffer.clone());
+
+    let subscriber = tracing_subscriber::registry()
+        .with(filter_layer)
+        .with(file_layer)
+        .with(buffer_layer);
+
+    tracing::subscriber::set_global_default(subscriber)
+        .map_err(io::Error::other)?;
+
+    Ok(LogHandle {
+        filter_handle,
+        buffer,
+        _file_guard: file_guard,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn buffer_caps_at_max_lines_by_dropping_the_oldest() {
+        let buffer = LogBuffer::default();
+        for i in 0..(MAX_BUFFERED_LINES + 10) {
+            buffer.push(format!("line {i}"));
+        }
+        let lines = buffer.lines();
+        assert_eq!(lines.len(), MAX_BUFFERED_LINES);
+        assert_eq!(lines.first().unwrap(), "line 10");
+    }
+}
@@ -0,0 +1,19 @@
+use serde::{Deserialize, Serialize};
+
+/// One recordable telemetry event. Deliberately coarse-grained: these are
+/// the categories `about:telemetry`'s summary breaks down into, not a
+/// general-purpose analytics schema.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum MetricEvent {
+    FeatureUsed { feature: String },
+    PageLoad { load_time_ms: u64 },
+    Crash { component: String },
+    BudgetViolation { budget: String, limit: u64, actual: u64 },
+}
+
+/// A [`MetricEvent`] with when it happened, in Unix seconds.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordedEvent {
+    pub event: MetricEvent,
+    pub recorded_at: u64,
+}
@@ -0,0 +1,18 @@
+//! Local-only telemetry: feature usage, page-load timings, crash counts,
+//! and resource-budget violations. Recording is opt-in and, unlike
+//! `crynn-sync`, there is deliberately no code path in this crate that
+//! sends anything anywhere — the only way events leave a
+//! [`MetricsStore`] is the explicit [`MetricsStore::export`] call behind
+//! the `about:telemetry` viewer's export button.
+//!
+//! Timestamps are supplied by the caller rather than read from the clock
+//! in here, matching the convention used by `StorageManager::maybe_auto_lock`
+//! and `crynn-i18n::format_date`: it keeps recording a pure, testable
+//! operation and leaves the choice of clock (wall clock vs. a fake one in
+//! tests) to whoever is calling in.
+
+mod event;
+mod store;
+
+pub use event::{MetricEvent, RecordedEvent};
+pub use store::MetricsStore;
@@ -0,0 +1,201 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::event::{MetricEvent, RecordedEvent};
+
+/// Local log of telemetry events, persisted as a single JSON file.
+///
+/// Recording is opt-in and enforced here rather than left to callers to
+/// remember: a store loaded with `enabled: false` silently drops every
+/// [`MetricsStore::record`] call, so forgetting to check the user's
+/// setting before recording can't leak an event anyway.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct MetricsStore {
+    events: Vec<RecordedEvent>,
+    #[serde(skip)]
+    path: Option<PathBuf>,
+    #[serde(skip)]
+    enabled: bool,
+}
+
+impl MetricsStore {
+    /// Loads events from `path` if it exists, otherwise starts empty. The
+    /// store remembers `path` so later [`MetricsStore::save`] calls don't
+    /// need to repeat it.
+    pub fn load(path: impl Into<PathBuf>, enabled: bool) -> io::Result<Self> {
+        let path = path.into();
+        let mut store = match fs::read_to_string(&path) {
+            Ok(contents) => serde_json::from_str::<MetricsStore>(&contents)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => MetricsStore::default(),
+            Err(e) => return Err(e),
+        };
+        store.path = Some(path);
+        store.enabled = enabled;
+        Ok(store)
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Flips the opt-in setting. Does not clear events already recorded
+    /// while enabled; clearing is a separate, explicit user action.
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    /// Appends `event`, or does nothing if telemetry is disabled.
+    pub fn record(&mut self, event: MetricEvent, recorded_at: u64) {
+        if !self.enabled {
+            return;
+        }
+        self.events.push(RecordedEvent { event, recorded_at });
+    }
+
+    pub fn events(&self) -> &[RecordedEvent] {
+        &self.events
+    }
+
+    pub fn clear(&mut self) {
+        self.events.clear();
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let contents = serde_json::to_string_pretty(self)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        fs::write(path, contents)
+    }
+
+    /// Writes every recorded event to `path` as pretty JSON. The only
+    /// code path in this crate that moves telemetry data anywhere other
+    /// than the local store file; the `about:telemetry` export button
+    /// calls this and nothing else.
+    pub fn export(&self, path: impl AsRef<std::path::Path>) -> io::Result<()> {
+        let contents = serde_json::to_string_pretty(&self.events)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        fs::write(path, contents)
+    }
+
+    pub fn feature_counts(&self) -> HashMap<String, u32> {
+        let mut counts = HashMap::new();
+        for recorded in &self.events {
+            if let MetricEvent::FeatureUsed { feature } = &recorded.event {
+                *counts.entry(feature.clone()).or_insert(0) += 1;
+            }
+        }
+        counts
+    }
+
+    pub fn crash_count(&self) -> usize {
+        self.events
+            .iter()
+            .filter(|r| matches!(r.event, MetricEvent::Crash { .. }))
+            .count()
+    }
+
+    pub fn budget_violation_count(&self) -> usize {
+        self.events
+            .iter()
+            .filter(|r| matches!(r.event, MetricEvent::BudgetViolation { .. }))
+            .count()
+    }
+
+    pub fn average_page_load_ms(&self) -> Option<f64> {
+        let loads: Vec<u64> = self
+            .events
+            .iter()
+            .filter_map(|r| match &r.event {
+                MetricEvent::PageLoad { load_time_ms } => Some(*load_time_ms),
+                _ => None,
+            })
+            .collect();
+        if loads.is_empty() {
+            return None;
+        }
+        Some(loads.iter().sum::<u64>() as f64 / loads.len() as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn record_is_a_no_op_when_disabled() {
+        let mut store = MetricsStore::default();
+        store.record(MetricEvent::FeatureUsed { feature: "zoom".to_string() }, 1);
+        assert!(store.events().is_empty());
+    }
+
+    #[test]
+    fn record_appends_when_enabled() {
+        let mut store = MetricsStore::default();
+        store.set_enabled(true);
+        store.record(MetricEvent::FeatureUsed { feature: "zoom".to_string() }, 1);
+        assert_eq!(store.events().len(), 1);
+    }
+
+    #[test]
+    fn feature_counts_tally_by_name() {
+        let mut store = MetricsStore::default();
+        store.set_enabled(true);
+        store.record(MetricEvent::FeatureUsed { feature: "zoom".to_string() }, 1);
+        store.record(MetricEvent::FeatureUsed { feature: "zoom".to_string() }, 2);
+        store.record(MetricEvent::FeatureUsed { feature: "bookmarks".to_string() }, 3);
+
+        let counts = store.feature_counts();
+        assert_eq!(counts.get("zoom"), Some(&2));
+        assert_eq!(counts.get("bookmarks"), Some(&1));
+    }
+
+    #[test]
+    fn average_page_load_ms_ignores_other_event_kinds() {
+        let mut store = MetricsStore::default();
+        store.set_enabled(true);
+        store.record(MetricEvent::PageLoad { load_time_ms: 100 }, 1);
+        store.record(MetricEvent::PageLoad { load_time_ms: 300 }, 2);
+        store.record(MetricEvent::Crash { component: "renderer".to_string() }, 3);
+
+        assert_eq!(store.average_page_load_ms(), Some(200.0));
+    }
+
+    #[test]
+    fn average_page_load_ms_is_none_without_any_loads() {
+        let store = MetricsStore::default();
+        assert_eq!(store.average_page_load_ms(), None);
+    }
+
+    #[test]
+    fn save_and_load_round_trip() {
+        let dir = std::env::temp_dir().join(format!("crynn-metrics-test-{}", std::process::id()));
+        let path = dir.join("telemetry.json");
+        let mut store = MetricsStore::load(&path, true).unwrap();
+        store.record(MetricEvent::Crash { component: "renderer".to_string() }, 42);
+        store.save().unwrap();
+
+        let reloaded = MetricsStore::load(&path, true).unwrap();
+        assert_eq!(reloaded.crash_count(), 1);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn disabling_does_not_clear_already_recorded_events() {
+        let mut store = MetricsStore::default();
+        store.set_enabled(true);
+        store.record(MetricEvent::Crash { component: "renderer".to_string() }, 1);
+        store.set_enabled(false);
+        assert_eq!(store.crash_count(), 1);
+    }
+}
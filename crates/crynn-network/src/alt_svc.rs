@@ -0,0 +1,139 @@
+use std::collections::HashMap;
+
+use crate::connection::Protocol;
+
+/// How long an `Alt-Svc` advertisement with no explicit `ma` parameter
+/// is trusted for, per RFC 7838 §3's default.
+const DEFAULT_MAX_AGE_SECS: u64 = 86_400;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct AltSvcEntry {
+    protocol: Protocol,
+    expires_at: u64,
+}
+
+/// Per-origin `Alt-Svc` protocol hints, with the header's own
+/// expiration honored: an origin that advertised HTTP/3 an hour ago
+/// with `ma=60` shouldn't still short-circuit [`crate::NetworkManager::select_protocol`]
+/// today. Has no sense of "now" of its own — every lookup and insert
+/// takes it as a parameter, the same as `crynn-storage`'s
+/// `retention::plan` takes `now` rather than calling
+/// `SystemTime::now()` itself.
+#[derive(Debug, Default)]
+pub struct AltSvcCache {
+    entries: HashMap<String, AltSvcEntry>,
+}
+
+impl AltSvcCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parses `header_value` — an `Alt-Svc` response header received
+    /// from `origin` — and records its highest-priority protocol hint,
+    /// expiring `ma` seconds after `now` (or [`DEFAULT_MAX_AGE_SECS`] if
+    /// the header didn't specify one). `Alt-Svc: clear` removes
+    /// whatever was previously recorded for `origin` instead, per the
+    /// header's own spec rather than being treated as an unparseable
+    /// entry.
+    pub fn record(&mut self, origin: &str, header_value: &str, now: u64) {
+        if header_value.trim().eq_ignore_ascii_case("clear") {
+            self.entries.remove(origin);
+            return;
+        }
+        let Some(entry) = header_value.split(',').filter_map(|part| parse_entry(part, now)).max_by_key(|entry| protocol_rank(entry.protocol)) else {
+            return;
+        };
+        self.entries.insert(origin.to_string(), entry);
+    }
+
+    /// The protocol `origin` most recently advertised, if that
+    /// advertisement hasn't expired as of `now`. `None` for an origin
+    /// never recorded, or whose advertisement has expired — either way,
+    /// the caller has no advertisement to trust and falls back to
+    /// probing or its own heuristic.
+    pub fn protocol_for(&self, origin: &str, now: u64) -> Option<Protocol> {
+        self.entries.get(origin).filter(|entry| entry.expires_at > now).map(|entry| entry.protocol)
+    }
+}
+
+fn protocol_rank(protocol: Protocol) -> u8 {
+    match protocol {
+        Protocol::Http3 => 2,
+        Protocol::Http2 => 1,
+        Protocol::Http1 => 0,
+    }
+}
+
+/// Parses one comma-separated alternative from an `Alt-Svc` value, e.g.
+/// `h3=":443"; ma=3600`. Only the protocol token and `ma` parameter
+/// matter here — the `:443` authority is irrelevant until this crate
+/// has a real transport to dial it with.
+fn parse_entry(part: &str, now: u64) -> Option<AltSvcEntry> {
+    let mut fields = part.split(';').map(str::trim);
+    let protocol = match fields.next()?.split('=').next()?.trim() {
+        "h3" | "h3-29" | "h3-27" => Protocol::Http3,
+        "h2" => Protocol::Http2,
+        _ => return None,
+    };
+    let max_age = fields
+        .find_map(|field| field.strip_prefix("ma="))
+        .and_then(|value| value.trim().parse().ok())
+        .unwrap_or(DEFAULT_MAX_AGE_SECS);
+    Some(AltSvcEntry { protocol, expires_at: now.saturating_add(max_age) })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn an_unrecorded_origin_has_no_advertised_protocol() {
+        let cache = AltSvcCache::new();
+        assert_eq!(cache.protocol_for("https://example.com", 0), None);
+    }
+
+    #[test]
+    fn a_recorded_advertisement_is_honored_before_it_expires() {
+        let mut cache = AltSvcCache::new();
+        cache.record("https://example.com", "h3=\":443\"; ma=3600", 1_000);
+        assert_eq!(cache.protocol_for("https://example.com", 1_500), Some(Protocol::Http3));
+    }
+
+    #[test]
+    fn an_advertisement_stops_being_honored_once_it_expires() {
+        let mut cache = AltSvcCache::new();
+        cache.record("https://example.com", "h3=\":443\"; ma=60", 1_000);
+        assert_eq!(cache.protocol_for("https://example.com", 1_061), None);
+    }
+
+    #[test]
+    fn an_advertisement_with_no_ma_falls_back_to_the_default_max_age() {
+        let mut cache = AltSvcCache::new();
+        cache.record("https://example.com", "h3=\":443\"", 0);
+        assert_eq!(cache.protocol_for("https://example.com", DEFAULT_MAX_AGE_SECS - 1), Some(Protocol::Http3));
+        assert_eq!(cache.protocol_for("https://example.com", DEFAULT_MAX_AGE_SECS + 1), None);
+    }
+
+    #[test]
+    fn the_highest_priority_alternative_wins_when_several_are_advertised() {
+        let mut cache = AltSvcCache::new();
+        cache.record("https://example.com", "h2=\":443\"; ma=3600, h3=\":443\"; ma=3600", 0);
+        assert_eq!(cache.protocol_for("https://example.com", 0), Some(Protocol::Http3));
+    }
+
+    #[test]
+    fn clear_removes_a_previously_recorded_advertisement() {
+        let mut cache = AltSvcCache::new();
+        cache.record("https://example.com", "h3=\":443\"; ma=3600", 0);
+        cache.record("https://example.com", "clear", 0);
+        assert_eq!(cache.protocol_for("https://example.com", 0), None);
+    }
+
+    #[test]
+    fn an_unrecognized_alternative_is_ignored_rather_than_recorded() {
+        let mut cache = AltSvcCache::new();
+        cache.record("https://example.com", "h1=\":443\"; ma=3600", 0);
+        assert_eq!(cache.protocol_for("https://example.com", 0), None);
+    }
+}
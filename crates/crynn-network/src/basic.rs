@@ -0,0 +1,28 @@
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+
+use crate::credential::Credential;
+
+/// Builds the `Authorization: Basic <token>` header value for `credential`,
+/// per RFC 7617.
+pub fn header_value(credential: &Credential) -> String {
+    let token = STANDARD.encode(format!("{}:{}", credential.username, credential.password));
+    format!("Basic {token}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_the_rfc_7617_example() {
+        let credential = Credential {
+            username: "Aladdin".to_string(),
+            password: "open sesame".to_string(),
+        };
+        assert_eq!(
+            header_value(&credential),
+            "Basic QWxhZGRpbjpvcGVuIHNlc2FtZQ=="
+        );
+    }
+}
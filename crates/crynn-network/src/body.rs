@@ -0,0 +1,138 @@
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::PathBuf;
+
+use crate::multipart::MultipartBody;
+
+/// Called as a request body is read, so a progress bar can track a large
+/// upload. `sent` is the cumulative byte count read so far; `total` is the
+/// full body size when it's known upfront (always true for bytes, a file,
+/// or a multipart body; `None` for an arbitrary reader of unknown length).
+pub type ProgressCallback = Box<dyn FnMut(u64, Option<u64>) + Send>;
+
+/// Where a request body's bytes come from. [`BodySource::File`] and
+/// [`BodySource::Reader`] exist so a large upload — an email attachment,
+/// say — doesn't have to be loaded into memory up front the way a plain
+/// `Vec<u8>` body would.
+pub enum BodySource {
+    Bytes(Vec<u8>),
+    /// Streamed from disk as the request body is read, rather than
+    /// loaded eagerly.
+    File(PathBuf),
+    /// Any other byte source, e.g. a pipe from a compressor. The real
+    /// transport would read this directly into the socket; until one
+    /// exists, [`BodySource::read_all`] is what drives it.
+    Reader(Box<dyn Read + Send>),
+    Multipart(MultipartBody),
+}
+
+const CHUNK_SIZE: usize = 64 * 1024;
+
+impl BodySource {
+    /// Total size in bytes if it's known without reading the body, e.g.
+    /// for progress bars that want a percentage rather than just a byte
+    /// count.
+    pub fn size_bytes(&self) -> Option<u64> {
+        match self {
+            BodySource::Bytes(bytes) => Some(bytes.len() as u64),
+            BodySource::File(path) => std::fs::metadata(path).ok().map(|m| m.len()),
+            BodySource::Reader(_) => None,
+            BodySource::Multipart(body) => body.size_bytes(),
+        }
+    }
+
+    /// Reads the whole body into memory, calling `on_progress` after each
+    /// chunk. A real streaming transport would hand chunks straight to
+    /// the socket instead of buffering them here; this is the contract
+    /// callers (the shell's upload UI, the email attachment path) drive
+    /// off of until one is wired in.
+    pub fn read_all(self, on_progress: Option<ProgressCallback>) -> io::Result<Vec<u8>> {
+        let total = self.size_bytes();
+        let mut on_progress = on_progress;
+        match self {
+            BodySource::Bytes(bytes) => {
+                if let Some(cb) = on_progress.as_mut() {
+                    cb(bytes.len() as u64, total);
+                }
+                Ok(bytes)
+            }
+            BodySource::File(path) => read_in_chunks(&mut File::open(path)?, total, on_progress),
+            BodySource::Reader(mut reader) => read_in_chunks(&mut *reader, total, on_progress),
+            BodySource::Multipart(body) => {
+                let bytes = body.into_bytes()?;
+                if let Some(cb) = on_progress.as_mut() {
+                    cb(bytes.len() as u64, total);
+                }
+                Ok(bytes)
+            }
+        }
+    }
+}
+
+fn read_in_chunks(reader: &mut dyn Read, total: Option<u64>, mut on_progress: Option<ProgressCallback>) -> io::Result<Vec<u8>> {
+    let mut buf = Vec::new();
+    let mut chunk = [0u8; CHUNK_SIZE];
+    let mut sent = 0u64;
+    loop {
+        let n = reader.read(&mut chunk)?;
+        if n == 0 {
+            break;
+        }
+        buf.extend_from_slice(&chunk[..n]);
+        sent += n as u64;
+        if let Some(cb) = on_progress.as_mut() {
+            cb(sent, total);
+        }
+    }
+    Ok(buf)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bytes_source_reports_its_length_up_front() {
+        let source = BodySource::Bytes(vec![1, 2, 3, 4]);
+        assert_eq!(source.size_bytes(), Some(4));
+    }
+
+    #[test]
+    fn reader_source_has_no_known_length() {
+        let source = BodySource::Reader(Box::new(std::io::Cursor::new(vec![1, 2, 3])));
+        assert_eq!(source.size_bytes(), None);
+    }
+
+    #[test]
+    fn read_all_reports_progress_in_increasing_chunks() {
+        let data = vec![0u8; CHUNK_SIZE * 2 + 10];
+        let source = BodySource::Reader(Box::new(std::io::Cursor::new(data.clone())));
+
+        let progress = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let progress_clone = progress.clone();
+        let callback: ProgressCallback = Box::new(move |sent, total| {
+            progress_clone.lock().unwrap().push((sent, total));
+        });
+
+        let read = source.read_all(Some(callback)).unwrap();
+        assert_eq!(read, data);
+
+        let calls = progress.lock().unwrap();
+        assert_eq!(calls.len(), 3);
+        assert_eq!(calls.last().unwrap().0, data.len() as u64);
+        assert!(calls.windows(2).all(|w| w[0].0 < w[1].0));
+    }
+
+    #[test]
+    fn read_all_on_a_file_streams_without_loading_it_in_one_call() {
+        let path = std::env::temp_dir().join(format!("crynn-body-test-{}.bin", std::process::id()));
+        std::fs::write(&path, vec![7u8; 100]).unwrap();
+
+        let source = BodySource::File(path.clone());
+        assert_eq!(source.size_bytes(), Some(100));
+        let bytes = BodySource::File(path.clone()).read_all(None).unwrap();
+        assert_eq!(bytes, vec![7u8; 100]);
+
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -0,0 +1,295 @@
+use std::collections::{HashMap, VecDeque};
+
+use crate::etag::ETag;
+use crate::ranges::{ByteRange, RangeSet};
+
+/// Which budget a cached response counts against. Media (images,
+/// video, audio) is evicted far more aggressively than documents and
+/// scripts — see [`HttpCache::insert`] — so one large video download
+/// can't evict the assets a tab that's already open is still rendering.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CacheTier {
+    DocumentAndScript,
+    Media,
+}
+
+/// How much further under budget [`HttpCache::insert`] evicts the media
+/// tier than it strictly needs to, so a second large download right
+/// after the first doesn't immediately trigger another eviction pass.
+/// Document/script entries get no such headroom — they're evicted only
+/// down to their budget, exactly.
+const MEDIA_EVICTION_HEADROOM_RATIO: f64 = 0.2;
+
+#[derive(Debug, Clone, PartialEq)]
+struct CacheEntry {
+    url: String,
+    size_bytes: u64,
+    etag: Option<ETag>,
+}
+
+/// A resource whose 206 Partial Content responses haven't yet combined
+/// into a complete entry, tracked separately from [`CacheEntry`] so an
+/// in-progress range download doesn't count against either tier's budget
+/// until [`RangeSet::is_complete`] says it's whole.
+#[derive(Debug, Clone)]
+struct PartialEntry {
+    ranges: RangeSet,
+    etag: Option<ETag>,
+}
+
+/// Usage for one [`CacheTier`], for the same kind of `about:cache`-style
+/// page [`crate::ConnectionStats`] is meant to back for `about:network`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TierStats {
+    pub entry_count: usize,
+    pub used_bytes: u64,
+    pub budget_bytes: u64,
+}
+
+/// Both tiers' usage at once, what [`HttpCache::stats`] reports.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StorageStats {
+    pub document_and_script: TierStats,
+    pub media: TierStats,
+}
+
+/// A quota-aware HTTP cache split into a [`CacheTier::DocumentAndScript`]
+/// tier and a [`CacheTier::Media`] tier, each with its own byte budget,
+/// so a single large video doesn't evict every other site asset a page
+/// needs to keep rendering correctly. No real response bytes are held
+/// here — like [`crate::ConnectionStats`], this is the bookkeeping a
+/// real cache would report through, keyed by URL rather than storing
+/// anything a caller couldn't re-fetch.
+#[derive(Debug)]
+pub struct HttpCache {
+    document_budget_bytes: u64,
+    media_budget_bytes: u64,
+    document_entries: VecDeque<CacheEntry>,
+    media_entries: VecDeque<CacheEntry>,
+    partial_entries: HashMap<String, PartialEntry>,
+}
+
+impl HttpCache {
+    pub fn new(document_budget_bytes: u64, media_budget_bytes: u64) -> Self {
+        Self {
+            document_budget_bytes,
+            media_budget_bytes,
+            document_entries: VecDeque::new(),
+            media_entries: VecDeque::new(),
+            partial_entries: HashMap::new(),
+        }
+    }
+
+    /// Records that `url` (`size_bytes` large) was cached under `tier`,
+    /// evicting the oldest entries in that tier until it's back under
+    /// budget — down to [`MEDIA_EVICTION_HEADROOM_RATIO`] below budget
+    /// for [`CacheTier::Media`], exactly to budget for
+    /// [`CacheTier::DocumentAndScript`].
+    pub fn insert(&mut self, tier: CacheTier, url: impl Into<String>, size_bytes: u64) {
+        self.entries_mut(tier).push_back(CacheEntry { url: url.into(), size_bytes, etag: None });
+        self.evict(tier);
+    }
+
+    /// Records one 206 Partial Content response of a `total_len`-byte
+    /// resource, merging `range` into whatever of it has already arrived.
+    /// Once the merged ranges cover the resource end-to-end, it's promoted
+    /// into a normal fully-cached entry under `tier` (evicting the same
+    /// way [`Self::insert`] does) and stops being tracked as partial —
+    /// this is the "combine ranges into complete entries" a media player
+    /// seeking through a cached video relies on.
+    pub fn insert_range(&mut self, tier: CacheTier, url: impl Into<String>, range: ByteRange, total_len: u64, etag: Option<&str>) {
+        let url = url.into();
+        let partial = self.partial_entries.entry(url.clone()).or_insert_with(|| PartialEntry { ranges: RangeSet::new(), etag: None });
+        partial.ranges.insert(range);
+        if let Some(etag) = etag.and_then(ETag::parse) {
+            partial.etag = Some(etag);
+        }
+        if !partial.ranges.is_complete(total_len) {
+            return;
+        }
+        let etag = self.partial_entries.remove(&url).and_then(|partial| partial.etag);
+        self.entries_mut(tier).push_back(CacheEntry { url, size_bytes: total_len, etag });
+        self.evict(tier);
+    }
+
+    /// The byte ranges of `url` received so far but not yet combined into
+    /// a complete entry. Empty once [`Self::insert_range`] has promoted it
+    /// (or if `url` was never partially cached at all).
+    pub fn cached_ranges(&self, url: &str) -> &[ByteRange] {
+        self.partial_entries.get(url).map(|partial| partial.ranges.ranges()).unwrap_or(&[])
+    }
+
+    /// Records `etag_header` (an `ETag` response header value) against an
+    /// already fully-cached `url`. A no-op if `url` isn't cached or the
+    /// header doesn't parse.
+    pub fn set_etag(&mut self, url: &str, etag_header: &str) {
+        let Some(etag) = ETag::parse(etag_header) else {
+            return;
+        };
+        if let Some(entry) = self.document_entries.iter_mut().chain(self.media_entries.iter_mut()).find(|entry| entry.url == url) {
+            entry.etag = Some(etag);
+        }
+    }
+
+    /// Whether a cached `url` is still fresh given a revalidation
+    /// response's `etag_header`, using weak comparison — see
+    /// [`ETag::weakly_matches`]. `false` if `url` isn't cached, has no
+    /// recorded `ETag`, or `etag_header` doesn't parse.
+    pub fn is_fresh(&self, url: &str, etag_header: &str) -> bool {
+        let Some(candidate) = ETag::parse(etag_header) else {
+            return false;
+        };
+        self.document_entries
+            .iter()
+            .chain(self.media_entries.iter())
+            .find(|entry| entry.url == url)
+            .and_then(|entry| entry.etag.as_ref())
+            .is_some_and(|stored| stored.weakly_matches(&candidate))
+    }
+
+    /// Whether `url` is currently cached, in either tier.
+    pub fn contains(&self, url: &str) -> bool {
+        self.document_entries.iter().chain(self.media_entries.iter()).any(|entry| entry.url == url)
+    }
+
+    pub fn stats(&self) -> StorageStats {
+        StorageStats {
+            document_and_script: tier_stats(&self.document_entries, self.document_budget_bytes),
+            media: tier_stats(&self.media_entries, self.media_budget_bytes),
+        }
+    }
+
+    fn evict(&mut self, tier: CacheTier) {
+        let target = match tier {
+            CacheTier::Media => (self.media_budget_bytes as f64 * (1.0 - MEDIA_EVICTION_HEADROOM_RATIO)) as u64,
+            CacheTier::DocumentAndScript => self.document_budget_bytes,
+        };
+        let entries = self.entries_mut(tier);
+        while total_bytes(entries) > target {
+            if entries.pop_front().is_none() {
+                break;
+            }
+        }
+    }
+
+    fn entries_mut(&mut self, tier: CacheTier) -> &mut VecDeque<CacheEntry> {
+        match tier {
+            CacheTier::DocumentAndScript => &mut self.document_entries,
+            CacheTier::Media => &mut self.media_entries,
+        }
+    }
+}
+
+fn total_bytes(entries: &VecDeque<CacheEntry>) -> u64 {
+    entries.iter().map(|entry| entry.size_bytes).sum()
+}
+
+fn tier_stats(entries: &VecDeque<CacheEntry>, budget_bytes: u64) -> TierStats {
+    TierStats { entry_count: entries.len(), used_bytes: total_bytes(entries), budget_bytes }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn entries_under_budget_are_not_evicted() {
+        let mut cache = HttpCache::new(1_000, 1_000);
+        cache.insert(CacheTier::DocumentAndScript, "https://example.com/app.js", 100);
+        assert!(cache.contains("https://example.com/app.js"));
+        assert_eq!(cache.stats().document_and_script.entry_count, 1);
+    }
+
+    #[test]
+    fn document_tier_evicts_the_oldest_entry_exactly_down_to_budget() {
+        let mut cache = HttpCache::new(150, 1_000);
+        cache.insert(CacheTier::DocumentAndScript, "https://example.com/a.html", 100);
+        cache.insert(CacheTier::DocumentAndScript, "https://example.com/b.html", 100);
+
+        assert!(!cache.contains("https://example.com/a.html"));
+        assert!(cache.contains("https://example.com/b.html"));
+        assert_eq!(cache.stats().document_and_script.used_bytes, 100);
+    }
+
+    #[test]
+    fn media_tier_evicts_past_budget_for_headroom() {
+        let mut cache = HttpCache::new(1_000, 100);
+        cache.insert(CacheTier::Media, "https://example.com/video1.mp4", 50);
+        cache.insert(CacheTier::Media, "https://example.com/video2.mp4", 50);
+
+        assert!(!cache.contains("https://example.com/video1.mp4"));
+        assert!(cache.contains("https://example.com/video2.mp4"));
+        assert!(cache.stats().media.used_bytes <= 80);
+    }
+
+    #[test]
+    fn a_large_media_download_does_not_evict_the_document_tier() {
+        let mut cache = HttpCache::new(1_000, 100);
+        cache.insert(CacheTier::DocumentAndScript, "https://example.com/app.js", 500);
+        cache.insert(CacheTier::Media, "https://example.com/huge-video.mp4", 10_000);
+
+        assert!(cache.contains("https://example.com/app.js"));
+        assert_eq!(cache.stats().document_and_script.used_bytes, 500);
+    }
+
+    #[test]
+    fn stats_reports_each_tiers_own_budget() {
+        let cache = HttpCache::new(2_000, 500);
+        let stats = cache.stats();
+        assert_eq!(stats.document_and_script.budget_bytes, 2_000);
+        assert_eq!(stats.media.budget_bytes, 500);
+    }
+
+    #[test]
+    fn a_partial_range_is_not_yet_cached() {
+        let mut cache = HttpCache::new(1_000, 1_000);
+        cache.insert_range(CacheTier::Media, "https://example.com/video.mp4", ByteRange::new(0, 499), 1_000, None);
+
+        assert!(!cache.contains("https://example.com/video.mp4"));
+        assert_eq!(cache.cached_ranges("https://example.com/video.mp4"), &[ByteRange::new(0, 499)]);
+    }
+
+    #[test]
+    fn ranges_combining_into_the_whole_resource_promote_it_to_a_complete_entry() {
+        let mut cache = HttpCache::new(1_000, 2_000);
+        let url = "https://example.com/video.mp4";
+        cache.insert_range(CacheTier::Media, url, ByteRange::new(0, 499), 1_000, None);
+        cache.insert_range(CacheTier::Media, url, ByteRange::new(500, 999), 1_000, None);
+
+        assert!(cache.contains(url));
+        assert!(cache.cached_ranges(url).is_empty());
+        assert_eq!(cache.stats().media.used_bytes, 1_000);
+    }
+
+    #[test]
+    fn a_completed_range_download_carries_its_etag_into_the_entry() {
+        let mut cache = HttpCache::new(1_000, 2_000);
+        let url = "https://example.com/video.mp4";
+        cache.insert_range(CacheTier::Media, url, ByteRange::new(0, 999), 1_000, Some("W/\"v1\""));
+
+        assert!(cache.is_fresh(url, "W/\"v1\""));
+        assert!(!cache.is_fresh(url, "W/\"v2\""));
+    }
+
+    #[test]
+    fn set_etag_records_a_tag_against_an_already_cached_entry() {
+        let mut cache = HttpCache::new(1_000, 1_000);
+        cache.insert(CacheTier::DocumentAndScript, "https://example.com/app.js", 100);
+        cache.set_etag("https://example.com/app.js", "\"abc\"");
+
+        assert!(cache.is_fresh("https://example.com/app.js", "W/\"abc\""));
+    }
+
+    #[test]
+    fn an_uncached_url_is_never_fresh() {
+        let cache = HttpCache::new(1_000, 1_000);
+        assert!(!cache.is_fresh("https://example.com/missing.js", "\"abc\""));
+    }
+
+    #[test]
+    fn a_cached_entry_with_no_recorded_etag_is_never_fresh() {
+        let mut cache = HttpCache::new(1_000, 1_000);
+        cache.insert(CacheTier::DocumentAndScript, "https://example.com/app.js", 100);
+        assert!(!cache.is_fresh("https://example.com/app.js", "\"abc\""));
+    }
+}
@@ -0,0 +1,137 @@
+use std::collections::HashSet;
+
+/// Per-phase timeouts for one request, replacing a single end-to-end
+/// timeout with the three phases it's actually made of: how long to wait
+/// for a connection, how long to wait for each read to make progress, and
+/// a hard ceiling on the request as a whole. Fed by
+/// `crynn_config::NetworkConfig`'s matching three fields.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PhaseTimeouts {
+    pub connect_ms: u64,
+    pub read_ms: u64,
+    pub total_ms: u64,
+}
+
+impl PhaseTimeouts {
+    pub fn new(connect_ms: u64, read_ms: u64, total_ms: u64) -> Self {
+        Self { connect_ms, read_ms, total_ms }
+    }
+}
+
+impl Default for PhaseTimeouts {
+    /// Matches `crynn_config::NetworkConfig`'s own defaults.
+    fn default() -> Self {
+        Self { connect_ms: 10_000, read_ms: 30_000, total_ms: 30_000 }
+    }
+}
+
+/// Identifies one in-flight request for cancellation — the shell's stop
+/// button holds onto this after [`CancellationRegistry::begin_request`]
+/// hands it out; it isn't constructible any other way, the same as
+/// `crynn_engine::TabId` is only ever handed out by `TabRegistry::open`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RequestHandle(u64);
+
+/// Tracks which in-flight requests have been cancelled. This crate has
+/// no real transport to abort yet — [`CancellationRegistry::is_cancelled`]
+/// is the contract whatever drives an actual connection attempt checks
+/// between phases (after connecting, after each read) and aborts on,
+/// the same gap every other decision in this crate is a contract around
+/// rather than an implementation of.
+#[derive(Debug, Default)]
+pub struct CancellationRegistry {
+    next_id: u64,
+    in_flight: HashSet<RequestHandle>,
+    cancelled: HashSet<RequestHandle>,
+}
+
+impl CancellationRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a new in-flight request and returns the handle it's
+    /// tracked under until [`Self::finish_request`] drops it.
+    pub fn begin_request(&mut self) -> RequestHandle {
+        let handle = RequestHandle(self.next_id);
+        self.next_id += 1;
+        self.in_flight.insert(handle);
+        handle
+    }
+
+    /// Marks `handle` cancelled. A no-op for a handle that's already
+    /// finished or was never registered — a stop button clicked after
+    /// the request already completed has nothing left to cancel.
+    pub fn cancel(&mut self, handle: RequestHandle) {
+        if self.in_flight.contains(&handle) {
+            self.cancelled.insert(handle);
+        }
+    }
+
+    pub fn is_cancelled(&self, handle: RequestHandle) -> bool {
+        self.cancelled.contains(&handle)
+    }
+
+    /// Drops `handle`'s bookkeeping once the request finishes, cancelled
+    /// or not — called by whatever drove the request, mirroring
+    /// `crynn_engine::TabRegistry::close` dropping a tab's state.
+    pub fn finish_request(&mut self, handle: RequestHandle) {
+        self.in_flight.remove(&handle);
+        self.cancelled.remove(&handle);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_freshly_registered_request_is_not_cancelled() {
+        let mut registry = CancellationRegistry::new();
+        let handle = registry.begin_request();
+        assert!(!registry.is_cancelled(handle));
+    }
+
+    #[test]
+    fn cancelling_an_in_flight_request_marks_it_cancelled() {
+        let mut registry = CancellationRegistry::new();
+        let handle = registry.begin_request();
+        registry.cancel(handle);
+        assert!(registry.is_cancelled(handle));
+    }
+
+    #[test]
+    fn cancelling_a_request_that_already_finished_is_a_no_op() {
+        let mut registry = CancellationRegistry::new();
+        let handle = registry.begin_request();
+        registry.finish_request(handle);
+        registry.cancel(handle);
+        assert!(!registry.is_cancelled(handle));
+    }
+
+    #[test]
+    fn distinct_requests_get_distinct_handles() {
+        let mut registry = CancellationRegistry::new();
+        let a = registry.begin_request();
+        let b = registry.begin_request();
+        assert_ne!(a, b);
+        registry.cancel(a);
+        assert!(registry.is_cancelled(a));
+        assert!(!registry.is_cancelled(b));
+    }
+
+    #[test]
+    fn finishing_a_request_clears_its_cancellation_state() {
+        let mut registry = CancellationRegistry::new();
+        let handle = registry.begin_request();
+        registry.cancel(handle);
+        registry.finish_request(handle);
+        assert!(!registry.is_cancelled(handle));
+    }
+
+    #[test]
+    fn phase_timeouts_default_to_the_same_values_as_network_config() {
+        let timeouts = PhaseTimeouts::default();
+        assert_eq!(timeouts, PhaseTimeouts::new(10_000, 30_000, 30_000));
+    }
+}
@@ -0,0 +1,133 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use crynn_error::NetworkError;
+
+/// The two schemes this crate knows how to answer. Servers also send
+/// `Negotiate`/`NTLM` challenges in the wild; those fall through
+/// [`parse_challenge`] as `None` rather than being guessed at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuthScheme {
+    Basic,
+    Digest,
+}
+
+impl FromStr for AuthScheme {
+    type Err = NetworkError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "basic" => Ok(Self::Basic),
+            "digest" => Ok(Self::Digest),
+            other => Err(NetworkError::UnsupportedAuthScheme { scheme: other.to_string() }),
+        }
+    }
+}
+
+/// One parsed `WWW-Authenticate`/`Proxy-Authenticate` challenge.
+///
+/// A server can send more than one challenge for the same request (as
+/// separate header field lines, per RFC 7235) offering a choice of
+/// schemes; callers parse each line with [`parse_challenge`] and pick the
+/// strongest one they can answer.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuthChallenge {
+    pub scheme: AuthScheme,
+    pub realm: String,
+    pub params: HashMap<String, String>,
+}
+
+impl AuthChallenge {
+    pub fn param(&self, key: &str) -> Option<&str> {
+        self.params.get(key).map(String::as_str)
+    }
+}
+
+/// Parses a single `WWW-Authenticate`/`Proxy-Authenticate` header value,
+/// e.g. `Digest realm="example", nonce="abc", qop="auth"`. Returns `None`
+/// for schemes this crate doesn't support rather than guessing at an
+/// unknown challenge shape.
+pub fn parse_challenge(header_value: &str) -> Option<AuthChallenge> {
+    let header_value = header_value.trim();
+    let (scheme_token, rest) = header_value
+        .split_once(char::is_whitespace)
+        .unwrap_or((header_value, ""));
+    let scheme = AuthScheme::from_str(scheme_token).ok()?;
+    let params = parse_params(rest.trim_start());
+    let realm = params.get("realm").cloned().unwrap_or_default();
+    Some(AuthChallenge { scheme, realm, params })
+}
+
+/// Splits `key=value, key2="quoted, value"` pairs on unquoted commas, then
+/// strips surrounding quotes from each value. Good enough for the
+/// auth-param grammar actual servers send; not a general HTTP header
+/// parser.
+fn parse_params(s: &str) -> HashMap<String, String> {
+    let mut params = HashMap::new();
+    for part in split_unquoted(s, ',') {
+        let Some((key, value)) = part.split_once('=') else {
+            continue;
+        };
+        let value = value.trim().trim_matches('"');
+        params.insert(key.trim().to_ascii_lowercase(), value.to_string());
+    }
+    params
+}
+
+fn split_unquoted(s: &str, separator: char) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+    for c in s.chars() {
+        match c {
+            '"' => {
+                in_quotes = !in_quotes;
+                current.push(c);
+            }
+            c if c == separator && !in_quotes => {
+                parts.push(std::mem::take(&mut current));
+            }
+            c => current.push(c),
+        }
+    }
+    if !current.trim().is_empty() {
+        parts.push(current);
+    }
+    parts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_a_basic_challenge() {
+        let challenge = parse_challenge(r#"Basic realm="Protected Area""#).unwrap();
+        assert_eq!(challenge.scheme, AuthScheme::Basic);
+        assert_eq!(challenge.realm, "Protected Area");
+    }
+
+    #[test]
+    fn parses_a_digest_challenge_with_several_params() {
+        let challenge = parse_challenge(
+            r#"Digest realm="example.com", qop="auth", nonce="abc123", opaque="xyz""#,
+        )
+        .unwrap();
+        assert_eq!(challenge.scheme, AuthScheme::Digest);
+        assert_eq!(challenge.realm, "example.com");
+        assert_eq!(challenge.param("nonce"), Some("abc123"));
+        assert_eq!(challenge.param("qop"), Some("auth"));
+        assert_eq!(challenge.param("opaque"), Some("xyz"));
+    }
+
+    #[test]
+    fn quoted_commas_do_not_split_a_param_in_two() {
+        let challenge = parse_challenge(r#"Digest realm="a, b", nonce="n""#).unwrap();
+        assert_eq!(challenge.realm, "a, b");
+    }
+
+    #[test]
+    fn unsupported_schemes_return_none() {
+        assert!(parse_challenge(r#"Negotiate"#).is_none());
+    }
+}
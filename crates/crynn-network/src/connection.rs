@@ -0,0 +1,554 @@
+use std::collections::HashMap;
+use std::net::IpAddr;
+
+use crate::alt_svc::AltSvcCache;
+use crate::cancellation::{CancellationRegistry, RequestHandle};
+use crate::hsts::HstsStore;
+use crate::pool::{CheckoutOutcome, ConnectionId, ConnectionPool, PoolConfig, PoolStats};
+use crate::request_log::{RequestLog, RequestLogEntry};
+use crate::retry::RetryPolicy;
+use crate::ssrf::{PrivateNetworkGuard, RequestOrigin};
+use crate::throttle::{ConditionSimulator, NetworkCondition};
+use crate::tls::{validate_with_overrides, CertificateError, CertificateOverrides, CertificateValidator, OverrideToken};
+
+/// Which HTTP version a connection negotiated. No h2/quinn client is
+/// wired into this crate yet — this is the contract one would report
+/// through, the same way [`crate::AuthPipeline`] is the contract a
+/// transport answers 401s/407s through.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Protocol {
+    Http1,
+    Http2,
+    Http3,
+}
+
+/// A connection-level event worth remembering for protocol-selection
+/// heuristics: an HTTP/2 `GOAWAY`, or the connection just dying outright.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConnectionError {
+    GoAway { debug_data: String },
+    Reset,
+    TimedOut,
+}
+
+/// Point-in-time health of one connection, as an h2/quinn client would
+/// report it. `open_streams` and `congestion_window_bytes` only mean
+/// anything for multiplexed protocols (HTTP/2, HTTP/3); an HTTP/1.1
+/// connection reports zero for both since it has neither.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConnectionStats {
+    pub protocol: Protocol,
+    pub open_streams: u32,
+    pub rtt_estimate_ms: u32,
+    pub congestion_window_bytes: u32,
+    pub errors: Vec<ConnectionError>,
+}
+
+impl ConnectionStats {
+    pub fn new(protocol: Protocol) -> Self {
+        Self { protocol, open_streams: 0, rtt_estimate_ms: 0, congestion_window_bytes: 0, errors: Vec::new() }
+    }
+}
+
+/// Above this many recorded connection errors for an origin, protocol
+/// selection backs off to the next-lower protocol. A real transport hits
+/// this with UDP blocked by a middlebox, or a server that just doesn't
+/// speak HTTP/3 reliably.
+const ERROR_DOWNGRADE_THRESHOLD: usize = 3;
+
+/// Owns per-origin [`ConnectionStats`], the protocol-selection heuristic
+/// built on top of them, and the [`AltSvcCache`] that can override that
+/// heuristic outright. Whatever ends up driving a real connection calls
+/// [`NetworkManager::record_stats`]/[`record_error`] as it goes, and
+/// consults [`NetworkManager::select_protocol`] before its next
+/// connection attempt to the same origin.
+#[derive(Debug, Default)]
+pub struct NetworkManager {
+    connections: HashMap<String, ConnectionStats>,
+    retry_policy: RetryPolicy,
+    private_network_guard: PrivateNetworkGuard,
+    /// Keyed by whatever the caller identifies a tab with — this crate
+    /// has no `TabId` of its own to key by, the same reason
+    /// [`Self::connections`] is keyed by origin string rather than a
+    /// richer type.
+    request_logs: HashMap<String, RequestLog>,
+    alt_svc: AltSvcCache,
+    cancellation: CancellationRegistry,
+    hsts: HstsStore,
+    condition_simulator: ConditionSimulator,
+    pool: ConnectionPool,
+    certificate_overrides: CertificateOverrides,
+}
+
+impl NetworkManager {
+    pub fn retry_policy(&self) -> RetryPolicy {
+        self.retry_policy
+    }
+
+    pub fn set_retry_policy(&mut self, policy: RetryPolicy) {
+        self.retry_policy = policy;
+    }
+
+    pub fn private_network_guard(&self) -> &PrivateNetworkGuard {
+        &self.private_network_guard
+    }
+
+    pub fn private_network_guard_mut(&mut self) -> &mut PrivateNetworkGuard {
+        &mut self.private_network_guard
+    }
+
+    /// Whether a request from `origin` to `ip`, initiated as
+    /// `request_origin`, should be blocked by the SSRF guard — the
+    /// check whatever drives an actual connection attempt runs before
+    /// dialing a resolved address.
+    pub fn should_block_request(&self, origin: &str, ip: IpAddr, request_origin: RequestOrigin) -> bool {
+        self.private_network_guard.should_block(origin, ip, request_origin)
+    }
+
+    pub fn record_stats(&mut self, origin: impl Into<String>, stats: ConnectionStats) {
+        self.connections.insert(origin.into(), stats);
+    }
+
+    /// Appends `entry` to `tab`'s network log, for the shell's network
+    /// panel. Whatever drives an actual connection calls this once a
+    /// request finishes (or fails outright).
+    pub fn record_request(&mut self, tab: impl Into<String>, entry: RequestLogEntry) {
+        self.request_logs.entry(tab.into()).or_default().push(entry);
+    }
+
+    /// Every entry logged for `tab`, oldest first. Empty for a tab with
+    /// no logged requests yet, same as an unseen origin reporting no
+    /// [`ConnectionStats`].
+    pub fn request_log(&self, tab: &str) -> impl Iterator<Item = &RequestLogEntry> {
+        self.request_logs.get(tab).into_iter().flat_map(RequestLog::entries)
+    }
+
+    pub fn record_error(&mut self, origin: &str, error: ConnectionError) {
+        self.connections
+            .entry(origin.to_string())
+            .or_insert_with(|| ConnectionStats::new(Protocol::Http1))
+            .errors
+            .push(error);
+    }
+
+    /// The most recently recorded connection health for `origin`, for the
+    /// `about:network` page and for [`NetworkManager::preferred_protocol`].
+    pub fn connection_stats(&self, origin: &str) -> Option<&ConnectionStats> {
+        self.connections.get(origin)
+    }
+
+    /// Every origin with recorded connection health, sorted by origin for
+    /// a stable display order, for the `about:network` page's full list.
+    pub fn connections(&self) -> Vec<(&str, &ConnectionStats)> {
+        let mut entries: Vec<(&str, &ConnectionStats)> =
+            self.connections.iter().map(|(origin, stats)| (origin.as_str(), stats)).collect();
+        entries.sort_by_key(|(origin, _)| *origin);
+        entries
+    }
+
+    /// The protocol to try next for `origin`: its last negotiated
+    /// protocol, downgraded one step per [`ERROR_DOWNGRADE_THRESHOLD`]
+    /// errors recorded against it. An origin never seen before gets
+    /// HTTP/3, the same optimistic guess a fresh connection attempt would
+    /// make before learning otherwise.
+    pub fn preferred_protocol(&self, origin: &str) -> Protocol {
+        let Some(stats) = self.connections.get(origin) else {
+            return Protocol::Http3;
+        };
+        let mut protocol = stats.protocol;
+        for _ in 0..(stats.errors.len() / ERROR_DOWNGRADE_THRESHOLD) {
+            protocol = downgrade(protocol);
+        }
+        protocol
+    }
+
+    /// Records an `Alt-Svc` response header received from `origin`, so
+    /// [`Self::select_protocol`] can upgrade later requests to it
+    /// without re-probing. Whatever parses response headers calls this;
+    /// this crate has no real transport to receive one from yet.
+    pub fn record_alt_svc(&mut self, origin: impl Into<String>, header_value: &str, now: u64) {
+        self.alt_svc.record(&origin.into(), header_value, now);
+    }
+
+    /// Whether `origin` currently has an unexpired HTTP/3 advertisement
+    /// on file, for callers that only care about that one protocol
+    /// (e.g. deciding whether it's worth offering 0-RTT resumption).
+    /// Callers choosing which protocol to actually connect with should
+    /// use [`Self::select_protocol`] instead.
+    pub fn supports_http3(&self, origin: &str, now: u64) -> bool {
+        self.alt_svc.protocol_for(origin, now) == Some(Protocol::Http3)
+    }
+
+    /// The protocol to open the next connection to `origin` with: an
+    /// unexpired `Alt-Svc` advertisement on file, trusted outright since
+    /// the server has already told us it speaks that protocol; failing
+    /// that, [`Self::preferred_protocol`]'s heuristic guess from past
+    /// connection stats. This is the entry point a real transport
+    /// should call — [`Self::preferred_protocol`] on its own only ever
+    /// reflects past probing, never an actual `Alt-Svc` advertisement.
+    pub fn select_protocol(&self, origin: &str, now: u64) -> Protocol {
+        self.alt_svc.protocol_for(origin, now).unwrap_or_else(|| self.preferred_protocol(origin))
+    }
+
+    /// Registers a new in-flight request and returns the handle a caller
+    /// (the shell's stop button) cancels it with.
+    pub fn begin_request(&mut self) -> RequestHandle {
+        self.cancellation.begin_request()
+    }
+
+    /// Cancels `handle`'s request — what the shell calls when the user
+    /// clicks stop on an in-flight navigation.
+    pub fn cancel_request(&mut self, handle: RequestHandle) {
+        self.cancellation.cancel(handle);
+    }
+
+    /// Whether `handle`'s request has been cancelled. Whatever drives an
+    /// actual connection attempt checks this between phases and aborts
+    /// if it's set.
+    pub fn is_request_cancelled(&self, handle: RequestHandle) -> bool {
+        self.cancellation.is_cancelled(handle)
+    }
+
+    /// Drops `handle`'s cancellation bookkeeping once its request
+    /// finishes, cancelled or not.
+    pub fn finish_request(&mut self, handle: RequestHandle) {
+        self.cancellation.finish_request(handle);
+    }
+
+    /// Records an `Strict-Transport-Security` response header received
+    /// from `host`. Whatever parses response headers calls this; this
+    /// crate has no real transport to receive one from yet, the same
+    /// gap [`Self::record_alt_svc`] is already a contract around.
+    pub fn record_hsts(&mut self, host: impl Into<String>, header_value: &str, now: u64) {
+        self.hsts.record(&host.into(), header_value, now);
+    }
+
+    /// Whether `host` must be fetched over HTTPS as of `now` — whatever
+    /// resolves a URL to dial should consult this before choosing
+    /// `http://` over `https://`.
+    pub fn requires_https(&self, host: &str, now: u64) -> bool {
+        self.hsts.requires_https(host, now)
+    }
+
+    /// Forces `condition` onto every request from `tab`, for the
+    /// devtools-like network-condition panel.
+    pub fn set_network_condition(&mut self, tab: impl Into<String>, condition: NetworkCondition) {
+        self.condition_simulator.set_condition(tab, condition);
+    }
+
+    /// Reverts `tab` to its real, unsimulated network behavior.
+    pub fn clear_network_condition(&mut self, tab: &str) {
+        self.condition_simulator.clear_condition(tab);
+    }
+
+    pub fn network_condition(&self, tab: &str) -> Option<NetworkCondition> {
+        self.condition_simulator.condition_for(tab)
+    }
+
+    /// Whether a request from `tab` should fail outright under its
+    /// simulated condition, given `roll` — see
+    /// [`ConditionSimulator::should_fail`] for why the draw is a
+    /// parameter rather than taken internally.
+    pub fn should_fail_request(&self, tab: &str, roll: f64) -> bool {
+        self.condition_simulator.should_fail(tab, roll)
+    }
+
+    /// Extra latency to add before a request from `tab` proceeds, from
+    /// its simulated condition.
+    pub fn simulated_latency_ms(&self, tab: &str) -> u64 {
+        self.condition_simulator.extra_latency_ms(tab)
+    }
+
+    pub fn pool_config(&self) -> PoolConfig {
+        self.pool.config()
+    }
+
+    /// Replaces the connection pool's limits outright — e.g. from a
+    /// settings change — discarding whatever pooled connections already
+    /// existed under the old config, the same as
+    /// [`Self::set_retry_policy`] replaces the retry policy wholesale
+    /// rather than mutating it in place.
+    pub fn set_pool_config(&mut self, config: PoolConfig) {
+        self.pool = ConnectionPool::new(config);
+    }
+
+    /// Checks out a connection for `origin` from the pool — whatever
+    /// drives an actual connection attempt calls this first to decide
+    /// whether it can reuse one or has to dial fresh.
+    pub fn checkout_connection(&mut self, origin: &str) -> CheckoutOutcome {
+        self.pool.checkout(origin)
+    }
+
+    /// Returns `conn` to `origin`'s idle set once the caller is done with
+    /// it, so a later request to the same origin can reuse it.
+    pub fn release_connection(&mut self, origin: &str, conn: ConnectionId, now: u64) {
+        self.pool.release(origin, conn, now);
+    }
+
+    /// Drops idle pooled connections past the configured idle timeout.
+    /// Whatever owns the event loop calls this periodically.
+    pub fn evict_idle_connections(&mut self, now: u64) {
+        self.pool.evict_idle(now);
+    }
+
+    pub fn pool_stats(&self, origin: &str) -> PoolStats {
+        self.pool.pool_stats(origin)
+    }
+
+    /// Every host with recorded pool activity, sorted by origin, for the
+    /// memory profiler's connection-pool breakdown.
+    pub fn pool_stats_all(&self) -> Vec<(&str, PoolStats)> {
+        self.pool.stats()
+    }
+
+    /// Mints a scoped [`OverrideToken`] letting `host` proceed past
+    /// `error` once the shell's interstitial has the user's consent.
+    pub fn mint_certificate_override(&mut self, host: &str, error: CertificateError) -> OverrideToken {
+        self.certificate_overrides.mint(host, error)
+    }
+
+    pub fn revoke_certificate_override(&mut self, host: &str) {
+        self.certificate_overrides.revoke(host);
+    }
+
+    /// Validates `host` through `validator`, honoring a previously minted
+    /// override for the same host/error pair — see
+    /// [`crate::validate_with_overrides`].
+    pub fn validate_certificate(&self, validator: &dyn CertificateValidator, host: &str, token: Option<&OverrideToken>) -> Result<(), CertificateError> {
+        validate_with_overrides(validator, &self.certificate_overrides, host, token)
+    }
+}
+
+fn downgrade(protocol: Protocol) -> Protocol {
+    match protocol {
+        Protocol::Http3 => Protocol::Http2,
+        Protocol::Http2 | Protocol::Http1 => Protocol::Http1,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unseen_origin_prefers_http3() {
+        let manager = NetworkManager::default();
+        assert_eq!(manager.preferred_protocol("https://example.com"), Protocol::Http3);
+    }
+
+    #[test]
+    fn errors_past_the_threshold_downgrade_the_protocol() {
+        let mut manager = NetworkManager::default();
+        manager.record_stats("https://example.com", ConnectionStats::new(Protocol::Http3));
+        for _ in 0..ERROR_DOWNGRADE_THRESHOLD {
+            manager.record_error("https://example.com", ConnectionError::TimedOut);
+        }
+        assert_eq!(manager.preferred_protocol("https://example.com"), Protocol::Http2);
+    }
+
+    #[test]
+    fn enough_errors_downgrade_all_the_way_to_http1() {
+        let mut manager = NetworkManager::default();
+        manager.record_stats("https://example.com", ConnectionStats::new(Protocol::Http3));
+        for _ in 0..(ERROR_DOWNGRADE_THRESHOLD * 2) {
+            manager.record_error("https://example.com", ConnectionError::GoAway { debug_data: String::new() });
+        }
+        assert_eq!(manager.preferred_protocol("https://example.com"), Protocol::Http1);
+    }
+
+    #[test]
+    fn request_log_is_empty_for_a_tab_with_no_logged_requests() {
+        let manager = NetworkManager::default();
+        assert_eq!(manager.request_log("tab-0").count(), 0);
+    }
+
+    #[test]
+    fn recorded_requests_come_back_in_order_for_their_own_tab_only() {
+        let mut manager = NetworkManager::default();
+        manager.record_request(
+            "tab-0",
+            RequestLogEntry {
+                method: "GET".to_string(),
+                url: "https://example.com/a".to_string(),
+                status: Some(200),
+                size_bytes: 512,
+                duration_ms: 20,
+                protocol: Protocol::Http2,
+                cache_hit: false,
+            },
+        );
+        manager.record_request(
+            "tab-0",
+            RequestLogEntry {
+                method: "GET".to_string(),
+                url: "https://example.com/b".to_string(),
+                status: Some(200),
+                size_bytes: 256,
+                duration_ms: 10,
+                protocol: Protocol::Http2,
+                cache_hit: true,
+            },
+        );
+        manager.record_request(
+            "tab-1",
+            RequestLogEntry {
+                method: "GET".to_string(),
+                url: "https://example.com/other-tab".to_string(),
+                status: Some(200),
+                size_bytes: 128,
+                duration_ms: 5,
+                protocol: Protocol::Http1,
+                cache_hit: false,
+            },
+        );
+
+        let logged: Vec<&str> = manager.request_log("tab-0").map(|e| e.url.as_str()).collect();
+        assert_eq!(logged, vec!["https://example.com/a", "https://example.com/b"]);
+    }
+
+    #[test]
+    fn connection_stats_reports_the_last_recorded_snapshot() {
+        let mut manager = NetworkManager::default();
+        let mut stats = ConnectionStats::new(Protocol::Http2);
+        stats.open_streams = 4;
+        manager.record_stats("https://example.com", stats);
+
+        let reported = manager.connection_stats("https://example.com").unwrap();
+        assert_eq!(reported.open_streams, 4);
+        assert_eq!(reported.protocol, Protocol::Http2);
+    }
+
+    #[test]
+    fn an_origin_with_no_recorded_advertisement_does_not_support_http3() {
+        let manager = NetworkManager::default();
+        assert!(!manager.supports_http3("https://example.com", 0));
+    }
+
+    #[test]
+    fn recording_an_alt_svc_advertisement_makes_the_origin_report_http3_support() {
+        let mut manager = NetworkManager::default();
+        manager.record_alt_svc("https://example.com", "h3=\":443\"; ma=3600", 0);
+        assert!(manager.supports_http3("https://example.com", 0));
+        assert!(!manager.supports_http3("https://other.example.com", 0));
+    }
+
+    #[test]
+    fn select_protocol_trusts_an_unexpired_alt_svc_advertisement_over_the_heuristic() {
+        let mut manager = NetworkManager::default();
+        manager.record_stats("https://example.com", ConnectionStats::new(Protocol::Http1));
+        manager.record_alt_svc("https://example.com", "h3=\":443\"; ma=3600", 0);
+
+        assert_eq!(manager.select_protocol("https://example.com", 1_000), Protocol::Http3);
+    }
+
+    #[test]
+    fn select_protocol_falls_back_to_the_heuristic_once_the_advertisement_expires() {
+        let mut manager = NetworkManager::default();
+        manager.record_stats("https://example.com", ConnectionStats::new(Protocol::Http2));
+        manager.record_alt_svc("https://example.com", "h3=\":443\"; ma=60", 0);
+
+        assert_eq!(manager.select_protocol("https://example.com", 1_000), Protocol::Http2);
+    }
+
+    #[test]
+    fn cancel_request_is_reflected_through_the_manager() {
+        let mut manager = NetworkManager::default();
+        let handle = manager.begin_request();
+        assert!(!manager.is_request_cancelled(handle));
+
+        manager.cancel_request(handle);
+
+        assert!(manager.is_request_cancelled(handle));
+    }
+
+    #[test]
+    fn finishing_a_request_clears_its_cancellation_state_through_the_manager() {
+        let mut manager = NetworkManager::default();
+        let handle = manager.begin_request();
+        manager.cancel_request(handle);
+
+        manager.finish_request(handle);
+
+        assert!(!manager.is_request_cancelled(handle));
+    }
+
+    #[test]
+    fn recording_hsts_is_reflected_through_the_manager() {
+        let mut manager = NetworkManager::default();
+        assert!(!manager.requires_https("example.org", 0));
+
+        manager.record_hsts("example.org", "max-age=3600", 0);
+
+        assert!(manager.requires_https("example.org", 1_000));
+    }
+
+    #[test]
+    fn a_simulated_offline_condition_is_reflected_through_the_manager() {
+        let mut manager = NetworkManager::default();
+        assert!(!manager.should_fail_request("tab-0", 0.0));
+
+        manager.set_network_condition("tab-0", NetworkCondition::Offline);
+
+        assert!(manager.should_fail_request("tab-0", 0.0));
+        assert_eq!(manager.network_condition("tab-0"), Some(NetworkCondition::Offline));
+    }
+
+    #[test]
+    fn clearing_a_simulated_condition_reverts_the_tab_through_the_manager() {
+        let mut manager = NetworkManager::default();
+        manager.set_network_condition("tab-0", NetworkCondition::Offline);
+
+        manager.clear_network_condition("tab-0");
+
+        assert!(!manager.should_fail_request("tab-0", 0.0));
+        assert_eq!(manager.network_condition("tab-0"), None);
+    }
+
+    #[test]
+    fn checking_out_and_releasing_a_connection_is_reflected_through_the_manager() {
+        let mut manager = NetworkManager::default();
+        let CheckoutOutcome::Created(conn) = manager.checkout_connection("https://example.com") else {
+            panic!("expected a freshly created connection");
+        };
+        assert_eq!(manager.pool_stats("https://example.com").active, 1);
+
+        manager.release_connection("https://example.com", conn, 0);
+
+        assert_eq!(manager.pool_stats("https://example.com"), PoolStats { active: 0, idle: 1, created: 1, reused: 0 });
+    }
+
+    #[test]
+    fn setting_the_pool_config_is_reflected_through_the_manager() {
+        let mut manager = NetworkManager::default();
+        manager.set_pool_config(PoolConfig { max_connections_per_host: 1, idle_timeout_ms: 1_000 });
+        assert!(matches!(manager.checkout_connection("https://example.com"), CheckoutOutcome::Created(_)));
+        assert_eq!(manager.checkout_connection("https://example.com"), CheckoutOutcome::AtLimit);
+    }
+
+    struct AlwaysExpired;
+
+    impl CertificateValidator for AlwaysExpired {
+        fn validate(&self, _host: &str) -> Result<(), CertificateError> {
+            Err(CertificateError::Expired)
+        }
+    }
+
+    #[test]
+    fn a_minted_certificate_override_is_honored_through_the_manager() {
+        let mut manager = NetworkManager::default();
+        assert_eq!(manager.validate_certificate(&AlwaysExpired, "example.com", None), Err(CertificateError::Expired));
+
+        let token = manager.mint_certificate_override("example.com", CertificateError::Expired);
+
+        assert_eq!(manager.validate_certificate(&AlwaysExpired, "example.com", Some(&token)), Ok(()));
+    }
+
+    #[test]
+    fn revoking_a_certificate_override_through_the_manager_removes_it() {
+        let mut manager = NetworkManager::default();
+        let token = manager.mint_certificate_override("example.com", CertificateError::Expired);
+        manager.revoke_certificate_override("example.com");
+
+        assert_eq!(manager.validate_certificate(&AlwaysExpired, "example.com", Some(&token)), Err(CertificateError::Expired));
+    }
+}
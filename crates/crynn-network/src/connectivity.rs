@@ -0,0 +1,75 @@
+use crynn_error::NetworkError;
+
+use crate::response::NetworkResponse;
+
+/// The generate_204-style endpoint this crate probes to tell a genuinely
+/// open network apart from a captive portal answering in its place.
+/// Real browsers point this at a host they control so a portal can't
+/// forge the expected response; any host works the same way here since
+/// this crate doesn't own a domain of its own yet.
+pub const CONNECTIVITY_CHECK_URL: &str = "http://connectivity-check.crynn.example/generate_204";
+
+/// What probing [`CONNECTIVITY_CHECK_URL`] found.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Connectivity {
+    /// Got back exactly the empty 204 the check expects: the network is
+    /// open.
+    Online,
+    /// Got back something other than an empty 204 — a captive portal
+    /// intercepting the request to serve its own login page, most often
+    /// a 200 with a body or a redirect to one.
+    CaptivePortal { portal_url: Option<String> },
+    /// The probe itself couldn't complete (DNS, TLS, connection refused,
+    /// timeout): there's no route to the internet at all, captive or
+    /// otherwise.
+    Offline,
+}
+
+/// Classifies a response to [`CONNECTIVITY_CHECK_URL`].
+pub fn check_connectivity(response: &NetworkResponse) -> Connectivity {
+    if response.status == 204 && response.body.is_empty() {
+        return Connectivity::Online;
+    }
+    Connectivity::CaptivePortal { portal_url: response.header("Location").map(str::to_string) }
+}
+
+/// Classifies the outcome of probing [`CONNECTIVITY_CHECK_URL`], whether
+/// the probe got a response at all or failed outright.
+pub fn check_connectivity_result(result: Result<&NetworkResponse, &NetworkError>) -> Connectivity {
+    match result {
+        Ok(response) => check_connectivity(response),
+        Err(_) => Connectivity::Offline,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn an_empty_204_is_online() {
+        let response = NetworkResponse::new(204, Vec::new());
+        assert_eq!(check_connectivity(&response), Connectivity::Online);
+    }
+
+    #[test]
+    fn a_200_with_a_body_is_a_captive_portal() {
+        let response = NetworkResponse::new(200, b"<html>login</html>".to_vec());
+        assert_eq!(check_connectivity(&response), Connectivity::CaptivePortal { portal_url: None });
+    }
+
+    #[test]
+    fn a_redirect_reports_the_portal_url() {
+        let response = NetworkResponse::new(302, Vec::new()).with_header("Location", "https://portal.example.com/login");
+        assert_eq!(
+            check_connectivity(&response),
+            Connectivity::CaptivePortal { portal_url: Some("https://portal.example.com/login".to_string()) }
+        );
+    }
+
+    #[test]
+    fn a_failed_probe_is_offline() {
+        let err = NetworkError::ConnectionRefused { host: "connectivity-check.crynn.example".to_string() };
+        assert_eq!(check_connectivity_result(Err(&err)), Connectivity::Offline);
+    }
+}
@@ -0,0 +1,105 @@
+use crate::request::NetworkRequest;
+use crate::response::NetworkResponse;
+
+/// Looks up and stores cookies on behalf of a request. No real transport
+/// is wired into this crate yet — the same contract-over-implementation
+/// split as [`crate::CredentialProvider`]/[`crate::DnsLookup`], so
+/// [`attach_cookies`]/[`record_set_cookie`] can be exercised in tests
+/// without depending on `crynn-cookies`, which this crate otherwise has
+/// no reason to pull in. `crynn_cookies::CookieManager` is the real
+/// implementation; its `request_get`/`request_set` already take the same
+/// `top_level_site`/`domain`/`at` shape these methods pass through.
+pub trait CookieJar {
+    /// The `Cookie` header value to send for `domain` under
+    /// `top_level_site` as of `at`, or `None` if there's nothing to
+    /// attach.
+    fn cookie_header(&mut self, top_level_site: &str, domain: &str, at: u64) -> Option<String>;
+
+    /// Stores one `Set-Cookie` header value received from `domain`
+    /// under `top_level_site` as of `at`.
+    fn store_set_cookie(&mut self, top_level_site: &str, domain: &str, header_value: &str, at: u64);
+}
+
+/// Attaches a `Cookie` header to `request` from whatever `jar` has
+/// stored for `domain` under `top_level_site`, if anything. Whatever
+/// drives an actual connection attempt should call this right before
+/// sending, the same point [`crate::apply_accept_encoding`] hooks in at.
+pub fn attach_cookies(request: NetworkRequest, jar: &mut dyn CookieJar, top_level_site: &str, domain: &str, at: u64) -> NetworkRequest {
+    match jar.cookie_header(top_level_site, domain, at) {
+        Some(header) => request.with_header("Cookie", header),
+        None => request,
+    }
+}
+
+/// Hands every `Set-Cookie` header on `response` to `jar` to store, for
+/// `domain` under `top_level_site`. Whatever parses response headers
+/// should call this once a real transport reads one; this crate has no
+/// real transport to receive one from yet, the same gap
+/// [`crate::NetworkManager::record_alt_svc`] is already a contract
+/// around.
+pub fn record_set_cookie(response: &NetworkResponse, jar: &mut dyn CookieJar, top_level_site: &str, domain: &str, at: u64) {
+    for header_value in response.headers_named("Set-Cookie") {
+        jar.store_set_cookie(top_level_site, domain, header_value, at);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default)]
+    struct FixedJar {
+        stored: Vec<(String, String, String, u64)>,
+        to_send: Option<String>,
+    }
+
+    impl CookieJar for FixedJar {
+        fn cookie_header(&mut self, _top_level_site: &str, _domain: &str, _at: u64) -> Option<String> {
+            self.to_send.clone()
+        }
+
+        fn store_set_cookie(&mut self, top_level_site: &str, domain: &str, header_value: &str, at: u64) {
+            self.stored.push((top_level_site.to_string(), domain.to_string(), header_value.to_string(), at));
+        }
+    }
+
+    #[test]
+    fn attach_cookies_is_a_no_op_when_the_jar_has_nothing_to_offer() {
+        let mut jar = FixedJar::default();
+        let request = NetworkRequest::new("GET", "https://example.com");
+        let request = attach_cookies(request, &mut jar, "example.com", "example.com", 0);
+        assert!(request.headers.is_empty());
+    }
+
+    #[test]
+    fn attach_cookies_sets_the_cookie_header_from_the_jar() {
+        let mut jar = FixedJar { to_send: Some("session=abc".to_string()), ..Default::default() };
+        let request = NetworkRequest::new("GET", "https://example.com");
+        let request = attach_cookies(request, &mut jar, "example.com", "example.com", 0);
+        assert_eq!(request.headers, vec![("Cookie".to_string(), "session=abc".to_string())]);
+    }
+
+    #[test]
+    fn record_set_cookie_stores_every_set_cookie_header_on_the_response() {
+        let mut jar = FixedJar::default();
+        let response = NetworkResponse::new(200, Vec::new())
+            .with_header("Set-Cookie", "a=1")
+            .with_header("Set-Cookie", "b=2");
+        record_set_cookie(&response, &mut jar, "example.com", "example.com", 1_000);
+        assert_eq!(
+            jar.stored,
+            vec![
+                ("example.com".to_string(), "example.com".to_string(), "a=1".to_string(), 1_000),
+                ("example.com".to_string(), "example.com".to_string(), "b=2".to_string(), 1_000),
+            ]
+        );
+    }
+
+    #[test]
+    fn record_set_cookie_does_nothing_when_the_response_has_none() {
+        let mut jar = FixedJar::default();
+        let response = NetworkResponse::new(200, Vec::new());
+        record_set_cookie(&response, &mut jar, "example.com", "example.com", 0);
+        assert!(jar.stored.is_empty());
+    }
+}
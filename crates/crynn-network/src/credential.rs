@@ -0,0 +1,38 @@
+use crate::challenge::AuthScheme;
+
+/// A username/password pair to answer a challenge with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Credential {
+    pub username: String,
+    pub password: String,
+}
+
+/// Identifies who a challenge came from, so a saved credential (or a
+/// prompt already answered this session) can be matched against a later
+/// challenge for the same realm without asking again.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AuthTarget {
+    pub host: String,
+    pub realm: String,
+    pub proxy: bool,
+}
+
+/// Answers challenges on behalf of the user: a shell implementation
+/// prompts and optionally saves the result to the credentials database;
+/// a headless caller (tests, the CLI) can answer from a fixed table.
+///
+/// [`AuthPipeline`](crate::AuthPipeline) never talks to the password
+/// store directly — it only calls through this trait, the same way
+/// `crynn-plugins`'s `PluginHost` only calls through `PluginRuntime`.
+pub trait CredentialProvider {
+    /// Returns a credential to try for `target`/`scheme`, or `None` if
+    /// the user cancelled the prompt.
+    fn provide(&mut self, target: &AuthTarget, scheme: AuthScheme) -> Option<Credential>;
+
+    /// Called once a retried request with `credential` succeeds, so a
+    /// shell that offers to remember the password can do so. Default
+    /// implementation does nothing.
+    fn on_authenticated(&mut self, target: &AuthTarget, credential: &Credential) {
+        let _ = (target, credential);
+    }
+}
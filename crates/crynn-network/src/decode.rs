@@ -0,0 +1,121 @@
+use std::io::Read;
+
+use crynn_error::NetworkError;
+
+use crate::request::NetworkRequest;
+
+/// Sent as the `Accept-Encoding` header on every request whose
+/// [`crate::NetworkRequest::auto_decompress`] hasn't been turned off,
+/// advertising every encoding [`decode`] can undo.
+pub const ACCEPT_ENCODING: &str = "gzip, br, zstd";
+
+/// Size of the intermediate buffer `brotli-decompressor`'s reader uses
+/// internally; unrelated to the size of `body` or its decoded output.
+const BROTLI_BUFFER_SIZE: usize = 4096;
+
+/// Decompresses `body` according to `content_encoding` — a `Content-Encoding`
+/// response header value — so a cached or inspected body is always the
+/// page's real bytes rather than whatever the wire format happened to be.
+/// `identity` and an absent/empty encoding pass `body` through unchanged.
+/// An encoding this crate doesn't recognize is an error rather than a
+/// silent pass-through, since a caller trusting the result to be decoded
+/// could otherwise do something unsafe with bytes that are still
+/// compressed.
+pub fn decode(content_encoding: &str, body: &[u8]) -> Result<Vec<u8>, NetworkError> {
+    match content_encoding.trim() {
+        "" | "identity" => Ok(body.to_vec()),
+        "gzip" | "x-gzip" => decode_gzip(body),
+        "br" => decode_brotli(body),
+        "zstd" => decode_zstd(body),
+        other => Err(NetworkError::UnsupportedContentEncoding { encoding: other.to_string() }),
+    }
+}
+
+/// Sets `request`'s `Accept-Encoding` header to [`ACCEPT_ENCODING`], unless
+/// [`NetworkRequest::auto_decompress`] is off — a request that isn't going
+/// to decode the response shouldn't ask the server to compress it either.
+/// Mirrors [`crate::apply_resistant_headers`]'s shape: whether to call this
+/// for a given request is already decided by the time it's called, this
+/// function only knows how to apply the header once it is.
+pub fn apply_accept_encoding(mut request: NetworkRequest) -> NetworkRequest {
+    if !request.auto_decompress {
+        return request;
+    }
+    request.headers.retain(|(name, _)| name != "Accept-Encoding");
+    request.headers.push(("Accept-Encoding".to_string(), ACCEPT_ENCODING.to_string()));
+    request
+}
+
+fn decode_gzip(body: &[u8]) -> Result<Vec<u8>, NetworkError> {
+    let mut decoded = Vec::new();
+    flate2::read::GzDecoder::new(body)
+        .read_to_end(&mut decoded)
+        .map_err(|source| NetworkError::Decompression { encoding: "gzip".to_string(), reason: source.to_string() })?;
+    Ok(decoded)
+}
+
+fn decode_brotli(body: &[u8]) -> Result<Vec<u8>, NetworkError> {
+    let mut decoded = Vec::new();
+    brotli_decompressor::Decompressor::new(body, BROTLI_BUFFER_SIZE)
+        .read_to_end(&mut decoded)
+        .map_err(|source| NetworkError::Decompression { encoding: "br".to_string(), reason: source.to_string() })?;
+    Ok(decoded)
+}
+
+fn decode_zstd(body: &[u8]) -> Result<Vec<u8>, NetworkError> {
+    let mut decoded = Vec::new();
+    ruzstd::decoding::StreamingDecoder::new(body)
+        .map_err(|source| NetworkError::Decompression { encoding: "zstd".to_string(), reason: source.to_string() })?
+        .read_to_end(&mut decoded)
+        .map_err(|source| NetworkError::Decompression { encoding: "zstd".to_string(), reason: source.to_string() })?;
+    Ok(decoded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn identity_and_empty_encoding_pass_the_body_through_unchanged() {
+        assert_eq!(decode("identity", b"hello").unwrap(), b"hello");
+        assert_eq!(decode("", b"hello").unwrap(), b"hello");
+    }
+
+    #[test]
+    fn an_unrecognized_encoding_is_an_error_not_a_silent_pass_through() {
+        let err = decode("compress", b"hello").unwrap_err();
+        assert!(matches!(err, NetworkError::UnsupportedContentEncoding { .. }));
+    }
+
+    #[test]
+    fn gzip_round_trips_through_flate2s_own_encoder() {
+        use std::io::Write;
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(b"hello, gzip").unwrap();
+        let compressed = encoder.finish().unwrap();
+        assert_eq!(decode("gzip", &compressed).unwrap(), b"hello, gzip");
+    }
+
+    #[test]
+    fn garbage_gzip_bytes_report_a_decompression_error() {
+        let err = decode("gzip", b"not actually gzip").unwrap_err();
+        assert!(matches!(err, NetworkError::Decompression { encoding, .. } if encoding == "gzip"));
+    }
+
+    #[test]
+    fn apply_accept_encoding_sets_the_header_by_default() {
+        let request = apply_accept_encoding(NetworkRequest::new("GET", "https://example.com"));
+        assert_eq!(
+            request.headers.iter().find(|(k, _)| k == "Accept-Encoding").map(|(_, v)| v.as_str()),
+            Some(ACCEPT_ENCODING)
+        );
+    }
+
+    #[test]
+    fn apply_accept_encoding_does_nothing_when_auto_decompress_is_off() {
+        let request = apply_accept_encoding(
+            NetworkRequest::new("GET", "https://example.com").with_auto_decompress(false),
+        );
+        assert!(request.headers.iter().all(|(k, _)| k != "Accept-Encoding"));
+    }
+}
@@ -0,0 +1,97 @@
+use rand::RngExt;
+
+use crate::challenge::AuthChallenge;
+use crate::credential::Credential;
+
+/// Builds the `Authorization: Digest ...` header value for `credential`
+/// against `challenge`, per RFC 2617. Supports the `qop=auth` variant
+/// servers actually send today; a challenge with no `qop` falls back to
+/// the older unqualified digest.
+///
+/// `nonce_count` is the 1-based count of requests answered with this
+/// challenge's nonce so far — the caller (here, [`crate::AuthPipeline`])
+/// tracks it per target and increments it on every retry, as RFC 2617
+/// requires.
+pub fn header_value(
+    credential: &Credential,
+    challenge: &AuthChallenge,
+    method: &str,
+    uri: &str,
+    nonce_count: u32,
+) -> String {
+    let nonce = challenge.param("nonce").unwrap_or_default();
+    let qop = challenge.param("qop");
+
+    let ha1 = md5_hex(&format!("{}:{}:{}", credential.username, challenge.realm, credential.password));
+    let ha2 = md5_hex(&format!("{method}:{uri}"));
+
+    let nc = format!("{nonce_count:08x}");
+    let cnonce = client_nonce();
+
+    let response = match qop {
+        Some(qop) => md5_hex(&format!("{ha1}:{nonce}:{nc}:{cnonce}:{qop}:{ha2}")),
+        None => md5_hex(&format!("{ha1}:{nonce}:{ha2}")),
+    };
+
+    let mut header = format!(
+        r#"Digest username="{}", realm="{}", nonce="{}", uri="{}", response="{}""#,
+        credential.username, challenge.realm, nonce, uri, response
+    );
+    if let Some(qop) = qop {
+        header.push_str(&format!(r#", qop={qop}, nc={nc}, cnonce="{cnonce}""#));
+    }
+    if let Some(opaque) = challenge.param("opaque") {
+        header.push_str(&format!(r#", opaque="{opaque}""#));
+    }
+    header
+}
+
+fn md5_hex(input: &str) -> String {
+    format!("{:x}", md5::compute(input.as_bytes()))
+}
+
+fn client_nonce() -> String {
+    let mut bytes = [0u8; 8];
+    rand::rng().fill(&mut bytes);
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::challenge::parse_challenge;
+
+    fn credential() -> Credential {
+        Credential { username: "Mufasa".to_string(), password: "Circle Of Life".to_string() }
+    }
+
+    #[test]
+    fn matches_the_rfc_2617_example_without_qop() {
+        let challenge = parse_challenge(
+            r#"Digest realm="testrealm@host.com", nonce="dcd98b7102dd2f0e8b11d0f600bfb0c093""#,
+        )
+        .unwrap();
+        let header = header_value(&credential(), &challenge, "GET", "/dir/index.html", 1);
+
+        let ha1 = md5_hex("Mufasa:testrealm@host.com:Circle Of Life");
+        let ha2 = md5_hex("GET:/dir/index.html");
+        let expected_response = md5_hex(&format!("{ha1}:dcd98b7102dd2f0e8b11d0f600bfb0c093:{ha2}"));
+        assert!(header.contains(&format!(r#"response="{expected_response}""#)));
+    }
+
+    #[test]
+    fn includes_qop_nc_and_cnonce_when_the_challenge_asks_for_them() {
+        let challenge = parse_challenge(r#"Digest realm="r", nonce="n", qop="auth""#).unwrap();
+        let header = header_value(&credential(), &challenge, "GET", "/", 1);
+        assert!(header.contains("qop=auth"));
+        assert!(header.contains("nc=00000001"));
+        assert!(header.contains("cnonce="));
+    }
+
+    #[test]
+    fn carries_the_opaque_value_through_unchanged() {
+        let challenge = parse_challenge(r#"Digest realm="r", nonce="n", opaque="o""#).unwrap();
+        let header = header_value(&credential(), &challenge, "GET", "/", 1);
+        assert!(header.contains(r#"opaque="o""#));
+    }
+}
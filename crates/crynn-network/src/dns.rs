@@ -0,0 +1,212 @@
+use std::collections::HashMap;
+use std::net::IpAddr;
+
+/// One resolved address and how long it's good for, the way a real DNS
+/// response reports a TTL per A/AAAA record rather than one TTL for the
+/// whole lookup.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DnsRecord {
+    pub address: IpAddr,
+    pub ttl_secs: u64,
+}
+
+/// Performs the actual lookup. No real resolver is wired into this crate
+/// yet — the same contract-over-implementation split as
+/// [`crate::CredentialProvider`]/[`crate::SuggestionsTransport`], injected
+/// so [`DnsResolver::resolve_all`] can be exercised in tests without a
+/// real network.
+pub trait DnsLookup {
+    fn lookup(&mut self, host: &str) -> Vec<DnsRecord>;
+}
+
+/// How many distinct hosts [`DnsResolver`] keeps cached at once. Past
+/// this, [`DnsResolver::evict_least_recently_used`] drops the
+/// least-recently-used host to make room for a new one, rather than
+/// wiping the whole cache the way a naive fixed-size cache would.
+const DEFAULT_CAPACITY: usize = 256;
+
+struct CacheEntry {
+    records: Vec<DnsRecord>,
+    expires_at: u64,
+}
+
+/// A DNS cache keyed by hostname. Each entry holds every A/AAAA address a
+/// lookup returned, not just the first one, and expires by the lowest
+/// TTL among those records rather than a hardcoded duration — the same
+/// "cache what the server actually told us" approach [`crate::AltSvcCache`]
+/// takes for `Alt-Svc` advertisements.
+pub struct DnsResolver {
+    capacity: usize,
+    entries: HashMap<String, CacheEntry>,
+    /// Least-recently-used host first, most-recently-used last.
+    recency: Vec<String>,
+}
+
+impl Default for DnsResolver {
+    fn default() -> Self {
+        Self::with_capacity(DEFAULT_CAPACITY)
+    }
+}
+
+impl DnsResolver {
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self { capacity, entries: HashMap::new(), recency: Vec::new() }
+    }
+
+    /// Every address currently on file for `host`, doing a fresh lookup
+    /// through `lookup` on a cache miss or once the cached records' TTL
+    /// has elapsed at `now`. A host resolving to more than one address
+    /// (round-robin DNS, a CDN) gets all of them back, in the order the
+    /// lookup returned them.
+    pub fn resolve_all(&mut self, host: &str, now: u64, lookup: &mut dyn DnsLookup) -> Vec<IpAddr> {
+        if let Some(entry) = self.entries.get(host) {
+            if now < entry.expires_at {
+                let addresses = entry.records.iter().map(|record| record.address).collect();
+                self.touch(host);
+                return addresses;
+            }
+        }
+        let records = lookup.lookup(host);
+        let addresses = records.iter().map(|record| record.address).collect();
+        self.insert(host, records, now);
+        addresses
+    }
+
+    /// The first cached (or freshly resolved) address for `host`, for
+    /// callers that only need one address to dial rather than the whole
+    /// set — use [`Self::resolve_all`] for round-robin or Happy-Eyeballs-
+    /// style callers that want to try more than one.
+    pub fn resolve(&mut self, host: &str, now: u64, lookup: &mut dyn DnsLookup) -> Option<IpAddr> {
+        self.resolve_all(host, now, lookup).into_iter().next()
+    }
+
+    fn insert(&mut self, host: &str, records: Vec<DnsRecord>, now: u64) {
+        let ttl_secs = records.iter().map(|record| record.ttl_secs).min().unwrap_or(0);
+        self.entries.insert(host.to_string(), CacheEntry { records, expires_at: now + ttl_secs });
+        self.touch(host);
+        self.evict_least_recently_used();
+    }
+
+    fn touch(&mut self, host: &str) {
+        self.recency.retain(|cached| cached != host);
+        self.recency.push(host.to_string());
+    }
+
+    fn evict_least_recently_used(&mut self) {
+        while self.entries.len() > self.capacity {
+            let Some(oldest) = self.recency.first().cloned() else {
+                break;
+            };
+            self.recency.remove(0);
+            self.entries.remove(&oldest);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct FakeDns {
+        responses: HashMap<String, Vec<DnsRecord>>,
+        lookups: usize,
+    }
+
+    impl FakeDns {
+        fn new() -> Self {
+            Self { responses: HashMap::new(), lookups: 0 }
+        }
+
+        fn respond(&mut self, host: &str, records: Vec<DnsRecord>) {
+            self.responses.insert(host.to_string(), records);
+        }
+    }
+
+    impl DnsLookup for FakeDns {
+        fn lookup(&mut self, host: &str) -> Vec<DnsRecord> {
+            self.lookups += 1;
+            self.responses.get(host).cloned().unwrap_or_default()
+        }
+    }
+
+    fn record(address: &str, ttl_secs: u64) -> DnsRecord {
+        DnsRecord { address: address.parse().unwrap(), ttl_secs }
+    }
+
+    #[test]
+    fn resolve_all_returns_every_record_a_lookup_returned() {
+        let mut resolver = DnsResolver::default();
+        let mut dns = FakeDns::new();
+        dns.respond("example.com", vec![record("93.184.216.34", 300), record("93.184.216.35", 300)]);
+
+        let addresses = resolver.resolve_all("example.com", 0, &mut dns);
+        assert_eq!(addresses, vec!["93.184.216.34".parse::<IpAddr>().unwrap(), "93.184.216.35".parse().unwrap()]);
+    }
+
+    #[test]
+    fn a_cached_entry_is_reused_without_another_lookup() {
+        let mut resolver = DnsResolver::default();
+        let mut dns = FakeDns::new();
+        dns.respond("example.com", vec![record("93.184.216.34", 300)]);
+
+        resolver.resolve_all("example.com", 0, &mut dns);
+        resolver.resolve_all("example.com", 100, &mut dns);
+
+        assert_eq!(dns.lookups, 1);
+    }
+
+    #[test]
+    fn an_entry_is_re_resolved_once_its_ttl_elapses() {
+        let mut resolver = DnsResolver::default();
+        let mut dns = FakeDns::new();
+        dns.respond("example.com", vec![record("93.184.216.34", 60)]);
+
+        resolver.resolve_all("example.com", 0, &mut dns);
+        resolver.resolve_all("example.com", 61, &mut dns);
+
+        assert_eq!(dns.lookups, 2);
+    }
+
+    #[test]
+    fn the_cache_expires_by_the_lowest_ttl_among_a_hosts_records() {
+        let mut resolver = DnsResolver::default();
+        let mut dns = FakeDns::new();
+        dns.respond("example.com", vec![record("93.184.216.34", 300), record("93.184.216.35", 30)]);
+
+        resolver.resolve_all("example.com", 0, &mut dns);
+        resolver.resolve_all("example.com", 31, &mut dns);
+
+        assert_eq!(dns.lookups, 2);
+    }
+
+    #[test]
+    fn evicting_over_capacity_drops_only_the_least_recently_used_host() {
+        let mut resolver = DnsResolver::with_capacity(2);
+        let mut dns = FakeDns::new();
+        dns.respond("a.example.com", vec![record("10.0.0.1", 300)]);
+        dns.respond("b.example.com", vec![record("10.0.0.2", 300)]);
+        dns.respond("c.example.com", vec![record("10.0.0.3", 300)]);
+
+        resolver.resolve_all("a.example.com", 0, &mut dns);
+        resolver.resolve_all("b.example.com", 0, &mut dns);
+        resolver.resolve_all("a.example.com", 0, &mut dns);
+        resolver.resolve_all("c.example.com", 0, &mut dns);
+
+        let lookups_before = dns.lookups;
+        resolver.resolve_all("a.example.com", 0, &mut dns);
+        resolver.resolve_all("c.example.com", 0, &mut dns);
+        assert_eq!(dns.lookups, lookups_before);
+
+        resolver.resolve_all("b.example.com", 0, &mut dns);
+        assert_eq!(dns.lookups, lookups_before + 1);
+    }
+
+    #[test]
+    fn resolve_returns_only_the_first_address() {
+        let mut resolver = DnsResolver::default();
+        let mut dns = FakeDns::new();
+        dns.respond("example.com", vec![record("93.184.216.34", 300), record("93.184.216.35", 300)]);
+
+        assert_eq!(resolver.resolve("example.com", 0, &mut dns), Some("93.184.216.34".parse().unwrap()));
+    }
+}
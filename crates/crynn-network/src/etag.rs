@@ -0,0 +1,75 @@
+/// An HTTP entity tag parsed from an `ETag`/`If-None-Match` header value:
+/// `"<opaque>"` for a strong tag, or `W/"<opaque>"` for a weak one. Used by
+/// [`crate::HttpCache`] to decide whether a cached response is still valid
+/// after revalidation, the same contract a real browser cache uses a 304
+/// response's `ETag` for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ETag {
+    weak: bool,
+    value: String,
+}
+
+impl ETag {
+    /// Parses a raw header value. `None` if it isn't a well-formed
+    /// quoted entity tag, optionally prefixed with `W/`.
+    pub fn parse(header_value: &str) -> Option<Self> {
+        let header_value = header_value.trim();
+        let (weak, quoted) = match header_value.strip_prefix("W/") {
+            Some(rest) => (true, rest),
+            None => (false, header_value),
+        };
+        let value = quoted.strip_prefix('"')?.strip_suffix('"')?;
+        Some(Self { weak, value: value.to_string() })
+    }
+
+    pub fn is_weak(&self) -> bool {
+        self.weak
+    }
+
+    /// Weak comparison per RFC 7232 §2.3.2: two tags match if their opaque
+    /// values are equal, regardless of either side's weak/strong marker.
+    /// This is the comparison a cache revalidating a response should use —
+    /// strong comparison (requiring both sides to be strong) only matters
+    /// for range requests this crate doesn't attempt to combine across a
+    /// weak-tagged resource.
+    pub fn weakly_matches(&self, other: &ETag) -> bool {
+        self.value == other.value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_a_strong_tag() {
+        let tag = ETag::parse("\"abc123\"").unwrap();
+        assert!(!tag.is_weak());
+    }
+
+    #[test]
+    fn parses_a_weak_tag() {
+        let tag = ETag::parse("W/\"abc123\"").unwrap();
+        assert!(tag.is_weak());
+    }
+
+    #[test]
+    fn an_unquoted_value_fails_to_parse() {
+        assert!(ETag::parse("abc123").is_none());
+    }
+
+    #[test]
+    fn weak_comparison_matches_regardless_of_either_sides_marker() {
+        let strong = ETag::parse("\"abc123\"").unwrap();
+        let weak = ETag::parse("W/\"abc123\"").unwrap();
+        assert!(strong.weakly_matches(&weak));
+        assert!(weak.weakly_matches(&strong));
+    }
+
+    #[test]
+    fn weak_comparison_rejects_differing_opaque_values() {
+        let a = ETag::parse("W/\"abc123\"").unwrap();
+        let b = ETag::parse("W/\"def456\"").unwrap();
+        assert!(!a.weakly_matches(&b));
+    }
+}
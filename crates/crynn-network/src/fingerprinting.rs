@@ -0,0 +1,43 @@
+use crate::request::NetworkRequest;
+
+/// The `User-Agent` every request sends when resist-fingerprinting is
+/// active, regardless of the platform Crynn is actually running on —
+/// the same "generic" value real fingerprinting-resistance modes ship,
+/// so the header stops narrowing down who's visiting.
+pub const RESISTANT_USER_AGENT: &str = "Mozilla/5.0 (Windows NT 10.0; Win64; x64; rv:128.0) Crynn/1.0";
+
+/// The `Accept-Language` every request sends when resist-fingerprinting
+/// is active, replacing whatever languages the user actually has
+/// configured.
+pub const RESISTANT_ACCEPT_LANGUAGE: &str = "en-US,en;q=0.5";
+
+/// Overwrites `request`'s `User-Agent` and `Accept-Language` headers with
+/// [`RESISTANT_USER_AGENT`] and [`RESISTANT_ACCEPT_LANGUAGE`]. Whether to
+/// call this for a given request is a per-site decision the caller makes
+/// (e.g. `crynn-shell`'s resist-fingerprinting coordinator); this function
+/// only knows how to apply the standardized values once that's decided.
+pub fn apply_resistant_headers(mut request: NetworkRequest) -> NetworkRequest {
+    request.headers.retain(|(name, _)| name != "User-Agent" && name != "Accept-Language");
+    request.headers.push(("User-Agent".to_string(), RESISTANT_USER_AGENT.to_string()));
+    request.headers.push(("Accept-Language".to_string(), RESISTANT_ACCEPT_LANGUAGE.to_string()));
+    request
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn replaces_an_existing_user_agent_header() {
+        let request = NetworkRequest::new("GET", "https://example.com").with_header("User-Agent", "Crynn/custom-build");
+        let request = apply_resistant_headers(request);
+        let user_agents: Vec<&str> = request.headers.iter().filter(|(k, _)| k == "User-Agent").map(|(_, v)| v.as_str()).collect();
+        assert_eq!(user_agents, vec![RESISTANT_USER_AGENT]);
+    }
+
+    #[test]
+    fn sets_accept_language_when_none_was_present() {
+        let request = apply_resistant_headers(NetworkRequest::new("GET", "https://example.com"));
+        assert!(request.headers.contains(&("Accept-Language".to_string(), RESISTANT_ACCEPT_LANGUAGE.to_string())));
+    }
+}
@@ -0,0 +1,81 @@
+use std::collections::HashMap;
+use std::net::IpAddr;
+
+/// What a [`HostsOverrides`] entry resolves a hostname to, instead of
+/// asking any resolver at all.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HostsEntry {
+    Address(IpAddr),
+    Blocked,
+}
+
+/// A user-editable hosts table, consulted before [`crate::DnsResolver`]
+/// or [`crate::FallbackResolver`] ever run a lookup — the same
+/// `/etc/hosts`-style override a real browser honors for local
+/// development (pointing a hostname at a dev server) and ad-blocking
+/// (pointing one at [`HostsEntry::Blocked`] instead of an address).
+#[derive(Debug, Default)]
+pub struct HostsOverrides {
+    entries: HashMap<String, HostsEntry>,
+}
+
+impl HostsOverrides {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set(&mut self, host: impl Into<String>, entry: HostsEntry) {
+        self.entries.insert(host.into(), entry);
+    }
+
+    pub fn remove(&mut self, host: &str) {
+        self.entries.remove(host);
+    }
+
+    /// The override on file for `host`, if any. `None` means no override
+    /// exists and callers should fall through to an actual resolver.
+    pub fn lookup(&self, host: &str) -> Option<HostsEntry> {
+        self.entries.get(host).copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_host_with_no_override_falls_through() {
+        let overrides = HostsOverrides::new();
+        assert_eq!(overrides.lookup("example.com"), None);
+    }
+
+    #[test]
+    fn an_address_override_is_returned() {
+        let mut overrides = HostsOverrides::new();
+        overrides.set("dev.local", HostsEntry::Address("127.0.0.1".parse().unwrap()));
+        assert_eq!(overrides.lookup("dev.local"), Some(HostsEntry::Address("127.0.0.1".parse().unwrap())));
+    }
+
+    #[test]
+    fn a_blocked_override_is_returned() {
+        let mut overrides = HostsOverrides::new();
+        overrides.set("ads.example.com", HostsEntry::Blocked);
+        assert_eq!(overrides.lookup("ads.example.com"), Some(HostsEntry::Blocked));
+    }
+
+    #[test]
+    fn removing_an_override_falls_back_through_again() {
+        let mut overrides = HostsOverrides::new();
+        overrides.set("dev.local", HostsEntry::Address("127.0.0.1".parse().unwrap()));
+        overrides.remove("dev.local");
+        assert_eq!(overrides.lookup("dev.local"), None);
+    }
+
+    #[test]
+    fn setting_an_override_again_replaces_the_previous_one() {
+        let mut overrides = HostsOverrides::new();
+        overrides.set("dev.local", HostsEntry::Address("127.0.0.1".parse().unwrap()));
+        overrides.set("dev.local", HostsEntry::Blocked);
+        assert_eq!(overrides.lookup("dev.local"), Some(HostsEntry::Blocked));
+    }
+}
@@ -0,0 +1,152 @@
+use std::collections::HashMap;
+
+/// A tiny built-in preload list — hosts that always require HTTPS even
+/// before this crate has ever seen a `Strict-Transport-Security` header
+/// from them, the same way a real browser ships Chromium's/Mozilla's
+/// HSTS preload list rather than trusting first contact with every site
+/// to be unintercepted. Not meant to be exhaustive; real preload
+/// loading is a data-update problem this crate doesn't solve, the same
+/// gap [`crate::fingerprinting::RESISTANT_USER_AGENT`] leaves for
+/// keeping a spoofed UA current.
+const PRELOADED_HOSTS: &[&str] = &["example.com"];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct HstsEntry {
+    expires_at: u64,
+    include_subdomains: bool,
+}
+
+/// Per-host HSTS policy, recorded from `Strict-Transport-Security`
+/// response headers with the header's own `max-age` honored — an entry
+/// recorded an hour ago with `max-age=60` shouldn't still force HTTPS on
+/// `host` today. Has no sense of "now" of its own, the same as
+/// [`crate::AltSvcCache`]: every record and lookup takes it as a
+/// parameter.
+#[derive(Debug, Default)]
+pub struct HstsStore {
+    entries: HashMap<String, HstsEntry>,
+}
+
+impl HstsStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parses `header_value` — a `Strict-Transport-Security` header
+    /// received from `host` — and records it, expiring `max-age`
+    /// seconds after `now`. `max-age=0` removes whatever was previously
+    /// recorded for `host`, per the header's own spec for unsetting a
+    /// policy early, rather than being treated as an already-expired
+    /// entry worth keeping around. A header with no `max-age` at all is
+    /// ignored outright, since `max-age` is mandatory for this
+    /// directive to mean anything.
+    pub fn record(&mut self, host: &str, header_value: &str, now: u64) {
+        let Some((max_age, include_subdomains)) = parse_header(header_value) else {
+            return;
+        };
+        if max_age == 0 {
+            self.entries.remove(host);
+            return;
+        }
+        self.entries.insert(host.to_string(), HstsEntry { expires_at: now.saturating_add(max_age), include_subdomains });
+    }
+
+    /// Whether `host` must be fetched over HTTPS as of `now`: an
+    /// unexpired dynamic policy recorded for `host` itself, an
+    /// unexpired `includeSubDomains` policy recorded for one of its
+    /// parent domains, or a hardcoded entry on [`PRELOADED_HOSTS`].
+    pub fn requires_https(&self, host: &str, now: u64) -> bool {
+        self.dynamic_requires_https(host, now) || is_preloaded(host)
+    }
+
+    fn dynamic_requires_https(&self, host: &str, now: u64) -> bool {
+        self.entries.iter().any(|(recorded_host, entry)| {
+            if entry.expires_at <= now {
+                return false;
+            }
+            host == recorded_host || (entry.include_subdomains && host.ends_with(&format!(".{recorded_host}")))
+        })
+    }
+}
+
+fn is_preloaded(host: &str) -> bool {
+    PRELOADED_HOSTS.iter().any(|preloaded| host == *preloaded || host.ends_with(&format!(".{preloaded}")))
+}
+
+/// Parses a `Strict-Transport-Security` header value into
+/// `(max_age_secs, include_subdomains)`. `None` if it has no `max-age`
+/// directive at all.
+fn parse_header(header_value: &str) -> Option<(u64, bool)> {
+    let mut max_age = None;
+    let mut include_subdomains = false;
+    for directive in header_value.split(';').map(str::trim) {
+        if let Some(value) = directive.strip_prefix("max-age=") {
+            max_age = value.trim().parse().ok();
+        } else if directive.eq_ignore_ascii_case("includeSubDomains") {
+            include_subdomains = true;
+        }
+    }
+    max_age.map(|max_age| (max_age, include_subdomains))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn an_unrecorded_non_preloaded_host_does_not_require_https() {
+        let store = HstsStore::new();
+        assert!(!store.requires_https("unrecorded.example", 0));
+    }
+
+    #[test]
+    fn a_recorded_policy_is_honored_before_it_expires() {
+        let mut store = HstsStore::new();
+        store.record("example.org", "max-age=3600", 1_000);
+        assert!(store.requires_https("example.org", 1_500));
+    }
+
+    #[test]
+    fn a_recorded_policy_stops_being_honored_once_it_expires() {
+        let mut store = HstsStore::new();
+        store.record("example.org", "max-age=60", 1_000);
+        assert!(!store.requires_https("example.org", 1_061));
+    }
+
+    #[test]
+    fn without_include_subdomains_a_subdomain_is_not_covered() {
+        let mut store = HstsStore::new();
+        store.record("example.org", "max-age=3600", 0);
+        assert!(!store.requires_https("app.example.org", 0));
+    }
+
+    #[test]
+    fn include_subdomains_covers_subdomains_but_not_unrelated_hosts() {
+        let mut store = HstsStore::new();
+        store.record("example.org", "max-age=3600; includeSubDomains", 0);
+        assert!(store.requires_https("app.example.org", 0));
+        assert!(!store.requires_https("other.example", 0));
+    }
+
+    #[test]
+    fn max_age_zero_removes_a_previously_recorded_policy() {
+        let mut store = HstsStore::new();
+        store.record("example.org", "max-age=3600", 0);
+        store.record("example.org", "max-age=0", 0);
+        assert!(!store.requires_https("example.org", 0));
+    }
+
+    #[test]
+    fn a_header_with_no_max_age_is_ignored() {
+        let mut store = HstsStore::new();
+        store.record("example.org", "includeSubDomains", 0);
+        assert!(!store.requires_https("example.org", 0));
+    }
+
+    #[test]
+    fn a_preloaded_host_requires_https_without_ever_recording_a_header() {
+        let store = HstsStore::new();
+        assert!(store.requires_https("example.com", 0));
+        assert!(store.requires_https("www.example.com", 0));
+    }
+}
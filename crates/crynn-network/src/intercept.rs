@@ -0,0 +1,251 @@
+use crate::request::NetworkRequest;
+
+/// What a [`RequestInterceptor`] decided to do with a request it
+/// inspected: let it through (optionally rewritten — swapping in a
+/// modified [`NetworkRequest`] is how an interceptor "modifies" one), or
+/// block it outright with a human-readable reason a devtools network
+/// panel could show.
+pub enum InterceptAction {
+    Allow(NetworkRequest),
+    Block { reason: String },
+}
+
+/// Inspects, rewrites, or blocks outgoing requests before they're sent.
+/// Whatever drives an actual connection attempt should run every
+/// registered interceptor via [`run_interceptors`] right before handing
+/// the request to a transport — the same "caller wires this in" split as
+/// [`crate::attach_cookies`]: this crate has no transport of its own to
+/// call it from yet.
+pub trait RequestInterceptor {
+    fn intercept(&self, request: NetworkRequest) -> InterceptAction;
+}
+
+/// Runs `request` through `interceptors` in order, threading each one's
+/// (possibly rewritten) request into the next. Stops at the first
+/// [`InterceptAction::Block`] rather than running the rest — a request an
+/// earlier interceptor already blocked has nothing left for a later one
+/// to inspect.
+pub fn run_interceptors(request: NetworkRequest, interceptors: &[&dyn RequestInterceptor]) -> InterceptAction {
+    let mut request = request;
+    for interceptor in interceptors {
+        match interceptor.intercept(request) {
+            InterceptAction::Allow(next) => request = next,
+            blocked @ InterceptAction::Block { .. } => return blocked,
+        }
+    }
+    InterceptAction::Allow(request)
+}
+
+/// One parsed line of an EasyList/uBlock-style filter list.
+struct FilterRule {
+    pattern: Pattern,
+    is_exception: bool,
+}
+
+enum Pattern {
+    /// `||example.com^` — anchors to the request's host, matching it or
+    /// any subdomain, the same rule disconnect.me-style lists and
+    /// `crynn_tracking_protection`'s bundled list both use for domains.
+    Domain(String),
+    /// Anything else: a plain substring match anywhere in the URL, the
+    /// bulk of a real EasyList.
+    Substring(String),
+}
+
+impl FilterRule {
+    /// Parses one filter-list line, or `None` for a comment (`!...`), a
+    /// cosmetic element-hiding rule (`##...`, irrelevant above the fetch
+    /// layer), or a blank line.
+    fn parse(line: &str) -> Option<Self> {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('!') || line.contains("##") || line.contains("#@#") {
+            return None;
+        }
+        let (is_exception, rest) = match line.strip_prefix("@@") {
+            Some(rest) => (true, rest),
+            None => (false, line),
+        };
+        // `$third-party`, `$script`, `$domain=...` and friends aren't
+        // parsed yet; stripping them leaves the underlying URL pattern,
+        // which is still a useful (if less targeted) match.
+        let rest = rest.split('$').next().unwrap_or(rest);
+        let pattern = match rest.strip_prefix("||") {
+            Some(domain) => Pattern::Domain(domain.trim_end_matches('^').to_string()),
+            None => Pattern::Substring(rest.to_string()),
+        };
+        if matches!(&pattern, Pattern::Domain(d) | Pattern::Substring(d) if d.is_empty()) {
+            return None;
+        }
+        Some(Self { pattern, is_exception })
+    }
+
+    fn matches(&self, url: &str, host: &str) -> bool {
+        match &self.pattern {
+            Pattern::Domain(domain) => host == domain || host.ends_with(&format!(".{domain}")),
+            Pattern::Substring(needle) => url.contains(needle.as_str()),
+        }
+    }
+}
+
+/// Extracts the host from a URL, stripping scheme, userinfo, port, path,
+/// and query. Good enough for anchoring `||domain^` rules; anything that
+/// needs a fully validated URL should reach for a real parser. The same
+/// trimming `crynn_tracking_protection::host_from_url` does, duplicated
+/// rather than pulled in as a dependency this crate otherwise has no
+/// reason for.
+fn host_from_url(url: &str) -> &str {
+    let without_scheme = url.split("://").nth(1).unwrap_or(url);
+    let host_and_port = without_scheme.split(['/', '?', '#']).next().unwrap_or("");
+    let host_and_port = host_and_port.rsplit('@').next().unwrap_or(host_and_port);
+    host_and_port.split(':').next().unwrap_or(host_and_port)
+}
+
+/// A built-in ad/tracker blocker backed by an EasyList/uBlock-style
+/// filter list: [`FilterListBlocker::parse`] loads one as-is, and
+/// [`FilterListBlocker::intercept`] blocks any request a rule matches,
+/// unless a later `@@` exception rule also matches — an exception always
+/// wins, the same override disconnect.me category lists don't need
+/// because they have no exceptions at all.
+pub struct FilterListBlocker {
+    rules: Vec<FilterRule>,
+}
+
+impl FilterListBlocker {
+    /// Parses `list`, one rule per line, skipping comments and cosmetic
+    /// rules rather than rejecting them.
+    pub fn parse(list: &str) -> Self {
+        Self { rules: list.lines().filter_map(FilterRule::parse).collect() }
+    }
+
+    pub fn rule_count(&self) -> usize {
+        self.rules.len()
+    }
+
+    fn is_blocked(&self, url: &str) -> bool {
+        let host = host_from_url(url);
+        let mut blocked = false;
+        for rule in &self.rules {
+            if rule.matches(url, host) {
+                if rule.is_exception {
+                    return false;
+                }
+                blocked = true;
+            }
+        }
+        blocked
+    }
+}
+
+impl RequestInterceptor for FilterListBlocker {
+    fn intercept(&self, request: NetworkRequest) -> InterceptAction {
+        if self.is_blocked(&request.url) {
+            InterceptAction::Block { reason: format!("blocked by filter list: {}", request.url) }
+        } else {
+            InterceptAction::Allow(request)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_domain_rule_blocks_the_host_and_its_subdomains() {
+        let blocker = FilterListBlocker::parse("||doubleclick.net^");
+        assert!(blocker.is_blocked("https://ads.doubleclick.net/pixel"));
+        assert!(blocker.is_blocked("https://doubleclick.net/pixel"));
+        assert!(!blocker.is_blocked("https://notdoubleclick.net/pixel"));
+    }
+
+    #[test]
+    fn a_substring_rule_matches_anywhere_in_the_url() {
+        let blocker = FilterListBlocker::parse("/ads/banner.js");
+        assert!(blocker.is_blocked("https://example.com/ads/banner.js?slot=1"));
+        assert!(!blocker.is_blocked("https://example.com/content.js"));
+    }
+
+    #[test]
+    fn an_exception_rule_overrides_a_matching_block_rule() {
+        let blocker = FilterListBlocker::parse("||tracker.example\n@@||cdn.tracker.example^");
+        assert!(blocker.is_blocked("https://tracker.example/beacon"));
+        assert!(!blocker.is_blocked("https://cdn.tracker.example/lib.js"));
+    }
+
+    #[test]
+    fn comment_and_cosmetic_lines_are_ignored() {
+        let blocker = FilterListBlocker::parse("! this is a comment\nexample.com##.ad-banner\n\n||ads.example^");
+        assert_eq!(blocker.rule_count(), 1);
+    }
+
+    #[test]
+    fn options_after_a_dollar_sign_are_stripped_rather_than_honored() {
+        let blocker = FilterListBlocker::parse("||ads.example^$third-party,script");
+        assert!(blocker.is_blocked("https://ads.example/tracker.js"));
+    }
+
+    #[test]
+    fn an_unmatched_url_is_allowed() {
+        let request = NetworkRequest::new("GET", "https://example.com/page");
+        let blocker = FilterListBlocker::parse("||ads.example^");
+        assert!(matches!(blocker.intercept(request), InterceptAction::Allow(_)));
+    }
+
+    #[test]
+    fn a_matched_url_is_blocked_with_a_reason() {
+        let request = NetworkRequest::new("GET", "https://ads.example/tracker.js");
+        let blocker = FilterListBlocker::parse("||ads.example^");
+        match blocker.intercept(request) {
+            InterceptAction::Block { reason } => assert!(reason.contains("ads.example")),
+            InterceptAction::Allow(_) => panic!("expected the request to be blocked"),
+        }
+    }
+
+    #[test]
+    fn run_interceptors_stops_at_the_first_block_rather_than_running_the_rest() {
+        struct AlwaysBlocks;
+        impl RequestInterceptor for AlwaysBlocks {
+            fn intercept(&self, _request: NetworkRequest) -> InterceptAction {
+                InterceptAction::Block { reason: "nope".to_string() }
+            }
+        }
+        struct PanicsIfCalled;
+        impl RequestInterceptor for PanicsIfCalled {
+            fn intercept(&self, _request: NetworkRequest) -> InterceptAction {
+                panic!("should not have been reached");
+            }
+        }
+
+        let request = NetworkRequest::new("GET", "https://example.com");
+        let blocks_everything = AlwaysBlocks;
+        let unreachable = PanicsIfCalled;
+        let interceptors: Vec<&dyn RequestInterceptor> = vec![&blocks_everything, &unreachable];
+        assert!(matches!(run_interceptors(request, &interceptors), InterceptAction::Block { .. }));
+    }
+
+    #[test]
+    fn run_interceptors_threads_a_rewritten_request_into_the_next_interceptor() {
+        struct AddsHeader;
+        impl RequestInterceptor for AddsHeader {
+            fn intercept(&self, request: NetworkRequest) -> InterceptAction {
+                InterceptAction::Allow(request.with_header("X-Intercepted", "1"))
+            }
+        }
+        struct ChecksHeader;
+        impl RequestInterceptor for ChecksHeader {
+            fn intercept(&self, request: NetworkRequest) -> InterceptAction {
+                if request.headers.iter().any(|(k, _)| k == "X-Intercepted") {
+                    InterceptAction::Allow(request)
+                } else {
+                    InterceptAction::Block { reason: "missing header".to_string() }
+                }
+            }
+        }
+
+        let request = NetworkRequest::new("GET", "https://example.com");
+        let adds_header = AddsHeader;
+        let checks_header = ChecksHeader;
+        let interceptors: Vec<&dyn RequestInterceptor> = vec![&adds_header, &checks_header];
+        assert!(matches!(run_interceptors(request, &interceptors), InterceptAction::Allow(_)));
+    }
+}
@@ -0,0 +1,349 @@
+//! HTTP and proxy authentication. Neither a real transport nor the shells
+//! can answer a 401/407 today; this crate is the answering half of that
+//! exchange — parsing `WWW-Authenticate`/`Proxy-Authenticate` challenges,
+//! building Basic and Digest `Authorization`/`Proxy-Authorization` header
+//! values, and [`AuthPipeline`], which drives a [`CredentialProvider`]
+//! (the shell prompting the user, or the credentials database answering
+//! from a saved entry) to produce the header the request should be
+//! retried with.
+//!
+//! Sending the retried request is the caller's job: whatever owns the
+//! actual connection calls [`AuthPipeline::respond_to_challenge`] when it
+//! sees a 401/407, and retries with the header it returns. This crate has
+//! no transport of its own yet — that's the natural next implementation,
+//! and this is the contract it has to satisfy.
+//!
+//! [`NetworkRequest`] and [`BodySource`] are that same kind of contract
+//! for request bodies: a [`BodySource`] can stream from a file or an
+//! arbitrary reader instead of requiring the whole body in memory up
+//! front, which is what the shell's upload UI and the email attachment
+//! path both need for anything larger than a form field.
+//!
+//! [`NetworkManager`] is the same contract again, for connection health:
+//! [`ConnectionStats`] is what an h2/quinn client would report per
+//! connection (open streams, RTT, congestion window, `GOAWAY`s), and
+//! [`NetworkManager::preferred_protocol`] is the heuristic the shell's
+//! `about:network` page and a real connection attempt would both consult
+//! before picking HTTP/1.1, HTTP/2, or HTTP/3 for an origin.
+//!
+//! [`RetryPolicy`], also configurable per [`NetworkManager`], is the
+//! decision a caller retrying an idempotent [`NetworkRequest`] consults
+//! after a transient failure: whether it's still within budget, and how
+//! long to wait — honoring a `Retry-After` header, or jittered
+//! exponential backoff otherwise. [`NetworkResponse::attempts`] is how
+//! many of those retries it took to get the response back.
+//!
+//! [`check_connectivity`] classifies the response from probing
+//! [`CONNECTIVITY_CHECK_URL`], a generate_204-style endpoint, as
+//! [`Connectivity::Online`], [`Connectivity::Offline`], or — when
+//! something other than an empty 204 comes back — a captive portal
+//! intercepting the request to serve its own login page. Posting that as
+//! an event the shell can act on (e.g. opening the portal's login page)
+//! is the caller's job, the same as sending the retried request is for
+//! [`AuthPipeline`].
+//!
+//! [`NetworkManager::should_block_request`] is the SSRF guard: by
+//! default it blocks [`RequestOrigin::WebContent`] from reaching a
+//! resolved address in [`is_private_network_address`]'s loopback/
+//! link-local/RFC-1918 ranges, the classic embedded-browser hole where
+//! public web content reaches back into the device's own LAN. The
+//! user's own navigations are exempt outright, and
+//! [`PrivateNetworkGuard::allow`] exempts specific origins the user has
+//! chosen to trust with local access.
+//!
+//! [`SuggestionsClient`] is the same contract for the search engine's
+//! suggestions endpoint: it builds the OpenSearch-style request URL and
+//! parses the `["query", ["suggestion", ...]]` response shape, but
+//! fetching it is up to a [`SuggestionsTransport`], for the same reason
+//! [`CredentialProvider`] exists — the omnibox needs to keep working in
+//! tests and before this crate has a real transport.
+//!
+//! [`apply_resistant_headers`] is the network-layer piece of resist-
+//! fingerprinting mode: it overwrites a request's `User-Agent` and
+//! `Accept-Language` with [`RESISTANT_USER_AGENT`]/
+//! [`RESISTANT_ACCEPT_LANGUAGE`] so neither narrows down who's visiting.
+//! Deciding *when* to call it per site is `crynn-shell`'s job, the same
+//! split as everywhere else in this crate.
+//!
+//! [`ProxyConfig`] is per-request proxy selection: [`NetworkRequest::proxy`]
+//! defaults to [`ProxyConfig::Direct`], and a caller that wants a
+//! request routed through a proxy sets it with
+//! [`NetworkRequest::with_proxy`]. Which proxy that should be for a
+//! given tab is `crynn-engine`'s `TabRegistry::effective_proxy` —
+//! a tab's own override, or its container's, the same per-tab-then-
+//! per-container fallback a Firefox-backed build's per-container proxy
+//! setting already works this way.
+//!
+//! [`RequestLog`] is the lightweight devtools network panel's backing
+//! store: a bounded per-tab ring buffer of [`RequestLogEntry`] that
+//! [`NetworkManager::record_request`] appends to and
+//! [`NetworkManager::request_log`] reads back, keyed by whatever string
+//! the caller identifies a tab with, the same as [`ConnectionStats`] is
+//! keyed by origin rather than a richer type this crate doesn't have.
+//!
+//! [`AltSvcCache`] parses `Alt-Svc` response headers into per-origin
+//! protocol hints with their own expirations, so
+//! [`NetworkManager::select_protocol`] can upgrade a later request to
+//! HTTP/2 or HTTP/3 on the strength of a server's own advertisement
+//! rather than [`NetworkManager::preferred_protocol`]'s unseen-origin
+//! optimism or re-probing from scratch. An actual `quinn`+`h3` client —
+//! 0-RTT resumption and all — is the real transport this crate still
+//! doesn't have, the same gap [`AuthPipeline`] and [`SuggestionsClient`]
+//! are already contracts around rather than implementations of.
+//!
+//! [`HttpCache`] is the quota-aware cache [`warm_up`] revalidates
+//! against: a [`CacheTier::DocumentAndScript`] budget and a separate,
+//! far more aggressively evicted [`CacheTier::Media`] budget, so one
+//! large video download can't take every other site asset down with
+//! it. [`HttpCache::stats`]'s [`StorageStats`] is the per-tier
+//! breakdown an `about:cache` page would show; like
+//! [`ConnectionStats`], it holds no real response bytes, just the
+//! bookkeeping a real cache would report through.
+//!
+//! [`HttpCache::insert_range`] is the partial-content half of the same
+//! cache: each 206 response's [`ByteRange`] merges into a [`RangeSet`]
+//! until it covers the resource end-to-end, at which point it's promoted
+//! into a normal entry the same way [`HttpCache::insert`] would have
+//! stored it whole — what a media player seeking through a cached video
+//! relies on instead of re-downloading the whole file per seek.
+//! [`HttpCache::is_fresh`] is revalidation's half: [`ETag::weakly_matches`]
+//! implements RFC 7232's weak comparison so a cache entry survives
+//! revalidation against a weak tag the same way a real browser's does,
+//! rather than this crate only ever being able to tell strong tags apart.
+//!
+//! [`DnsResolver`] caches A/AAAA lookups by hostname, every address a
+//! lookup returned rather than just the first one, expiring by the
+//! lowest TTL among those records rather than a hardcoded duration.
+//! Past [`DnsResolver::with_capacity`]'s limit it evicts the least-
+//! recently-used host rather than wiping the whole cache. The actual
+//! lookup is a [`DnsLookup`], for the same reason [`SuggestionsTransport`]
+//! exists; [`DnsResolver::resolve_all`] returns every address for callers
+//! that want to try more than one (round-robin DNS, Happy Eyeballs),
+//! and [`DnsResolver::resolve`] is the single-address convenience on
+//! top of it.
+//!
+//! [`HostsOverrides`] sits in front of either: a user-editable
+//! `/etc/hosts`-style table checked before [`DnsResolver`] or
+//! [`FallbackResolver`] ever run a lookup, resolving a hostname straight
+//! to an address or to [`HostsEntry::Blocked`] without touching the
+//! network at all. [`FallbackResolver`] is resolver selection itself,
+//! for the hosts a table entry doesn't cover: [`ResolverKind::Doh`] tried
+//! first, [`ResolverKind::Dot`] next, [`ResolverKind::System`] last,
+//! each [`DnsLookup`] the same injected contract [`DnsResolver`] takes —
+//! and a kind that keeps coming back empty gets skipped until it
+//! recovers, the same spirit as [`NetworkManager::preferred_protocol`]
+//! backing off a struggling protocol rather than retrying it forever.
+//!
+//! [`warm_up`] is session restore's cache-preheating pass: given the
+//! URLs of the tabs being restored, it preconnects to each distinct
+//! origin and revalidates that origin's main document, through a
+//! [`CacheWarmer`] for the same reason [`SuggestionsTransport`] exists.
+//! There's no `Priority` concept anywhere in this crate to hang "low
+//! priority" off of, so [`warm_up`] expresses it structurally instead —
+//! a caller-supplied `max_concurrency` cap, with earlier URLs (the
+//! caller's own ordering, e.g. the session's active tab first) winning
+//! the limited slots — rather than by adding one just for this.
+//!
+//! [`CancellationRegistry`] is the stop-button contract:
+//! [`NetworkManager::begin_request`] hands out a [`RequestHandle`] a
+//! caller holds onto, [`NetworkManager::cancel_request`] is what the
+//! shell's stop button calls, and [`NetworkManager::is_request_cancelled`]
+//! is what whatever drives an actual connection attempt checks between
+//! phases — the same gap every other per-request decision in this crate
+//! is a contract around rather than an implementation of.
+//! [`NetworkRequest::timeouts`] carries [`PhaseTimeouts`] alongside it:
+//! separate connect/read/total budgets instead of one end-to-end clock,
+//! so a slow-to-connect server and one that connects fine but trickles
+//! bytes fail for different, more specific reasons.
+//!
+//! [`HstsStore`] tracks per-host `Strict-Transport-Security` policy the
+//! same way [`AltSvcCache`] tracks `Alt-Svc`: [`HstsStore::record`]
+//! parses a response header with its own `max-age`/`includeSubDomains`
+//! honored, and [`HstsStore::requires_https`] is what whatever resolves
+//! a URL to dial should consult before choosing `http://` over
+//! `https://` — it also always returns `true` for a small built-in
+//! preload list, so a host never gets one unencrypted first request
+//! before this crate has seen a header from it.
+//!
+//! [`ConditionSimulator`] is the developer-facing counterpart to all of
+//! the above: a devtools-like panel forces [`NetworkCondition::Offline`],
+//! `Slow3g`, `HighLatency`, or `PacketLoss` onto one tab at a time, and
+//! [`ConditionSimulator::should_fail`]/[`ConditionSimulator::extra_latency_ms`]
+//! are what whatever drives an actual request for that tab consults
+//! before sending it — the same contract-not-implementation split as
+//! everywhere else in this crate.
+//!
+//! [`CertificateValidator`] is TLS handshake validation's own contract,
+//! for the same reason [`DnsLookup`] exists: a real TLS library is what
+//! would actually walk a certificate chain, so this crate only owns the
+//! decision built on top of its answer. [`CertificateError`] replaces an
+//! all-or-nothing failure with the specific reason (expired, untrusted, a
+//! name mismatch, revoked) the shell's interstitial needs to explain
+//! itself, and [`CertificateOverrides::mint`]/[`validate_with_overrides`]
+//! are the scoped per-host consent [`NetworkManager::mint_certificate_override`]
+//! hands out once the user clicks through that interstitial — scoped to
+//! the exact host and error it was shown for, the same explicit-consent
+//! shape [`PrivateNetworkGuard::allow`] already uses for private-network
+//! access.
+//!
+//! [`validate_stapled_ocsp`]/[`CrliteList`] are revocation checking's own
+//! soft-fail contracts, folding into the same [`CertificateError::Revoked`]
+//! [`CertificateValidator`] already reports: [`RevocationStatus::Unknown`]
+//! (an expired stapled response, or a serial this crate's local list has
+//! never seen) is deliberately never treated as revoked, so an
+//! unreachable OCSP responder can't turn into a false positive the way a
+//! hard-fail check would. There's no `crates/network/security.rs` in this
+//! tree to extend — TLS handshake decisions already live in this module
+//! alongside [`CertificateValidator`], so that's where this landed
+//! instead.
+//!
+//! [`decode`] is different from the rest of this crate: undoing
+//! `Content-Encoding` is a pure byte transform, not something that needs
+//! a real transport to exist first, so it's implemented rather than left
+//! as a contract, the same as [`AuthChallenge`]'s header parsing or
+//! [`MultipartBuilder`]'s body building. [`apply_accept_encoding`] sets
+//! the request side of the exchange, the same shape as
+//! [`apply_resistant_headers`]; [`NetworkResponse::decode_body`] is what
+//! a real transport calls on the response side, honoring
+//! [`NetworkRequest::auto_decompress`] for a caller that wants the wire
+//! bytes as-is (devtools inspecting a response, say) — so whatever ends
+//! up stored in [`HttpCache`] is always the decoded body.
+//!
+//! [`attach_cookies`]/[`record_set_cookie`] are the `Cookie`/`Set-Cookie`
+//! round trip, for the same reason [`CredentialProvider`]/[`DnsLookup`]
+//! exist: this crate has no real cookie store of its own (`crynn-cookies`'s
+//! `CookieManager` is that), and depending on it just to hold a setting
+//! would invert the direction `crynn-shell` already depends on both
+//! crates in. [`CookieJar`] is the contract `CookieManager` answers
+//! through instead.
+//!
+//! [`TranslationClient`] is the same OpenSearch-style split as
+//! [`SuggestionsClient`], for a self-hosted LibreTranslate-compatible
+//! page-translation endpoint instead of search suggestions: it builds
+//! the `{"q", "source", "target"}` request body and parses
+//! `{"translatedText": ...}` back out, but sending it is a
+//! [`TranslationTransport`]'s job.
+//!
+//! [`RequestInterceptor`] is the fetch-layer hook for inspecting,
+//! rewriting, or blocking an outgoing [`NetworkRequest`]; [`run_interceptors`]
+//! is what a real transport would call right before sending, threading
+//! each interceptor's (possibly rewritten) request into the next and
+//! stopping at the first block. [`FilterListBlocker`] is the one built-in
+//! implementation: an EasyList/uBlock-style filter list, with `||domain^`
+//! and plain-substring block rules and `@@`-prefixed exceptions that
+//! always win over a matching block rule — content-blocking at the fetch
+//! layer, upstream of `crynn_tracking_protection::TrackingGuard`'s
+//! category-based classification.
+//!
+//! [`ConnectionPool`] is explicit per-host keep-alive bookkeeping, for
+//! the same reason [`ConnectionStats`] is kept separately from whatever
+//! would actually dial a socket: [`ConnectionPool::checkout`] hands back
+//! an idle [`ConnectionId`] to reuse, a freshly "dialed" one, or
+//! [`CheckoutOutcome::AtLimit`] once a host is already at
+//! [`PoolConfig::max_connections_per_host`], and
+//! [`ConnectionPool::release`] returns one to the idle set for
+//! [`ConnectionPool::evict_idle`] to eventually reclaim past
+//! [`PoolConfig::idle_timeout_ms`]. [`NetworkManager::pool_stats`]/
+//! [`NetworkManager::pool_stats_all`] expose [`PoolStats`] the same way
+//! [`NetworkManager::connection_stats`] exposes [`ConnectionStats`], so
+//! the memory profiler can attribute socket usage per host and tests can
+//! assert a connection was reused rather than redialed.
+//!
+//! [`WebSocketManager`] is `ws://`/`wss://`'s contract, the same split as
+//! everywhere else in this crate that needs a real transport: opening a
+//! connection is [`handshake_request`] (the same [`ProxyConfig`] and
+//! [`CookieJar`] a plain HTTP request to the origin would carry) followed
+//! by [`validate_handshake_response`] checking the server's answer —
+//! [`generate_client_key`] and [`accept_key`] are the random nonce and
+//! SHA-1 digest RFC 6455 defines for that handshake, implemented rather
+//! than left a contract the same as [`decode`]. [`requires_secure_websocket`]
+//! folds in [`HstsStore`] the same way a plain navigation would, so a host
+//! that's pinned itself to HTTPS can't be downgraded by opening a
+//! plaintext WebSocket to it instead. Once open, [`WebSocketManager::receive`]/
+//! [`WebSocketManager::drain`] is the poll/drain queue standing in for an
+//! async stream of [`WebSocketFrame`]s, the same shape `crynn_engine`'s
+//! `PushInbox` uses for push messages.
+//!
+//! [`TlsInfo`] is what [`NetworkResponse::tls`] carries for a secure
+//! request: the negotiated [`TlsVersion`], cipher suite, ALPN protocol,
+//! and certificate chain a real handshake would report, for the shell's
+//! padlock/site-info panel to read directly instead of guessing security
+//! from the URL scheme. It leaves the chain as raw DER bytes and the
+//! revocation check unrun, the same split [`CertificateValidator`]
+//! already draws between carrying TLS data and validating it.
+
+mod alt_svc;
+mod basic;
+mod body;
+mod cache;
+mod cancellation;
+mod challenge;
+mod connection;
+mod connectivity;
+mod cookie;
+mod credential;
+mod decode;
+mod digest;
+mod dns;
+mod etag;
+mod fingerprinting;
+mod hosts;
+mod hsts;
+mod intercept;
+mod multipart;
+mod pipeline;
+mod pool;
+mod preheat;
+mod proxy;
+mod ranges;
+mod request;
+mod request_log;
+mod resolver_fallback;
+mod response;
+mod retry;
+mod ssrf;
+mod suggestions;
+mod throttle;
+mod tls;
+mod translation;
+mod websocket;
+
+pub use alt_svc::AltSvcCache;
+pub use body::{BodySource, ProgressCallback};
+pub use cache::{CacheTier, HttpCache, StorageStats, TierStats};
+pub use cancellation::{CancellationRegistry, PhaseTimeouts, RequestHandle};
+pub use challenge::{AuthChallenge, AuthScheme};
+pub use connection::{ConnectionError, ConnectionStats, NetworkManager, Protocol};
+pub use connectivity::{check_connectivity, check_connectivity_result, Connectivity, CONNECTIVITY_CHECK_URL};
+pub use cookie::{attach_cookies, record_set_cookie, CookieJar};
+pub use credential::{AuthTarget, Credential, CredentialProvider};
+pub use decode::{apply_accept_encoding, decode, ACCEPT_ENCODING};
+pub use dns::{DnsLookup, DnsRecord, DnsResolver};
+pub use etag::ETag;
+pub use fingerprinting::{apply_resistant_headers, RESISTANT_ACCEPT_LANGUAGE, RESISTANT_USER_AGENT};
+pub use hosts::{HostsEntry, HostsOverrides};
+pub use hsts::HstsStore;
+pub use intercept::{run_interceptors, FilterListBlocker, InterceptAction, RequestInterceptor};
+pub use multipart::{MultipartBody, MultipartBuilder};
+pub use pipeline::AuthPipeline;
+pub use pool::{CheckoutOutcome, ConnectionId, ConnectionPool, PoolConfig, PoolStats};
+pub use preheat::{warm_up, CacheWarmer, WarmupOutcome};
+pub use proxy::ProxyConfig;
+pub use ranges::{ByteRange, RangeSet};
+pub use request::NetworkRequest;
+pub use request_log::{RequestLog, RequestLogEntry, REQUEST_LOG_CAPACITY};
+pub use resolver_fallback::{FallbackResolver, ResolverKind};
+pub use response::NetworkResponse;
+pub use retry::{RetryPolicy, TransientFailure};
+pub use ssrf::{is_private_network_address, PrivateNetworkGuard, RequestOrigin};
+pub use suggestions::{SuggestionsClient, SuggestionsTransport};
+pub use throttle::{ConditionSimulator, NetworkCondition};
+pub use tls::{
+    validate_stapled_ocsp, validate_with_overrides, CertificateError, CertificateOverrides, CertificateValidator, CrliteList, OcspResponse,
+    OverrideToken, RevocationStatus, TlsInfo, TlsVersion,
+};
+pub use translation::{TranslationClient, TranslationTransport};
+pub use websocket::{
+    accept_key, generate_client_key, handshake_request, requires_secure_websocket, validate_handshake_response, HandshakeError,
+    WebSocketFrame, WebSocketId, WebSocketManager,
+};
@@ -0,0 +1,177 @@
+use std::path::PathBuf;
+
+use rand::RngExt;
+
+enum PartData {
+    Bytes(Vec<u8>),
+    File(PathBuf),
+}
+
+struct Part {
+    name: String,
+    filename: Option<String>,
+    content_type: Option<String>,
+    data: PartData,
+}
+
+/// Builds a `multipart/form-data` body one field at a time, per RFC 7578.
+/// File fields are stored as a path rather than read eagerly, so adding
+/// one doesn't load the attachment into memory until [`Self::build`]
+/// actually assembles the request.
+#[derive(Default)]
+pub struct MultipartBuilder {
+    parts: Vec<Part>,
+}
+
+impl MultipartBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a plain text field, e.g. a form value alongside an upload.
+    pub fn field(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.parts.push(Part {
+            name: name.into(),
+            filename: None,
+            content_type: None,
+            data: PartData::Bytes(value.into().into_bytes()),
+        });
+        self
+    }
+
+    /// Adds a file field read from disk when the body is built — the
+    /// shape an attachment upload needs so large files aren't buffered
+    /// twice.
+    pub fn file_field(
+        mut self,
+        name: impl Into<String>,
+        filename: impl Into<String>,
+        content_type: impl Into<String>,
+        path: impl Into<PathBuf>,
+    ) -> Self {
+        self.parts.push(Part {
+            name: name.into(),
+            filename: Some(filename.into()),
+            content_type: Some(content_type.into()),
+            data: PartData::File(path.into()),
+        });
+        self
+    }
+
+    /// Adds a file field from bytes already in memory, e.g. a
+    /// generated thumbnail that never touched disk.
+    pub fn bytes_field(
+        mut self,
+        name: impl Into<String>,
+        filename: impl Into<String>,
+        content_type: impl Into<String>,
+        bytes: Vec<u8>,
+    ) -> Self {
+        self.parts.push(Part {
+            name: name.into(),
+            filename: Some(filename.into()),
+            content_type: Some(content_type.into()),
+            data: PartData::Bytes(bytes),
+        });
+        self
+    }
+
+    pub fn build(self) -> MultipartBody {
+        MultipartBody { boundary: random_boundary(), parts: self.parts }
+    }
+}
+
+/// An assembled `multipart/form-data` body, ready for
+/// [`crate::BodySource::Multipart`].
+pub struct MultipartBody {
+    boundary: String,
+    parts: Vec<Part>,
+}
+
+impl MultipartBody {
+    /// Value for the request's `Content-Type` header.
+    pub fn content_type(&self) -> String {
+        format!("multipart/form-data; boundary={}", self.boundary)
+    }
+
+    /// Total size in bytes if every part's size is known upfront, which is
+    /// always true today since parts are either in-memory bytes or a file
+    /// whose length `stat` reports.
+    pub fn size_bytes(&self) -> Option<u64> {
+        let mut total = 0u64;
+        for part in &self.parts {
+            total += match &part.data {
+                PartData::Bytes(bytes) => bytes.len() as u64,
+                PartData::File(path) => std::fs::metadata(path).ok()?.len(),
+            };
+            total += part_header(part, &self.boundary).len() as u64;
+            total += b"\r\n".len() as u64;
+        }
+        total += format!("--{}--\r\n", self.boundary).len() as u64;
+        Some(total)
+    }
+
+    pub(crate) fn into_bytes(self) -> std::io::Result<Vec<u8>> {
+        let mut out = Vec::new();
+        for part in &self.parts {
+            out.extend_from_slice(part_header(part, &self.boundary).as_bytes());
+            match &part.data {
+                PartData::Bytes(bytes) => out.extend_from_slice(bytes),
+                PartData::File(path) => out.extend_from_slice(&std::fs::read(path)?),
+            }
+            out.extend_from_slice(b"\r\n");
+        }
+        out.extend_from_slice(format!("--{}--\r\n", self.boundary).as_bytes());
+        Ok(out)
+    }
+}
+
+fn part_header(part: &Part, boundary: &str) -> String {
+    let mut header = format!("--{boundary}\r\nContent-Disposition: form-data; name=\"{}\"", part.name);
+    if let Some(filename) = &part.filename {
+        header.push_str(&format!("; filename=\"{filename}\""));
+    }
+    header.push_str("\r\n");
+    if let Some(content_type) = &part.content_type {
+        header.push_str(&format!("Content-Type: {content_type}\r\n"));
+    }
+    header.push_str("\r\n");
+    header
+}
+
+fn random_boundary() -> String {
+    let mut bytes = [0u8; 16];
+    rand::rng().fill(&mut bytes);
+    let hex: String = bytes.iter().map(|b| format!("{b:02x}")).collect();
+    format!("crynn-boundary-{hex}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn build_wraps_every_field_in_its_own_boundary() {
+        let body = MultipartBuilder::new()
+            .field("name", "Alice")
+            .bytes_field("avatar", "avatar.png", "image/png", vec![1, 2, 3])
+            .build();
+        let boundary = body.boundary.clone();
+        let bytes = body.into_bytes().unwrap();
+        let text = String::from_utf8_lossy(&bytes);
+
+        assert!(text.contains(&format!("--{boundary}")));
+        assert!(text.contains(r#"name="name""#));
+        assert!(text.contains(r#"name="avatar"; filename="avatar.png""#));
+        assert!(text.contains("Content-Type: image/png"));
+        assert!(text.ends_with(&format!("--{boundary}--\r\n")));
+    }
+
+    #[test]
+    fn size_bytes_accounts_for_headers_and_the_closing_boundary() {
+        let body = MultipartBuilder::new().field("name", "Alice").build();
+        let reported = body.size_bytes().unwrap();
+        let actual = body.into_bytes().unwrap().len() as u64;
+        assert_eq!(reported, actual);
+    }
+}
@@ -0,0 +1,195 @@
+use std::collections::HashMap;
+
+use crynn_error::NetworkError;
+
+use crate::basic;
+use crate::challenge::{parse_challenge, AuthChallenge, AuthScheme};
+use crate::credential::{AuthTarget, Credential, CredentialProvider};
+use crate::digest;
+
+/// Drives a [`CredentialProvider`] to answer 401/407 challenges and
+/// produces the header value a request should be retried with.
+///
+/// Remembers the credential and Digest nonce count per [`AuthTarget`], so
+/// repeated requests to the same realm (every resource on a
+/// Digest-protected site, for instance) don't prompt again and increment
+/// `nc` correctly.
+#[derive(Default)]
+pub struct AuthPipeline {
+    credentials: HashMap<AuthTarget, Credential>,
+    nonce_counts: HashMap<AuthTarget, u32>,
+}
+
+impl AuthPipeline {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parses `header_value` (the `WWW-Authenticate`/`Proxy-Authenticate`
+    /// line from a 401/407 response) and returns the
+    /// `Authorization`/`Proxy-Authorization` value to retry the request
+    /// with, asking `provider` for a credential if one for `host` isn't
+    /// already known.
+    ///
+    /// `header_name` is the header the caller should set on the retry:
+    /// `"Authorization"` for a 401, `"Proxy-Authorization"` for a 407.
+    pub fn respond_to_challenge(
+        &mut self,
+        host: &str,
+        proxy: bool,
+        header_value: &str,
+        method: &str,
+        uri: &str,
+        provider: &mut dyn CredentialProvider,
+    ) -> Result<(&'static str, String), NetworkError> {
+        let challenge = parse_challenge(header_value).ok_or_else(|| NetworkError::UnsupportedAuthScheme {
+            scheme: header_value.split_whitespace().next().unwrap_or(header_value).to_string(),
+        })?;
+
+        let target = AuthTarget { host: host.to_string(), realm: challenge.realm.clone(), proxy };
+
+        let credential = match self.credentials.get(&target) {
+            Some(credential) => credential.clone(),
+            None => {
+                let credential = provider.provide(&target, challenge.scheme).ok_or_else(|| {
+                    NetworkError::AuthCredentialMissing { realm: target.realm.clone(), host: target.host.clone() }
+                })?;
+                self.credentials.insert(target.clone(), credential.clone());
+                credential
+            }
+        };
+
+        let header_name = if proxy { "Proxy-Authorization" } else { "Authorization" };
+        let value = self.authorization_value(&target, &challenge, &credential, method, uri);
+        Ok((header_name, value))
+    }
+
+    fn authorization_value(
+        &mut self,
+        target: &AuthTarget,
+        challenge: &AuthChallenge,
+        credential: &Credential,
+        method: &str,
+        uri: &str,
+    ) -> String {
+        match challenge.scheme {
+            AuthScheme::Basic => basic::header_value(credential),
+            AuthScheme::Digest => {
+                let nonce_count = self.nonce_counts.entry(target.clone()).or_insert(0);
+                *nonce_count += 1;
+                digest::header_value(credential, challenge, method, uri, *nonce_count)
+            }
+        }
+    }
+
+    /// Call once a request retried with `credential` has actually
+    /// succeeded, so a shell offering to remember the password can do so
+    /// via [`CredentialProvider::on_authenticated`].
+    pub fn confirm_authenticated(&self, target: &AuthTarget, credential: &Credential, provider: &mut dyn CredentialProvider) {
+        provider.on_authenticated(target, credential);
+    }
+
+    /// Drops every cached credential and nonce count, e.g. on logout.
+    pub fn clear(&mut self) {
+        self.credentials.clear();
+        self.nonce_counts.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct FixedProvider {
+        credential: Option<Credential>,
+        calls: u32,
+    }
+
+    impl CredentialProvider for FixedProvider {
+        fn provide(&mut self, _target: &AuthTarget, _scheme: AuthScheme) -> Option<Credential> {
+            self.calls += 1;
+            self.credential.clone()
+        }
+    }
+
+    fn provider(username: &str, password: &str) -> FixedProvider {
+        FixedProvider {
+            credential: Some(Credential { username: username.to_string(), password: password.to_string() }),
+            calls: 0,
+        }
+    }
+
+    #[test]
+    fn basic_challenge_produces_a_basic_header() {
+        let mut pipeline = AuthPipeline::new();
+        let mut provider = provider("Aladdin", "open sesame");
+
+        let (name, value) = pipeline
+            .respond_to_challenge("example.com", false, r#"Basic realm="Protected""#, "GET", "/", &mut provider)
+            .unwrap();
+
+        assert_eq!(name, "Authorization");
+        assert_eq!(value, "Basic QWxhZGRpbjpvcGVuIHNlc2FtZQ==");
+        assert_eq!(provider.calls, 1);
+    }
+
+    #[test]
+    fn a_second_challenge_for_the_same_realm_reuses_the_cached_credential() {
+        let mut pipeline = AuthPipeline::new();
+        let mut provider = provider("user", "pass");
+
+        pipeline
+            .respond_to_challenge("example.com", false, r#"Basic realm="R""#, "GET", "/a", &mut provider)
+            .unwrap();
+        pipeline
+            .respond_to_challenge("example.com", false, r#"Basic realm="R""#, "GET", "/b", &mut provider)
+            .unwrap();
+
+        assert_eq!(provider.calls, 1);
+    }
+
+    #[test]
+    fn proxy_challenges_use_the_proxy_authorization_header_name() {
+        let mut pipeline = AuthPipeline::new();
+        let mut provider = provider("user", "pass");
+
+        let (name, _) = pipeline
+            .respond_to_challenge("proxy.example.com", true, r#"Basic realm="R""#, "GET", "/", &mut provider)
+            .unwrap();
+
+        assert_eq!(name, "Proxy-Authorization");
+    }
+
+    #[test]
+    fn digest_nonce_count_increments_on_each_retry_for_the_same_target() {
+        let mut pipeline = AuthPipeline::new();
+        let mut provider = provider("user", "pass");
+        let challenge = r#"Digest realm="R", nonce="n", qop="auth""#;
+
+        let (_, first) = pipeline.respond_to_challenge("example.com", false, challenge, "GET", "/", &mut provider).unwrap();
+        let (_, second) = pipeline.respond_to_challenge("example.com", false, challenge, "GET", "/", &mut provider).unwrap();
+
+        assert!(first.contains("nc=00000001"));
+        assert!(second.contains("nc=00000002"));
+    }
+
+    #[test]
+    fn an_unsupported_scheme_is_reported_rather_than_guessed_at() {
+        let mut pipeline = AuthPipeline::new();
+        let mut provider = provider("user", "pass");
+
+        let err = pipeline.respond_to_challenge("example.com", false, "Negotiate abc", "GET", "/", &mut provider);
+
+        assert!(err.is_err());
+    }
+
+    #[test]
+    fn a_cancelled_prompt_reports_a_missing_credential_error() {
+        let mut pipeline = AuthPipeline::new();
+        let mut provider = FixedProvider { credential: None, calls: 0 };
+
+        let err = pipeline.respond_to_challenge("example.com", false, r#"Basic realm="R""#, "GET", "/", &mut provider);
+
+        assert!(matches!(err, Err(NetworkError::AuthCredentialMissing { .. })));
+    }
+}
@@ -0,0 +1,226 @@
+use std::collections::HashMap;
+
+/// Per-host connection pool limits: how many concurrent connections a
+/// single host may hold open, and how long an idle one is kept around
+/// before [`ConnectionPool::evict_idle`] closes it. A real transport's
+/// socket reuse would be configured with exactly these two knobs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PoolConfig {
+    pub max_connections_per_host: u32,
+    pub idle_timeout_ms: u64,
+}
+
+impl Default for PoolConfig {
+    fn default() -> Self {
+        Self { max_connections_per_host: 6, idle_timeout_ms: 60_000 }
+    }
+}
+
+/// Identifies a pooled connection for the lifetime of the pool. Not
+/// meaningful across pools, the same as [`crate::RequestHandle`] is only
+/// meaningful against the [`crate::CancellationRegistry`] that issued it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ConnectionId(u64);
+
+/// What [`ConnectionPool::checkout`] handed back: a connection pulled out
+/// of the idle set, a brand new one because the host had none idle, or a
+/// refusal because the host is already at [`PoolConfig::max_connections_per_host`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckoutOutcome {
+    Reused(ConnectionId),
+    Created(ConnectionId),
+    AtLimit,
+}
+
+/// Point-in-time accounting for one host's connections, as the memory
+/// profiler and an `about:network` pool view would both want to show:
+/// how many connections are doing work right now, how many are idle and
+/// waiting for reuse, and the lifetime counts behind those numbers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct PoolStats {
+    pub active: u32,
+    pub idle: u32,
+    pub created: u64,
+    pub reused: u64,
+}
+
+#[derive(Debug, Default)]
+struct HostPool {
+    idle: Vec<(ConnectionId, u64)>,
+    active: u32,
+    created: u64,
+    reused: u64,
+}
+
+/// Explicit connection-pool bookkeeping, keyed by origin the same way
+/// [`crate::NetworkManager`]'s own [`crate::ConnectionStats`] map is.
+/// There's no real socket behind a [`ConnectionId`] yet — this is the
+/// contract a transport's keep-alive pool would answer through, the same
+/// split as everywhere else in this crate that needs one.
+#[derive(Debug)]
+pub struct ConnectionPool {
+    config: PoolConfig,
+    hosts: HashMap<String, HostPool>,
+    next_id: u64,
+}
+
+impl ConnectionPool {
+    pub fn new(config: PoolConfig) -> Self {
+        Self { config, hosts: HashMap::new(), next_id: 0 }
+    }
+
+    pub fn config(&self) -> PoolConfig {
+        self.config
+    }
+
+    /// Checks out a connection for `origin`: an idle one if `origin` has
+    /// one on hand, otherwise a freshly "dialed" one, unless `origin` is
+    /// already at [`PoolConfig::max_connections_per_host`] active
+    /// connections.
+    pub fn checkout(&mut self, origin: &str) -> CheckoutOutcome {
+        let host = self.hosts.entry(origin.to_string()).or_default();
+        if let Some((id, _)) = host.idle.pop() {
+            host.active += 1;
+            host.reused += 1;
+            return CheckoutOutcome::Reused(id);
+        }
+        if host.active >= self.config.max_connections_per_host {
+            return CheckoutOutcome::AtLimit;
+        }
+        let id = ConnectionId(self.next_id);
+        self.next_id += 1;
+        host.active += 1;
+        host.created += 1;
+        CheckoutOutcome::Created(id)
+    }
+
+    /// Returns `conn` to `origin`'s idle set, timestamped `now` so
+    /// [`Self::evict_idle`] knows how long it's been sitting there.
+    pub fn release(&mut self, origin: &str, conn: ConnectionId, now: u64) {
+        let host = self.hosts.entry(origin.to_string()).or_default();
+        host.active = host.active.saturating_sub(1);
+        host.idle.push((conn, now));
+    }
+
+    /// Drops every idle connection across every host that's been sitting
+    /// longer than [`PoolConfig::idle_timeout_ms`] as of `now`. Whatever
+    /// drives a real keep-alive pool calls this periodically, the same
+    /// as [`crate::HttpCache`]'s own eviction is driven from outside.
+    pub fn evict_idle(&mut self, now: u64) {
+        for host in self.hosts.values_mut() {
+            host.idle.retain(|(_, released_at)| now.saturating_sub(*released_at) < self.config.idle_timeout_ms);
+        }
+    }
+
+    pub fn pool_stats(&self, origin: &str) -> PoolStats {
+        self.hosts
+            .get(origin)
+            .map(|host| PoolStats { active: host.active, idle: host.idle.len() as u32, created: host.created, reused: host.reused })
+            .unwrap_or_default()
+    }
+
+    /// Every host with any recorded pool activity, sorted by origin for a
+    /// stable display order, for the memory profiler's full breakdown.
+    pub fn stats(&self) -> Vec<(&str, PoolStats)> {
+        let mut entries: Vec<(&str, PoolStats)> = self
+            .hosts
+            .iter()
+            .map(|(origin, host)| {
+                (origin.as_str(), PoolStats { active: host.active, idle: host.idle.len() as u32, created: host.created, reused: host.reused })
+            })
+            .collect();
+        entries.sort_by_key(|(origin, _)| *origin);
+        entries
+    }
+}
+
+impl Default for ConnectionPool {
+    fn default() -> Self {
+        Self::new(PoolConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn an_unseen_host_reports_empty_stats() {
+        let pool = ConnectionPool::default();
+        assert_eq!(pool.pool_stats("https://example.com"), PoolStats::default());
+    }
+
+    #[test]
+    fn checkout_on_an_empty_host_creates_a_new_connection() {
+        let mut pool = ConnectionPool::default();
+        let outcome = pool.checkout("https://example.com");
+        assert!(matches!(outcome, CheckoutOutcome::Created(_)));
+        assert_eq!(pool.pool_stats("https://example.com"), PoolStats { active: 1, idle: 0, created: 1, reused: 0 });
+    }
+
+    #[test]
+    fn releasing_and_checking_out_again_reuses_the_connection() {
+        let mut pool = ConnectionPool::default();
+        let CheckoutOutcome::Created(conn) = pool.checkout("https://example.com") else {
+            panic!("expected a freshly created connection");
+        };
+        pool.release("https://example.com", conn, 0);
+        assert_eq!(pool.pool_stats("https://example.com"), PoolStats { active: 0, idle: 1, created: 1, reused: 0 });
+
+        let outcome = pool.checkout("https://example.com");
+
+        assert_eq!(outcome, CheckoutOutcome::Reused(conn));
+        assert_eq!(pool.pool_stats("https://example.com"), PoolStats { active: 1, idle: 0, created: 1, reused: 1 });
+    }
+
+    #[test]
+    fn checkout_past_the_per_host_limit_is_refused() {
+        let mut pool = ConnectionPool::new(PoolConfig { max_connections_per_host: 1, idle_timeout_ms: 60_000 });
+        assert!(matches!(pool.checkout("https://example.com"), CheckoutOutcome::Created(_)));
+        assert_eq!(pool.checkout("https://example.com"), CheckoutOutcome::AtLimit);
+    }
+
+    #[test]
+    fn a_different_host_has_its_own_independent_limit() {
+        let mut pool = ConnectionPool::new(PoolConfig { max_connections_per_host: 1, idle_timeout_ms: 60_000 });
+        assert!(matches!(pool.checkout("https://a.example.com"), CheckoutOutcome::Created(_)));
+        assert!(matches!(pool.checkout("https://b.example.com"), CheckoutOutcome::Created(_)));
+    }
+
+    #[test]
+    fn evict_idle_drops_connections_past_the_idle_timeout() {
+        let mut pool = ConnectionPool::new(PoolConfig { max_connections_per_host: 6, idle_timeout_ms: 1_000 });
+        let CheckoutOutcome::Created(conn) = pool.checkout("https://example.com") else {
+            panic!("expected a freshly created connection");
+        };
+        pool.release("https://example.com", conn, 0);
+
+        pool.evict_idle(5_000);
+
+        assert_eq!(pool.pool_stats("https://example.com").idle, 0);
+    }
+
+    #[test]
+    fn evict_idle_keeps_connections_still_within_the_timeout() {
+        let mut pool = ConnectionPool::new(PoolConfig { max_connections_per_host: 6, idle_timeout_ms: 10_000 });
+        let CheckoutOutcome::Created(conn) = pool.checkout("https://example.com") else {
+            panic!("expected a freshly created connection");
+        };
+        pool.release("https://example.com", conn, 0);
+
+        pool.evict_idle(5_000);
+
+        assert_eq!(pool.pool_stats("https://example.com").idle, 1);
+    }
+
+    #[test]
+    fn stats_lists_every_host_sorted_by_origin() {
+        let mut pool = ConnectionPool::default();
+        pool.checkout("https://b.example.com");
+        pool.checkout("https://a.example.com");
+
+        let origins: Vec<&str> = pool.stats().into_iter().map(|(origin, _)| origin).collect();
+
+        assert_eq!(origins, vec!["https://a.example.com", "https://b.example.com"]);
+    }
+}
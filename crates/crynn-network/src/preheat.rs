@@ -0,0 +1,112 @@
+use std::collections::HashSet;
+
+/// Decides whether preconnecting to an origin, or revalidating a cached
+/// document's freshness, would succeed. No real socket or on-disk HTTP
+/// cache exists in this build — the same split as [`crate::SuggestionsTransport`]
+/// — so [`warm_up`] takes a warmer rather than attempting either itself:
+/// a fake for tests, eventually a real h2/quinn connection pool plus
+/// cache revalidation once one exists to ask.
+pub trait CacheWarmer {
+    fn preconnect(&mut self, origin: &str) -> bool;
+    fn revalidate(&mut self, url: &str) -> bool;
+}
+
+/// What [`warm_up`] did for one restored tab's origin: whether
+/// preconnecting succeeded, and — only attempted if it did, since
+/// revalidating over a connection that isn't there yet would just fail
+/// again — whether its main document was already fresh in the cache.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WarmupOutcome {
+    pub url: String,
+    pub preconnected: bool,
+    pub revalidated: bool,
+}
+
+/// Preconnects to and revalidates the main document of up to
+/// `max_concurrency` of `urls`, one per distinct origin, so restoring a
+/// session doesn't open far more connections than a real low-priority
+/// background pass should ever hold open at once. Earlier URLs win ties
+/// for which origin gets the limited slots, so the caller should already
+/// have `urls` ordered by whichever tab the user's most likely to switch
+/// to first (e.g. the session's active tab first).
+pub fn warm_up(urls: &[String], warmer: &mut dyn CacheWarmer, max_concurrency: usize) -> Vec<WarmupOutcome> {
+    let mut seen_origins = HashSet::new();
+    let mut outcomes = Vec::new();
+
+    for url in urls {
+        if outcomes.len() >= max_concurrency {
+            break;
+        }
+        let origin = origin_of(url);
+        if !seen_origins.insert(origin.clone()) {
+            continue;
+        }
+
+        let preconnected = warmer.preconnect(&origin);
+        let revalidated = preconnected && warmer.revalidate(url);
+        outcomes.push(WarmupOutcome { url: url.clone(), preconnected, revalidated });
+    }
+
+    outcomes
+}
+
+fn origin_of(url: &str) -> String {
+    url.split_once("://").map(|(scheme, rest)| format!("{scheme}://{}", rest.split('/').next().unwrap_or(rest))).unwrap_or_else(|| url.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default)]
+    struct RecordingWarmer {
+        preconnected: Vec<String>,
+        revalidated: Vec<String>,
+        fail_preconnect_for: Option<String>,
+    }
+
+    impl CacheWarmer for RecordingWarmer {
+        fn preconnect(&mut self, origin: &str) -> bool {
+            self.preconnected.push(origin.to_string());
+            self.fail_preconnect_for.as_deref() != Some(origin)
+        }
+
+        fn revalidate(&mut self, url: &str) -> bool {
+            self.revalidated.push(url.to_string());
+            true
+        }
+    }
+
+    #[test]
+    fn warms_up_each_distinct_origin_once() {
+        let urls = vec!["https://example.com/a".to_string(), "https://example.com/b".to_string(), "https://other.example.com/".to_string()];
+        let mut warmer = RecordingWarmer::default();
+
+        let outcomes = warm_up(&urls, &mut warmer, 10);
+
+        assert_eq!(warmer.preconnected, vec!["https://example.com".to_string(), "https://other.example.com".to_string()]);
+        assert_eq!(outcomes.len(), 2);
+    }
+
+    #[test]
+    fn bounded_concurrency_caps_how_many_tabs_get_warmed() {
+        let urls = vec!["https://a.example.com/".to_string(), "https://b.example.com/".to_string(), "https://c.example.com/".to_string()];
+        let mut warmer = RecordingWarmer::default();
+
+        let outcomes = warm_up(&urls, &mut warmer, 2);
+
+        assert_eq!(outcomes.len(), 2);
+        assert_eq!(warmer.preconnected, vec!["https://a.example.com".to_string(), "https://b.example.com".to_string()]);
+    }
+
+    #[test]
+    fn revalidation_is_skipped_when_preconnecting_fails() {
+        let urls = vec!["https://example.com/".to_string()];
+        let mut warmer = RecordingWarmer { fail_preconnect_for: Some("https://example.com".to_string()), ..Default::default() };
+
+        let outcomes = warm_up(&urls, &mut warmer, 10);
+
+        assert_eq!(outcomes, vec![WarmupOutcome { url: "https://example.com/".to_string(), preconnected: false, revalidated: false }]);
+        assert!(warmer.revalidated.is_empty());
+    }
+}
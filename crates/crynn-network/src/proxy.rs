@@ -0,0 +1,33 @@
+/// Where a request should be routed: straight to the origin, or through
+/// an HTTP(S) CONNECT or SOCKS5 proxy. [`ProxyConfig::Direct`] is the
+/// default — most requests aren't proxied — so assigning a proxy to a
+/// tab or container is purely additive.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum ProxyConfig {
+    #[default]
+    Direct,
+    Http { host: String, port: u16 },
+    Socks5 { host: String, port: u16 },
+}
+
+impl ProxyConfig {
+    pub fn is_direct(&self) -> bool {
+        matches!(self, ProxyConfig::Direct)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn direct_is_the_default() {
+        assert_eq!(ProxyConfig::default(), ProxyConfig::Direct);
+        assert!(ProxyConfig::default().is_direct());
+    }
+
+    #[test]
+    fn a_configured_proxy_is_not_direct() {
+        assert!(!ProxyConfig::Http { host: "proxy.example.com".to_string(), port: 8080 }.is_direct());
+    }
+}
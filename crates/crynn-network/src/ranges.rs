@@ -0,0 +1,135 @@
+/// An inclusive byte range, as parsed from a `Range`/`Content-Range`
+/// header — `bytes=0-999` is `ByteRange { start: 0, end: 999 }`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ByteRange {
+    pub start: u64,
+    pub end: u64,
+}
+
+impl ByteRange {
+    pub fn new(start: u64, end: u64) -> Self {
+        Self { start, end }
+    }
+
+    pub fn len(&self) -> u64 {
+        self.end - self.start + 1
+    }
+
+    /// Always `false` — a range's `start..=end` bounds are inclusive on
+    /// both ends, so there is no way to construct an empty one.
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+
+    fn overlaps_or_is_adjacent_to(&self, other: &ByteRange) -> bool {
+        self.start <= other.end.saturating_add(1) && other.start <= self.end.saturating_add(1)
+    }
+}
+
+/// The byte ranges of one resource received so far, merged as they come
+/// in so a media player seeking back and forth through a partially
+/// downloaded video doesn't keep re-requesting bytes it already has.
+/// [`RangeSet::insert`] combines a newly received range with whatever
+/// overlaps or touches it; [`RangeSet::is_complete`] is what
+/// [`crate::HttpCache::insert_range`] checks before promoting a resource
+/// from partial to fully cached.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RangeSet {
+    ranges: Vec<ByteRange>,
+}
+
+impl RangeSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Merges `range` into the set, combining it with any range it
+    /// overlaps or is directly adjacent to into a single contiguous
+    /// range.
+    pub fn insert(&mut self, range: ByteRange) {
+        self.ranges.push(range);
+        self.ranges.sort_by_key(|r| r.start);
+        let merged = self.ranges.drain(..).fold(Vec::new(), |mut merged: Vec<ByteRange>, next| {
+            match merged.last_mut() {
+                Some(last) if last.overlaps_or_is_adjacent_to(&next) => {
+                    last.end = last.end.max(next.end);
+                }
+                _ => merged.push(next),
+            }
+            merged
+        });
+        self.ranges = merged;
+    }
+
+    /// How many distinct bytes have been received, across every merged
+    /// range.
+    pub fn covered_bytes(&self) -> u64 {
+        self.ranges.iter().map(ByteRange::len).sum()
+    }
+
+    /// Whether the merged ranges cover a `total_len`-byte resource with no
+    /// gaps, i.e. whether enough partial responses have arrived to combine
+    /// into one complete entry.
+    pub fn is_complete(&self, total_len: u64) -> bool {
+        total_len > 0 && self.ranges.as_slice() == [ByteRange::new(0, total_len - 1)]
+    }
+
+    pub fn ranges(&self) -> &[ByteRange] {
+        &self.ranges
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_single_range_is_complete_only_once_it_spans_the_whole_resource() {
+        let mut set = RangeSet::new();
+        set.insert(ByteRange::new(0, 499));
+        assert!(!set.is_complete(1_000));
+        set.insert(ByteRange::new(500, 999));
+        assert!(set.is_complete(1_000));
+    }
+
+    #[test]
+    fn overlapping_ranges_merge_into_one() {
+        let mut set = RangeSet::new();
+        set.insert(ByteRange::new(0, 599));
+        set.insert(ByteRange::new(400, 999));
+        assert_eq!(set.ranges(), &[ByteRange::new(0, 999)]);
+    }
+
+    #[test]
+    fn adjacent_ranges_merge_into_one() {
+        let mut set = RangeSet::new();
+        set.insert(ByteRange::new(0, 499));
+        set.insert(ByteRange::new(500, 999));
+        assert_eq!(set.ranges(), &[ByteRange::new(0, 999)]);
+    }
+
+    #[test]
+    fn a_gap_between_ranges_is_not_merged() {
+        let mut set = RangeSet::new();
+        set.insert(ByteRange::new(0, 199));
+        set.insert(ByteRange::new(800, 999));
+        assert_eq!(set.ranges().len(), 2);
+        assert!(!set.is_complete(1_000));
+    }
+
+    #[test]
+    fn covered_bytes_sums_every_merged_range() {
+        let mut set = RangeSet::new();
+        set.insert(ByteRange::new(0, 199));
+        set.insert(ByteRange::new(800, 999));
+        assert_eq!(set.covered_bytes(), 200 + 200);
+    }
+
+    #[test]
+    fn inserting_out_of_order_still_merges_correctly() {
+        let mut set = RangeSet::new();
+        set.insert(ByteRange::new(500, 999));
+        set.insert(ByteRange::new(0, 499));
+        assert_eq!(set.ranges(), &[ByteRange::new(0, 999)]);
+    }
+}
@@ -0,0 +1,141 @@
+use crate::body::BodySource;
+use crate::cancellation::PhaseTimeouts;
+use crate::proxy::ProxyConfig;
+
+/// An outgoing HTTP request, built up with [`NetworkRequest::new`] plus
+/// the `with_*` methods. Carries its own headers so a [`BodySource`] that
+/// needs one set (multipart's `Content-Type`, a file upload's
+/// `Content-Length`) doesn't have to be wired in separately by whatever
+/// ends up sending this.
+pub struct NetworkRequest {
+    pub method: String,
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Option<BodySource>,
+    /// Which proxy to dial this request through, resolved by the caller
+    /// from whatever tab or container it belongs to — see
+    /// `crynn_engine::TabRegistry::effective_proxy`. [`ProxyConfig::Direct`]
+    /// by default, the same as every other per-request decision this
+    /// crate models without a real transport to carry it out yet.
+    pub proxy: ProxyConfig,
+    /// Connect/read/total timeout budget for this request.
+    /// [`PhaseTimeouts::default`] by default; a caller with a
+    /// `crynn_config::NetworkConfig` to read from should build one from
+    /// that instead of relying on the built-in default.
+    pub timeouts: PhaseTimeouts,
+    /// Whether a response whose `Content-Encoding` names a
+    /// [`crate::decode`]-supported encoding should be decompressed before
+    /// it's handed back or cached. `true` by default; devtools' "disable
+    /// cache" style per-request overrides and anything inspecting the raw
+    /// wire bytes should set this to `false` instead of decoding and
+    /// re-compressing afterwards.
+    pub auto_decompress: bool,
+}
+
+impl NetworkRequest {
+    pub fn new(method: impl Into<String>, url: impl Into<String>) -> Self {
+        Self {
+            method: method.into(),
+            url: url.into(),
+            headers: Vec::new(),
+            body: None,
+            proxy: ProxyConfig::Direct,
+            timeouts: PhaseTimeouts::default(),
+            auto_decompress: true,
+        }
+    }
+
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    pub fn with_proxy(mut self, proxy: ProxyConfig) -> Self {
+        self.proxy = proxy;
+        self
+    }
+
+    pub fn with_timeouts(mut self, timeouts: PhaseTimeouts) -> Self {
+        self.timeouts = timeouts;
+        self
+    }
+
+    pub fn with_auto_decompress(mut self, auto_decompress: bool) -> Self {
+        self.auto_decompress = auto_decompress;
+        self
+    }
+
+    /// Attaches `body`, setting `Content-Length` when its size is known
+    /// upfront and, for a multipart body, `Content-Type` with its
+    /// boundary.
+    pub fn with_body(mut self, body: BodySource) -> Self {
+        if let BodySource::Multipart(multipart) = &body {
+            self = self.with_header("Content-Type", multipart.content_type());
+        }
+        if let Some(len) = body.size_bytes() {
+            self = self.with_header("Content-Length", len.to_string());
+        }
+        self.body = Some(body);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::multipart::MultipartBuilder;
+
+    #[test]
+    fn with_body_sets_content_length_when_the_size_is_known() {
+        let request = NetworkRequest::new("POST", "https://example.com/upload")
+            .with_body(BodySource::Bytes(vec![1, 2, 3]));
+        assert_eq!(
+            request.headers.iter().find(|(k, _)| k == "Content-Length").map(|(_, v)| v.as_str()),
+            Some("3")
+        );
+    }
+
+    #[test]
+    fn multipart_body_sets_content_type_with_its_boundary() {
+        let multipart = MultipartBuilder::new().field("name", "Alice").build();
+        let request = NetworkRequest::new("POST", "https://example.com/upload")
+            .with_body(BodySource::Multipart(multipart));
+        let content_type = request.headers.iter().find(|(k, _)| k == "Content-Type").unwrap().1.clone();
+        assert!(content_type.starts_with("multipart/form-data; boundary="));
+    }
+
+    #[test]
+    fn requests_default_to_a_direct_proxy() {
+        assert_eq!(NetworkRequest::new("GET", "https://example.com").proxy, crate::proxy::ProxyConfig::Direct);
+    }
+
+    #[test]
+    fn with_proxy_overrides_the_default() {
+        let proxy = crate::proxy::ProxyConfig::Socks5 { host: "proxy.example.com".to_string(), port: 1080 };
+        let request = NetworkRequest::new("GET", "https://example.com").with_proxy(proxy.clone());
+        assert_eq!(request.proxy, proxy);
+    }
+
+    #[test]
+    fn requests_default_to_phase_timeouts_default() {
+        assert_eq!(NetworkRequest::new("GET", "https://example.com").timeouts, PhaseTimeouts::default());
+    }
+
+    #[test]
+    fn with_timeouts_overrides_the_default() {
+        let timeouts = PhaseTimeouts::new(1_000, 2_000, 3_000);
+        let request = NetworkRequest::new("GET", "https://example.com").with_timeouts(timeouts);
+        assert_eq!(request.timeouts, timeouts);
+    }
+
+    #[test]
+    fn requests_auto_decompress_by_default() {
+        assert!(NetworkRequest::new("GET", "https://example.com").auto_decompress);
+    }
+
+    #[test]
+    fn with_auto_decompress_overrides_the_default() {
+        let request = NetworkRequest::new("GET", "https://example.com").with_auto_decompress(false);
+        assert!(!request.auto_decompress);
+    }
+}
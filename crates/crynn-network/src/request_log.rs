@@ -0,0 +1,86 @@
+use std::collections::VecDeque;
+
+use crate::connection::Protocol;
+
+/// One logged exchange, as a lightweight devtools network panel would
+/// show it. `status` is `None` for a request that failed outright rather
+/// than coming back with an HTTP error status.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RequestLogEntry {
+    pub method: String,
+    pub url: String,
+    pub status: Option<u16>,
+    pub size_bytes: u64,
+    pub duration_ms: u64,
+    pub protocol: Protocol,
+    pub cache_hit: bool,
+}
+
+/// Above this many entries for one tab, the oldest is dropped to make
+/// room for the newest — a tab left open for a long session shouldn't
+/// grow this log without bound.
+pub const REQUEST_LOG_CAPACITY: usize = 200;
+
+/// A bounded ring buffer of [`RequestLogEntry`], one per tab, owned by
+/// [`crate::NetworkManager`] the same way its per-origin
+/// [`crate::ConnectionStats`] are.
+#[derive(Debug, Clone, Default)]
+pub struct RequestLog {
+    entries: VecDeque<RequestLogEntry>,
+}
+
+impl RequestLog {
+    pub fn push(&mut self, entry: RequestLogEntry) {
+        if self.entries.len() >= REQUEST_LOG_CAPACITY {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(entry);
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = &RequestLogEntry> {
+        self.entries.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(url: &str) -> RequestLogEntry {
+        RequestLogEntry {
+            method: "GET".to_string(),
+            url: url.to_string(),
+            status: Some(200),
+            size_bytes: 1024,
+            duration_ms: 50,
+            protocol: Protocol::Http2,
+            cache_hit: false,
+        }
+    }
+
+    #[test]
+    fn push_beyond_capacity_drops_the_oldest_entry() {
+        let mut log = RequestLog::default();
+        for i in 0..REQUEST_LOG_CAPACITY {
+            log.push(entry(&format!("https://example.com/{i}")));
+        }
+        log.push(entry("https://example.com/newest"));
+
+        assert_eq!(log.len(), REQUEST_LOG_CAPACITY);
+        assert_eq!(log.entries().next().unwrap().url, "https://example.com/1");
+        assert_eq!(log.entries().last().unwrap().url, "https://example.com/newest");
+    }
+
+    #[test]
+    fn a_fresh_log_is_empty() {
+        assert!(RequestLog::default().is_empty());
+    }
+}
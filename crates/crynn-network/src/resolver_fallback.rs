@@ -0,0 +1,169 @@
+use std::collections::HashMap;
+
+use crate::dns::{DnsLookup, DnsRecord};
+
+/// Which transport a DNS lookup went out over, in the order
+/// [`FallbackResolver::resolve`] tries them: DNS-over-HTTPS first (the
+/// hardest for a network observer to see), DNS-over-TLS next, then
+/// whatever the OS's own resolver would have answered anyway.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ResolverKind {
+    Doh,
+    Dot,
+    System,
+}
+
+const FALLBACK_ORDER: [ResolverKind; 3] = [ResolverKind::Doh, ResolverKind::Dot, ResolverKind::System];
+
+/// Once a resolver kind has failed this many lookups in a row,
+/// [`FallbackResolver`] skips it entirely until it succeeds again,
+/// rather than paying its timeout on every single lookup while it's
+/// down.
+const UNHEALTHY_THRESHOLD: u32 = 3;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct Health {
+    consecutive_failures: u32,
+}
+
+impl Health {
+    fn is_healthy(&self) -> bool {
+        self.consecutive_failures < UNHEALTHY_THRESHOLD
+    }
+}
+
+/// Tries each [`ResolverKind`] in [`FALLBACK_ORDER`] in turn, skipping
+/// any currently unhealthy one, and remembers how each performed so a
+/// resolver that's down stops being tried on every lookup. An empty
+/// [`DnsLookup::lookup`] result counts as a failure for health-tracking
+/// purposes, the same way this crate has no stronger signal than that
+/// for any other contract-over-implementation seam.
+#[derive(Debug, Default)]
+pub struct FallbackResolver {
+    health: HashMap<ResolverKind, Health>,
+}
+
+impl FallbackResolver {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_healthy(&self, kind: ResolverKind) -> bool {
+        self.health.get(&kind).copied().unwrap_or_default().is_healthy()
+    }
+
+    /// Resolves `host` by trying each healthy [`ResolverKind`] in
+    /// [`FALLBACK_ORDER`], through whichever of `resolvers` answers for
+    /// it — a kind with no entry in `resolvers` is skipped, the same as
+    /// an unhealthy one. Stops at the first kind that returns a
+    /// non-empty result.
+    pub fn resolve(&mut self, host: &str, resolvers: &mut HashMap<ResolverKind, &mut dyn DnsLookup>) -> Vec<DnsRecord> {
+        for kind in FALLBACK_ORDER {
+            if !self.is_healthy(kind) {
+                continue;
+            }
+            let Some(lookup) = resolvers.get_mut(&kind) else {
+                continue;
+            };
+            let records = lookup.lookup(host);
+            let health = self.health.entry(kind).or_default();
+            if records.is_empty() {
+                health.consecutive_failures += 1;
+                continue;
+            }
+            health.consecutive_failures = 0;
+            return records;
+        }
+        Vec::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct FakeLookup(Vec<DnsRecord>);
+
+    impl DnsLookup for FakeLookup {
+        fn lookup(&mut self, _host: &str) -> Vec<DnsRecord> {
+            self.0.clone()
+        }
+    }
+
+    fn record(address: &str) -> DnsRecord {
+        DnsRecord { address: address.parse().unwrap(), ttl_secs: 300 }
+    }
+
+    #[test]
+    fn resolves_through_doh_first_when_it_succeeds() {
+        let mut fallback = FallbackResolver::new();
+        let mut doh = FakeLookup(vec![record("1.1.1.1")]);
+        let mut dot = FakeLookup(vec![record("9.9.9.9")]);
+        let mut resolvers: HashMap<ResolverKind, &mut dyn DnsLookup> =
+            HashMap::from([(ResolverKind::Doh, &mut doh as &mut dyn DnsLookup), (ResolverKind::Dot, &mut dot as &mut dyn DnsLookup)]);
+
+        let records = fallback.resolve("example.com", &mut resolvers);
+        assert_eq!(records, vec![record("1.1.1.1")]);
+    }
+
+    #[test]
+    fn falls_back_to_dot_when_doh_returns_nothing() {
+        let mut fallback = FallbackResolver::new();
+        let mut doh = FakeLookup(vec![]);
+        let mut dot = FakeLookup(vec![record("9.9.9.9")]);
+        let mut resolvers: HashMap<ResolverKind, &mut dyn DnsLookup> =
+            HashMap::from([(ResolverKind::Doh, &mut doh as &mut dyn DnsLookup), (ResolverKind::Dot, &mut dot as &mut dyn DnsLookup)]);
+
+        let records = fallback.resolve("example.com", &mut resolvers);
+        assert_eq!(records, vec![record("9.9.9.9")]);
+    }
+
+    #[test]
+    fn repeated_failures_mark_a_resolver_unhealthy() {
+        let mut fallback = FallbackResolver::new();
+        for _ in 0..UNHEALTHY_THRESHOLD {
+            let mut doh = FakeLookup(vec![]);
+            let mut resolvers: HashMap<ResolverKind, &mut dyn DnsLookup> = HashMap::from([(ResolverKind::Doh, &mut doh as &mut dyn DnsLookup)]);
+            fallback.resolve("example.com", &mut resolvers);
+        }
+        assert!(!fallback.is_healthy(ResolverKind::Doh));
+    }
+
+    #[test]
+    fn an_unhealthy_resolver_is_skipped_in_favor_of_the_next_one() {
+        let mut fallback = FallbackResolver::new();
+        for _ in 0..UNHEALTHY_THRESHOLD {
+            let mut doh = FakeLookup(vec![]);
+            let mut resolvers: HashMap<ResolverKind, &mut dyn DnsLookup> = HashMap::from([(ResolverKind::Doh, &mut doh as &mut dyn DnsLookup)]);
+            fallback.resolve("example.com", &mut resolvers);
+        }
+
+        let mut doh = FakeLookup(vec![record("1.1.1.1")]);
+        let mut dot = FakeLookup(vec![record("9.9.9.9")]);
+        let mut resolvers: HashMap<ResolverKind, &mut dyn DnsLookup> =
+            HashMap::from([(ResolverKind::Doh, &mut doh as &mut dyn DnsLookup), (ResolverKind::Dot, &mut dot as &mut dyn DnsLookup)]);
+        let records = fallback.resolve("example.com", &mut resolvers);
+        assert_eq!(records, vec![record("9.9.9.9")]);
+    }
+
+    #[test]
+    fn a_success_resets_the_consecutive_failure_count() {
+        let mut fallback = FallbackResolver::new();
+        let mut failing = FakeLookup(vec![]);
+        let mut resolvers: HashMap<ResolverKind, &mut dyn DnsLookup> = HashMap::from([(ResolverKind::Doh, &mut failing as &mut dyn DnsLookup)]);
+        fallback.resolve("example.com", &mut resolvers);
+
+        let mut succeeding = FakeLookup(vec![record("1.1.1.1")]);
+        let mut resolvers: HashMap<ResolverKind, &mut dyn DnsLookup> = HashMap::from([(ResolverKind::Doh, &mut succeeding as &mut dyn DnsLookup)]);
+        fallback.resolve("example.com", &mut resolvers);
+
+        assert!(fallback.is_healthy(ResolverKind::Doh));
+    }
+
+    #[test]
+    fn a_host_with_no_resolvers_available_returns_nothing() {
+        let mut fallback = FallbackResolver::new();
+        let mut resolvers: HashMap<ResolverKind, &mut dyn DnsLookup> = HashMap::new();
+        assert_eq!(fallback.resolve("example.com", &mut resolvers), Vec::new());
+    }
+}
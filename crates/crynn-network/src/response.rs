@@ -0,0 +1,141 @@
+use crate::decode;
+use crate::request::NetworkRequest;
+use crate::tls::TlsInfo;
+use crynn_error::NetworkError;
+
+/// An HTTP response, however many attempts it took to get one that
+/// wasn't a transient failure. `attempts` is 1 for a request that
+/// succeeded the first time; a caller driving retries through
+/// [`crate::RetryPolicy`] bumps it once per retry before returning.
+/// `tls` is `None` for a plain `http://` request — the shell's
+/// padlock/site-info panel reads that directly rather than guessing
+/// security from the URL scheme the way it otherwise would have to.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NetworkResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+    pub attempts: u32,
+    pub tls: Option<TlsInfo>,
+}
+
+impl NetworkResponse {
+    pub fn new(status: u16, body: Vec<u8>) -> Self {
+        Self { status, headers: Vec::new(), body, attempts: 1, tls: None }
+    }
+
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    pub fn with_tls(mut self, tls: TlsInfo) -> Self {
+        self.tls = Some(tls);
+        self
+    }
+
+    /// Looks up a header by name, case-insensitively as HTTP requires.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.iter().find(|(k, _)| k.eq_ignore_ascii_case(name)).map(|(_, v)| v.as_str())
+    }
+
+    /// Every header value stored under `name`, case-insensitively, in
+    /// the order they arrived. [`Self::header`] only ever returns the
+    /// first match, which is wrong for a header like `Set-Cookie` that
+    /// legitimately repeats.
+    pub fn headers_named<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a str> {
+        self.headers.iter().filter(move |(k, _)| k.eq_ignore_ascii_case(name)).map(|(_, v)| v.as_str())
+    }
+
+    /// The `Retry-After` header value, for feeding straight into
+    /// [`crate::RetryPolicy::next_delay_ms`].
+    pub fn retry_after(&self) -> Option<&str> {
+        self.header("Retry-After")
+    }
+
+    /// Decompresses `self.body` per its own `Content-Encoding` header,
+    /// honoring `request.auto_decompress`, so whatever calls this right
+    /// after a real transport reads the response — before it's handed
+    /// back to the caller or stored in [`crate::HttpCache`] — never has to
+    /// deal with the wire format again. A response with no
+    /// `Content-Encoding`, or whose request opted out, is returned
+    /// unchanged.
+    pub fn decode_body(mut self, request: &NetworkRequest) -> Result<Self, NetworkError> {
+        if !request.auto_decompress {
+            return Ok(self);
+        }
+        let Some(content_encoding) = self.header("Content-Encoding").map(str::to_string) else {
+            return Ok(self);
+        };
+        self.body = decode::decode(&content_encoding, &self.body)?;
+        Ok(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn header_lookup_is_case_insensitive() {
+        let response = NetworkResponse::new(503, Vec::new()).with_header("Retry-After", "5");
+        assert_eq!(response.header("retry-after"), Some("5"));
+    }
+
+    #[test]
+    fn headers_named_returns_every_repeated_header_in_order() {
+        let response = NetworkResponse::new(200, Vec::new())
+            .with_header("Set-Cookie", "a=1")
+            .with_header("Content-Type", "text/html")
+            .with_header("set-cookie", "b=2");
+        let cookies: Vec<&str> = response.headers_named("Set-Cookie").collect();
+        assert_eq!(cookies, vec!["a=1", "b=2"]);
+    }
+
+    #[test]
+    fn a_fresh_response_reports_a_single_attempt() {
+        assert_eq!(NetworkResponse::new(200, Vec::new()).attempts, 1);
+    }
+
+    #[test]
+    fn a_fresh_response_has_no_tls_info() {
+        assert_eq!(NetworkResponse::new(200, Vec::new()).tls, None);
+    }
+
+    #[test]
+    fn with_tls_attaches_the_negotiated_parameters() {
+        use crate::tls::{TlsInfo, TlsVersion};
+        let tls = TlsInfo::new(TlsVersion::Tls1_3, "TLS_AES_128_GCM_SHA256", vec![b"leaf".to_vec()]).with_alpn_protocol("h2");
+        let response = NetworkResponse::new(200, Vec::new()).with_tls(tls.clone());
+        assert_eq!(response.tls, Some(tls));
+    }
+
+    #[test]
+    fn a_response_with_no_content_encoding_is_returned_unchanged() {
+        let request = NetworkRequest::new("GET", "https://example.com");
+        let response = NetworkResponse::new(200, b"hello".to_vec());
+        assert_eq!(response.decode_body(&request).unwrap().body, b"hello");
+    }
+
+    #[test]
+    fn a_gzip_encoded_response_is_decompressed() {
+        use std::io::Write;
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(b"hello, gzip").unwrap();
+        let compressed = encoder.finish().unwrap();
+        let request = NetworkRequest::new("GET", "https://example.com");
+        let response = NetworkResponse::new(200, compressed).with_header("Content-Encoding", "gzip");
+        assert_eq!(response.decode_body(&request).unwrap().body, b"hello, gzip");
+    }
+
+    #[test]
+    fn opting_out_of_auto_decompress_leaves_the_body_compressed() {
+        use std::io::Write;
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(b"hello, gzip").unwrap();
+        let compressed = encoder.finish().unwrap();
+        let request = NetworkRequest::new("GET", "https://example.com").with_auto_decompress(false);
+        let response = NetworkResponse::new(200, compressed.clone()).with_header("Content-Encoding", "gzip");
+        assert_eq!(response.decode_body(&request).unwrap().body, compressed);
+    }
+}
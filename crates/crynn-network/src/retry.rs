@@ -0,0 +1,112 @@
+/// A transient failure worth retrying: an HTTP status a server uses to
+/// signal "try again" (429, 502, 503, 504), or the connection resetting
+/// outright rather than returning a response at all.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransientFailure {
+    Status(u16),
+    ConnectionReset,
+}
+
+impl TransientFailure {
+    fn is_retryable(&self) -> bool {
+        match self {
+            TransientFailure::Status(code) => matches!(code, 429 | 502 | 503 | 504),
+            TransientFailure::ConnectionReset => true,
+        }
+    }
+}
+
+/// How many times and how long to wait before retrying an idempotent
+/// request that failed transiently. Only meant for idempotent methods
+/// (GET, HEAD, PUT, DELETE) — retrying a POST blind risks doing it
+/// twice, so that decision stays with the caller, not this policy.
+///
+/// `max_attempts` counts retries, not the original request: a policy of
+/// 3 makes up to 4 requests total.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RetryPolicy {
+    pub max_attempts: u32,
+    pub base_delay_ms: u64,
+    pub max_delay_ms: u64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self { max_attempts: 3, base_delay_ms: 250, max_delay_ms: 30_000 }
+    }
+}
+
+impl RetryPolicy {
+    /// Whether `attempt` (1 for the first retry after the original
+    /// request fails) is still within budget for `failure`, and if so,
+    /// how long to wait before making it. Honors a `Retry-After` header
+    /// value when the server sent one; falls back to jittered
+    /// exponential backoff otherwise. Returns `None` when the failure
+    /// isn't retryable or the budget is exhausted, so the caller should
+    /// give up and surface the failure.
+    pub fn next_delay_ms(&self, attempt: u32, failure: TransientFailure, retry_after: Option<&str>) -> Option<u64> {
+        if attempt > self.max_attempts || !failure.is_retryable() {
+            return None;
+        }
+        if let Some(seconds) = retry_after.and_then(|value| value.trim().parse::<u64>().ok()) {
+            return Some(seconds * 1_000);
+        }
+        Some(self.jittered_backoff_ms(attempt))
+    }
+
+    /// Doubles per attempt off `base_delay_ms`, capped at
+    /// `max_delay_ms`, then picks uniformly between zero and that cap so
+    /// many clients backing off from the same overloaded server don't
+    /// all retry in lockstep.
+    fn jittered_backoff_ms(&self, attempt: u32) -> u64 {
+        let exponential = self.base_delay_ms.saturating_mul(1u64 << attempt.min(20));
+        let capped = exponential.min(self.max_delay_ms);
+        rand::random_range(0..=capped)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn non_retryable_statuses_are_never_retried() {
+        let policy = RetryPolicy::default();
+        assert_eq!(policy.next_delay_ms(1, TransientFailure::Status(404), None), None);
+    }
+
+    #[test]
+    fn retryable_statuses_are_retried_within_budget() {
+        let policy = RetryPolicy::default();
+        assert!(policy.next_delay_ms(1, TransientFailure::Status(503), None).is_some());
+        assert!(policy.next_delay_ms(policy.max_attempts, TransientFailure::Status(503), None).is_some());
+    }
+
+    #[test]
+    fn attempts_past_the_budget_are_not_retried() {
+        let policy = RetryPolicy::default();
+        assert_eq!(policy.next_delay_ms(policy.max_attempts + 1, TransientFailure::Status(503), None), None);
+    }
+
+    #[test]
+    fn connection_resets_are_always_retryable() {
+        let policy = RetryPolicy::default();
+        assert!(policy.next_delay_ms(1, TransientFailure::ConnectionReset, None).is_some());
+    }
+
+    #[test]
+    fn a_retry_after_header_overrides_backoff() {
+        let policy = RetryPolicy::default();
+        let delay = policy.next_delay_ms(1, TransientFailure::Status(429), Some("5")).unwrap();
+        assert_eq!(delay, 5_000);
+    }
+
+    #[test]
+    fn backoff_delay_never_exceeds_the_cap() {
+        let policy = RetryPolicy { max_attempts: 10, base_delay_ms: 1_000, max_delay_ms: 4_000 };
+        for attempt in 1..=policy.max_attempts {
+            let delay = policy.next_delay_ms(attempt, TransientFailure::ConnectionReset, None).unwrap();
+            assert!(delay <= policy.max_delay_ms);
+        }
+    }
+}
@@ -0,0 +1,142 @@
+use std::collections::HashSet;
+use std::net::{IpAddr, Ipv6Addr};
+
+/// Who initiated a request: the user's own navigation (address bar,
+/// bookmarks, a link they clicked) or content running on an already
+/// loaded page (a `fetch`, an `<img>`, an iframe). Only the latter is
+/// SSRF-relevant — a user navigating their own browser to
+/// `http://192.168.1.1` to open their router's admin page is normal,
+/// not an attack; public web content doing the same fetch on the user's
+/// behalf is the classic embedded-browser SSRF.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RequestOrigin {
+    UserNavigation,
+    WebContent,
+}
+
+/// Whether `ip` falls in a private-network range: loopback, link-local,
+/// or RFC 1918 (IPv4) and the IPv6 equivalents.
+pub fn is_private_network_address(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(v4) => v4.is_loopback() || v4.is_link_local() || v4.is_private(),
+        IpAddr::V6(v6) => {
+            if let Some(mapped) = v6.to_ipv4_mapped() {
+                return is_private_network_address(IpAddr::V4(mapped));
+            }
+            v6.is_loopback() || is_unique_local(&v6) || is_unicast_link_local(&v6)
+        }
+    }
+}
+
+fn is_unique_local(ip: &Ipv6Addr) -> bool {
+    (ip.segments()[0] & 0xfe00) == 0xfc00
+}
+
+fn is_unicast_link_local(ip: &Ipv6Addr) -> bool {
+    (ip.segments()[0] & 0xffc0) == 0xfe80
+}
+
+/// Blocks web content from reaching private-network addresses by
+/// default, with an allowlist for sites the user has explicitly chosen
+/// to exempt (e.g. a local admin tool that a page they use legitimately
+/// embeds a request to). The user's own navigations are never blocked —
+/// this guard only applies to [`RequestOrigin::WebContent`].
+#[derive(Debug, Clone)]
+pub struct PrivateNetworkGuard {
+    enabled: bool,
+    allowlist: HashSet<String>,
+}
+
+impl Default for PrivateNetworkGuard {
+    fn default() -> Self {
+        Self { enabled: true, allowlist: HashSet::new() }
+    }
+}
+
+impl PrivateNetworkGuard {
+    pub fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    /// Exempts `origin` from the guard, e.g. after the user confirms a
+    /// prompt explaining the site wants to reach a local address.
+    pub fn allow(&mut self, origin: impl Into<String>) {
+        self.allowlist.insert(origin.into());
+    }
+
+    pub fn revoke(&mut self, origin: &str) {
+        self.allowlist.remove(origin);
+    }
+
+    pub fn is_allowed(&self, origin: &str) -> bool {
+        self.allowlist.contains(origin)
+    }
+
+    /// Whether a request from `origin` to `ip`, initiated as
+    /// `request_origin`, should be blocked.
+    pub fn should_block(&self, origin: &str, ip: IpAddr, request_origin: RequestOrigin) -> bool {
+        self.enabled
+            && request_origin == RequestOrigin::WebContent
+            && !self.is_allowed(origin)
+            && is_private_network_address(ip)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn loopback_and_rfc1918_addresses_are_private() {
+        assert!(is_private_network_address("127.0.0.1".parse().unwrap()));
+        assert!(is_private_network_address("192.168.1.1".parse().unwrap()));
+        assert!(is_private_network_address("10.0.0.5".parse().unwrap()));
+        assert!(is_private_network_address("169.254.1.1".parse().unwrap()));
+        assert!(is_private_network_address("::1".parse().unwrap()));
+        assert!(is_private_network_address("fd12:3456:789a::1".parse().unwrap()));
+        assert!(is_private_network_address("fe80::1".parse().unwrap()));
+    }
+
+    #[test]
+    fn ipv4_mapped_ipv6_addresses_are_checked_against_their_embedded_ipv4() {
+        assert!(is_private_network_address("::ffff:127.0.0.1".parse().unwrap()));
+        assert!(is_private_network_address("::ffff:192.168.1.1".parse().unwrap()));
+        assert!(!is_private_network_address("::ffff:93.184.216.34".parse().unwrap()));
+    }
+
+    #[test]
+    fn public_addresses_are_not_private() {
+        assert!(!is_private_network_address("93.184.216.34".parse().unwrap()));
+        assert!(!is_private_network_address("2606:2800:220:1::1".parse().unwrap()));
+    }
+
+    #[test]
+    fn web_content_reaching_a_private_address_is_blocked() {
+        let guard = PrivateNetworkGuard::default();
+        assert!(guard.should_block("https://evil.example.com", "192.168.1.1".parse().unwrap(), RequestOrigin::WebContent));
+    }
+
+    #[test]
+    fn user_navigation_is_never_blocked() {
+        let guard = PrivateNetworkGuard::default();
+        assert!(!guard.should_block("https://evil.example.com", "192.168.1.1".parse().unwrap(), RequestOrigin::UserNavigation));
+    }
+
+    #[test]
+    fn an_allowlisted_origin_is_exempt() {
+        let mut guard = PrivateNetworkGuard::default();
+        guard.allow("https://intranet-tool.example.com");
+        assert!(!guard.should_block("https://intranet-tool.example.com", "10.0.0.5".parse().unwrap(), RequestOrigin::WebContent));
+    }
+
+    #[test]
+    fn disabling_the_guard_blocks_nothing() {
+        let mut guard = PrivateNetworkGuard::default();
+        guard.set_enabled(false);
+        assert!(!guard.should_block("https://evil.example.com", "127.0.0.1".parse().unwrap(), RequestOrigin::WebContent));
+    }
+}
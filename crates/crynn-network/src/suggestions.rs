@@ -0,0 +1,120 @@
+use crynn_error::NetworkError;
+
+/// Fetches the raw response body for a suggestions request. A real
+/// implementation is an HTTP GET once this crate has a transport; tests
+/// (and the shell, until then) can answer from a fixed table the same
+/// way [`crate::CredentialProvider`] does for auth prompts.
+pub trait SuggestionsTransport {
+    fn fetch(&mut self, url: &str) -> Result<String, NetworkError>;
+}
+
+/// Builds and parses OpenSearch-style search-suggestions requests: the
+/// `application/x-suggestions+json` endpoint most search engines expose
+/// alongside their OpenSearch description, returning
+/// `["query", ["suggestion one", "suggestion two"]]`.
+///
+/// Doesn't send anything itself — see [`SuggestionsTransport`] for why —
+/// it only builds the request URL and parses the response shape.
+#[derive(Debug, Clone)]
+pub struct SuggestionsClient {
+    /// The endpoint URL with `{}` standing in for the URL-encoded query,
+    /// e.g. `"https://example.com/suggest?q={}"`.
+    url_template: String,
+}
+
+impl SuggestionsClient {
+    pub fn new(url_template: impl Into<String>) -> Self {
+        Self { url_template: url_template.into() }
+    }
+
+    pub fn request_url(&self, query: &str) -> String {
+        self.url_template.replace("{}", &percent_encode(query))
+    }
+
+    /// Fetches and parses suggestions for `query` via `transport`. A
+    /// transport error or a malformed response yields no suggestions
+    /// rather than propagating an error — a flaky suggestions endpoint
+    /// shouldn't block the rest of the omnibox dropdown.
+    pub fn fetch(&self, transport: &mut dyn SuggestionsTransport, query: &str) -> Vec<String> {
+        match transport.fetch(&self.request_url(query)) {
+            Ok(body) => parse_opensearch_response(&body),
+            Err(_) => Vec::new(),
+        }
+    }
+}
+
+/// Percent-encodes the characters that would otherwise break a query
+/// string; not a general URL encoder, just enough for a search query.
+fn percent_encode(input: &str) -> String {
+    let mut out = String::with_capacity(input.len());
+    for byte in input.as_bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => out.push(*byte as char),
+            b' ' => out.push('+'),
+            _ => out.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    out
+}
+
+/// Parses an OpenSearch suggestions response body. The second element of
+/// the top-level array is the list of suggestion strings; anything else
+/// (malformed JSON, unexpected shape) yields an empty list.
+fn parse_opensearch_response(body: &str) -> Vec<String> {
+    let Ok(value) = serde_json::from_str::<serde_json::Value>(body) else {
+        return Vec::new();
+    };
+    value
+        .as_array()
+        .and_then(|top| top.get(1))
+        .and_then(|suggestions| suggestions.as_array())
+        .map(|suggestions| suggestions.iter().filter_map(|s| s.as_str().map(str::to_string)).collect())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct FixedTransport {
+        body: Option<String>,
+    }
+
+    impl SuggestionsTransport for FixedTransport {
+        fn fetch(&mut self, url: &str) -> Result<String, NetworkError> {
+            self.body.clone().ok_or_else(|| NetworkError::Timeout { url: url.to_string(), elapsed_ms: 0 })
+        }
+    }
+
+    #[test]
+    fn request_url_substitutes_the_encoded_query() {
+        let client = SuggestionsClient::new("https://example.com/suggest?q={}");
+        assert_eq!(client.request_url("rust lang"), "https://example.com/suggest?q=rust+lang");
+    }
+
+    #[test]
+    fn fetch_parses_the_second_array_element_as_suggestions() {
+        let client = SuggestionsClient::new("https://example.com/suggest?q={}");
+        let mut transport = FixedTransport { body: Some(r#"["rust", ["rust lang", "rust programming"]]"#.to_string()) };
+
+        let suggestions = client.fetch(&mut transport, "rust");
+
+        assert_eq!(suggestions, vec!["rust lang".to_string(), "rust programming".to_string()]);
+    }
+
+    #[test]
+    fn fetch_returns_nothing_for_malformed_json() {
+        let client = SuggestionsClient::new("https://example.com/suggest?q={}");
+        let mut transport = FixedTransport { body: Some("not json".to_string()) };
+
+        assert!(client.fetch(&mut transport, "rust").is_empty());
+    }
+
+    #[test]
+    fn fetch_returns_nothing_when_the_transport_fails() {
+        let client = SuggestionsClient::new("https://example.com/suggest?q={}");
+        let mut transport = FixedTransport { body: None };
+
+        assert!(client.fetch(&mut transport, "rust").is_empty());
+    }
+}
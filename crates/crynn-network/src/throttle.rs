@@ -0,0 +1,154 @@
+use std::collections::HashMap;
+
+/// A devtools-style network condition preset a developer can force onto
+/// one tab, the same granularity [`crate::RequestLog`] already logs
+/// requests at — keyed by whatever string the caller identifies a tab
+/// with, since this crate has no `TabId` of its own.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NetworkCondition {
+    /// Every request from the tab fails outright.
+    Offline,
+    /// Typical "Slow 3G" devtools preset: high latency, low throughput.
+    Slow3g,
+    /// Latency alone, at full throughput — for testing a site's loading
+    /// states on a connection that's merely far away rather than
+    /// bandwidth-starved.
+    HighLatency,
+    /// Each request independently fails with probability `0.0..=1.0`,
+    /// simulating a flaky connection rather than a consistently slow or
+    /// dead one.
+    PacketLoss(f64),
+}
+
+impl NetworkCondition {
+    /// Extra latency this condition adds before a request is allowed to
+    /// proceed, on top of whatever the connection itself would take.
+    pub fn extra_latency_ms(&self) -> u64 {
+        match self {
+            NetworkCondition::Offline => 0,
+            NetworkCondition::Slow3g => 400,
+            NetworkCondition::HighLatency => 2_000,
+            NetworkCondition::PacketLoss(_) => 0,
+        }
+    }
+
+    /// Throughput ceiling this condition imposes, or `None` for a
+    /// condition that doesn't constrain bandwidth.
+    pub fn throughput_bytes_per_sec(&self) -> Option<u64> {
+        match self {
+            NetworkCondition::Slow3g => Some(50_000),
+            _ => None,
+        }
+    }
+}
+
+/// Per-tab network-condition simulation for testing site behavior under
+/// bad conditions, toggled from a devtools-like panel. This crate has no
+/// real throttling or interceptor chain to hook into yet — [`Self::should_fail`]
+/// and [`Self::extra_latency_ms`] are the contract whatever drives an
+/// actual request consults before sending it, the same gap every other
+/// per-request decision in this crate is a contract around rather than
+/// an implementation of.
+#[derive(Debug, Default)]
+pub struct ConditionSimulator {
+    conditions: HashMap<String, NetworkCondition>,
+}
+
+impl ConditionSimulator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set_condition(&mut self, tab: impl Into<String>, condition: NetworkCondition) {
+        self.conditions.insert(tab.into(), condition);
+    }
+
+    /// Reverts `tab` to its real, unsimulated network behavior.
+    pub fn clear_condition(&mut self, tab: &str) {
+        self.conditions.remove(tab);
+    }
+
+    pub fn condition_for(&self, tab: &str) -> Option<NetworkCondition> {
+        self.conditions.get(tab).copied()
+    }
+
+    /// Whether a request from `tab` should fail outright under the
+    /// condition simulated for it: always for [`NetworkCondition::Offline`],
+    /// or with probability `p` for [`NetworkCondition::PacketLoss`] —
+    /// `roll` is the caller-supplied draw (e.g. `rand::random()`) that
+    /// decision is made against, kept as a parameter rather than drawn
+    /// in here so the decision stays deterministic for tests, the same
+    /// split [`crate::RetryPolicy::jittered_backoff_ms`] keeps between
+    /// computing a delay and the randomness that picks one.
+    pub fn should_fail(&self, tab: &str, roll: f64) -> bool {
+        match self.condition_for(tab) {
+            Some(NetworkCondition::Offline) => true,
+            Some(NetworkCondition::PacketLoss(p)) => roll < p,
+            _ => false,
+        }
+    }
+
+    /// Extra latency to add before a request from `tab` proceeds; zero
+    /// for a tab with no condition simulated.
+    pub fn extra_latency_ms(&self, tab: &str) -> u64 {
+        self.condition_for(tab).map(|condition| condition.extra_latency_ms()).unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_tab_with_no_condition_set_is_unaffected() {
+        let sim = ConditionSimulator::new();
+        assert_eq!(sim.condition_for("tab-0"), None);
+        assert!(!sim.should_fail("tab-0", 0.0));
+        assert_eq!(sim.extra_latency_ms("tab-0"), 0);
+    }
+
+    #[test]
+    fn offline_always_fails_regardless_of_the_roll() {
+        let mut sim = ConditionSimulator::new();
+        sim.set_condition("tab-0", NetworkCondition::Offline);
+        assert!(sim.should_fail("tab-0", 0.0));
+        assert!(sim.should_fail("tab-0", 0.999));
+    }
+
+    #[test]
+    fn packet_loss_fails_only_when_the_roll_is_under_the_probability() {
+        let mut sim = ConditionSimulator::new();
+        sim.set_condition("tab-0", NetworkCondition::PacketLoss(0.3));
+        assert!(sim.should_fail("tab-0", 0.1));
+        assert!(!sim.should_fail("tab-0", 0.5));
+    }
+
+    #[test]
+    fn slow_3g_adds_latency_but_never_fails_outright() {
+        let mut sim = ConditionSimulator::new();
+        sim.set_condition("tab-0", NetworkCondition::Slow3g);
+        assert_eq!(sim.extra_latency_ms("tab-0"), 400);
+        assert!(!sim.should_fail("tab-0", 0.0));
+    }
+
+    #[test]
+    fn conditions_are_scoped_per_tab() {
+        let mut sim = ConditionSimulator::new();
+        sim.set_condition("tab-0", NetworkCondition::Offline);
+        assert!(!sim.should_fail("tab-1", 0.0));
+    }
+
+    #[test]
+    fn clearing_a_condition_reverts_the_tab_to_unaffected() {
+        let mut sim = ConditionSimulator::new();
+        sim.set_condition("tab-0", NetworkCondition::Offline);
+        sim.clear_condition("tab-0");
+        assert!(!sim.should_fail("tab-0", 0.0));
+    }
+
+    #[test]
+    fn slow_3g_caps_throughput_but_high_latency_does_not() {
+        assert_eq!(NetworkCondition::Slow3g.throughput_bytes_per_sec(), Some(50_000));
+        assert_eq!(NetworkCondition::HighLatency.throughput_bytes_per_sec(), None);
+    }
+}
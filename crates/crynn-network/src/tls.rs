@@ -0,0 +1,373 @@
+use std::collections::{HashMap, HashSet};
+
+use rand::RngExt;
+
+/// Why a TLS handshake failed, structured instead of an all-or-nothing
+/// failure so the shell can show an interstitial that explains what's
+/// actually wrong rather than a generic "connection not secure" message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CertificateError {
+    Expired,
+    Untrusted,
+    NameMismatch { expected_host: String },
+    Revoked,
+}
+
+/// The seam between "negotiated TLS" and this crate: validating a
+/// server's certificate chain is real TLS-library work no contract here
+/// can do instead, the same gap [`crate::DnsLookup`] leaves for actual
+/// resolution. Implementations return `Ok(())` once the chain is
+/// trusted, or the specific [`CertificateError`] it failed for.
+pub trait CertificateValidator {
+    fn validate(&self, host: &str) -> Result<(), CertificateError>;
+}
+
+/// An opaque, scoped permission to proceed past a [`CertificateError`]
+/// for one host, minted once the user has seen the shell's interstitial
+/// and chosen to continue anyway. Callers compare it with
+/// [`CertificateOverrides::is_valid`] rather than parsing it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OverrideToken(String);
+
+fn generate_token() -> String {
+    let mut bytes = [0u8; 16];
+    rand::rng().fill(&mut bytes);
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+/// Per-host [`OverrideToken`]s minted after the user accepts a
+/// [`CertificateError`] interstitial, the same kind of explicit-consent
+/// record [`crate::PrivateNetworkGuard::allow`] keeps for private-network
+/// access. A token is scoped to the exact host it was minted for and the
+/// exact error it was shown for — an override accepted for an expired
+/// certificate doesn't silently cover a later name mismatch on the same
+/// host, and minting a fresh token for a host supersedes whatever was
+/// minted for it before.
+#[derive(Debug, Default)]
+pub struct CertificateOverrides {
+    tokens: HashMap<String, (OverrideToken, CertificateError)>,
+}
+
+impl CertificateOverrides {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Mints a fresh token scoped to `host`/`error`, replacing whatever
+    /// was previously minted for `host`.
+    pub fn mint(&mut self, host: &str, error: CertificateError) -> OverrideToken {
+        let token = OverrideToken(generate_token());
+        self.tokens.insert(host.to_string(), (token.clone(), error));
+        token
+    }
+
+    /// Whether `token` is still the live override for `host` against
+    /// `error` — a mismatched host, a different error than it was minted
+    /// for, or a token superseded by a later [`Self::mint`] all fail.
+    pub fn is_valid(&self, host: &str, error: &CertificateError, token: &OverrideToken) -> bool {
+        self.tokens.get(host).is_some_and(|(stored_token, stored_error)| stored_token == token && stored_error == error)
+    }
+
+    pub fn revoke(&mut self, host: &str) {
+        self.tokens.remove(host);
+    }
+}
+
+/// Validates `host` through `validator`, letting the connection through
+/// despite a failed [`CertificateError`] only if `token` is a still-live
+/// override for that exact host/error pair in `overrides`.
+pub fn validate_with_overrides(
+    validator: &dyn CertificateValidator,
+    overrides: &CertificateOverrides,
+    host: &str,
+    token: Option<&OverrideToken>,
+) -> Result<(), CertificateError> {
+    let Err(error) = validator.validate(host) else {
+        return Ok(());
+    };
+    if let Some(token) = token {
+        if overrides.is_valid(host, &error, token) {
+            return Ok(());
+        }
+    }
+    Err(error)
+}
+
+/// The outcome of a soft-fail revocation check: a stapled OCSP response
+/// ([`validate_stapled_ocsp`]) or a local CRLite-style lookup
+/// ([`CrliteList::check`]). "Soft-fail" means [`RevocationStatus::Unknown`]
+/// is deliberately not [`CertificateError::Revoked`] — an unreachable OCSP
+/// responder or a serial this crate's local list has simply never heard of
+/// shouldn't, on its own, block navigation the way an actual revocation
+/// should.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RevocationStatus {
+    Good,
+    Revoked,
+    Unknown,
+}
+
+/// A stapled OCSP response as delivered alongside the TLS handshake
+/// (RFC 6066), already parsed down to the fields [`validate_stapled_ocsp`]
+/// checks. Producing one from the raw DER the handshake actually carries
+/// is real TLS-library work this crate doesn't do, the same gap
+/// [`CertificateValidator`] leaves for chain validation itself.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OcspResponse {
+    pub next_update_secs: u64,
+    pub revoked: bool,
+}
+
+/// Validates a stapled OCSP response at `now`. An expired response (past
+/// `next_update_secs`) is [`RevocationStatus::Unknown`] rather than
+/// trusted either way, since the responder hasn't vouched for the
+/// certificate's current state at all.
+pub fn validate_stapled_ocsp(response: &OcspResponse, now: u64) -> RevocationStatus {
+    if now > response.next_update_secs {
+        return RevocationStatus::Unknown;
+    }
+    if response.revoked {
+        RevocationStatus::Revoked
+    } else {
+        RevocationStatus::Good
+    }
+}
+
+/// A CRLite-style local revocation list: compact enough to ship with the
+/// browser and check without a network round trip, the fallback for a
+/// handshake with no stapled OCSP response to validate at all. Soft-fail
+/// like [`validate_stapled_ocsp`] — a serial this crate has no record of
+/// is [`RevocationStatus::Unknown`], not [`RevocationStatus::Good`],
+/// since an incomplete local list shouldn't silently vouch for a
+/// certificate it's never actually seen.
+#[derive(Debug, Default)]
+pub struct CrliteList {
+    known_serials: HashSet<String>,
+    revoked_serials: HashSet<String>,
+}
+
+impl CrliteList {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn mark_revoked(&mut self, serial: impl Into<String>) {
+        let serial = serial.into();
+        self.known_serials.insert(serial.clone());
+        self.revoked_serials.insert(serial);
+    }
+
+    pub fn mark_good(&mut self, serial: impl Into<String>) {
+        let serial = serial.into();
+        self.known_serials.insert(serial.clone());
+        self.revoked_serials.remove(&serial);
+    }
+
+    pub fn check(&self, serial: &str) -> RevocationStatus {
+        if !self.known_serials.contains(serial) {
+            return RevocationStatus::Unknown;
+        }
+        if self.revoked_serials.contains(serial) {
+            RevocationStatus::Revoked
+        } else {
+            RevocationStatus::Good
+        }
+    }
+}
+
+/// The TLS protocol version a handshake negotiated, typed rather than a
+/// free-form string since the whole set of versions a real TLS library
+/// will ever report is this small and fixed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TlsVersion {
+    Tls1_2,
+    Tls1_3,
+}
+
+/// Negotiated TLS parameters for one connection, attached to a
+/// [`crate::NetworkResponse`] so the shell can render a padlock/site-info
+/// panel from what the handshake actually negotiated instead of guessing
+/// from the URL scheme. `cipher_suite` and `alpn_protocol` are left as
+/// whatever name the handshake reported (`"TLS_AES_128_GCM_SHA256"`,
+/// `"h2"`) rather than typed, since the set of either is effectively
+/// open-ended; `certificate_chain` is each certificate's raw DER bytes,
+/// leaf first, opaque to this crate the same way [`OverrideToken`] is —
+/// parsing them into anything richer is real certificate-library work
+/// [`CertificateValidator`] already leaves to a real implementation. It
+/// carries no [`RevocationStatus`] of its own: that's [`OcspResponse`]'s
+/// or [`CrliteList`]'s job once a real handshake hands this crate
+/// something to check it against.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TlsInfo {
+    pub version: TlsVersion,
+    pub cipher_suite: String,
+    pub alpn_protocol: Option<String>,
+    pub certificate_chain: Vec<Vec<u8>>,
+}
+
+impl TlsInfo {
+    pub fn new(version: TlsVersion, cipher_suite: impl Into<String>, certificate_chain: Vec<Vec<u8>>) -> Self {
+        Self { version, cipher_suite: cipher_suite.into(), alpn_protocol: None, certificate_chain }
+    }
+
+    pub fn with_alpn_protocol(mut self, alpn_protocol: impl Into<String>) -> Self {
+        self.alpn_protocol = Some(alpn_protocol.into());
+        self
+    }
+
+    /// The leaf certificate — the one [`CertificateValidator`] actually
+    /// validated the host against — or `None` for a chain the handshake
+    /// reported as empty.
+    pub fn leaf_certificate(&self) -> Option<&[u8]> {
+        self.certificate_chain.first().map(Vec::as_slice)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct AlwaysFails(CertificateError);
+
+    impl CertificateValidator for AlwaysFails {
+        fn validate(&self, _host: &str) -> Result<(), CertificateError> {
+            Err(self.0.clone())
+        }
+    }
+
+    struct AlwaysSucceeds;
+
+    impl CertificateValidator for AlwaysSucceeds {
+        fn validate(&self, _host: &str) -> Result<(), CertificateError> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn a_valid_certificate_needs_no_override() {
+        let overrides = CertificateOverrides::new();
+        assert_eq!(validate_with_overrides(&AlwaysSucceeds, &overrides, "example.com", None), Ok(()));
+    }
+
+    #[test]
+    fn a_failed_validation_with_no_token_is_reported() {
+        let overrides = CertificateOverrides::new();
+        assert_eq!(
+            validate_with_overrides(&AlwaysFails(CertificateError::Expired), &overrides, "example.com", None),
+            Err(CertificateError::Expired)
+        );
+    }
+
+    #[test]
+    fn a_minted_token_lets_the_same_host_and_error_through() {
+        let mut overrides = CertificateOverrides::new();
+        let token = overrides.mint("example.com", CertificateError::Expired);
+        assert_eq!(validate_with_overrides(&AlwaysFails(CertificateError::Expired), &overrides, "example.com", Some(&token)), Ok(()));
+    }
+
+    #[test]
+    fn a_token_does_not_cover_a_different_host() {
+        let mut overrides = CertificateOverrides::new();
+        let token = overrides.mint("example.com", CertificateError::Expired);
+        assert_eq!(
+            validate_with_overrides(&AlwaysFails(CertificateError::Expired), &overrides, "other.example.com", Some(&token)),
+            Err(CertificateError::Expired)
+        );
+    }
+
+    #[test]
+    fn a_token_does_not_cover_a_different_error_on_the_same_host() {
+        let mut overrides = CertificateOverrides::new();
+        let token = overrides.mint("example.com", CertificateError::Expired);
+        assert_eq!(
+            validate_with_overrides(&AlwaysFails(CertificateError::Untrusted), &overrides, "example.com", Some(&token)),
+            Err(CertificateError::Untrusted)
+        );
+    }
+
+    #[test]
+    fn minting_a_new_token_supersedes_the_old_one() {
+        let mut overrides = CertificateOverrides::new();
+        let old_token = overrides.mint("example.com", CertificateError::Expired);
+        overrides.mint("example.com", CertificateError::Expired);
+        assert!(!overrides.is_valid("example.com", &CertificateError::Expired, &old_token));
+    }
+
+    #[test]
+    fn revoking_a_host_removes_its_override() {
+        let mut overrides = CertificateOverrides::new();
+        let token = overrides.mint("example.com", CertificateError::Expired);
+        overrides.revoke("example.com");
+        assert!(!overrides.is_valid("example.com", &CertificateError::Expired, &token));
+    }
+
+    #[test]
+    fn a_fresh_unrevoked_stapled_response_is_good() {
+        let response = OcspResponse { next_update_secs: 1_000, revoked: false };
+        assert_eq!(validate_stapled_ocsp(&response, 500), RevocationStatus::Good);
+    }
+
+    #[test]
+    fn a_fresh_revoked_stapled_response_is_revoked() {
+        let response = OcspResponse { next_update_secs: 1_000, revoked: true };
+        assert_eq!(validate_stapled_ocsp(&response, 500), RevocationStatus::Revoked);
+    }
+
+    #[test]
+    fn an_expired_stapled_response_is_unknown_rather_than_trusted_either_way() {
+        let response = OcspResponse { next_update_secs: 1_000, revoked: false };
+        assert_eq!(validate_stapled_ocsp(&response, 1_001), RevocationStatus::Unknown);
+    }
+
+    #[test]
+    fn a_serial_with_no_crlite_record_is_unknown() {
+        let list = CrliteList::new();
+        assert_eq!(list.check("abc123"), RevocationStatus::Unknown);
+    }
+
+    #[test]
+    fn a_serial_marked_revoked_in_crlite_is_reported_revoked() {
+        let mut list = CrliteList::new();
+        list.mark_revoked("abc123");
+        assert_eq!(list.check("abc123"), RevocationStatus::Revoked);
+    }
+
+    #[test]
+    fn a_serial_marked_good_in_crlite_is_reported_good() {
+        let mut list = CrliteList::new();
+        list.mark_good("abc123");
+        assert_eq!(list.check("abc123"), RevocationStatus::Good);
+    }
+
+    #[test]
+    fn marking_a_previously_revoked_serial_good_clears_its_revocation() {
+        let mut list = CrliteList::new();
+        list.mark_revoked("abc123");
+        list.mark_good("abc123");
+        assert_eq!(list.check("abc123"), RevocationStatus::Good);
+    }
+
+    #[test]
+    fn a_fresh_tls_info_has_no_alpn_protocol() {
+        let info = TlsInfo::new(TlsVersion::Tls1_3, "TLS_AES_128_GCM_SHA256", vec![b"leaf".to_vec()]);
+        assert_eq!(info.alpn_protocol, None);
+    }
+
+    #[test]
+    fn with_alpn_protocol_sets_the_negotiated_protocol() {
+        let info = TlsInfo::new(TlsVersion::Tls1_3, "TLS_AES_128_GCM_SHA256", Vec::new()).with_alpn_protocol("h2");
+        assert_eq!(info.alpn_protocol.as_deref(), Some("h2"));
+    }
+
+    #[test]
+    fn leaf_certificate_is_the_first_entry_in_the_chain() {
+        let info = TlsInfo::new(TlsVersion::Tls1_3, "TLS_AES_128_GCM_SHA256", vec![b"leaf".to_vec(), b"intermediate".to_vec()]);
+        assert_eq!(info.leaf_certificate(), Some(b"leaf".as_slice()));
+    }
+
+    #[test]
+    fn leaf_certificate_is_none_for_an_empty_chain() {
+        let info = TlsInfo::new(TlsVersion::Tls1_3, "TLS_AES_128_GCM_SHA256", Vec::new());
+        assert_eq!(info.leaf_certificate(), None);
+    }
+}
@@ -0,0 +1,108 @@
+use crynn_error::NetworkError;
+
+/// Sends a translation request body to `endpoint` and returns the raw
+/// response body. A real implementation is an HTTP POST once this crate
+/// has a transport; tests (and the shell, until then) can answer from a
+/// fixed table the same way [`crate::SuggestionsTransport`] does for
+/// search suggestions.
+pub trait TranslationTransport {
+    fn translate(&mut self, endpoint: &str, request_body: &str) -> Result<String, NetworkError>;
+}
+
+/// Builds and parses requests against a self-hosted LibreTranslate-
+/// compatible `/translate` endpoint: `{"q", "source", "target",
+/// "format": "text"}` in, `{"translatedText": "..."}` out.
+///
+/// Doesn't send anything itself — see [`TranslationTransport`] for why —
+/// it only builds the request body and parses the response shape, the
+/// same split [`crate::SuggestionsClient`] makes for suggestions.
+#[derive(Debug, Clone)]
+pub struct TranslationClient {
+    /// The self-hosted endpoint's full `/translate` URL, e.g.
+    /// `"https://translate.example/translate"`.
+    endpoint: String,
+}
+
+impl TranslationClient {
+    pub fn new(endpoint: impl Into<String>) -> Self {
+        Self { endpoint: endpoint.into() }
+    }
+
+    pub fn endpoint(&self) -> &str {
+        &self.endpoint
+    }
+
+    pub fn request_body(&self, text: &str, source_language: &str, target_language: &str) -> String {
+        serde_json::json!({
+            "q": text,
+            "source": source_language,
+            "target": target_language,
+            "format": "text",
+        })
+        .to_string()
+    }
+
+    /// Translates `text` from `source_language` to `target_language` via
+    /// `transport`. A transport error or a malformed response yields
+    /// `None` rather than propagating an error — a translation backend
+    /// being unreachable shouldn't block the rest of the page from
+    /// rendering.
+    pub fn translate(&self, transport: &mut dyn TranslationTransport, text: &str, source_language: &str, target_language: &str) -> Option<String> {
+        let body = self.request_body(text, source_language, target_language);
+        let response = transport.translate(&self.endpoint, &body).ok()?;
+        parse_translated_text(&response)
+    }
+}
+
+fn parse_translated_text(body: &str) -> Option<String> {
+    serde_json::from_str::<serde_json::Value>(body).ok()?.get("translatedText")?.as_str().map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct FixedTransport {
+        body: Option<String>,
+    }
+
+    impl TranslationTransport for FixedTransport {
+        fn translate(&mut self, endpoint: &str, _request_body: &str) -> Result<String, NetworkError> {
+            self.body.clone().ok_or_else(|| NetworkError::Timeout { url: endpoint.to_string(), elapsed_ms: 0 })
+        }
+    }
+
+    #[test]
+    fn request_body_carries_the_text_and_both_languages() {
+        let client = TranslationClient::new("https://translate.example/translate");
+        let body: serde_json::Value = serde_json::from_str(&client.request_body("hello", "en", "fr")).unwrap();
+        assert_eq!(body["q"], "hello");
+        assert_eq!(body["source"], "en");
+        assert_eq!(body["target"], "fr");
+        assert_eq!(body["format"], "text");
+    }
+
+    #[test]
+    fn translate_parses_the_translated_text_field() {
+        let client = TranslationClient::new("https://translate.example/translate");
+        let mut transport = FixedTransport { body: Some(r#"{"translatedText": "bonjour"}"#.to_string()) };
+
+        assert_eq!(client.translate(&mut transport, "hello", "en", "fr"), Some("bonjour".to_string()));
+    }
+
+    #[test]
+    fn translate_returns_nothing_for_malformed_json() {
+        let client = TranslationClient::new("https://translate.example/translate");
+        let mut transport = FixedTransport { body: Some("not json".to_string()) };
+
+        assert_eq!(client.translate(&mut transport, "hello", "en", "fr"), None);
+    }
+
+    #[test]
+    fn translate_returns_nothing_when_the_transport_fails() {
+        let client = TranslationClient::new("https://translate.example/translate");
+        let mut transport = FixedTransport { body: None };
+
+        assert_eq!(client.translate(&mut transport, "hello", "en", "fr"), None);
+    }
+}
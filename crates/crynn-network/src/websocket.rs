@@ -0,0 +1,307 @@
+use std::collections::{HashMap, VecDeque};
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use rand::RngExt;
+use sha1::{Digest, Sha1};
+
+use crate::hsts::HstsStore;
+use crate::proxy::ProxyConfig;
+use crate::request::NetworkRequest;
+use crate::response::NetworkResponse;
+
+/// The fixed GUID `Sec-WebSocket-Accept` is always computed against, per
+/// RFC 6455 §1.3.
+const WEBSOCKET_GUID: &str = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+
+/// One WebSocket frame, the unit [`WebSocketManager`] exchanges once a
+/// connection is open. `Close`'s `reason` is the UTF-8 tail of the close
+/// payload after its 2-byte status code, empty if the peer sent none.
+#[derive(Debug, Clone, PartialEq)]
+pub enum WebSocketFrame {
+    Text(String),
+    Binary(Vec<u8>),
+    Ping(Vec<u8>),
+    Pong(Vec<u8>),
+    Close { code: u16, reason: String },
+}
+
+/// Identifies one open connection, assigned by [`WebSocketManager::open`]
+/// the same way [`crate::RequestHandle`] is assigned by
+/// [`crate::NetworkManager::begin_request`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct WebSocketId(u64);
+
+/// Generates a fresh `Sec-WebSocket-Key`: 16 random bytes, base64-encoded,
+/// per RFC 6455 §4.1.
+pub fn generate_client_key() -> String {
+    let mut bytes = [0u8; 16];
+    rand::rng().fill(&mut bytes);
+    STANDARD.encode(bytes)
+}
+
+/// The `Sec-WebSocket-Accept` value a compliant server must answer
+/// `client_key` with: `base64(SHA1(client_key + GUID))`, per RFC 6455
+/// §1.3. A pure byte transform, implemented rather than left a contract
+/// the same way [`crate::decode::decode`] is.
+pub fn accept_key(client_key: &str) -> String {
+    let mut hasher = Sha1::new();
+    hasher.update(client_key.as_bytes());
+    hasher.update(WEBSOCKET_GUID.as_bytes());
+    STANDARD.encode(hasher.finalize())
+}
+
+/// Whether a `ws://`/`wss://` connection to `host` must use TLS: it's
+/// already `wss://`, or the host requires HTTPS per `hsts` — the same
+/// policy [`HstsStore::requires_https`] already applies to a plain
+/// `http://` URL, so a site that's pinned itself to HTTPS can't be
+/// downgraded by opening a plaintext WebSocket to it instead.
+pub fn requires_secure_websocket(url: &str, host: &str, hsts: &HstsStore, now: u64) -> bool {
+    url.starts_with("wss://") || hsts.requires_https(host, now)
+}
+
+/// Builds the opening HTTP request for a `ws://`/`wss://` handshake at
+/// `url`: the `Upgrade`/`Connection`/`Sec-WebSocket-Version`/
+/// `Sec-WebSocket-Key` headers every server expects, plus
+/// `Sec-WebSocket-Extensions: permessage-deflate` when `permessage_deflate`
+/// is requested, and routed through `proxy` the same as any other
+/// [`NetworkRequest`] to the same origin. Attaching a `Cookie` header is
+/// the caller's job, same as [`attach_cookies`] always is, so the site's
+/// existing session carries over to the WebSocket connection the same as
+/// it would to the page that opened it.
+pub fn handshake_request(url: &str, client_key: &str, permessage_deflate: bool, proxy: ProxyConfig) -> NetworkRequest {
+    let mut request = NetworkRequest::new("GET", url)
+        .with_header("Connection", "Upgrade")
+        .with_header("Upgrade", "websocket")
+        .with_header("Sec-WebSocket-Version", "13")
+        .with_header("Sec-WebSocket-Key", client_key)
+        .with_proxy(proxy);
+    if permessage_deflate {
+        request = request.with_header("Sec-WebSocket-Extensions", "permessage-deflate");
+    }
+    request
+}
+
+/// Why a handshake response didn't complete the connection
+/// [`handshake_request`] started.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HandshakeError {
+    UnexpectedStatus { status: u16 },
+    MissingUpgradeHeader,
+    AcceptMismatch,
+}
+
+/// Confirms `response` is a valid answer to a handshake started with
+/// `client_key`: status 101, an `Upgrade: websocket` header, and a
+/// `Sec-WebSocket-Accept` matching what [`accept_key`] computes for
+/// `client_key`. Whatever drives an actual connection should call this
+/// right after reading the response headers, before treating the
+/// connection as open.
+pub fn validate_handshake_response(response: &NetworkResponse, client_key: &str) -> Result<(), HandshakeError> {
+    if response.status != 101 {
+        return Err(HandshakeError::UnexpectedStatus { status: response.status });
+    }
+    let upgraded = response.header("Upgrade").is_some_and(|v| v.eq_ignore_ascii_case("websocket"));
+    if !upgraded {
+        return Err(HandshakeError::MissingUpgradeHeader);
+    }
+    if response.header("Sec-WebSocket-Accept") != Some(accept_key(client_key).as_str()) {
+        return Err(HandshakeError::AcceptMismatch);
+    }
+    Ok(())
+}
+
+/// Owns every open connection's received-frame queue, keyed by
+/// [`WebSocketId`] — the same poll/drain shape
+/// `crynn_engine::PushInbox` uses for push messages, standing in for the
+/// real async stream of frames the shell/engine bridge would otherwise
+/// `await`: this crate has no async runtime of its own, the same gap
+/// every other real transport in this crate is a contract around rather
+/// than an implementation of.
+#[derive(Debug, Default)]
+pub struct WebSocketManager {
+    next_id: u64,
+    inboxes: HashMap<WebSocketId, VecDeque<WebSocketFrame>>,
+}
+
+impl WebSocketManager {
+    /// Registers a newly handshaken connection and returns the id the
+    /// shell/engine bridge receives and sends frames through.
+    pub fn open(&mut self) -> WebSocketId {
+        let id = WebSocketId(self.next_id);
+        self.next_id += 1;
+        self.inboxes.insert(id, VecDeque::new());
+        id
+    }
+
+    /// Closes `id`'s connection, dropping any frames still queued for it.
+    pub fn close(&mut self, id: WebSocketId) {
+        self.inboxes.remove(&id);
+    }
+
+    pub fn is_open(&self, id: WebSocketId) -> bool {
+        self.inboxes.contains_key(&id)
+    }
+
+    /// What a real transport calls as frames arrive off the wire for
+    /// `id`. A no-op for a connection that's already closed.
+    pub fn receive(&mut self, id: WebSocketId, frame: WebSocketFrame) {
+        if let Some(queue) = self.inboxes.get_mut(&id) {
+            queue.push_back(frame);
+        }
+    }
+
+    /// Removes and returns every frame received for `id` since the last
+    /// drain, oldest first.
+    pub fn drain(&mut self, id: WebSocketId) -> Vec<WebSocketFrame> {
+        self.inboxes.get_mut(&id).map(|queue| queue.drain(..).collect()).unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cookie::{attach_cookies, CookieJar};
+
+    #[derive(Default)]
+    struct FixedJar {
+        to_send: Option<String>,
+    }
+
+    impl CookieJar for FixedJar {
+        fn cookie_header(&mut self, _top_level_site: &str, _domain: &str, _at: u64) -> Option<String> {
+            self.to_send.clone()
+        }
+
+        fn store_set_cookie(&mut self, _top_level_site: &str, _domain: &str, _header_value: &str, _at: u64) {}
+    }
+
+    #[test]
+    fn accept_key_matches_the_rfc_6455_worked_example() {
+        assert_eq!(accept_key("dGhlIHNhbXBsZSBub25jZQ=="), "s3pPLMBiTxaQ9kYGzzhZRbK+xOo=");
+    }
+
+    #[test]
+    fn generated_client_keys_are_sixteen_bytes_base64_encoded() {
+        let key = generate_client_key();
+        assert_eq!(STANDARD.decode(&key).unwrap().len(), 16);
+    }
+
+    #[test]
+    fn wss_urls_always_require_a_secure_connection() {
+        let hsts = HstsStore::default();
+        assert!(requires_secure_websocket("wss://example.com/socket", "example.com", &hsts, 0));
+    }
+
+    #[test]
+    fn a_plain_ws_url_is_upgraded_when_the_host_requires_https() {
+        let mut hsts = HstsStore::default();
+        hsts.record("example.com", "max-age=3600", 0);
+        assert!(requires_secure_websocket("ws://example.com/socket", "example.com", &hsts, 0));
+    }
+
+    #[test]
+    fn a_plain_ws_url_stays_plain_without_an_hsts_policy() {
+        let hsts = HstsStore::default();
+        assert!(!requires_secure_websocket("ws://chat.example.org/socket", "chat.example.org", &hsts, 0));
+    }
+
+    #[test]
+    fn handshake_request_sets_the_upgrade_headers_and_carries_the_proxy() {
+        let proxy = ProxyConfig::Socks5 { host: "proxy.example.com".to_string(), port: 1080 };
+        let request = handshake_request("wss://example.com/socket", "dGhlIHNhbXBsZSBub25jZQ==", true, proxy.clone());
+
+        assert_eq!(request.headers.iter().find(|(k, _)| k == "Upgrade").map(|(_, v)| v.as_str()), Some("websocket"));
+        assert_eq!(request.headers.iter().find(|(k, _)| k == "Connection").map(|(_, v)| v.as_str()), Some("Upgrade"));
+        assert_eq!(
+            request.headers.iter().find(|(k, _)| k == "Sec-WebSocket-Extensions").map(|(_, v)| v.as_str()),
+            Some("permessage-deflate")
+        );
+        assert_eq!(request.proxy, proxy);
+    }
+
+    #[test]
+    fn handshake_request_omits_the_extension_header_when_deflate_is_not_requested() {
+        let request = handshake_request("ws://example.com/socket", "key", false, ProxyConfig::Direct);
+        assert!(!request.headers.iter().any(|(k, _)| k == "Sec-WebSocket-Extensions"));
+    }
+
+    #[test]
+    fn the_caller_attaches_cookies_to_the_handshake_request_the_same_as_any_other() {
+        let mut jar = FixedJar { to_send: Some("session=abc".to_string()) };
+        let request = handshake_request("wss://example.com/socket", "key", false, ProxyConfig::Direct);
+        let request = attach_cookies(request, &mut jar, "example.com", "example.com", 0);
+        assert_eq!(request.headers.iter().find(|(k, _)| k == "Cookie").map(|(_, v)| v.as_str()), Some("session=abc"));
+    }
+
+    #[test]
+    fn a_matching_101_response_validates() {
+        let client_key = "dGhlIHNhbXBsZSBub25jZQ==";
+        let response = NetworkResponse::new(101, Vec::new())
+            .with_header("Upgrade", "websocket")
+            .with_header("Sec-WebSocket-Accept", accept_key(client_key));
+        assert_eq!(validate_handshake_response(&response, client_key), Ok(()));
+    }
+
+    #[test]
+    fn a_non_101_status_is_rejected() {
+        let response = NetworkResponse::new(200, Vec::new());
+        assert_eq!(validate_handshake_response(&response, "key"), Err(HandshakeError::UnexpectedStatus { status: 200 }));
+    }
+
+    #[test]
+    fn a_missing_upgrade_header_is_rejected() {
+        let response = NetworkResponse::new(101, Vec::new()).with_header("Sec-WebSocket-Accept", accept_key("key"));
+        assert_eq!(validate_handshake_response(&response, "key"), Err(HandshakeError::MissingUpgradeHeader));
+    }
+
+    #[test]
+    fn a_mismatched_accept_key_is_rejected() {
+        let response = NetworkResponse::new(101, Vec::new()).with_header("Upgrade", "websocket").with_header("Sec-WebSocket-Accept", "wrong");
+        assert_eq!(validate_handshake_response(&response, "key"), Err(HandshakeError::AcceptMismatch));
+    }
+
+    #[test]
+    fn a_newly_opened_connection_has_no_queued_frames() {
+        let mut manager = WebSocketManager::default();
+        let id = manager.open();
+        assert!(manager.is_open(id));
+        assert!(manager.drain(id).is_empty());
+    }
+
+    #[test]
+    fn received_frames_drain_in_arrival_order_and_empty_the_queue() {
+        let mut manager = WebSocketManager::default();
+        let id = manager.open();
+        manager.receive(id, WebSocketFrame::Text("first".to_string()));
+        manager.receive(id, WebSocketFrame::Text("second".to_string()));
+
+        let drained = manager.drain(id);
+
+        assert_eq!(drained, vec![WebSocketFrame::Text("first".to_string()), WebSocketFrame::Text("second".to_string())]);
+        assert!(manager.drain(id).is_empty());
+    }
+
+    #[test]
+    fn closing_a_connection_drops_its_queued_frames() {
+        let mut manager = WebSocketManager::default();
+        let id = manager.open();
+        manager.receive(id, WebSocketFrame::Ping(Vec::new()));
+
+        manager.close(id);
+
+        assert!(!manager.is_open(id));
+        assert!(manager.drain(id).is_empty());
+    }
+
+    #[test]
+    fn receiving_on_a_closed_connection_is_a_no_op() {
+        let mut manager = WebSocketManager::default();
+        let id = manager.open();
+        manager.close(id);
+
+        manager.receive(id, WebSocketFrame::Pong(Vec::new()));
+
+        assert!(manager.drain(id).is_empty());
+    }
+}
@@ -0,0 +1,26 @@
+use serde::{Deserialize, Serialize};
+
+/// A capability a site can ask the user's permission for. Push
+/// notifications and cookies are wired up end to end; geolocation,
+/// camera, and microphone will follow the same [`PermissionKind`]/
+/// [`crate::PermissionState`]/[`crate::PermissionStore`] shape once their
+/// subsystems exist.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum PermissionKind {
+    Push,
+    /// Whether `crynn-cookies` is allowed to set cookies for a site.
+    /// [`PermissionState::Denied`] here is what the cookie panel's
+    /// "block" button sets.
+    Cookies,
+    /// Whether audible media is allowed to autoplay on a site, overriding
+    /// the engine's default block-audible-autoplay policy.
+    /// [`PermissionState::Granted`] here is what the autoplay indicator's
+    /// one-click "Allow" button sets.
+    Autoplay,
+    /// Whether a non-user-initiated `window.open` is allowed to succeed
+    /// on a site, overriding the popup blocker's default. Unlike
+    /// [`PermissionState::Granted`] here, the blocked-popups indicator's
+    /// "allow once" opens a single queued popup without touching this —
+    /// only its "always allow this site" button sets it.
+    Popups,
+}
@@ -0,0 +1,17 @@
+//! Per-site content permissions. [`PermissionKind::Push`] is the first
+//! one wired up, gating the web-push bridge in `crynn-engine` and its
+//! toast display in `crynn-shell`; geolocation, camera, and microphone
+//! will extend [`PermissionKind`] the same way once their subsystems
+//! exist.
+//!
+//! [`PermissionStore`] only persists non-default decisions, so a fresh
+//! profile's file stays empty until the user actually grants or denies
+//! something.
+
+mod kind;
+mod state;
+mod store;
+
+pub use kind::PermissionKind;
+pub use state::PermissionState;
+pub use store::{origin_of, PermissionStore};
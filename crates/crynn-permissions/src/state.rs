@@ -0,0 +1,18 @@
+use serde::{Deserialize, Serialize};
+
+/// The user's decision for one site/[`crate::PermissionKind`] pair.
+/// `Prompt` is the default: the site hasn't been asked, or the user
+/// dismissed the prompt without deciding either way.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum PermissionState {
+    #[default]
+    Prompt,
+    Granted,
+    Denied,
+}
+
+impl PermissionState {
+    pub fn is_granted(self) -> bool {
+        matches!(self, Self::Granted)
+    }
+}
@@ -0,0 +1,180 @@
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::kind::PermissionKind;
+use crate::state::PermissionState;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct PermissionRecord {
+    origin: String,
+    kind: PermissionKind,
+    state: PermissionState,
+}
+
+/// Per-site permission decisions, persisted across restarts. Only sites
+/// with a non-default decision are stored — a site that's never been
+/// asked (or whose prompt was dismissed) has no record and reads back as
+/// [`PermissionState::Prompt`].
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct PermissionStore {
+    records: Vec<PermissionRecord>,
+    #[serde(skip)]
+    path: Option<PathBuf>,
+}
+
+impl PermissionStore {
+    /// Loads decisions from `path` if it exists, otherwise starts empty.
+    pub fn load(path: impl Into<PathBuf>) -> io::Result<Self> {
+        let path = path.into();
+        let mut store = match fs::read_to_string(&path) {
+            Ok(contents) => serde_json::from_str::<PermissionStore>(&contents)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => PermissionStore::default(),
+            Err(e) => return Err(e),
+        };
+        store.path = Some(path);
+        Ok(store)
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let contents = serde_json::to_string_pretty(self)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        fs::write(path, contents)
+    }
+
+    pub fn state(&self, origin: &str, kind: PermissionKind) -> PermissionState {
+        self.records
+            .iter()
+            .find(|r| r.origin == origin && r.kind == kind)
+            .map(|r| r.state)
+            .unwrap_or_default()
+    }
+
+    pub fn is_allowed(&self, origin: &str, kind: PermissionKind) -> bool {
+        self.state(origin, kind).is_granted()
+    }
+
+    /// Records the user's decision for `origin`/`kind`, overwriting any
+    /// previous one. Setting back to [`PermissionState::Prompt`] removes
+    /// the record instead of storing the default explicitly.
+    pub fn set(&mut self, origin: &str, kind: PermissionKind, state: PermissionState) {
+        self.records.retain(|r| !(r.origin == origin && r.kind == kind));
+        if state != PermissionState::Prompt {
+            self.records.push(PermissionRecord { origin: origin.to_string(), kind, state });
+        }
+    }
+
+    pub fn grant(&mut self, origin: &str, kind: PermissionKind) {
+        self.set(origin, kind, PermissionState::Granted);
+    }
+
+    pub fn deny(&mut self, origin: &str, kind: PermissionKind) {
+        self.set(origin, kind, PermissionState::Denied);
+    }
+
+    pub fn reset(&mut self, origin: &str, kind: PermissionKind) {
+        self.set(origin, kind, PermissionState::Prompt);
+    }
+
+    /// Drops every decision recorded for `origin`, across every
+    /// [`PermissionKind`], e.g. when the user forgets a site from
+    /// history. Other origins' decisions are untouched.
+    pub fn forget_origin(&mut self, origin: &str) {
+        self.records.retain(|r| r.origin != origin);
+    }
+}
+
+/// Extracts `scheme://host[:port]` from a URL without pulling in a full
+/// URL parser — good enough for permission keying, which only needs to
+/// group pages on the same site. Mirrors `crynn-shell`'s zoom-level
+/// origin keying.
+pub fn origin_of(url: &str) -> String {
+    match url.split_once("://") {
+        Some((scheme, rest)) => {
+            let host_end = rest.find('/').unwrap_or(rest.len());
+            format!("{scheme}://{}", &rest[..host_end])
+        }
+        None => url.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unasked_sites_default_to_prompt() {
+        let store = PermissionStore::default();
+        assert_eq!(store.state("https://example.com", PermissionKind::Push), PermissionState::Prompt);
+        assert!(!store.is_allowed("https://example.com", PermissionKind::Push));
+    }
+
+    #[test]
+    fn granting_then_denying_overwrites_the_previous_decision() {
+        let mut store = PermissionStore::default();
+        store.grant("https://example.com", PermissionKind::Push);
+        assert!(store.is_allowed("https://example.com", PermissionKind::Push));
+
+        store.deny("https://example.com", PermissionKind::Push);
+        assert!(!store.is_allowed("https://example.com", PermissionKind::Push));
+        assert_eq!(store.state("https://example.com", PermissionKind::Push), PermissionState::Denied);
+    }
+
+    #[test]
+    fn resetting_to_prompt_drops_the_stored_record() {
+        let mut store = PermissionStore::default();
+        store.grant("https://example.com", PermissionKind::Push);
+        store.reset("https://example.com", PermissionKind::Push);
+        assert!(store.records.is_empty());
+    }
+
+    #[test]
+    fn forget_origin_drops_every_kind_for_that_origin_only() {
+        let mut store = PermissionStore::default();
+        store.grant("https://example.com", PermissionKind::Push);
+        store.deny("https://example.com", PermissionKind::Cookies);
+        store.grant("https://other.com", PermissionKind::Push);
+
+        store.forget_origin("https://example.com");
+
+        assert_eq!(store.state("https://example.com", PermissionKind::Push), PermissionState::Prompt);
+        assert_eq!(store.state("https://example.com", PermissionKind::Cookies), PermissionState::Prompt);
+        assert!(store.is_allowed("https://other.com", PermissionKind::Push));
+    }
+
+    #[test]
+    fn decisions_are_scoped_per_origin() {
+        let mut store = PermissionStore::default();
+        store.grant("https://a.example.com", PermissionKind::Push);
+        assert!(!store.is_allowed("https://b.example.com", PermissionKind::Push));
+    }
+
+    #[test]
+    fn save_and_load_round_trip() {
+        let dir = std::env::temp_dir().join(format!("crynn-permissions-test-{}", std::process::id()));
+        let path = dir.join("permissions.json");
+        let mut store = PermissionStore::load(&path).unwrap();
+        store.grant("https://example.com", PermissionKind::Push);
+        store.save().unwrap();
+
+        let reloaded = PermissionStore::load(&path).unwrap();
+        assert!(reloaded.is_allowed("https://example.com", PermissionKind::Push));
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn origin_of_strips_path_but_keeps_scheme_and_host() {
+        assert_eq!(origin_of("https://example.com/page?x=1"), "https://example.com");
+        assert_eq!(origin_of("https://example.com"), "https://example.com");
+    }
+}
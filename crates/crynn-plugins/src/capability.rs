@@ -0,0 +1,26 @@
+/// A permission a plugin must declare in its [`crate::PluginManifest`] before
+/// [`crate::PluginHost`] will dispatch the matching hook to it. Mirrors a
+/// native extension's permission manifest, except enforced on every call
+/// rather than trusted once at install time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Capability {
+    /// Receive [`crate::NavigationEvent`]s as tabs navigate.
+    ObserveNavigations,
+    /// Participate in the request interceptor chain.
+    ModifyRequests,
+    /// Contribute suggestions to the omnibox.
+    OmniboxProvider,
+    /// Contribute toolbar actions.
+    ToolbarActions,
+}
+
+impl Capability {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Capability::ObserveNavigations => "observe-navigations",
+            Capability::ModifyRequests => "modify-requests",
+            Capability::OmniboxProvider => "omnibox-provider",
+            Capability::ToolbarActions => "toolbar-actions",
+        }
+    }
+}
@@ -0,0 +1,41 @@
+/// Reported to plugins with [`crate::Capability::ObserveNavigations`] when
+/// a tab navigates.
+#[derive(Debug, Clone)]
+pub struct NavigationEvent {
+    pub tab_id: u64,
+    pub url: String,
+}
+
+/// What a plugin with [`crate::Capability::ModifyRequests`] sees for each
+/// outgoing request, before it reaches the network stack.
+#[derive(Debug, Clone)]
+pub struct RequestContext {
+    pub url: String,
+    pub method: String,
+}
+
+/// A request interceptor's verdict. [`crate::PluginHost::intercept_request`]
+/// runs every granted plugin in manifest order and stops at the first
+/// non-[`RequestAction::Allow`] result.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RequestAction {
+    Allow,
+    Block,
+    Redirect { url: String },
+}
+
+/// One suggestion contributed by an [`crate::Capability::OmniboxProvider`]
+/// plugin for a given query.
+#[derive(Debug, Clone)]
+pub struct OmniboxSuggestion {
+    pub text: String,
+    pub url: String,
+}
+
+/// One toolbar button contributed by a [`crate::Capability::ToolbarActions`]
+/// plugin.
+#[derive(Debug, Clone)]
+pub struct ToolbarAction {
+    pub id: String,
+    pub title: String,
+}
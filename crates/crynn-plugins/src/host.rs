@@ -0,0 +1,184 @@
+use crate::capability::Capability;
+use crate::events::{NavigationEvent, OmniboxSuggestion, RequestAction, RequestContext, ToolbarAction};
+use crate::plugin::Plugin;
+
+/// Owns every loaded plugin and is the only thing in the shell that calls
+/// into them. Every dispatch method here checks
+/// [`crate::PluginManifest::grants`] before calling a plugin's runtime, so
+/// an un-granted capability is enforced at the host boundary rather than
+/// trusted to the plugin.
+#[derive(Default)]
+pub struct PluginHost {
+    plugins: Vec<Plugin>,
+}
+
+impl PluginHost {
+    pub fn register(&mut self, plugin: Plugin) {
+        self.plugins.push(plugin);
+    }
+
+    pub fn plugins(&self) -> impl Iterator<Item = &Plugin> {
+        self.plugins.iter()
+    }
+
+    /// Notifies every plugin with [`Capability::ObserveNavigations`]. A
+    /// plugin that faults is logged and skipped — one broken observer
+    /// shouldn't stop navigation.
+    pub fn notify_navigation(&mut self, event: &NavigationEvent) {
+        for plugin in self.granted_mut(Capability::ObserveNavigations) {
+            if let Err(e) = plugin.runtime.on_navigation(event) {
+                tracing::warn!(plugin = %plugin.manifest.id, error = %e, "plugin faulted observing navigation");
+            }
+        }
+    }
+
+    /// Runs the request interceptor chain in registration order, stopping
+    /// at the first plugin that doesn't allow the request. A faulting
+    /// plugin is logged and treated as if it had allowed the request, so
+    /// one broken interceptor can't block all network traffic.
+    pub fn intercept_request(&mut self, ctx: &RequestContext) -> RequestAction {
+        for plugin in self.granted_mut(Capability::ModifyRequests) {
+            match plugin.runtime.intercept_request(ctx) {
+                Ok(RequestAction::Allow) => continue,
+                Ok(action) => return action,
+                Err(e) => {
+                    tracing::warn!(plugin = %plugin.manifest.id, error = %e, "plugin faulted intercepting request");
+                }
+            }
+        }
+        RequestAction::Allow
+    }
+
+    /// Collects omnibox suggestions from every plugin with
+    /// [`Capability::OmniboxProvider`], in registration order.
+    pub fn omnibox_suggestions(&mut self, query: &str) -> Vec<OmniboxSuggestion> {
+        let mut suggestions = Vec::new();
+        for plugin in self.granted_mut(Capability::OmniboxProvider) {
+            match plugin.runtime.omnibox_suggestions(query) {
+                Ok(mut contributed) => suggestions.append(&mut contributed),
+                Err(e) => {
+                    tracing::warn!(plugin = %plugin.manifest.id, error = %e, "plugin faulted providing omnibox suggestions");
+                }
+            }
+        }
+        suggestions
+    }
+
+    /// Collects toolbar actions from every plugin with
+    /// [`Capability::ToolbarActions`], paired with the contributing
+    /// plugin's id so the shell can route clicks back to it.
+    pub fn toolbar_actions(&mut self) -> Vec<(String, ToolbarAction)> {
+        let mut actions = Vec::new();
+        for plugin in self.granted_mut(Capability::ToolbarActions) {
+            let plugin_id = plugin.manifest.id.clone();
+            match plugin.runtime.toolbar_actions() {
+                Ok(contributed) => actions.extend(contributed.into_iter().map(|action| (plugin_id.clone(), action))),
+                Err(e) => {
+                    tracing::warn!(plugin = %plugin_id, error = %e, "plugin faulted contributing toolbar actions");
+                }
+            }
+        }
+        actions
+    }
+
+    fn granted_mut(&mut self, capability: Capability) -> impl Iterator<Item = &mut Plugin> {
+        self.plugins.iter_mut().filter(move |plugin| plugin.manifest.grants(capability))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manifest::PluginManifest;
+    use crate::runtime::PluginRuntime;
+    use crynn_error::PluginError;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    struct RecordingRuntime {
+        navigations_seen: Arc<AtomicUsize>,
+    }
+
+    impl PluginRuntime for RecordingRuntime {
+        fn on_navigation(&mut self, _event: &NavigationEvent) -> Result<(), PluginError> {
+            self.navigations_seen.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+    }
+
+    struct BlockingRuntime;
+
+    impl PluginRuntime for BlockingRuntime {
+        fn intercept_request(&mut self, _ctx: &RequestContext) -> Result<RequestAction, PluginError> {
+            Ok(RequestAction::Block)
+        }
+    }
+
+    struct FaultingRuntime;
+
+    impl PluginRuntime for FaultingRuntime {
+        fn intercept_request(&mut self, _ctx: &RequestContext) -> Result<RequestAction, PluginError> {
+            Err(PluginError::RuntimeFault {
+                plugin: "faulting".to_string(),
+                detail: "boom".to_string(),
+            })
+        }
+    }
+
+    #[test]
+    fn navigation_events_only_reach_plugins_with_the_capability() {
+        let observer_seen = Arc::new(AtomicUsize::new(0));
+        let bystander_seen = Arc::new(AtomicUsize::new(0));
+
+        let mut host = PluginHost::default();
+        host.register(Plugin::new(
+            PluginManifest::new("observer", "Observer", "1.0.0", vec![Capability::ObserveNavigations]),
+            Box::new(RecordingRuntime { navigations_seen: observer_seen.clone() }),
+        ));
+        host.register(Plugin::new(
+            PluginManifest::new("bystander", "Bystander", "1.0.0", vec![]),
+            Box::new(RecordingRuntime { navigations_seen: bystander_seen.clone() }),
+        ));
+
+        host.notify_navigation(&NavigationEvent { tab_id: 1, url: "https://example.com".to_string() });
+
+        assert_eq!(observer_seen.load(Ordering::SeqCst), 1);
+        assert_eq!(bystander_seen.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn intercept_request_stops_at_the_first_non_allow_verdict() {
+        let mut host = PluginHost::default();
+        host.register(Plugin::new(
+            PluginManifest::new("blocker", "Blocker", "1.0.0", vec![Capability::ModifyRequests]),
+            Box::new(BlockingRuntime),
+        ));
+
+        let action = host.intercept_request(&RequestContext { url: "https://example.com".to_string(), method: "GET".to_string() });
+        assert_eq!(action, RequestAction::Block);
+    }
+
+    #[test]
+    fn ungranted_plugins_never_run_as_interceptors() {
+        let mut host = PluginHost::default();
+        host.register(Plugin::new(
+            PluginManifest::new("blocker", "Blocker", "1.0.0", vec![]),
+            Box::new(BlockingRuntime),
+        ));
+
+        let action = host.intercept_request(&RequestContext { url: "https://example.com".to_string(), method: "GET".to_string() });
+        assert_eq!(action, RequestAction::Allow);
+    }
+
+    #[test]
+    fn a_faulting_interceptor_is_skipped_rather_than_blocking_the_request() {
+        let mut host = PluginHost::default();
+        host.register(Plugin::new(
+            PluginManifest::new("faulty", "Faulty", "1.0.0", vec![Capability::ModifyRequests]),
+            Box::new(FaultingRuntime),
+        ));
+
+        let action = host.intercept_request(&RequestContext { url: "https://example.com".to_string(), method: "GET".to_string() });
+        assert_eq!(action, RequestAction::Allow);
+    }
+}
@@ -0,0 +1,25 @@
+//! Capability-scoped host API for plugins, giving extension-like
+//! functionality without trusting native code: plugins observe
+//! navigations, modify requests via the interceptor chain, contribute
+//! omnibox suggestions, and add toolbar actions, all gated on the
+//! capabilities their [`PluginManifest`] declares.
+//!
+//! [`PluginHost`] never calls into a plugin directly — it goes through the
+//! [`PluginRuntime`] trait, which is the seam where a real sandbox plugs
+//! in. A wasmtime-backed `PluginRuntime` that loads and calls into actual
+//! WASM modules is the natural next implementation; this crate defines
+//! the host-side contract it has to satisfy.
+
+mod capability;
+mod events;
+mod host;
+mod manifest;
+mod plugin;
+mod runtime;
+
+pub use capability::Capability;
+pub use events::{NavigationEvent, OmniboxSuggestion, RequestAction, RequestContext, ToolbarAction};
+pub use host::PluginHost;
+pub use manifest::PluginManifest;
+pub use plugin::Plugin;
+pub use runtime::PluginRuntime;
@@ -0,0 +1,42 @@
+use crate::capability::Capability;
+
+/// A plugin's declared identity and the capabilities it's asking for.
+/// [`crate::PluginHost`] only dispatches hooks covered by
+/// [`PluginManifest::grants`] — a plugin that doesn't list
+/// [`Capability::ModifyRequests`] simply never appears in the request
+/// interceptor chain, regardless of what its code tries to do.
+#[derive(Debug, Clone)]
+pub struct PluginManifest {
+    pub id: String,
+    pub name: String,
+    pub version: String,
+    capabilities: Vec<Capability>,
+}
+
+impl PluginManifest {
+    pub fn new(id: impl Into<String>, name: impl Into<String>, version: impl Into<String>, capabilities: Vec<Capability>) -> Self {
+        Self {
+            id: id.into(),
+            name: name.into(),
+            version: version.into(),
+            capabilities,
+        }
+    }
+
+    pub fn grants(&self, capability: Capability) -> bool {
+        self.capabilities.contains(&capability)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn grants_only_reflects_declared_capabilities() {
+        let manifest = PluginManifest::new("id", "name", "1.0.0", vec![Capability::ObserveNavigations]);
+
+        assert!(manifest.grants(Capability::ObserveNavigations));
+        assert!(!manifest.grants(Capability::ModifyRequests));
+    }
+}
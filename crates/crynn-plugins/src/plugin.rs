@@ -0,0 +1,15 @@
+use crate::manifest::PluginManifest;
+use crate::runtime::PluginRuntime;
+
+/// A loaded plugin: its declared manifest plus the sandboxed instance
+/// backing it.
+pub struct Plugin {
+    pub manifest: PluginManifest,
+    pub(crate) runtime: Box<dyn PluginRuntime>,
+}
+
+impl Plugin {
+    pub fn new(manifest: PluginManifest, runtime: Box<dyn PluginRuntime>) -> Self {
+        Self { manifest, runtime }
+    }
+}
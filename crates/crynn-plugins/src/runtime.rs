@@ -0,0 +1,31 @@
+use crate::events::{NavigationEvent, OmniboxSuggestion, RequestAction, RequestContext, ToolbarAction};
+use crynn_error::PluginError;
+
+/// The sandboxed execution backend for one loaded plugin. [`crate::PluginHost`]
+/// only ever calls through this trait, never into plugin code directly, so
+/// swapping in a real sandbox (a wasmtime instance, one per plugin) is a
+/// matter of implementing this trait — the capability gating and dispatch
+/// order in [`crate::PluginHost`] don't change.
+///
+/// Every method takes `&mut self` because a plugin's sandboxed instance is
+/// exclusive, stateful execution context, not a pure function.
+pub trait PluginRuntime {
+    fn on_navigation(&mut self, event: &NavigationEvent) -> Result<(), PluginError> {
+        let _ = event;
+        Ok(())
+    }
+
+    fn intercept_request(&mut self, ctx: &RequestContext) -> Result<RequestAction, PluginError> {
+        let _ = ctx;
+        Ok(RequestAction::Allow)
+    }
+
+    fn omnibox_suggestions(&mut self, query: &str) -> Result<Vec<OmniboxSuggestion>, PluginError> {
+        let _ = query;
+        Ok(Vec::new())
+    }
+
+    fn toolbar_actions(&mut self) -> Result<Vec<ToolbarAction>, PluginError> {
+        Ok(Vec::new())
+    }
+}
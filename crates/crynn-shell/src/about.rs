@@ -0,0 +1,336 @@
+use std::path::{Path, PathBuf};
+
+use crynn_storage::StorageManager;
+
+use crate::startup::LazySubsystem;
+use crate::ShellState;
+
+/// One of the built-in `about:` pages. Both this native egui shell and a
+/// future Firefox-backed shell (which would load internal pages as real
+/// navigations rather than overlay windows) resolve the same page from
+/// the same URL through [`AboutPage::from_url`], so the two stay
+/// consistent without duplicating the page list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AboutPage {
+    History,
+    Downloads,
+    Memory,
+    Network,
+    Vpn,
+    Settings,
+}
+
+impl AboutPage {
+    pub const ALL: [AboutPage; 6] = [
+        AboutPage::History,
+        AboutPage::Downloads,
+        AboutPage::Memory,
+        AboutPage::Network,
+        AboutPage::Vpn,
+        AboutPage::Settings,
+    ];
+
+    pub fn url(&self) -> &'static str {
+        match self {
+            AboutPage::History => "about:history",
+            AboutPage::Downloads => "about:downloads",
+            AboutPage::Memory => "about:memory",
+            AboutPage::Network => "about:network",
+            AboutPage::Vpn => "about:vpn",
+            AboutPage::Settings => "about:settings",
+        }
+    }
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            AboutPage::History => "History",
+            AboutPage::Downloads => "Downloads",
+            AboutPage::Memory => "Memory",
+            AboutPage::Network => "Network",
+            AboutPage::Vpn => "VPN",
+            AboutPage::Settings => "Settings",
+        }
+    }
+
+    /// Resolves `url` to the page it names, or `None` if it isn't one of
+    /// [`AboutPage::ALL`].
+    pub fn from_url(url: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|page| page.url() == url)
+    }
+}
+
+/// What a page has to show, gathered fresh from the shell's own state each
+/// time it's rendered. `Unavailable` is how a page says its backing
+/// subsystem doesn't exist in this build yet, rather than rendering empty
+/// or fabricated rows.
+pub enum AboutPageContent {
+    Lines(Vec<String>),
+    Unavailable { subsystem: &'static str },
+}
+
+/// Gathers `page`'s content from whatever real data `state` has on hand.
+pub fn content(page: AboutPage, state: &ShellState) -> AboutPageContent {
+    match page {
+        AboutPage::History => match &state.storage {
+            Some(storage) => AboutPageContent::Lines(history_lines(storage)),
+            None => AboutPageContent::Unavailable { subsystem: "crynn-storage" },
+        },
+        AboutPage::Downloads => AboutPageContent::Unavailable { subsystem: "crynn-downloads" },
+        AboutPage::Memory => {
+            let snapshot = state.engine.profiler_snapshot();
+            let mut lines: Vec<String> = snapshot
+                .components
+                .iter()
+                .map(|c| format!("{}: {} bytes", c.label, c.memory_bytes))
+                .collect();
+            lines.insert(0, format!("Total: {} bytes", snapshot.total_memory_bytes()));
+            lines.push(String::new());
+            lines.push(startup_line(&state.network));
+            lines.push(startup_line(&state.email));
+            lines.push(startup_line(&state.vpn));
+            AboutPageContent::Lines(lines)
+        }
+        AboutPage::Network => match state.network.peek() {
+            Some(network) => AboutPageContent::Lines(network_lines(network)),
+            None => AboutPageContent::Lines(vec!["Network stack not yet initialized (deferred until first use)".to_string()]),
+        },
+        AboutPage::Vpn => match &state.storage {
+            Some(storage) => AboutPageContent::Lines(vpn_lines(storage, &state.vpn_manager)),
+            None => AboutPageContent::Unavailable { subsystem: "crynn-storage" },
+        },
+        AboutPage::Settings => {
+            let config = state.config.config();
+            AboutPageContent::Lines(vec![
+                format!("Locale: {}", config.locale.locale),
+                format!("Tracking protection: {}", config.tracking.strictness),
+                format!("Telemetry enabled: {}", config.metrics.enabled),
+                format!("Log level: {}", config.logging.level),
+                format!("Update channel: {}", config.updates.channel),
+            ])
+        }
+    }
+}
+
+/// Reports how expensive a [`LazySubsystem`] was to start, or that it
+/// hasn't started at all yet, so a startup regression shows up here
+/// instead of only in a profiler no one is running.
+fn startup_line<T>(subsystem: &LazySubsystem<T>) -> String {
+    match subsystem.cost() {
+        Some(cost) => format!("{}: init {}ms, {:+} bytes RSS", subsystem.label(), cost.init_time_ms, cost.rss_delta_bytes),
+        None => format!("{}: not yet initialized (lazy)", subsystem.label()),
+    }
+}
+
+fn network_lines(network: &crynn_network::NetworkManager) -> Vec<String> {
+    network
+        .connections()
+        .into_iter()
+        .map(|(origin, stats)| {
+            format!(
+                "{origin}: {:?}, {} open streams, {}ms RTT, {} errors",
+                stats.protocol,
+                stats.open_streams,
+                stats.rtt_estimate_ms,
+                stats.errors.len()
+            )
+        })
+        .collect()
+}
+
+fn history_lines(storage: &StorageManager) -> Vec<String> {
+    let Ok(mut entries) = crynn_storage::visits(storage) else {
+        return Vec::new();
+    };
+    entries.sort_by(|a, b| a.id.cmp(&b.id));
+    let mut lines: Vec<String> = entries
+        .iter()
+        .map(|visit| format!("{}: {} ({}) [{:?}]", visit.id, visit.title, visit.url, visit.visit_type))
+        .collect();
+
+    if let Ok(stats) = crynn_storage::stats(storage, 0, u64::MAX) {
+        lines.push(String::new());
+        lines.push(format!("Unique sites visited: {}", stats.unique_sites));
+        if let Some((domain, count)) = stats.top_domains.first() {
+            lines.push(format!("Most visited: {domain} ({count} visits)"));
+        }
+    }
+    lines
+}
+
+/// Session list plus a day-by-day data-usage chart for the VPN panel,
+/// built from persisted sessions (so it survives a restart) and the
+/// current connection status (which only lives in `vpn_manager`).
+fn vpn_lines(storage: &StorageManager, vpn_manager: &crynn_vpn::VpnManager) -> Vec<String> {
+    let mut lines = vec![format!("Status: {:?}", vpn_manager.status())];
+
+    let Ok(mut sessions) = crynn_storage::vpn_sessions(storage) else {
+        return lines;
+    };
+    sessions.sort_by_key(|s| s.connected_at);
+    lines.extend(sessions.iter().map(|s| {
+        format!(
+            "{} @ {}: {} bytes up / {} bytes down{}",
+            s.provider,
+            s.location,
+            s.bytes_up,
+            s.bytes_down,
+            if s.disconnected_at.is_none() { " (active)" } else { "" }
+        )
+    }));
+
+    if let Ok(usage) = crynn_storage::usage_per_day(storage, 0, u64::MAX) {
+        if !usage.is_empty() {
+            lines.push(String::new());
+            lines.push("Daily usage:".to_string());
+            let max_total = usage.values().map(|(up, down)| up + down).max().unwrap_or(0).max(1);
+            for (day, (up, down)) in usage {
+                let bar_len = ((up + down) * 20 / max_total) as usize;
+                lines.push(format!("day {day}: {} ({} bytes)", "#".repeat(bar_len), up + down));
+            }
+        }
+    }
+    lines
+}
+
+/// Renders `content` as the small HTML document a Firefox-backed shell
+/// would load for `page` in place of a real network fetch.
+pub fn render_html(page: AboutPage, content: &AboutPageContent) -> String {
+    let body = match content {
+        AboutPageContent::Lines(lines) => {
+            let items: String = lines.iter().map(|line| format!("<li>{line}</li>")).collect();
+            format!("<ul>{items}</ul>")
+        }
+        AboutPageContent::Unavailable { subsystem } => {
+            format!("<p>{subsystem} isn't wired into this build yet.</p>")
+        }
+    };
+    format!("<html><head><title>{0}</title></head><body><h1>{0}</h1>{body}</body></html>", page.title())
+}
+
+/// Central registry of which `about:` pages are currently open, shared by
+/// every entry point (actions, tab navigation) so the shell never ends up
+/// with two independent notions of "is about:memory open".
+#[derive(Default)]
+pub struct AboutPages {
+    open: Vec<AboutPage>,
+}
+
+impl AboutPages {
+    pub fn is_open(&self, page: AboutPage) -> bool {
+        self.open.contains(&page)
+    }
+
+    pub fn open(&mut self, page: AboutPage) {
+        if !self.is_open(page) {
+            self.open.push(page);
+        }
+    }
+
+    pub fn close(&mut self, page: AboutPage) {
+        self.open.retain(|p| *p != page);
+    }
+
+    pub fn toggle(&mut self, page: AboutPage) {
+        if self.is_open(page) {
+            self.close(page);
+        } else {
+            self.open(page);
+        }
+    }
+
+    /// The single entry point both shells should call when a tab
+    /// navigates to an `about:` URL. Returns whether `url` named a known
+    /// page; unknown `about:` URLs are left for the caller to handle
+    /// however it already does (e.g. a "page not found" navigation).
+    pub fn navigate(&mut self, url: &str) -> bool {
+        match AboutPage::from_url(url) {
+            Some(page) => {
+                self.open(page);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Draws every open page as its own window.
+    pub fn ui(&mut self, ctx: &egui::Context, state: &ShellState) {
+        let pages = self.open.clone();
+        for page in pages {
+            let mut open = true;
+            egui::Window::new(page.title()).open(&mut open).show(ctx, |ui| {
+                match content(page, state) {
+                    AboutPageContent::Lines(lines) => {
+                        if lines.is_empty() {
+                            ui.label("Nothing to show yet.");
+                        }
+                        for line in lines {
+                            ui.label(line);
+                        }
+                    }
+                    AboutPageContent::Unavailable { subsystem } => {
+                        ui.label(format!("{subsystem} isn't wired into this build yet."));
+                    }
+                }
+            });
+            if !open {
+                self.close(page);
+            }
+        }
+    }
+}
+
+/// Default profile data directory for the databases behind `about:history`,
+/// mirroring `crynn-cli`'s own fallback since the shell has no dependency
+/// on the CLI crate to share it through.
+pub(crate) fn default_storage_dir() -> Option<PathBuf> {
+    std::env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| Path::new(&home).join(".local/share")))
+        .map(|dir| dir.join("crynn"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_url_round_trips_every_page() {
+        for page in AboutPage::ALL {
+            assert_eq!(AboutPage::from_url(page.url()), Some(page));
+        }
+    }
+
+    #[test]
+    fn from_url_rejects_unknown_about_urls() {
+        assert_eq!(AboutPage::from_url("about:blank"), None);
+    }
+
+    #[test]
+    fn navigate_opens_the_named_page_and_reports_success() {
+        let mut pages = AboutPages::default();
+        assert!(pages.navigate("about:memory"));
+        assert!(pages.is_open(AboutPage::Memory));
+    }
+
+    #[test]
+    fn navigate_to_an_unknown_page_leaves_everything_closed() {
+        let mut pages = AboutPages::default();
+        assert!(!pages.navigate("about:blank"));
+        assert!(AboutPage::ALL.iter().all(|p| !pages.is_open(*p)));
+    }
+
+    #[test]
+    fn toggle_flips_open_state() {
+        let mut pages = AboutPages::default();
+        pages.toggle(AboutPage::Vpn);
+        assert!(pages.is_open(AboutPage::Vpn));
+        pages.toggle(AboutPage::Vpn);
+        assert!(!pages.is_open(AboutPage::Vpn));
+    }
+
+    #[test]
+    fn render_html_reports_unavailable_subsystems() {
+        let html = render_html(AboutPage::Vpn, &AboutPageContent::Unavailable { subsystem: "crynn-vpn" });
+        assert!(html.contains("crynn-vpn"));
+    }
+}
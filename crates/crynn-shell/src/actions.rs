@@ -0,0 +1,248 @@
+use crate::ShellState;
+
+/// A single browser action: something the command palette can list and the
+/// keybinding dispatcher can trigger. New subsystems register their own
+/// actions here instead of wiring bespoke menu items and shortcuts.
+pub struct Action {
+    pub id: &'static str,
+    pub title: &'static str,
+    /// Human-readable shortcut shown in the palette, e.g. `"Ctrl+Shift+P"`.
+    /// Actual key matching lives in [`crate::keybindings::KeybindingDispatcher`].
+    pub shortcut: Option<&'static str>,
+    pub run: fn(&mut ShellState),
+}
+
+/// Central list of actions the shell exposes, in registration order.
+#[derive(Default)]
+pub struct ActionRegistry {
+    actions: Vec<Action>,
+}
+
+impl ActionRegistry {
+    pub fn register(&mut self, action: Action) {
+        debug_assert!(
+            !self.actions.iter().any(|a| a.id == action.id),
+            "duplicate action id {}",
+            action.id
+        );
+        self.actions.push(action);
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Action> {
+        self.actions.iter()
+    }
+
+    pub fn get(&self, id: &str) -> Option<&Action> {
+        self.actions.iter().find(|a| a.id == id)
+    }
+
+    /// Looks up and runs the action by id, if it exists.
+    pub fn run(&self, id: &str, state: &mut ShellState) -> bool {
+        match self.get(id) {
+            Some(action) => {
+                (action.run)(state);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// The built-in actions every shell window registers on startup.
+    /// Subsystem crates that don't exist yet (settings, VPN, cache) will
+    /// add their own actions here as they land.
+    pub fn builtin() -> Self {
+        let mut registry = Self::default();
+        registry.register(Action {
+            id: "view.toggle-task-manager",
+            title: "View: Toggle Task Manager",
+            shortcut: Some("Ctrl+Shift+M"),
+            run: |state| state.performance_view.toggle(),
+        });
+        registry.register(Action {
+            id: "view.toggle-command-palette",
+            title: "Show All Commands",
+            shortcut: Some("Ctrl+Shift+P"),
+            run: |state| state.command_palette.toggle(),
+        });
+        registry.register(Action {
+            id: "view.zoom-in",
+            title: "Zoom In",
+            shortcut: Some("Ctrl++"),
+            run: |state| {
+                let zoom = state.active_tab().map(|t| t.zoom()).unwrap_or(crynn_engine::DEFAULT_ZOOM);
+                state.set_active_tab_zoom(zoom + crate::zoom::ZOOM_STEP);
+            },
+        });
+        registry.register(Action {
+            id: "view.zoom-out",
+            title: "Zoom Out",
+            shortcut: Some("Ctrl+-"),
+            run: |state| {
+                let zoom = state.active_tab().map(|t| t.zoom()).unwrap_or(crynn_engine::DEFAULT_ZOOM);
+                state.set_active_tab_zoom(zoom - crate::zoom::ZOOM_STEP);
+            },
+        });
+        registry.register(Action {
+            id: "view.toggle-notification-history",
+            title: "View: Notification History",
+            shortcut: None,
+            run: |state| state.toasts.toggle_history(),
+        });
+        registry.register(Action {
+            id: "view.toggle-logs",
+            title: "about:logs",
+            shortcut: None,
+            run: |state| state.logs_view.toggle(),
+        });
+        registry.register(Action {
+            id: "view.toggle-telemetry",
+            title: "about:telemetry",
+            shortcut: None,
+            run: |state| state.telemetry_view.toggle(),
+        });
+        registry.register(Action {
+            id: "view.toggle-crashes",
+            title: "about:crashes",
+            shortcut: None,
+            run: |state| state.crashes_view.toggle(),
+        });
+        registry.register(Action {
+            id: "view.zoom-reset",
+            title: "Reset Zoom",
+            shortcut: Some("Ctrl+0"),
+            run: |state| state.set_active_tab_zoom(crynn_engine::DEFAULT_ZOOM),
+        });
+        registry.register(Action {
+            id: "privacy.disable-push-for-site",
+            title: "Disable Notifications for This Site",
+            shortcut: None,
+            run: crate::push::disable_push_for_active_tab,
+        });
+        registry.register(Action {
+            id: "view.about-history",
+            title: "about:history",
+            shortcut: None,
+            run: |state| state.about_pages.toggle(crate::about::AboutPage::History),
+        });
+        registry.register(Action {
+            id: "view.about-downloads",
+            title: "about:downloads",
+            shortcut: None,
+            run: |state| state.about_pages.toggle(crate::about::AboutPage::Downloads),
+        });
+        registry.register(Action {
+            id: "view.about-memory",
+            title: "about:memory",
+            shortcut: None,
+            run: |state| state.about_pages.toggle(crate::about::AboutPage::Memory),
+        });
+        registry.register(Action {
+            id: "view.about-vpn",
+            title: "about:vpn",
+            shortcut: None,
+            run: |state| state.about_pages.toggle(crate::about::AboutPage::Vpn),
+        });
+        registry.register(Action {
+            id: "view.about-settings",
+            title: "about:settings",
+            shortcut: None,
+            run: |state| state.about_pages.toggle(crate::about::AboutPage::Settings),
+        });
+        registry.register(Action {
+            id: "view.toggle-tab-groups",
+            title: "View: Toggle Tab Groups",
+            shortcut: None,
+            run: |state| state.tab_groups_view.toggle(),
+        });
+        registry.register(Action {
+            id: "privacy.toggle-cookie-panel",
+            title: "View: Cookies for This Site",
+            shortcut: None,
+            run: |state| state.cookie_panel.toggle(),
+        });
+        registry.register(Action {
+            id: "network.open-captive-portal",
+            title: "Network: Sign In to This Network",
+            shortcut: None,
+            run: crate::connectivity::open_captive_portal,
+        });
+        registry.register(Action {
+            id: "protocol.cancel-external-launch",
+            title: "Dismiss External App Prompt",
+            shortcut: None,
+            run: crate::protocol_handlers::cancel_external_launch,
+        });
+        registry.register(Action {
+            id: "sync.now",
+            title: "Sync Now",
+            shortcut: None,
+            run: crate::sync::sync_now,
+        });
+        registry.register(Action {
+            id: "window.new-private",
+            title: "New Private Window",
+            shortcut: None,
+            // Opens a fresh tab rather than an actually isolated window:
+            // there's no session-isolated private-browsing storage mode
+            // in this build yet, only a single shared `ShellState` and
+            // one `TabRegistry`. A real implementation would need its
+            // own in-memory-only storage/cookie/history stack behind
+            // this action rather than the shared ones `open_tab` uses.
+            run: |state| {
+                state.open_tab("about:blank", "New Private Window");
+            },
+        });
+        registry.register(Action {
+            id: "navigation.new-tab",
+            title: "New Tab",
+            shortcut: None,
+            run: |state| {
+                state.open_tab("about:blank", "New Tab");
+            },
+        });
+        registry.register(Action {
+            id: "navigation.back",
+            title: "Back",
+            shortcut: None,
+            // No-op: there's no per-tab navigation history in this engine
+            // yet, the same gap `crate::hints::activate_link`'s "Click"
+            // mode already works around by closing and reopening a tab
+            // rather than truly navigating it. Recorded here so a mouse
+            // gesture or keybinding can be wired to it already, the same
+            // "decision recorded, effect deferred" split as
+            // `crate::popups::PopupRoute::Window`.
+            run: |_state| {},
+        });
+        registry.register(Action {
+            id: "navigation.forward",
+            title: "Forward",
+            shortcut: None,
+            // Same gap as `navigation.back`.
+            run: |_state| {},
+        });
+        registry
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn run_invokes_the_matching_action() {
+        let registry = ActionRegistry::builtin();
+        let mut state = ShellState::default();
+        assert!(!state.performance_view.is_open());
+
+        assert!(registry.run("view.toggle-task-manager", &mut state));
+
+        assert!(state.performance_view.is_open());
+    }
+
+    #[test]
+    fn run_returns_false_for_unknown_id() {
+        let registry = ActionRegistry::builtin();
+        let mut state = ShellState::default();
+        assert!(!registry.run("does.not.exist", &mut state));
+    }
+}
@@ -0,0 +1,110 @@
+use crynn_engine::{EnginePrefs, PrefValue, PREF_GLOBAL_MUTE, PREF_MUTE_BACKGROUND_TABS};
+
+/// Instance-wide audio policy: a global mute toggle and whether a
+/// background tab's playback is automatically muted while it isn't the
+/// focused tab. Per-tab mute and volume live on [`crynn_engine::Tab`]
+/// itself ([`crynn_engine::Tab::set_muted`]/[`crynn_engine::Tab::set_volume`])
+/// since those are per-tab-instance state, not a site-scoped preference
+/// [`crynn_engine::SitePrefStore`] would otherwise hold.
+#[derive(Debug, Default)]
+pub struct AudioGuard {
+    global_muted: bool,
+    mute_background_tabs: bool,
+}
+
+impl AudioGuard {
+    pub fn is_globally_muted(&self) -> bool {
+        self.global_muted
+    }
+
+    pub fn mutes_background_tabs(&self) -> bool {
+        self.mute_background_tabs
+    }
+
+    /// Whether a tab's audio should actually be silenced: its own mute
+    /// state, the global mute toggle, or — if enabled — simply not being
+    /// the focused tab.
+    pub fn effective_mute(&self, tab_muted: bool, is_background: bool) -> bool {
+        tab_muted || self.global_muted || (is_background && self.mute_background_tabs)
+    }
+}
+
+/// Flips the global mute toggle and pushes it onto the engine through
+/// `sink`, the same "decide, then push the pref" split
+/// [`crate::autoplay::allow_origin`] uses for its own instance-wide
+/// override.
+pub fn toggle_global_mute(guard: &mut AudioGuard, sink: &mut dyn EnginePrefs) {
+    guard.global_muted = !guard.global_muted;
+    sink.set_pref(PREF_GLOBAL_MUTE, PrefValue::Bool(guard.global_muted));
+}
+
+/// Sets whether background tabs are automatically muted, pushing the
+/// choice onto the engine the same way [`toggle_global_mute`] does.
+pub fn set_mute_background_tabs(guard: &mut AudioGuard, sink: &mut dyn EnginePrefs, enabled: bool) {
+    guard.mute_background_tabs = enabled;
+    sink.set_pref(PREF_MUTE_BACKGROUND_TABS, PrefValue::Bool(enabled));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default)]
+    struct RecordingPrefs {
+        set: Vec<(String, PrefValue)>,
+    }
+
+    impl EnginePrefs for RecordingPrefs {
+        fn set_pref(&mut self, name: &str, value: PrefValue) {
+            self.set.push((name.to_string(), value));
+        }
+
+        fn get_pref(&self, name: &str) -> Option<PrefValue> {
+            self.set.iter().rev().find(|(existing, _)| existing == name).map(|(_, value)| value.clone())
+        }
+    }
+
+    #[test]
+    fn a_foreground_tab_with_nothing_muted_plays() {
+        let guard = AudioGuard::default();
+        assert!(!guard.effective_mute(false, false));
+    }
+
+    #[test]
+    fn a_tabs_own_mute_silences_it_regardless_of_background_state() {
+        let guard = AudioGuard::default();
+        assert!(guard.effective_mute(true, false));
+    }
+
+    #[test]
+    fn global_mute_silences_every_tab() {
+        let mut guard = AudioGuard::default();
+        let mut sink = RecordingPrefs::default();
+        toggle_global_mute(&mut guard, &mut sink);
+        assert!(guard.effective_mute(false, false));
+        assert_eq!(sink.get_pref(PREF_GLOBAL_MUTE), Some(PrefValue::Bool(true)));
+    }
+
+    #[test]
+    fn toggling_global_mute_twice_returns_to_unmuted() {
+        let mut guard = AudioGuard::default();
+        let mut sink = RecordingPrefs::default();
+        toggle_global_mute(&mut guard, &mut sink);
+        toggle_global_mute(&mut guard, &mut sink);
+        assert!(!guard.is_globally_muted());
+        assert_eq!(sink.get_pref(PREF_GLOBAL_MUTE), Some(PrefValue::Bool(false)));
+    }
+
+    #[test]
+    fn background_tabs_are_muted_only_once_the_option_is_enabled() {
+        let mut guard = AudioGuard::default();
+        let mut sink = RecordingPrefs::default();
+        assert!(!guard.effective_mute(false, true));
+
+        set_mute_background_tabs(&mut guard, &mut sink, true);
+
+        assert!(guard.effective_mute(false, true));
+        assert!(!guard.effective_mute(false, false));
+        assert_eq!(sink.get_pref(PREF_MUTE_BACKGROUND_TABS), Some(PrefValue::Bool(true)));
+    }
+}
@@ -0,0 +1,87 @@
+use crynn_engine::Tab;
+
+use crate::audio::AudioGuard;
+
+/// Which speaker icon the tab strip shows for a tab, decided the same way
+/// [`crate::autoplay`]'s blocked-count is: a pure function over engine
+/// state so the egui-drawing code just matches on the result.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpeakerIcon {
+    /// Nothing playing; no icon shown.
+    Silent,
+    Playing,
+    Muted,
+}
+
+/// Decides `tab`'s speaker icon: [`SpeakerIcon::Silent`] if it has nothing
+/// playing, otherwise [`SpeakerIcon::Muted`] or [`SpeakerIcon::Playing`]
+/// depending on [`AudioGuard::effective_mute`] for whether it's the
+/// background tab.
+pub fn speaker_icon_for(tab: &Tab, guard: &AudioGuard, is_background: bool) -> SpeakerIcon {
+    if !tab.is_audio_playing() {
+        return SpeakerIcon::Silent;
+    }
+    if guard.effective_mute(tab.is_muted(), is_background) {
+        SpeakerIcon::Muted
+    } else {
+        SpeakerIcon::Playing
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crynn_engine::TabRegistry;
+
+    use super::*;
+
+    #[test]
+    fn a_silent_tab_shows_no_icon_even_if_muted() {
+        let mut registry = TabRegistry::new();
+        let id = registry.open("https://example.com", "Example");
+        let tab = registry.get_mut(id).unwrap();
+        tab.set_muted(true);
+        let guard = AudioGuard::default();
+        assert_eq!(speaker_icon_for(tab, &guard, false), SpeakerIcon::Silent);
+    }
+
+    #[test]
+    fn a_playing_tab_shows_playing_by_default() {
+        let mut registry = TabRegistry::new();
+        let id = registry.open("https://example.com", "Example");
+        let tab = registry.get_mut(id).unwrap();
+        tab.set_audio_playing(true);
+        let guard = AudioGuard::default();
+        assert_eq!(speaker_icon_for(tab, &guard, false), SpeakerIcon::Playing);
+    }
+
+    #[test]
+    fn a_playing_tab_that_is_muted_shows_muted() {
+        let mut registry = TabRegistry::new();
+        let id = registry.open("https://example.com", "Example");
+        let tab = registry.get_mut(id).unwrap();
+        tab.set_audio_playing(true);
+        tab.set_muted(true);
+        let guard = AudioGuard::default();
+        assert_eq!(speaker_icon_for(tab, &guard, false), SpeakerIcon::Muted);
+    }
+
+    #[test]
+    fn a_playing_background_tab_shows_muted_once_background_muting_is_on() {
+        let mut registry = TabRegistry::new();
+        let id = registry.open("https://example.com", "Example");
+        let tab = registry.get_mut(id).unwrap();
+        tab.set_audio_playing(true);
+        let mut guard = AudioGuard::default();
+        crate::audio::set_mute_background_tabs(&mut guard, &mut NoopPrefs, true);
+        assert_eq!(speaker_icon_for(tab, &guard, true), SpeakerIcon::Muted);
+        assert_eq!(speaker_icon_for(tab, &guard, false), SpeakerIcon::Playing);
+    }
+
+    struct NoopPrefs;
+    impl crynn_engine::EnginePrefs for NoopPrefs {
+        fn set_pref(&mut self, _name: &str, _value: crynn_engine::PrefValue) {}
+        fn get_pref(&self, _name: &str) -> Option<crynn_engine::PrefValue> {
+            None
+        }
+    }
+}
@@ -0,0 +1,113 @@
+use std::collections::HashMap;
+
+use crynn_engine::{autoplay_policy_pref, AutoplayPolicy, SitePrefStore, TabId};
+use crynn_permissions::{PermissionKind, PermissionStore};
+
+/// Whether a playback attempt on a page is allowed, decided against the
+/// engine's default [`AutoplayPolicy::BlockAudible`] and any per-site
+/// override granted through [`PermissionKind::Autoplay`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AutoplayDecision {
+    Allow,
+    Block,
+}
+
+/// Enforces the default block-audible-autoplay policy, overridable per
+/// site, and counts how many playback attempts it has blocked per tab
+/// for the status-bar indicator — the same shape as
+/// [`crynn_tracking_protection::TrackingGuard`].
+#[derive(Debug, Default)]
+pub struct AutoplayGuard {
+    blocked_counts: HashMap<TabId, u32>,
+}
+
+impl AutoplayGuard {
+    /// Decides whether `origin`'s playback attempt is allowed, bumping
+    /// `tab`'s blocked count when it isn't. `audible` mirrors Gecko's own
+    /// distinction: a muted autoplay is always allowed since nothing
+    /// forces a sound on the user that they didn't ask for.
+    pub fn decide(&mut self, tab: TabId, origin: &str, audible: bool, permissions: &PermissionStore) -> AutoplayDecision {
+        if !audible || permissions.is_allowed(origin, PermissionKind::Autoplay) {
+            return AutoplayDecision::Allow;
+        }
+        *self.blocked_counts.entry(tab).or_insert(0) += 1;
+        tracing::debug!(?tab, %origin, "blocked audible autoplay");
+        AutoplayDecision::Block
+    }
+
+    pub fn blocked_count(&self, tab: TabId) -> u32 {
+        self.blocked_counts.get(&tab).copied().unwrap_or(0)
+    }
+
+    /// Clears a tab's count, e.g. when it navigates to a new page.
+    pub fn reset_tab(&mut self, tab: TabId) {
+        self.blocked_counts.remove(&tab);
+    }
+}
+
+/// The status-bar indicator's one-click "Allow": grants
+/// [`PermissionKind::Autoplay`] for `origin`, pushes the matching
+/// override onto the engine through [`SitePrefStore`] so it takes effect
+/// without waiting for the next navigation, and clears `tab`'s blocked
+/// count since it describes attempts blocked under the policy that was
+/// just lifted.
+pub fn allow_origin(guard: &mut AutoplayGuard, site_prefs: &mut SitePrefStore, tab: TabId, origin: &str, permissions: &mut PermissionStore) {
+    permissions.grant(origin, PermissionKind::Autoplay);
+    site_prefs.set(origin, crynn_engine::PREF_AUTOPLAY_POLICY, autoplay_policy_pref(AutoplayPolicy::Allowed));
+    guard.reset_tab(tab);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn a_tab() -> TabId {
+        crynn_engine::TabRegistry::new().open("https://example.com", "Example")
+    }
+
+    #[test]
+    fn audible_autoplay_is_blocked_by_default() {
+        let mut guard = AutoplayGuard::default();
+        let tab = a_tab();
+        let permissions = PermissionStore::default();
+        assert_eq!(guard.decide(tab, "https://example.com", true, &permissions), AutoplayDecision::Block);
+        assert_eq!(guard.blocked_count(tab), 1);
+    }
+
+    #[test]
+    fn silent_autoplay_is_always_allowed() {
+        let mut guard = AutoplayGuard::default();
+        let tab = a_tab();
+        let permissions = PermissionStore::default();
+        assert_eq!(guard.decide(tab, "https://example.com", false, &permissions), AutoplayDecision::Allow);
+        assert_eq!(guard.blocked_count(tab), 0);
+    }
+
+    #[test]
+    fn a_site_with_autoplay_granted_is_allowed() {
+        let mut guard = AutoplayGuard::default();
+        let tab = a_tab();
+        let mut permissions = PermissionStore::default();
+        permissions.grant("https://example.com", PermissionKind::Autoplay);
+        assert_eq!(guard.decide(tab, "https://example.com", true, &permissions), AutoplayDecision::Allow);
+    }
+
+    #[test]
+    fn allow_origin_grants_permission_pushes_the_engine_override_and_resets_the_count() {
+        let mut guard = AutoplayGuard::default();
+        let mut site_prefs = SitePrefStore::new();
+        let mut permissions = PermissionStore::default();
+        let tab = a_tab();
+        guard.decide(tab, "https://example.com", true, &permissions);
+        assert_eq!(guard.blocked_count(tab), 1);
+
+        allow_origin(&mut guard, &mut site_prefs, tab, "https://example.com", &mut permissions);
+
+        assert!(permissions.is_allowed("https://example.com", PermissionKind::Autoplay));
+        assert_eq!(guard.blocked_count(tab), 0);
+        assert_eq!(
+            site_prefs.overrides_for("https://example.com"),
+            &[(crynn_engine::PREF_AUTOPLAY_POLICY.to_string(), autoplay_policy_pref(AutoplayPolicy::Allowed))]
+        );
+    }
+}
@@ -0,0 +1,42 @@
+use crynn_engine::{SitePrefStore, TabId};
+use crynn_permissions::PermissionStore;
+
+use crate::autoplay::{allow_origin, AutoplayGuard};
+
+/// Everything the "Allow autoplay on this site" button needs, bundled so
+/// [`AutoplayIndicator::ui`] doesn't take one parameter per subsystem.
+pub struct AutoplayContext<'a> {
+    pub guard: &'a mut AutoplayGuard,
+    pub site_prefs: &'a mut SitePrefStore,
+    pub permissions: &'a mut PermissionStore,
+    pub tab: TabId,
+    pub origin: &'a str,
+}
+
+/// The popover opened from the status bar's autoplay icon: how many
+/// audible autoplay attempts have been blocked on the active tab, and a
+/// one-click "Allow" that lifts the block for that site.
+#[derive(Default)]
+pub struct AutoplayIndicator {
+    open: bool,
+}
+
+impl AutoplayIndicator {
+    pub fn toggle(&mut self) {
+        self.open = !self.open;
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    pub fn ui(&mut self, ui: &mut egui::Ui, blocked_message: impl Into<String>, context: AutoplayContext<'_>) {
+        if !self.open {
+            return;
+        }
+        ui.label(blocked_message.into());
+        if ui.button("Allow autoplay on this site").clicked() {
+            allow_origin(context.guard, context.site_prefs, context.tab, context.origin, context.permissions);
+        }
+    }
+}
@@ -0,0 +1,154 @@
+use crate::url_utils::{classify, Classification};
+use crate::ShellState;
+
+/// Reads the OS clipboard's current text contents. No real OS
+/// integration exists in this build — a native platform layer backs
+/// this trait, the same contract-over-implementation split as
+/// [`crate::global_hotkeys::GlobalHotkeyPlatform`].
+pub trait ClipboardSource {
+    fn get_text(&mut self) -> Option<String>;
+}
+
+/// What copying a URL-shaped value to the clipboard offers: navigating
+/// straight to it, or, for text that doesn't look like a URL, searching
+/// for it instead — the same [`Classification`] the omnibox itself
+/// would make of typed input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PasteAction {
+    PasteAndGo(String),
+    PasteAndSearch(String),
+}
+
+/// Watches a [`ClipboardSource`] for new text, opt-in per
+/// [`crynn_config::PrivacyConfig::clipboard_url_detection_enabled`] since
+/// polling the clipboard on every copy is itself a privacy-sensitive
+/// default to have on. Only offers a suggestion once per distinct
+/// clipboard value — copying the same URL twice, or switching away and
+/// back to a tab with it still on the clipboard, doesn't re-surface the
+/// same suggestion on every poll.
+#[derive(Debug, Default)]
+pub struct ClipboardWatcher {
+    last_seen: Option<String>,
+}
+
+impl ClipboardWatcher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Polls `source` for a change since the last call. Always records
+    /// whatever it read, even when `enabled` is `false`, so flipping the
+    /// setting on doesn't immediately fire for whatever was already on
+    /// the clipboard before the user opted in.
+    pub fn check(&mut self, source: &mut dyn ClipboardSource, enabled: bool) -> Option<PasteAction> {
+        let current = source.get_text();
+        let changed = current != self.last_seen;
+        self.last_seen = current.clone();
+        if !enabled || !changed {
+            return None;
+        }
+        let text = current?;
+        if text.trim().is_empty() {
+            return None;
+        }
+        Some(match classify(&text) {
+            Classification::Url(url) => PasteAction::PasteAndGo(url),
+            Classification::Search(query) => PasteAction::PasteAndSearch(query),
+        })
+    }
+}
+
+/// Carries out a [`PasteAction`] the omnibox context menu or tab-strip
+/// right-click entry was clicked for: [`PasteAction::PasteAndGo`]
+/// navigates directly, [`PasteAction::PasteAndSearch`] opens the default
+/// search engine's results for it, the same search URL
+/// [`crate::context_menu`]'s "Search the web for selection" uses.
+pub fn execute(state: &mut ShellState, action: PasteAction) {
+    let url = match action {
+        PasteAction::PasteAndGo(url) => url,
+        PasteAction::PasteAndSearch(query) => crate::context_menu::web_search_url(&query),
+    };
+    crate::protocol_handlers::request_navigation(state, &url);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct FakeClipboard {
+        text: Option<String>,
+    }
+
+    impl ClipboardSource for FakeClipboard {
+        fn get_text(&mut self) -> Option<String> {
+            self.text.clone()
+        }
+    }
+
+    #[test]
+    fn an_empty_clipboard_offers_nothing() {
+        let mut watcher = ClipboardWatcher::new();
+        let mut clipboard = FakeClipboard { text: None };
+        assert_eq!(watcher.check(&mut clipboard, true), None);
+    }
+
+    #[test]
+    fn a_url_shaped_value_offers_paste_and_go() {
+        let mut watcher = ClipboardWatcher::new();
+        let mut clipboard = FakeClipboard { text: Some("example.com".to_string()) };
+        assert_eq!(watcher.check(&mut clipboard, true), Some(PasteAction::PasteAndGo("https://example.com".to_string())));
+    }
+
+    #[test]
+    fn a_non_url_value_offers_paste_and_search() {
+        let mut watcher = ClipboardWatcher::new();
+        let mut clipboard = FakeClipboard { text: Some("rust lang book".to_string()) };
+        assert_eq!(watcher.check(&mut clipboard, true), Some(PasteAction::PasteAndSearch("rust lang book".to_string())));
+    }
+
+    #[test]
+    fn disabled_watching_never_offers_a_suggestion() {
+        let mut watcher = ClipboardWatcher::new();
+        let mut clipboard = FakeClipboard { text: Some("example.com".to_string()) };
+        assert_eq!(watcher.check(&mut clipboard, false), None);
+    }
+
+    #[test]
+    fn the_same_value_is_only_offered_once() {
+        let mut watcher = ClipboardWatcher::new();
+        let mut clipboard = FakeClipboard { text: Some("example.com".to_string()) };
+        assert!(watcher.check(&mut clipboard, true).is_some());
+        assert_eq!(watcher.check(&mut clipboard, true), None);
+    }
+
+    #[test]
+    fn turning_detection_on_does_not_fire_for_a_value_already_on_the_clipboard() {
+        let mut watcher = ClipboardWatcher::new();
+        let mut clipboard = FakeClipboard { text: Some("example.com".to_string()) };
+        assert_eq!(watcher.check(&mut clipboard, false), None);
+        assert_eq!(watcher.check(&mut clipboard, true), None);
+    }
+
+    #[test]
+    fn a_new_value_after_a_seen_one_is_offered_again() {
+        let mut watcher = ClipboardWatcher::new();
+        let mut clipboard = FakeClipboard { text: Some("example.com".to_string()) };
+        assert!(watcher.check(&mut clipboard, true).is_some());
+        clipboard.text = Some("other.example".to_string());
+        assert!(watcher.check(&mut clipboard, true).is_some());
+    }
+
+    #[test]
+    fn execute_paste_and_go_navigates_directly() {
+        let mut state = ShellState::default();
+        execute(&mut state, PasteAction::PasteAndGo("https://example.com".to_string()));
+        assert!(state.engine.iter().any(|tab| tab.url() == "https://example.com"));
+    }
+
+    #[test]
+    fn execute_paste_and_search_opens_a_search_tab() {
+        let mut state = ShellState::default();
+        execute(&mut state, PasteAction::PasteAndSearch("rust lang".to_string()));
+        assert!(state.engine.iter().any(|tab| tab.url().contains("q=rust+lang")));
+    }
+}
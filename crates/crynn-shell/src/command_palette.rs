@@ -0,0 +1,248 @@
+use crynn_engine::TabId;
+
+use crate::actions::ActionRegistry;
+use crate::ShellState;
+
+/// One row shown in the palette: either a static registered action, or a
+/// dynamically generated entry such as "switch to tab" that depends on
+/// current browser state.
+enum PaletteEntry {
+    Action {
+        id: &'static str,
+        title: &'static str,
+        shortcut: Option<&'static str>,
+    },
+    SwitchTab {
+        id: TabId,
+        title: String,
+    },
+    SearchResult {
+        url: String,
+        title: String,
+        source: crynn_storage::SourceKind,
+    },
+}
+
+impl PaletteEntry {
+    fn label(&self) -> &str {
+        match self {
+            PaletteEntry::Action { title, .. } => title,
+            PaletteEntry::SwitchTab { title, .. } => title,
+            PaletteEntry::SearchResult { title, .. } => title,
+        }
+    }
+}
+
+/// Ctrl+Shift+P command palette: a fuzzy filter over every registered
+/// [`Action`](crate::actions::Action) plus tab-switch entries generated
+/// from the live tab list.
+#[derive(Default)]
+pub struct CommandPalette {
+    open: bool,
+    query: String,
+}
+
+impl CommandPalette {
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    pub fn toggle(&mut self) {
+        self.open = !self.open;
+        if self.open {
+            self.query.clear();
+        }
+    }
+
+    pub fn close(&mut self) {
+        self.open = false;
+    }
+
+    /// Builds the ranked, filtered list of entries for the current query.
+    /// Exposed separately from `ui` so it's unit-testable without egui.
+    /// `now` is the caller's clock reading, passed through to
+    /// [`crynn_storage::search_local_data`] for its frecency scoring.
+    fn matches(&self, registry: &ActionRegistry, state: &ShellState, now: u64) -> Vec<PaletteEntry> {
+        let mut entries: Vec<PaletteEntry> = registry
+            .iter()
+            .map(|a| PaletteEntry::Action {
+                id: a.id,
+                title: a.title,
+                shortcut: a.shortcut,
+            })
+            .collect();
+        entries.extend(state.engine.iter().map(|tab| PaletteEntry::SwitchTab {
+            id: tab.id(),
+            title: format!("Switch to Tab: {}", tab.title()),
+        }));
+
+        let mut scored: Vec<(i32, PaletteEntry)> = entries
+            .into_iter()
+            .filter_map(|entry| fuzzy_score(&self.query, entry.label()).map(|score| (score, entry)))
+            .collect();
+
+        if !self.query.is_empty() {
+            if let Some(storage) = &state.storage {
+                if let Ok(results) = crynn_storage::search_local_data(storage, &self.query, now) {
+                    scored.extend(results.into_iter().map(|result| {
+                        let score = (result.score * 10.0).round() as i32;
+                        (score, PaletteEntry::SearchResult { url: result.url, title: result.title, source: result.source })
+                    }));
+                }
+            }
+        }
+
+        scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+        scored.into_iter().map(|(_, entry)| entry).collect()
+    }
+
+    pub fn ui(&mut self, ctx: &egui::Context, registry: &ActionRegistry, state: &mut ShellState, now: u64) {
+        if !self.open {
+            return;
+        }
+
+        let mut open = self.open;
+        let mut switch_to: Option<TabId> = None;
+        let mut run_action: Option<&'static str> = None;
+        let mut navigate_to: Option<String> = None;
+
+        egui::Window::new("Command Palette")
+            .open(&mut open)
+            .collapsible(false)
+            .show(ctx, |ui| {
+                ui.text_edit_singleline(&mut self.query);
+                ui.separator();
+                for entry in self.matches(registry, state, now) {
+                    let clicked = match &entry {
+                        PaletteEntry::Action { title, shortcut, .. } => {
+                            let label = match shortcut {
+                                Some(s) => format!("{title}  ({s})"),
+                                None => title.to_string(),
+                            };
+                            ui.button(label).clicked()
+                        }
+                        PaletteEntry::SwitchTab { title, .. } => ui.button(title).clicked(),
+                        PaletteEntry::SearchResult { title, url, source } => ui.button(format!("{title}  ({url})  [{}]", source_label(*source))).clicked(),
+                    };
+                    if clicked {
+                        match entry {
+                            PaletteEntry::Action { id, .. } => run_action = Some(id),
+                            PaletteEntry::SwitchTab { id, .. } => switch_to = Some(id),
+                            PaletteEntry::SearchResult { url, .. } => navigate_to = Some(url),
+                        }
+                    }
+                }
+            });
+
+        if let Some(id) = run_action {
+            registry.run(id, state);
+        }
+        if switch_to.is_some() || navigate_to.is_some() {
+            // Which tab is "active", and navigating to a search result's
+            // URL, are tracked by the caller, not this crate; it observes
+            // the returned id/URL and acts on its own state.
+            self.close();
+        } else {
+            self.open = open;
+        }
+    }
+}
+
+/// Short label for a [`PaletteEntry::SearchResult`]'s
+/// [`crynn_storage::SourceKind`], shown alongside the result so the user
+/// can tell a bookmark match from a history one at a glance.
+fn source_label(source: crynn_storage::SourceKind) -> &'static str {
+    match source {
+        crynn_storage::SourceKind::Bookmark => "Bookmark",
+        crynn_storage::SourceKind::History => "History",
+        crynn_storage::SourceKind::ReadingList => "Reading List",
+        crynn_storage::SourceKind::CachedPageTitle => "Page",
+        crynn_storage::SourceKind::EmailSubject => "Email",
+    }
+}
+
+/// Case-insensitive subsequence match: every character of `query` must
+/// appear in `candidate` in order, possibly with gaps. Returns a score that
+/// rewards tighter, earlier matches, or `None` if `query` doesn't match at
+/// all (an empty query matches everything with a neutral score).
+fn fuzzy_score(query: &str, candidate: &str) -> Option<i32> {
+    if query.is_empty() {
+        return Some(0);
+    }
+    let query = query.to_lowercase();
+    let candidate_lower = candidate.to_lowercase();
+    let mut score = 0i32;
+    let mut last_match: Option<usize> = None;
+    let mut chars = candidate_lower.char_indices();
+
+    for qc in query.chars() {
+        loop {
+            let (idx, cc) = chars.next()?;
+            if cc == qc {
+                score += 10;
+                if let Some(last) = last_match {
+                    if idx == last + cc.len_utf8() {
+                        score += 5; // contiguous run
+                    }
+                }
+                last_match = Some(idx);
+                break;
+            }
+        }
+    }
+    Some(score)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_query_matches_everything() {
+        assert_eq!(fuzzy_score("", "anything"), Some(0));
+    }
+
+    #[test]
+    fn subsequence_matches_out_of_order_gaps() {
+        assert!(fuzzy_score("cmd", "Command Palette").is_some());
+        assert!(fuzzy_score("xyz", "Command Palette").is_none());
+    }
+
+    #[test]
+    fn contiguous_matches_score_higher_than_scattered() {
+        let tight = fuzzy_score("com", "Command Palette").unwrap();
+        let scattered = fuzzy_score("cde", "Command Palette").unwrap();
+        assert!(tight > scattered);
+    }
+
+    #[test]
+    fn a_query_matching_a_bookmark_surfaces_a_search_result_entry() {
+        let dir = std::env::temp_dir().join(format!("crynn-shell-command-palette-search-{}", std::process::id()));
+        let mut storage = crynn_storage::StorageManager::open(&dir, None).unwrap();
+        crynn_storage::save_bookmark(
+            &mut storage,
+            &crynn_storage::Bookmark { id: "1".to_string(), url: "https://tokio.rs".to_string(), title: "Tokio backpressure guide".to_string(), created_at: 0, keyword: None },
+        )
+        .unwrap();
+        let state = ShellState { storage: Some(storage), ..ShellState::default() };
+        let registry = ActionRegistry::default();
+        let palette = CommandPalette { query: "backpressure".to_string(), ..CommandPalette::default() };
+
+        let entries = palette.matches(&registry, &state, 0);
+
+        assert!(entries.iter().any(|e| matches!(e, PaletteEntry::SearchResult { url, .. } if url == "https://tokio.rs")));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn toggle_clears_query_on_open() {
+        let mut palette = CommandPalette {
+            open: false,
+            query: "leftover".to_string(),
+        };
+        palette.toggle();
+        assert!(palette.is_open());
+        assert!(palette.query.is_empty());
+    }
+}
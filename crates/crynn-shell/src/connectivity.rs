@@ -0,0 +1,64 @@
+use crynn_network::Connectivity;
+
+use crate::events::{Severity, ShellEvent};
+use crate::ShellState;
+
+/// Turns the outcome of probing `crynn_network`'s connectivity-check
+/// endpoint into a toast, same as [`crate::push::deliver_push_messages`]
+/// does for push messages. A captive portal gets an actionable toast
+/// pointing at `"network.open-captive-portal"`; being fully offline is
+/// left for the chrome's own connection indicator rather than a toast
+/// the user would see repeatedly every time a probe retries.
+pub fn report_connectivity(state: &mut ShellState, connectivity: Connectivity) {
+    match connectivity {
+        Connectivity::CaptivePortal { portal_url } => {
+            state.captive_portal_url = portal_url.clone().or_else(|| Some(crynn_network::CONNECTIVITY_CHECK_URL.to_string()));
+            state.events.post(
+                ShellEvent::new(Severity::Warning, "This network needs you to sign in before it will work.")
+                    .with_action("network.open-captive-portal"),
+            );
+        }
+        Connectivity::Online | Connectivity::Offline => {}
+    }
+}
+
+/// Opens the captive portal's login page in a new tab, then clears it so
+/// the action is a no-op until the next portal is detected.
+pub fn open_captive_portal(state: &mut ShellState) {
+    if let Some(url) = state.captive_portal_url.take() {
+        state.open_tab(url, "Network sign-in");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_captive_portal_posts_an_actionable_toast() {
+        let mut state = ShellState::default();
+        report_connectivity(&mut state, Connectivity::CaptivePortal { portal_url: Some("https://portal.example.com".to_string()) });
+
+        let events = state.events.drain();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].action, Some("network.open-captive-portal"));
+    }
+
+    #[test]
+    fn online_posts_nothing() {
+        let mut state = ShellState::default();
+        report_connectivity(&mut state, Connectivity::Online);
+        assert!(state.events.drain().is_empty());
+    }
+
+    #[test]
+    fn opening_the_portal_consumes_the_stored_url() {
+        let mut state = ShellState::default();
+        report_connectivity(&mut state, Connectivity::CaptivePortal { portal_url: Some("https://portal.example.com".to_string()) });
+
+        open_captive_portal(&mut state);
+
+        assert!(state.captive_portal_url.is_none());
+        assert_eq!(state.engine.len(), 1);
+    }
+}
@@ -0,0 +1,107 @@
+use std::collections::HashMap;
+
+use crynn_engine::{SitePrefStore, TabId, PREF_IMAGES_ENABLED, PREF_JAVASCRIPT_ENABLED};
+
+/// One piece of content a page asked for that this crate's policy
+/// blocked, for [`ContentSettingsGuard::blocked_kinds`]'s breakdown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlockedContent {
+    Images,
+    JavaScript,
+}
+
+/// Decides whether images and JavaScript are allowed on a page, combining
+/// `crynn_config::ContentConfig`'s global defaults with any per-site
+/// override in [`SitePrefStore`], and records which categories ended up
+/// blocked on the current page per tab — the same shape as
+/// [`crynn_tracking_protection::TrackingGuard`], for the status bar's
+/// indicator.
+#[derive(Debug, Default)]
+pub struct ContentSettingsGuard {
+    blocked: HashMap<TabId, Vec<BlockedContent>>,
+}
+
+impl ContentSettingsGuard {
+    /// Evaluates `origin`'s settings and records which categories are
+    /// blocked for `tab`, replacing whatever was recorded for it before —
+    /// called once per navigation, the same as
+    /// [`crynn_tracking_protection::TrackingGuard::reset_tab`] being
+    /// paired with a fresh page's requests.
+    pub fn evaluate_page(&mut self, tab: TabId, origin: &str, site_prefs: &SitePrefStore, images_enabled_by_default: bool) {
+        let mut blocked = Vec::new();
+        if !site_prefs.bool_pref(origin, PREF_IMAGES_ENABLED, images_enabled_by_default) {
+            blocked.push(BlockedContent::Images);
+        }
+        if !site_prefs.bool_pref(origin, PREF_JAVASCRIPT_ENABLED, true) {
+            blocked.push(BlockedContent::JavaScript);
+        }
+        self.blocked.insert(tab, blocked);
+    }
+
+    pub fn blocked_count(&self, tab: TabId) -> u32 {
+        self.blocked_kinds(tab).len() as u32
+    }
+
+    pub fn blocked_kinds(&self, tab: TabId) -> &[BlockedContent] {
+        self.blocked.get(&tab).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    pub fn reset_tab(&mut self, tab: TabId) {
+        self.blocked.remove(&tab);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crynn_engine::{PrefValue, TabRegistry};
+
+    fn a_tab() -> TabId {
+        TabRegistry::new().open("https://example.com", "Example")
+    }
+
+    #[test]
+    fn evaluate_page_reports_nothing_blocked_under_default_settings() {
+        let mut guard = ContentSettingsGuard::default();
+        let tab = a_tab();
+        guard.evaluate_page(tab, "https://example.com", &SitePrefStore::new(), true);
+        assert_eq!(guard.blocked_count(tab), 0);
+    }
+
+    #[test]
+    fn evaluate_page_blocks_images_when_the_global_default_is_off() {
+        let mut guard = ContentSettingsGuard::default();
+        let tab = a_tab();
+        guard.evaluate_page(tab, "https://example.com", &SitePrefStore::new(), false);
+        assert_eq!(guard.blocked_kinds(tab), &[BlockedContent::Images]);
+    }
+
+    #[test]
+    fn a_per_site_override_beats_the_global_default() {
+        let mut guard = ContentSettingsGuard::default();
+        let tab = a_tab();
+        let mut site_prefs = SitePrefStore::new();
+        site_prefs.set("https://example.com", PREF_IMAGES_ENABLED, PrefValue::Bool(true));
+        guard.evaluate_page(tab, "https://example.com", &site_prefs, false);
+        assert_eq!(guard.blocked_count(tab), 0);
+    }
+
+    #[test]
+    fn evaluate_page_counts_both_categories_when_both_are_blocked() {
+        let mut guard = ContentSettingsGuard::default();
+        let tab = a_tab();
+        let mut site_prefs = SitePrefStore::new();
+        site_prefs.set("https://example.com", PREF_JAVASCRIPT_ENABLED, PrefValue::Bool(false));
+        guard.evaluate_page(tab, "https://example.com", &site_prefs, false);
+        assert_eq!(guard.blocked_count(tab), 2);
+    }
+
+    #[test]
+    fn reset_tab_clears_its_recorded_blocks() {
+        let mut guard = ContentSettingsGuard::default();
+        let tab = a_tab();
+        guard.evaluate_page(tab, "https://example.com", &SitePrefStore::new(), false);
+        guard.reset_tab(tab);
+        assert_eq!(guard.blocked_count(tab), 0);
+    }
+}
@@ -0,0 +1,160 @@
+use crate::ShellState;
+
+/// Default search engine for "Search the web for selection", in the
+/// same `{}`-templated shape as [`crate::KeywordShortcuts`] and
+/// [`crynn_config::SearchConfig::suggest_url`].
+const DEFAULT_WEB_SEARCH_URL_TEMPLATE: &str = "https://search.crynn.example/search?q={}";
+
+/// Hands the selected text to the embedder's OS clipboard. A trait for
+/// the same reason [`crate::protocol_handlers::ExternalLauncher`] is:
+/// the decision to copy and the platform-specific mechanism stay
+/// decoupled.
+pub trait ClipboardSink {
+    fn set_text(&mut self, text: &str);
+}
+
+/// What the page's selection context menu offers. Empty selections
+/// offer nothing — there's nothing to copy, search for, or send.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContextMenuAction {
+    Copy,
+    SearchSelection,
+    SendToEmail,
+}
+
+/// The entries to show for the given selection, in menu order.
+pub fn available_actions(selected_text: Option<&str>) -> Vec<ContextMenuAction> {
+    if selected_text.unwrap_or("").trim().is_empty() {
+        return Vec::new();
+    }
+    vec![ContextMenuAction::Copy, ContextMenuAction::SearchSelection, ContextMenuAction::SendToEmail]
+}
+
+/// Performs `action` against the active tab's current selection. A no-op
+/// if the active tab has nothing selected, e.g. because the selection
+/// changed between the menu being opened and the entry being clicked.
+pub fn perform(state: &mut ShellState, action: ContextMenuAction, clipboard: &mut dyn ClipboardSink) {
+    let Some(selection) = state.active_tab().and_then(|tab| tab.selected_text()).map(str::to_string) else {
+        return;
+    };
+    match action {
+        ContextMenuAction::Copy => clipboard.set_text(&selection),
+        ContextMenuAction::SearchSelection => {
+            let url = web_search_url(&selection);
+            crate::protocol_handlers::request_navigation(state, &url);
+        }
+        ContextMenuAction::SendToEmail => {
+            let url = mailto_url(&selection);
+            crate::protocol_handlers::request_navigation(state, &url);
+        }
+    }
+}
+
+pub(crate) fn web_search_url(query: &str) -> String {
+    DEFAULT_WEB_SEARCH_URL_TEMPLATE.replace("{}", &percent_encode(query))
+}
+
+fn mailto_url(body: &str) -> String {
+    format!("mailto:?body={}", percent_encode(body))
+}
+
+/// Percent-encodes everything but unreserved characters, with `+` for
+/// spaces — the same scheme [`crate::protocol_handlers::MailtoLink`]'s
+/// `decode_query_value` reverses, and the one query strings conventionally
+/// use.
+fn percent_encode(input: &str) -> String {
+    let mut out = String::with_capacity(input.len());
+    for byte in input.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => out.push(byte as char),
+            b' ' => out.push('+'),
+            _ => out.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct RecordingClipboard {
+        copied: Vec<String>,
+    }
+
+    impl ClipboardSink for RecordingClipboard {
+        fn set_text(&mut self, text: &str) {
+            self.copied.push(text.to_string());
+        }
+    }
+
+    #[test]
+    fn no_selection_offers_no_actions() {
+        assert!(available_actions(None).is_empty());
+        assert!(available_actions(Some("   ")).is_empty());
+    }
+
+    #[test]
+    fn a_selection_offers_all_three_actions() {
+        assert_eq!(
+            available_actions(Some("hello")),
+            vec![ContextMenuAction::Copy, ContextMenuAction::SearchSelection, ContextMenuAction::SendToEmail]
+        );
+    }
+
+    #[test]
+    fn copy_sends_the_active_tabs_selection_to_the_clipboard() {
+        let mut state = ShellState::default();
+        let id = state.open_tab("https://example.com", "Example");
+        state.active_tab = Some(id);
+        state.active_tab_mut().unwrap().set_selected_text(Some("hello world".to_string()));
+
+        let mut clipboard = RecordingClipboard { copied: Vec::new() };
+        perform(&mut state, ContextMenuAction::Copy, &mut clipboard);
+
+        assert_eq!(clipboard.copied, vec!["hello world".to_string()]);
+    }
+
+    #[test]
+    fn copy_without_a_selection_is_a_no_op() {
+        let mut state = ShellState::default();
+        let id = state.open_tab("https://example.com", "Example");
+        state.active_tab = Some(id);
+
+        let mut clipboard = RecordingClipboard { copied: Vec::new() };
+        perform(&mut state, ContextMenuAction::Copy, &mut clipboard);
+
+        assert!(clipboard.copied.is_empty());
+    }
+
+    #[test]
+    fn search_selection_opens_a_search_tab() {
+        let mut state = ShellState::default();
+        let id = state.open_tab("https://example.com", "Example");
+        state.active_tab = Some(id);
+        state.active_tab_mut().unwrap().set_selected_text(Some("rust lang".to_string()));
+
+        let mut clipboard = RecordingClipboard { copied: Vec::new() };
+        perform(&mut state, ContextMenuAction::SearchSelection, &mut clipboard);
+
+        assert!(state.engine.iter().any(|tab| tab.url().contains("q=rust+lang")));
+    }
+
+    #[test]
+    fn send_to_email_opens_a_mailto_compose_tab() {
+        let mut state = ShellState::default();
+        let id = state.open_tab("https://example.com", "Example");
+        state.active_tab = Some(id);
+        state.active_tab_mut().unwrap().set_selected_text(Some("quoted text".to_string()));
+
+        let mut clipboard = RecordingClipboard { copied: Vec::new() };
+        perform(&mut state, ContextMenuAction::SendToEmail, &mut clipboard);
+
+        assert!(state.engine.iter().any(|tab| tab.url().contains("mailto:?body=quoted+text")));
+    }
+
+    #[test]
+    fn percent_encode_escapes_reserved_characters() {
+        assert_eq!(percent_encode("a b&c"), "a+b%26c");
+    }
+}
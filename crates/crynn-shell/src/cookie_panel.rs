@@ -0,0 +1,72 @@
+use crynn_cookies::CookieManager;
+use crynn_permissions::{PermissionKind, PermissionState, PermissionStore};
+
+/// The per-site cookie panel: every cookie the active site has set, how
+/// many times it's been set/read, when it was last touched, and whether
+/// it's first- or third-party — plus delete and block controls. Opened
+/// from the status bar the same way [`crate::shield_view::ShieldView`]
+/// and [`crate::page_info::PageInfo`] are.
+#[derive(Default)]
+pub struct CookiePanel {
+    open: bool,
+}
+
+impl CookiePanel {
+    pub fn toggle(&mut self) {
+        self.open = !self.open;
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    pub fn ui(&mut self, ui: &mut egui::Ui, origin: &str, cookies: &mut CookieManager, permissions: &mut PermissionStore) {
+        if !self.open {
+            return;
+        }
+        let blocked = permissions.state(origin, PermissionKind::Cookies) == PermissionState::Denied;
+        ui.horizontal(|ui| {
+            ui.label(if blocked { "Cookies are blocked for this site" } else { "Cookies are allowed for this site" });
+            let button_label = if blocked { "Allow" } else { "Block" };
+            if ui.button(button_label).clicked() {
+                if blocked {
+                    permissions.reset(origin, PermissionKind::Cookies);
+                } else {
+                    permissions.deny(origin, PermissionKind::Cookies);
+                }
+            }
+        });
+
+        let summaries = cookies.cookies_for_site(origin);
+        if summaries.is_empty() {
+            ui.label("No cookies recorded for this site.");
+            return;
+        }
+        egui::Grid::new("cookie-panel-grid").show(ui, |ui| {
+            ui.label("Name");
+            ui.label("Party");
+            ui.label("Set / Read");
+            ui.label("Last access");
+            ui.label("");
+            ui.end_row();
+
+            let mut to_delete = None;
+            for summary in &summaries {
+                ui.label(&summary.name);
+                ui.label(match summary.party {
+                    crynn_cookies::CookieParty::First => "First-party",
+                    crynn_cookies::CookieParty::Third => "Third-party",
+                });
+                ui.label(format!("{} / {}", summary.set_count, summary.read_count));
+                ui.label(summary.last_access.to_string());
+                if ui.button("Delete").clicked() {
+                    to_delete = Some(summary.name.clone());
+                }
+                ui.end_row();
+            }
+            if let Some(name) = to_delete {
+                cookies.delete(origin, &name);
+            }
+        });
+    }
+}
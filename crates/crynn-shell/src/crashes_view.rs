@@ -0,0 +1,72 @@
+use std::path::PathBuf;
+
+use crynn_crash::CrashStore;
+
+/// The `about:crashes` window: lists reports the panic hook has written
+/// to disk and offers a one-click export — the only window onto
+/// [`CrashStore`] a user gets, since nothing it records is ever
+/// uploaded.
+#[derive(Default)]
+pub struct CrashesView {
+    open: bool,
+    last_export: Option<Result<std::path::PathBuf, String>>,
+}
+
+impl CrashesView {
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    pub fn toggle(&mut self) {
+        self.open = !self.open;
+    }
+
+    pub fn ui(&mut self, ctx: &egui::Context, crashes: &CrashStore, export_path: &std::path::Path) {
+        if !self.open {
+            return;
+        }
+        let mut open = self.open;
+        egui::Window::new("about:crashes").open(&mut open).show(ctx, |ui| {
+            ui.label("Crash reports stay on this device unless you export them below.");
+            ui.separator();
+
+            if crashes.is_empty() {
+                ui.label("No crashes recorded.");
+            } else {
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    for report in crashes.reports() {
+                        ui.label(format!("#{} {} — {}", report.id, report.component, report.message));
+                    }
+                });
+            }
+
+            ui.separator();
+            if ui.button("Export as JSON").clicked() {
+                self.last_export = Some(
+                    crashes
+                        .export(export_path)
+                        .map(|_| export_path.to_path_buf())
+                        .map_err(|e| e.to_string()),
+                );
+            }
+            match &self.last_export {
+                Some(Ok(path)) => {
+                    ui.label(format!("Exported to {}", path.display()));
+                }
+                Some(Err(reason)) => {
+                    ui.colored_label(egui::Color32::RED, format!("Export failed: {reason}"));
+                }
+                None => {}
+            }
+        });
+        self.open = open;
+    }
+}
+
+pub(crate) fn default_store_path() -> Option<PathBuf> {
+    crate::zoom::default_store_path().map(|p| p.with_file_name("crashes.json"))
+}
+
+pub(crate) fn default_export_path() -> Option<PathBuf> {
+    crate::zoom::default_store_path().map(|p| p.with_file_name("crashes-export.json"))
+}
@@ -0,0 +1,94 @@
+use crynn_engine::{EnginePrefs, PrefValue, SitePrefStore, PREF_FORCE_DARK_MODE};
+
+/// Color-inversion override injected into a page when forced dark mode is
+/// active for it. A `prefers-color-scheme: dark` media query alone isn't
+/// enough — most pages don't have a dark stylesheet to switch to — so this
+/// also inverts the page's own colors back to something readable.
+pub const FORCE_DARK_STYLESHEET: &str = ":root { color-scheme: dark; }\n\
+html { filter: invert(1) hue-rotate(180deg); background: #fff; }\n\
+img, video, picture, canvas, svg, [style*=\"background-image\"] { filter: invert(1) hue-rotate(180deg); }\n";
+
+/// Whether forced dark mode is active for `origin`: the per-site override
+/// in [`SitePrefStore`] if the site has one (what the site settings
+/// panel's "Force dark" checkbox sets), otherwise `shell_theme_is_dark` —
+/// whether the shell chrome itself is currently drawn in a dark theme.
+/// Mirrors [`crate::fingerprinting::is_active`]'s same site-override-over-
+/// global-default shape.
+pub fn is_active(origin: &str, site_prefs: &SitePrefStore, shell_theme_is_dark: bool) -> bool {
+    site_prefs.bool_pref(origin, PREF_FORCE_DARK_MODE, shell_theme_is_dark)
+}
+
+/// The stylesheet to inject for `origin`, or `None` if forced dark mode
+/// isn't active there.
+pub fn stylesheet_for(origin: &str, site_prefs: &SitePrefStore, shell_theme_is_dark: bool) -> Option<&'static str> {
+    is_active(origin, site_prefs, shell_theme_is_dark).then_some(FORCE_DARK_STYLESHEET)
+}
+
+/// Pushes `origin`'s resolved forced-dark-mode decision onto `sink`, so the
+/// engine picks it up the same way it picks up every other [`SitePrefStore`]
+/// override when a tab navigates.
+pub fn apply(origin: &str, site_prefs: &SitePrefStore, shell_theme_is_dark: bool, sink: &mut dyn EnginePrefs) {
+    sink.set_pref(PREF_FORCE_DARK_MODE, PrefValue::Bool(is_active(origin, site_prefs, shell_theme_is_dark)));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default)]
+    struct RecordingPrefs {
+        set: Vec<(String, PrefValue)>,
+    }
+
+    impl EnginePrefs for RecordingPrefs {
+        fn set_pref(&mut self, name: &str, value: PrefValue) {
+            self.set.push((name.to_string(), value));
+        }
+
+        fn get_pref(&self, name: &str) -> Option<PrefValue> {
+            self.set.iter().rev().find(|(existing, _)| existing == name).map(|(_, value)| value.clone())
+        }
+    }
+
+    #[test]
+    fn inactive_by_default_in_a_light_shell_theme() {
+        let site_prefs = SitePrefStore::new();
+        assert!(!is_active("https://example.com", &site_prefs, false));
+    }
+
+    #[test]
+    fn active_automatically_when_the_shell_theme_is_dark() {
+        let site_prefs = SitePrefStore::new();
+        assert!(is_active("https://example.com", &site_prefs, true));
+    }
+
+    #[test]
+    fn a_per_site_override_wins_over_the_shell_theme() {
+        let mut site_prefs = SitePrefStore::new();
+        site_prefs.set("https://example.com", PREF_FORCE_DARK_MODE, PrefValue::Bool(false));
+        assert!(!is_active("https://example.com", &site_prefs, true));
+
+        site_prefs.set("https://other.example.com", PREF_FORCE_DARK_MODE, PrefValue::Bool(true));
+        assert!(is_active("https://other.example.com", &site_prefs, false));
+    }
+
+    #[test]
+    fn stylesheet_for_is_none_when_inactive() {
+        let site_prefs = SitePrefStore::new();
+        assert!(stylesheet_for("https://example.com", &site_prefs, false).is_none());
+    }
+
+    #[test]
+    fn stylesheet_for_returns_the_inversion_override_when_active() {
+        let site_prefs = SitePrefStore::new();
+        assert_eq!(stylesheet_for("https://example.com", &site_prefs, true), Some(FORCE_DARK_STYLESHEET));
+    }
+
+    #[test]
+    fn apply_pushes_the_resolved_decision_onto_the_sink() {
+        let site_prefs = SitePrefStore::new();
+        let mut sink = RecordingPrefs::default();
+        apply("https://example.com", &site_prefs, true, &mut sink);
+        assert_eq!(sink.get_pref(PREF_FORCE_DARK_MODE), Some(PrefValue::Bool(true)));
+    }
+}
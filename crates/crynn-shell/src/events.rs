@@ -0,0 +1,75 @@
+use std::collections::VecDeque;
+
+/// How prominently a [`ShellEvent`] should be surfaced to the user.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Info,
+    Success,
+    Warning,
+    Error,
+}
+
+/// Something a subsystem wants the shell to tell the user about: a
+/// download finishing, the VPN dropping, new mail arriving, and so on.
+/// Subsystems post to the [`EventBus`]; they don't render UI themselves.
+#[derive(Debug, Clone)]
+pub struct ShellEvent {
+    pub severity: Severity,
+    pub message: String,
+    /// Id of an [`Action`](crate::actions::Action) to run if the user
+    /// clicks the toast, e.g. jumping to the downloads view.
+    pub action: Option<&'static str>,
+}
+
+impl ShellEvent {
+    pub fn new(severity: Severity, message: impl Into<String>) -> Self {
+        Self {
+            severity,
+            message: message.into(),
+            action: None,
+        }
+    }
+
+    pub fn with_action(mut self, action: &'static str) -> Self {
+        self.action = Some(action);
+        self
+    }
+}
+
+/// FIFO queue any subsystem can post [`ShellEvent`]s to. The shell drains
+/// it once per frame (currently into the toast area); nothing else reads
+/// from it, so there is exactly one consumer at a time.
+#[derive(Debug, Default)]
+pub struct EventBus {
+    queue: VecDeque<ShellEvent>,
+}
+
+impl EventBus {
+    pub fn post(&mut self, event: ShellEvent) {
+        self.queue.push_back(event);
+    }
+
+    /// Removes and returns every event posted since the last drain, oldest
+    /// first.
+    pub fn drain(&mut self) -> Vec<ShellEvent> {
+        self.queue.drain(..).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn drain_returns_events_in_post_order_and_empties_the_queue() {
+        let mut bus = EventBus::default();
+        bus.post(ShellEvent::new(Severity::Info, "first"));
+        bus.post(ShellEvent::new(Severity::Error, "second"));
+
+        let drained = bus.drain();
+        assert_eq!(drained.len(), 2);
+        assert_eq!(drained[0].message, "first");
+        assert_eq!(drained[1].message, "second");
+        assert!(bus.drain().is_empty());
+    }
+}
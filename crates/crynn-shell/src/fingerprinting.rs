@@ -0,0 +1,94 @@
+use crynn_engine::{PrefValue, SitePrefStore, TabId, PREF_RESIST_FINGERPRINTING};
+use crynn_network::NetworkRequest;
+use crynn_tracking_protection::{TrackerCategory, TrackingGuard, Verdict};
+
+/// Whether resist-fingerprinting is active for `origin`: the per-site
+/// override in [`SitePrefStore`] if the site has one (what the site
+/// settings panel's "Resist fingerprinting" checkbox sets), otherwise
+/// `globally_enabled` — the user's `privacy.resist_fingerprinting`
+/// setting.
+pub fn is_active(origin: &str, site_prefs: &SitePrefStore, globally_enabled: bool) -> bool {
+    site_prefs
+        .overrides_for(origin)
+        .iter()
+        .find(|(name, _)| name == PREF_RESIST_FINGERPRINTING)
+        .map(|(_, value)| matches!(value, PrefValue::Bool(true)))
+        .unwrap_or(globally_enabled)
+}
+
+/// Standardizes `request`'s `User-Agent`/`Accept-Language` when resist-
+/// fingerprinting is active for `origin`, coordinating the network
+/// layer with the engine-pref and content-blocker layers below.
+pub fn prepare_request(request: NetworkRequest, origin: &str, site_prefs: &SitePrefStore, globally_enabled: bool) -> NetworkRequest {
+    if is_active(origin, site_prefs, globally_enabled) {
+        crynn_network::apply_resistant_headers(request)
+    } else {
+        request
+    }
+}
+
+/// Evaluates `url` against tracking protection, forcing a block on
+/// [`TrackerCategory::Fingerprinting`] when resist-fingerprinting is
+/// active for `origin` — even under a tracking-protection strictness
+/// level that wouldn't otherwise block it.
+pub fn evaluate(tracking: &mut TrackingGuard, tab: TabId, url: &str, origin: &str, site_prefs: &SitePrefStore, globally_enabled: bool) -> Verdict {
+    if is_active(origin, site_prefs, globally_enabled) {
+        tracking.evaluate_with_override(tab, url, &[TrackerCategory::Fingerprinting])
+    } else {
+        tracking.evaluate(tab, url)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crynn_engine::TabRegistry;
+    use crynn_tracking_protection::StrictnessLevel;
+
+    fn a_tab() -> TabId {
+        TabRegistry::new().open("https://example.com", "Example")
+    }
+
+    #[test]
+    fn inactive_by_default_and_without_a_site_override() {
+        let site_prefs = SitePrefStore::new();
+        assert!(!is_active("https://example.com", &site_prefs, false));
+        assert!(is_active("https://example.com", &site_prefs, true));
+    }
+
+    #[test]
+    fn a_site_override_wins_over_the_global_setting() {
+        let mut site_prefs = SitePrefStore::new();
+        site_prefs.set("https://example.com", PREF_RESIST_FINGERPRINTING, PrefValue::Bool(true));
+        assert!(is_active("https://example.com", &site_prefs, false));
+
+        site_prefs.set("https://other.example.com", PREF_RESIST_FINGERPRINTING, PrefValue::Bool(false));
+        assert!(!is_active("https://other.example.com", &site_prefs, true));
+    }
+
+    #[test]
+    fn prepare_request_only_standardizes_headers_when_active() {
+        let site_prefs = SitePrefStore::new();
+        let request = NetworkRequest::new("GET", "https://example.com").with_header("User-Agent", "Crynn/custom-build");
+
+        let unchanged = prepare_request(request, "https://example.com", &site_prefs, false);
+        assert!(unchanged.headers.contains(&("User-Agent".to_string(), "Crynn/custom-build".to_string())));
+
+        let request = NetworkRequest::new("GET", "https://example.com").with_header("User-Agent", "Crynn/custom-build");
+        let standardized = prepare_request(request, "https://example.com", &site_prefs, true);
+        assert!(standardized.headers.contains(&("User-Agent".to_string(), crynn_network::RESISTANT_USER_AGENT.to_string())));
+    }
+
+    #[test]
+    fn evaluate_forces_a_block_on_fingerprinting_scripts_when_active() {
+        let mut tracking = TrackingGuard::new(StrictnessLevel::Custom(vec![]));
+        let site_prefs = SitePrefStore::new();
+        let tab = a_tab();
+
+        assert_eq!(evaluate(&mut tracking, tab, "https://fpjs.io/agent.js", "https://example.com", &site_prefs, false), Verdict::Allowed);
+        assert_eq!(
+            evaluate(&mut tracking, tab, "https://fpjs.io/agent.js", "https://example.com", &site_prefs, true),
+            Verdict::Blocked(TrackerCategory::Fingerprinting)
+        );
+    }
+}
@@ -0,0 +1,70 @@
+use crynn_error::StorageError;
+
+use crate::ShellState;
+
+/// Purges every trace of `origin` (`scheme://host[:port]`, the same
+/// format [`crynn_permissions::origin_of`] and [`crynn_engine::SitePrefStore`]
+/// already key by) this shell knows how to hold: history, cookies,
+/// permissions, and the per-site engine-pref overrides that stand in for
+/// Gecko-side storage until a real engine is linked in. Called when the
+/// user removes a site from the history panel, so "forget this site"
+/// means what it says rather than leaving cookies and permission grants
+/// behind.
+///
+/// There is no favicon store in this build to purge — a site removed
+/// this way simply has none to begin with, the same way `about:downloads`
+/// reports `crynn-downloads` as not wired in rather than pretending it
+/// has nothing to show.
+pub fn forget_site(state: &mut ShellState, origin: &str) -> Result<(), StorageError> {
+    let host = host_of(origin);
+
+    if let Some(storage) = &mut state.storage {
+        crynn_storage::delete_visits_for_domain(storage, &host)?;
+    }
+    state.cookies.clear_site(&host);
+    state.permissions.forget_origin(origin);
+    state.site_prefs.clear_origin(origin);
+    Ok(())
+}
+
+/// Strips the scheme from `origin`, leaving `host[:port]` for the
+/// host-keyed stores ([`crynn_cookies::CookieManager`],
+/// `crynn_storage`'s history), which don't carry a scheme the way
+/// [`crynn_permissions::PermissionStore`] and [`crynn_engine::SitePrefStore`]
+/// do.
+fn host_of(origin: &str) -> String {
+    origin.split_once("://").map(|(_, host)| host).unwrap_or(origin).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crynn_permissions::PermissionKind;
+
+    #[test]
+    fn forget_site_clears_cookies_permissions_and_site_prefs() {
+        let mut state = ShellState::default();
+        state.cookies.set("example.com", "session", "abc", crynn_cookies::CookieParty::First, 1);
+        state.permissions.grant("https://example.com", PermissionKind::Push);
+        state.site_prefs.set("https://example.com", crynn_engine::PREF_JAVASCRIPT_ENABLED, crynn_engine::PrefValue::Bool(false));
+
+        forget_site(&mut state, "https://example.com").unwrap();
+
+        assert_eq!(state.cookies.count_for_site("example.com"), 0);
+        assert!(!state.permissions.is_allowed("https://example.com", PermissionKind::Push));
+        assert!(state.site_prefs.overrides_for("https://example.com").is_empty());
+    }
+
+    #[test]
+    fn forget_site_leaves_other_sites_untouched() {
+        let mut state = ShellState::default();
+        state.cookies.set("example.com", "session", "abc", crynn_cookies::CookieParty::First, 1);
+        state.cookies.set("other.com", "session", "def", crynn_cookies::CookieParty::First, 1);
+        state.permissions.grant("https://other.com", PermissionKind::Push);
+
+        forget_site(&mut state, "https://example.com").unwrap();
+
+        assert_eq!(state.cookies.count_for_site("other.com"), 1);
+        assert!(state.permissions.is_allowed("https://other.com", PermissionKind::Push));
+    }
+}
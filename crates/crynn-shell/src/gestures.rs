@@ -0,0 +1,357 @@
+use std::collections::HashMap;
+
+use crate::actions::ActionRegistry;
+use crate::ShellState;
+
+/// A mouse button a gesture can be held or clicked with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MouseButton {
+    Left,
+    Right,
+}
+
+fn parse_button(token: &str) -> Option<MouseButton> {
+    match token {
+        "left" => Some(MouseButton::Left),
+        "right" => Some(MouseButton::Right),
+        _ => None,
+    }
+}
+
+/// A point in the same units `ElementRect` uses — CSS pixels relative to
+/// the viewport.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Point {
+    pub x: f32,
+    pub y: f32,
+}
+
+/// One stroke direction recognized as movement accumulates past
+/// [`STROKE_THRESHOLD`] along an axis — the vocabulary a drag gesture is
+/// built from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+fn parse_direction(token: &str) -> Option<Direction> {
+    match token {
+        "up" => Some(Direction::Up),
+        "down" => Some(Direction::Down),
+        "left" => Some(Direction::Left),
+        "right" => Some(Direction::Right),
+        _ => None,
+    }
+}
+
+/// What a completed mouse gesture matched: a right-button drag through a
+/// sequence of [`Direction`] strokes, or a rocker gesture — one button
+/// held, the other clicked while it's still down.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Gesture {
+    Drag(Vec<Direction>),
+    Rocker { held: MouseButton, pressed: MouseButton },
+}
+
+/// Parses one gesture spec from [`crynn_config::GesturesConfig::bindings`]:
+/// `"drag:left,down"` for a drag through that sequence of strokes, or
+/// `"rocker:left,right"` for holding the first button and clicking the
+/// second. `None` for anything malformed — a typo'd binding is dropped
+/// rather than panicking the shell on a bad config file.
+pub fn parse_gesture(spec: &str) -> Option<Gesture> {
+    let (kind, rest) = spec.split_once(':')?;
+    match kind {
+        "drag" => {
+            let strokes: Option<Vec<Direction>> = rest.split(',').map(parse_direction).collect();
+            let strokes = strokes?;
+            if strokes.is_empty() {
+                None
+            } else {
+                Some(Gesture::Drag(strokes))
+            }
+        }
+        "rocker" => {
+            let (held, pressed) = rest.split_once(',')?;
+            Some(Gesture::Rocker { held: parse_button(held)?, pressed: parse_button(pressed)? })
+        }
+        _ => None,
+    }
+}
+
+/// Every configured gesture, parsed from
+/// [`crynn_config::GesturesConfig::bindings`] into the action id it
+/// should run — the same action-id-keyed shape
+/// [`crate::global_hotkeys::GlobalHotkeyRegistry`] builds from
+/// `crynn_config::GlobalHotkeysConfig::bindings`.
+#[derive(Default)]
+pub struct GestureMap {
+    bindings: HashMap<Gesture, String>,
+}
+
+impl GestureMap {
+    /// Parses `bindings` (action id -> gesture spec), skipping any spec
+    /// [`parse_gesture`] can't make sense of.
+    pub fn from_bindings(bindings: &HashMap<String, String>) -> Self {
+        let mut parsed = HashMap::new();
+        for (action_id, spec) in bindings {
+            if let Some(gesture) = parse_gesture(spec) {
+                parsed.insert(gesture, action_id.clone());
+            }
+        }
+        Self { bindings: parsed }
+    }
+
+    pub fn action_for(&self, gesture: &Gesture) -> Option<&str> {
+        self.bindings.get(gesture).map(String::as_str)
+    }
+}
+
+/// Minimum movement, in logical pixels, a drag must cover along an axis
+/// before it counts as one [`Direction`] stroke — short jitter under this
+/// doesn't register, the same debouncing a trackpad swipe needs to
+/// ignore a shaky hand.
+const STROKE_THRESHOLD: f32 = 40.0;
+
+struct Drag {
+    button: MouseButton,
+    last: Point,
+    strokes: Vec<Direction>,
+    trail: Vec<Point>,
+}
+
+/// Recognizes gestures from raw mouse button/move events: a drag through
+/// a sequence of [`Direction`] strokes, or a rocker gesture. Pure
+/// input-layer logic with no egui dependency, the same split
+/// [`crate::dark_mode`] keeps between deciding and applying — tested
+/// directly against synthetic points rather than a real pointer device.
+#[derive(Default)]
+pub struct GestureRecognizer {
+    drag: Option<Drag>,
+}
+
+impl GestureRecognizer {
+    pub fn is_dragging(&self) -> bool {
+        self.drag.is_some()
+    }
+
+    /// The trail of points recorded so far this drag, for a gesture
+    /// overlay to draw — empty when nothing's in progress.
+    pub fn trail(&self) -> &[Point] {
+        self.drag.as_ref().map(|d| d.trail.as_slice()).unwrap_or(&[])
+    }
+
+    /// A button was pressed at `at`. Starts a new drag if nothing was
+    /// held, or resolves a [`Gesture::Rocker`] immediately if a
+    /// different button was already held — a rocker gesture fires on
+    /// the second button's press, not its release.
+    pub fn button_down(&mut self, button: MouseButton, at: Point) -> Option<Gesture> {
+        if let Some(drag) = &self.drag {
+            if drag.button == button {
+                return None;
+            }
+            let rocker = Gesture::Rocker { held: drag.button, pressed: button };
+            self.drag = None;
+            return Some(rocker);
+        }
+        self.drag = Some(Drag { button, last: at, strokes: Vec::new(), trail: vec![at] });
+        None
+    }
+
+    /// Records pointer movement during an active drag, appending a new
+    /// [`Direction`] stroke whenever movement along the dominant axis
+    /// passes [`STROKE_THRESHOLD`] since the last one. A no-op when no
+    /// drag is in progress.
+    pub fn mouse_move(&mut self, at: Point) {
+        let Some(drag) = &mut self.drag else {
+            return;
+        };
+        drag.trail.push(at);
+        let dx = at.x - drag.last.x;
+        let dy = at.y - drag.last.y;
+        let (direction, magnitude) =
+            if dx.abs() > dy.abs() { (if dx > 0.0 { Direction::Right } else { Direction::Left }, dx.abs()) } else { (if dy > 0.0 { Direction::Down } else { Direction::Up }, dy.abs()) };
+        if magnitude < STROKE_THRESHOLD {
+            return;
+        }
+        if drag.strokes.last() != Some(&direction) {
+            drag.strokes.push(direction);
+        }
+        drag.last = at;
+    }
+
+    /// `button` was released. Ends the drag it started and returns the
+    /// [`Gesture::Drag`] it recorded, if any strokes were long enough to
+    /// register. Releasing a button that isn't the one holding the
+    /// current drag (or releasing with nothing held) does nothing.
+    pub fn button_up(&mut self, button: MouseButton) -> Option<Gesture> {
+        let drag = self.drag.as_ref()?;
+        if drag.button != button {
+            return None;
+        }
+        let strokes = drag.strokes.clone();
+        self.drag = None;
+        if strokes.is_empty() {
+            None
+        } else {
+            Some(Gesture::Drag(strokes))
+        }
+    }
+
+    /// Abandons whatever drag is in progress without resolving a
+    /// gesture, e.g. when focus leaves the window mid-drag.
+    pub fn cancel(&mut self) {
+        self.drag = None;
+    }
+}
+
+/// Feeds `ctx`'s pointer events through `recognizer` and runs whatever
+/// action `map` has bound to the gesture they resolve to, if any — the
+/// shell's once-per-frame poll, the same point [`crate::zoom::handle_ctrl_scroll`]
+/// hooks Ctrl+scroll in at.
+pub fn handle_mouse_gestures(ctx: &egui::Context, recognizer: &mut GestureRecognizer, map: &GestureMap, registry: &ActionRegistry, state: &mut ShellState) {
+    let resolved = ctx.input(|input| {
+        let mut resolved = None;
+        for event in &input.events {
+            match event {
+                egui::Event::PointerButton { pos, button, pressed, .. } => {
+                    let Some(button) = egui_button(*button) else { continue };
+                    let point = Point { x: pos.x, y: pos.y };
+                    let gesture = if *pressed { recognizer.button_down(button, point) } else { recognizer.button_up(button) };
+                    if gesture.is_some() {
+                        resolved = gesture;
+                    }
+                }
+                egui::Event::PointerMoved(pos) => recognizer.mouse_move(Point { x: pos.x, y: pos.y }),
+                egui::Event::PointerGone => recognizer.cancel(),
+                _ => {}
+            }
+        }
+        resolved
+    });
+
+    let Some(gesture) = resolved else {
+        return;
+    };
+    if let Some(action_id) = map.action_for(&gesture) {
+        registry.run(action_id, state);
+    }
+}
+
+fn egui_button(button: egui::PointerButton) -> Option<MouseButton> {
+    match button {
+        egui::PointerButton::Primary => Some(MouseButton::Left),
+        egui::PointerButton::Secondary => Some(MouseButton::Right),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point(x: f32, y: f32) -> Point {
+        Point { x, y }
+    }
+
+    #[test]
+    fn parse_gesture_reads_a_drag_spec() {
+        assert_eq!(parse_gesture("drag:left,down"), Some(Gesture::Drag(vec![Direction::Left, Direction::Down])));
+    }
+
+    #[test]
+    fn parse_gesture_reads_a_rocker_spec() {
+        assert_eq!(parse_gesture("rocker:left,right"), Some(Gesture::Rocker { held: MouseButton::Left, pressed: MouseButton::Right }));
+    }
+
+    #[test]
+    fn parse_gesture_rejects_an_unknown_kind() {
+        assert_eq!(parse_gesture("swipe:left"), None);
+    }
+
+    #[test]
+    fn parse_gesture_rejects_an_unknown_direction() {
+        assert_eq!(parse_gesture("drag:sideways"), None);
+    }
+
+    #[test]
+    fn gesture_map_skips_unparsable_bindings_rather_than_panicking() {
+        let bindings = [("navigation.back".to_string(), "drag:left".to_string()), ("navigation.broken".to_string(), "nonsense".to_string())]
+            .into_iter()
+            .collect();
+        let map = GestureMap::from_bindings(&bindings);
+        assert_eq!(map.action_for(&Gesture::Drag(vec![Direction::Left])), Some("navigation.back"));
+    }
+
+    #[test]
+    fn a_short_drag_under_the_threshold_resolves_to_no_gesture() {
+        let mut recognizer = GestureRecognizer::default();
+        recognizer.button_down(MouseButton::Right, point(0.0, 0.0));
+        recognizer.mouse_move(point(5.0, 0.0));
+        assert_eq!(recognizer.button_up(MouseButton::Right), None);
+    }
+
+    #[test]
+    fn a_drag_past_the_threshold_resolves_to_its_direction() {
+        let mut recognizer = GestureRecognizer::default();
+        recognizer.button_down(MouseButton::Right, point(0.0, 0.0));
+        recognizer.mouse_move(point(-60.0, 0.0));
+        assert_eq!(recognizer.button_up(MouseButton::Right), Some(Gesture::Drag(vec![Direction::Left])));
+    }
+
+    #[test]
+    fn a_drag_records_each_distinct_direction_change_as_its_own_stroke() {
+        let mut recognizer = GestureRecognizer::default();
+        recognizer.button_down(MouseButton::Right, point(0.0, 0.0));
+        recognizer.mouse_move(point(0.0, 60.0));
+        recognizer.mouse_move(point(60.0, 60.0));
+        assert_eq!(recognizer.button_up(MouseButton::Right), Some(Gesture::Drag(vec![Direction::Down, Direction::Right])));
+    }
+
+    #[test]
+    fn continued_movement_in_the_same_direction_does_not_duplicate_the_stroke() {
+        let mut recognizer = GestureRecognizer::default();
+        recognizer.button_down(MouseButton::Right, point(0.0, 0.0));
+        recognizer.mouse_move(point(60.0, 0.0));
+        recognizer.mouse_move(point(120.0, 0.0));
+        assert_eq!(recognizer.button_up(MouseButton::Right), Some(Gesture::Drag(vec![Direction::Right])));
+    }
+
+    #[test]
+    fn pressing_a_second_button_mid_drag_resolves_a_rocker_gesture() {
+        let mut recognizer = GestureRecognizer::default();
+        recognizer.button_down(MouseButton::Right, point(0.0, 0.0));
+        let gesture = recognizer.button_down(MouseButton::Left, point(0.0, 0.0));
+        assert_eq!(gesture, Some(Gesture::Rocker { held: MouseButton::Right, pressed: MouseButton::Left }));
+        assert!(!recognizer.is_dragging());
+    }
+
+    #[test]
+    fn releasing_a_button_that_is_not_the_held_one_does_nothing() {
+        let mut recognizer = GestureRecognizer::default();
+        recognizer.button_down(MouseButton::Right, point(0.0, 0.0));
+        assert_eq!(recognizer.button_up(MouseButton::Left), None);
+        assert!(recognizer.is_dragging());
+    }
+
+    #[test]
+    fn cancel_abandons_the_drag_without_resolving_a_gesture() {
+        let mut recognizer = GestureRecognizer::default();
+        recognizer.button_down(MouseButton::Right, point(0.0, 0.0));
+        recognizer.mouse_move(point(60.0, 0.0));
+        recognizer.cancel();
+        assert!(!recognizer.is_dragging());
+        assert!(recognizer.trail().is_empty());
+    }
+
+    #[test]
+    fn trail_records_every_move_during_the_drag() {
+        let mut recognizer = GestureRecognizer::default();
+        recognizer.button_down(MouseButton::Right, point(0.0, 0.0));
+        recognizer.mouse_move(point(10.0, 0.0));
+        recognizer.mouse_move(point(20.0, 0.0));
+        assert_eq!(recognizer.trail().len(), 3);
+    }
+}
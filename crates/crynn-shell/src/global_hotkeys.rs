@@ -0,0 +1,249 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use crate::actions::ActionRegistry;
+use crate::ShellState;
+
+/// Registers/unregisters OS-level global shortcuts — ones that fire even
+/// when no Crynn window has focus. No real OS integration exists in this
+/// build (that's the Tauri global-shortcut API's job, or a native
+/// platform layer for the egui shell); a real implementation backs this
+/// trait, the same contract-over-implementation split as
+/// [`crynn_engine::DevtoolsLauncher`].
+pub trait GlobalHotkeyPlatform {
+    /// Registers `chord` (e.g. `"Ctrl+Shift+V"`) with the OS. `false`
+    /// means the OS already has that chord claimed by another
+    /// application — a conflict this crate can't see or resolve, unlike
+    /// [`conflicts`]'s in-app check.
+    fn register(&mut self, chord: &str) -> bool;
+
+    fn unregister(&mut self, chord: &str);
+}
+
+/// Two or more action ids configured to the same global chord.
+/// [`GlobalHotkeyRegistry::apply`] doesn't register any action in a
+/// conflicting group rather than picking one arbitrarily — the settings
+/// UI surfaces this list so the user fixes it instead.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HotkeyConflict {
+    pub chord: String,
+    pub action_ids: Vec<String>,
+}
+
+/// Finds every chord bound to more than one action in `bindings` (action
+/// id -> chord, the shape [`crynn_config::GlobalHotkeysConfig::bindings`]
+/// stores).
+pub fn conflicts(bindings: &HashMap<String, String>) -> Vec<HotkeyConflict> {
+    let mut by_chord: HashMap<&str, Vec<&str>> = HashMap::new();
+    for (action_id, chord) in bindings {
+        by_chord.entry(chord.as_str()).or_default().push(action_id.as_str());
+    }
+    let mut found: Vec<HotkeyConflict> = by_chord
+        .into_iter()
+        .filter(|(_, action_ids)| action_ids.len() > 1)
+        .map(|(chord, mut action_ids)| {
+            action_ids.sort_unstable();
+            HotkeyConflict { chord: chord.to_string(), action_ids: action_ids.into_iter().map(str::to_string).collect() }
+        })
+        .collect();
+    found.sort_by(|a, b| a.chord.cmp(&b.chord));
+    found
+}
+
+/// The shell-side half of the global-hotkey bridge:
+/// [`GlobalHotkeyRegistry::apply`] pushes the configured bindings out to
+/// the platform layer (skipping conflicting chords),
+/// [`GlobalHotkeyRegistry::on_triggered`] is what a real platform binding
+/// calls into when the OS reports a chord was pressed, and
+/// [`GlobalHotkeyRegistry::drain_triggered_actions`] is what the shell
+/// polls once per frame to run them — the same receive/drain split
+/// [`crynn_engine::PushInbox`] uses for push messages arriving from
+/// outside the process.
+#[derive(Default)]
+pub struct GlobalHotkeyRegistry {
+    /// Chord currently registered with the platform, per action id.
+    active: HashMap<String, String>,
+    chord_to_action: HashMap<String, String>,
+    triggered: VecDeque<String>,
+}
+
+impl GlobalHotkeyRegistry {
+    /// Replaces whatever's currently registered with `bindings`,
+    /// unregistering every active chord first so a removed or rebound
+    /// action doesn't leave a stale OS-level registration behind.
+    /// Chords in a [`conflicts`] group are left unregistered entirely;
+    /// everything else is handed to `platform`, which may still reject
+    /// an individual chord the OS has already claimed elsewhere.
+    pub fn apply(&mut self, bindings: &HashMap<String, String>, platform: &mut dyn GlobalHotkeyPlatform) -> Vec<HotkeyConflict> {
+        for chord in self.active.values() {
+            platform.unregister(chord);
+        }
+        self.active.clear();
+        self.chord_to_action.clear();
+
+        let found = conflicts(bindings);
+        let conflicting_action_ids: HashSet<&str> = found.iter().flat_map(|c| c.action_ids.iter().map(String::as_str)).collect();
+
+        for (action_id, chord) in bindings {
+            if conflicting_action_ids.contains(action_id.as_str()) {
+                continue;
+            }
+            if platform.register(chord) {
+                self.active.insert(action_id.clone(), chord.clone());
+                self.chord_to_action.insert(chord.clone(), action_id.clone());
+            }
+        }
+        found
+    }
+
+    /// What a real [`GlobalHotkeyPlatform`] binding calls into when the
+    /// OS reports `chord` was pressed. A chord with nothing registered
+    /// for it (unregistered since, or never ours) is ignored.
+    pub fn on_triggered(&mut self, chord: &str) {
+        if let Some(action_id) = self.chord_to_action.get(chord) {
+            self.triggered.push_back(action_id.clone());
+        }
+    }
+
+    /// Removes and returns every action id triggered since the last
+    /// drain, oldest first.
+    pub fn drain_triggered_actions(&mut self) -> Vec<String> {
+        self.triggered.drain(..).collect()
+    }
+}
+
+/// Runs every action id [`GlobalHotkeyRegistry::drain_triggered_actions`]
+/// returns through `registry` — the shell's once-per-frame poll,
+/// mirroring [`crate::push::deliver_push_messages`]'s own poll-and-apply
+/// shape for the engine's push inbox.
+pub fn dispatch_triggered_hotkeys(state: &mut ShellState, hotkeys: &mut GlobalHotkeyRegistry, registry: &ActionRegistry) {
+    for action_id in hotkeys.drain_triggered_actions() {
+        registry.run(&action_id, state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default)]
+    struct FakePlatform {
+        registered: Vec<String>,
+        unregistered: Vec<String>,
+        rejects: HashSet<String>,
+    }
+
+    impl GlobalHotkeyPlatform for FakePlatform {
+        fn register(&mut self, chord: &str) -> bool {
+            self.registered.push(chord.to_string());
+            !self.rejects.contains(chord)
+        }
+
+        fn unregister(&mut self, chord: &str) {
+            self.unregistered.push(chord.to_string());
+        }
+    }
+
+    fn bindings(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs.iter().map(|(action_id, chord)| (action_id.to_string(), chord.to_string())).collect()
+    }
+
+    #[test]
+    fn two_actions_sharing_a_chord_are_reported_as_a_conflict() {
+        let bindings = bindings(&[("vpn.toggle", "Ctrl+Alt+V"), ("window.new-private", "Ctrl+Alt+V")]);
+        let found = conflicts(&bindings);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].chord, "Ctrl+Alt+V");
+        assert_eq!(found[0].action_ids, vec!["vpn.toggle".to_string(), "window.new-private".to_string()]);
+    }
+
+    #[test]
+    fn distinct_chords_report_no_conflicts() {
+        let bindings = bindings(&[("vpn.toggle", "Ctrl+Alt+V"), ("window.new-private", "Ctrl+Shift+N")]);
+        assert!(conflicts(&bindings).is_empty());
+    }
+
+    #[test]
+    fn apply_registers_every_non_conflicting_binding() {
+        let bindings = bindings(&[("vpn.toggle", "Ctrl+Alt+V"), ("window.new-private", "Ctrl+Shift+N")]);
+        let mut registry = GlobalHotkeyRegistry::default();
+        let mut platform = FakePlatform::default();
+
+        let found = registry.apply(&bindings, &mut platform);
+
+        assert!(found.is_empty());
+        assert_eq!(platform.registered.len(), 2);
+        assert_eq!(registry.active.len(), 2);
+    }
+
+    #[test]
+    fn apply_skips_registering_either_side_of_a_conflict() {
+        let bindings = bindings(&[("vpn.toggle", "Ctrl+Alt+V"), ("window.new-private", "Ctrl+Alt+V")]);
+        let mut registry = GlobalHotkeyRegistry::default();
+        let mut platform = FakePlatform::default();
+
+        let found = registry.apply(&bindings, &mut platform);
+
+        assert_eq!(found.len(), 1);
+        assert!(platform.registered.is_empty());
+        assert!(registry.active.is_empty());
+    }
+
+    #[test]
+    fn a_platform_rejected_chord_is_not_recorded_as_active() {
+        let bindings = bindings(&[("vpn.toggle", "Ctrl+Alt+V")]);
+        let mut registry = GlobalHotkeyRegistry::default();
+        let mut platform = FakePlatform { rejects: ["Ctrl+Alt+V".to_string()].into_iter().collect(), ..Default::default() };
+
+        registry.apply(&bindings, &mut platform);
+
+        assert!(registry.active.is_empty());
+    }
+
+    #[test]
+    fn reapplying_unregisters_chords_that_were_active_before() {
+        let mut registry = GlobalHotkeyRegistry::default();
+        let mut platform = FakePlatform::default();
+
+        registry.apply(&bindings(&[("vpn.toggle", "Ctrl+Alt+V")]), &mut platform);
+        registry.apply(&bindings(&[("vpn.toggle", "Ctrl+Alt+Q")]), &mut platform);
+
+        assert_eq!(platform.unregistered, vec!["Ctrl+Alt+V".to_string()]);
+        assert_eq!(registry.active.get("vpn.toggle"), Some(&"Ctrl+Alt+Q".to_string()));
+    }
+
+    #[test]
+    fn a_triggered_chord_queues_its_action_for_the_next_drain() {
+        let mut registry = GlobalHotkeyRegistry::default();
+        let mut platform = FakePlatform::default();
+        registry.apply(&bindings(&[("vpn.toggle", "Ctrl+Alt+V")]), &mut platform);
+
+        registry.on_triggered("Ctrl+Alt+V");
+
+        assert_eq!(registry.drain_triggered_actions(), vec!["vpn.toggle".to_string()]);
+        assert!(registry.drain_triggered_actions().is_empty());
+    }
+
+    #[test]
+    fn an_unregistered_chord_being_triggered_is_ignored() {
+        let mut registry = GlobalHotkeyRegistry::default();
+        registry.on_triggered("Ctrl+Alt+V");
+        assert!(registry.drain_triggered_actions().is_empty());
+    }
+
+    #[test]
+    fn dispatch_triggered_hotkeys_runs_the_queued_action() {
+        let mut state = ShellState::default();
+        let id = state.open_tab("https://example.com", "Example");
+        state.active_tab = Some(id);
+        let zoom_before = state.active_tab().unwrap().zoom();
+
+        let mut registry = GlobalHotkeyRegistry::default();
+        let mut platform = FakePlatform::default();
+        registry.apply(&bindings(&[("view.zoom-in", "Ctrl+Alt+V")]), &mut platform);
+        registry.on_triggered("Ctrl+Alt+V");
+
+        dispatch_triggered_hotkeys(&mut state, &mut registry, &ActionRegistry::builtin());
+
+        assert!(state.active_tab().unwrap().zoom() > zoom_before);
+    }
+}
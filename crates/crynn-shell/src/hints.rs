@@ -0,0 +1,314 @@
+use crynn_engine::TabId;
+
+use crate::ShellState;
+
+/// An on-page rect a real overlay would position a hint's letter label
+/// at, in the units whatever injected the scan script reports — CSS
+/// pixels relative to the viewport, the same frame `getBoundingClientRect`
+/// uses.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ElementRect {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+/// One clickable element an injected DOM-walking script found: its
+/// `href` and the rect to draw its hint label over.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HintTarget {
+    pub href: String,
+    pub rect: ElementRect,
+}
+
+/// Runs the hint-mode scan script against a tab and returns every
+/// clickable element it found, in document order. A trait for the same
+/// reason [`crate::translate::PageTranslator`] is: this crate has no
+/// real script-evaluation binding of its own, so [`HintOverlay::activate`]
+/// can be exercised in tests against a fixed result instead.
+pub trait LinkScanner {
+    fn scan_links(&mut self, tab: TabId) -> Vec<HintTarget>;
+}
+
+/// Whether typing a hint's label should navigate the active tab to its
+/// link or open it in a new one — Vimium's plain `f` vs `F` hint modes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HintActivation {
+    Click,
+    OpenInNewTab,
+}
+
+/// Home-row letters hints are labeled from, the same set Vimium's default
+/// alphabet starts with. Digits and punctuation are avoided so a typed
+/// hint never collides with an address-bar shortcut on the same keys.
+const HINT_ALPHABET: &[u8] = b"asdfghjkl";
+
+/// Assigns every one of `count` targets a fixed-length label built from
+/// [`HINT_ALPHABET`] — long enough that `count` labels fit, and all the
+/// same length so no label is ever a prefix of another (typing `a` can
+/// never be ambiguous between selecting it outright and continuing on to
+/// `aa`).
+fn assign_labels(count: usize) -> Vec<String> {
+    if count == 0 {
+        return Vec::new();
+    }
+    let base = HINT_ALPHABET.len();
+    let mut length = 1;
+    while base.pow(length as u32) < count {
+        length += 1;
+    }
+    (0..count)
+        .map(|i| {
+            let mut digits = vec![0u8; length];
+            let mut n = i;
+            for slot in (0..length).rev() {
+                digits[slot] = HINT_ALPHABET[n % base];
+                n /= base;
+            }
+            String::from_utf8(digits).expect("HINT_ALPHABET is ASCII")
+        })
+        .collect()
+}
+
+/// What typing a character into an active hint session produced.
+#[derive(Debug, Clone, PartialEq)]
+pub enum HintOutcome {
+    /// Nothing activated yet; the session is still narrowing on more
+    /// than one candidate.
+    Pending,
+    /// The typed label uniquely identified a target, which hint mode
+    /// should now be activated for.
+    Activated { href: String, activation: HintActivation },
+    /// No remaining candidate's label starts with what's been typed; the
+    /// keypress was ignored rather than appended.
+    NoMatch,
+}
+
+struct Session {
+    activation: HintActivation,
+    labeled: Vec<(String, HintTarget)>,
+    typed: String,
+}
+
+/// A hint-mode session: scans the active tab's links through a
+/// [`LinkScanner`] when activated, labels each one, and narrows on the
+/// label typed so far one character at a time until it uniquely
+/// identifies a target to activate.
+#[derive(Default)]
+pub struct HintOverlay {
+    session: Option<Session>,
+}
+
+impl HintOverlay {
+    pub fn is_active(&self) -> bool {
+        self.session.is_some()
+    }
+
+    /// Scans `tab` via `scanner` and starts a session over the results,
+    /// labeled for `activation`. Scanning up a page with nothing
+    /// clickable leaves no session active, the same as there being
+    /// nothing to show a command palette match for.
+    pub fn activate(&mut self, tab: TabId, activation: HintActivation, scanner: &mut dyn LinkScanner) {
+        let targets = scanner.scan_links(tab);
+        let labels = assign_labels(targets.len());
+        self.session = Some(Session {
+            activation,
+            labeled: labels.into_iter().zip(targets).collect(),
+            typed: String::new(),
+        });
+    }
+
+    /// Ends the session without activating anything, e.g. on Escape.
+    pub fn cancel(&mut self) {
+        self.session = None;
+    }
+
+    /// The labels and rects to draw right now, limited to targets whose
+    /// label still starts with what's been typed.
+    pub fn visible_hints(&self) -> Vec<(&str, &ElementRect)> {
+        let Some(session) = &self.session else {
+            return Vec::new();
+        };
+        session
+            .labeled
+            .iter()
+            .filter(|(label, _)| label.starts_with(&session.typed))
+            .map(|(label, target)| (label.as_str(), &target.rect))
+            .collect()
+    }
+
+    /// Appends `c` to the label typed so far. Ends the session and
+    /// reports [`HintOutcome::Activated`] once that narrows to exactly
+    /// one target; leaves it untouched and reports [`HintOutcome::NoMatch`]
+    /// if `c` would rule out every remaining candidate.
+    pub fn type_char(&mut self, c: char) -> HintOutcome {
+        let Some(session) = &mut self.session else {
+            return HintOutcome::NoMatch;
+        };
+        let mut typed = session.typed.clone();
+        typed.push(c.to_ascii_lowercase());
+
+        let remaining: Vec<&(String, HintTarget)> = session.labeled.iter().filter(|(label, _)| label.starts_with(&typed)).collect();
+        if remaining.is_empty() {
+            return HintOutcome::NoMatch;
+        }
+
+        if remaining.len() == 1 && remaining[0].0 == typed {
+            let href = remaining[0].1.href.clone();
+            let activation = session.activation;
+            self.session = None;
+            return HintOutcome::Activated { href, activation };
+        }
+
+        session.typed = typed;
+        HintOutcome::Pending
+    }
+}
+
+/// Carries out a hint activation: opens `href` in a new background tab
+/// for [`HintActivation::OpenInNewTab`], or navigates the active tab to
+/// it for [`HintActivation::Click`]. This shell has no in-place tab
+/// navigation yet — a `Tab`'s URL is set once, at `open_tab` time, the
+/// same gap [`crate::popups::PopupRoute::Window`] already records rather
+/// than papers over — so "click" closes the active tab and reopens
+/// `href` in its place instead of truly reusing it.
+pub fn activate_link(state: &mut ShellState, href: &str, activation: HintActivation) {
+    if activation == HintActivation::Click {
+        if let Some(id) = state.active_tab().map(|tab| tab.id()) {
+            state.engine.close(id);
+        }
+        let id = state.open_tab(href.to_string(), href.to_string());
+        state.active_tab = Some(id);
+    } else {
+        state.open_tab(href.to_string(), href.to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect() -> ElementRect {
+        ElementRect { x: 0.0, y: 0.0, width: 10.0, height: 10.0 }
+    }
+
+    fn target(href: &str) -> HintTarget {
+        HintTarget { href: href.to_string(), rect: rect() }
+    }
+
+    struct FixedScanner {
+        targets: Vec<HintTarget>,
+    }
+
+    impl LinkScanner for FixedScanner {
+        fn scan_links(&mut self, _tab: TabId) -> Vec<HintTarget> {
+            self.targets.clone()
+        }
+    }
+
+    fn a_tab() -> TabId {
+        crynn_engine::TabRegistry::new().open("https://example.com", "Example")
+    }
+
+    #[test]
+    fn assign_labels_uses_single_letters_while_they_fit_the_alphabet() {
+        assert_eq!(assign_labels(3), vec!["a", "s", "d"]);
+    }
+
+    #[test]
+    fn assign_labels_pads_to_a_fixed_length_once_the_alphabet_is_exhausted() {
+        let labels = assign_labels(HINT_ALPHABET.len() + 1);
+        assert!(labels.iter().all(|l| l.len() == 2));
+        assert_eq!(labels.len(), labels.iter().collect::<std::collections::HashSet<_>>().len());
+    }
+
+    #[test]
+    fn activating_with_no_links_leaves_no_session() {
+        let mut overlay = HintOverlay::default();
+        let mut scanner = FixedScanner { targets: Vec::new() };
+        overlay.activate(a_tab(), HintActivation::Click, &mut scanner);
+        assert!(overlay.is_active());
+        assert!(overlay.visible_hints().is_empty());
+    }
+
+    #[test]
+    fn activating_labels_every_scanned_target() {
+        let mut overlay = HintOverlay::default();
+        let mut scanner = FixedScanner { targets: vec![target("https://a.example"), target("https://b.example")] };
+        overlay.activate(a_tab(), HintActivation::Click, &mut scanner);
+        assert_eq!(overlay.visible_hints().len(), 2);
+    }
+
+    #[test]
+    fn typing_narrows_visible_hints_to_matching_labels() {
+        let mut overlay = HintOverlay::default();
+        let mut scanner = FixedScanner { targets: (0..HINT_ALPHABET.len() + 1).map(|i| target(&format!("https://{i}.example"))).collect() };
+        overlay.activate(a_tab(), HintActivation::Click, &mut scanner);
+        let before = overlay.visible_hints().len();
+
+        let outcome = overlay.type_char('a');
+
+        assert_eq!(outcome, HintOutcome::Pending);
+        assert!(overlay.visible_hints().len() < before);
+        assert!(overlay.visible_hints().iter().all(|(label, _)| label.starts_with('a')));
+    }
+
+    #[test]
+    fn typing_a_unique_labels_full_sequence_activates_it_and_ends_the_session() {
+        let mut overlay = HintOverlay::default();
+        let mut scanner = FixedScanner { targets: vec![target("https://a.example"), target("https://b.example")] };
+        overlay.activate(a_tab(), HintActivation::OpenInNewTab, &mut scanner);
+
+        let outcome = overlay.type_char('a');
+
+        assert_eq!(outcome, HintOutcome::Activated { href: "https://a.example".to_string(), activation: HintActivation::OpenInNewTab });
+        assert!(!overlay.is_active());
+    }
+
+    #[test]
+    fn typing_a_character_matching_nothing_is_reported_as_no_match() {
+        let mut overlay = HintOverlay::default();
+        let mut scanner = FixedScanner { targets: vec![target("https://a.example")] };
+        overlay.activate(a_tab(), HintActivation::Click, &mut scanner);
+
+        assert_eq!(overlay.type_char('z'), HintOutcome::NoMatch);
+        assert!(overlay.is_active());
+    }
+
+    #[test]
+    fn cancel_ends_the_session_without_activating_anything() {
+        let mut overlay = HintOverlay::default();
+        let mut scanner = FixedScanner { targets: vec![target("https://a.example")] };
+        overlay.activate(a_tab(), HintActivation::Click, &mut scanner);
+
+        overlay.cancel();
+
+        assert!(!overlay.is_active());
+        assert!(overlay.visible_hints().is_empty());
+    }
+
+    #[test]
+    fn click_activation_replaces_the_active_tab() {
+        let mut state = ShellState::default();
+        let id = state.open_tab("https://example.com", "Example");
+        state.active_tab = Some(id);
+
+        activate_link(&mut state, "https://a.example", HintActivation::Click);
+
+        assert!(state.engine.get(id).is_none());
+        assert_eq!(state.active_tab().unwrap().url(), "https://a.example");
+    }
+
+    #[test]
+    fn open_in_new_tab_activation_keeps_the_active_tab_unchanged() {
+        let mut state = ShellState::default();
+        let id = state.open_tab("https://example.com", "Example");
+        state.active_tab = Some(id);
+
+        activate_link(&mut state, "https://a.example", HintActivation::OpenInNewTab);
+
+        assert_eq!(state.active_tab, Some(id));
+        assert!(state.engine.iter().any(|tab| tab.url() == "https://a.example"));
+    }
+}
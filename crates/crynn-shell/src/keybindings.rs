@@ -0,0 +1,98 @@
+use std::collections::HashMap;
+
+use egui::{Key, Modifiers};
+
+use crate::actions::ActionRegistry;
+use crate::ShellState;
+
+/// A key chord, e.g. Ctrl+Shift+P.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Chord {
+    pub key: Key,
+    pub ctrl: bool,
+    pub shift: bool,
+    pub alt: bool,
+}
+
+impl Chord {
+    pub fn new(key: Key, modifiers: Modifiers) -> Self {
+        Self {
+            key,
+            ctrl: modifiers.ctrl,
+            shift: modifiers.shift,
+            alt: modifiers.alt,
+        }
+    }
+}
+
+/// Maps key chords to [`Action`](crate::actions::Action) ids and dispatches
+/// them through the same [`ActionRegistry`] the command palette lists from,
+/// so a shortcut and its palette entry can never drift apart.
+#[derive(Default)]
+pub struct KeybindingDispatcher {
+    bindings: HashMap<Chord, &'static str>,
+}
+
+impl KeybindingDispatcher {
+    pub fn bind(&mut self, chord: Chord, action_id: &'static str) {
+        self.bindings.insert(chord, action_id);
+    }
+
+    /// The default bindings for the actions registered by
+    /// [`ActionRegistry::builtin`].
+    pub fn builtin() -> Self {
+        let mut dispatcher = Self::default();
+        dispatcher.bind(
+            Chord::new(Key::P, Modifiers::CTRL | Modifiers::SHIFT),
+            "view.toggle-command-palette",
+        );
+        dispatcher.bind(
+            Chord::new(Key::M, Modifiers::CTRL | Modifiers::SHIFT),
+            "view.toggle-task-manager",
+        );
+        dispatcher.bind(Chord::new(Key::Plus, Modifiers::CTRL), "view.zoom-in");
+        dispatcher.bind(Chord::new(Key::Minus, Modifiers::CTRL), "view.zoom-out");
+        dispatcher.bind(Chord::new(Key::Num0, Modifiers::CTRL), "view.zoom-reset");
+        dispatcher
+    }
+
+    /// Checks `ctx`'s input for any bound chord and runs the matching
+    /// action. Returns the id of the action that ran, if any.
+    pub fn dispatch(
+        &self,
+        ctx: &egui::Context,
+        registry: &ActionRegistry,
+        state: &mut ShellState,
+    ) -> Option<&'static str> {
+        let pressed = ctx.input(|i| {
+            self.bindings.keys().find(|chord| {
+                i.key_pressed(chord.key)
+                    && i.modifiers.ctrl == chord.ctrl
+                    && i.modifiers.shift == chord.shift
+                    && i.modifiers.alt == chord.alt
+            }).copied()
+        });
+
+        let chord = pressed?;
+        let action_id = self.bindings.get(&chord).copied()?;
+        registry.run(action_id, state);
+        Some(action_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builtin_bindings_resolve_to_registered_actions() {
+        let dispatcher = KeybindingDispatcher::builtin();
+        let registry = ActionRegistry::builtin();
+        for action_id in dispatcher.bindings.values() {
+            assert!(
+                registry.get(action_id).is_some(),
+                "binding for {action_id} has no matching action"
+            );
+        }
+    }
+}
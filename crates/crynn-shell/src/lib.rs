@@ -0,0 +1,104 @@
+//! The egui-based chrome around the engine: status bar, tab strip, and the
+//! built-in `about:` style views.
+
+pub mod about;
+pub mod actions;
+pub mod audio;
+pub mod audio_view;
+pub mod autoplay;
+pub mod autoplay_view;
+pub mod clipboard;
+pub mod command_palette;
+pub mod connectivity;
+pub mod content_settings;
+pub mod context_menu;
+pub mod cookie_panel;
+pub mod crashes_view;
+pub mod dark_mode;
+pub mod events;
+pub mod fingerprinting;
+pub mod forget_site;
+pub mod gestures;
+pub mod global_hotkeys;
+pub mod hints;
+pub mod keybindings;
+pub mod logs_view;
+pub mod network_panel;
+pub mod onboarding;
+pub mod page_info;
+pub mod performance_view;
+pub mod popups;
+pub mod popups_view;
+pub mod protocol_handlers;
+pub mod push;
+pub mod qr;
+pub mod resource_governor;
+pub mod retention;
+pub mod session;
+pub mod shell_state;
+pub mod shield_view;
+pub mod site_settings_panel;
+pub mod startup;
+pub mod status_bar;
+pub mod sync;
+pub mod system_conditions;
+pub mod tab_groups;
+pub mod tab_proxy_menu;
+pub mod telemetry_view;
+pub mod toasts;
+pub mod translate;
+pub mod url_utils;
+pub mod view_state;
+pub mod webapps;
+pub mod zoom;
+
+pub use about::{AboutPage, AboutPages};
+pub use actions::ActionRegistry;
+pub use audio::{set_mute_background_tabs, toggle_global_mute, AudioGuard};
+pub use audio_view::{speaker_icon_for, SpeakerIcon};
+pub use autoplay::{allow_origin, AutoplayDecision, AutoplayGuard};
+pub use autoplay_view::{AutoplayContext, AutoplayIndicator};
+pub use clipboard::{execute as execute_paste_action, ClipboardSource, ClipboardWatcher, PasteAction};
+pub use command_palette::CommandPalette;
+pub use connectivity::{open_captive_portal, report_connectivity};
+pub use content_settings::{BlockedContent, ContentSettingsGuard};
+pub use context_menu::{available_actions, perform, ClipboardSink, ContextMenuAction};
+pub use cookie_panel::CookiePanel;
+pub use crashes_view::CrashesView;
+pub use dark_mode::{is_active as dark_mode_is_active, stylesheet_for as dark_mode_stylesheet_for, FORCE_DARK_STYLESHEET};
+pub use events::{EventBus, ShellEvent, Severity};
+pub use fingerprinting::{
+    evaluate as evaluate_resist_fingerprinting, is_active as resist_fingerprinting_is_active, prepare_request as prepare_resistant_request,
+};
+pub use forget_site::forget_site;
+pub use gestures::{handle_mouse_gestures, parse_gesture, Direction, Gesture, GestureMap, GestureRecognizer, MouseButton};
+pub use global_hotkeys::{conflicts as global_hotkey_conflicts, dispatch_triggered_hotkeys, GlobalHotkeyPlatform, GlobalHotkeyRegistry, HotkeyConflict};
+pub use hints::{activate_link, ElementRect, HintActivation, HintOutcome, HintOverlay, HintTarget, LinkScanner};
+pub use keybindings::KeybindingDispatcher;
+pub use logs_view::LogsView;
+pub use network_panel::NetworkPanel;
+pub use onboarding::{BrowserMigration, DetectedBrowser, ImportKind, ImportSummary, OnboardingStep, OnboardingWizard};
+pub use page_info::PageInfo;
+pub use performance_view::PerformanceView;
+pub use popups::{allow_origin as allow_popups_origin, open_popup, BlockedPopup, PopupDecision, PopupGuard, PopupRoute};
+pub use popups_view::{PopupContext, PopupIndicator};
+pub use protocol_handlers::{cancel_external_launch, confirm_external_launch, request_navigation, ProtocolHandlerRegistry};
+pub use push::{deliver_push_messages, disable_push_for_active_tab};
+pub use qr::{scan_to_navigate, share_page_as_qr, QrEncoder, QrMatrix, QrScanner};
+pub use resource_governor::{enforce_resource_limits, kill_offending_tab, whitelist_offending_site};
+pub use retention::{apply_cleanup, CleanupPreview, MaintenanceScheduler};
+pub use shell_state::ShellState;
+pub use shield_view::ShieldView;
+pub use site_settings_panel::SiteSettingsPanel;
+pub use startup::{EmailHelperHandle, InitCost, LazySubsystem, VpnHelperHandle};
+pub use status_bar::StatusBar;
+pub use sync::sync_now;
+pub use system_conditions::{SystemConditions, SystemConditionsMonitor};
+pub use tab_groups::TabGroupsView;
+pub use tab_proxy_menu::TabProxyMenu;
+pub use telemetry_view::TelemetryView;
+pub use toasts::ToastCenter;
+pub use translate::{always_translate, set_always_translate, translate_page, PageTranslator};
+pub use url_utils::{fetch_remote_suggestions, suggest, KeywordShortcuts, OmniboxSuggestion, SuggestionKind};
+pub use view_state::{restore_view_state, save_active_tab_view_state};
+pub use webapps::{AppLauncherInstaller, WebAppManager};
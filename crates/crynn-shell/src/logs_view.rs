@@ -0,0 +1,38 @@
+/// The `about:logs` window: shows recent lines from the shared ring buffer
+/// [`crynn_log`] feeds from every subscriber it's attached to.
+#[derive(Default)]
+pub struct LogsView {
+    open: bool,
+}
+
+impl LogsView {
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    pub fn toggle(&mut self) {
+        self.open = !self.open;
+    }
+
+    pub fn ui(&mut self, ctx: &egui::Context, log_handle: Option<&crynn_log::LogHandle>) {
+        if !self.open {
+            return;
+        }
+        let mut open = self.open;
+        egui::Window::new("about:logs").open(&mut open).show(ctx, |ui| {
+            match log_handle {
+                Some(handle) => {
+                    egui::ScrollArea::vertical().show(ui, |ui| {
+                        for line in handle.recent_lines() {
+                            ui.label(line);
+                        }
+                    });
+                }
+                None => {
+                    ui.label("Logging has not been initialized for this session.");
+                }
+            }
+        });
+        self.open = open;
+    }
+}
@@ -0,0 +1,71 @@
+use crynn_engine::TabId;
+use crynn_network::NetworkManager;
+
+/// The `about:network`-adjacent "Network" window: a lightweight devtools
+/// network panel listing every request logged for the active tab, read
+/// straight from [`NetworkManager::request_log`]. Shows nothing until
+/// the network subsystem has actually been initialized; see
+/// [`crate::startup::LazySubsystem::peek`].
+#[derive(Default)]
+pub struct NetworkPanel {
+    open: bool,
+}
+
+impl NetworkPanel {
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    pub fn toggle(&mut self) {
+        self.open = !self.open;
+    }
+
+    pub fn ui(&mut self, ctx: &egui::Context, network: Option<&NetworkManager>, active_tab: Option<TabId>) {
+        if !self.open {
+            return;
+        }
+        let mut open = self.open;
+        egui::Window::new("Network").open(&mut open).resizable(true).show(ctx, |ui| {
+            match (network, active_tab) {
+                (Some(network), Some(tab_id)) => {
+                    let key = tab_id.to_string();
+                    egui::ScrollArea::vertical().show(ui, |ui| {
+                        for entry in network.request_log(&key) {
+                            ui.horizontal(|ui| {
+                                ui.label(&entry.method);
+                                ui.label(&entry.url);
+                                ui.label(entry.status.map(|s| s.to_string()).unwrap_or_else(|| "failed".to_string()));
+                                ui.label(format!("{} B", entry.size_bytes));
+                                ui.label(format!("{} ms", entry.duration_ms));
+                                ui.label(format!("{:?}", entry.protocol));
+                                if entry.cache_hit {
+                                    ui.label("(cache)");
+                                }
+                            });
+                        }
+                    });
+                }
+                (None, _) => {
+                    ui.label("The network subsystem hasn't been initialized yet.");
+                }
+                (_, None) => {
+                    ui.label("No active tab.");
+                }
+            }
+        });
+        self.open = open;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn toggle_flips_open_state() {
+        let mut panel = NetworkPanel::default();
+        assert!(!panel.is_open());
+        panel.toggle();
+        assert!(panel.is_open());
+    }
+}
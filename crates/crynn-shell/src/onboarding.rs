@@ -0,0 +1,239 @@
+use crynn_config::{OnboardingConfig, Theme};
+use crynn_error::StorageError;
+use crynn_storage::StorageManager;
+
+/// A browser install detected on the system, with the profile directory
+/// the import step would read from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DetectedBrowser {
+    pub name: String,
+    pub profile_path: String,
+}
+
+/// Which categories of data the import step can pull from a detected
+/// browser.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ImportKind {
+    Bookmarks,
+    History,
+    Cookies,
+}
+
+/// How many records of each kind the import step pulled in, for the
+/// wizard's "Imported N bookmarks, M history entries" summary screen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ImportSummary {
+    pub bookmarks: usize,
+    pub history: usize,
+    pub cookies: usize,
+}
+
+/// The seam between the wizard and the OS/filesystem: finding what other
+/// browsers are installed, and pulling records out of a chosen profile.
+/// Once a real per-browser reader exists (Chrome's SQLite profile,
+/// Firefox's `places.sqlite`, ...) its implementation answers through
+/// this, the same split `crynn_network::CredentialProvider` uses for
+/// prompting — this crate has no real OS integration to do it with yet.
+pub trait BrowserMigration {
+    fn detect(&self) -> Vec<DetectedBrowser>;
+
+    fn import(
+        &self,
+        browser: &DetectedBrowser,
+        kinds: &[ImportKind],
+        storage: &mut StorageManager,
+    ) -> Result<ImportSummary, StorageError>;
+}
+
+/// Which screen of the first-run wizard is showing. Frontends drive their
+/// own UI off this rather than re-deriving it from scattered booleans.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OnboardingStep {
+    DetectBrowsers,
+    ChooseImport,
+    DefaultSearch,
+    ChooseTheme,
+    Complete,
+}
+
+/// Walks both frontends through the same first-run sequence: detect
+/// installed browsers, offer to import their bookmarks/history/cookies,
+/// then pick a default search engine and theme, recording everything in
+/// [`OnboardingConfig`] once finished so a restart never re-asks.
+#[derive(Debug)]
+pub struct OnboardingWizard {
+    step: OnboardingStep,
+    detected: Vec<DetectedBrowser>,
+    import_summary: Option<ImportSummary>,
+    default_search_engine: Option<String>,
+    theme: Option<Theme>,
+}
+
+impl Default for OnboardingWizard {
+    fn default() -> Self {
+        Self { step: OnboardingStep::DetectBrowsers, detected: Vec::new(), import_summary: None, default_search_engine: None, theme: None }
+    }
+}
+
+impl OnboardingWizard {
+    pub fn step(&self) -> OnboardingStep {
+        self.step
+    }
+
+    pub fn detected_browsers(&self) -> &[DetectedBrowser] {
+        &self.detected
+    }
+
+    pub fn import_summary(&self) -> Option<ImportSummary> {
+        self.import_summary
+    }
+
+    /// Runs browser detection and advances to the import step.
+    pub fn detect_browsers(&mut self, migration: &dyn BrowserMigration) {
+        self.detected = migration.detect();
+        self.step = OnboardingStep::ChooseImport;
+    }
+
+    /// Imports the chosen data from `browser` and advances to the
+    /// default-search step. A wizard with nothing worth importing should
+    /// call [`Self::skip_import`] instead.
+    pub fn import_from(
+        &mut self,
+        migration: &dyn BrowserMigration,
+        browser: &DetectedBrowser,
+        kinds: &[ImportKind],
+        storage: &mut StorageManager,
+    ) -> Result<(), StorageError> {
+        self.import_summary = Some(migration.import(browser, kinds, storage)?);
+        self.step = OnboardingStep::DefaultSearch;
+        Ok(())
+    }
+
+    pub fn skip_import(&mut self) {
+        self.step = OnboardingStep::DefaultSearch;
+    }
+
+    pub fn choose_default_search(&mut self, engine: impl Into<String>) {
+        self.default_search_engine = Some(engine.into());
+        self.step = OnboardingStep::ChooseTheme;
+    }
+
+    pub fn choose_theme(&mut self, theme: Theme) {
+        self.theme = Some(theme);
+        self.step = OnboardingStep::Complete;
+    }
+
+    /// Writes the wizard's choices into `config` once it's reached
+    /// [`OnboardingStep::Complete`], so neither frontend re-asks after a
+    /// restart. A no-op at any earlier step.
+    pub fn finish(&self, config: &mut OnboardingConfig) {
+        if self.step != OnboardingStep::Complete {
+            return;
+        }
+        config.completed = true;
+        if let Some(engine) = &self.default_search_engine {
+            config.default_search_engine = engine.clone();
+        }
+        if let Some(theme) = self.theme {
+            config.theme = theme;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct StubMigration {
+        browsers: Vec<DetectedBrowser>,
+        summary: ImportSummary,
+    }
+
+    impl BrowserMigration for StubMigration {
+        fn detect(&self) -> Vec<DetectedBrowser> {
+            self.browsers.clone()
+        }
+
+        fn import(&self, _browser: &DetectedBrowser, _kinds: &[ImportKind], _storage: &mut StorageManager) -> Result<ImportSummary, StorageError> {
+            Ok(self.summary)
+        }
+    }
+
+    fn storage() -> StorageManager {
+        let dir = std::env::temp_dir().join(format!("crynn-shell-onboarding-test-{}", std::process::id()));
+        let _ = std::fs::create_dir_all(&dir);
+        StorageManager::open(&dir, None).unwrap()
+    }
+
+    #[test]
+    fn a_fresh_wizard_starts_at_detect_browsers() {
+        let wizard = OnboardingWizard::default();
+        assert_eq!(wizard.step(), OnboardingStep::DetectBrowsers);
+        assert!(wizard.detected_browsers().is_empty());
+    }
+
+    #[test]
+    fn detecting_browsers_advances_to_choose_import() {
+        let mut wizard = OnboardingWizard::default();
+        let migration = StubMigration {
+            browsers: vec![DetectedBrowser { name: "Other Browser".to_string(), profile_path: "/home/user/.other".to_string() }],
+            summary: ImportSummary::default(),
+        };
+
+        wizard.detect_browsers(&migration);
+
+        assert_eq!(wizard.step(), OnboardingStep::ChooseImport);
+        assert_eq!(wizard.detected_browsers().len(), 1);
+    }
+
+    #[test]
+    fn importing_records_the_summary_and_advances_to_default_search() {
+        let mut wizard = OnboardingWizard::default();
+        let migration = StubMigration {
+            browsers: vec![DetectedBrowser { name: "Other Browser".to_string(), profile_path: "/home/user/.other".to_string() }],
+            summary: ImportSummary { bookmarks: 12, history: 340, cookies: 8 },
+        };
+        let browser = DetectedBrowser { name: "Other Browser".to_string(), profile_path: "/home/user/.other".to_string() };
+        let mut storage = storage();
+
+        wizard.import_from(&migration, &browser, &[ImportKind::Bookmarks, ImportKind::History], &mut storage).unwrap();
+
+        assert_eq!(wizard.step(), OnboardingStep::DefaultSearch);
+        assert_eq!(wizard.import_summary(), Some(ImportSummary { bookmarks: 12, history: 340, cookies: 8 }));
+    }
+
+    #[test]
+    fn skipping_import_also_advances_to_default_search() {
+        let mut wizard = OnboardingWizard::default();
+        wizard.skip_import();
+        assert_eq!(wizard.step(), OnboardingStep::DefaultSearch);
+        assert!(wizard.import_summary().is_none());
+    }
+
+    #[test]
+    fn finish_is_a_no_op_before_the_wizard_reaches_complete() {
+        let mut wizard = OnboardingWizard::default();
+        wizard.skip_import();
+        wizard.choose_default_search("crynn");
+
+        let mut config = OnboardingConfig::default();
+        wizard.finish(&mut config);
+
+        assert!(!config.completed);
+    }
+
+    #[test]
+    fn finishing_the_full_sequence_records_every_choice_in_the_config() {
+        let mut wizard = OnboardingWizard::default();
+        wizard.skip_import();
+        wizard.choose_default_search("duckduckgo");
+        wizard.choose_theme(Theme::Dark);
+
+        let mut config = OnboardingConfig::default();
+        wizard.finish(&mut config);
+
+        assert!(config.completed);
+        assert_eq!(config.default_search_engine, "duckduckgo");
+        assert_eq!(config.theme, Theme::Dark);
+    }
+}
@@ -0,0 +1,56 @@
+use crynn_engine::Tab;
+
+/// The popover opened from the status bar's padlock: content size, load
+/// time, cookie count, and permissions for the active tab's site.
+#[derive(Default)]
+pub struct PageInfo {
+    open: bool,
+}
+
+impl PageInfo {
+    pub fn toggle(&mut self) {
+        self.open = !self.open;
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    /// `cookie_count` and `permissions` are supplied by the caller rather
+    /// than looked up here, the same way `tab` is: this popover just
+    /// renders whatever the render loop already has in hand, typically
+    /// `crynn_cookies::CookieManager::count_for_site` and
+    /// `crynn_permissions::PermissionStore`. The full cookie breakdown
+    /// lives in `crate::cookie_panel::CookiePanel`; this is just the
+    /// at-a-glance count.
+    pub fn ui(&mut self, ui: &mut egui::Ui, tab: &Tab, cookie_count: usize, permissions: &[&str]) {
+        if !self.open {
+            return;
+        }
+        let timings = tab.timings();
+        egui::Grid::new("page-info-grid").show(ui, |ui| {
+            ui.label("Content size:");
+            ui.label(format!("{} KB", timings.content_size_bytes / 1024));
+            ui.end_row();
+
+            ui.label("Load time:");
+            match timings.load_time_ms {
+                Some(ms) => ui.label(format!("{ms} ms")),
+                None => ui.label("—"),
+            };
+            ui.end_row();
+
+            ui.label("Cookies:");
+            ui.label(cookie_count.to_string());
+            ui.end_row();
+
+            ui.label("Permissions:");
+            if permissions.is_empty() {
+                ui.label("none granted");
+            } else {
+                ui.label(permissions.join(", "));
+            }
+            ui.end_row();
+        });
+    }
+}
@@ -0,0 +1,175 @@
+use std::time::{Duration, Instant};
+
+use crynn_engine::{ProfilerSnapshot, TabId, TabRegistry};
+
+/// State for the `about:performance` task-manager window: one row per tab
+/// plus the shared subsystems, with a configurable refresh interval and
+/// per-row unload/close actions.
+pub struct PerformanceView {
+    open: bool,
+    refresh_interval: Duration,
+    last_refresh: Option<Instant>,
+    snapshot: ProfilerSnapshot,
+}
+
+impl Default for PerformanceView {
+    fn default() -> Self {
+        Self {
+            open: false,
+            refresh_interval: Duration::from_secs(2),
+            last_refresh: None,
+            snapshot: ProfilerSnapshot::default(),
+        }
+    }
+}
+
+impl PerformanceView {
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    pub fn toggle(&mut self) {
+        self.open = !self.open;
+    }
+
+    pub fn open(&mut self) {
+        self.open = true;
+    }
+
+    pub fn close(&mut self) {
+        self.open = false;
+    }
+
+    pub fn refresh_interval(&self) -> Duration {
+        self.refresh_interval
+    }
+
+    pub fn set_refresh_interval(&mut self, interval: Duration) {
+        self.refresh_interval = interval;
+    }
+
+    pub fn snapshot(&self) -> &ProfilerSnapshot {
+        &self.snapshot
+    }
+
+    /// Re-captures the snapshot if the refresh interval has elapsed, or if
+    /// this is the first call. Called once per frame while the window is
+    /// open; cheap no-op otherwise.
+    pub fn maybe_refresh(&mut self, now: Instant, engine: &TabRegistry) {
+        if !self.open {
+            return;
+        }
+        let due = match self.last_refresh {
+            None => true,
+            Some(last) => now.duration_since(last) >= self.refresh_interval,
+        };
+        if due {
+            self.snapshot = engine.profiler_snapshot();
+            self.last_refresh = Some(now);
+        }
+    }
+
+    /// Forces an immediate refresh, e.g. right after an unload/close action.
+    pub fn refresh_now(&mut self, engine: &TabRegistry) {
+        self.snapshot = engine.profiler_snapshot();
+        self.last_refresh = Some(Instant::now());
+    }
+
+    pub fn unload_tab(&mut self, engine: &mut TabRegistry, id: TabId) {
+        engine.unload(id);
+        self.refresh_now(engine);
+    }
+
+    pub fn close_tab(&mut self, engine: &mut TabRegistry, id: TabId) {
+        engine.close(id);
+        self.refresh_now(engine);
+    }
+
+    /// Draws the task-manager window, returning a list of tabs the user
+    /// asked to close so the caller can drop any shell-side tab-strip state
+    /// that outlives the engine's own registry.
+    pub fn ui(&mut self, ctx: &egui::Context, engine: &mut TabRegistry) -> Vec<TabId> {
+        let mut closed = Vec::new();
+        if !self.open {
+            return closed;
+        }
+
+        self.maybe_refresh(Instant::now(), engine);
+
+        let mut open = self.open;
+        egui::Window::new("Task Manager")
+            .open(&mut open)
+            .resizable(true)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("Refresh every (ms):");
+                    let mut millis = self.refresh_interval.as_millis() as u64;
+                    if ui
+                        .add(egui::Slider::new(&mut millis, 250..=10_000))
+                        .changed()
+                    {
+                        self.refresh_interval = Duration::from_millis(millis);
+                    }
+                });
+
+                ui.separator();
+
+                let rows: Vec<_> = self.snapshot.components.clone().into_iter().collect();
+                for component in rows {
+                    ui.horizontal(|ui| {
+                        ui.label(&component.label);
+                        ui.label(format!("{} MB", component.memory_bytes / (1024 * 1024)));
+                        ui.label(format!("{:.1}%", component.cpu_percent));
+                        if let crynn_engine::ComponentKind::Tab(id) = component.kind {
+                            if ui.button("Unload").clicked() {
+                                self.unload_tab(engine, id);
+                            }
+                            if ui.button("Close").clicked() {
+                                self.close_tab(engine, id);
+                                closed.push(id);
+                            }
+                        }
+                    });
+                }
+            });
+        self.open = open;
+
+        closed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn refresh_only_happens_after_interval_elapses() {
+        let mut view = PerformanceView::default();
+        view.set_refresh_interval(Duration::from_millis(100));
+        view.open();
+        let engine = TabRegistry::new();
+
+        let t0 = Instant::now();
+        view.maybe_refresh(t0, &engine);
+        assert!(view.last_refresh.is_some());
+
+        let first = view.last_refresh;
+        view.maybe_refresh(t0 + Duration::from_millis(10), &engine);
+        assert_eq!(view.last_refresh, first, "should not refresh before interval elapses");
+
+        view.maybe_refresh(t0 + Duration::from_millis(150), &engine);
+        assert_ne!(view.last_refresh, first, "should refresh once interval elapses");
+    }
+
+    #[test]
+    fn close_tab_removes_it_from_engine() {
+        let mut view = PerformanceView::default();
+        view.open();
+        let mut engine = TabRegistry::new();
+        let id = engine.open("https://example.com", "Example");
+
+        view.close_tab(&mut engine, id);
+
+        assert!(engine.get(id).is_none());
+    }
+}
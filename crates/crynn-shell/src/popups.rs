@@ -0,0 +1,202 @@
+use std::collections::HashMap;
+
+use crynn_engine::{PrefValue, SitePrefStore, TabId, TabRegistry, PREF_POPUPS_ENABLED};
+use crynn_permissions::{PermissionKind, PermissionStore};
+
+/// Whether a `window.open` call on a page is allowed, decided against
+/// the popup blocker's default and any per-site override granted
+/// through [`PermissionKind::Popups`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PopupDecision {
+    Allow,
+    Block,
+}
+
+/// Where an allowed popup opens. This shell has no multi-window support
+/// yet, so [`PopupRoute::Window`] is recorded as a preference but
+/// [`open_popup`] opens it as a tab regardless, the same "decision
+/// recorded, real effect deferred" shape as `crynn_vpn`'s `VpnManager`
+/// tracking a profile before any real tunnel exists.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PopupRoute {
+    #[default]
+    Tab,
+    Window,
+}
+
+/// A `window.open` call this crate blocked, queued so the indicator's
+/// "allow once" can open the exact popup the page asked for rather than
+/// just lifting the block for next time.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlockedPopup {
+    pub url: String,
+}
+
+/// Enforces the default block-non-user-initiated-popups policy,
+/// overridable per site, and queues what it blocked per tab for the
+/// status-bar indicator — the same shape as
+/// [`crate::autoplay::AutoplayGuard`], except it keeps the blocked
+/// popups themselves (for "allow once") rather than just a count.
+#[derive(Debug, Default)]
+pub struct PopupGuard {
+    blocked: HashMap<TabId, Vec<BlockedPopup>>,
+    route: PopupRoute,
+}
+
+impl PopupGuard {
+    pub fn route(&self) -> PopupRoute {
+        self.route
+    }
+
+    pub fn set_route(&mut self, route: PopupRoute) {
+        self.route = route;
+    }
+
+    /// Decides whether `origin`'s `window.open` to `url` is allowed,
+    /// queuing it for `tab` when it isn't. `user_initiated` mirrors
+    /// [`crate::autoplay::AutoplayGuard::decide`]'s `audible` split: a
+    /// popup opened from a direct click is never blocked, since the
+    /// policy exists to stop scripts from opening windows the user
+    /// didn't ask for, not to stop the user's own action.
+    pub fn decide(&mut self, tab: TabId, origin: &str, url: &str, user_initiated: bool, permissions: &PermissionStore) -> PopupDecision {
+        if user_initiated || permissions.is_allowed(origin, PermissionKind::Popups) {
+            return PopupDecision::Allow;
+        }
+        self.blocked.entry(tab).or_default().push(BlockedPopup { url: url.to_string() });
+        tracing::debug!(?tab, %origin, %url, "blocked popup");
+        PopupDecision::Block
+    }
+
+    pub fn blocked_popups(&self, tab: TabId) -> &[BlockedPopup] {
+        self.blocked.get(&tab).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    pub fn blocked_count(&self, tab: TabId) -> u32 {
+        self.blocked_popups(tab).len() as u32
+    }
+
+    /// Removes and returns the popup queued for `tab` at `index`, for
+    /// [`open_popup`] to act on. Out-of-range silently returns `None`,
+    /// the same as [`crynn_engine::TabRegistry::get`] on an id that
+    /// doesn't exist.
+    fn take(&mut self, tab: TabId, index: usize) -> Option<BlockedPopup> {
+        let queue = self.blocked.get_mut(&tab)?;
+        if index >= queue.len() {
+            return None;
+        }
+        Some(queue.remove(index))
+    }
+
+    /// Clears every popup queued for a tab, e.g. when it navigates to a
+    /// new page.
+    pub fn reset_tab(&mut self, tab: TabId) {
+        self.blocked.remove(&tab);
+    }
+}
+
+/// The indicator's "allow once": opens the queued popup at `index`
+/// through [`engine`] without granting [`PermissionKind::Popups`], so
+/// the next script-initiated `window.open` on this site is blocked
+/// again. Returns the new tab's id, or `None` if `index` no longer
+/// points at a queued popup.
+pub fn open_popup(guard: &mut PopupGuard, engine: &mut TabRegistry, tab: TabId, index: usize) -> Option<TabId> {
+    let popup = guard.take(tab, index)?;
+    Some(engine.open(popup.url, ""))
+}
+
+/// The indicator's "always allow this site": grants
+/// [`PermissionKind::Popups`] for `origin`, pushes the matching override
+/// onto the engine through [`SitePrefStore`] so it takes effect without
+/// waiting for the next navigation, and clears `tab`'s queue since it
+/// describes popups blocked under the policy that was just lifted —
+/// mirrors [`crate::autoplay::allow_origin`].
+pub fn allow_origin(guard: &mut PopupGuard, site_prefs: &mut SitePrefStore, tab: TabId, origin: &str, permissions: &mut PermissionStore) {
+    permissions.grant(origin, PermissionKind::Popups);
+    site_prefs.set(origin, PREF_POPUPS_ENABLED, PrefValue::Bool(true));
+    guard.reset_tab(tab);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn a_tab() -> TabId {
+        TabRegistry::new().open("https://example.com", "Example")
+    }
+
+    #[test]
+    fn a_script_initiated_popup_is_blocked_by_default() {
+        let mut guard = PopupGuard::default();
+        let tab = a_tab();
+        let permissions = PermissionStore::default();
+        let decision = guard.decide(tab, "https://example.com", "https://ads.example/popup", false, &permissions);
+        assert_eq!(decision, PopupDecision::Block);
+        assert_eq!(guard.blocked_count(tab), 1);
+    }
+
+    #[test]
+    fn a_user_initiated_popup_is_always_allowed() {
+        let mut guard = PopupGuard::default();
+        let tab = a_tab();
+        let permissions = PermissionStore::default();
+        let decision = guard.decide(tab, "https://example.com", "https://example.com/help", true, &permissions);
+        assert_eq!(decision, PopupDecision::Allow);
+        assert_eq!(guard.blocked_count(tab), 0);
+    }
+
+    #[test]
+    fn a_site_with_popups_granted_is_allowed() {
+        let mut guard = PopupGuard::default();
+        let tab = a_tab();
+        let mut permissions = PermissionStore::default();
+        permissions.grant("https://example.com", PermissionKind::Popups);
+        let decision = guard.decide(tab, "https://example.com", "https://example.com/popup", false, &permissions);
+        assert_eq!(decision, PopupDecision::Allow);
+    }
+
+    #[test]
+    fn open_popup_opens_the_queued_url_and_removes_it_from_the_queue() {
+        let mut guard = PopupGuard::default();
+        let mut engine = TabRegistry::new();
+        let tab = a_tab();
+        let permissions = PermissionStore::default();
+        guard.decide(tab, "https://example.com", "https://example.com/popup", false, &permissions);
+
+        let opened = open_popup(&mut guard, &mut engine, tab, 0).unwrap();
+
+        assert_eq!(engine.get(opened).unwrap().url(), "https://example.com/popup");
+        assert_eq!(guard.blocked_count(tab), 0);
+    }
+
+    #[test]
+    fn open_popup_is_a_no_op_for_an_index_that_is_not_queued() {
+        let mut guard = PopupGuard::default();
+        let mut engine = TabRegistry::new();
+        let tab = a_tab();
+        assert_eq!(open_popup(&mut guard, &mut engine, tab, 0), None);
+    }
+
+    #[test]
+    fn allow_origin_grants_permission_pushes_the_engine_override_and_clears_the_queue() {
+        let mut guard = PopupGuard::default();
+        let mut site_prefs = SitePrefStore::new();
+        let mut permissions = PermissionStore::default();
+        let tab = a_tab();
+        guard.decide(tab, "https://example.com", "https://example.com/popup", false, &permissions);
+        assert_eq!(guard.blocked_count(tab), 1);
+
+        allow_origin(&mut guard, &mut site_prefs, tab, "https://example.com", &mut permissions);
+
+        assert!(permissions.is_allowed("https://example.com", PermissionKind::Popups));
+        assert_eq!(guard.blocked_count(tab), 0);
+        assert_eq!(site_prefs.overrides_for("https://example.com"), &[(PREF_POPUPS_ENABLED.to_string(), PrefValue::Bool(true))]);
+    }
+
+    #[test]
+    fn route_defaults_to_tab_and_is_settable() {
+        let mut guard = PopupGuard::default();
+        assert_eq!(guard.route(), PopupRoute::Tab);
+        guard.set_route(PopupRoute::Window);
+        assert_eq!(guard.route(), PopupRoute::Window);
+    }
+}
@@ -0,0 +1,51 @@
+use crynn_engine::{SitePrefStore, TabId, TabRegistry};
+use crynn_permissions::PermissionStore;
+
+use crate::popups::{allow_origin, open_popup, PopupGuard};
+
+/// Everything the blocked-popups popover needs, bundled so
+/// [`PopupIndicator::ui`] doesn't take one parameter per subsystem.
+pub struct PopupContext<'a> {
+    pub guard: &'a mut PopupGuard,
+    pub engine: &'a mut TabRegistry,
+    pub site_prefs: &'a mut SitePrefStore,
+    pub permissions: &'a mut PermissionStore,
+    pub tab: TabId,
+    pub origin: &'a str,
+}
+
+/// The popover opened from the status bar's popup-blocked icon: every
+/// popup queued for the active tab, each with its own one-time "Open",
+/// plus a site-wide "Always allow" that lifts the block entirely.
+#[derive(Default)]
+pub struct PopupIndicator {
+    open: bool,
+}
+
+impl PopupIndicator {
+    pub fn toggle(&mut self) {
+        self.open = !self.open;
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    pub fn ui(&mut self, ui: &mut egui::Ui, context: PopupContext<'_>) {
+        if !self.open {
+            return;
+        }
+        let queued = context.guard.blocked_popups(context.tab).to_vec();
+        for (index, popup) in queued.iter().enumerate() {
+            ui.horizontal(|ui| {
+                ui.label(&popup.url);
+                if ui.button("Open").clicked() {
+                    open_popup(context.guard, context.engine, context.tab, index);
+                }
+            });
+        }
+        if ui.button("Always allow popups on this site").clicked() {
+            allow_origin(context.guard, context.site_prefs, context.tab, context.origin, context.permissions);
+        }
+    }
+}
@@ -0,0 +1,264 @@
+use std::collections::HashMap;
+
+use crate::events::{Severity, ShellEvent};
+use crate::ShellState;
+
+/// A parsed `mailto:` link: the compose tab pre-fills its fields from
+/// this instead of re-parsing the raw URL itself.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MailtoLink {
+    pub to: Vec<String>,
+    pub subject: Option<String>,
+    pub body: Option<String>,
+}
+
+impl MailtoLink {
+    /// Parses a `mailto:` URL's address part and `subject`/`body` query
+    /// parameters. Multiple recipients are comma-separated in the
+    /// address part, same as a mail client's "To" field. Only decodes
+    /// `+` and `%20` as spaces — good enough for the subject/body values
+    /// real `mailto:` links send, not a general percent-decoder.
+    pub fn parse(url: &str) -> Option<Self> {
+        let rest = url.strip_prefix("mailto:")?;
+        let (address_part, query) = rest.split_once('?').unwrap_or((rest, ""));
+        let to = address_part.split(',').map(str::trim).filter(|s| !s.is_empty()).map(str::to_string).collect();
+
+        let mut link = MailtoLink { to, subject: None, body: None };
+        for pair in query.split('&').filter(|p| !p.is_empty()) {
+            let Some((key, value)) = pair.split_once('=') else { continue };
+            match key {
+                "subject" => link.subject = Some(decode_query_value(value)),
+                "body" => link.body = Some(decode_query_value(value)),
+                _ => {}
+            }
+        }
+        Some(link)
+    }
+}
+
+fn decode_query_value(value: &str) -> String {
+    value.replace("%20", " ").replace('+', " ")
+}
+
+/// What the registry decided to do with a navigation to a non-web
+/// scheme.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SchemeAction {
+    /// An `http`/`https`/`about` URL: navigate normally, same as today.
+    Navigate,
+    /// A `mailto:` link: open the built-in compose tab.
+    Compose(MailtoLink),
+    /// A scheme the user has registered an external app for. Still
+    /// needs [`confirm_external_launch`] before the app actually opens —
+    /// resolving to this action only queues the confirmation.
+    LaunchExternal { scheme: String, app: String, url: String },
+    /// A scheme with no handler at all.
+    Unsupported { scheme: String },
+}
+
+/// Maps non-web URL schemes to how the shell should handle them:
+/// built-in compose for `mailto:`, a user-configured external app for
+/// anything else registered (`magnet:`, a custom app's own scheme), or
+/// [`SchemeAction::Unsupported`] for the rest. `http`/`https`/`about`
+/// fall through to [`SchemeAction::Navigate`] since those already have
+/// their own handling elsewhere in the shell.
+#[derive(Debug, Default)]
+pub struct ProtocolHandlerRegistry {
+    external_apps: HashMap<String, String>,
+}
+
+impl ProtocolHandlerRegistry {
+    /// Registers `app` (a command or path the shell would invoke with
+    /// the URL) to handle `scheme`.
+    pub fn register_external(&mut self, scheme: impl Into<String>, app: impl Into<String>) {
+        self.external_apps.insert(scheme.into(), app.into());
+    }
+
+    pub fn unregister_external(&mut self, scheme: &str) {
+        self.external_apps.remove(scheme);
+    }
+
+    pub fn external_app(&self, scheme: &str) -> Option<&str> {
+        self.external_apps.get(scheme).map(String::as_str)
+    }
+
+    pub fn resolve(&self, url: &str) -> SchemeAction {
+        let scheme = url.split(':').next().unwrap_or(url).to_ascii_lowercase();
+        match scheme.as_str() {
+            "http" | "https" | "about" => SchemeAction::Navigate,
+            "mailto" => match MailtoLink::parse(url) {
+                Some(link) => SchemeAction::Compose(link),
+                None => SchemeAction::Unsupported { scheme },
+            },
+            _ => match self.external_app(&scheme) {
+                Some(app) => SchemeAction::LaunchExternal { scheme, app: app.to_string(), url: url.to_string() },
+                None => SchemeAction::Unsupported { scheme },
+            },
+        }
+    }
+}
+
+/// An external-app launch waiting on the user to confirm before the
+/// shell leaves the browser to open it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PendingExternalLaunch {
+    pub scheme: String,
+    pub app: String,
+    pub url: String,
+}
+
+/// Something able to actually launch an external app for a confirmed
+/// [`PendingExternalLaunch`] — the shell's own process-spawning code in
+/// a real build, a fake recording one in tests. Kept as a trait for the
+/// same reason [`crynn_network::CredentialProvider`] is: the decision
+/// and the environment-specific action stay decoupled.
+pub trait ExternalLauncher {
+    fn launch(&mut self, app: &str, url: &str) -> std::io::Result<()>;
+}
+
+/// Renders an unsupported scheme as a small HTML document, for whenever
+/// the shell has a real page to show it in rather than just a toast.
+pub fn unsupported_scheme_html(scheme: &str) -> String {
+    format!(
+        "<html><head><title>Can't open this link</title></head><body><h1>Can't open this link</h1><p>No app is set up to handle \"{scheme}:\" links.</p></body></html>"
+    )
+}
+
+/// Resolves `url` against `state.protocol_handlers` and acts on it:
+/// opens the compose tab for a `mailto:` link, queues an external-app
+/// launch for confirmation, posts a toast for an unsupported scheme, or
+/// opens `url` as a normal tab.
+pub fn request_navigation(state: &mut ShellState, url: &str) {
+    match state.protocol_handlers.resolve(url) {
+        SchemeAction::Navigate => {
+            state.open_tab(url, url);
+        }
+        SchemeAction::Compose(link) => {
+            let title = link.subject.clone().unwrap_or_else(|| "New message".to_string());
+            state.open_tab(url, title);
+        }
+        SchemeAction::LaunchExternal { scheme, app, url } => {
+            state.pending_external_launch = Some(PendingExternalLaunch { scheme, app, url });
+        }
+        SchemeAction::Unsupported { scheme } => {
+            state.events.post(ShellEvent::new(Severity::Warning, format!("No app is set up to handle \"{scheme}:\" links.")));
+        }
+    }
+}
+
+/// Confirms a pending external-app launch, handing it to `launcher`.
+/// Clears the pending state either way, so a failed launch doesn't leave
+/// the confirmation prompt stuck open.
+pub fn confirm_external_launch(state: &mut ShellState, launcher: &mut dyn ExternalLauncher) {
+    let Some(pending) = state.pending_external_launch.take() else { return };
+    if let Err(err) = launcher.launch(&pending.app, &pending.url) {
+        state.events.post(ShellEvent::new(Severity::Error, format!("Couldn't open {}: {err}", pending.app)));
+    }
+}
+
+/// Dismisses a pending external-app launch without opening it.
+pub fn cancel_external_launch(state: &mut ShellState) {
+    state.pending_external_launch = None;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_a_mailto_link_with_subject_and_body() {
+        let link = MailtoLink::parse("mailto:a@example.com?subject=Hello+there&body=How%20are%20you").unwrap();
+        assert_eq!(link.to, vec!["a@example.com"]);
+        assert_eq!(link.subject, Some("Hello there".to_string()));
+        assert_eq!(link.body, Some("How are you".to_string()));
+    }
+
+    #[test]
+    fn parses_multiple_recipients() {
+        let link = MailtoLink::parse("mailto:a@example.com,b@example.com").unwrap();
+        assert_eq!(link.to, vec!["a@example.com", "b@example.com"]);
+    }
+
+    #[test]
+    fn resolve_routes_standard_schemes_to_navigate() {
+        let registry = ProtocolHandlerRegistry::default();
+        assert_eq!(registry.resolve("https://example.com"), SchemeAction::Navigate);
+    }
+
+    #[test]
+    fn resolve_routes_mailto_to_compose() {
+        let registry = ProtocolHandlerRegistry::default();
+        assert!(matches!(registry.resolve("mailto:a@example.com"), SchemeAction::Compose(_)));
+    }
+
+    #[test]
+    fn resolve_routes_a_registered_scheme_to_launch_external() {
+        let mut registry = ProtocolHandlerRegistry::default();
+        registry.register_external("magnet", "/usr/bin/transmission");
+        assert_eq!(
+            registry.resolve("magnet:?xt=urn:btih:abc"),
+            SchemeAction::LaunchExternal {
+                scheme: "magnet".to_string(),
+                app: "/usr/bin/transmission".to_string(),
+                url: "magnet:?xt=urn:btih:abc".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn resolve_routes_unknown_schemes_to_unsupported() {
+        let registry = ProtocolHandlerRegistry::default();
+        assert_eq!(registry.resolve("gopher://example.com"), SchemeAction::Unsupported { scheme: "gopher".to_string() });
+    }
+
+    #[test]
+    fn an_unsupported_scheme_navigation_posts_a_toast_not_a_tab() {
+        let mut state = ShellState::default();
+        request_navigation(&mut state, "gopher://example.com");
+        assert_eq!(state.engine.len(), 0);
+        assert_eq!(state.events.drain().len(), 1);
+    }
+
+    #[test]
+    fn a_registered_scheme_navigation_queues_confirmation_instead_of_launching() {
+        let mut state = ShellState::default();
+        state.protocol_handlers.register_external("magnet", "/usr/bin/transmission");
+        request_navigation(&mut state, "magnet:?xt=urn:btih:abc");
+        assert!(state.pending_external_launch.is_some());
+    }
+
+    struct RecordingLauncher {
+        calls: Vec<(String, String)>,
+    }
+
+    impl ExternalLauncher for RecordingLauncher {
+        fn launch(&mut self, app: &str, url: &str) -> std::io::Result<()> {
+            self.calls.push((app.to_string(), url.to_string()));
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn confirming_a_pending_launch_calls_the_launcher_and_clears_the_pending_state() {
+        let mut state = ShellState::default();
+        state.protocol_handlers.register_external("magnet", "/usr/bin/transmission");
+        request_navigation(&mut state, "magnet:?xt=urn:btih:abc");
+
+        let mut launcher = RecordingLauncher { calls: Vec::new() };
+        confirm_external_launch(&mut state, &mut launcher);
+
+        assert_eq!(launcher.calls, vec![("/usr/bin/transmission".to_string(), "magnet:?xt=urn:btih:abc".to_string())]);
+        assert!(state.pending_external_launch.is_none());
+    }
+
+    #[test]
+    fn cancelling_clears_the_pending_state_without_launching() {
+        let mut state = ShellState::default();
+        state.protocol_handlers.register_external("magnet", "/usr/bin/transmission");
+        request_navigation(&mut state, "magnet:?xt=urn:btih:abc");
+
+        cancel_external_launch(&mut state);
+
+        assert!(state.pending_external_launch.is_none());
+    }
+}
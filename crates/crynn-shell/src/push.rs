@@ -0,0 +1,83 @@
+use std::path::PathBuf;
+
+use crynn_permissions::{origin_of, PermissionKind, PermissionState};
+
+use crate::events::{Severity, ShellEvent};
+use crate::ShellState;
+
+/// Drains any push messages the engine has received since the last call
+/// and, for sites the user has granted [`PermissionKind::Push`] to,
+/// turns each into a toast via the [`crate::events::EventBus`]. Messages
+/// for sites that were never granted (or were denied, including by the
+/// per-site disable control) are dropped silently rather than prompted
+/// for here — a push message arriving is evidence a subscription already
+/// exists, not a new permission request.
+pub fn deliver_push_messages(state: &mut ShellState) {
+    for message in state.engine.drain_push_messages() {
+        if !state.permissions.is_allowed(&message.origin, PermissionKind::Push) {
+            continue;
+        }
+        state.events.post(ShellEvent::new(
+            Severity::Info,
+            format!("{}: {}", message.title, message.body),
+        ));
+    }
+}
+
+/// Denies future push messages for the active tab's site. The per-site
+/// disable control the request asks for; re-subscribing would need the
+/// site to ask again and the user to grant it.
+pub fn disable_push_for_active_tab(state: &mut ShellState) {
+    let Some(tab) = state.active_tab() else { return };
+    let origin = origin_of(tab.url());
+    state.permissions.set(&origin, PermissionKind::Push, PermissionState::Denied);
+}
+
+pub(crate) fn default_store_path() -> Option<PathBuf> {
+    crate::zoom::default_store_path().map(|p| p.with_file_name("permissions.json"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crynn_engine::PushMessage;
+
+    fn push_message(origin: &str) -> PushMessage {
+        PushMessage { origin: origin.to_string(), title: "New message".to_string(), body: "Hello!".to_string() }
+    }
+
+    #[test]
+    fn a_granted_site_s_push_message_becomes_a_toast() {
+        let mut state = ShellState::default();
+        state.permissions.grant("https://chat.example.com", PermissionKind::Push);
+        state.engine.receive_push(push_message("https://chat.example.com"));
+
+        deliver_push_messages(&mut state);
+
+        assert_eq!(state.events.drain().len(), 1);
+    }
+
+    #[test]
+    fn an_ungranted_site_s_push_message_is_dropped() {
+        let mut state = ShellState::default();
+        state.engine.receive_push(push_message("https://chat.example.com"));
+
+        deliver_push_messages(&mut state);
+
+        assert!(state.events.drain().is_empty());
+    }
+
+    #[test]
+    fn disabling_push_stops_future_messages_for_that_site() {
+        let mut state = ShellState::default();
+        state.permissions.grant("https://chat.example.com", PermissionKind::Push);
+        let tab_id = state.open_tab("https://chat.example.com", "Chat");
+        state.active_tab = Some(tab_id);
+
+        disable_push_for_active_tab(&mut state);
+        state.engine.receive_push(push_message("https://chat.example.com"));
+        deliver_push_messages(&mut state);
+
+        assert!(state.events.drain().is_empty());
+    }
+}
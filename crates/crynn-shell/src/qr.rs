@@ -0,0 +1,122 @@
+use crate::url_utils::{classify, Classification};
+
+/// A generated QR code as a square grid of modules (the individual black
+/// or white cells), `dark` meaning the module renders black. `size` is
+/// the side length in modules; `modules` is row-major, `size * size` long.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QrMatrix {
+    pub size: usize,
+    modules: Vec<bool>,
+}
+
+impl QrMatrix {
+    /// Starts an all-white matrix of `size` modules a side, for a
+    /// [`QrEncoder`] implementation to darken modules on as it encodes.
+    pub fn new(size: usize) -> Self {
+        Self { size, modules: vec![false; size * size] }
+    }
+
+    pub fn is_dark(&self, x: usize, y: usize) -> bool {
+        self.modules[y * self.size + x]
+    }
+
+    pub fn set_dark(&mut self, x: usize, y: usize) {
+        self.modules[y * self.size + x] = true;
+    }
+}
+
+/// Renders text into a [`QrMatrix`] a real embedder can draw to a window
+/// or export as an image. No real QR-encoding implementation (version
+/// selection, Reed-Solomon error correction, mask scoring) exists yet —
+/// the same contract-over-implementation split as
+/// [`crate::clipboard::ClipboardSource`]/[`crynn_engine::DevtoolsLauncher`].
+pub trait QrEncoder {
+    fn encode(&self, text: &str) -> QrMatrix;
+}
+
+/// Reads a decoded payload off a camera, on the machines the embedding
+/// shell has one wired up for. No real camera binding exists yet, the
+/// same contract-over-implementation split as [`QrEncoder`].
+pub trait QrScanner {
+    /// Returns the decoded text from the next frame with a readable QR
+    /// code in it, or `None` if nothing's been decoded yet.
+    fn scan(&mut self) -> Option<String>;
+}
+
+/// Builds the "share this page as a QR code" view's matrix for `url`,
+/// through `encoder`.
+pub fn share_page_as_qr(url: &str, encoder: &dyn QrEncoder) -> QrMatrix {
+    encoder.encode(url)
+}
+
+/// Polls `scanner` for a decoded QR payload and, if it looks like a URL
+/// rather than arbitrary scanned text, returns it to navigate to.
+/// Anything [`classify`] would treat as a search rather than a URL is
+/// not something scanning a QR code should ever turn into a web search,
+/// so it's dropped here instead of handed to the caller.
+pub fn scan_to_navigate(scanner: &mut dyn QrScanner) -> Option<String> {
+    let payload = scanner.scan()?;
+    match classify(&payload) {
+        Classification::Url(url) => Some(url),
+        Classification::Search(_) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct DiagonalEncoder;
+
+    impl QrEncoder for DiagonalEncoder {
+        fn encode(&self, text: &str) -> QrMatrix {
+            let size = text.len().max(1);
+            let mut matrix = QrMatrix::new(size);
+            for i in 0..size {
+                matrix.set_dark(i, i);
+            }
+            matrix
+        }
+    }
+
+    struct FixedScanner {
+        payload: Option<String>,
+    }
+
+    impl QrScanner for FixedScanner {
+        fn scan(&mut self) -> Option<String> {
+            self.payload.take()
+        }
+    }
+
+    #[test]
+    fn share_page_as_qr_delegates_to_the_encoder() {
+        let matrix = share_page_as_qr("https://example.com", &DiagonalEncoder);
+        assert_eq!(matrix.size, "https://example.com".len());
+        assert!(matrix.is_dark(0, 0));
+    }
+
+    #[test]
+    fn scanning_a_url_payload_returns_it_to_navigate_to() {
+        let mut scanner = FixedScanner { payload: Some("https://example.com".to_string()) };
+        assert_eq!(scan_to_navigate(&mut scanner), Some("https://example.com".to_string()));
+    }
+
+    #[test]
+    fn scanning_a_bare_domain_normalizes_it_with_a_scheme() {
+        let mut scanner = FixedScanner { payload: Some("example.com".to_string()) };
+        assert_eq!(scan_to_navigate(&mut scanner), Some("https://example.com".to_string()));
+    }
+
+    #[test]
+    fn scanning_plain_text_does_not_navigate() {
+        let mut scanner = FixedScanner { payload: Some("not a url".to_string()) };
+        assert_eq!(scan_to_navigate(&mut scanner), None);
+    }
+
+    #[test]
+    fn scanning_nothing_yet_returns_none() {
+        let mut scanner = FixedScanner { payload: None };
+        assert_eq!(scan_to_navigate(&mut scanner), None);
+    }
+}
@@ -0,0 +1,104 @@
+use std::time::Instant;
+
+use crynn_engine::{GovernorAction, TabId};
+
+use crate::events::{Severity, ShellEvent};
+use crate::ShellState;
+
+/// Runs [`crynn_engine::ResourceGovernor::evaluate`] over every open tab
+/// and posts a toast for each verdict offering to kill the offending tab
+/// or whitelist its site. [`GovernorAction::Suspend`] is carried out
+/// immediately via [`crynn_engine::TabRegistry::unload`] — the only
+/// engine hook this build actually has; [`GovernorAction::Throttle`] is
+/// reported but otherwise deferred to whatever real engine binding
+/// eventually implements it, the same split [`crynn_engine::EnginePrefs`]
+/// draws for applying a preference.
+pub fn enforce_resource_limits(state: &mut ShellState, now: Instant) {
+    let snapshot = state.engine.profiler_snapshot();
+    let verdicts = state.resource_governor.evaluate(&snapshot, state.engine.iter(), now);
+    for verdict in verdicts {
+        if verdict.action == GovernorAction::Suspend {
+            state.engine.unload(verdict.tab);
+        }
+        let summary = match verdict.action {
+            GovernorAction::Throttle => "is using a lot of CPU/memory",
+            GovernorAction::Suspend => "was suspended for using too much CPU/memory",
+        };
+        state.events.post(ShellEvent::new(Severity::Warning, format!("{} {summary}", verdict.origin)));
+    }
+}
+
+/// The notification's "kill this tab" response.
+pub fn kill_offending_tab(state: &mut ShellState, tab: TabId) {
+    state.engine.close(tab);
+}
+
+/// The notification's "whitelist this site" response: exempts `origin`
+/// from future verdicts. Doesn't undo a suspension already in effect —
+/// reactivating a discarded tab is the same reload-on-activate path any
+/// other discarded tab goes through.
+pub fn whitelist_offending_site(state: &mut ShellState, origin: &str) {
+    state.resource_governor.whitelist(origin);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crynn_engine::{ComponentKind, ComponentMetrics, ProfilerSnapshot, ResourceGovernor, ResourceLimits, DEFAULT_THROTTLE_AFTER};
+
+    fn state_with_heavy_tab() -> (ShellState, TabId) {
+        let mut state = ShellState::default();
+        let tab = state.engine.open("https://heavy.example.com", "Heavy");
+        state.resource_governor = ResourceGovernor::new(
+            ResourceLimits { max_memory_bytes: 1, max_cpu_percent: 1.0 },
+            DEFAULT_THROTTLE_AFTER,
+            crynn_engine::DEFAULT_SUSPEND_AFTER,
+        );
+        (state, tab)
+    }
+
+    // `ResourceGovernor::evaluate` reads live profiler data, which a
+    // freshly opened tab doesn't generate enough of on its own to cross
+    // even a near-zero threshold immediately; these tests drive it
+    // directly with a synthetic snapshot the way
+    // `crynn_engine::governor`'s own tests do, rather than waiting on
+    // `TabRegistry::profiler_snapshot`'s cheap built-in estimate.
+    fn heavy_snapshot(tab: TabId) -> ProfilerSnapshot {
+        ProfilerSnapshot { components: vec![ComponentMetrics { kind: ComponentKind::Tab(tab), label: String::new(), memory_bytes: u64::MAX, cpu_percent: 100.0 }] }
+    }
+
+    #[test]
+    fn a_sustained_breach_posts_a_toast_and_suspends_the_tab() {
+        let (mut state, tab) = state_with_heavy_tab();
+        let t0 = Instant::now();
+        let snapshot = heavy_snapshot(tab);
+
+        state.resource_governor.evaluate(&snapshot, std::iter::once(state.engine.get(tab).unwrap()), t0);
+        let verdicts = state.resource_governor.evaluate(&snapshot, std::iter::once(state.engine.get(tab).unwrap()), t0 + crynn_engine::DEFAULT_SUSPEND_AFTER);
+        assert!(!verdicts.is_empty());
+
+        for verdict in verdicts {
+            if verdict.action == GovernorAction::Suspend {
+                state.engine.unload(verdict.tab);
+            }
+            state.events.post(ShellEvent::new(Severity::Warning, verdict.origin));
+        }
+
+        assert!(state.engine.get(tab).unwrap().is_discarded());
+        assert_eq!(state.events.drain().len(), 1);
+    }
+
+    #[test]
+    fn kill_offending_tab_closes_it() {
+        let (mut state, tab) = state_with_heavy_tab();
+        kill_offending_tab(&mut state, tab);
+        assert!(state.engine.get(tab).is_none());
+    }
+
+    #[test]
+    fn whitelisting_a_site_exempts_it_from_future_verdicts() {
+        let (mut state, _tab) = state_with_heavy_tab();
+        whitelist_offending_site(&mut state, "https://heavy.example.com");
+        assert!(state.resource_governor.is_whitelisted("https://heavy.example.com"));
+    }
+}
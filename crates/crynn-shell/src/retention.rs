@@ -0,0 +1,191 @@
+use crynn_error::StorageError;
+use crynn_storage::{RetentionPlan, RetentionRule};
+
+use crate::ShellState;
+
+/// What a scheduled cleanup sweep would remove across both the
+/// subsystems a [`RetentionRule`] covers directly (history) and the one
+/// it can't reach on its own (cookies) — the same split
+/// [`crate::forget_site`] draws for purging a single origin, just
+/// evaluated in bulk on a schedule instead of triggered by a user click.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CleanupPreview {
+    pub history: RetentionPlan,
+    /// Domains [`MaintenanceScheduler`] would clear cookies for: ones
+    /// with no recorded history visit in the configured window. Cookies
+    /// don't carry their own last-visit timestamp, so history's is the
+    /// closest proxy for it.
+    pub stale_cookie_sites: Vec<String>,
+}
+
+impl CleanupPreview {
+    pub fn is_empty(&self) -> bool {
+        self.history.is_empty() && self.stale_cookie_sites.is_empty()
+    }
+}
+
+/// User-configurable cleanup policy, evaluated on a schedule: remove
+/// history per [`RetentionRule`], and clear cookies from any site with
+/// no recorded visit in the last `cookie_unvisited_days` days. Mirrors
+/// `crynn_email::SyncScheduler`'s is-due/interval shape — deciding
+/// *when* a sweep should run without driving a clock itself.
+pub struct MaintenanceScheduler {
+    history_rule: RetentionRule,
+    cookie_unvisited_days: u32,
+    interval_secs: u64,
+    last_run_at: Option<u64>,
+}
+
+impl Default for MaintenanceScheduler {
+    fn default() -> Self {
+        Self {
+            history_rule: RetentionRule::HistoryOlderThanDays(90),
+            cookie_unvisited_days: 30,
+            interval_secs: 86_400,
+            last_run_at: None,
+        }
+    }
+}
+
+impl MaintenanceScheduler {
+    pub fn history_rule(&self) -> RetentionRule {
+        self.history_rule
+    }
+
+    pub fn set_history_rule(&mut self, rule: RetentionRule) {
+        self.history_rule = rule;
+    }
+
+    pub fn cookie_unvisited_days(&self) -> u32 {
+        self.cookie_unvisited_days
+    }
+
+    pub fn set_cookie_unvisited_days(&mut self, days: u32) {
+        self.cookie_unvisited_days = days;
+    }
+
+    pub fn set_interval_secs(&mut self, interval_secs: u64) {
+        self.interval_secs = interval_secs;
+    }
+
+    /// Whether a sweep is due: never run, or `interval_secs` has elapsed
+    /// since the last one.
+    pub fn is_due(&self, now: u64) -> bool {
+        match self.last_run_at {
+            None => true,
+            Some(last) => now.saturating_sub(last) >= self.interval_secs,
+        }
+    }
+
+    /// Previews what a sweep right now would remove, without removing
+    /// anything — the dry-run preview the cleanup settings UI shows
+    /// before the user confirms.
+    pub fn preview(&self, state: &ShellState, now: u64) -> Result<CleanupPreview, StorageError> {
+        let history = match &state.storage {
+            Some(storage) => crynn_storage::plan(storage, self.history_rule, now)?,
+            None => RetentionPlan::default(),
+        };
+
+        let cutoff = now.saturating_sub(self.cookie_unvisited_days as u64 * 86_400);
+        let mut stale_cookie_sites = Vec::new();
+        for domain in state.cookies.sites() {
+            let last_visit = match &state.storage {
+                Some(storage) => crynn_storage::last_visit_at(storage, domain)?,
+                None => None,
+            };
+            if last_visit.map(|at| at < cutoff).unwrap_or(true) {
+                stale_cookie_sites.push(domain.to_string());
+            }
+        }
+
+        Ok(CleanupPreview { history, stale_cookie_sites })
+    }
+
+    /// Runs the sweep if due, applying its own preview and recording
+    /// `now` as the last run time. Returns `None` without touching
+    /// anything if not due yet.
+    pub fn run_if_due(&mut self, state: &mut ShellState, now: u64) -> Result<Option<CleanupPreview>, StorageError> {
+        if !self.is_due(now) {
+            return Ok(None);
+        }
+        let preview = self.preview(state, now)?;
+        apply_cleanup(state, &preview)?;
+        self.last_run_at = Some(now);
+        Ok(Some(preview))
+    }
+}
+
+/// Applies a previously previewed sweep: deletes the planned history
+/// visits and clears cookies for every stale site.
+pub fn apply_cleanup(state: &mut ShellState, preview: &CleanupPreview) -> Result<(), StorageError> {
+    if let Some(storage) = &mut state.storage {
+        let ids: Vec<String> = preview.history.visits_to_delete.iter().map(|visit| visit.id.clone()).collect();
+        crynn_storage::delete_visits_by_id(storage, &ids)?;
+    }
+    for site in &preview.stale_cookie_sites {
+        state.cookies.clear_site(site);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crynn_storage::{record_visit, Visit, VisitType};
+
+    const DAY: u64 = 86_400;
+
+    fn visit(id: &str, url: &str, at: u64) -> Visit {
+        Visit { id: id.to_string(), url: url.to_string(), title: "Title".to_string(), visit_type: VisitType::Typed, at, from_visit: None }
+    }
+
+    #[test]
+    fn preview_flags_cookie_sites_with_no_recent_visit() {
+        let storage = crynn_storage::StorageManager::open(
+            std::env::temp_dir().join(format!("crynn-shell-retention-test-{}", std::process::id())),
+            None,
+        )
+        .unwrap();
+        let mut state = ShellState { storage: Some(storage), ..ShellState::default() };
+        record_visit(state.storage.as_mut().unwrap(), &visit("1", "https://fresh.com", 100 * DAY)).unwrap();
+        state.cookies.set("fresh.com", "session", "abc", crynn_cookies::CookieParty::First, 1);
+        state.cookies.set("stale.com", "session", "def", crynn_cookies::CookieParty::First, 1);
+
+        let scheduler = MaintenanceScheduler::default();
+        let preview = scheduler.preview(&state, 100 * DAY).unwrap();
+
+        assert_eq!(preview.stale_cookie_sites, vec!["stale.com".to_string()]);
+    }
+
+    #[test]
+    fn is_due_before_the_first_run_and_after_the_interval_elapses() {
+        let mut scheduler = MaintenanceScheduler::default();
+        scheduler.set_interval_secs(DAY);
+        assert!(scheduler.is_due(0));
+
+        scheduler.run_if_due(&mut ShellState::default(), 0).unwrap();
+        assert!(!scheduler.is_due(DAY / 2));
+        assert!(scheduler.is_due(DAY));
+    }
+
+    #[test]
+    fn run_if_due_is_a_no_op_before_the_interval_elapses() {
+        let mut scheduler = MaintenanceScheduler::default();
+        scheduler.set_interval_secs(DAY);
+        let mut state = ShellState::default();
+
+        assert!(scheduler.run_if_due(&mut state, 0).unwrap().is_some());
+        assert!(scheduler.run_if_due(&mut state, DAY / 2).unwrap().is_none());
+    }
+
+    #[test]
+    fn apply_cleanup_clears_cookies_for_stale_sites() {
+        let mut state = ShellState::default();
+        state.cookies.set("stale.com", "session", "def", crynn_cookies::CookieParty::First, 1);
+
+        let preview = CleanupPreview { history: RetentionPlan::default(), stale_cookie_sites: vec!["stale.com".to_string()] };
+        apply_cleanup(&mut state, &preview).unwrap();
+
+        assert_eq!(state.cookies.count_for_site("stale.com"), 0);
+    }
+}
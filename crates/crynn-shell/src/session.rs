@@ -0,0 +1,112 @@
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use crynn_engine::{SessionSnapshot, TabRegistry};
+use crynn_network::{warm_up, CacheWarmer, WarmupOutcome};
+
+/// How many of `snapshot`'s tabs to preconnect/revalidate at once,
+/// capping the background connections a restore opens the same way
+/// [`crate::startup::LazySubsystem`] caps eager work elsewhere at
+/// startup.
+const WARM_UP_MAX_CONCURRENCY: usize = 4;
+
+/// Captures `engine`'s open tabs and groups and writes them to `path`, the
+/// layout a future launch restores via [`load`].
+pub fn save(path: &std::path::Path, engine: &TabRegistry) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let contents = serde_json::to_string_pretty(&engine.snapshot())
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    fs::write(path, contents)
+}
+
+/// Reads back a snapshot saved by [`save`], or an empty one if there's no
+/// session file yet (first launch, or a profile with nothing to restore).
+pub fn load(path: &std::path::Path) -> io::Result<SessionSnapshot> {
+    match fs::read_to_string(path) {
+        Ok(contents) => serde_json::from_str(&contents).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(SessionSnapshot::default()),
+        Err(e) => Err(e),
+    }
+}
+
+pub(crate) fn default_store_path() -> Option<PathBuf> {
+    crate::zoom::default_store_path().map(|p| p.with_file_name("session.json"))
+}
+
+/// Preconnects to and revalidates the main documents of `snapshot`'s
+/// restored tabs in the order they were saved, so switching to one
+/// loads near-instantly — the session-restore counterpart to
+/// [`crynn_network::warm_up`]'s own doc comment. Bounded to
+/// [`WARM_UP_MAX_CONCURRENCY`] tabs; callers that restore more than
+/// that just don't warm up the rest, the same as any other background,
+/// low-priority pass in this codebase that never holds up the tabs
+/// that already loaded.
+pub fn warm_up_restored_session(snapshot: &SessionSnapshot, warmer: &mut dyn CacheWarmer) -> Vec<WarmupOutcome> {
+    let urls: Vec<String> = snapshot.tabs.iter().map(|tab| tab.url.clone()).collect();
+    warm_up(&urls, warmer, WARM_UP_MAX_CONCURRENCY)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crynn_engine::GroupColor;
+
+    #[test]
+    fn save_and_load_round_trip() {
+        let dir = std::env::temp_dir().join(format!("crynn-session-test-{}", std::process::id()));
+        let path = dir.join("session.json");
+
+        let mut engine = TabRegistry::new();
+        let group = engine.create_group("Work", GroupColor::rgb(10, 20, 30));
+        let tab = engine.open("https://example.com", "Example");
+        engine.assign_tab_to_group(tab, group);
+
+        save(&path, &engine).unwrap();
+        let restored = TabRegistry::restore(load(&path).unwrap());
+
+        assert_eq!(restored.len(), 1);
+        assert_eq!(restored.groups().iter().count(), 1);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn loading_a_missing_file_returns_an_empty_snapshot() {
+        let path = std::env::temp_dir().join("crynn-session-test-missing-does-not-exist.json");
+        let snapshot = load(&path).unwrap();
+        assert!(snapshot.tabs.is_empty());
+        assert!(snapshot.groups.is_empty());
+    }
+
+    #[derive(Default)]
+    struct FakeWarmer;
+
+    impl CacheWarmer for FakeWarmer {
+        fn preconnect(&mut self, _origin: &str) -> bool {
+            true
+        }
+
+        fn revalidate(&mut self, _url: &str) -> bool {
+            true
+        }
+    }
+
+    #[test]
+    fn warm_up_restored_session_preheats_each_restored_tab() {
+        let snapshot = SessionSnapshot {
+            tabs: vec![
+                crynn_engine::TabSnapshot { url: "https://example.com/a".to_string(), title: "A".to_string(), group: None },
+                crynn_engine::TabSnapshot { url: "https://example.org/".to_string(), title: "B".to_string(), group: None },
+            ],
+            groups: Vec::new(),
+        };
+
+        let outcomes = warm_up_restored_session(&snapshot, &mut FakeWarmer);
+
+        assert_eq!(outcomes.len(), 2);
+        assert!(outcomes.iter().all(|o| o.preconnected && o.revalidated));
+    }
+}
@@ -0,0 +1,325 @@
+use std::str::FromStr;
+use std::sync::Arc;
+
+use crynn_cookies::CookieManager;
+use crynn_crash::CrashStore;
+use crynn_engine::{ResourceGovernor, SitePrefStore, TabId, TabRegistry};
+use crynn_i18n::Catalog;
+use crynn_metrics::MetricsStore;
+use crynn_network::{NetworkManager, SuggestionsClient};
+use crynn_permissions::PermissionStore;
+use crynn_storage::StorageManager;
+use crynn_tracking_protection::{StrictnessLevel, TrackingGuard};
+
+use crate::about::AboutPages;
+use crate::audio::AudioGuard;
+use crate::autoplay::AutoplayGuard;
+use crate::autoplay_view::AutoplayIndicator;
+use crate::command_palette::CommandPalette;
+use crate::content_settings::ContentSettingsGuard;
+use crate::cookie_panel::CookiePanel;
+use crate::crashes_view::CrashesView;
+use crate::tab_groups::TabGroupsView;
+use crate::tab_proxy_menu::TabProxyMenu;
+use crate::events::EventBus;
+use crate::logs_view::LogsView;
+use crate::network_panel::NetworkPanel;
+use crate::page_info::PageInfo;
+use crate::performance_view::PerformanceView;
+use crate::popups::PopupGuard;
+use crate::popups_view::PopupIndicator;
+use crate::protocol_handlers::{PendingExternalLaunch, ProtocolHandlerRegistry};
+use crate::retention::MaintenanceScheduler;
+use crate::url_utils::KeywordShortcuts;
+use crate::shield_view::ShieldView;
+use crate::site_settings_panel::SiteSettingsPanel;
+use crate::startup::{EmailHelperHandle, LazySubsystem, VpnHelperHandle};
+use crate::status_bar::StatusBar;
+use crate::system_conditions::SystemConditionsMonitor;
+use crate::telemetry_view::TelemetryView;
+use crate::toasts::ToastCenter;
+use crate::zoom::ZoomStore;
+
+/// Everything an [`Action`](crate::actions::Action) handler needs to act on.
+/// Grows as more subsystems are wired into the shell; new built-in actions
+/// should read/write through here rather than taking ad hoc parameters.
+pub struct ShellState {
+    pub engine: TabRegistry,
+    pub performance_view: PerformanceView,
+    pub status_bar: StatusBar,
+    pub command_palette: CommandPalette,
+    pub page_info: PageInfo,
+    pub shield_view: ShieldView,
+    pub tracking: TrackingGuard,
+    pub zoom_store: ZoomStore,
+    pub active_tab: Option<TabId>,
+    pub events: EventBus,
+    pub toasts: ToastCenter,
+    pub config: crynn_config::ConfigManager,
+    pub logs_view: LogsView,
+    pub network_panel: NetworkPanel,
+    pub log_handle: Option<Arc<crynn_log::LogHandle>>,
+    pub i18n: Catalog,
+    pub metrics: MetricsStore,
+    pub telemetry_view: TelemetryView,
+    pub permissions: PermissionStore,
+    pub storage: Option<StorageManager>,
+    pub about_pages: AboutPages,
+    pub tab_groups_view: TabGroupsView,
+    pub tab_proxy_menu: TabProxyMenu,
+    pub cookies: CookieManager,
+    pub cookie_panel: CookiePanel,
+    /// Crash reports written by this process's panic hook, or read back
+    /// from disk from a previous session's crash. Never uploaded; see
+    /// [`crate::crashes_view::CrashesView`].
+    pub crashes: CrashStore,
+    pub crashes_view: CrashesView,
+    /// Deferred until the first request goes out, so cold start doesn't
+    /// pay for a connection pool before any tab has navigated anywhere.
+    pub network: LazySubsystem<NetworkManager>,
+    /// Deferred until the inbox is opened; see [`LazySubsystem`].
+    pub email: LazySubsystem<EmailHelperHandle>,
+    /// Deferred until the VPN is toggled on; see [`LazySubsystem`].
+    pub vpn: LazySubsystem<VpnHelperHandle>,
+    pub captive_portal_url: Option<String>,
+    pub protocol_handlers: ProtocolHandlerRegistry,
+    pub pending_external_launch: Option<PendingExternalLaunch>,
+    pub keyword_shortcuts: KeywordShortcuts,
+    /// `None` when `config.search.suggestions_enabled` is off, so the
+    /// omnibox never fetches remote completions without the user's
+    /// consent.
+    pub search_suggestions: Option<SuggestionsClient>,
+    /// Per-site overrides (JavaScript, fingerprinting resistance,
+    /// autoplay) the settings panel edits, applied to the engine on
+    /// navigation once a real [`crynn_engine::EnginePrefs`] sink exists.
+    pub site_prefs: SitePrefStore,
+    pub site_settings_panel: SiteSettingsPanel,
+    pub autoplay: AutoplayGuard,
+    pub autoplay_indicator: AutoplayIndicator,
+    /// Global mute and background-tab auto-muting; see [`crate::audio`].
+    pub audio: AudioGuard,
+    /// Which content categories (images, JavaScript) ended up blocked on
+    /// the current page per tab, combining [`Self::config`]'s
+    /// `content` defaults with [`Self::site_prefs`]'s overrides; backs
+    /// the status bar's blocked-content indicator that opens
+    /// [`Self::site_settings_panel`].
+    pub content_settings: ContentSettingsGuard,
+    pub popups: PopupGuard,
+    pub popup_indicator: PopupIndicator,
+    /// Battery/metered-connection/low-memory state, broadcast to
+    /// whatever subsystem's activity should scale back under it (email
+    /// sync, prefetching, cache write batching, VPN auto-connect).
+    pub system_conditions: SystemConditionsMonitor,
+    /// Scheduled history/cookie cleanup; see [`crate::retention`].
+    pub maintenance_scheduler: MaintenanceScheduler,
+    /// Connection profiles, current status, and session history for the
+    /// VPN panel. Separate from [`ShellState::vpn`], which is the
+    /// not-yet-real out-of-process helper handle rather than this
+    /// in-memory decision state.
+    pub vpn_manager: crynn_vpn::VpnManager,
+    /// Watches per-tab memory/CPU against [`crate::resource_governor`]'s
+    /// limits; [`crate::enforce_resource_limits`] drives it each tick.
+    pub resource_governor: crynn_engine::ResourceGovernor,
+}
+
+impl ShellState {
+    /// Opens a tab and immediately applies the site's persisted zoom
+    /// level, so returning to a site restores the zoom the user chose
+    /// last time rather than resetting to 100%. Then restores the rest
+    /// of the site's [`crynn_storage::ViewState`] (scroll position, text
+    /// size, and zoom again if one was saved there) via
+    /// [`crate::view_state::restore_view_state`] — ignoring a storage
+    /// error the same way the rest of this constructor treats a missing
+    /// or unreadable store as nothing to restore.
+    pub fn open_tab(&mut self, url: impl Into<String>, title: impl Into<String>) -> TabId {
+        let url = url.into();
+        let zoom = self.zoom_store.get(&url);
+        let id = self.engine.open(url, title);
+        if let Some(tab) = self.engine.get_mut(id) {
+            tab.set_zoom(zoom);
+        }
+        let _ = crate::view_state::restore_view_state(self, id);
+        id
+    }
+
+    /// The tab zoom/scroll controls and similar per-tab actions apply to,
+    /// i.e. whichever tab is currently selected in the tab strip.
+    pub fn active_tab(&self) -> Option<&crynn_engine::Tab> {
+        self.active_tab.and_then(|id| self.engine.get(id))
+    }
+
+    pub fn active_tab_mut(&mut self) -> Option<&mut crynn_engine::Tab> {
+        let id = self.active_tab?;
+        self.engine.get_mut(id)
+    }
+
+    /// Applies `level` to the active tab's engine zoom and persists it for
+    /// the tab's site.
+    pub fn set_active_tab_zoom(&mut self, level: f32) {
+        let Some(id) = self.active_tab else { return };
+        let Some(tab) = self.engine.get_mut(id) else {
+            return;
+        };
+        tab.set_zoom(level);
+        let url = tab.url().to_string();
+        let zoom = tab.zoom();
+        self.zoom_store.set(&url, zoom);
+    }
+
+    /// Pushes `config.logging.level` into the live tracing subscriber.
+    /// Call after loading config and after every [`Self::config`] change
+    /// (e.g. from a `config.on_change` listener) so settings take effect
+    /// without a restart.
+    pub fn apply_log_level(&self) {
+        if let Some(handle) = &self.log_handle {
+            let _ = handle.set_level(&self.config.config().logging.level);
+        }
+    }
+
+    /// Pushes every section `changed` flags into its live subsystem, the
+    /// same way [`Self::apply_log_level`] does for `logging` alone —
+    /// register this as the body of a `config.on_change` listener so an
+    /// externally edited `config.toml` (or [`Self::config`]'s own
+    /// [`crynn_config::ConfigManager::reload_if_changed`]) takes effect
+    /// without restarting. Sections with no live subsystem to push into
+    /// (`storage`, `vpn`, `email`, `updates`, `content`, `privacy`,
+    /// `power`) are read fresh wherever they're consulted instead, so
+    /// there's nothing to re-apply here for them.
+    pub fn apply_config_change(&mut self, changed: crynn_config::ChangedSections) {
+        if changed.logging {
+            self.apply_log_level();
+        }
+        if changed.tracking {
+            if let Ok(strictness) = StrictnessLevel::from_str(&self.config.config().tracking.strictness) {
+                self.tracking.set_strictness(strictness);
+            }
+        }
+        if changed.cookies {
+            if let Ok(policy) = crynn_cookies::CookiePolicy::from_str(&self.config.config().cookies.policy) {
+                self.cookies.set_policy(policy);
+            }
+        }
+        if changed.metrics {
+            self.metrics.set_enabled(self.config.config().metrics.enabled);
+        }
+        if changed.search {
+            let search = &self.config.config().search;
+            self.search_suggestions = search.suggestions_enabled.then(|| SuggestionsClient::new(&search.suggest_url));
+        }
+    }
+
+    /// Whether the active locale's script reads right-to-left, so the
+    /// shell's chrome can mirror its layout direction.
+    pub fn is_rtl(&self) -> bool {
+        crynn_i18n::is_rtl(self.i18n.current_locale())
+    }
+
+    /// Where the `about:telemetry` export button writes to. Exposed so the
+    /// render loop doesn't need to duplicate the path convention.
+    pub fn telemetry_export_path(&self) -> std::path::PathBuf {
+        crate::telemetry_view::default_export_path()
+            .unwrap_or_else(|| std::env::temp_dir().join("crynn-telemetry-export.json"))
+    }
+
+    /// Where the `about:crashes` export button writes to. Exposed so the
+    /// render loop doesn't need to duplicate the path convention.
+    pub fn crashes_export_path(&self) -> std::path::PathBuf {
+        crate::crashes_view::default_export_path()
+            .unwrap_or_else(|| std::env::temp_dir().join("crynn-crashes-export.json"))
+    }
+
+    /// Persists the current tabs and groups so the next launch can restore
+    /// them. Call on a clean shutdown; a crash simply loses the session
+    /// like it would without this at all.
+    pub fn save_session(&self) {
+        if let Some(path) = crate::session::default_store_path() {
+            if let Err(err) = crate::session::save(&path, &self.engine) {
+                tracing::warn!(%err, "failed to save session");
+            }
+        }
+    }
+}
+
+impl Default for ShellState {
+    fn default() -> Self {
+        let zoom_store = crate::zoom::default_store_path()
+            .and_then(|path| ZoomStore::load(path).ok())
+            .unwrap_or_default();
+        let config = crate::zoom::default_store_path()
+            .map(|p| p.with_file_name("config.toml"))
+            .and_then(|path| crynn_config::ConfigManager::load(path).ok())
+            .or_else(|| crynn_config::ConfigManager::without_file().ok())
+            .expect("defaults-only config load cannot fail");
+        let strictness = StrictnessLevel::from_str(&config.config().tracking.strictness)
+            .unwrap_or_default();
+        let cookie_policy = crynn_cookies::CookiePolicy::from_str(&config.config().cookies.policy)
+            .unwrap_or_default();
+        let i18n = Catalog::new(&config.config().locale.locale);
+        let metrics = crate::telemetry_view::default_store_path()
+            .and_then(|path| MetricsStore::load(path, config.config().metrics.enabled).ok())
+            .unwrap_or_default();
+        let permissions = crate::push::default_store_path()
+            .and_then(|path| PermissionStore::load(path).ok())
+            .unwrap_or_default();
+        let crashes = crate::crashes_view::default_store_path()
+            .and_then(|path| CrashStore::load(path).ok())
+            .unwrap_or_default();
+        let storage = crate::about::default_storage_dir()
+            .and_then(|dir| StorageManager::open(dir, None).ok());
+        let engine = crate::session::default_store_path()
+            .and_then(|path| crate::session::load(&path).ok())
+            .map(TabRegistry::restore)
+            .unwrap_or_default();
+        let search_suggestions =
+            config.config().search.suggestions_enabled.then(|| SuggestionsClient::new(&config.config().search.suggest_url));
+        Self {
+            engine,
+            performance_view: PerformanceView::default(),
+            status_bar: StatusBar,
+            command_palette: CommandPalette::default(),
+            page_info: PageInfo::default(),
+            shield_view: ShieldView::default(),
+            tracking: TrackingGuard::new(strictness),
+            zoom_store,
+            active_tab: None,
+            events: EventBus::default(),
+            toasts: ToastCenter::default(),
+            config,
+            logs_view: LogsView::default(),
+            network_panel: NetworkPanel::default(),
+            log_handle: None,
+            i18n,
+            metrics,
+            telemetry_view: TelemetryView::default(),
+            permissions,
+            storage,
+            about_pages: AboutPages::default(),
+            tab_groups_view: TabGroupsView::default(),
+            tab_proxy_menu: TabProxyMenu::default(),
+            cookies: CookieManager::new(cookie_policy),
+            cookie_panel: CookiePanel::default(),
+            crashes,
+            crashes_view: CrashesView::default(),
+            network: LazySubsystem::new("Network"),
+            email: LazySubsystem::new("Email helper"),
+            vpn: LazySubsystem::new("VPN helper"),
+            captive_portal_url: None,
+            protocol_handlers: ProtocolHandlerRegistry::default(),
+            pending_external_launch: None,
+            keyword_shortcuts: KeywordShortcuts::default(),
+            search_suggestions,
+            site_prefs: SitePrefStore::new(),
+            site_settings_panel: SiteSettingsPanel::default(),
+            autoplay: AutoplayGuard::default(),
+            autoplay_indicator: AutoplayIndicator::default(),
+            audio: AudioGuard::default(),
+            content_settings: ContentSettingsGuard::default(),
+            popups: PopupGuard::default(),
+            popup_indicator: PopupIndicator::default(),
+            system_conditions: SystemConditionsMonitor::default(),
+            maintenance_scheduler: MaintenanceScheduler::default(),
+            vpn_manager: crynn_vpn::VpnManager::default(),
+            resource_governor: ResourceGovernor::default(),
+        }
+    }
+}
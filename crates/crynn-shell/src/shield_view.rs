@@ -0,0 +1,34 @@
+use crynn_tracking_protection::StrictnessLevel;
+
+/// The popover opened from the status bar's shield icon: how many
+/// trackers tracking protection has blocked on the active tab, and a
+/// picker for the strictness level that decides what gets blocked.
+#[derive(Default)]
+pub struct ShieldView {
+    open: bool,
+}
+
+impl ShieldView {
+    pub fn toggle(&mut self) {
+        self.open = !self.open;
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    pub fn ui(&mut self, ui: &mut egui::Ui, blocked_message: impl Into<String>, strictness: &mut StrictnessLevel) {
+        if !self.open {
+            return;
+        }
+        ui.label(blocked_message.into());
+        ui.horizontal(|ui| {
+            if ui.selectable_label(matches!(strictness, StrictnessLevel::Standard), "Standard").clicked() {
+                *strictness = StrictnessLevel::Standard;
+            }
+            if ui.selectable_label(matches!(strictness, StrictnessLevel::Strict), "Strict").clicked() {
+                *strictness = StrictnessLevel::Strict;
+            }
+        });
+    }
+}
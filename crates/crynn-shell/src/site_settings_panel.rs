@@ -0,0 +1,105 @@
+use crynn_engine::{
+    AutoplayPolicy, PrefValue, SitePrefStore, PREF_AUTOPLAY_POLICY, PREF_FORCE_DARK_MODE, PREF_IMAGES_ENABLED, PREF_JAVASCRIPT_ENABLED,
+    PREF_POPUPS_ENABLED, PREF_RESIST_FINGERPRINTING,
+};
+
+/// The per-site settings panel: JavaScript, images, popups, fingerprinting
+/// resistance, and autoplay, each overridable for the active site on top
+/// of `crynn_config::ContentConfig`'s global defaults. Opened from the
+/// status bar the same way [`crate::cookie_panel::CookiePanel`] and
+/// [`crate::page_info::PageInfo`] are.
+#[derive(Default)]
+pub struct SiteSettingsPanel {
+    open: bool,
+}
+
+impl SiteSettingsPanel {
+    pub fn toggle(&mut self) {
+        self.open = !self.open;
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    pub fn ui(
+        &mut self,
+        ui: &mut egui::Ui,
+        origin: &str,
+        site_prefs: &mut SitePrefStore,
+        images_enabled_by_default: bool,
+        popups_enabled_by_default: bool,
+        shell_theme_is_dark: bool,
+    ) {
+        if !self.open {
+            return;
+        }
+        bool_row(ui, site_prefs, origin, "JavaScript", PREF_JAVASCRIPT_ENABLED, true);
+        bool_row(ui, site_prefs, origin, "Images", PREF_IMAGES_ENABLED, images_enabled_by_default);
+        bool_row(ui, site_prefs, origin, "Popups", PREF_POPUPS_ENABLED, popups_enabled_by_default);
+        bool_row(ui, site_prefs, origin, "Resist fingerprinting", PREF_RESIST_FINGERPRINTING, false);
+        bool_row(ui, site_prefs, origin, "Force dark", PREF_FORCE_DARK_MODE, shell_theme_is_dark);
+        autoplay_row(ui, site_prefs, origin);
+    }
+}
+
+fn bool_row(ui: &mut egui::Ui, site_prefs: &mut SitePrefStore, origin: &str, label: &str, pref_name: &str, default: bool) {
+    let current = current_bool(site_prefs, origin, pref_name, default);
+    ui.horizontal(|ui| {
+        ui.label(label);
+        let mut enabled = current;
+        if ui.checkbox(&mut enabled, "").changed() {
+            if enabled == default {
+                site_prefs.clear(origin, pref_name);
+            } else {
+                site_prefs.set(origin, pref_name, PrefValue::Bool(enabled));
+            }
+        }
+    });
+}
+
+fn current_bool(site_prefs: &SitePrefStore, origin: &str, pref_name: &str, default: bool) -> bool {
+    site_prefs.bool_pref(origin, pref_name, default)
+}
+
+fn autoplay_row(ui: &mut egui::Ui, site_prefs: &mut SitePrefStore, origin: &str) {
+    ui.horizontal(|ui| {
+        ui.label("Autoplay");
+        for (policy, label) in [
+            (AutoplayPolicy::Allowed, "Allow"),
+            (AutoplayPolicy::BlockAudible, "Block audible"),
+            (AutoplayPolicy::BlockAll, "Block all"),
+        ] {
+            if ui.button(label).clicked() {
+                site_prefs.set(origin, PREF_AUTOPLAY_POLICY, crynn_engine::autoplay_policy_pref(policy));
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn current_bool_falls_back_to_the_default_without_an_override() {
+        let store = SitePrefStore::new();
+        assert!(current_bool(&store, "https://example.com", PREF_JAVASCRIPT_ENABLED, true));
+        assert!(!current_bool(&store, "https://example.com", PREF_RESIST_FINGERPRINTING, false));
+    }
+
+    #[test]
+    fn current_bool_reflects_a_recorded_override() {
+        let mut store = SitePrefStore::new();
+        store.set("https://example.com", PREF_JAVASCRIPT_ENABLED, PrefValue::Bool(false));
+        assert!(!current_bool(&store, "https://example.com", PREF_JAVASCRIPT_ENABLED, true));
+    }
+
+    #[test]
+    fn toggle_flips_open_state() {
+        let mut panel = SiteSettingsPanel::default();
+        assert!(!panel.is_open());
+        panel.toggle();
+        assert!(panel.is_open());
+    }
+}
@@ -0,0 +1,144 @@
+use std::time::Instant;
+
+/// How expensive one subsystem's first initialization turned out to be:
+/// wall-clock time and the resulting change in the process's resident
+/// memory. Cheap to keep around afterwards, so `about:memory` can show a
+/// permanent record rather than only what's true at the instant it's
+/// viewed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InitCost {
+    pub init_time_ms: u64,
+    pub rss_delta_bytes: i64,
+}
+
+/// A subsystem that isn't built until something actually needs it.
+/// Cold start only pays for what the user's first few seconds touch —
+/// [`crynn_storage::StorageManager`] stays eager since history/bookmarks
+/// are read on launch, but the network stack and the email/VPN helper
+/// processes sit uninitialized until a tab fetches something, the inbox
+/// is opened, or the VPN toggle is flipped.
+pub struct LazySubsystem<T> {
+    label: &'static str,
+    value: Option<T>,
+    cost: Option<InitCost>,
+}
+
+impl<T> LazySubsystem<T> {
+    pub fn new(label: &'static str) -> Self {
+        Self { label, value: None, cost: None }
+    }
+
+    pub fn label(&self) -> &'static str {
+        self.label
+    }
+
+    pub fn is_initialized(&self) -> bool {
+        self.value.is_some()
+    }
+
+    /// The cost of the initialization that already happened, or `None`
+    /// if `ensure` hasn't run yet.
+    pub fn cost(&self) -> Option<InitCost> {
+        self.cost
+    }
+
+    /// The value if it's already been initialized, without forcing
+    /// initialization. `about:network` reads through this rather than
+    /// `ensure` so merely opening the page never counts as "first use".
+    pub fn peek(&self) -> Option<&T> {
+        self.value.as_ref()
+    }
+
+    /// Initializes on first call, recording how long `init` took and how
+    /// much the process's RSS grew while it ran. Later calls are a cheap
+    /// no-op that just returns the value built the first time.
+    pub fn ensure(&mut self, init: impl FnOnce() -> T) -> &mut T {
+        if self.value.is_none() {
+            let start = Instant::now();
+            let rss_before = current_rss_bytes();
+            let value = init();
+            self.cost = Some(InitCost {
+                init_time_ms: start.elapsed().as_millis() as u64,
+                rss_delta_bytes: current_rss_bytes() as i64 - rss_before as i64,
+            });
+            self.value = Some(value);
+        }
+        self.value.as_mut().expect("just initialized above")
+    }
+}
+
+/// Marker for the out-of-process email-sync helper, once started. Really
+/// spawning it is `crynn_ipc::Supervisor::spawn`'s job once this shell
+/// has a concrete helper binary and account to pass it; until then this
+/// is just the "it's running" value [`LazySubsystem::ensure`] holds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EmailHelperHandle;
+
+/// Marker for the out-of-process VPN helper, once started. See
+/// [`EmailHelperHandle`] for why this isn't a real `crynn-ipc` handle yet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VpnHelperHandle;
+
+/// Best-effort resident memory for this process, read from
+/// `/proc/self/status` on Linux. Returns 0 off Linux or if the read
+/// fails, same fallback as `crynn_ipc::Supervisor::memory_bytes`.
+#[cfg(target_os = "linux")]
+fn current_rss_bytes() -> u64 {
+    let Ok(status) = std::fs::read_to_string("/proc/self/status") else {
+        return 0;
+    };
+    for line in status.lines() {
+        if let Some(rest) = line.strip_prefix("VmRSS:") {
+            let kb: u64 = rest.trim().trim_end_matches("kB").trim().parse().unwrap_or(0);
+            return kb * 1024;
+        }
+    }
+    0
+}
+
+#[cfg(not(target_os = "linux"))]
+fn current_rss_bytes() -> u64 {
+    0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ensure_only_runs_init_once() {
+        let mut subsystem = LazySubsystem::new("Network");
+        assert!(!subsystem.is_initialized());
+
+        let mut calls = 0;
+        subsystem.ensure(|| {
+            calls += 1;
+            42
+        });
+        subsystem.ensure(|| {
+            calls += 1;
+            43
+        });
+
+        assert_eq!(calls, 1);
+        assert_eq!(subsystem.peek(), Some(&42));
+        assert!(subsystem.is_initialized());
+    }
+
+    #[test]
+    fn cost_is_recorded_only_after_ensure_runs() {
+        let mut subsystem = LazySubsystem::new("Email helper");
+        assert_eq!(subsystem.cost(), None);
+
+        subsystem.ensure(|| EmailHelperHandle);
+
+        assert!(subsystem.cost().is_some());
+    }
+
+    #[test]
+    fn peek_never_forces_initialization() {
+        let subsystem: LazySubsystem<VpnHelperHandle> = LazySubsystem::new("VPN helper");
+        assert_eq!(subsystem.peek(), None);
+        assert!(!subsystem.is_initialized());
+    }
+}
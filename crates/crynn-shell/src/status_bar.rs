@@ -0,0 +1,186 @@
+use crynn_cookies::CookieManager;
+use crynn_engine::{SitePrefStore, Tab, TabRegistry};
+use crynn_i18n::Catalog;
+use crynn_permissions::PermissionStore;
+use crynn_tracking_protection::TrackingGuard;
+
+use crate::autoplay::AutoplayGuard;
+use crate::autoplay_view::{AutoplayContext, AutoplayIndicator};
+use crate::content_settings::ContentSettingsGuard;
+use crate::cookie_panel::CookiePanel;
+use crate::page_info::PageInfo;
+use crate::popups::PopupGuard;
+use crate::popups_view::{PopupContext, PopupIndicator};
+use crate::shield_view::ShieldView;
+use crate::site_settings_panel::SiteSettingsPanel;
+use crate::PerformanceView;
+
+/// Site-specific details the status bar doesn't own the source of truth
+/// for: cookie count and permissions come from subsystems that live
+/// outside this crate, and `i18n` is threaded through here rather than
+/// as its own parameter since every localized label below only renders
+/// once there's an active tab.
+pub struct ActiveTabInfo<'a> {
+    pub tab: &'a Tab,
+    pub cookie_count: usize,
+    pub permissions: &'a [&'a str],
+    pub tracking: &'a mut TrackingGuard,
+    pub shield_view: &'a mut ShieldView,
+    pub cookies: &'a mut CookieManager,
+    pub cookie_panel: &'a mut CookiePanel,
+    pub permission_store: &'a mut PermissionStore,
+    pub i18n: &'a Catalog,
+    pub autoplay: &'a mut AutoplayGuard,
+    pub autoplay_indicator: &'a mut AutoplayIndicator,
+    pub site_prefs: &'a mut SitePrefStore,
+    pub content_settings: &'a mut ContentSettingsGuard,
+    pub site_settings_panel: &'a mut SiteSettingsPanel,
+    pub images_enabled_by_default: bool,
+    pub popups_enabled_by_default: bool,
+    pub popups: &'a mut PopupGuard,
+    pub popup_indicator: &'a mut PopupIndicator,
+}
+
+/// The bottom status bar: memory usage (doubling as the task-manager entry
+/// point), hovered-link preview, and the page-info padlock.
+#[derive(Default)]
+pub struct StatusBar;
+
+impl StatusBar {
+    pub fn ui(
+        &self,
+        ui: &mut egui::Ui,
+        engine: &mut TabRegistry,
+        performance_view: &mut PerformanceView,
+        active_tab: Option<ActiveTabInfo<'_>>,
+        page_info: &mut PageInfo,
+        rtl: bool,
+    ) {
+        let layout = if rtl {
+            egui::Layout::right_to_left(egui::Align::Center)
+        } else {
+            egui::Layout::left_to_right(egui::Align::Center)
+        };
+        ui.with_layout(layout, |ui| {
+            let snapshot = performance_view.snapshot();
+            let mb = snapshot.total_memory_bytes() / (1024 * 1024);
+            if ui
+                .button(format!("Memory: {mb} MB ({} tabs)", engine.len()))
+                .on_hover_text("Open the task manager")
+                .clicked()
+            {
+                performance_view.toggle();
+            }
+
+            if let Some(info) = active_tab {
+                if ui.button("🔒").on_hover_text(info.i18n.message("page-info-tooltip")).clicked() {
+                    page_info.toggle();
+                }
+                if page_info.is_open() {
+                    page_info.ui(ui, info.tab, info.cookie_count, info.permissions);
+                }
+
+                if ui.button("🍪").on_hover_text("Cookies for this site").clicked() {
+                    info.cookie_panel.toggle();
+                }
+                if info.cookie_panel.is_open() {
+                    let origin = crynn_permissions::origin_of(info.tab.url());
+                    info.cookie_panel.ui(ui, &origin, info.cookies, info.permission_store);
+                }
+
+                let blocked = info.tracking.blocked_count(info.tab.id());
+                let shield_label = if blocked > 0 {
+                    format!("🛡 {blocked}")
+                } else {
+                    "🛡".to_string()
+                };
+                if ui
+                    .button(shield_label)
+                    .on_hover_text(info.i18n.message("tracking-protection-tooltip"))
+                    .clicked()
+                {
+                    info.shield_view.toggle();
+                }
+                if info.shield_view.is_open() {
+                    let mut strictness = info.tracking.strictness().clone();
+                    info.shield_view.ui(ui, info.i18n.message_with_count("trackers-blocked", blocked), &mut strictness);
+                    if strictness != *info.tracking.strictness() {
+                        info.tracking.set_strictness(strictness);
+                    }
+                }
+
+                let origin = crynn_permissions::origin_of(info.tab.url());
+                let blocked_media = info.autoplay.blocked_count(info.tab.id());
+                let autoplay_label = if blocked_media > 0 {
+                    format!("🔇 {blocked_media}")
+                } else {
+                    "🔇".to_string()
+                };
+                if ui.button(autoplay_label).on_hover_text(info.i18n.message("autoplay-tooltip")).clicked() {
+                    info.autoplay_indicator.toggle();
+                }
+                if info.autoplay_indicator.is_open() {
+                    info.autoplay_indicator.ui(
+                        ui,
+                        info.i18n.message_with_count("media-blocked", blocked_media),
+                        AutoplayContext {
+                            guard: info.autoplay,
+                            site_prefs: info.site_prefs,
+                            permissions: info.permission_store,
+                            tab: info.tab.id(),
+                            origin: &origin,
+                        },
+                    );
+                }
+
+                let blocked_content = info.content_settings.blocked_count(info.tab.id());
+                let content_label = if blocked_content > 0 {
+                    format!("🚫 {blocked_content}")
+                } else {
+                    "🚫".to_string()
+                };
+                if ui.button(content_label).on_hover_text(info.i18n.message("site-settings-tooltip")).clicked() {
+                    info.site_settings_panel.toggle();
+                }
+                if info.site_settings_panel.is_open() {
+                    info.site_settings_panel.ui(
+                        ui,
+                        &origin,
+                        info.site_prefs,
+                        info.images_enabled_by_default,
+                        info.popups_enabled_by_default,
+                        ui.visuals().dark_mode,
+                    );
+                }
+
+                let blocked_popups = info.popups.blocked_count(info.tab.id());
+                let popups_label = if blocked_popups > 0 {
+                    format!("🗗 {blocked_popups}")
+                } else {
+                    "🗗".to_string()
+                };
+                if ui.button(popups_label).on_hover_text(info.i18n.message("popups-tooltip")).clicked() {
+                    info.popup_indicator.toggle();
+                }
+                if info.popup_indicator.is_open() {
+                    info.popup_indicator.ui(
+                        ui,
+                        PopupContext {
+                            guard: info.popups,
+                            engine,
+                            site_prefs: info.site_prefs,
+                            permissions: info.permission_store,
+                            tab: info.tab.id(),
+                            origin: &origin,
+                        },
+                    );
+                }
+
+                if let Some(hovered) = info.tab.hovered_link() {
+                    ui.separator();
+                    ui.label(hovered);
+                }
+            }
+        });
+    }
+}
@@ -0,0 +1,31 @@
+use crate::events::{Severity, ShellEvent};
+use crate::ShellState;
+
+/// Triggers an immediate sync instead of waiting for a scheduled one —
+/// the manual counterpart a "Sync Now" command calls. No transport is
+/// configured anywhere in the shell yet (`crynn_sync::WebDavTransport`
+/// is the one this will drive, once a WebDAV URL and passphrase live in
+/// settings), so for now this reports that rather than silently doing
+/// nothing.
+pub fn sync_now(state: &mut ShellState) {
+    state.events.post(ShellEvent::new(
+        Severity::Info,
+        "Sync isn't set up yet — add a WebDAV URL and passphrase in settings first.",
+    ));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sync_now_posts_a_not_configured_notice() {
+        let mut state = ShellState::default();
+
+        sync_now(&mut state);
+
+        let events = state.events.drain();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].severity, Severity::Info);
+    }
+}
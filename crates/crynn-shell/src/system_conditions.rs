@@ -0,0 +1,151 @@
+use crynn_config::PowerConfig;
+use crynn_email::ConnectionConstraints;
+
+/// The system state this shell watches and broadcasts to every
+/// subscribed subsystem: battery power, a metered network connection,
+/// and memory pressure. Nothing in this crate detects these itself —
+/// whatever embeds Crynn on a given platform calls
+/// [`SystemConditionsMonitor::set`] from its own OS hooks (a battery
+/// status API, the platform's "on a metered connection" flag, a
+/// memory-pressure notification), the same split
+/// [`crynn_engine::EnginePrefs`] has between deciding a value and
+/// actually pushing it somewhere.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SystemConditions {
+    pub on_battery: bool,
+    pub metered: bool,
+    pub low_memory: bool,
+}
+
+impl SystemConditions {
+    /// What [`crynn_email::SyncScheduler::is_due`] backs sync intervals
+    /// off against.
+    pub fn email_constraints(&self) -> ConnectionConstraints {
+        ConnectionConstraints { on_battery: self.on_battery, metered: self.metered }
+    }
+
+    /// Whether speculative prefetching (preconnects, link preloading)
+    /// should run at all: it's pure upside when nothing is constrained
+    /// and pure cost under any of the three.
+    pub fn should_prefetch(&self) -> bool {
+        !self.on_battery && !self.metered && !self.low_memory
+    }
+
+    /// How many writes a cache should batch together before flushing to
+    /// disk, from `policy`. More aggressive batching under constraint
+    /// trades a larger loss window on crash for fewer wakeups/writes.
+    pub fn cache_batch_size(&self, policy: &PowerConfig) -> u32 {
+        if self.on_battery || self.metered || self.low_memory {
+            policy.constrained_cache_batch_size
+        } else {
+            policy.default_cache_batch_size
+        }
+    }
+
+    /// Whether the VPN's auto-connect-on-launch should wait for
+    /// conditions to improve rather than connecting immediately. Only
+    /// a metered connection matters here — a tunnel adds overhead that
+    /// costs more data, not less, so auto-connecting through one is the
+    /// opposite of what a metered connection wants.
+    pub fn should_defer_vpn_auto_connect(&self) -> bool {
+        self.metered
+    }
+}
+
+type ConditionsListener = Box<dyn Fn(SystemConditions)>;
+
+/// Watches [`SystemConditions`] and broadcasts changes to subscribers —
+/// email sync, prefetching, cache writes, and VPN auto-connect all read
+/// the latest value through here instead of each polling for it
+/// separately. Mirrors [`crynn_config::ConfigManager`]'s
+/// `on_change`/listener shape.
+#[derive(Default)]
+pub struct SystemConditionsMonitor {
+    current: SystemConditions,
+    listeners: Vec<ConditionsListener>,
+}
+
+impl SystemConditionsMonitor {
+    pub fn current(&self) -> SystemConditions {
+        self.current
+    }
+
+    pub fn on_change(&mut self, listener: impl Fn(SystemConditions) + 'static) {
+        self.listeners.push(Box::new(listener));
+    }
+
+    /// Updates the watched conditions and notifies subscribers, but only
+    /// when something actually changed — a platform hook that polls and
+    /// reports the same state every tick shouldn't re-run every
+    /// subscriber's reaction each time.
+    pub fn set(&mut self, conditions: SystemConditions) {
+        if conditions == self.current {
+            return;
+        }
+        self.current = conditions;
+        for listener in &self.listeners {
+            listener(self.current);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    #[test]
+    fn prefetch_stops_under_any_single_constraint() {
+        assert!(SystemConditions::default().should_prefetch());
+        assert!(!SystemConditions { on_battery: true, ..Default::default() }.should_prefetch());
+        assert!(!SystemConditions { metered: true, ..Default::default() }.should_prefetch());
+        assert!(!SystemConditions { low_memory: true, ..Default::default() }.should_prefetch());
+    }
+
+    #[test]
+    fn email_constraints_carries_over_battery_and_metered_only() {
+        let conditions = SystemConditions { on_battery: true, metered: false, low_memory: true };
+        assert_eq!(conditions.email_constraints(), ConnectionConstraints { on_battery: true, metered: false });
+    }
+
+    #[test]
+    fn cache_batching_gets_more_aggressive_under_constraint() {
+        let policy = PowerConfig::default();
+        assert_eq!(SystemConditions::default().cache_batch_size(&policy), policy.default_cache_batch_size);
+        let constrained = SystemConditions { low_memory: true, ..Default::default() };
+        assert_eq!(constrained.cache_batch_size(&policy), policy.constrained_cache_batch_size);
+    }
+
+    #[test]
+    fn vpn_auto_connect_defers_only_while_metered() {
+        assert!(!SystemConditions { on_battery: true, ..Default::default() }.should_defer_vpn_auto_connect());
+        assert!(SystemConditions { metered: true, ..Default::default() }.should_defer_vpn_auto_connect());
+    }
+
+    #[test]
+    fn setting_conditions_notifies_subscribers() {
+        let mut monitor = SystemConditionsMonitor::default();
+        let seen: Rc<Cell<Option<SystemConditions>>> = Rc::new(Cell::new(None));
+        let seen_clone = seen.clone();
+        monitor.on_change(move |conditions| seen_clone.set(Some(conditions)));
+
+        monitor.set(SystemConditions { metered: true, ..Default::default() });
+
+        assert_eq!(seen.get(), Some(SystemConditions { metered: true, ..Default::default() }));
+        assert_eq!(monitor.current(), SystemConditions { metered: true, ..Default::default() });
+    }
+
+    #[test]
+    fn setting_the_same_conditions_again_does_not_renotify() {
+        let mut monitor = SystemConditionsMonitor::default();
+        let calls = Rc::new(Cell::new(0));
+        let calls_clone = calls.clone();
+        monitor.on_change(move |_| calls_clone.set(calls_clone.get() + 1));
+
+        monitor.set(SystemConditions { on_battery: true, ..Default::default() });
+        monitor.set(SystemConditions { on_battery: true, ..Default::default() });
+
+        assert_eq!(calls.get(), 1);
+    }
+}
@@ -0,0 +1,104 @@
+use crynn_engine::{GroupColor, TabGroupId, TabRegistry};
+
+/// The "Tab Groups" window: create, rename, recolor, and collapse groups,
+/// and move tabs between them. Grouping itself is data the engine owns
+/// ([`crynn_engine::TabGroupRegistry`]); this view only renders it and
+/// turns clicks into `TabRegistry` calls.
+#[derive(Default)]
+pub struct TabGroupsView {
+    open: bool,
+    new_group_name: String,
+}
+
+impl TabGroupsView {
+    pub fn toggle(&mut self) {
+        self.open = !self.open;
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    pub fn ui(&mut self, ctx: &egui::Context, engine: &mut TabRegistry) {
+        if !self.open {
+            return;
+        }
+        let mut open = self.open;
+        egui::Window::new("Tab Groups").open(&mut open).resizable(true).show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.text_edit_singleline(&mut self.new_group_name);
+                if ui.button("New Group").clicked() {
+                    let name = self.new_group_name.trim();
+                    if !name.is_empty() {
+                        engine.create_group(name.to_string(), GroupColor::rgb(120, 150, 220));
+                        self.new_group_name.clear();
+                    }
+                }
+            });
+            ui.separator();
+
+            let group_ids: Vec<TabGroupId> = engine.groups().iter().map(|g| g.id()).collect();
+            for group_id in group_ids {
+                let Some(group) = engine.groups().get(group_id) else { continue };
+                let mut name = group.name().to_string();
+                let mut collapsed = group.is_collapsed();
+                let color = group.color();
+                let mut rgb = [color.r, color.g, color.b];
+
+                ui.horizontal(|ui| {
+                    if ui.color_edit_button_srgb(&mut rgb).changed() {
+                        engine.set_group_color(group_id, GroupColor::rgb(rgb[0], rgb[1], rgb[2]));
+                    }
+                    if ui.text_edit_singleline(&mut name).changed() {
+                        engine.rename_group(group_id, name.clone());
+                    }
+                    if ui.checkbox(&mut collapsed, "Collapsed").changed() {
+                        engine.set_group_collapsed(group_id, collapsed);
+                    }
+                    if ui.button("Remove").clicked() {
+                        engine.remove_group(group_id);
+                    }
+                });
+
+                if !collapsed {
+                    ui.indent(("group-tabs", group_id), |ui| {
+                        for tab in engine.iter().filter(|t| t.group_id() == Some(group_id)) {
+                            ui.label(tab.title());
+                        }
+                    });
+                }
+            }
+
+            ui.separator();
+            ui.label("Move a tab into a group:");
+            let tab_ids: Vec<_> = engine.iter().map(|t| t.id()).collect();
+            for tab_id in tab_ids {
+                let Some(tab) = engine.get(tab_id) else { continue };
+                let title = tab.title().to_string();
+                let current = tab.group_id();
+                ui.horizontal(|ui| {
+                    ui.label(&title);
+                    egui::ComboBox::from_id_salt(("tab-group-picker", tab_id))
+                        .selected_text(
+                            current
+                                .and_then(|id| engine.groups().get(id))
+                                .map(|g| g.name().to_string())
+                                .unwrap_or_else(|| "Ungrouped".to_string()),
+                        )
+                        .show_ui(ui, |ui| {
+                            if ui.selectable_label(current.is_none(), "Ungrouped").clicked() {
+                                engine.remove_tab_from_group(tab_id);
+                            }
+                            for group_id in engine.groups().iter().map(|g| g.id()).collect::<Vec<_>>() {
+                                let Some(group) = engine.groups().get(group_id) else { continue };
+                                if ui.selectable_label(current == Some(group_id), group.name()).clicked() {
+                                    engine.assign_tab_to_group(tab_id, group_id);
+                                }
+                            }
+                        });
+                });
+            }
+        });
+        self.open = open;
+    }
+}
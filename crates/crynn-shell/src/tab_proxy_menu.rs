@@ -0,0 +1,103 @@
+use crynn_engine::{TabId, TabRegistry};
+use crynn_network::ProxyConfig;
+
+/// The tab-strip context menu's proxy entry: pins one tab to Direct, HTTP,
+/// or SOCKS5, overriding whatever its [`crynn_engine::TabGroup`] is set to.
+/// Opens for a single tab at a time, the same as a native right-click menu,
+/// rather than [`crate::site_settings_panel::SiteSettingsPanel`]'s single
+/// always-available toggle.
+#[derive(Default)]
+pub struct TabProxyMenu {
+    open_for: Option<TabId>,
+    host: String,
+    port: String,
+}
+
+impl TabProxyMenu {
+    /// Opens the menu for `tab_id`, discarding whatever host/port was
+    /// being typed for a previously open tab.
+    pub fn open(&mut self, tab_id: TabId) {
+        self.open_for = Some(tab_id);
+        self.host.clear();
+        self.port.clear();
+    }
+
+    pub fn close(&mut self) {
+        self.open_for = None;
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.open_for.is_some()
+    }
+
+    pub fn ui(&mut self, ctx: &egui::Context, engine: &mut TabRegistry) {
+        let Some(tab_id) = self.open_for else { return };
+        let Some(tab) = engine.get(tab_id) else {
+            self.open_for = None;
+            return;
+        };
+        let title = tab.title().to_string();
+        let current = tab.proxy_override().cloned();
+        let mut window_open = true;
+        egui::Window::new(format!("Proxy — {title}")).open(&mut window_open).resizable(false).show(ctx, |ui| {
+            if ui.selectable_label(current.is_none(), "Inherit from group").clicked() {
+                engine.get_mut(tab_id).unwrap().clear_proxy_override();
+            }
+            if ui.selectable_label(current == Some(ProxyConfig::Direct), "Direct").clicked() {
+                engine.get_mut(tab_id).unwrap().set_proxy_override(ProxyConfig::Direct);
+            }
+            ui.separator();
+            ui.horizontal(|ui| {
+                ui.label("Host");
+                ui.text_edit_singleline(&mut self.host);
+                ui.label("Port");
+                ui.text_edit_singleline(&mut self.port);
+            });
+            ui.horizontal(|ui| {
+                if ui.button("Use HTTP").clicked() {
+                    if let Some(port) = parse_port(&self.port) {
+                        engine.get_mut(tab_id).unwrap().set_proxy_override(ProxyConfig::Http { host: self.host.clone(), port });
+                    }
+                }
+                if ui.button("Use SOCKS5").clicked() {
+                    if let Some(port) = parse_port(&self.port) {
+                        engine.get_mut(tab_id).unwrap().set_proxy_override(ProxyConfig::Socks5 { host: self.host.clone(), port });
+                    }
+                }
+            });
+        });
+        if !window_open {
+            self.open_for = None;
+        }
+    }
+}
+
+fn parse_port(text: &str) -> Option<u16> {
+    text.trim().parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn open_close_round_trip() {
+        let mut engine = TabRegistry::new();
+        let tab_id = engine.open("https://example.com", "Example");
+
+        let mut menu = TabProxyMenu::default();
+        assert!(!menu.is_open());
+        menu.open(tab_id);
+        assert!(menu.is_open());
+        menu.close();
+        assert!(!menu.is_open());
+    }
+
+    #[test]
+    fn parse_port_rejects_non_numeric_or_out_of_range_input() {
+        assert_eq!(parse_port("8080"), Some(8080));
+        assert_eq!(parse_port(" 1080 "), Some(1080));
+        assert_eq!(parse_port("not-a-port"), None);
+        assert_eq!(parse_port("999999"), None);
+    }
+}
@@ -0,0 +1,74 @@
+use std::path::PathBuf;
+
+use crynn_metrics::MetricsStore;
+
+/// The `about:telemetry` window: a summary of locally recorded usage
+/// metrics, a toggle for the opt-in setting, and a one-click export —
+/// the only window onto [`MetricsStore`] a user gets, since nothing it
+/// records is ever uploaded.
+#[derive(Default)]
+pub struct TelemetryView {
+    open: bool,
+    last_export: Option<Result<std::path::PathBuf, String>>,
+}
+
+impl TelemetryView {
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    pub fn toggle(&mut self) {
+        self.open = !self.open;
+    }
+
+    pub fn ui(&mut self, ctx: &egui::Context, metrics: &mut MetricsStore, export_path: &std::path::Path) {
+        if !self.open {
+            return;
+        }
+        let mut open = self.open;
+        egui::Window::new("about:telemetry").open(&mut open).show(ctx, |ui| {
+            let mut enabled = metrics.is_enabled();
+            if ui.checkbox(&mut enabled, "Record local usage metrics").changed() {
+                metrics.set_enabled(enabled);
+            }
+            ui.label("Recorded events never leave this device unless you export them below.");
+
+            ui.separator();
+            ui.label(format!("Events recorded: {}", metrics.events().len()));
+            ui.label(format!("Crashes: {}", metrics.crash_count()));
+            ui.label(format!("Budget violations: {}", metrics.budget_violation_count()));
+            match metrics.average_page_load_ms() {
+                Some(avg) => ui.label(format!("Average page load: {avg:.0} ms")),
+                None => ui.label("Average page load: no data yet"),
+            };
+
+            ui.separator();
+            if ui.button("Export as JSON").clicked() {
+                self.last_export = Some(
+                    metrics
+                        .export(export_path)
+                        .map(|_| export_path.to_path_buf())
+                        .map_err(|e| e.to_string()),
+                );
+            }
+            match &self.last_export {
+                Some(Ok(path)) => {
+                    ui.label(format!("Exported to {}", path.display()));
+                }
+                Some(Err(reason)) => {
+                    ui.colored_label(egui::Color32::RED, format!("Export failed: {reason}"));
+                }
+                None => {}
+            }
+        });
+        self.open = open;
+    }
+}
+
+pub(crate) fn default_store_path() -> Option<PathBuf> {
+    crate::zoom::default_store_path().map(|p| p.with_file_name("telemetry.json"))
+}
+
+pub(crate) fn default_export_path() -> Option<PathBuf> {
+    crate::zoom::default_store_path().map(|p| p.with_file_name("telemetry-export.json"))
+}
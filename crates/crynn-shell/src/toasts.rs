@@ -0,0 +1,172 @@
+use std::time::{Duration, Instant};
+
+use crate::events::{EventBus, ShellEvent, Severity};
+
+const VISIBLE_DURATION: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ToastId(u64);
+
+#[derive(Debug, Clone)]
+struct Toast {
+    id: ToastId,
+    event: ShellEvent,
+    shown_at: Instant,
+}
+
+/// Non-blocking toast area fed by the [`EventBus`]: active toasts
+/// auto-dismiss after a few seconds and move into a history drawer so
+/// nothing the user missed is lost.
+#[derive(Default)]
+pub struct ToastCenter {
+    active: Vec<Toast>,
+    history: Vec<Toast>,
+    history_open: bool,
+    next_id: u64,
+}
+
+impl ToastCenter {
+    /// Pulls any pending events off `bus` and turns them into visible
+    /// toasts, then expires any toast whose visible duration has elapsed.
+    pub fn tick(&mut self, bus: &mut EventBus, now: Instant) {
+        for event in bus.drain() {
+            let id = ToastId(self.next_id);
+            self.next_id += 1;
+            self.active.push(Toast {
+                id,
+                event,
+                shown_at: now,
+            });
+        }
+
+        let (still_active, expired): (Vec<_>, Vec<_>) = self
+            .active
+            .drain(..)
+            .partition(|t| now.duration_since(t.shown_at) < VISIBLE_DURATION);
+        self.active = still_active;
+        self.history.extend(expired);
+    }
+
+    pub fn dismiss(&mut self, id: ToastId) {
+        if let Some(pos) = self.active.iter().position(|t| t.id == id) {
+            let toast = self.active.remove(pos);
+            self.history.push(toast);
+        }
+    }
+
+    pub fn toggle_history(&mut self) {
+        self.history_open = !self.history_open;
+    }
+
+    pub fn is_history_open(&self) -> bool {
+        self.history_open
+    }
+
+    pub fn active_count(&self) -> usize {
+        self.active.len()
+    }
+
+    pub fn history_count(&self) -> usize {
+        self.history.len()
+    }
+
+    pub fn ui(&mut self, ctx: &egui::Context, registry: &crate::ActionRegistry, state: &mut crate::ShellState) {
+        let mut dismissed = Vec::new();
+        let mut run_action = None;
+
+        egui::Area::new(egui::Id::new("toast-area"))
+            .anchor(egui::Align2::RIGHT_BOTTOM, egui::vec2(-8.0, -8.0))
+            .show(ctx, |ui| {
+                for toast in &self.active {
+                    ui.group(|ui| {
+                        ui.horizontal(|ui| {
+                            ui.label(severity_icon(toast.event.severity));
+                            ui.label(&toast.event.message);
+                            if let Some(action) = toast.event.action {
+                                if ui.small_button("View").clicked() {
+                                    run_action = Some(action);
+                                    dismissed.push(toast.id);
+                                }
+                            }
+                            if ui.small_button("✕").clicked() {
+                                dismissed.push(toast.id);
+                            }
+                        });
+                    });
+                }
+            });
+
+        if self.history_open {
+            let mut open = true;
+            egui::Window::new("Notification History")
+                .open(&mut open)
+                .show(ctx, |ui| {
+                    for toast in self.history.iter().rev() {
+                        ui.label(format!("{} {}", severity_icon(toast.event.severity), toast.event.message));
+                    }
+                });
+            self.history_open = open;
+        }
+
+        for id in dismissed {
+            self.dismiss(id);
+        }
+        if let Some(action) = run_action {
+            registry.run(action, state);
+        }
+    }
+}
+
+fn severity_icon(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Info => "ℹ",
+        Severity::Success => "✓",
+        Severity::Warning => "⚠",
+        Severity::Error => "✕",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tick_converts_posted_events_into_active_toasts() {
+        let mut bus = EventBus::default();
+        bus.post(ShellEvent::new(Severity::Success, "Download finished"));
+        let mut center = ToastCenter::default();
+
+        center.tick(&mut bus, Instant::now());
+
+        assert_eq!(center.active_count(), 1);
+        assert_eq!(center.history_count(), 0);
+    }
+
+    #[test]
+    fn toasts_expire_into_history_after_visible_duration() {
+        let mut bus = EventBus::default();
+        bus.post(ShellEvent::new(Severity::Info, "hello"));
+        let mut center = ToastCenter::default();
+        let t0 = Instant::now();
+        center.tick(&mut bus, t0);
+
+        center.tick(&mut bus, t0 + VISIBLE_DURATION + Duration::from_millis(1));
+
+        assert_eq!(center.active_count(), 0);
+        assert_eq!(center.history_count(), 1);
+    }
+
+    #[test]
+    fn dismiss_moves_an_active_toast_to_history() {
+        let mut bus = EventBus::default();
+        bus.post(ShellEvent::new(Severity::Warning, "careful"));
+        let mut center = ToastCenter::default();
+        center.tick(&mut bus, Instant::now());
+        let id = center.active[0].id;
+
+        center.dismiss(id);
+
+        assert_eq!(center.active_count(), 0);
+        assert_eq!(center.history_count(), 1);
+    }
+}
@@ -0,0 +1,147 @@
+use crynn_engine::{PrefValue, SitePrefStore, TabId, PREF_ALWAYS_TRANSLATE};
+use crynn_network::{TranslationClient, TranslationTransport};
+
+/// Replaces a tab's rendered page text with translated text, through
+/// whatever script-injection mechanism the embedding engine provides. No
+/// real engine binding exists yet — the same contract-over-implementation
+/// split as [`crate::clipboard::ClipboardSource`]/
+/// [`crate::global_hotkeys::GlobalHotkeyPlatform`].
+pub trait PageTranslator {
+    fn replace_text(&mut self, tab: TabId, translated_text: &str);
+}
+
+/// Whether `origin` should be translated automatically, without asking —
+/// the translate bar's "always translate this site" checkbox, the same
+/// per-site override [`crate::fingerprinting::is_active`] reads, except
+/// there's no global default to fall back to: translation is always off
+/// until a site has explicitly opted in.
+pub fn always_translate(origin: &str, site_prefs: &SitePrefStore) -> bool {
+    site_prefs.bool_pref(origin, PREF_ALWAYS_TRANSLATE, false)
+}
+
+/// Sets or clears `origin`'s "always translate" override, the same
+/// set-or-clear shape as [`crate::forget_site::forget_site`]'s individual
+/// preference clears.
+pub fn set_always_translate(site_prefs: &mut SitePrefStore, origin: &str, always: bool) {
+    if always {
+        site_prefs.set(origin, PREF_ALWAYS_TRANSLATE, PrefValue::Bool(true));
+    } else {
+        site_prefs.clear(origin, PREF_ALWAYS_TRANSLATE);
+    }
+}
+
+/// Translates `page_text` from `source_language` to `target_language` via
+/// `client`/`transport`, replacing `tab`'s rendered text through
+/// `injector` on success. Returns whether anything was replaced — `false`
+/// if the source and target already match (nothing to do) or the backend
+/// couldn't translate it.
+pub fn translate_page(
+    tab: TabId,
+    page_text: &str,
+    source_language: &str,
+    target_language: &str,
+    client: &TranslationClient,
+    transport: &mut dyn TranslationTransport,
+    injector: &mut dyn PageTranslator,
+) -> bool {
+    if source_language == target_language {
+        return false;
+    }
+    let Some(translated) = client.translate(transport, page_text, source_language, target_language) else {
+        return false;
+    };
+    injector.replace_text(tab, &translated);
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crynn_error::NetworkError;
+
+    struct FixedTransport {
+        body: Option<String>,
+    }
+
+    impl TranslationTransport for FixedTransport {
+        fn translate(&mut self, endpoint: &str, _request_body: &str) -> Result<String, NetworkError> {
+            self.body.clone().ok_or_else(|| NetworkError::Timeout { url: endpoint.to_string(), elapsed_ms: 0 })
+        }
+    }
+
+    #[derive(Default)]
+    struct RecordingTranslator {
+        replaced: Vec<(TabId, String)>,
+    }
+
+    impl PageTranslator for RecordingTranslator {
+        fn replace_text(&mut self, tab: TabId, translated_text: &str) {
+            self.replaced.push((tab, translated_text.to_string()));
+        }
+    }
+
+    fn a_tab() -> TabId {
+        crynn_engine::TabRegistry::new().open("https://example.com", "Example")
+    }
+
+    #[test]
+    fn not_translated_by_default_and_without_a_site_override() {
+        let site_prefs = SitePrefStore::new();
+        assert!(!always_translate("https://example.com", &site_prefs));
+    }
+
+    #[test]
+    fn setting_always_translate_is_reflected_back() {
+        let mut site_prefs = SitePrefStore::new();
+        set_always_translate(&mut site_prefs, "https://example.com", true);
+        assert!(always_translate("https://example.com", &site_prefs));
+    }
+
+    #[test]
+    fn clearing_always_translate_reverts_to_off() {
+        let mut site_prefs = SitePrefStore::new();
+        set_always_translate(&mut site_prefs, "https://example.com", true);
+        set_always_translate(&mut site_prefs, "https://example.com", false);
+        assert!(!always_translate("https://example.com", &site_prefs));
+    }
+
+    #[test]
+    fn always_translate_is_scoped_per_origin() {
+        let mut site_prefs = SitePrefStore::new();
+        set_always_translate(&mut site_prefs, "https://a.example.com", true);
+        assert!(!always_translate("https://b.example.com", &site_prefs));
+    }
+
+    #[test]
+    fn matching_source_and_target_languages_translate_nothing() {
+        let tab = a_tab();
+        let client = TranslationClient::new("https://translate.example/translate");
+        let mut transport = FixedTransport { body: Some(r#"{"translatedText": "hello"}"#.to_string()) };
+        let mut injector = RecordingTranslator::default();
+
+        assert!(!translate_page(tab, "hello", "en", "en", &client, &mut transport, &mut injector));
+        assert!(injector.replaced.is_empty());
+    }
+
+    #[test]
+    fn a_successful_translation_replaces_the_tabs_text() {
+        let tab = a_tab();
+        let client = TranslationClient::new("https://translate.example/translate");
+        let mut transport = FixedTransport { body: Some(r#"{"translatedText": "bonjour"}"#.to_string()) };
+        let mut injector = RecordingTranslator::default();
+
+        assert!(translate_page(tab, "hello", "en", "fr", &client, &mut transport, &mut injector));
+        assert_eq!(injector.replaced, vec![(tab, "bonjour".to_string())]);
+    }
+
+    #[test]
+    fn a_failed_translation_replaces_nothing() {
+        let tab = a_tab();
+        let client = TranslationClient::new("https://translate.example/translate");
+        let mut transport = FixedTransport { body: None };
+        let mut injector = RecordingTranslator::default();
+
+        assert!(!translate_page(tab, "hello", "en", "fr", &client, &mut transport, &mut injector));
+        assert!(injector.replaced.is_empty());
+    }
+}
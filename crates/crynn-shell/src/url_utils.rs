@@ -0,0 +1,445 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+use crynn_network::{SuggestionsClient, SuggestionsTransport};
+use crynn_storage::StorageManager;
+
+/// Why an [`OmniboxSuggestion`] was offered, so the dropdown can render
+/// each kind differently (a plain history match vs. a correction the
+/// user has to actively pick to accept).
+#[derive(Debug, Clone, PartialEq)]
+pub enum SuggestionKind {
+    /// A previously visited page whose title or URL matches the input.
+    History,
+    /// The input looks like a URL with a mistyped TLD. Offered as a
+    /// suggestion rather than applied automatically — picking it from
+    /// the dropdown is the user's confirmation.
+    TldCorrection,
+    /// `keyword query` expanded against a registered [`KeywordShortcuts`]
+    /// entry.
+    KeywordSearch { keyword: String },
+    /// `keyword` (optionally followed by a query) resolved against a
+    /// bookmark's own [`crynn_storage::Bookmark::keyword`].
+    BookmarkKeyword { keyword: String },
+    /// A completion from the configured search engine's suggestions
+    /// endpoint, already fetched by the caller — see
+    /// [`fetch_remote_suggestions`].
+    Remote,
+    /// A match from [`crynn_storage::search_local_data`]'s unified index,
+    /// tagged with which [`crynn_storage::SourceKind`] it came from.
+    LocalSearch { source: crynn_storage::SourceKind },
+}
+
+/// One ranked candidate for the address bar dropdown. `text` is what the
+/// shell navigates to if the user picks this suggestion; `label` is what
+/// the dropdown shows (e.g. the page title for a history match).
+#[derive(Debug, Clone, PartialEq)]
+pub struct OmniboxSuggestion {
+    pub text: String,
+    pub label: String,
+    pub kind: SuggestionKind,
+    pub score: f64,
+}
+
+/// User-configured `keyword query` shortcuts, e.g. `g` expanding to a
+/// Google search. `{}` in the template is replaced with the query.
+#[derive(Debug, Default, Clone)]
+pub struct KeywordShortcuts {
+    engines: HashMap<String, String>,
+}
+
+impl KeywordShortcuts {
+    pub fn register(&mut self, keyword: impl Into<String>, url_template: impl Into<String>) {
+        self.engines.insert(keyword.into(), url_template.into());
+    }
+
+    pub fn unregister(&mut self, keyword: &str) {
+        self.engines.remove(keyword);
+    }
+
+    fn expand(&self, keyword: &str, query: &str) -> Option<String> {
+        self.engines.get(keyword).map(|template| template.replace("{}", query))
+    }
+}
+
+/// Common TLD typos worth auto-suggesting a fix for — the ones that come
+/// from a finger slip one key over, not an exhaustive list.
+const TLD_CORRECTIONS: &[(&str, &str)] = &[(".con", ".com"), (".cmo", ".com"), (".ocm", ".com"), (".nte", ".net"), (".ogr", ".org")];
+
+fn tld_correction(input: &str) -> Option<String> {
+    TLD_CORRECTIONS.iter().find_map(|(typo, fix)| input.strip_suffix(typo).map(|prefix| format!("{prefix}{fix}")))
+}
+
+/// Whether freeform text should be treated as a URL to navigate to
+/// directly or a query to search for — the same judgment call the
+/// omnibox makes on whatever's typed into it, reused here for text that
+/// arrives from outside the omnibox entirely (a copied clipboard value).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Classification {
+    Url(String),
+    Search(String),
+}
+
+/// Classifies `input`: something with a scheme or that looks like a
+/// bare `host.tld` with no spaces is a [`Classification::Url`] (scheme
+/// defaulted to `https://` if missing); anything else is a
+/// [`Classification::Search`] of the trimmed text.
+pub fn classify(input: &str) -> Classification {
+    let trimmed = input.trim();
+    if looks_like_url(trimmed) {
+        Classification::Url(with_scheme(trimmed))
+    } else {
+        Classification::Search(trimmed.to_string())
+    }
+}
+
+fn looks_like_url(input: &str) -> bool {
+    if input.is_empty() || input.contains(char::is_whitespace) {
+        return false;
+    }
+    if input.starts_with("http://") || input.starts_with("https://") {
+        return true;
+    }
+    let host = input.split(['/', '?', '#']).next().unwrap_or(input);
+    match host.rsplit_once('.') {
+        Some((_, tld)) => tld.len() >= 2 && tld.chars().all(|c| c.is_ascii_alphabetic()),
+        None => false,
+    }
+}
+
+fn with_scheme(input: &str) -> String {
+    if input.starts_with("http://") || input.starts_with("https://") {
+        input.to_string()
+    } else {
+        format!("https://{input}")
+    }
+}
+
+/// Where remote suggestions rank among the others — below a keyword
+/// search (the user already has a registered shortcut for that exact
+/// prefix) but above a plain history match unless the page is visited
+/// often enough for its frecency to outscore it.
+const REMOTE_SUGGESTION_SCORE: f64 = 0.5;
+
+/// Ranks every suggestion worth offering for `input`, highest score
+/// first: a TLD-typo correction if the input looks like one, a
+/// keyword-search expansion if it starts with a registered keyword
+/// followed by a space, history matches scored by
+/// [`crynn_storage::frecency`], and `remote` completions already fetched
+/// from the search engine's suggestions endpoint (empty if remote
+/// suggestions are disabled or haven't come back yet). `now` is the
+/// caller's clock reading, the same way `crynn_storage::history` takes
+/// it rather than reaching for one itself.
+pub fn suggest(
+    input: &str,
+    storage: Option<&StorageManager>,
+    keywords: &KeywordShortcuts,
+    remote: &[String],
+    now: u64,
+) -> Vec<OmniboxSuggestion> {
+    let mut suggestions = Vec::new();
+    if input.trim().is_empty() {
+        return suggestions;
+    }
+
+    if let Some(corrected) = tld_correction(input) {
+        suggestions.push(OmniboxSuggestion {
+            text: corrected.clone(),
+            label: format!("Did you mean {corrected}?"),
+            kind: SuggestionKind::TldCorrection,
+            score: 1.0,
+        });
+    }
+
+    if let Some((keyword, query)) = input.split_once(' ') {
+        if let Some(url) = keywords.expand(keyword, query) {
+            suggestions.push(OmniboxSuggestion {
+                text: url,
+                label: format!("Search {keyword} for \"{query}\""),
+                kind: SuggestionKind::KeywordSearch { keyword: keyword.to_string() },
+                score: 0.9,
+            });
+        }
+    }
+
+    if let Some(storage) = storage {
+        if let Some(suggestion) = bookmark_keyword_suggestion(input, storage) {
+            suggestions.push(suggestion);
+        }
+        suggestions.extend(history_suggestions(input, storage, now));
+        suggestions.extend(local_search_suggestions(input, storage, now));
+    }
+
+    for completion in remote {
+        suggestions.push(OmniboxSuggestion {
+            text: completion.clone(),
+            label: completion.clone(),
+            kind: SuggestionKind::Remote,
+            score: REMOTE_SUGGESTION_SCORE,
+        });
+    }
+
+    suggestions.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(Ordering::Equal));
+    suggestions
+}
+
+/// Fetches remote completions for `query` via `client`, or returns
+/// nothing if the privacy setting has turned remote suggestions off
+/// (`client` is `None` in that case — see
+/// [`crynn_config::SearchConfig::suggestions_enabled`]).
+pub fn fetch_remote_suggestions(client: Option<&SuggestionsClient>, transport: &mut dyn SuggestionsTransport, query: &str) -> Vec<String> {
+    match client {
+        Some(client) => client.fetch(transport, query),
+        None => Vec::new(),
+    }
+}
+
+/// Resolves `input` against a bookmark's own keyword, ahead of treating
+/// it as a URL to normalize or a plain search: `input`'s first word is
+/// looked up via [`crynn_storage::bookmark_by_keyword`], and if found,
+/// its URL's `%s` (if any) is substituted with whatever follows. A
+/// keyword whose bookmark has no `%s` in its URL resolves on its own,
+/// with no query required — the "`gh` → github.com" direct shortcut
+/// case; one that does (a parameterized quick search, e.g. `"w %s"`)
+/// only resolves once there's a query to substitute in.
+fn bookmark_keyword_suggestion(input: &str, storage: &StorageManager) -> Option<OmniboxSuggestion> {
+    let (keyword, query) = match input.split_once(' ') {
+        Some((keyword, query)) => (keyword, Some(query)),
+        None => (input, None),
+    };
+    let bookmark = crynn_storage::bookmark_by_keyword(storage, keyword).ok().flatten()?;
+    let resolved = if bookmark.url.contains("%s") {
+        bookmark.url.replace("%s", query?)
+    } else {
+        bookmark.url.clone()
+    };
+    let label = match query {
+        Some(query) if bookmark.url.contains("%s") => format!("Search {} for \"{}\"", bookmark.title, query),
+        _ => bookmark.title,
+    };
+    Some(OmniboxSuggestion { text: resolved, label, kind: SuggestionKind::BookmarkKeyword { keyword: keyword.to_string() }, score: 0.95 })
+}
+
+/// Every visited URL matching `input` by title or URL, scored by the
+/// sum of its visits' frecency and capped to the top 5 — the dropdown
+/// doesn't need a long tail of weak matches.
+fn history_suggestions(input: &str, storage: &StorageManager, now: u64) -> Vec<OmniboxSuggestion> {
+    let Ok(visits) = crynn_storage::visits(storage) else {
+        return Vec::new();
+    };
+    let needle = input.to_lowercase();
+
+    let mut by_url: HashMap<String, (String, f64)> = HashMap::new();
+    for visit in visits.iter().filter(|v| v.url.to_lowercase().contains(&needle) || v.title.to_lowercase().contains(&needle)) {
+        let entry = by_url.entry(visit.url.clone()).or_insert_with(|| (visit.title.clone(), 0.0));
+        entry.1 += crynn_storage::frecency(visit, now);
+    }
+
+    let mut suggestions: Vec<OmniboxSuggestion> = by_url
+        .into_iter()
+        .map(|(url, (title, score))| OmniboxSuggestion { text: url, label: title, kind: SuggestionKind::History, score })
+        .collect();
+    suggestions.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(Ordering::Equal));
+    suggestions.truncate(5);
+    suggestions
+}
+
+/// Wraps [`crynn_storage::search_local_data`] for the dropdown: every
+/// match it finds becomes a [`SuggestionKind::LocalSearch`] suggestion,
+/// tagged with the [`crynn_storage::SourceKind`] it came from so the
+/// dropdown can render a bookmark match differently from a history one.
+/// This is what lets a query match a bookmark or history entry by
+/// content rather than only the exact prefix [`history_suggestions`]'s
+/// substring check and [`bookmark_keyword_suggestion`]'s keyword lookup
+/// already cover — the same cross-source ranking the command palette's
+/// search entries use.
+fn local_search_suggestions(input: &str, storage: &StorageManager, now: u64) -> Vec<OmniboxSuggestion> {
+    let Ok(results) = crynn_storage::search_local_data(storage, input, now) else {
+        return Vec::new();
+    };
+    results
+        .into_iter()
+        .map(|result| OmniboxSuggestion { text: result.url, label: result.title, kind: SuggestionKind::LocalSearch { source: result.source }, score: result.score })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crynn_storage::{record_visit, Visit, VisitType};
+
+    #[test]
+    fn empty_input_suggests_nothing() {
+        assert!(suggest("", None, &KeywordShortcuts::default(), &[], 0).is_empty());
+    }
+
+    #[test]
+    fn a_mistyped_tld_is_suggested_as_a_correction() {
+        let suggestions = suggest("github.con", None, &KeywordShortcuts::default(), &[], 0);
+        assert!(suggestions.iter().any(|s| s.kind == SuggestionKind::TldCorrection && s.text == "github.com"));
+    }
+
+    #[test]
+    fn a_known_good_tld_gets_no_correction() {
+        let suggestions = suggest("github.com", None, &KeywordShortcuts::default(), &[], 0);
+        assert!(!suggestions.iter().any(|s| s.kind == SuggestionKind::TldCorrection));
+    }
+
+    #[test]
+    fn a_registered_keyword_expands_into_a_search_suggestion() {
+        let mut keywords = KeywordShortcuts::default();
+        keywords.register("g", "https://google.com/search?q={}");
+
+        let suggestions = suggest("g rust lang", None, &keywords, &[], 0);
+
+        assert!(suggestions.iter().any(|s| s.text == "https://google.com/search?q=rust lang"));
+    }
+
+    #[test]
+    fn an_unregistered_keyword_produces_no_search_suggestion() {
+        let suggestions = suggest("zz rust lang", None, &KeywordShortcuts::default(), &[], 0);
+        assert!(!suggestions.iter().any(|s| matches!(s.kind, SuggestionKind::KeywordSearch { .. })));
+    }
+
+    #[test]
+    fn remote_completions_are_merged_in_as_remote_suggestions() {
+        let remote = vec!["rust lang book".to_string(), "rust lang playground".to_string()];
+        let suggestions = suggest("rust lang", None, &KeywordShortcuts::default(), &remote, 0);
+        assert_eq!(suggestions.iter().filter(|s| s.kind == SuggestionKind::Remote).count(), 2);
+    }
+
+    #[test]
+    fn fetch_remote_suggestions_returns_nothing_without_a_client() {
+        struct UnreachableTransport;
+        impl SuggestionsTransport for UnreachableTransport {
+            fn fetch(&mut self, _url: &str) -> Result<String, crynn_error::NetworkError> {
+                panic!("should not be called when remote suggestions are disabled");
+            }
+        }
+        assert!(fetch_remote_suggestions(None, &mut UnreachableTransport, "rust").is_empty());
+    }
+
+    #[test]
+    fn a_bookmark_keyword_with_no_placeholder_resolves_without_a_query() {
+        let dir = std::env::temp_dir().join(format!("crynn-shell-url-utils-bookmark-direct-{}", std::process::id()));
+        let mut storage = StorageManager::open(&dir, None).unwrap();
+        crynn_storage::save_bookmark(
+            &mut storage,
+            &crynn_storage::Bookmark { id: "1".to_string(), url: "https://github.com".to_string(), title: "GitHub".to_string(), created_at: 0, keyword: Some("gh".to_string()) },
+        )
+        .unwrap();
+
+        let suggestions = suggest("gh", Some(&storage), &KeywordShortcuts::default(), &[], 0);
+        assert!(suggestions.iter().any(|s| s.text == "https://github.com" && matches!(&s.kind, SuggestionKind::BookmarkKeyword { keyword } if keyword == "gh")));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn a_bookmark_keyword_with_a_placeholder_substitutes_the_query() {
+        let dir = std::env::temp_dir().join(format!("crynn-shell-url-utils-bookmark-search-{}", std::process::id()));
+        let mut storage = StorageManager::open(&dir, None).unwrap();
+        crynn_storage::save_bookmark(
+            &mut storage,
+            &crynn_storage::Bookmark {
+                id: "1".to_string(),
+                url: "https://en.wikipedia.org/wiki/Special:Search?search=%s".to_string(),
+                title: "Wikipedia".to_string(),
+                created_at: 0,
+                keyword: Some("w".to_string()),
+            },
+        )
+        .unwrap();
+
+        let suggestions = suggest("w rust lang", Some(&storage), &KeywordShortcuts::default(), &[], 0);
+        assert!(suggestions.iter().any(|s| s.text == "https://en.wikipedia.org/wiki/Special:Search?search=rust lang"));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn a_bookmark_keyword_with_a_placeholder_and_no_query_does_not_resolve() {
+        let dir = std::env::temp_dir().join(format!("crynn-shell-url-utils-bookmark-no-query-{}", std::process::id()));
+        let mut storage = StorageManager::open(&dir, None).unwrap();
+        crynn_storage::save_bookmark(
+            &mut storage,
+            &crynn_storage::Bookmark {
+                id: "1".to_string(),
+                url: "https://en.wikipedia.org/wiki/Special:Search?search=%s".to_string(),
+                title: "Wikipedia".to_string(),
+                created_at: 0,
+                keyword: Some("w".to_string()),
+            },
+        )
+        .unwrap();
+
+        let suggestions = suggest("w", Some(&storage), &KeywordShortcuts::default(), &[], 0);
+        assert!(!suggestions.iter().any(|s| matches!(s.kind, SuggestionKind::BookmarkKeyword { .. })));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn a_bookmark_not_visited_recently_still_surfaces_via_local_search() {
+        let dir = std::env::temp_dir().join(format!("crynn-shell-url-utils-local-search-{}", std::process::id()));
+        let mut storage = StorageManager::open(&dir, None).unwrap();
+        crynn_storage::save_bookmark(
+            &mut storage,
+            &crynn_storage::Bookmark { id: "1".to_string(), url: "https://tokio.rs".to_string(), title: "Tokio backpressure guide".to_string(), created_at: 0, keyword: None },
+        )
+        .unwrap();
+
+        let suggestions = suggest("backpressure", Some(&storage), &KeywordShortcuts::default(), &[], 0);
+
+        assert!(suggestions.iter().any(|s| matches!(&s.kind, SuggestionKind::LocalSearch { source: crynn_storage::SourceKind::Bookmark }) && s.text == "https://tokio.rs"));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn a_bare_host_with_a_tld_classifies_as_a_url_with_https_assumed() {
+        assert_eq!(classify("example.com"), Classification::Url("https://example.com".to_string()));
+    }
+
+    #[test]
+    fn a_url_with_an_explicit_scheme_is_left_as_is() {
+        assert_eq!(classify("http://example.com/path"), Classification::Url("http://example.com/path".to_string()));
+    }
+
+    #[test]
+    fn text_with_no_tld_or_spaces_classifies_as_a_search() {
+        assert_eq!(classify("rust lang book"), Classification::Search("rust lang book".to_string()));
+        assert_eq!(classify("notaurl"), Classification::Search("notaurl".to_string()));
+    }
+
+    #[test]
+    fn classify_trims_surrounding_whitespace() {
+        assert_eq!(classify("  example.com  "), Classification::Url("https://example.com".to_string()));
+    }
+
+    #[test]
+    fn history_matches_rank_by_frecency_and_cap_at_five() {
+        let dir = std::env::temp_dir().join(format!("crynn-shell-url-utils-test-{}", std::process::id()));
+        let mut storage = StorageManager::open(&dir, None).unwrap();
+        for i in 0..7 {
+            record_visit(
+                &mut storage,
+                &Visit {
+                    id: i.to_string(),
+                    url: format!("https://example.com/rust-{i}"),
+                    title: "Rust docs".to_string(),
+                    visit_type: VisitType::Typed,
+                    at: i,
+                    from_visit: None,
+                },
+            )
+            .unwrap();
+        }
+
+        let suggestions = suggest("rust", Some(&storage), &KeywordShortcuts::default(), &[], 100);
+
+        assert_eq!(suggestions.iter().filter(|s| s.kind == SuggestionKind::History).count(), 5);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
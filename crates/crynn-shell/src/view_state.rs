@@ -0,0 +1,88 @@
+use crynn_error::StorageError;
+use crynn_storage::ViewState;
+
+use crate::zoom::origin_of;
+use crate::ShellState;
+
+/// Applies `tab_id`'s persisted zoom/scroll/text-size back onto the tab if
+/// its origin has a [`ViewState`] on file, e.g. right after
+/// [`ShellState::open_tab`] navigates it — the full-view-state counterpart
+/// to that same method's zoom-only restore from [`crate::zoom::ZoomStore`].
+/// A no-op without [`ShellState::storage`] or with nothing saved for the
+/// origin yet.
+pub fn restore_view_state(state: &mut ShellState, tab_id: crynn_engine::TabId) -> Result<(), StorageError> {
+    let Some(storage) = &state.storage else {
+        return Ok(());
+    };
+    let Some(tab) = state.engine.get(tab_id) else {
+        return Ok(());
+    };
+    let origin = origin_of(tab.url());
+    let Some(saved) = crynn_storage::view_state_for(storage, &origin)? else {
+        return Ok(());
+    };
+    if let Some(tab) = state.engine.get_mut(tab_id) {
+        tab.restore_view_state(saved.zoom, saved.scroll_y, saved.text_size);
+    }
+    Ok(())
+}
+
+/// Persists the active tab's current zoom/scroll/text-size under its
+/// origin, evicting the least-recently-used origin past
+/// [`crynn_storage::VIEW_STATE_DEFAULT_CAPACITY`] the same way
+/// [`crynn_storage::save_view_state`] always does. A no-op without
+/// [`ShellState::storage`] or an active tab.
+pub fn save_active_tab_view_state(state: &mut ShellState, now: u64) -> Result<(), StorageError> {
+    let Some(tab) = state.active_tab() else {
+        return Ok(());
+    };
+    let origin = origin_of(tab.url());
+    let view_state = ViewState::new(tab.zoom(), tab.scroll_y(), tab.text_size());
+    let Some(storage) = &mut state.storage else {
+        return Ok(());
+    };
+    crynn_storage::save_view_state(storage, &origin, view_state, now, crynn_storage::VIEW_STATE_DEFAULT_CAPACITY)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_storage() -> crynn_storage::StorageManager {
+        let dir = std::env::temp_dir().join(format!("crynn-shell-view-state-test-{}", std::process::id()));
+        crynn_storage::StorageManager::open(&dir, None).unwrap()
+    }
+
+    #[test]
+    fn restoring_with_no_saved_state_leaves_the_tab_at_its_defaults() {
+        let mut state = ShellState { storage: Some(test_storage()), ..ShellState::default() };
+        let id = state.open_tab("https://example.com", "Example");
+
+        restore_view_state(&mut state, id).unwrap();
+
+        let tab = state.engine.get(id).unwrap();
+        assert_eq!(tab.zoom(), crynn_engine::DEFAULT_ZOOM);
+        assert_eq!(tab.scroll_y(), crynn_engine::DEFAULT_SCROLL_Y);
+    }
+
+    #[test]
+    fn saving_then_restoring_round_trips_through_a_fresh_tab() {
+        let mut state = ShellState { storage: Some(test_storage()), ..ShellState::default() };
+        let id = state.open_tab("https://example.com/page", "Example");
+        state.active_tab = Some(id);
+
+        {
+            let tab = state.engine.get_mut(id).unwrap();
+            tab.restore_view_state(1.5, 640.0, 1.2);
+        }
+        save_active_tab_view_state(&mut state, 1).unwrap();
+
+        let other_id = state.open_tab("https://example.com/other-page", "Example");
+        restore_view_state(&mut state, other_id).unwrap();
+
+        let other_tab = state.engine.get(other_id).unwrap();
+        assert_eq!(other_tab.zoom(), 1.5);
+        assert_eq!(other_tab.scroll_y(), 640.0);
+        assert_eq!(other_tab.text_size(), 1.2);
+    }
+}
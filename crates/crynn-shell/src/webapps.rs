@@ -0,0 +1,133 @@
+use crynn_error::StorageError;
+use crynn_storage::{install_webapp, uninstall_webapp, webapps, StorageManager, WebApp};
+
+/// The seam between an installed web app and the OS it's installed on: a
+/// real binding would create (or remove) a desktop launcher entry —
+/// a `.desktop` file, a Start Menu shortcut, a `.app` bundle — that
+/// reopens the app's `start_url` in its own chromeless window. No real
+/// binding exists yet, the same contract-over-implementation split as
+/// [`crate::clipboard::ClipboardSource`]/[`crynn_engine::DevtoolsLauncher`].
+pub trait AppLauncherInstaller {
+    /// Creates the OS launcher entry for `app`.
+    fn create_entry(&mut self, app: &WebApp) -> std::io::Result<()>;
+
+    /// Removes the OS launcher entry previously created for `app`.
+    fn remove_entry(&mut self, app: &WebApp) -> std::io::Result<()>;
+}
+
+/// Installs and uninstalls sites as standalone web apps: persisting the
+/// [`WebApp`] record in storage and driving an [`AppLauncherInstaller`]
+/// to create or remove the OS-level entry that reopens it, the same
+/// persist-plus-drive-a-seam split as [`crate::retention::MaintenanceScheduler`]
+/// applying a retention rule and reporting it to the event bus.
+#[derive(Debug, Default)]
+pub struct WebAppManager;
+
+impl WebAppManager {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Persists `app` and creates its OS launcher entry. If creating the
+    /// entry fails, the app is still left installed in storage — matching
+    /// [`crynn_engine::open_devtools`]'s own no-rollback-on-launcher-error
+    /// behavior, since the launcher entry is a convenience on top of the
+    /// install, not the install itself.
+    pub fn install(&mut self, storage: &mut StorageManager, launcher: &mut dyn AppLauncherInstaller, app: &WebApp) -> Result<(), StorageError> {
+        install_webapp(storage, app)?;
+        launcher.create_entry(app)?;
+        Ok(())
+    }
+
+    /// Removes `app`'s OS launcher entry and its storage record.
+    /// Uninstalling an id with no matching web app is a no-op, the same
+    /// as [`crynn_storage::uninstall_webapp`] on its own.
+    pub fn uninstall(&mut self, storage: &mut StorageManager, launcher: &mut dyn AppLauncherInstaller, id: &str) -> Result<(), StorageError> {
+        if let Some(app) = webapps(storage)?.into_iter().find(|app| app.id == id) {
+            launcher.remove_entry(&app)?;
+        }
+        uninstall_webapp(storage, id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::path::PathBuf;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("crynn-shell-webapps-test-{}-{name}", std::process::id()));
+        let _ = fs::create_dir_all(&dir);
+        dir
+    }
+
+    #[derive(Default)]
+    struct RecordingLauncher {
+        created: Vec<String>,
+        removed: Vec<String>,
+    }
+
+    impl AppLauncherInstaller for RecordingLauncher {
+        fn create_entry(&mut self, app: &WebApp) -> std::io::Result<()> {
+            self.created.push(app.id.clone());
+            Ok(())
+        }
+
+        fn remove_entry(&mut self, app: &WebApp) -> std::io::Result<()> {
+            self.removed.push(app.id.clone());
+            Ok(())
+        }
+    }
+
+    fn an_app(id: &str) -> WebApp {
+        WebApp {
+            id: id.to_string(),
+            name: "Example".to_string(),
+            start_url: "https://example.com".to_string(),
+            container_id: format!("webapp-container-{id}"),
+            installed_at: 1_000,
+        }
+    }
+
+    #[test]
+    fn installing_persists_the_app_and_creates_its_launcher_entry() {
+        let dir = temp_dir("install");
+        let mut storage = StorageManager::open(&dir, None).unwrap();
+        let mut launcher = RecordingLauncher::default();
+        let mut manager = WebAppManager::new();
+
+        manager.install(&mut storage, &mut launcher, &an_app("1")).unwrap();
+
+        assert_eq!(webapps(&storage).unwrap(), vec![an_app("1")]);
+        assert_eq!(launcher.created, vec!["1".to_string()]);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn uninstalling_removes_the_launcher_entry_and_the_storage_record() {
+        let dir = temp_dir("uninstall");
+        let mut storage = StorageManager::open(&dir, None).unwrap();
+        let mut launcher = RecordingLauncher::default();
+        let mut manager = WebAppManager::new();
+        manager.install(&mut storage, &mut launcher, &an_app("1")).unwrap();
+
+        manager.uninstall(&mut storage, &mut launcher, "1").unwrap();
+
+        assert!(webapps(&storage).unwrap().is_empty());
+        assert_eq!(launcher.removed, vec!["1".to_string()]);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn uninstalling_a_missing_app_touches_neither_storage_nor_the_launcher() {
+        let dir = temp_dir("uninstall-missing");
+        let mut storage = StorageManager::open(&dir, None).unwrap();
+        let mut launcher = RecordingLauncher::default();
+        let mut manager = WebAppManager::new();
+
+        manager.uninstall(&mut storage, &mut launcher, "missing").unwrap();
+
+        assert!(launcher.removed.is_empty());
+    }
+}
@@ -0,0 +1,152 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crynn_engine::DEFAULT_ZOOM;
+use serde::{Deserialize, Serialize};
+
+/// Per-site zoom levels, keyed by origin (`scheme://host[:port]`) so the
+/// same level applies across pages and sessions on a site, not per-URL.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ZoomStore {
+    levels: HashMap<String, f32>,
+    #[serde(skip)]
+    path: Option<PathBuf>,
+}
+
+impl ZoomStore {
+    /// Loads zoom levels from `path` if it exists, otherwise starts empty.
+    /// The store remembers `path` so later [`ZoomStore::save`] calls don't
+    /// need to repeat it.
+    pub fn load(path: impl Into<PathBuf>) -> io::Result<Self> {
+        let path = path.into();
+        let mut store = match fs::read_to_string(&path) {
+            Ok(contents) => serde_json::from_str::<ZoomStore>(&contents)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => ZoomStore::default(),
+            Err(e) => return Err(e),
+        };
+        store.path = Some(path);
+        Ok(store)
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let contents = serde_json::to_string_pretty(self)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        fs::write(path, contents)
+    }
+
+    pub fn get(&self, url: &str) -> f32 {
+        self.levels
+            .get(&origin_of(url))
+            .copied()
+            .unwrap_or(DEFAULT_ZOOM)
+    }
+
+    pub fn set(&mut self, url: &str, level: f32) {
+        let origin = origin_of(url);
+        if (level - DEFAULT_ZOOM).abs() < f32::EPSILON {
+            self.levels.remove(&origin);
+        } else {
+            self.levels.insert(origin, level);
+        }
+    }
+}
+
+/// Extracts `scheme://host[:port]` from a URL without pulling in a full URL
+/// parser: good enough for zoom-level keying, which only needs to group
+/// pages on the same site. Shared with [`crate::view_state`], which keys
+/// its storage-backed zoom/scroll/text-size table the same way.
+pub(crate) fn origin_of(url: &str) -> String {
+    let after_scheme = match url.split_once("://") {
+        Some((scheme, rest)) => {
+            let host_end = rest.find('/').unwrap_or(rest.len());
+            return format!("{scheme}://{}", &rest[..host_end]);
+        }
+        None => url,
+    };
+    after_scheme.to_string()
+}
+
+/// Step used by the zoom-in/zoom-out actions and keyboard shortcuts.
+pub const ZOOM_STEP: f32 = 0.1;
+
+/// Applies Ctrl+scroll as a zoom gesture on the active tab, matching the
+/// step used by the zoom-in/out actions. Called once per frame.
+pub fn handle_ctrl_scroll(ctx: &egui::Context, state: &mut crate::ShellState) {
+    let scroll = ctx.input(|i| {
+        if i.modifiers.ctrl {
+            i.smooth_scroll_delta.y
+        } else {
+            0.0
+        }
+    });
+    if scroll == 0.0 {
+        return;
+    }
+    let Some(tab) = state.active_tab() else {
+        return;
+    };
+    let new_zoom = tab.zoom() + scroll.signum() * ZOOM_STEP;
+    state.set_active_tab_zoom(new_zoom);
+}
+
+pub(crate) fn default_store_path() -> Option<PathBuf> {
+    dirs_fallback().map(|dir| dir.join("crynn").join("zoom.json"))
+}
+
+/// Minimal stand-in for a platform config-dir lookup until the shared
+/// config crate (tracked separately) provides one.
+fn dirs_fallback() -> Option<PathBuf> {
+    std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| Path::new(&home).join(".config")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn get_defaults_to_default_zoom_for_unknown_site() {
+        let store = ZoomStore::default();
+        assert_eq!(store.get("https://example.com/page"), DEFAULT_ZOOM);
+    }
+
+    #[test]
+    fn set_persists_per_origin_not_per_page() {
+        let mut store = ZoomStore::default();
+        store.set("https://example.com/page-one", 1.5);
+        assert_eq!(store.get("https://example.com/page-two"), 1.5);
+        assert_eq!(store.get("https://other.com/page-two"), DEFAULT_ZOOM);
+    }
+
+    #[test]
+    fn setting_back_to_default_clears_the_entry() {
+        let mut store = ZoomStore::default();
+        store.set("https://example.com", 2.0);
+        store.set("https://example.com", DEFAULT_ZOOM);
+        assert!(store.levels.is_empty());
+    }
+
+    #[test]
+    fn save_and_load_round_trip() {
+        let dir = std::env::temp_dir().join(format!("crynn-zoom-test-{}", std::process::id()));
+        let path = dir.join("zoom.json");
+        let mut store = ZoomStore::load(&path).unwrap();
+        store.set("https://example.com", 1.75);
+        store.save().unwrap();
+
+        let reloaded = ZoomStore::load(&path).unwrap();
+        assert_eq!(reloaded.get("https://example.com"), 1.75);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,157 @@
+use std::collections::{HashMap, HashSet};
+
+use crynn_error::SpellcheckError;
+
+use crate::dictionary::Dictionary;
+use crate::fetcher::DictionaryFetcher;
+
+/// One misspelled word found in a checked text: the word itself and its
+/// byte offset, so a compose view can underline it in place rather than
+/// re-searching the text for it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Misspelling {
+    pub word: String,
+    pub offset: usize,
+}
+
+/// Checks text against a locale's dictionary plus the user's personal
+/// additions. Dictionaries are loaded lazily per locale via
+/// [`SpellChecker::load_locale`] rather than all at once up front, so a
+/// compose view only pays for the locales it actually checks against.
+#[derive(Debug, Default)]
+pub struct SpellChecker {
+    dictionaries: HashMap<String, Dictionary>,
+    personal: HashSet<String>,
+}
+
+impl SpellChecker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Fetches and caches `locale`'s dictionary via `fetcher` if it
+    /// isn't already loaded. A no-op once loaded, so a compose view can
+    /// call this on every keystroke without re-fetching.
+    pub fn load_locale(&mut self, fetcher: &mut dyn DictionaryFetcher, locale: &str) -> Result<(), SpellcheckError> {
+        if self.dictionaries.contains_key(locale) {
+            return Ok(());
+        }
+        let dictionary = fetcher.fetch(locale)?;
+        self.dictionaries.insert(locale.to_string(), dictionary);
+        Ok(())
+    }
+
+    pub fn is_locale_loaded(&self, locale: &str) -> bool {
+        self.dictionaries.contains_key(locale)
+    }
+
+    /// Adds `word` to the in-memory personal dictionary. The caller is
+    /// responsible for persisting it via `crynn-storage`'s
+    /// `personal_dictionary` module so it survives a restart — the same
+    /// split `crynn-storage`'s `vpn_sessions` keeps from
+    /// `crynn_vpn::VpnManager`'s in-memory session history.
+    pub fn add_personal_word(&mut self, word: &str) {
+        self.personal.insert(word.to_lowercase());
+    }
+
+    pub fn personal_words(&self) -> impl Iterator<Item = &str> {
+        self.personal.iter().map(String::as_str)
+    }
+
+    /// Seeds the personal dictionary from words already persisted
+    /// elsewhere, e.g. loaded from storage at startup.
+    pub fn load_personal_words(&mut self, words: impl IntoIterator<Item = String>) {
+        self.personal.extend(words.into_iter().map(|word| word.to_lowercase()));
+    }
+
+    /// Finds every word in `text` that's neither in `locale`'s loaded
+    /// dictionary nor the personal dictionary. Returns no misspellings at
+    /// all if `locale` hasn't been loaded yet — flagging every word as
+    /// wrong for a locale that failed to load would be noisier than just
+    /// not checking it.
+    pub fn check(&self, text: &str, locale: &str) -> Vec<Misspelling> {
+        let Some(dictionary) = self.dictionaries.get(locale) else {
+            return Vec::new();
+        };
+
+        let mut misspellings = Vec::new();
+        let mut word_start = None;
+        for (index, ch) in text.char_indices() {
+            if ch.is_alphanumeric() || ch == '\'' {
+                word_start.get_or_insert(index);
+            } else if let Some(start) = word_start.take() {
+                self.check_word(&text[start..index], start, dictionary, &mut misspellings);
+            }
+        }
+        if let Some(start) = word_start {
+            self.check_word(&text[start..], start, dictionary, &mut misspellings);
+        }
+        misspellings
+    }
+
+    fn check_word(&self, word: &str, offset: usize, dictionary: &Dictionary, out: &mut Vec<Misspelling>) {
+        if word.chars().all(|c| c.is_ascii_digit()) {
+            return;
+        }
+        if dictionary.contains(word) || self.personal.contains(&word.to_lowercase()) {
+            return;
+        }
+        out.push(Misspelling { word: word.to_string(), offset });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fetcher::EmptyDictionary;
+
+    struct FixedDictionary(Vec<String>);
+
+    impl DictionaryFetcher for FixedDictionary {
+        fn fetch(&mut self, locale: &str) -> Result<Dictionary, SpellcheckError> {
+            Ok(Dictionary::new(locale.to_string(), self.0.clone()))
+        }
+    }
+
+    #[test]
+    fn load_locale_is_idempotent() {
+        let mut checker = SpellChecker::new();
+        let mut fetcher = FixedDictionary(vec!["hello".to_string()]);
+        checker.load_locale(&mut fetcher, "en-US").unwrap();
+        checker.load_locale(&mut fetcher, "en-US").unwrap();
+        assert!(checker.is_locale_loaded("en-US"));
+    }
+
+    #[test]
+    fn check_flags_words_outside_the_dictionary() {
+        let mut checker = SpellChecker::new();
+        let mut fetcher = FixedDictionary(vec!["hello".to_string(), "world".to_string()]);
+        checker.load_locale(&mut fetcher, "en-US").unwrap();
+
+        let misspellings = checker.check("hello wrold", "en-US");
+        assert_eq!(misspellings, vec![Misspelling { word: "wrold".to_string(), offset: 6 }]);
+    }
+
+    #[test]
+    fn personal_words_are_never_flagged() {
+        let mut checker = SpellChecker::new();
+        let mut fetcher = EmptyDictionary;
+        checker.load_locale(&mut fetcher, "en-US").unwrap();
+        checker.add_personal_word("Crynn");
+
+        assert!(checker.check("Crynn is great", "en-US").iter().all(|m| m.word != "Crynn"));
+    }
+
+    #[test]
+    fn unloaded_locale_reports_no_misspellings() {
+        let checker = SpellChecker::new();
+        assert!(checker.check("anything at all", "de-DE").is_empty());
+    }
+
+    #[test]
+    fn load_personal_words_seeds_from_an_existing_source() {
+        let mut checker = SpellChecker::new();
+        checker.load_personal_words(vec!["crynn".to_string(), "hunspell".to_string()]);
+        assert_eq!(checker.personal_words().count(), 2);
+    }
+}
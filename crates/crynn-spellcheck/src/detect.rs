@@ -0,0 +1,39 @@
+use std::collections::HashSet;
+
+/// Guesses which of `candidates` a chunk of text is written in by
+/// counting how many of each locale's common stop words appear in it,
+/// picking whichever locale scores the most hits. Good enough to pick a
+/// dictionary to spell-check against before the user has chosen a
+/// locale explicitly; not a real language model.
+pub fn detect_language<'a>(text: &str, candidates: &[(&'a str, &[&str])]) -> Option<&'a str> {
+    let words: HashSet<String> =
+        text.split_whitespace().map(|word| word.trim_matches(|c: char| !c.is_alphanumeric()).to_lowercase()).filter(|word| !word.is_empty()).collect();
+
+    candidates
+        .iter()
+        .map(|(locale, stop_words)| (*locale, stop_words.iter().filter(|stop_word| words.contains(&stop_word.to_lowercase())).count()))
+        .filter(|(_, hits)| *hits > 0)
+        .max_by_key(|(_, hits)| *hits)
+        .map(|(locale, _)| locale)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EN_STOP_WORDS: &[&str] = &["the", "and", "is", "to", "of"];
+    const FR_STOP_WORDS: &[&str] = &["le", "la", "et", "est", "de"];
+
+    #[test]
+    fn picks_the_locale_with_the_most_stop_word_hits() {
+        let candidates = [("en-US", EN_STOP_WORDS), ("fr-FR", FR_STOP_WORDS)];
+        assert_eq!(detect_language("the quick fox and the lazy dog", &candidates), Some("en-US"));
+        assert_eq!(detect_language("le chat et la souris", &candidates), Some("fr-FR"));
+    }
+
+    #[test]
+    fn returns_none_when_no_candidate_scores_a_hit() {
+        let candidates = [("en-US", EN_STOP_WORDS), ("fr-FR", FR_STOP_WORDS)];
+        assert_eq!(detect_language("xyzzy plugh qwerty", &candidates), None);
+    }
+}
@@ -0,0 +1,48 @@
+use std::collections::HashSet;
+
+/// One locale's known-correct words, as a real hunspell dictionary's
+/// affix-expanded `.dic` word list would end up. This crate doesn't parse
+/// `.dic`/`.aff` files itself — [`crate::DictionaryFetcher`] is the seam
+/// that would, once one exists to ask — so a [`Dictionary`] here is just
+/// the flat, already-expanded set of words lookup checks against.
+#[derive(Debug, Clone, Default)]
+pub struct Dictionary {
+    locale: String,
+    words: HashSet<String>,
+}
+
+impl Dictionary {
+    pub fn new(locale: impl Into<String>, words: impl IntoIterator<Item = String>) -> Self {
+        Self { locale: locale.into(), words: words.into_iter().map(|word| word.to_lowercase()).collect() }
+    }
+
+    pub fn locale(&self) -> &str {
+        &self.locale
+    }
+
+    pub fn contains(&self, word: &str) -> bool {
+        self.words.contains(&word.to_lowercase())
+    }
+
+    pub fn word_count(&self) -> usize {
+        self.words.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lookup_is_case_insensitive() {
+        let dictionary = Dictionary::new("en-US", vec!["Hello".to_string()]);
+        assert!(dictionary.contains("hello"));
+        assert!(dictionary.contains("HELLO"));
+    }
+
+    #[test]
+    fn word_count_reflects_the_deduplicated_set() {
+        let dictionary = Dictionary::new("en-US", vec!["cat".to_string(), "Cat".to_string(), "dog".to_string()]);
+        assert_eq!(dictionary.word_count(), 2);
+    }
+}
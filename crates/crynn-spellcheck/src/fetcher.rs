@@ -0,0 +1,25 @@
+use crynn_error::SpellcheckError;
+
+use crate::dictionary::Dictionary;
+
+/// Supplies the word list for a locale's dictionary. No real download
+/// happens in this build — the same split as `crynn_vpn::HandshakeProbe`
+/// — so [`crate::SpellChecker::load_locale`] takes a fetcher rather than
+/// reaching for the network itself: a fixed in-memory word list for
+/// tests, eventually a real hunspell-dictionary-repository client once
+/// one exists to ask.
+pub trait DictionaryFetcher {
+    fn fetch(&mut self, locale: &str) -> Result<Dictionary, SpellcheckError>;
+}
+
+/// A fetcher that always succeeds with an empty word list, for callers
+/// that only care about the personal dictionary or haven't bundled a
+/// locale's hunspell data yet.
+#[derive(Debug, Default)]
+pub struct EmptyDictionary;
+
+impl DictionaryFetcher for EmptyDictionary {
+    fn fetch(&mut self, locale: &str) -> Result<Dictionary, SpellcheckError> {
+        Ok(Dictionary::new(locale.to_string(), std::iter::empty()))
+    }
+}
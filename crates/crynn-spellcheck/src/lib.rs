@@ -0,0 +1,25 @@
+//! Per-locale spell-check dictionaries and the decision contract for
+//! fetching them. No real hunspell `.dic`/`.aff` parsing or dictionary
+//! download happens in this build — [`DictionaryFetcher`] is the same
+//! kind of seam `crynn_vpn::HandshakeProbe` is for a real tunnel, asked
+//! rather than implemented until there's a dictionary repository to
+//! fetch from.
+//!
+//! [`SpellChecker`] owns the loaded [`Dictionary`] per locale plus an
+//! in-memory personal word list; [`checker::Misspelling`] is what
+//! [`SpellChecker::check`] reports back for a compose view to
+//! underline. [`detect::detect_language`] picks which locale's
+//! dictionary to check against when the user hasn't chosen one
+//! explicitly. The personal dictionary's persistence lives in
+//! `crynn-storage`'s own `personal_dictionary` module, the same split
+//! `crynn-storage`'s `vpn_sessions` keeps from `crynn_vpn::VpnManager`.
+
+mod checker;
+mod detect;
+mod dictionary;
+mod fetcher;
+
+pub use checker::{Misspelling, SpellChecker};
+pub use detect::detect_language;
+pub use dictionary::Dictionary;
+pub use fetcher::{DictionaryFetcher, EmptyDictionary};
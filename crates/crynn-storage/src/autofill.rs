@@ -0,0 +1,336 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crynn_error::StorageError;
+
+use crate::manager::StorageManager;
+
+const ADDRESS_PREFIX: &str = "autofill:address:";
+const PAYMENT_PREFIX: &str = "autofill:payment:";
+const LAST_USED_PREFIX: &str = "autofill:last-used:";
+
+/// What kind of data a form field expects. The engine layer infers this
+/// from a field's `name`/`id`/`type`/`autocomplete` attributes; this crate
+/// only needs the resulting classification to know which profile field
+/// answers which form field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum FieldKind {
+    Name,
+    Email,
+    Phone,
+    AddressLine1,
+    AddressLine2,
+    City,
+    Region,
+    PostalCode,
+    Country,
+    CardholderName,
+    CardNumber,
+    CardExpiry,
+    CardCvv,
+}
+
+/// A saved postal address, filled into the `AddressLine1`/`City`/...
+/// family of fields.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AddressProfile {
+    pub id: String,
+    pub name: String,
+    pub email: String,
+    pub phone: String,
+    pub address_line1: String,
+    pub address_line2: String,
+    pub city: String,
+    pub region: String,
+    pub postal_code: String,
+    pub country: String,
+}
+
+impl AddressProfile {
+    fn field(&self, kind: FieldKind) -> Option<&str> {
+        match kind {
+            FieldKind::Name => Some(&self.name),
+            FieldKind::Email => Some(&self.email),
+            FieldKind::Phone => Some(&self.phone),
+            FieldKind::AddressLine1 => Some(&self.address_line1),
+            FieldKind::AddressLine2 => Some(&self.address_line2),
+            FieldKind::City => Some(&self.city),
+            FieldKind::Region => Some(&self.region),
+            FieldKind::PostalCode => Some(&self.postal_code),
+            FieldKind::Country => Some(&self.country),
+            FieldKind::CardholderName | FieldKind::CardNumber | FieldKind::CardExpiry | FieldKind::CardCvv => None,
+        }
+    }
+}
+
+/// A saved payment card. Kept in the `credentials` database rather than
+/// `storage` — the same security tier as saved site passwords, since a
+/// card number deserves at least as much protection.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PaymentProfile {
+    pub id: String,
+    pub cardholder_name: String,
+    pub card_number: String,
+    pub expiry: String,
+}
+
+impl PaymentProfile {
+    fn field(&self, kind: FieldKind) -> Option<&str> {
+        match kind {
+            FieldKind::CardholderName => Some(&self.cardholder_name),
+            FieldKind::CardNumber => Some(&self.card_number),
+            FieldKind::CardExpiry => Some(&self.expiry),
+            _ => None,
+        }
+    }
+}
+
+/// Identifies a form across reloads of the same page, so the query API
+/// can remember which profile was used last time without needing a full
+/// DOM diff. Good enough for "same origin, same set of field kinds in
+/// the same order" — not a structural fingerprint of the form's markup.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct FormSignature(String);
+
+impl FormSignature {
+    pub fn compute(origin: &str, fields: &[FieldKind]) -> Self {
+        let fields = fields.iter().map(|f| format!("{f:?}")).collect::<Vec<_>>().join(",");
+        Self(format!("{origin}|{fields}"))
+    }
+}
+
+pub fn save_address(storage: &mut StorageManager, profile: &AddressProfile) -> Result<(), StorageError> {
+    let db = storage.database_mut("storage").expect("storage database always present");
+    db.set(format!("{ADDRESS_PREFIX}{}", profile.id), serde_json::to_value(profile)?)?;
+    db.save()
+}
+
+pub fn delete_address(storage: &mut StorageManager, id: &str) -> Result<(), StorageError> {
+    let db = storage.database_mut("storage").expect("storage database always present");
+    db.remove(&format!("{ADDRESS_PREFIX}{id}"))?;
+    db.save()
+}
+
+pub fn addresses(storage: &StorageManager) -> Result<Vec<AddressProfile>, StorageError> {
+    let db = storage.database("storage").expect("storage database always present");
+    db.iter()?
+        .filter(|(key, _)| key.starts_with(ADDRESS_PREFIX))
+        .map(|(_, value)| serde_json::from_value(value.clone()).map_err(StorageError::from))
+        .collect()
+}
+
+pub fn save_payment_profile(storage: &mut StorageManager, profile: &PaymentProfile) -> Result<(), StorageError> {
+    let db = storage.database_mut("credentials").expect("credentials database always present");
+    db.set(format!("{PAYMENT_PREFIX}{}", profile.id), serde_json::to_value(profile)?)?;
+    db.save()
+}
+
+pub fn delete_payment_profile(storage: &mut StorageManager, id: &str) -> Result<(), StorageError> {
+    let db = storage.database_mut("credentials").expect("credentials database always present");
+    db.remove(&format!("{PAYMENT_PREFIX}{id}"))?;
+    db.save()
+}
+
+pub fn payment_profiles(storage: &StorageManager) -> Result<Vec<PaymentProfile>, StorageError> {
+    let db = storage.database("credentials").expect("credentials database always present");
+    db.iter()?
+        .filter(|(key, _)| key.starts_with(PAYMENT_PREFIX))
+        .map(|(_, value)| serde_json::from_value(value.clone()).map_err(StorageError::from))
+        .collect()
+}
+
+/// The fill values a form with the given `fields` should be offered,
+/// drawn from whichever profile was last confirmed for `signature`, or
+/// (the first time a matching form is seen) whichever saved profile
+/// answers the most of the requested fields.
+pub fn fill_values(
+    storage: &StorageManager,
+    signature: &FormSignature,
+    fields: &[FieldKind],
+) -> Result<HashMap<FieldKind, String>, StorageError> {
+    let last_used = last_used_profile(storage, signature)?;
+
+    let addresses = addresses(storage)?;
+    let address = match &last_used {
+        Some(id) => addresses.into_iter().find(|a| &a.id == id),
+        None => best_match(addresses, fields, AddressProfile::field),
+    };
+
+    let payments = payment_profiles(storage)?;
+    let payment = match &last_used {
+        Some(id) => payments.into_iter().find(|p| &p.id == id),
+        None => best_match(payments, fields, PaymentProfile::field),
+    };
+
+    let mut values = HashMap::new();
+    for &kind in fields {
+        if let Some(value) = address.as_ref().and_then(|a| a.field(kind)).filter(|v| !v.is_empty()) {
+            values.insert(kind, value.to_string());
+        } else if let Some(value) = payment.as_ref().and_then(|p| p.field(kind)).filter(|v| !v.is_empty()) {
+            values.insert(kind, value.to_string());
+        }
+    }
+    Ok(values)
+}
+
+/// Called by the engine layer once the user has confirmed a fill (e.g.
+/// clicked an autofill suggestion in the shell), so the next time this
+/// form signature is seen the same profile is offered without re-ranking.
+/// `consented` guards against ever recording a choice the user didn't
+/// actually make — the confirmation UI itself lives in the shell, not
+/// here.
+pub fn confirm_fill(
+    storage: &mut StorageManager,
+    signature: &FormSignature,
+    profile_id: &str,
+    consented: bool,
+) -> Result<(), StorageError> {
+    if !consented {
+        return Ok(());
+    }
+    let db = storage.database_mut("storage").expect("storage database always present");
+    db.set(format!("{LAST_USED_PREFIX}{}", signature.0), serde_json::Value::String(profile_id.to_string()))?;
+    db.save()
+}
+
+fn last_used_profile(storage: &StorageManager, signature: &FormSignature) -> Result<Option<String>, StorageError> {
+    let db = storage.database("storage").expect("storage database always present");
+    Ok(db
+        .get(&format!("{LAST_USED_PREFIX}{}", signature.0))?
+        .and_then(|v| v.as_str().map(str::to_string)))
+}
+
+/// Picks the saved profile answering the most of `fields`, if any answers
+/// at least one.
+fn best_match<T>(profiles: Vec<T>, fields: &[FieldKind], field: fn(&T, FieldKind) -> Option<&str>) -> Option<T> {
+    profiles
+        .into_iter()
+        .map(|profile| {
+            let score = fields
+                .iter()
+                .filter(|&&kind| field(&profile, kind).is_some_and(|v| !v.is_empty()))
+                .count();
+            (score, profile)
+        })
+        .filter(|(score, _)| *score > 0)
+        .max_by_key(|(score, _)| *score)
+        .map(|(_, profile)| profile)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::path::PathBuf;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("crynn-storage-autofill-test-{}-{name}", std::process::id()));
+        let _ = fs::create_dir_all(&dir);
+        dir
+    }
+
+    fn address(id: &str) -> AddressProfile {
+        AddressProfile {
+            id: id.to_string(),
+            name: "Ada Lovelace".to_string(),
+            email: "ada@example.com".to_string(),
+            phone: "555-0100".to_string(),
+            address_line1: "12 Analytical Engine Way".to_string(),
+            address_line2: String::new(),
+            city: "London".to_string(),
+            region: String::new(),
+            postal_code: "SW1A 1AA".to_string(),
+            country: "UK".to_string(),
+        }
+    }
+
+    fn payment(id: &str) -> PaymentProfile {
+        PaymentProfile {
+            id: id.to_string(),
+            cardholder_name: "Ada Lovelace".to_string(),
+            card_number: "4111111111111111".to_string(),
+            expiry: "12/30".to_string(),
+        }
+    }
+
+    #[test]
+    fn saved_addresses_round_trip() {
+        let dir = temp_dir("address-round-trip");
+        let mut storage = StorageManager::open(&dir, None).unwrap();
+        save_address(&mut storage, &address("1")).unwrap();
+
+        let saved = addresses(&storage).unwrap();
+        assert_eq!(saved.len(), 1);
+        assert_eq!(saved[0].city, "London");
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn payment_profiles_live_in_the_credentials_database() {
+        let dir = temp_dir("payment-round-trip");
+        let mut storage = StorageManager::open(&dir, None).unwrap();
+        save_payment_profile(&mut storage, &payment("1")).unwrap();
+
+        assert!(storage.database("credentials").unwrap().get("autofill:payment:1").unwrap().is_some());
+        assert!(storage.database("storage").unwrap().get("autofill:payment:1").unwrap().is_none());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn fill_values_picks_the_best_matching_address_first_time() {
+        let dir = temp_dir("fill-values-first-time");
+        let mut storage = StorageManager::open(&dir, None).unwrap();
+        save_address(&mut storage, &address("1")).unwrap();
+
+        let signature = FormSignature::compute("https://shop.example.com", &[FieldKind::Name, FieldKind::City]);
+        let values = fill_values(&storage, &signature, &[FieldKind::Name, FieldKind::City, FieldKind::CardNumber]).unwrap();
+
+        assert_eq!(values.get(&FieldKind::Name), Some(&"Ada Lovelace".to_string()));
+        assert_eq!(values.get(&FieldKind::City), Some(&"London".to_string()));
+        assert!(!values.contains_key(&FieldKind::CardNumber));
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn confirmed_fill_is_reused_on_the_next_query_for_the_same_signature() {
+        let dir = temp_dir("confirm-fill");
+        let mut storage = StorageManager::open(&dir, None).unwrap();
+        save_address(&mut storage, &address("1")).unwrap();
+        save_address(&mut storage, &address("2")).unwrap();
+
+        let signature = FormSignature::compute("https://shop.example.com", &[FieldKind::Name]);
+        confirm_fill(&mut storage, &signature, "2", true).unwrap();
+
+        let values = fill_values(&storage, &signature, &[FieldKind::Name]).unwrap();
+        assert_eq!(values.get(&FieldKind::Name), Some(&"Ada Lovelace".to_string()));
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn confirm_fill_without_consent_does_not_record_anything() {
+        let dir = temp_dir("confirm-fill-no-consent");
+        let mut storage = StorageManager::open(&dir, None).unwrap();
+        let signature = FormSignature::compute("https://shop.example.com", &[FieldKind::Name]);
+
+        confirm_fill(&mut storage, &signature, "1", false).unwrap();
+
+        assert!(last_used_profile(&storage, &signature).unwrap().is_none());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn deleting_an_address_removes_it_from_future_queries() {
+        let dir = temp_dir("delete-address");
+        let mut storage = StorageManager::open(&dir, None).unwrap();
+        save_address(&mut storage, &address("1")).unwrap();
+        delete_address(&mut storage, "1").unwrap();
+
+        assert!(addresses(&storage).unwrap().is_empty());
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
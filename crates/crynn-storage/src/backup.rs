@@ -0,0 +1,190 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crynn_error::StorageError;
+
+use crate::manager::StorageManager;
+
+/// How often [`create_backup`] should run, and how many rotating archives
+/// it should keep, absent an explicit [`BackupPolicy`].
+pub const DEFAULT_BACKUP_FREQUENCY: Duration = Duration::from_secs(24 * 60 * 60);
+pub const DEFAULT_BACKUP_COUNT: usize = 7;
+
+/// How often to snapshot a profile and how many of the rotating archives
+/// to keep around, the same per-caller-configurable shape
+/// [`crate::RetentionRule`] gives history cleanup.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BackupPolicy {
+    pub frequency: Duration,
+    pub keep: usize,
+}
+
+impl Default for BackupPolicy {
+    fn default() -> Self {
+        Self { frequency: DEFAULT_BACKUP_FREQUENCY, keep: DEFAULT_BACKUP_COUNT }
+    }
+}
+
+/// A single rotating backup archive, identified by the Unix timestamp
+/// (seconds) it was taken at — also the sort key [`list_backups`] orders
+/// by and the suffix of its file name on disk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct BackupId(u64);
+
+impl BackupId {
+    pub fn timestamp(&self) -> u64 {
+        self.0
+    }
+}
+
+const BACKUP_PREFIX: &str = "backup-";
+const BACKUP_EXTENSION: &str = "json";
+
+pub(crate) fn backup_path(dir: &Path, id: BackupId) -> PathBuf {
+    dir.join(format!("{BACKUP_PREFIX}{}.{BACKUP_EXTENSION}", id.0))
+}
+
+/// Every rotating backup found under `dir`, most recent first — the data
+/// a "restore from backup" picker in the shell renders as a list, since
+/// this crate has no UI of its own to show one in. Used both for
+/// [`StorageManager::restore_backup`] and for offering a pick when
+/// [`StorageManager::open_with_recovery`] reports corruption.
+pub fn list_backups(dir: impl AsRef<Path>) -> Result<Vec<BackupId>, StorageError> {
+    let dir = dir.as_ref();
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+    let mut ids = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.extension().and_then(|ext| ext.to_str()) != Some(BACKUP_EXTENSION) {
+            continue;
+        }
+        let Some(stem) = path.file_stem().and_then(|stem| stem.to_str()) else {
+            continue;
+        };
+        let Some(timestamp) = stem.strip_prefix(BACKUP_PREFIX) else {
+            continue;
+        };
+        if let Ok(timestamp) = timestamp.parse() {
+            ids.push(BackupId(timestamp));
+        }
+    }
+    ids.sort_by(|a, b| b.cmp(a));
+    Ok(ids)
+}
+
+/// Snapshots `manager` to a fresh rotating archive under `dir`, then
+/// removes whichever older siblings no longer fit within
+/// `policy.keep`. Returns the id of the backup just taken.
+pub fn create_backup(manager: &StorageManager, dir: impl AsRef<Path>, policy: &BackupPolicy, now: SystemTime) -> Result<BackupId, StorageError> {
+    let dir = dir.as_ref();
+    fs::create_dir_all(dir)?;
+    let id = BackupId(now.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs());
+    manager.export_all(backup_path(dir, id))?;
+
+    for stale in list_backups(dir)?.into_iter().skip(policy.keep) {
+        let _ = fs::remove_file(backup_path(dir, stale));
+    }
+    Ok(id)
+}
+
+/// Whether enough of `policy.frequency` has passed since `last_backup`
+/// (or whether there's never been one at all) that [`create_backup`]
+/// should run again.
+pub fn is_backup_due(last_backup: Option<SystemTime>, now: SystemTime, policy: &BackupPolicy) -> bool {
+    match last_backup {
+        None => true,
+        Some(last) => now.duration_since(last).unwrap_or_default() >= policy.frequency,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("crynn-storage-backup-test-{}-{name}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn a_fresh_profile_has_no_backups_due_immediately() {
+        assert!(is_backup_due(None, SystemTime::now(), &BackupPolicy::default()));
+    }
+
+    #[test]
+    fn a_backup_is_not_due_before_the_frequency_elapses() {
+        let policy = BackupPolicy { frequency: Duration::from_secs(3600), keep: 7 };
+        let now = SystemTime::now();
+        assert!(!is_backup_due(Some(now), now + Duration::from_secs(60), &policy));
+    }
+
+    #[test]
+    fn a_backup_is_due_once_the_frequency_elapses() {
+        let policy = BackupPolicy { frequency: Duration::from_secs(3600), keep: 7 };
+        let now = SystemTime::now();
+        assert!(is_backup_due(Some(now), now + Duration::from_secs(3601), &policy));
+    }
+
+    #[test]
+    fn create_backup_writes_a_restorable_archive_and_lists_it() {
+        let profile_dir = temp_dir("profile");
+        let backup_dir = temp_dir("backups");
+        let mut manager = StorageManager::open(&profile_dir, Some("hunter2")).unwrap();
+        manager.database_mut("storage").unwrap().set("k", serde_json::json!("v")).unwrap();
+
+        let policy = BackupPolicy::default();
+        let id = create_backup(&manager, &backup_dir, &policy, SystemTime::now()).unwrap();
+
+        assert_eq!(list_backups(&backup_dir).unwrap(), vec![id]);
+
+        let _ = fs::remove_dir_all(&profile_dir);
+        let _ = fs::remove_dir_all(&backup_dir);
+    }
+
+    #[test]
+    fn create_backup_rotates_out_backups_beyond_the_keep_count() {
+        let profile_dir = temp_dir("profile-rotate");
+        let backup_dir = temp_dir("backups-rotate");
+        let manager = StorageManager::open(&profile_dir, Some("hunter2")).unwrap();
+        let policy = BackupPolicy { frequency: DEFAULT_BACKUP_FREQUENCY, keep: 2 };
+
+        let now = SystemTime::now();
+        create_backup(&manager, &backup_dir, &policy, now).unwrap();
+        create_backup(&manager, &backup_dir, &policy, now + Duration::from_secs(1)).unwrap();
+        create_backup(&manager, &backup_dir, &policy, now + Duration::from_secs(2)).unwrap();
+
+        assert_eq!(list_backups(&backup_dir).unwrap().len(), 2);
+
+        let _ = fs::remove_dir_all(&profile_dir);
+        let _ = fs::remove_dir_all(&backup_dir);
+    }
+
+    #[test]
+    fn list_backups_on_a_directory_that_does_not_exist_yet_is_empty() {
+        let dir = temp_dir("missing");
+        assert_eq!(list_backups(&dir).unwrap(), Vec::new());
+    }
+
+    #[test]
+    fn restore_backup_through_the_manager_brings_records_back() {
+        let profile_dir = temp_dir("restore-profile");
+        let backup_dir = temp_dir("restore-backups");
+        let mut manager = StorageManager::open(&profile_dir, Some("hunter2")).unwrap();
+        manager.database_mut("storage").unwrap().set("k", serde_json::json!("v")).unwrap();
+        let policy = BackupPolicy::default();
+        let id = create_backup(&manager, &backup_dir, &policy, SystemTime::now()).unwrap();
+
+        manager.database_mut("storage").unwrap().remove("k").unwrap();
+        manager.restore_backup(&backup_dir, id).unwrap();
+
+        assert_eq!(manager.database("storage").unwrap().get("k").unwrap().unwrap(), "v");
+
+        let _ = fs::remove_dir_all(&profile_dir);
+        let _ = fs::remove_dir_all(&backup_dir);
+    }
+}
@@ -0,0 +1,152 @@
+use serde::{Deserialize, Serialize};
+
+use crynn_error::StorageError;
+
+use crate::manager::StorageManager;
+
+const BOOKMARK_PREFIX: &str = "bookmark:";
+
+/// A saved bookmark. Minimal for now — no folders or tags, just enough
+/// for a bookmarks panel and [`crate::export_bookmarks`] to work with.
+///
+/// `keyword` is an optional omnibox shortcut for this bookmark (e.g.
+/// `"gh"` for `https://github.com`, or `"w %s"`'s `"w"` for a
+/// parameterized quick search whose `url` contains a `%s` placeholder);
+/// [`save_bookmark`] enforces that at most one bookmark holds a given
+/// keyword at a time.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Bookmark {
+    pub id: String,
+    pub url: String,
+    pub title: String,
+    pub created_at: u64,
+    #[serde(default)]
+    pub keyword: Option<String>,
+}
+
+pub fn save_bookmark(storage: &mut StorageManager, bookmark: &Bookmark) -> Result<(), StorageError> {
+    if let Some(keyword) = &bookmark.keyword {
+        if bookmarks(storage)?.iter().any(|existing| existing.id != bookmark.id && existing.keyword.as_deref() == Some(keyword.as_str())) {
+            return Err(StorageError::KeywordAlreadyAssigned { keyword: keyword.clone() });
+        }
+    }
+    let db = storage.database_mut("storage").expect("storage database always present");
+    db.set(format!("{BOOKMARK_PREFIX}{}", bookmark.id), serde_json::to_value(bookmark)?)?;
+    db.save()
+}
+
+/// The bookmark registered under `keyword`, if any — what the omnibox
+/// classifier resolves a typed keyword against before falling back to
+/// treating the input as a URL or a search.
+pub fn bookmark_by_keyword(storage: &StorageManager, keyword: &str) -> Result<Option<Bookmark>, StorageError> {
+    Ok(bookmarks(storage)?.into_iter().find(|bookmark| bookmark.keyword.as_deref() == Some(keyword)))
+}
+
+/// Silently removes nothing if `id` has no matching bookmark, same as
+/// [`crate::delete_visits_for_domain`] on a domain with none.
+pub fn delete_bookmark(storage: &mut StorageManager, id: &str) -> Result<(), StorageError> {
+    let db = storage.database_mut("storage").expect("storage database always present");
+    db.remove(&format!("{BOOKMARK_PREFIX}{id}"))?;
+    db.save()
+}
+
+pub fn bookmarks(storage: &StorageManager) -> Result<Vec<Bookmark>, StorageError> {
+    let db = storage.database("storage").expect("storage database always present");
+    db.iter()?
+        .filter(|(key, _)| key.starts_with(BOOKMARK_PREFIX))
+        .map(|(_, value)| serde_json::from_value(value.clone()).map_err(StorageError::from))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::path::PathBuf;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("crynn-storage-bookmarks-test-{}-{name}", std::process::id()));
+        let _ = fs::create_dir_all(&dir);
+        dir
+    }
+
+    fn bookmark(id: &str, url: &str) -> Bookmark {
+        Bookmark { id: id.to_string(), url: url.to_string(), title: format!("Title for {url}"), created_at: 1, keyword: None }
+    }
+
+    #[test]
+    fn saved_bookmarks_round_trip() {
+        let dir = temp_dir("round-trip");
+        let mut storage = StorageManager::open(&dir, None).unwrap();
+        save_bookmark(&mut storage, &bookmark("1", "https://example.com")).unwrap();
+
+        let all = bookmarks(&storage).unwrap();
+        assert_eq!(all.len(), 1);
+        assert_eq!(all[0].url, "https://example.com");
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn delete_bookmark_removes_only_that_one() {
+        let dir = temp_dir("delete");
+        let mut storage = StorageManager::open(&dir, None).unwrap();
+        save_bookmark(&mut storage, &bookmark("1", "https://example.com")).unwrap();
+        save_bookmark(&mut storage, &bookmark("2", "https://other.com")).unwrap();
+
+        delete_bookmark(&mut storage, "1").unwrap();
+
+        let remaining = bookmarks(&storage).unwrap();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].id, "2");
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn a_keyword_resolves_to_its_bookmark() {
+        let dir = temp_dir("keyword-resolve");
+        let mut storage = StorageManager::open(&dir, None).unwrap();
+        let mut gh = bookmark("1", "https://github.com");
+        gh.keyword = Some("gh".to_string());
+        save_bookmark(&mut storage, &gh).unwrap();
+
+        assert_eq!(bookmark_by_keyword(&storage, "gh").unwrap(), Some(gh));
+        assert_eq!(bookmark_by_keyword(&storage, "nope").unwrap(), None);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn saving_a_bookmark_with_an_already_assigned_keyword_fails() {
+        let dir = temp_dir("keyword-conflict");
+        let mut storage = StorageManager::open(&dir, None).unwrap();
+        let mut gh = bookmark("1", "https://github.com");
+        gh.keyword = Some("g".to_string());
+        save_bookmark(&mut storage, &gh).unwrap();
+
+        let mut maps = bookmark("2", "https://maps.google.com");
+        maps.keyword = Some("g".to_string());
+        let result = save_bookmark(&mut storage, &maps);
+
+        assert!(matches!(result, Err(StorageError::KeywordAlreadyAssigned { keyword }) if keyword == "g"));
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn resaving_the_same_bookmark_with_its_own_keyword_is_not_a_conflict() {
+        let dir = temp_dir("keyword-resave");
+        let mut storage = StorageManager::open(&dir, None).unwrap();
+        let mut gh = bookmark("1", "https://github.com");
+        gh.keyword = Some("gh".to_string());
+        save_bookmark(&mut storage, &gh).unwrap();
+
+        gh.title = "GitHub".to_string();
+        save_bookmark(&mut storage, &gh).unwrap();
+
+        assert_eq!(bookmarks(&storage).unwrap().len(), 1);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
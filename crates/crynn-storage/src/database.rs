@@ -0,0 +1,320 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use aes_gcm::aead::{Aead, KeyInit};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use rand::RngExt;
+use serde::{Deserialize, Serialize};
+
+use crynn_error::StorageError;
+
+use crate::master_password::{self, KEY_LEN, SALT_LEN};
+
+const NONCE_LEN: usize = 12;
+
+/// The KV store behind a single local database (history, credentials, the
+/// email cache, ...). Encrypted at rest with AES-256-GCM once a master
+/// password is set; plaintext otherwise. Stands in for SQLCipher's
+/// page-level encryption until we can link against it.
+pub struct EncryptedDatabase {
+    name: String,
+    path: PathBuf,
+    state: State,
+}
+
+enum State {
+    Locked,
+    Unlocked {
+        key: Option<[u8; KEY_LEN]>,
+        salt: [u8; SALT_LEN],
+        records: HashMap<String, serde_json::Value>,
+    },
+}
+
+/// On-disk representation. `key` is `None` when the database has no master
+/// password, in which case `payload` is plain JSON rather than ciphertext.
+#[derive(Serialize, Deserialize)]
+struct OnDisk {
+    salt: [u8; SALT_LEN],
+    nonce: Option<[u8; NONCE_LEN]>,
+    payload: Vec<u8>,
+}
+
+impl EncryptedDatabase {
+    /// Creates a fresh, empty database at `path`, optionally protected by a
+    /// master password.
+    pub fn create(
+        name: impl Into<String>,
+        path: impl Into<PathBuf>,
+        password: Option<&str>,
+    ) -> Result<Self, StorageError> {
+        let mut salt = [0u8; SALT_LEN];
+        rand::rng().fill(&mut salt);
+        let key = password.map(|p| master_password::derive_key(p, &salt)).transpose()?;
+
+        let db = Self {
+            name: name.into(),
+            path: path.into(),
+            state: State::Unlocked {
+                key,
+                salt,
+                records: HashMap::new(),
+            },
+        };
+        db.save()?;
+        Ok(db)
+    }
+
+    /// Opens the database at `path` without decrypting it. Use
+    /// [`EncryptedDatabase::unlock`] before reading or writing records.
+    pub fn open_locked(name: impl Into<String>, path: impl Into<PathBuf>) -> Result<Self, StorageError> {
+        let path = path.into();
+        let _ = read_on_disk(&path)?;
+        Ok(Self {
+            name: name.into(),
+            path,
+            state: State::Locked,
+        })
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn is_locked(&self) -> bool {
+        matches!(self.state, State::Locked)
+    }
+
+    /// Derives the key from `password` and decrypts the database's records
+    /// into memory. `password` may be `None` for a database that was never
+    /// given a master password.
+    pub fn unlock(&mut self, password: Option<&str>) -> Result<(), StorageError> {
+        let on_disk = read_on_disk(&self.path)?;
+        let key = password
+            .map(|p| master_password::derive_key(p, &on_disk.salt))
+            .transpose()?;
+        let records = decrypt_records(&self.name, &on_disk, key.as_ref())?;
+        self.state = State::Unlocked {
+            key,
+            salt: on_disk.salt,
+            records,
+        };
+        Ok(())
+    }
+
+    /// Discards the in-memory key and records, leaving the encrypted file on
+    /// disk untouched. Called after an idle timeout or on explicit lock.
+    pub fn lock(&mut self) {
+        if matches!(self.state, State::Unlocked { .. }) {
+            self.state = State::Locked;
+        }
+    }
+
+    pub fn get(&self, key: &str) -> Result<Option<&serde_json::Value>, StorageError> {
+        Ok(self.records()?.get(key))
+    }
+
+    pub fn set(&mut self, key: impl Into<String>, value: serde_json::Value) -> Result<(), StorageError> {
+        self.records_mut()?.insert(key.into(), value);
+        Ok(())
+    }
+
+    pub fn remove(&mut self, key: &str) -> Result<Option<serde_json::Value>, StorageError> {
+        Ok(self.records_mut()?.remove(key))
+    }
+
+    /// Drops every record, used by [`crate::StorageManager::import_all`]
+    /// before restoring an archive into a fresh profile.
+    pub fn clear(&mut self) -> Result<(), StorageError> {
+        self.records_mut()?.clear();
+        Ok(())
+    }
+
+    /// Snapshot of every record, used by full-database export.
+    pub fn iter(&self) -> Result<impl Iterator<Item = (&String, &serde_json::Value)>, StorageError> {
+        Ok(self.records()?.iter())
+    }
+
+    /// Re-encrypts (or re-serializes, if unprotected) and writes the
+    /// database to disk with a fresh nonce.
+    pub fn save(&self) -> Result<(), StorageError> {
+        let (key, salt, records) = match &self.state {
+            State::Unlocked { key, salt, records } => (key, salt, records),
+            State::Locked => return Err(StorageError::MasterPasswordRequired),
+        };
+
+        let on_disk = match key {
+            Some(key) => {
+                let mut nonce_bytes = [0u8; NONCE_LEN];
+                rand::rng().fill(&mut nonce_bytes);
+                let cipher = Aes256Gcm::new(&Key::<Aes256Gcm>::try_from(key.as_slice()).expect("key is 32 bytes"));
+                let plaintext = serde_json::to_vec(records)?;
+                let ciphertext = cipher
+                    .encrypt(&Nonce::try_from(nonce_bytes.as_slice()).expect("nonce is 12 bytes"), plaintext.as_ref())
+                    .map_err(|e| StorageError::Corrupt {
+                        name: self.name.clone(),
+                        detail: e.to_string(),
+                    })?;
+                OnDisk {
+                    salt: *salt,
+                    nonce: Some(nonce_bytes),
+                    payload: ciphertext,
+                }
+            }
+            None => OnDisk {
+                salt: *salt,
+                nonce: None,
+                payload: serde_json::to_vec(records)?,
+            },
+        };
+
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&self.path, serde_json::to_vec(&on_disk)?)?;
+        Ok(())
+    }
+
+    /// Re-derives the key from `new_password` (or drops it, if `None`) and
+    /// re-encrypts every record under it, with a fresh salt.
+    pub fn set_password(&mut self, new_password: Option<&str>) -> Result<(), StorageError> {
+        let records = match &self.state {
+            State::Unlocked { records, .. } => records.clone(),
+            State::Locked => return Err(StorageError::MasterPasswordRequired),
+        };
+
+        let mut salt = [0u8; SALT_LEN];
+        rand::rng().fill(&mut salt);
+        let key = new_password
+            .map(|p| master_password::derive_key(p, &salt))
+            .transpose()?;
+
+        self.state = State::Unlocked { key, salt, records };
+        self.save()
+    }
+
+    fn records(&self) -> Result<&HashMap<String, serde_json::Value>, StorageError> {
+        match &self.state {
+            State::Unlocked { records, .. } => Ok(records),
+            State::Locked => Err(StorageError::MasterPasswordRequired),
+        }
+    }
+
+    fn records_mut(&mut self) -> Result<&mut HashMap<String, serde_json::Value>, StorageError> {
+        match &mut self.state {
+            State::Unlocked { records, .. } => Ok(records),
+            State::Locked => Err(StorageError::MasterPasswordRequired),
+        }
+    }
+}
+
+fn read_on_disk(path: &Path) -> Result<OnDisk, StorageError> {
+    let bytes = fs::read(path)?;
+    Ok(serde_json::from_slice(&bytes)?)
+}
+
+fn decrypt_records(
+    name: &str,
+    on_disk: &OnDisk,
+    key: Option<&[u8; KEY_LEN]>,
+) -> Result<HashMap<String, serde_json::Value>, StorageError> {
+    match (key, on_disk.nonce) {
+        (Some(key), Some(nonce)) => {
+            let cipher = Aes256Gcm::new(&Key::<Aes256Gcm>::try_from(key.as_slice()).expect("key is 32 bytes"));
+            let plaintext = cipher
+                .decrypt(&Nonce::try_from(nonce.as_slice()).expect("nonce is 12 bytes"), on_disk.payload.as_ref())
+                .map_err(|_| StorageError::Corrupt {
+                    name: name.to_string(),
+                    detail: "wrong master password or corrupted ciphertext".to_string(),
+                })?;
+            Ok(serde_json::from_slice(&plaintext)?)
+        }
+        (None, None) => Ok(serde_json::from_slice(&on_disk.payload)?),
+        _ => Err(StorageError::Corrupt {
+            name: name.to_string(),
+            detail: "master password state does not match on-disk format".to_string(),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!(
+            "crynn-storage-test-{}-{name}-{:?}",
+            std::process::id(),
+            std::thread::current().id()
+        ))
+    }
+
+    #[test]
+    fn round_trips_records_through_encryption() {
+        let path = temp_path("round-trip.db");
+        let mut db = EncryptedDatabase::create("test", &path, Some("hunter2")).unwrap();
+        db.set("greeting", serde_json::json!("hello")).unwrap();
+        db.save().unwrap();
+
+        let mut reopened = EncryptedDatabase::open_locked("test", &path).unwrap();
+        assert!(reopened.is_locked());
+        reopened.unlock(Some("hunter2")).unwrap();
+        assert_eq!(reopened.get("greeting").unwrap().unwrap(), "hello");
+
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn unlocking_with_the_wrong_password_fails() {
+        let path = temp_path("wrong-password.db");
+        EncryptedDatabase::create("test", &path, Some("right")).unwrap();
+
+        let mut db = EncryptedDatabase::open_locked("test", &path).unwrap();
+        let err = db.unlock(Some("wrong")).unwrap_err();
+        assert!(matches!(err, StorageError::Corrupt { .. }));
+
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn reading_a_locked_database_requires_the_master_password() {
+        let path = temp_path("locked-read.db");
+        EncryptedDatabase::create("test", &path, Some("hunter2")).unwrap();
+
+        let db = EncryptedDatabase::open_locked("test", &path).unwrap();
+        let err = db.get("greeting").unwrap_err();
+        assert!(matches!(err, StorageError::MasterPasswordRequired));
+
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn changing_the_password_reencrypts_existing_records() {
+        let path = temp_path("change-password.db");
+        let mut db = EncryptedDatabase::create("test", &path, Some("old")).unwrap();
+        db.set("k", serde_json::json!(1)).unwrap();
+        db.set_password(Some("new")).unwrap();
+
+        let mut reopened = EncryptedDatabase::open_locked("test", &path).unwrap();
+        assert!(reopened.unlock(Some("old")).is_err());
+        reopened.unlock(Some("new")).unwrap();
+        assert_eq!(reopened.get("k").unwrap().unwrap(), 1);
+
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn removing_the_password_leaves_records_readable_without_one() {
+        let path = temp_path("remove-password.db");
+        let mut db = EncryptedDatabase::create("test", &path, Some("hunter2")).unwrap();
+        db.set("k", serde_json::json!("v")).unwrap();
+        db.set_password(None).unwrap();
+
+        let mut reopened = EncryptedDatabase::open_locked("test", &path).unwrap();
+        reopened.unlock(None).unwrap();
+        assert_eq!(reopened.get("k").unwrap().unwrap(), "v");
+
+        let _ = fs::remove_file(&path);
+    }
+}
@@ -0,0 +1,379 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use crynn_error::StorageError;
+
+use crate::manager::StorageManager;
+
+const HISTORY_PREFIX: &str = "history:";
+
+/// How a page ended up in history. Typed and bookmark visits are
+/// deliberate, so [`frecency`] weights them well above a redirect hop a
+/// page made on the way to somewhere else.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum VisitType {
+    /// The user typed or pasted the URL into the address bar.
+    Typed,
+    /// Reached by clicking a link on another page.
+    Link,
+    /// An HTTP or script redirect the page made on its own, not a
+    /// visit the user chose.
+    Redirect,
+    /// Opened from a saved bookmark.
+    Bookmark,
+    /// The same page, reloaded.
+    Reload,
+}
+
+/// One visit to a URL. `from_visit` is the id of the visit that led here
+/// (the page whose link was clicked, or whose redirect landed here) —
+/// `None` for a visit with no predecessor, e.g. a typed URL or the first
+/// tab opened. [`visit_chain`] follows this chain backward for "how did I
+/// get here" display.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Visit {
+    pub id: String,
+    pub url: String,
+    pub title: String,
+    pub visit_type: VisitType,
+    pub at: u64,
+    pub from_visit: Option<String>,
+}
+
+pub fn record_visit(storage: &mut StorageManager, visit: &Visit) -> Result<(), StorageError> {
+    let db = storage.database_mut("storage").expect("storage database always present");
+    db.set(format!("{HISTORY_PREFIX}{}", visit.id), serde_json::to_value(visit)?)?;
+    db.save()
+}
+
+/// Deletes every visit whose URL's host is `domain`, e.g. when the user
+/// removes a site from the history panel. Silently removes nothing if
+/// `domain` has no recorded visits.
+pub fn delete_visits_for_domain(storage: &mut StorageManager, domain: &str) -> Result<(), StorageError> {
+    let keys: Vec<String> = visits(storage)?
+        .into_iter()
+        .filter(|visit| domain_of(&visit.url) == domain)
+        .map(|visit| format!("{HISTORY_PREFIX}{}", visit.id))
+        .collect();
+    let db = storage.database_mut("storage").expect("storage database always present");
+    for key in keys {
+        db.remove(&key)?;
+    }
+    db.save()
+}
+
+/// Deletes every visit whose id is in `ids`, used by
+/// [`crate::apply_retention`] to act on what a dry-run
+/// [`crate::RetentionPlan`] decided to remove. Silently skips an id with
+/// no matching visit, same as [`delete_visits_for_domain`] on a domain
+/// with none.
+pub fn delete_visits_by_id(storage: &mut StorageManager, ids: &[String]) -> Result<(), StorageError> {
+    let db = storage.database_mut("storage").expect("storage database always present");
+    for id in ids {
+        db.remove(&format!("{HISTORY_PREFIX}{id}"))?;
+    }
+    db.save()
+}
+
+pub fn visits(storage: &StorageManager) -> Result<Vec<Visit>, StorageError> {
+    let db = storage.database("storage").expect("storage database always present");
+    db.iter()?
+        .filter(|(key, _)| key.starts_with(HISTORY_PREFIX))
+        .map(|(_, value)| serde_json::from_value(value.clone()).map_err(StorageError::from))
+        .collect()
+}
+
+/// Follows `from_visit` back from `id`, starting with the visit itself,
+/// so the UI can show "how did I get here": typed `example.com`, which
+/// linked to `example.com/sale`, which redirected to
+/// `example.com/sale/2026`.
+pub fn visit_chain(storage: &StorageManager, id: &str) -> Result<Vec<Visit>, StorageError> {
+    let all = visits(storage)?;
+    let mut chain = Vec::new();
+    let mut current = all.iter().find(|v| v.id == id).cloned();
+    while let Some(visit) = current {
+        current = visit
+            .from_visit
+            .as_ref()
+            .and_then(|from_id| all.iter().find(|v| &v.id == from_id))
+            .cloned();
+        chain.push(visit);
+    }
+    Ok(chain)
+}
+
+/// Base weight for one visit, before recency decay. Deliberate visits
+/// (typed, bookmark) count for far more than a redirect the page made on
+/// its own, so the address bar ranks sites the user actually chose to
+/// visit above ones they were only passing through.
+fn type_weight(visit_type: VisitType) -> f64 {
+    match visit_type {
+        VisitType::Typed => 20.0,
+        VisitType::Bookmark => 10.0,
+        VisitType::Link => 5.0,
+        VisitType::Reload => 1.0,
+        VisitType::Redirect => 0.0,
+    }
+}
+
+/// This visit's contribution to its URL's frecency at `now`: its type
+/// weight, decayed by age so a single old typed visit doesn't outrank a
+/// site visited constantly this week. Not Firefox's exact frecency
+/// formula — just the same frequency-times-recency-times-visit-type
+/// shape, good enough to rank the address bar's suggestions.
+pub fn frecency(visit: &Visit, now: u64) -> f64 {
+    let age_days = now.saturating_sub(visit.at) as f64 / 86_400.0;
+    type_weight(visit.visit_type) / (age_days + 1.0)
+}
+
+/// Summed [`frecency`] across every recorded visit to `url`, for ranking
+/// address bar suggestions.
+pub fn frecency_for_url(storage: &StorageManager, url: &str, now: u64) -> Result<f64, StorageError> {
+    Ok(visits(storage)?.iter().filter(|v| v.url == url).map(|v| frecency(v, now)).sum())
+}
+
+/// Browsing statistics over a time range, for the new-tab page's
+/// statistics view and the data export.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HistoryStats {
+    /// Visit count keyed by day (Unix time divided by seconds-per-day).
+    pub visits_per_day: BTreeMap<u64, u32>,
+    /// `(domain, visit count)`, most-visited first; ties break
+    /// alphabetically so the order is stable.
+    pub top_domains: Vec<(String, u32)>,
+    /// Visit count keyed by hour of day, `0..24`.
+    pub busiest_hours: BTreeMap<u8, u32>,
+    pub unique_sites: usize,
+}
+
+const SECONDS_PER_DAY: u64 = 86_400;
+const SECONDS_PER_HOUR: u64 = 3_600;
+
+/// Computes [`HistoryStats`] over visits with `at` in `since..until`.
+pub fn stats(storage: &StorageManager, since: u64, until: u64) -> Result<HistoryStats, StorageError> {
+    let in_range: Vec<Visit> = visits(storage)?.into_iter().filter(|v| v.at >= since && v.at < until).collect();
+
+    let mut visits_per_day = BTreeMap::new();
+    let mut busiest_hours = BTreeMap::new();
+    let mut domain_counts: HashMap<String, u32> = HashMap::new();
+    let mut sites = HashSet::new();
+
+    for visit in &in_range {
+        *visits_per_day.entry(visit.at / SECONDS_PER_DAY).or_insert(0) += 1;
+        *busiest_hours.entry(((visit.at / SECONDS_PER_HOUR) % 24) as u8).or_insert(0) += 1;
+        let domain = domain_of(&visit.url).to_string();
+        *domain_counts.entry(domain.clone()).or_insert(0) += 1;
+        sites.insert(domain);
+    }
+
+    let mut top_domains: Vec<(String, u32)> = domain_counts.into_iter().collect();
+    top_domains.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+    Ok(HistoryStats {
+        visits_per_day,
+        top_domains,
+        busiest_hours,
+        unique_sites: sites.len(),
+    })
+}
+
+/// The most recent visit to `domain`, for the retention scheduler's
+/// "cookies from sites not visited in N days" rule — cookies don't carry
+/// their own last-visit timestamp, so this is the closest proxy for it.
+/// `None` if `domain` has no recorded visits.
+pub fn last_visit_at(storage: &StorageManager, domain: &str) -> Result<Option<u64>, StorageError> {
+    Ok(visits(storage)?.into_iter().filter(|v| domain_of(&v.url) == domain).map(|v| v.at).max())
+}
+
+/// Extracts the host from a URL, good enough to group visits by site for
+/// [`stats`]. Mirrors `crynn-tracking-protection::host_from_url`, kept
+/// local rather than adding a dependency just for this.
+fn domain_of(url: &str) -> &str {
+    let without_scheme = url.split("://").nth(1).unwrap_or(url);
+    let host_and_port = without_scheme.split(['/', '?', '#']).next().unwrap_or(without_scheme);
+    let host_and_port = host_and_port.rsplit('@').next().unwrap_or(host_and_port);
+    host_and_port.split(':').next().unwrap_or(host_and_port)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::path::PathBuf;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("crynn-storage-history-test-{}-{name}", std::process::id()));
+        let _ = fs::create_dir_all(&dir);
+        dir
+    }
+
+    fn visit(id: &str, url: &str, visit_type: VisitType, at: u64, from_visit: Option<&str>) -> Visit {
+        Visit {
+            id: id.to_string(),
+            url: url.to_string(),
+            title: format!("Title for {url}"),
+            visit_type,
+            at,
+            from_visit: from_visit.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn recorded_visits_round_trip() {
+        let dir = temp_dir("round-trip");
+        let mut storage = StorageManager::open(&dir, None).unwrap();
+        record_visit(&mut storage, &visit("1", "https://example.com", VisitType::Typed, 1, None)).unwrap();
+
+        let all = visits(&storage).unwrap();
+        assert_eq!(all.len(), 1);
+        assert_eq!(all[0].url, "https://example.com");
+        assert_eq!(all[0].visit_type, VisitType::Typed);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn visit_chain_follows_from_visit_back_to_the_start() {
+        let dir = temp_dir("chain");
+        let mut storage = StorageManager::open(&dir, None).unwrap();
+        record_visit(&mut storage, &visit("1", "https://example.com", VisitType::Typed, 1, None)).unwrap();
+        record_visit(&mut storage, &visit("2", "https://example.com/sale", VisitType::Link, 2, Some("1"))).unwrap();
+        record_visit(&mut storage, &visit("3", "https://example.com/sale/2026", VisitType::Redirect, 3, Some("2"))).unwrap();
+
+        let chain = visit_chain(&storage, "3").unwrap();
+
+        assert_eq!(chain.iter().map(|v| v.id.as_str()).collect::<Vec<_>>(), vec!["3", "2", "1"]);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn visit_chain_on_an_unknown_id_is_empty() {
+        let dir = temp_dir("chain-unknown");
+        let storage = StorageManager::open(&dir, None).unwrap();
+        assert!(visit_chain(&storage, "missing").unwrap().is_empty());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn typed_visits_outweigh_redirects_at_the_same_age() {
+        let typed = visit("1", "https://example.com", VisitType::Typed, 0, None);
+        let redirect = visit("2", "https://example.com", VisitType::Redirect, 0, None);
+        assert!(frecency(&typed, 0) > frecency(&redirect, 0));
+    }
+
+    #[test]
+    fn older_visits_decay_towards_zero() {
+        let recent = visit("1", "https://example.com", VisitType::Link, 100, None);
+        let old = visit("2", "https://example.com", VisitType::Link, 0, None);
+        assert!(frecency(&recent, 100) > frecency(&old, 100));
+    }
+
+    #[test]
+    fn stats_counts_visits_per_day_and_top_domains() {
+        let dir = temp_dir("stats");
+        let mut storage = StorageManager::open(&dir, None).unwrap();
+        record_visit(&mut storage, &visit("1", "https://example.com/a", VisitType::Typed, 0, None)).unwrap();
+        record_visit(&mut storage, &visit("2", "https://example.com/b", VisitType::Link, 10, None)).unwrap();
+        record_visit(&mut storage, &visit("3", "https://other.com", VisitType::Typed, SECONDS_PER_DAY, None)).unwrap();
+
+        let result = stats(&storage, 0, u64::MAX).unwrap();
+
+        assert_eq!(result.visits_per_day.get(&0), Some(&2));
+        assert_eq!(result.visits_per_day.get(&1), Some(&1));
+        assert_eq!(result.top_domains[0], ("example.com".to_string(), 2));
+        assert_eq!(result.unique_sites, 2);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn stats_buckets_busiest_hours_by_hour_of_day() {
+        let dir = temp_dir("stats-hours");
+        let mut storage = StorageManager::open(&dir, None).unwrap();
+        record_visit(&mut storage, &visit("1", "https://example.com", VisitType::Typed, 2 * SECONDS_PER_HOUR, None)).unwrap();
+        record_visit(&mut storage, &visit("2", "https://example.com", VisitType::Typed, 2 * SECONDS_PER_HOUR + 30, None)).unwrap();
+
+        let result = stats(&storage, 0, u64::MAX).unwrap();
+
+        assert_eq!(result.busiest_hours.get(&2), Some(&2));
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn stats_excludes_visits_outside_the_time_range() {
+        let dir = temp_dir("stats-range");
+        let mut storage = StorageManager::open(&dir, None).unwrap();
+        record_visit(&mut storage, &visit("1", "https://example.com", VisitType::Typed, 5, None)).unwrap();
+        record_visit(&mut storage, &visit("2", "https://example.com", VisitType::Typed, 500, None)).unwrap();
+
+        let result = stats(&storage, 0, 100).unwrap();
+
+        assert_eq!(result.unique_sites, 1);
+        assert_eq!(result.top_domains[0].1, 1);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn delete_visits_for_domain_removes_only_that_domains_visits() {
+        let dir = temp_dir("delete-domain");
+        let mut storage = StorageManager::open(&dir, None).unwrap();
+        record_visit(&mut storage, &visit("1", "https://example.com/a", VisitType::Typed, 0, None)).unwrap();
+        record_visit(&mut storage, &visit("2", "https://example.com/b", VisitType::Link, 1, None)).unwrap();
+        record_visit(&mut storage, &visit("3", "https://other.com", VisitType::Typed, 2, None)).unwrap();
+
+        delete_visits_for_domain(&mut storage, "example.com").unwrap();
+
+        let remaining = visits(&storage).unwrap();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].url, "https://other.com");
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn delete_visits_by_id_removes_only_the_listed_ids() {
+        let dir = temp_dir("delete-by-id");
+        let mut storage = StorageManager::open(&dir, None).unwrap();
+        record_visit(&mut storage, &visit("1", "https://example.com/a", VisitType::Typed, 0, None)).unwrap();
+        record_visit(&mut storage, &visit("2", "https://example.com/b", VisitType::Link, 1, None)).unwrap();
+
+        delete_visits_by_id(&mut storage, &["1".to_string()]).unwrap();
+
+        let remaining = visits(&storage).unwrap();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].id, "2");
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn last_visit_at_reports_the_most_recent_visit_for_that_domain() {
+        let dir = temp_dir("last-visit-at");
+        let mut storage = StorageManager::open(&dir, None).unwrap();
+        record_visit(&mut storage, &visit("1", "https://example.com/a", VisitType::Typed, 5, None)).unwrap();
+        record_visit(&mut storage, &visit("2", "https://example.com/b", VisitType::Link, 20, None)).unwrap();
+
+        assert_eq!(last_visit_at(&storage, "example.com").unwrap(), Some(20));
+        assert_eq!(last_visit_at(&storage, "other.com").unwrap(), None);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn frecency_for_url_sums_every_visit_to_that_url() {
+        let dir = temp_dir("frecency-sum");
+        let mut storage = StorageManager::open(&dir, None).unwrap();
+        record_visit(&mut storage, &visit("1", "https://example.com", VisitType::Typed, 0, None)).unwrap();
+        record_visit(&mut storage, &visit("2", "https://example.com", VisitType::Typed, 0, None)).unwrap();
+        record_visit(&mut storage, &visit("3", "https://other.com", VisitType::Typed, 0, None)).unwrap();
+
+        let score = frecency_for_url(&storage, "https://example.com", 0).unwrap();
+        assert_eq!(score, frecency(&visit("1", "https://example.com", VisitType::Typed, 0, None), 0) * 2.0);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,139 @@
+//! Local encrypted storage for the profile's data: history, bookmarks and
+//! cookies in the `storage` database, the offline email cache, and saved
+//! credentials — each AES-256-GCM-encrypted at rest with a key derived via
+//! Argon2 from an optional master password. Stands in for SQLCipher's
+//! page-level encryption until we can link against it in this environment.
+//!
+//! [`StorageManager`] owns all three databases and applies one master
+//! password across them; [`StorageManager::maybe_auto_lock`] locks them
+//! after an idle period so a device left unattended doesn't leave
+//! credentials decrypted in memory.
+//!
+//! The `autofill` module builds typed address and payment profiles on top
+//! of the same databases, plus a query API keyed by [`FormSignature`] that
+//! the engine layer calls through after a user confirms a fill. `history`
+//! does the same for visits: a typed [`Visit`] record with a
+//! [`VisitType`] and a `from_visit` reference, plus [`frecency`] to rank
+//! them for the address bar.
+//!
+//! `retention` is the standing, user-configurable counterpart to
+//! `delete_visits_for_domain`'s one-off deletion: a [`RetentionRule`]
+//! describes a cleanup policy ("history older than 90 days", "cap
+//! history at N bytes"), [`plan`] previews what evaluating it would
+//! remove, and [`apply_retention`] actually removes it. No real
+//! on-disk HTTP cache exists yet in this build for a byte cap to
+//! target, so [`RetentionRule::HistoryCapBytes`] caps history's own
+//! estimated size instead.
+//!
+//! `bookmarks` is history's much smaller sibling — a [`Bookmark`] is
+//! just an id, url, title and creation time, with the same save/delete/
+//! list shape. `export` writes either one out as CSV (for a
+//! spreadsheet) or JSON (which [`import_history`] can read back in),
+//! streaming record-by-record rather than building the whole export in
+//! memory first.
+//!
+//! `vpn_sessions` persists completed `crynn-vpn` connections —
+//! provider, location, connect/disconnect times, bytes up/down — so a
+//! restart doesn't lose the VPN panel's usage history; [`usage_per_day`]
+//! buckets it by day for that panel's chart.
+//!
+//! `personal_dictionary` is `crynn-spellcheck`'s equivalent of
+//! `vpn_sessions`: it persists the words a user has added to their
+//! personal dictionary so a fresh `crynn_spellcheck::SpellChecker` can
+//! be seeded with them at startup, the same split that crate's own
+//! in-memory `SpellChecker::personal_words` leaves to its caller.
+//!
+//! `view_state` persists each origin's zoom level, scroll position, and
+//! text size, so reopening a site on navigation or session restore can
+//! put the page back where the user left it rather than at the top of
+//! the page at 100% zoom. [`save_view_state`] evicts the
+//! least-recently-used origin once there are more than its `capacity`
+//! on file, the same LRU-over-wipe-the-whole-cache approach
+//! `crynn_network::DnsResolver` takes for resolved hosts.
+//!
+//! `tempfiles` is the plaintext-on-disk counterpart to this crate's
+//! encrypted databases, for bytes that only ever need to survive one
+//! session: [`open_download_part`] streams a download into a
+//! `.part`-suffixed file with owner-only permissions, and
+//! [`finalize_download`] drops the suffix once the transfer completes.
+//! [`resumable_downloads`] is what a restart reads back to offer
+//! resuming a `.part` file it finds still sitting there, and
+//! [`garbage_collect_downloads`] removes the ones old enough that
+//! they're more likely abandoned than still wanted. [`open_attachment_dir`]
+//! gives each email attachment its own owner-only temp directory so one
+//! attachment can't read or overwrite another's extracted bytes, and
+//! [`cleanup_attachment_dir`] removes it once its viewer closes.
+//!
+//! `webapps` persists sites installed as standalone "web apps" —
+//! [`WebApp`] is bookmarks' shape again (id, a couple of fields, a
+//! timestamp), keyed the same way in the `storage` database, for
+//! `crynn-shell`'s `WebAppManager` to drive its OS-launcher-entry seam
+//! from.
+//!
+//! [`StorageManager::open_with_recovery`] is a startup-time alternative to
+//! [`StorageManager::open`] that verifies the profile directory first:
+//! stray files get removed, and a database that fails to parse or decrypt
+//! gets quarantined and restored from an [`StorageManager::export_all`]
+//! archive or recreated empty. It reports every [`IntegrityIssue`] and
+//! [`RecoveryAction`] it took back to the caller rather than posting to an
+//! event bus itself, the same split `crynn_network::CertificateValidator`
+//! leaves to whatever shows the user its interstitial.
+//!
+//! `backup` is what actually fills that `backup_path`: [`create_backup`]
+//! snapshots a [`StorageManager`] to a timestamped [`BackupId`]-named
+//! archive and rotates out whatever [`BackupPolicy::keep`] no longer has
+//! room for, [`is_backup_due`] is what a caller polls on its own schedule
+//! (this crate runs no timers of its own) to decide when to call it next,
+//! and [`list_backups`] is the same kind of picker data
+//! [`IntegrityReport`] already hands the shell, this time for choosing
+//! which backup [`StorageManager::restore_backup`] should restore.
+//!
+//! `search` is the single ranked query [`crate::url_utils`]'s bookmark
+//! and history lookups (in `crynn-shell`) would otherwise have to run
+//! separately against each store: [`search_local_data`] scores
+//! [`Bookmark`]s and [`Visit`]s together into one [`SearchResult`] list,
+//! tagged by [`SourceKind`] so a caller can render "Bookmark" vs.
+//! "History" differently. [`SourceKind::ReadingList`],
+//! [`SourceKind::CachedPageTitle`], and [`SourceKind::EmailSubject`]
+//! round out the kinds a unified index needs to carry, even though
+//! nothing in this build feeds [`search_local_data`] from them yet.
+
+mod autofill;
+mod backup;
+mod bookmarks;
+mod database;
+mod export;
+mod history;
+mod manager;
+mod master_password;
+mod personal_dictionary;
+mod retention;
+mod search;
+mod tempfiles;
+mod view_state;
+mod vpn_sessions;
+mod webapps;
+
+pub use autofill::{
+    addresses, confirm_fill, delete_address, delete_payment_profile, fill_values, payment_profiles, save_address,
+    save_payment_profile, AddressProfile, FieldKind, FormSignature, PaymentProfile,
+};
+pub use backup::{create_backup, is_backup_due, list_backups, BackupId, BackupPolicy, DEFAULT_BACKUP_COUNT, DEFAULT_BACKUP_FREQUENCY};
+pub use bookmarks::{bookmark_by_keyword, bookmarks, delete_bookmark, save_bookmark, Bookmark};
+pub use database::EncryptedDatabase;
+pub use export::{export_bookmarks, export_history, import_history, ExportFormat};
+pub use history::{
+    delete_visits_by_id, delete_visits_for_domain, frecency, frecency_for_url, last_visit_at, record_visit, stats,
+    visit_chain, visits, HistoryStats, Visit, VisitType,
+};
+pub use manager::{IntegrityIssue, IntegrityReport, RecoveryAction, StorageManager, DEFAULT_AUTO_LOCK_AFTER};
+pub use personal_dictionary::{add_word as add_personal_word, remove_word as remove_personal_word, words as personal_words};
+pub use retention::{apply_retention, plan, RetentionPlan, RetentionRule};
+pub use search::{search_local_data, SearchResult, SourceKind, BOOKMARK_SCORE};
+pub use tempfiles::{
+    cleanup_attachment_dir, finalize_download, garbage_collect_downloads, open_attachment_dir, open_download_part,
+    resumable_downloads, PartialDownload, PART_EXTENSION, STALE_DOWNLOAD_AFTER,
+};
+pub use view_state::{save_view_state, view_state_for, ViewState, DEFAULT_CAPACITY as VIEW_STATE_DEFAULT_CAPACITY};
+pub use vpn_sessions::{record_session, usage_per_day, vpn_sessions, VpnSession};
+pub use webapps::{install_webapp, uninstall_webapp, webapp_by_id, webapps, WebApp};
@@ -0,0 +1,434 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crynn_error::StorageError;
+
+use crate::database::EncryptedDatabase;
+
+/// A single backup/migration archive: every database's records, in the
+/// clear. Produced by [`StorageManager::export_all`] and consumed by
+/// [`StorageManager::import_all`].
+#[derive(Serialize, Deserialize)]
+struct Archive {
+    databases: HashMap<String, HashMap<String, serde_json::Value>>,
+}
+
+/// Idle period after which [`StorageManager::maybe_auto_lock`] locks every
+/// database, requiring the master password again.
+pub const DEFAULT_AUTO_LOCK_AFTER: Duration = Duration::from_secs(15 * 60);
+
+const DATABASE_NAMES: [&str; 3] = ["storage", "email_cache", "credentials"];
+
+/// Owns the databases behind bookmarks/history/cookies, the email cache,
+/// and saved credentials, and applies one master password across all of
+/// them.
+pub struct StorageManager {
+    databases: Vec<EncryptedDatabase>,
+    auto_lock_after: Duration,
+    last_activity: Instant,
+}
+
+impl StorageManager {
+    /// Opens (creating if missing) every database under `dir`, unlocked
+    /// with `password`.
+    pub fn open(dir: impl AsRef<Path>, password: Option<&str>) -> Result<Self, StorageError> {
+        let dir = dir.as_ref();
+        let mut databases = Vec::with_capacity(DATABASE_NAMES.len());
+        for name in DATABASE_NAMES {
+            let path = dir.join(format!("{name}.db"));
+            let db = if path.exists() {
+                let mut db = EncryptedDatabase::open_locked(name, &path)?;
+                db.unlock(password)?;
+                db
+            } else {
+                EncryptedDatabase::create(name, &path, password)?
+            };
+            databases.push(db);
+        }
+
+        Ok(Self {
+            databases,
+            auto_lock_after: DEFAULT_AUTO_LOCK_AFTER,
+            last_activity: Instant::now(),
+        })
+    }
+
+    pub fn auto_lock_after(&self) -> Duration {
+        self.auto_lock_after
+    }
+
+    pub fn set_auto_lock_after(&mut self, duration: Duration) {
+        self.auto_lock_after = duration;
+    }
+
+    pub fn database(&self, name: &str) -> Option<&EncryptedDatabase> {
+        self.databases.iter().find(|db| db.name() == name)
+    }
+
+    pub fn database_mut(&mut self, name: &str) -> Option<&mut EncryptedDatabase> {
+        self.databases.iter_mut().find(|db| db.name() == name)
+    }
+
+    pub fn is_locked(&self) -> bool {
+        self.databases.iter().any(|db| db.is_locked())
+    }
+
+    /// Resets the idle timer. Call on every user-driven read or write.
+    pub fn touch(&mut self) {
+        self.last_activity = Instant::now();
+    }
+
+    /// Locks every database if `now` is past the auto-lock deadline. Returns
+    /// whether it locked anything.
+    pub fn maybe_auto_lock(&mut self, now: Instant) -> bool {
+        if now.saturating_duration_since(self.last_activity) < self.auto_lock_after {
+            return false;
+        }
+        for db in &mut self.databases {
+            db.lock();
+        }
+        true
+    }
+
+    /// Unlocks every database with `password` after an auto-lock (or at
+    /// startup, if opened without one).
+    pub fn unlock(&mut self, password: Option<&str>) -> Result<(), StorageError> {
+        for db in &mut self.databases {
+            db.unlock(password)?;
+        }
+        self.touch();
+        Ok(())
+    }
+
+    /// Changes (or sets, or removes, if `new_password` is `None`) the
+    /// master password across every database.
+    pub fn change_password(&mut self, new_password: Option<&str>) -> Result<(), StorageError> {
+        for db in &mut self.databases {
+            db.set_password(new_password)?;
+        }
+        Ok(())
+    }
+
+    /// Writes every database's records (bookmarks, history, cookies,
+    /// settings, saved sessions, download records, ...) to a single
+    /// plaintext archive at `path`, for backup or machine migration.
+    pub fn export_all(&self, path: impl AsRef<Path>) -> Result<(), StorageError> {
+        let mut databases = HashMap::with_capacity(self.databases.len());
+        for db in &self.databases {
+            let records = db
+                .iter()?
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect::<HashMap<_, _>>();
+            databases.insert(db.name().to_string(), records);
+        }
+        let archive = Archive { databases };
+        fs::write(path, serde_json::to_vec(&archive)?)?;
+        Ok(())
+    }
+
+    /// Restores an archive produced by [`StorageManager::export_all`] into
+    /// this (fresh) profile, replacing whatever records are currently
+    /// present.
+    pub fn import_all(&mut self, path: impl AsRef<Path>) -> Result<(), StorageError> {
+        let bytes = fs::read(path)?;
+        let archive: Archive = serde_json::from_slice(&bytes)?;
+
+        for db in &mut self.databases {
+            let Some(records) = archive.databases.get(db.name()) else {
+                continue;
+            };
+            db.clear()?;
+            for (key, value) in records {
+                db.set(key.clone(), value.clone())?;
+            }
+            db.save()?;
+        }
+        Ok(())
+    }
+
+    /// Restores this profile from the rotating backup `id` under
+    /// `backup_dir` (one taken by [`crate::create_backup`]), through the
+    /// same [`StorageManager::import_all`] archive format `export_all`
+    /// writes and [`StorageManager::open_with_recovery`]'s `backup_path`
+    /// already reads.
+    pub fn restore_backup(&mut self, backup_dir: impl AsRef<Path>, id: crate::backup::BackupId) -> Result<(), StorageError> {
+        self.import_all(crate::backup::backup_path(backup_dir.as_ref(), id))
+    }
+
+    /// Like [`StorageManager::open`], but verifies every database first and
+    /// repairs what it can before opening. A file under `dir` that isn't one
+    /// of [`DATABASE_NAMES`] is treated as orphaned and removed; a
+    /// `{name}.db` that fails to parse or decrypt is quarantined (renamed
+    /// aside with a `.corrupt` suffix) and then either restored from
+    /// `backup_path` (an [`StorageManager::export_all`] archive, if the
+    /// database it's missing has an entry there) or recreated empty. This
+    /// format has no separate index to rebuild the way a SQLite database
+    /// would — every database is one JSON map — so "rebuild" here means
+    /// restoring or recreating that map rather than repairing an index.
+    /// Every [`IntegrityIssue`] found and [`RecoveryAction`] taken is
+    /// returned alongside the opened manager for the caller to report
+    /// through `crynn-shell`'s event bus; this crate has no event bus of
+    /// its own to post to.
+    pub fn open_with_recovery(
+        dir: impl AsRef<Path>,
+        password: Option<&str>,
+        backup_path: Option<&Path>,
+    ) -> Result<(Self, IntegrityReport), StorageError> {
+        let dir = dir.as_ref();
+        fs::create_dir_all(dir)?;
+        let mut report = IntegrityReport::default();
+
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            if !path.is_file() {
+                continue;
+            }
+            let is_known_database = path.extension().and_then(|ext| ext.to_str()) == Some("db")
+                && path.file_stem().and_then(|stem| stem.to_str()).is_some_and(|stem| DATABASE_NAMES.contains(&stem));
+            if is_known_database {
+                continue;
+            }
+            report.issues.push(IntegrityIssue::OrphanedFile { path: path.clone() });
+            if fs::remove_file(&path).is_ok() {
+                report.recovered.push(RecoveryAction::RemovedOrphan { path });
+            }
+        }
+
+        for name in DATABASE_NAMES {
+            let path = dir.join(format!("{name}.db"));
+            if !path.exists() {
+                continue;
+            }
+            let opened = EncryptedDatabase::open_locked(name, &path).and_then(|mut db| db.unlock(password));
+            let Err(error) = opened else {
+                continue;
+            };
+            report.issues.push(IntegrityIssue::CorruptDatabase { name: name.to_string(), detail: error.to_string() });
+            let _ = fs::rename(&path, path.with_extension("db.corrupt"));
+
+            let restored = backup_path.map(|backup| restore_from_backup(name, &path, backup, password)).transpose()?.unwrap_or(false);
+            if restored {
+                report.recovered.push(RecoveryAction::RestoredFromBackup { name: name.to_string() });
+            } else {
+                EncryptedDatabase::create(name, &path, password)?;
+                report.recovered.push(RecoveryAction::RecreatedEmpty { name: name.to_string() });
+            }
+        }
+
+        let manager = Self::open(dir, password)?;
+        Ok((manager, report))
+    }
+}
+
+/// Recreates `{name}.db` at `path` from `backup_path`'s archive, if it has
+/// an entry for `name`. Returns whether a backup entry was found.
+fn restore_from_backup(name: &str, path: &Path, backup_path: &Path, password: Option<&str>) -> Result<bool, StorageError> {
+    let bytes = fs::read(backup_path)?;
+    let archive: Archive = serde_json::from_slice(&bytes)?;
+    let Some(records) = archive.databases.get(name) else {
+        return Ok(false);
+    };
+
+    let mut db = EncryptedDatabase::create(name, path, password)?;
+    for (key, value) in records {
+        db.set(key.clone(), value.clone())?;
+    }
+    db.save()?;
+    Ok(true)
+}
+
+/// A problem [`StorageManager::open_with_recovery`] found while verifying
+/// the profile directory at startup.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IntegrityIssue {
+    /// `{name}.db` exists but failed to parse or decrypt.
+    CorruptDatabase { name: String, detail: String },
+    /// A file in the profile directory that isn't one of [`DATABASE_NAMES`].
+    OrphanedFile { path: PathBuf },
+}
+
+/// What [`StorageManager::open_with_recovery`] did about an
+/// [`IntegrityIssue`] it found.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RecoveryAction {
+    /// The corrupt database was quarantined and restored from a backup
+    /// archive.
+    RestoredFromBackup { name: String },
+    /// No usable backup was available, so the corrupt database was
+    /// quarantined and recreated empty.
+    RecreatedEmpty { name: String },
+    /// An orphaned file was removed.
+    RemovedOrphan { path: PathBuf },
+}
+
+/// Every [`IntegrityIssue`] [`StorageManager::open_with_recovery`] found,
+/// paired with the [`RecoveryAction`] taken for each.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IntegrityReport {
+    pub issues: Vec<IntegrityIssue>,
+    pub recovered: Vec<RecoveryAction>,
+}
+
+impl IntegrityReport {
+    pub fn is_clean(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::path::PathBuf;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("crynn-storage-manager-test-{}-{name}", std::process::id()));
+        let _ = fs::create_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn open_creates_all_three_databases() {
+        let dir = temp_dir("create-all");
+        let manager = StorageManager::open(&dir, Some("hunter2")).unwrap();
+        assert!(manager.database("storage").is_some());
+        assert!(manager.database("email_cache").is_some());
+        assert!(manager.database("credentials").is_some());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn auto_lock_locks_every_database_after_the_idle_period() {
+        let dir = temp_dir("auto-lock");
+        let mut manager = StorageManager::open(&dir, Some("hunter2")).unwrap();
+        manager.set_auto_lock_after(Duration::from_secs(0));
+
+        let locked = manager.maybe_auto_lock(Instant::now());
+
+        assert!(locked);
+        assert!(manager.is_locked());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn export_then_import_restores_records_into_a_fresh_profile() {
+        let source_dir = temp_dir("export-source");
+        let dest_dir = temp_dir("export-dest");
+        let archive_path = temp_dir("archive").join("backup.json");
+
+        let mut source = StorageManager::open(&source_dir, Some("hunter2")).unwrap();
+        source
+            .database_mut("storage")
+            .unwrap()
+            .set("bookmark:1", serde_json::json!({"url": "https://example.com"}))
+            .unwrap();
+        source.export_all(&archive_path).unwrap();
+
+        let mut dest = StorageManager::open(&dest_dir, Some("different")).unwrap();
+        dest.import_all(&archive_path).unwrap();
+
+        assert_eq!(
+            dest.database("storage").unwrap().get("bookmark:1").unwrap().unwrap()["url"],
+            "https://example.com"
+        );
+
+        let _ = fs::remove_dir_all(&source_dir);
+        let _ = fs::remove_dir_all(&dest_dir);
+        let _ = fs::remove_file(&archive_path);
+    }
+
+    #[test]
+    fn changing_the_password_allows_unlocking_with_the_new_one() {
+        let dir = temp_dir("change-password");
+        let mut manager = StorageManager::open(&dir, Some("old")).unwrap();
+        manager
+            .database_mut("storage")
+            .unwrap()
+            .set("k", serde_json::json!("v"))
+            .unwrap();
+        manager.change_password(Some("new")).unwrap();
+
+        manager.maybe_auto_lock(Instant::now() + Duration::from_secs(3600));
+        assert!(manager.unlock(Some("old")).is_err());
+        manager.unlock(Some("new")).unwrap();
+        assert_eq!(
+            manager.database("storage").unwrap().get("k").unwrap().unwrap(),
+            "v"
+        );
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn a_corrupt_database_with_no_backup_is_recreated_empty() {
+        let dir = temp_dir("recover-no-backup");
+        StorageManager::open(&dir, Some("hunter2")).unwrap();
+        fs::write(dir.join("credentials.db"), b"not json at all").unwrap();
+
+        let (manager, report) = StorageManager::open_with_recovery(&dir, Some("hunter2"), None).unwrap();
+
+        assert!(!report.is_clean());
+        assert!(matches!(&report.issues[0], IntegrityIssue::CorruptDatabase { name, .. } if name == "credentials"));
+        assert!(matches!(&report.recovered[0], RecoveryAction::RecreatedEmpty { name } if name == "credentials"));
+        assert!(!manager.is_locked());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn a_corrupt_database_is_restored_from_a_backup_archive() {
+        let dir = temp_dir("recover-with-backup");
+        let archive_path = temp_dir("recover-archive").join("backup.json");
+
+        let mut manager = StorageManager::open(&dir, Some("hunter2")).unwrap();
+        manager
+            .database_mut("credentials")
+            .unwrap()
+            .set("login:example.com", serde_json::json!("secret"))
+            .unwrap();
+        manager.export_all(&archive_path).unwrap();
+        fs::write(dir.join("credentials.db"), b"not json at all").unwrap();
+
+        let (manager, report) = StorageManager::open_with_recovery(&dir, Some("hunter2"), Some(&archive_path)).unwrap();
+
+        assert!(matches!(&report.recovered[0], RecoveryAction::RestoredFromBackup { name } if name == "credentials"));
+        assert_eq!(
+            manager.database("credentials").unwrap().get("login:example.com").unwrap().unwrap(),
+            "secret"
+        );
+
+        let _ = fs::remove_dir_all(&dir);
+        let _ = fs::remove_file(&archive_path);
+    }
+
+    #[test]
+    fn an_orphaned_file_in_the_profile_directory_is_removed() {
+        let dir = temp_dir("recover-orphan");
+        StorageManager::open(&dir, Some("hunter2")).unwrap();
+        let orphan = dir.join("leftover.db");
+        fs::write(&orphan, b"stale").unwrap();
+
+        let (_, report) = StorageManager::open_with_recovery(&dir, Some("hunter2"), None).unwrap();
+
+        assert!(matches!(&report.issues[0], IntegrityIssue::OrphanedFile { path } if path == &orphan));
+        assert!(matches!(&report.recovered[0], RecoveryAction::RemovedOrphan { path } if path == &orphan));
+        assert!(!orphan.exists());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn a_clean_profile_reports_no_issues() {
+        let dir = temp_dir("recover-clean");
+        StorageManager::open(&dir, Some("hunter2")).unwrap();
+
+        let (_, report) = StorageManager::open_with_recovery(&dir, Some("hunter2"), None).unwrap();
+
+        assert!(report.is_clean());
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,39 @@
+use argon2::Argon2;
+use crynn_error::StorageError;
+
+pub const SALT_LEN: usize = 16;
+pub const KEY_LEN: usize = 32;
+
+/// Derives the AES-256-GCM key that encrypts every local database from the
+/// master password, using the database's own salt so the same password
+/// produces a different key per profile.
+pub fn derive_key(password: &str, salt: &[u8; SALT_LEN]) -> Result<[u8; KEY_LEN], StorageError> {
+    let mut key = [0u8; KEY_LEN];
+    Argon2::default()
+        .hash_password_into(password.as_bytes(), salt, &mut key)
+        .map_err(|e| StorageError::Corrupt {
+            name: "master-password".to_string(),
+            detail: e.to_string(),
+        })?;
+    Ok(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_password_and_salt_derive_the_same_key() {
+        let salt = [7u8; SALT_LEN];
+        let a = derive_key("correct horse battery staple", &salt).unwrap();
+        let b = derive_key("correct horse battery staple", &salt).unwrap();
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn different_salts_derive_different_keys() {
+        let a = derive_key("same password", &[1u8; SALT_LEN]).unwrap();
+        let b = derive_key("same password", &[2u8; SALT_LEN]).unwrap();
+        assert_ne!(a, b);
+    }
+}
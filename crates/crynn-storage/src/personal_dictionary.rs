@@ -0,0 +1,63 @@
+use crynn_error::StorageError;
+
+use crate::manager::StorageManager;
+
+const PERSONAL_WORD_PREFIX: &str = "personal_word:";
+
+/// Adds `word` to the persisted personal dictionary. Idempotent — adding
+/// an already-present word just overwrites its own record.
+pub fn add_word(storage: &mut StorageManager, word: &str) -> Result<(), StorageError> {
+    let db = storage.database_mut("storage").expect("storage database always present");
+    db.set(format!("{PERSONAL_WORD_PREFIX}{}", word.to_lowercase()), serde_json::Value::Bool(true))?;
+    db.save()
+}
+
+/// Removes `word` from the persisted personal dictionary, if present.
+pub fn remove_word(storage: &mut StorageManager, word: &str) -> Result<(), StorageError> {
+    let db = storage.database_mut("storage").expect("storage database always present");
+    db.remove(&format!("{PERSONAL_WORD_PREFIX}{}", word.to_lowercase()))?;
+    db.save()
+}
+
+/// Every word the user has added to their personal dictionary, for
+/// seeding a fresh `crynn_spellcheck::SpellChecker` at startup via
+/// [`crynn_spellcheck::SpellChecker::load_personal_words`].
+pub fn words(storage: &StorageManager) -> Result<Vec<String>, StorageError> {
+    let db = storage.database("storage").expect("storage database always present");
+    Ok(db.iter()?.filter(|(key, _)| key.starts_with(PERSONAL_WORD_PREFIX)).map(|(key, _)| key[PERSONAL_WORD_PREFIX.len()..].to_string()).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::path::PathBuf;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("crynn-storage-personal-dictionary-test-{}-{name}", std::process::id()));
+        let _ = fs::create_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn added_words_round_trip() {
+        let dir = temp_dir("round-trip");
+        let mut storage = StorageManager::open(&dir, None).unwrap();
+        add_word(&mut storage, "Crynn").unwrap();
+        add_word(&mut storage, "hunspell").unwrap();
+        let mut stored = words(&storage).unwrap();
+        stored.sort();
+        assert_eq!(stored, vec!["crynn".to_string(), "hunspell".to_string()]);
+        fs::remove_dir_all(dir).ok();
+    }
+
+    #[test]
+    fn removed_words_no_longer_appear() {
+        let dir = temp_dir("remove");
+        let mut storage = StorageManager::open(&dir, None).unwrap();
+        add_word(&mut storage, "crynn").unwrap();
+        remove_word(&mut storage, "crynn").unwrap();
+        assert!(words(&storage).unwrap().is_empty());
+        fs::remove_dir_all(dir).ok();
+    }
+}
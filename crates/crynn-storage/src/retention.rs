@@ -0,0 +1,175 @@
+use crynn_error::StorageError;
+
+use crate::history::{delete_visits_by_id, visits, Visit};
+use crate::manager::StorageManager;
+
+/// A single configurable cleanup rule. Evaluating one against the
+/// current history doesn't delete anything on its own — see [`plan`] for
+/// the dry-run preview and [`apply_retention`] for actually acting on
+/// it, the same split [`crate::delete_visits_for_domain`] skips because
+/// it always has a specific domain the user asked to forget rather than
+/// a standing rule the scheduler re-evaluates on its own.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RetentionRule {
+    /// Remove history visits older than this many days.
+    HistoryOlderThanDays(u32),
+    /// Cap history's total estimated size at this many bytes, removing
+    /// the oldest visits first until it fits. No real on-disk HTTP
+    /// cache exists in this build to cap directly — see this crate's
+    /// doc comment — so this caps the same history records
+    /// [`RetentionRule::HistoryOlderThanDays`] does, by size instead of
+    /// age.
+    HistoryCapBytes(u64),
+}
+
+/// What evaluating a [`RetentionRule`] against the current history would
+/// remove, without removing it — the maintenance scheduler's dry-run
+/// preview.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RetentionPlan {
+    pub visits_to_delete: Vec<Visit>,
+}
+
+impl RetentionPlan {
+    pub fn is_empty(&self) -> bool {
+        self.visits_to_delete.is_empty()
+    }
+}
+
+/// Estimated on-disk size of one visit record: its serialized JSON size,
+/// the same kind of stand-in [`crynn_engine::ComponentMetrics`]'s tab
+/// memory estimate uses rather than reading a real allocator.
+fn estimated_size_bytes(visit: &Visit) -> u64 {
+    serde_json::to_vec(visit).map(|bytes| bytes.len() as u64).unwrap_or(0)
+}
+
+/// Evaluates `rule` against `storage`'s current history without
+/// deleting anything, for the maintenance scheduler's dry-run preview.
+pub fn plan(storage: &StorageManager, rule: RetentionRule, now: u64) -> Result<RetentionPlan, StorageError> {
+    let mut all = visits(storage)?;
+    match rule {
+        RetentionRule::HistoryOlderThanDays(days) => {
+            let cutoff = now.saturating_sub(days as u64 * 86_400);
+            all.retain(|visit| visit.at < cutoff);
+            Ok(RetentionPlan { visits_to_delete: all })
+        }
+        RetentionRule::HistoryCapBytes(max_bytes) => {
+            all.sort_by_key(|visit| visit.at);
+            let mut total: u64 = all.iter().map(estimated_size_bytes).sum();
+            let mut to_delete = Vec::new();
+            for visit in all {
+                if total <= max_bytes {
+                    break;
+                }
+                total -= estimated_size_bytes(&visit);
+                to_delete.push(visit);
+            }
+            Ok(RetentionPlan { visits_to_delete: to_delete })
+        }
+    }
+}
+
+/// Applies `rule`, deleting every visit [`plan`] would have reported and
+/// returning that same [`RetentionPlan`] as a record of what was
+/// removed.
+pub fn apply_retention(storage: &mut StorageManager, rule: RetentionRule, now: u64) -> Result<RetentionPlan, StorageError> {
+    let result = plan(storage, rule, now)?;
+    let ids: Vec<String> = result.visits_to_delete.iter().map(|visit| visit.id.clone()).collect();
+    delete_visits_by_id(storage, &ids)?;
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::history::{record_visit, VisitType};
+    use std::fs;
+    use std::path::PathBuf;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("crynn-storage-retention-test-{}-{name}", std::process::id()));
+        let _ = fs::create_dir_all(&dir);
+        dir
+    }
+
+    fn visit(id: &str, url: &str, at: u64) -> Visit {
+        Visit { id: id.to_string(), url: url.to_string(), title: "Title".to_string(), visit_type: VisitType::Typed, at, from_visit: None }
+    }
+
+    const DAY: u64 = 86_400;
+
+    #[test]
+    fn plan_for_history_older_than_days_reports_only_stale_visits() {
+        let dir = temp_dir("plan-older-than");
+        let mut storage = StorageManager::open(&dir, None).unwrap();
+        record_visit(&mut storage, &visit("1", "https://example.com/old", 0)).unwrap();
+        record_visit(&mut storage, &visit("2", "https://example.com/new", 100 * DAY)).unwrap();
+
+        let result = plan(&storage, RetentionRule::HistoryOlderThanDays(90), 100 * DAY).unwrap();
+
+        assert_eq!(result.visits_to_delete.len(), 1);
+        assert_eq!(result.visits_to_delete[0].id, "1");
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn plan_does_not_delete_anything() {
+        let dir = temp_dir("plan-is-dry-run");
+        let mut storage = StorageManager::open(&dir, None).unwrap();
+        record_visit(&mut storage, &visit("1", "https://example.com/old", 0)).unwrap();
+
+        plan(&storage, RetentionRule::HistoryOlderThanDays(0), 10 * DAY).unwrap();
+
+        assert_eq!(visits(&storage).unwrap().len(), 1);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn apply_retention_removes_exactly_what_plan_reported() {
+        let dir = temp_dir("apply-older-than");
+        let mut storage = StorageManager::open(&dir, None).unwrap();
+        record_visit(&mut storage, &visit("1", "https://example.com/old", 0)).unwrap();
+        record_visit(&mut storage, &visit("2", "https://example.com/new", 100 * DAY)).unwrap();
+
+        let removed = apply_retention(&mut storage, RetentionRule::HistoryOlderThanDays(90), 100 * DAY).unwrap();
+
+        assert_eq!(removed.visits_to_delete.len(), 1);
+        let remaining = visits(&storage).unwrap();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].id, "2");
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn cap_bytes_removes_the_oldest_visits_first_until_it_fits() {
+        let dir = temp_dir("cap-bytes");
+        let mut storage = StorageManager::open(&dir, None).unwrap();
+        record_visit(&mut storage, &visit("1", "https://example.com/a", 0)).unwrap();
+        record_visit(&mut storage, &visit("2", "https://example.com/b", 1)).unwrap();
+        record_visit(&mut storage, &visit("3", "https://example.com/c", 2)).unwrap();
+
+        let one_visit_size = estimated_size_bytes(&visit("1", "https://example.com/a", 0));
+        let result = plan(&storage, RetentionRule::HistoryCapBytes(one_visit_size), 2).unwrap();
+
+        let deleted_ids: Vec<&str> = result.visits_to_delete.iter().map(|v| v.id.as_str()).collect();
+        assert_eq!(deleted_ids, vec!["1", "2"]);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn cap_bytes_under_the_limit_removes_nothing() {
+        let dir = temp_dir("cap-bytes-under-limit");
+        let mut storage = StorageManager::open(&dir, None).unwrap();
+        record_visit(&mut storage, &visit("1", "https://example.com/a", 0)).unwrap();
+
+        let result = plan(&storage, RetentionRule::HistoryCapBytes(u64::MAX), 0).unwrap();
+
+        assert!(result.is_empty());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
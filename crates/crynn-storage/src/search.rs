@@ -0,0 +1,142 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crynn_error::StorageError;
+
+use crate::manager::StorageManager;
+
+/// Which kind of locally stored data a [`SearchResult`] came from. Only
+/// [`SourceKind::Bookmark`] and [`SourceKind::History`] have a backing
+/// store [`search_local_data`] can actually query today —
+/// [`SourceKind::ReadingList`], [`SourceKind::CachedPageTitle`], and
+/// [`SourceKind::EmailSubject`] are enumerated so a unified index has
+/// somewhere to put results from those sources once they exist, the same
+/// gap this crate leaves a reading-list feature and `crynn-email`'s
+/// `email_cache` database today.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SourceKind {
+    Bookmark,
+    History,
+    ReadingList,
+    CachedPageTitle,
+    EmailSubject,
+}
+
+/// One ranked match across every data type [`search_local_data`] covers.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchResult {
+    pub source: SourceKind,
+    pub title: String,
+    pub url: String,
+    pub score: f64,
+}
+
+/// Flat score every matching [`crate::Bookmark`] gets — there's no
+/// frecency to weight one bookmark above another the way
+/// [`crate::frecency`] does for history.
+pub const BOOKMARK_SCORE: f64 = 1.0;
+
+/// Ranks [`crate::Bookmark`]s and [`crate::Visit`]s whose title or URL
+/// contains `query`, case-insensitively, into one list spanning both
+/// sources: a bookmark always scores [`BOOKMARK_SCORE`], and a history
+/// entry scores by the summed [`crate::frecency`] of every visit to that
+/// URL, the same ranking [`crate::history`]'s own address-bar
+/// suggestions already use — so a page visited often and recently ranks
+/// above one whose title just happens to match more closely. `now` is
+/// the caller's clock reading, the same way [`crate::frecency`] takes
+/// it rather than reaching for one itself.
+pub fn search_local_data(storage: &StorageManager, query: &str, now: u64) -> Result<Vec<SearchResult>, StorageError> {
+    let needle = query.trim().to_lowercase();
+    if needle.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut results = Vec::new();
+
+    for bookmark in crate::bookmarks(storage)? {
+        if bookmark.title.to_lowercase().contains(&needle) || bookmark.url.to_lowercase().contains(&needle) {
+            results.push(SearchResult { source: SourceKind::Bookmark, title: bookmark.title, url: bookmark.url, score: BOOKMARK_SCORE });
+        }
+    }
+
+    let mut history_scores: HashMap<String, (String, f64)> = HashMap::new();
+    for visit in crate::visits(storage)? {
+        if visit.title.to_lowercase().contains(&needle) || visit.url.to_lowercase().contains(&needle) {
+            let entry = history_scores.entry(visit.url.clone()).or_insert_with(|| (visit.title.clone(), 0.0));
+            entry.1 += crate::frecency(&visit, now);
+        }
+    }
+    results.extend(history_scores.into_iter().map(|(url, (title, score))| SearchResult { source: SourceKind::History, title, url, score }));
+
+    results.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{record_visit, save_bookmark, Bookmark, Visit, VisitType};
+    use std::fs;
+    use std::path::PathBuf;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("crynn-storage-search-test-{}-{name}", std::process::id()));
+        let _ = fs::create_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn an_empty_query_matches_nothing() {
+        let dir = temp_dir("empty-query");
+        let storage = StorageManager::open(&dir, None).unwrap();
+        assert!(search_local_data(&storage, "", 0).unwrap().is_empty());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn a_bookmark_matching_by_title_is_returned() {
+        let dir = temp_dir("bookmark-title");
+        let mut storage = StorageManager::open(&dir, None).unwrap();
+        save_bookmark(&mut storage, &Bookmark { id: "1".to_string(), url: "https://tokio.rs".to_string(), title: "Tokio backpressure guide".to_string(), created_at: 0, keyword: None }).unwrap();
+
+        let results = search_local_data(&storage, "backpressure", 0).unwrap();
+
+        assert_eq!(results, vec![SearchResult { source: SourceKind::Bookmark, title: "Tokio backpressure guide".to_string(), url: "https://tokio.rs".to_string(), score: BOOKMARK_SCORE }]);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn history_matches_are_ranked_by_summed_frecency() {
+        let dir = temp_dir("history-frecency");
+        let mut storage = StorageManager::open(&dir, None).unwrap();
+        record_visit(&mut storage, &Visit { id: "1".to_string(), url: "https://a.example.com".to_string(), title: "tokio backpressure article".to_string(), visit_type: VisitType::Typed, at: 100, from_visit: None }).unwrap();
+        record_visit(&mut storage, &Visit { id: "2".to_string(), url: "https://b.example.com".to_string(), title: "tokio backpressure article, older".to_string(), visit_type: VisitType::Link, at: 0, from_visit: None }).unwrap();
+
+        let results = search_local_data(&storage, "backpressure", 100).unwrap();
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].url, "https://a.example.com");
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn a_query_with_no_matches_returns_an_empty_list() {
+        let dir = temp_dir("no-matches");
+        let storage = StorageManager::open(&dir, None).unwrap();
+        assert!(search_local_data(&storage, "nonexistent", 0).unwrap().is_empty());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn matching_by_url_works_as_well_as_by_title() {
+        let dir = temp_dir("match-by-url");
+        let mut storage = StorageManager::open(&dir, None).unwrap();
+        save_bookmark(&mut storage, &Bookmark { id: "1".to_string(), url: "https://docs.rs/tokio".to_string(), title: "Docs".to_string(), created_at: 0, keyword: None }).unwrap();
+
+        let results = search_local_data(&storage, "tokio", 0).unwrap();
+
+        assert_eq!(results.len(), 1);
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
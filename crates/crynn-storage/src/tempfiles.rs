@@ -0,0 +1,220 @@
+use std::fs::{self, File, OpenOptions};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use crynn_error::StorageError;
+
+/// Extension a download carries while it's still in flight.
+/// [`finalize_download`] strips it once the transfer completes.
+pub const PART_EXTENSION: &str = "part";
+
+/// A `.part` file older than this with no activity is assumed abandoned
+/// (the browser crashed, or the user gave up) rather than still in
+/// progress, and [`garbage_collect_downloads`] removes it on the next
+/// startup instead of leaving it around indefinitely.
+pub const STALE_DOWNLOAD_AFTER: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+/// A `.part` file found on disk at startup, for the downloads view to
+/// offer resuming from `bytes_written` rather than restarting the
+/// transfer from scratch.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PartialDownload {
+    pub path: PathBuf,
+    pub bytes_written: u64,
+    pub modified: SystemTime,
+}
+
+fn part_path(dir: &Path, file_name: &str) -> PathBuf {
+    dir.join(format!("{file_name}.{PART_EXTENSION}"))
+}
+
+#[cfg(unix)]
+fn restrict_to_owner(path: &Path, mode: u32) -> Result<(), StorageError> {
+    use std::os::unix::fs::PermissionsExt;
+    fs::set_permissions(path, fs::Permissions::from_mode(mode))?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn restrict_to_owner(_path: &Path, _mode: u32) -> Result<(), StorageError> {
+    Ok(())
+}
+
+/// Opens (creating if necessary) `dir`/`file_name.part` for a download in
+/// progress, appending whatever bytes a real transport streams in next,
+/// with permissions restricted to the owner on platforms that support it
+/// — the same privacy guarantee file bytes at rest get everywhere else in
+/// this crate, just for a plaintext file on disk instead of an encrypted
+/// database record.
+pub fn open_download_part(dir: impl AsRef<Path>, file_name: &str) -> Result<File, StorageError> {
+    fs::create_dir_all(&dir)?;
+    let path = part_path(dir.as_ref(), file_name);
+    let file = OpenOptions::new().create(true).append(true).open(&path)?;
+    restrict_to_owner(&path, 0o600)?;
+    Ok(file)
+}
+
+/// Renames `dir`/`file_name.part` to `dir`/`file_name` once a download
+/// completes, so a half-written file never appears under its final name.
+pub fn finalize_download(dir: impl AsRef<Path>, file_name: &str) -> Result<PathBuf, StorageError> {
+    let part = part_path(dir.as_ref(), file_name);
+    let finished = dir.as_ref().join(file_name);
+    fs::rename(&part, &finished)?;
+    Ok(finished)
+}
+
+/// Every `.part` file under `dir` left over from a previous run.
+pub fn resumable_downloads(dir: impl AsRef<Path>) -> Result<Vec<PartialDownload>, StorageError> {
+    let dir = dir.as_ref();
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+    let mut downloads = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.extension().and_then(|ext| ext.to_str()) != Some(PART_EXTENSION) {
+            continue;
+        }
+        let metadata = fs::metadata(&path)?;
+        downloads.push(PartialDownload { bytes_written: metadata.len(), modified: metadata.modified()?, path });
+    }
+    Ok(downloads)
+}
+
+/// Deletes every `.part` file under `dir` whose last write is older than
+/// `now` minus [`STALE_DOWNLOAD_AFTER`], rather than letting
+/// [`resumable_downloads`] offer to resume a transfer nobody's coming
+/// back for. Returns how many it removed.
+pub fn garbage_collect_downloads(dir: impl AsRef<Path>, now: SystemTime) -> Result<usize, StorageError> {
+    let mut removed = 0;
+    for partial in resumable_downloads(dir)? {
+        if now.duration_since(partial.modified).unwrap_or_default() > STALE_DOWNLOAD_AFTER {
+            fs::remove_file(&partial.path)?;
+            removed += 1;
+        }
+    }
+    Ok(removed)
+}
+
+/// A fresh, owner-only temp directory under `base` for one email
+/// attachment to open into — isolated per attachment so one malicious
+/// attachment can't overwrite or read another's extracted bytes while
+/// both are open. [`cleanup_attachment_dir`] removes it once the viewer
+/// closes.
+pub fn open_attachment_dir(base: impl AsRef<Path>, attachment_id: &str) -> Result<PathBuf, StorageError> {
+    let dir = base.as_ref().join(attachment_id);
+    fs::create_dir_all(&dir)?;
+    restrict_to_owner(&dir, 0o700)?;
+    Ok(dir)
+}
+
+/// Removes an attachment's temp directory and everything extracted into
+/// it. Called when its viewer closes, and again for every attachment
+/// directory still present at exit, so decrypted attachment bytes never
+/// outlive the session that opened them.
+pub fn cleanup_attachment_dir(base: impl AsRef<Path>, attachment_id: &str) -> Result<(), StorageError> {
+    let dir = base.as_ref().join(attachment_id);
+    if dir.exists() {
+        fs::remove_dir_all(&dir)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("crynn-tempfiles-test-{name}-{:?}", std::thread::current().id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn open_download_part_creates_a_part_file_under_the_given_name() {
+        let dir = temp_dir("open-part");
+        let mut file = open_download_part(&dir, "movie.mp4").unwrap();
+        file.write_all(b"partial bytes").unwrap();
+        assert!(dir.join("movie.mp4.part").exists());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn finalize_download_renames_the_part_file_to_its_final_name() {
+        let dir = temp_dir("finalize");
+        open_download_part(&dir, "movie.mp4").unwrap();
+        let finished = finalize_download(&dir, "movie.mp4").unwrap();
+        assert!(finished.exists());
+        assert!(!dir.join("movie.mp4.part").exists());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn resumable_downloads_finds_part_files_but_not_finished_ones() {
+        let dir = temp_dir("resumable");
+        open_download_part(&dir, "a.zip").unwrap();
+        open_download_part(&dir, "b.zip").unwrap();
+        finalize_download(&dir, "b.zip").unwrap();
+        let partials = resumable_downloads(&dir).unwrap();
+        assert_eq!(partials.len(), 1);
+        assert!(partials[0].path.ends_with("a.zip.part"));
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn resumable_downloads_on_a_directory_that_does_not_exist_yet_is_empty() {
+        let dir = temp_dir("missing");
+        assert_eq!(resumable_downloads(&dir).unwrap(), Vec::new());
+    }
+
+    #[test]
+    fn garbage_collect_downloads_removes_only_stale_part_files() {
+        let dir = temp_dir("gc");
+        open_download_part(&dir, "fresh.zip").unwrap();
+        open_download_part(&dir, "stale.zip").unwrap();
+        let now = SystemTime::now();
+        let far_future = now + STALE_DOWNLOAD_AFTER + Duration::from_secs(1);
+        let removed = garbage_collect_downloads(&dir, far_future).unwrap();
+        assert_eq!(removed, 2);
+        assert_eq!(resumable_downloads(&dir).unwrap(), Vec::new());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn garbage_collect_downloads_leaves_downloads_within_the_staleness_window() {
+        let dir = temp_dir("gc-fresh");
+        open_download_part(&dir, "fresh.zip").unwrap();
+        let removed = garbage_collect_downloads(&dir, SystemTime::now()).unwrap();
+        assert_eq!(removed, 0);
+        assert_eq!(resumable_downloads(&dir).unwrap().len(), 1);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn open_attachment_dir_creates_an_isolated_directory_per_attachment() {
+        let base = temp_dir("attachments");
+        let one = open_attachment_dir(&base, "msg-1-att-0").unwrap();
+        let two = open_attachment_dir(&base, "msg-1-att-1").unwrap();
+        assert!(one.exists());
+        assert!(two.exists());
+        assert_ne!(one, two);
+        let _ = fs::remove_dir_all(&base);
+    }
+
+    #[test]
+    fn cleanup_attachment_dir_removes_the_directory_and_its_contents() {
+        let base = temp_dir("attachments-cleanup");
+        let dir = open_attachment_dir(&base, "msg-1-att-0").unwrap();
+        fs::write(dir.join("invoice.pdf"), b"pdf bytes").unwrap();
+        cleanup_attachment_dir(&base, "msg-1-att-0").unwrap();
+        assert!(!dir.exists());
+        let _ = fs::remove_dir_all(&base);
+    }
+
+    #[test]
+    fn cleanup_attachment_dir_on_an_already_removed_attachment_is_not_an_error() {
+        let base = temp_dir("attachments-noop");
+        cleanup_attachment_dir(&base, "never-opened").unwrap();
+    }
+}
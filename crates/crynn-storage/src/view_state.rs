@@ -0,0 +1,149 @@
+use serde::{Deserialize, Serialize};
+
+use crynn_error::StorageError;
+
+use crate::manager::StorageManager;
+
+const VIEW_STATE_PREFIX: &str = "view_state:";
+
+/// Past this many distinct origins, [`save_view_state`] evicts the
+/// least-recently-used one to make room for a new one, the same
+/// LRU-over-wipe-the-whole-cache approach [`crynn_network::DnsResolver`]
+/// takes for resolved hosts.
+pub const DEFAULT_CAPACITY: usize = 500;
+
+/// A tab's zoom level, scroll position, and text size for one origin —
+/// everything [`crate::view_state_for`] needs to hand back to the
+/// engine on navigation or session restore so a site reopens the way
+/// the user left it, rather than at the top of the page at 100% zoom.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ViewState {
+    pub zoom: f32,
+    pub scroll_y: f32,
+    pub text_size: f32,
+    /// When this entry was last written, for [`save_view_state`]'s LRU
+    /// eviction. Not meant to be read back by callers — [`ViewState`]'s
+    /// fields above are the only ones the engine cares about.
+    last_used_at: u64,
+}
+
+impl ViewState {
+    /// Builds a fresh `ViewState` for [`save_view_state`] to stamp with
+    /// its own `last_used_at`; `last_used_at` isn't a caller-supplied
+    /// field, the same way nothing outside this module sets a
+    /// `crynn_network::DnsResolver` cache entry's expiry directly.
+    pub fn new(zoom: f32, scroll_y: f32, text_size: f32) -> Self {
+        Self { zoom, scroll_y, text_size, last_used_at: 0 }
+    }
+}
+
+/// Records `state` for `origin`, overwriting whatever was saved for it
+/// before, then evicts the least-recently-used origin(s) until at most
+/// `capacity` remain.
+pub fn save_view_state(storage: &mut StorageManager, origin: &str, mut state: ViewState, now: u64, capacity: usize) -> Result<(), StorageError> {
+    state.last_used_at = now;
+    let db = storage.database_mut("storage").expect("storage database always present");
+    db.set(format!("{VIEW_STATE_PREFIX}{origin}"), serde_json::to_value(state)?)?;
+    db.save()?;
+    evict_least_recently_used(storage, capacity)
+}
+
+/// The view state saved for `origin`, if any — an origin that's never
+/// been saved (or was evicted) gets `None`, same as a fresh profile's
+/// zoom store reporting the default for an unseen site.
+pub fn view_state_for(storage: &StorageManager, origin: &str) -> Result<Option<ViewState>, StorageError> {
+    let db = storage.database("storage").expect("storage database always present");
+    match db.get(&format!("{VIEW_STATE_PREFIX}{origin}"))? {
+        Some(value) => Ok(Some(serde_json::from_value(value.clone())?)),
+        None => Ok(None),
+    }
+}
+
+fn all_view_states(storage: &StorageManager) -> Result<Vec<(String, ViewState)>, StorageError> {
+    let db = storage.database("storage").expect("storage database always present");
+    db.iter()?
+        .filter_map(|(key, value)| key.strip_prefix(VIEW_STATE_PREFIX).map(|origin| (origin.to_string(), value.clone())))
+        .map(|(origin, value)| serde_json::from_value(value).map(|state| (origin, state)).map_err(StorageError::from))
+        .collect()
+}
+
+fn evict_least_recently_used(storage: &mut StorageManager, capacity: usize) -> Result<(), StorageError> {
+    let mut entries = all_view_states(storage)?;
+    if entries.len() <= capacity {
+        return Ok(());
+    }
+    entries.sort_by_key(|(_, state)| state.last_used_at);
+    let db = storage.database_mut("storage").expect("storage database always present");
+    for (origin, _) in entries.iter().take(entries.len() - capacity) {
+        db.remove(&format!("{VIEW_STATE_PREFIX}{origin}"))?;
+    }
+    db.save()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::path::PathBuf;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("crynn-storage-view-state-test-{}-{name}", std::process::id()));
+        let _ = fs::create_dir_all(&dir);
+        dir
+    }
+
+    fn state(zoom: f32, scroll_y: f32) -> ViewState {
+        ViewState::new(zoom, scroll_y, 1.0)
+    }
+
+    #[test]
+    fn saved_view_state_round_trips() {
+        let dir = temp_dir("round-trip");
+        let mut storage = StorageManager::open(&dir, None).unwrap();
+        save_view_state(&mut storage, "https://example.com", state(1.5, 400.0), 1, DEFAULT_CAPACITY).unwrap();
+
+        let saved = view_state_for(&storage, "https://example.com").unwrap().unwrap();
+        assert_eq!(saved.zoom, 1.5);
+        assert_eq!(saved.scroll_y, 400.0);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn an_unsaved_origin_has_no_view_state() {
+        let dir = temp_dir("unsaved");
+        let storage = StorageManager::open(&dir, None).unwrap();
+        assert_eq!(view_state_for(&storage, "https://example.com").unwrap(), None);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn saving_over_capacity_evicts_the_least_recently_used_origin() {
+        let dir = temp_dir("evict-lru");
+        let mut storage = StorageManager::open(&dir, None).unwrap();
+        save_view_state(&mut storage, "https://a.example.com", state(1.0, 0.0), 1, 2).unwrap();
+        save_view_state(&mut storage, "https://b.example.com", state(1.0, 0.0), 2, 2).unwrap();
+        save_view_state(&mut storage, "https://c.example.com", state(1.0, 0.0), 3, 2).unwrap();
+
+        assert_eq!(view_state_for(&storage, "https://a.example.com").unwrap(), None);
+        assert!(view_state_for(&storage, "https://b.example.com").unwrap().is_some());
+        assert!(view_state_for(&storage, "https://c.example.com").unwrap().is_some());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn re_saving_an_origin_refreshes_its_recency() {
+        let dir = temp_dir("refresh-recency");
+        let mut storage = StorageManager::open(&dir, None).unwrap();
+        save_view_state(&mut storage, "https://a.example.com", state(1.0, 0.0), 1, 2).unwrap();
+        save_view_state(&mut storage, "https://b.example.com", state(1.0, 0.0), 2, 2).unwrap();
+        save_view_state(&mut storage, "https://a.example.com", state(1.0, 0.0), 3, 2).unwrap();
+        save_view_state(&mut storage, "https://c.example.com", state(1.0, 0.0), 4, 2).unwrap();
+
+        assert!(view_state_for(&storage, "https://a.example.com").unwrap().is_some());
+        assert_eq!(view_state_for(&storage, "https://b.example.com").unwrap(), None);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,122 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crynn_error::StorageError;
+
+use crate::manager::StorageManager;
+
+const VPN_SESSION_PREFIX: &str = "vpn_session:";
+const SECONDS_PER_DAY: u64 = 86_400;
+
+/// One VPN connection, from connect to disconnect, with how much
+/// traffic it carried. `disconnected_at` is `None` for a session still
+/// in progress when it was persisted.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VpnSession {
+    pub id: String,
+    pub provider: String,
+    pub location: String,
+    pub connected_at: u64,
+    pub disconnected_at: Option<u64>,
+    pub bytes_up: u64,
+    pub bytes_down: u64,
+}
+
+pub fn record_session(storage: &mut StorageManager, session: &VpnSession) -> Result<(), StorageError> {
+    let db = storage.database_mut("storage").expect("storage database always present");
+    db.set(format!("{VPN_SESSION_PREFIX}{}", session.id), serde_json::to_value(session)?)?;
+    db.save()
+}
+
+pub fn vpn_sessions(storage: &StorageManager) -> Result<Vec<VpnSession>, StorageError> {
+    let db = storage.database("storage").expect("storage database always present");
+    db.iter()?
+        .filter(|(key, _)| key.starts_with(VPN_SESSION_PREFIX))
+        .map(|(_, value)| serde_json::from_value(value.clone()).map_err(StorageError::from))
+        .collect()
+}
+
+/// Total bytes transferred per day (Unix time divided by
+/// seconds-per-day) by sessions connected in `since..until`, as
+/// `(bytes_up, bytes_down)`, for the VPN panel's data-usage chart.
+pub fn usage_per_day(storage: &StorageManager, since: u64, until: u64) -> Result<BTreeMap<u64, (u64, u64)>, StorageError> {
+    let mut usage = BTreeMap::new();
+    for session in vpn_sessions(storage)?.into_iter().filter(|s| s.connected_at >= since && s.connected_at < until) {
+        let day = session.connected_at / SECONDS_PER_DAY;
+        let entry = usage.entry(day).or_insert((0, 0));
+        entry.0 += session.bytes_up;
+        entry.1 += session.bytes_down;
+    }
+    Ok(usage)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::path::PathBuf;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("crynn-storage-vpn-sessions-test-{}-{name}", std::process::id()));
+        let _ = fs::create_dir_all(&dir);
+        dir
+    }
+
+    fn session(id: &str, connected_at: u64, bytes_up: u64, bytes_down: u64) -> VpnSession {
+        VpnSession {
+            id: id.to_string(),
+            provider: "Example VPN".to_string(),
+            location: "nl-ams".to_string(),
+            connected_at,
+            disconnected_at: Some(connected_at + 60),
+            bytes_up,
+            bytes_down,
+        }
+    }
+
+    #[test]
+    fn recorded_sessions_round_trip() {
+        let dir = temp_dir("round-trip");
+        let mut storage = StorageManager::open(&dir, None).unwrap();
+        record_session(&mut storage, &session("1", 0, 10, 20)).unwrap();
+
+        let all = vpn_sessions(&storage).unwrap();
+        assert_eq!(all.len(), 1);
+        assert_eq!(all[0].bytes_up, 10);
+        assert_eq!(all[0].bytes_down, 20);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn usage_per_day_sums_bytes_for_sessions_on_the_same_day() {
+        let dir = temp_dir("usage-per-day");
+        let mut storage = StorageManager::open(&dir, None).unwrap();
+        record_session(&mut storage, &session("1", 0, 10, 20)).unwrap();
+        record_session(&mut storage, &session("2", 10, 5, 5)).unwrap();
+        record_session(&mut storage, &session("3", SECONDS_PER_DAY, 100, 200)).unwrap();
+
+        let usage = usage_per_day(&storage, 0, u64::MAX).unwrap();
+
+        assert_eq!(usage.get(&0), Some(&(15, 25)));
+        assert_eq!(usage.get(&1), Some(&(100, 200)));
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn usage_per_day_excludes_sessions_outside_the_range() {
+        let dir = temp_dir("usage-range");
+        let mut storage = StorageManager::open(&dir, None).unwrap();
+        record_session(&mut storage, &session("1", 5, 10, 10)).unwrap();
+        record_session(&mut storage, &session("2", 500, 10, 10)).unwrap();
+
+        let usage = usage_per_day(&storage, 0, 100).unwrap();
+
+        assert_eq!(usage.len(), 1);
+        assert_eq!(usage.get(&0), Some(&(10, 10)));
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,114 @@
+use serde::{Deserialize, Serialize};
+
+use crynn_error::StorageError;
+
+use crate::manager::StorageManager;
+
+const WEBAPP_PREFIX: &str = "webapp:";
+
+/// A site installed as a standalone "web app": its own window pinned to
+/// `start_url`, isolated from the user's regular browsing by
+/// `container_id` — a dedicated container the same way the tab-strip's
+/// own per-container proxy/storage scoping works, just one generated at
+/// install time instead of created by hand.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WebApp {
+    pub id: String,
+    pub name: String,
+    pub start_url: String,
+    pub container_id: String,
+    pub installed_at: u64,
+}
+
+pub fn install_webapp(storage: &mut StorageManager, app: &WebApp) -> Result<(), StorageError> {
+    let db = storage.database_mut("storage").expect("storage database always present");
+    db.set(format!("{WEBAPP_PREFIX}{}", app.id), serde_json::to_value(app)?)?;
+    db.save()
+}
+
+/// Silently removes nothing if `id` has no matching web app, same as
+/// [`crate::delete_bookmark`] on a missing bookmark id.
+pub fn uninstall_webapp(storage: &mut StorageManager, id: &str) -> Result<(), StorageError> {
+    let db = storage.database_mut("storage").expect("storage database always present");
+    db.remove(&format!("{WEBAPP_PREFIX}{id}"))?;
+    db.save()
+}
+
+pub fn webapp_by_id(storage: &StorageManager, id: &str) -> Result<Option<WebApp>, StorageError> {
+    Ok(webapps(storage)?.into_iter().find(|app| app.id == id))
+}
+
+pub fn webapps(storage: &StorageManager) -> Result<Vec<WebApp>, StorageError> {
+    let db = storage.database("storage").expect("storage database always present");
+    db.iter()?
+        .filter(|(key, _)| key.starts_with(WEBAPP_PREFIX))
+        .map(|(_, value)| serde_json::from_value(value.clone()).map_err(StorageError::from))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::path::PathBuf;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("crynn-storage-webapps-test-{}-{name}", std::process::id()));
+        let _ = fs::create_dir_all(&dir);
+        dir
+    }
+
+    fn a_webapp(id: &str) -> WebApp {
+        WebApp {
+            id: id.to_string(),
+            name: "Example".to_string(),
+            start_url: "https://example.com".to_string(),
+            container_id: format!("webapp-container-{id}"),
+            installed_at: 1_000,
+        }
+    }
+
+    #[test]
+    fn installing_then_listing_round_trips() {
+        let dir = temp_dir("install-list");
+        let mut storage = StorageManager::open(&dir, Some("hunter2")).unwrap();
+
+        install_webapp(&mut storage, &a_webapp("1")).unwrap();
+
+        assert_eq!(webapps(&storage).unwrap(), vec![a_webapp("1")]);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn webapp_by_id_finds_only_the_matching_app() {
+        let dir = temp_dir("by-id");
+        let mut storage = StorageManager::open(&dir, Some("hunter2")).unwrap();
+        install_webapp(&mut storage, &a_webapp("1")).unwrap();
+        install_webapp(&mut storage, &a_webapp("2")).unwrap();
+
+        assert_eq!(webapp_by_id(&storage, "2").unwrap(), Some(a_webapp("2")));
+        assert_eq!(webapp_by_id(&storage, "missing").unwrap(), None);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn uninstalling_a_missing_app_is_not_an_error() {
+        let dir = temp_dir("uninstall-missing");
+        let mut storage = StorageManager::open(&dir, Some("hunter2")).unwrap();
+
+        assert!(uninstall_webapp(&mut storage, "missing").is_ok());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn uninstalling_removes_it_from_the_list() {
+        let dir = temp_dir("uninstall");
+        let mut storage = StorageManager::open(&dir, Some("hunter2")).unwrap();
+        install_webapp(&mut storage, &a_webapp("1")).unwrap();
+
+        uninstall_webapp(&mut storage, "1").unwrap();
+
+        assert!(webapps(&storage).unwrap().is_empty());
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
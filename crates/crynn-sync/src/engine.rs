@@ -0,0 +1,197 @@
+use std::collections::HashMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crynn_error::SyncError;
+
+use crate::key::KEY_LEN;
+use crate::record::{Collection, SyncRecord};
+use crate::status::SyncStatus;
+use crate::transport::SyncTransport;
+
+/// Drives sync for one device: encrypts outgoing records, decrypts and
+/// merges incoming ones, and reports [`SyncStatus`] for the shells.
+pub struct SyncEngine<T: SyncTransport> {
+    transport: T,
+    device_id: String,
+    key: [u8; KEY_LEN],
+    status: SyncStatus,
+}
+
+impl<T: SyncTransport> SyncEngine<T> {
+    pub fn new(transport: T, device_id: impl Into<String>, key: [u8; KEY_LEN]) -> Self {
+        Self {
+            transport,
+            device_id: device_id.into(),
+            key,
+            status: SyncStatus::Idle,
+        }
+    }
+
+    pub fn status(&self) -> &SyncStatus {
+        &self.status
+    }
+
+    /// Encrypts `payload` and uploads it as the current version of `id`.
+    pub fn push(
+        &mut self,
+        collection: Collection,
+        id: impl Into<String>,
+        updated_at_unix_ms: u64,
+        payload: &serde_json::Value,
+    ) -> Result<(), SyncError> {
+        let record = SyncRecord::encrypt(collection, id, self.device_id.clone(), updated_at_unix_ms, payload, &self.key)?;
+        self.transport.upload(&record).inspect_err(|e| self.record_error(e))
+    }
+
+    /// Downloads every record in `collection` and merges it into `local`
+    /// (keyed by record id, each paired with its own-device timestamp),
+    /// resolving same-id conflicts with [`resolve_conflict`]. Does not
+    /// write anything back to the transport — callers that want the
+    /// merged result visible to other devices should [`SyncEngine::push`]
+    /// it afterwards.
+    pub fn pull(
+        &mut self,
+        collection: Collection,
+        local: &HashMap<String, (u64, serde_json::Value)>,
+    ) -> Result<HashMap<String, (u64, serde_json::Value)>, SyncError> {
+        self.status = SyncStatus::Syncing;
+
+        let remote = match self.transport.download_all(collection) {
+            Ok(records) => records,
+            Err(e) => {
+                self.record_error(&e);
+                return Err(e);
+            }
+        };
+
+        let mut merged = local.clone();
+        for record in remote {
+            let payload = record.decrypt(&self.key).inspect_err(|e| self.record_error(e))?;
+            match merged.get(&record.id) {
+                Some((local_at, local_payload)) => {
+                    let resolved = resolve_conflict(collection, *local_at, local_payload, record.updated_at_unix_ms, &payload);
+                    merged.insert(record.id, resolved);
+                }
+                None => {
+                    merged.insert(record.id, (record.updated_at_unix_ms, payload));
+                }
+            }
+        }
+
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_millis() as u64;
+        self.status = SyncStatus::Synced { at_unix_ms: now };
+        Ok(merged)
+    }
+
+    fn record_error(&mut self, e: &SyncError) {
+        self.status = SyncStatus::Error { message: e.to_string() };
+    }
+}
+
+/// Resolves a same-id conflict between a local and a remote version of a
+/// record. [`Collection::Settings`] merges field-by-field (the newer side
+/// wins per key, keys unique to either side are kept); every other
+/// collection is last-write-wins by `updated_at_unix_ms`.
+fn resolve_conflict(
+    collection: Collection,
+    local_at: u64,
+    local: &serde_json::Value,
+    remote_at: u64,
+    remote: &serde_json::Value,
+) -> (u64, serde_json::Value) {
+    if collection != Collection::Settings {
+        return if remote_at >= local_at {
+            (remote_at, remote.clone())
+        } else {
+            (local_at, local.clone())
+        };
+    }
+
+    let (older, newer, newer_at) = if local_at <= remote_at {
+        (local, remote, remote_at)
+    } else {
+        (remote, local, local_at)
+    };
+
+    let mut merged = older.clone();
+    match (merged.as_object_mut(), newer.as_object()) {
+        (Some(merged_obj), Some(newer_obj)) => {
+            for (key, value) in newer_obj {
+                merged_obj.insert(key.clone(), value.clone());
+            }
+            (newer_at, merged)
+        }
+        _ => (newer_at, newer.clone()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transport::FileSystemTransport;
+    use std::fs;
+    use std::path::PathBuf;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("crynn-sync-engine-test-{}-{name}", std::process::id()))
+    }
+
+    #[test]
+    fn push_then_pull_on_another_device_sees_the_record() {
+        let dir = temp_dir("push-pull");
+        let key = [5u8; KEY_LEN];
+
+        let mut device_a = SyncEngine::new(FileSystemTransport::new(&dir), "device-a", key);
+        device_a
+            .push(Collection::Bookmarks, "bm-1", 1000, &serde_json::json!({"url": "https://example.com"}))
+            .unwrap();
+
+        let mut device_b = SyncEngine::new(FileSystemTransport::new(&dir), "device-b", key);
+        let merged = device_b.pull(Collection::Bookmarks, &HashMap::new()).unwrap();
+
+        assert_eq!(merged["bm-1"].1["url"], "https://example.com");
+        assert!(matches!(device_b.status(), SyncStatus::Synced { .. }));
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn non_settings_conflicts_resolve_to_the_newer_timestamp() {
+        let mut local = HashMap::new();
+        local.insert("entry-1".to_string(), (1000u64, serde_json::json!({"title": "old"})));
+
+        let dir = temp_dir("last-write-wins");
+        let key = [6u8; KEY_LEN];
+        let transport = FileSystemTransport::new(&dir);
+        let record = SyncRecord::encrypt(Collection::History, "entry-1", "device-a", 2000, &serde_json::json!({"title": "new"}), &key).unwrap();
+        transport.upload(&record).unwrap();
+
+        let mut engine = SyncEngine::new(transport, "device-b", key);
+        let merged = engine.pull(Collection::History, &local).unwrap();
+
+        assert_eq!(merged["entry-1"].1["title"], "new");
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn settings_conflicts_merge_fields_instead_of_replacing_wholesale() {
+        let mut local = HashMap::new();
+        local.insert(
+            "settings".to_string(),
+            (2000u64, serde_json::json!({"theme": "dark", "zoom": 1.0})),
+        );
+
+        let dir = temp_dir("settings-merge");
+        let key = [7u8; KEY_LEN];
+        let transport = FileSystemTransport::new(&dir);
+        let record = SyncRecord::encrypt(Collection::Settings, "settings", "device-a", 1000, &serde_json::json!({"theme": "light"}), &key).unwrap();
+        transport.upload(&record).unwrap();
+
+        let mut engine = SyncEngine::new(transport, "device-b", key);
+        let merged = engine.pull(Collection::Settings, &local).unwrap();
+
+        assert_eq!(merged["settings"].1["theme"], "dark");
+        assert_eq!(merged["settings"].1["zoom"], 1.0);
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,36 @@
+use argon2::Argon2;
+use crynn_error::SyncError;
+
+pub const SALT_LEN: usize = 16;
+pub const KEY_LEN: usize = 32;
+
+/// Derives the AES-256-GCM key every device encrypts and decrypts sync
+/// records with, from the user's sync passphrase and a salt shared between
+/// devices (carried in [`crate::engine::SyncEngine::new`]'s setup, out of
+/// band — e.g. a pairing QR code). Every device that knows the passphrase
+/// and salt derives the same key, so records encrypted on one device are
+/// readable on another without the transport ever seeing plaintext.
+pub fn derive_key(passphrase: &str, salt: &[u8; SALT_LEN]) -> Result<[u8; KEY_LEN], SyncError> {
+    let mut key = [0u8; KEY_LEN];
+    Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|e| SyncError::Corrupt {
+            collection: "sync-key".to_string(),
+            id: "derive".to_string(),
+            detail: e.to_string(),
+        })?;
+    Ok(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_passphrase_and_salt_derive_the_same_key() {
+        let salt = [3u8; SALT_LEN];
+        let a = derive_key("shared secret", &salt).unwrap();
+        let b = derive_key("shared secret", &salt).unwrap();
+        assert_eq!(a, b);
+    }
+}
@@ -0,0 +1,30 @@
+//! End-to-end-encrypted sync of bookmarks, history, open tabs, and
+//! settings across a user's devices. Every device derives the same
+//! AES-256-GCM key from a shared passphrase (see [`key::derive_key`]), so
+//! the transport — a WebDAV share or S3 bucket the user already owns, via
+//! [`transport::FileSystemTransport`] to start — never sees plaintext.
+//!
+//! [`engine::SyncEngine`] drives push/pull and resolves same-id conflicts
+//! by timestamp, merging field-by-field for [`record::Collection::Settings`]
+//! instead of replacing the whole record. [`status::SyncStatus`] is what
+//! the shells show in their sync indicator.
+//!
+//! [`webdav::WebDavTransport`] is the first transport backed by a real
+//! network protocol rather than a local directory: it drives a
+//! [`webdav::WebDavClient`] to push/pull the same [`record::SyncRecord`]s
+//! as [`transport::FileSystemTransport`], using `If-Match` on every
+//! write to detect a conflicting change another device made since this
+//! one last read that resource.
+
+pub mod engine;
+pub mod key;
+pub mod record;
+pub mod status;
+pub mod transport;
+pub mod webdav;
+
+pub use engine::SyncEngine;
+pub use record::{Collection, SyncRecord};
+pub use status::SyncStatus;
+pub use transport::{FileSystemTransport, SyncTransport};
+pub use webdav::{WebDavClient, WebDavTransport};
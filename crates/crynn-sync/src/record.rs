@@ -0,0 +1,194 @@
+use aes_gcm::aead::{Aead, KeyInit, Payload};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use rand::RngExt;
+use serde::{Deserialize, Serialize};
+
+use crynn_error::SyncError;
+
+use crate::key::KEY_LEN;
+
+const NONCE_LEN: usize = 12;
+
+/// The data sets this subsystem keeps in sync. Each has its own conflict
+/// resolution rule in [`crate::engine::SyncEngine`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Collection {
+    Bookmarks,
+    History,
+    OpenTabs,
+    Settings,
+}
+
+impl Collection {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Collection::Bookmarks => "bookmarks",
+            Collection::History => "history",
+            Collection::OpenTabs => "open-tabs",
+            Collection::Settings => "settings",
+        }
+    }
+}
+
+/// One synced item, as seen by the transport: everything but `ciphertext`
+/// is metadata needed to route and order records without decrypting them.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncRecord {
+    pub collection: Collection,
+    pub id: String,
+    pub device_id: String,
+    pub updated_at_unix_ms: u64,
+    nonce: [u8; NONCE_LEN],
+    ciphertext: Vec<u8>,
+}
+
+impl SyncRecord {
+    /// Encrypts `payload` under the shared sync key, stamped with the
+    /// originating device and time for conflict resolution.
+    pub fn encrypt(
+        collection: Collection,
+        id: impl Into<String>,
+        device_id: impl Into<String>,
+        updated_at_unix_ms: u64,
+        payload: &serde_json::Value,
+        key: &[u8; KEY_LEN],
+    ) -> Result<Self, SyncError> {
+        let id = id.into();
+        let device_id = device_id.into();
+        let mut nonce_bytes = [0u8; NONCE_LEN];
+        rand::rng().fill(&mut nonce_bytes);
+
+        let cipher = Aes256Gcm::new(&Key::<Aes256Gcm>::try_from(key.as_slice()).expect("key is 32 bytes"));
+        let plaintext = serde_json::to_vec(payload)?;
+        let aad = associated_data(collection, &id, &device_id, updated_at_unix_ms);
+        let ciphertext = cipher
+            .encrypt(
+                &Nonce::try_from(nonce_bytes.as_slice()).expect("nonce is 12 bytes"),
+                Payload { msg: plaintext.as_ref(), aad: &aad },
+            )
+            .map_err(|e| SyncError::Corrupt {
+                collection: collection.as_str().to_string(),
+                id: id.clone(),
+                detail: e.to_string(),
+            })?;
+
+        Ok(Self {
+            collection,
+            id,
+            device_id,
+            updated_at_unix_ms,
+            nonce: nonce_bytes,
+            ciphertext,
+        })
+    }
+
+    /// Decrypts this record's payload with the shared sync key. Fails with
+    /// [`SyncError::Corrupt`] if the key doesn't match (wrong passphrase),
+    /// the ciphertext was tampered with, or `collection`/`id`/`device_id`/
+    /// `updated_at_unix_ms` were changed since encryption — those ride
+    /// alongside the ciphertext in plain sight on an untrusted transport,
+    /// so they're bound in as AEAD associated data rather than trusted as
+    /// plain metadata; a server relabeling or replaying a record with a
+    /// forged `id` or `updated_at_unix_ms` fails the GCM tag instead of
+    /// silently winning [`crate::engine::SyncEngine`]'s conflict resolution.
+    pub fn decrypt(&self, key: &[u8; KEY_LEN]) -> Result<serde_json::Value, SyncError> {
+        let cipher = Aes256Gcm::new(&Key::<Aes256Gcm>::try_from(key.as_slice()).expect("key is 32 bytes"));
+        let aad = associated_data(self.collection, &self.id, &self.device_id, self.updated_at_unix_ms);
+        let plaintext = cipher
+            .decrypt(
+                &Nonce::try_from(self.nonce.as_slice()).expect("nonce is 12 bytes"),
+                Payload { msg: self.ciphertext.as_ref(), aad: &aad },
+            )
+            .map_err(|_| SyncError::Corrupt {
+                collection: self.collection.as_str().to_string(),
+                id: self.id.clone(),
+                detail: "wrong sync key or corrupted ciphertext".to_string(),
+            })?;
+        Ok(serde_json::from_slice(&plaintext)?)
+    }
+}
+
+/// The bytes bound into the AEAD tag alongside the ciphertext: every
+/// field the transport carries in plaintext next to it, so tampering
+/// with any of them (relabeling `id`/`collection`, forging a newer
+/// `updated_at_unix_ms` to win a conflict) invalidates the tag instead
+/// of passing through unnoticed.
+fn associated_data(collection: Collection, id: &str, device_id: &str, updated_at_unix_ms: u64) -> Vec<u8> {
+    format!("{}:{}:{}:{}", collection.as_str(), id, device_id, updated_at_unix_ms).into_bytes()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encrypt_then_decrypt_round_trips_the_payload() {
+        let key = [9u8; KEY_LEN];
+        let record = SyncRecord::encrypt(
+            Collection::Bookmarks,
+            "bm-1",
+            "device-a",
+            1000,
+            &serde_json::json!({"url": "https://example.com"}),
+            &key,
+        )
+        .unwrap();
+
+        let decrypted = record.decrypt(&key).unwrap();
+        assert_eq!(decrypted["url"], "https://example.com");
+    }
+
+    #[test]
+    fn tampering_with_the_timestamp_after_encryption_fails_decryption() {
+        let key = [9u8; KEY_LEN];
+        let mut record = SyncRecord::encrypt(
+            Collection::Bookmarks,
+            "bm-1",
+            "device-a",
+            1000,
+            &serde_json::json!({"url": "https://example.com"}),
+            &key,
+        )
+        .unwrap();
+
+        record.updated_at_unix_ms = 9_999_999_999;
+
+        let err = record.decrypt(&key).unwrap_err();
+        assert!(matches!(err, SyncError::Corrupt { .. }));
+    }
+
+    #[test]
+    fn relabeling_the_id_after_encryption_fails_decryption() {
+        let key = [9u8; KEY_LEN];
+        let mut record = SyncRecord::encrypt(
+            Collection::Bookmarks,
+            "bm-1",
+            "device-a",
+            1000,
+            &serde_json::json!({"url": "https://example.com"}),
+            &key,
+        )
+        .unwrap();
+
+        record.id = "bm-2".to_string();
+
+        let err = record.decrypt(&key).unwrap_err();
+        assert!(matches!(err, SyncError::Corrupt { .. }));
+    }
+
+    #[test]
+    fn decrypting_with_the_wrong_key_fails() {
+        let record = SyncRecord::encrypt(
+            Collection::Settings,
+            "settings",
+            "device-a",
+            1000,
+            &serde_json::json!({}),
+            &[1u8; KEY_LEN],
+        )
+        .unwrap();
+
+        let err = record.decrypt(&[2u8; KEY_LEN]).unwrap_err();
+        assert!(matches!(err, SyncError::Corrupt { .. }));
+    }
+}
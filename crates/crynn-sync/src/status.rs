@@ -0,0 +1,10 @@
+/// Reported to the shells so they can show a sync indicator in the status
+/// bar or settings page.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum SyncStatus {
+    #[default]
+    Idle,
+    Syncing,
+    Synced { at_unix_ms: u64 },
+    Error { message: String },
+}
@@ -0,0 +1,104 @@
+use std::fs;
+use std::path::PathBuf;
+
+use crynn_error::SyncError;
+
+use crate::record::{Collection, SyncRecord};
+
+/// Where encrypted sync records are stored. Implementations never see
+/// plaintext, so any remote the user already has (their own WebDAV share,
+/// an S3 bucket) is safe to use.
+///
+/// [`FileSystemTransport`] is the first implementation, backing onto a
+/// synced folder; a WebDAV or S3 transport implements the same trait
+/// without touching [`crate::engine::SyncEngine`].
+pub trait SyncTransport {
+    fn upload(&self, record: &SyncRecord) -> Result<(), SyncError>;
+    fn download_all(&self, collection: Collection) -> Result<Vec<SyncRecord>, SyncError>;
+}
+
+/// Stores each record as its own file under `root/<collection>/<id>.json`.
+/// Pointing `root` at a WebDAV- or S3-mounted directory turns this into a
+/// real cross-device transport without any code changes.
+pub struct FileSystemTransport {
+    root: PathBuf,
+}
+
+impl FileSystemTransport {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    fn collection_dir(&self, collection: Collection) -> PathBuf {
+        self.root.join(collection.as_str())
+    }
+}
+
+impl SyncTransport for FileSystemTransport {
+    fn upload(&self, record: &SyncRecord) -> Result<(), SyncError> {
+        let dir = self.collection_dir(record.collection);
+        fs::create_dir_all(&dir)?;
+        let path = dir.join(format!("{}.json", record.id));
+        fs::write(path, serde_json::to_vec(record)?)?;
+        Ok(())
+    }
+
+    fn download_all(&self, collection: Collection) -> Result<Vec<SyncRecord>, SyncError> {
+        let dir = self.collection_dir(collection);
+        if !dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut records = Vec::new();
+        for entry in fs::read_dir(&dir)? {
+            let entry = entry?;
+            if entry.path().extension().and_then(|e| e.to_str()) != Some("json") {
+                continue;
+            }
+            let bytes = fs::read(entry.path())?;
+            records.push(serde_json::from_slice(&bytes)?);
+        }
+        Ok(records)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::key::KEY_LEN;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("crynn-sync-transport-test-{}-{name}", std::process::id()))
+    }
+
+    #[test]
+    fn uploaded_records_are_returned_by_download_all() {
+        let dir = temp_dir("round-trip");
+        let transport = FileSystemTransport::new(&dir);
+
+        let record = SyncRecord::encrypt(
+            Collection::History,
+            "entry-1",
+            "device-a",
+            1000,
+            &serde_json::json!({"url": "https://example.com"}),
+            &[0u8; KEY_LEN],
+        )
+        .unwrap();
+        transport.upload(&record).unwrap();
+
+        let downloaded = transport.download_all(Collection::History).unwrap();
+        assert_eq!(downloaded.len(), 1);
+        assert_eq!(downloaded[0].id, "entry-1");
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn download_all_on_an_unused_collection_is_empty() {
+        let dir = temp_dir("empty");
+        let transport = FileSystemTransport::new(&dir);
+
+        assert!(transport.download_all(Collection::Settings).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,167 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use crynn_error::SyncError;
+
+use crate::record::{Collection, SyncRecord};
+use crate::transport::SyncTransport;
+
+/// One WebDAV operation [`WebDavTransport`] needs. A real implementation
+/// is an HTTP client issuing `PROPFIND`/`GET`/`PUT` once this crate has
+/// one; tests (and the shell, until then) can answer from a fixed table,
+/// the same way `crynn_network::SuggestionsTransport` does.
+pub trait WebDavClient {
+    /// Names of every resource directly under `collection_url` (a
+    /// `PROPFIND` with `Depth: 1`). Empty, not an error, for a
+    /// collection that doesn't exist yet.
+    fn list(&mut self, collection_url: &str) -> Result<Vec<String>, SyncError>;
+
+    /// `url`'s current body and ETag, or `None` if nothing is there.
+    fn get(&mut self, url: &str) -> Result<Option<(String, Vec<u8>)>, SyncError>;
+
+    /// Writes `body` to `url`, conditioned on `if_match`: the ETag the
+    /// caller last saw for this resource, or `None` if it believes
+    /// nothing exists there yet. Returns the new ETag on success.
+    fn put(&mut self, url: &str, body: &[u8], if_match: Option<&str>) -> Result<String, SyncError>;
+}
+
+/// Backs [`crate::engine::SyncEngine`] onto a WebDAV share the user
+/// already owns — the first transport in this crate that talks to a
+/// real network protocol rather than a local directory (see
+/// [`crate::FileSystemTransport`]). Detects a conflicting write the same
+/// way the server does: every `PUT` carries `If-Match` set to the ETag
+/// this transport last saw for that resource, so a write that landed
+/// from another device in between is caught rather than silently
+/// overwritten — [`SyncTransport::upload`] returns
+/// [`SyncError::TransportRejected`] when that happens.
+pub struct WebDavTransport<C: WebDavClient> {
+    base_url: String,
+    client: RefCell<C>,
+    known_etags: RefCell<HashMap<String, String>>,
+}
+
+impl<C: WebDavClient> WebDavTransport<C> {
+    pub fn new(base_url: impl Into<String>, client: C) -> Self {
+        Self {
+            base_url: base_url.into(),
+            client: RefCell::new(client),
+            known_etags: RefCell::new(HashMap::new()),
+        }
+    }
+
+    fn collection_url(&self, collection: Collection) -> String {
+        format!("{}/{}", self.base_url.trim_end_matches('/'), collection.as_str())
+    }
+}
+
+impl<C: WebDavClient> SyncTransport for WebDavTransport<C> {
+    fn upload(&self, record: &SyncRecord) -> Result<(), SyncError> {
+        let url = format!("{}/{}.json", self.collection_url(record.collection), record.id);
+        let body = serde_json::to_vec(record)?;
+        let if_match = self.known_etags.borrow().get(&url).cloned();
+
+        let new_etag = self.client.borrow_mut().put(&url, &body, if_match.as_deref())?;
+        self.known_etags.borrow_mut().insert(url, new_etag);
+        Ok(())
+    }
+
+    fn download_all(&self, collection: Collection) -> Result<Vec<SyncRecord>, SyncError> {
+        let collection_url = self.collection_url(collection);
+        let names = self.client.borrow_mut().list(&collection_url)?;
+
+        let mut records = Vec::new();
+        for name in names {
+            let url = format!("{collection_url}/{name}");
+            let Some((etag, body)) = self.client.borrow_mut().get(&url)? else { continue };
+            self.known_etags.borrow_mut().insert(url, etag);
+            records.push(serde_json::from_slice(&body)?);
+        }
+        Ok(records)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::key::KEY_LEN;
+
+    /// In-memory stand-in for a WebDAV server: a map from URL to
+    /// `(etag, body)`, with [`WebDavClient::put`] enforcing the same
+    /// `If-Match` precondition a real server would.
+    #[derive(Default)]
+    struct FakeServer {
+        resources: HashMap<String, (String, Vec<u8>)>,
+        next_etag: u64,
+    }
+
+    impl WebDavClient for FakeServer {
+        fn list(&mut self, collection_url: &str) -> Result<Vec<String>, SyncError> {
+            let prefix = format!("{collection_url}/");
+            Ok(self
+                .resources
+                .keys()
+                .filter_map(|url| url.strip_prefix(&prefix).map(str::to_string))
+                .collect())
+        }
+
+        fn get(&mut self, url: &str) -> Result<Option<(String, Vec<u8>)>, SyncError> {
+            Ok(self.resources.get(url).cloned())
+        }
+
+        fn put(&mut self, url: &str, body: &[u8], if_match: Option<&str>) -> Result<String, SyncError> {
+            let current_etag = self.resources.get(url).map(|(etag, _)| etag.as_str());
+            if current_etag != if_match {
+                return Err(SyncError::TransportRejected {
+                    transport: "webdav".to_string(),
+                    reason: format!("etag mismatch for {url}: expected {if_match:?}, found {current_etag:?}"),
+                });
+            }
+            self.next_etag += 1;
+            let etag = self.next_etag.to_string();
+            self.resources.insert(url.to_string(), (etag.clone(), body.to_vec()));
+            Ok(etag)
+        }
+    }
+
+    fn record(id: &str) -> SyncRecord {
+        SyncRecord::encrypt(Collection::Bookmarks, id, "device-a", 1000, &serde_json::json!({"url": "https://example.com"}), &[0u8; KEY_LEN]).unwrap()
+    }
+
+    #[test]
+    fn uploaded_records_are_returned_by_download_all() {
+        let transport = WebDavTransport::new("https://dav.example.com/sync", FakeServer::default());
+        transport.upload(&record("bm-1")).unwrap();
+
+        let downloaded = transport.download_all(Collection::Bookmarks).unwrap();
+
+        assert_eq!(downloaded.len(), 1);
+        assert_eq!(downloaded[0].id, "bm-1");
+    }
+
+    #[test]
+    fn download_all_on_an_unused_collection_is_empty() {
+        let transport = WebDavTransport::new("https://dav.example.com/sync", FakeServer::default());
+        assert!(transport.download_all(Collection::Settings).unwrap().is_empty());
+    }
+
+    #[test]
+    fn re_uploading_the_same_record_after_reading_its_etag_succeeds() {
+        let transport = WebDavTransport::new("https://dav.example.com/sync", FakeServer::default());
+        transport.upload(&record("bm-1")).unwrap();
+
+        transport.upload(&record("bm-1")).unwrap();
+    }
+
+    #[test]
+    fn uploading_over_a_write_this_transport_never_saw_is_rejected() {
+        let transport = WebDavTransport::new("https://dav.example.com/sync", FakeServer::default());
+        transport.upload(&record("bm-1")).unwrap();
+
+        // Simulate another device writing a newer version directly to
+        // the server, behind this transport's back.
+        transport.client.borrow_mut().put("https://dav.example.com/sync/bookmarks/bm-1.json", b"{}", Some("1")).unwrap();
+
+        let err = transport.upload(&record("bm-1")).unwrap_err();
+        assert!(matches!(err, SyncError::TransportRejected { .. }));
+    }
+}
@@ -0,0 +1,19 @@
+/// A bundled tracker list's classification for a domain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TrackerCategory {
+    Advertising,
+    Analytics,
+    Social,
+    Fingerprinting,
+}
+
+impl TrackerCategory {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Advertising => "advertising",
+            Self::Analytics => "analytics",
+            Self::Social => "social",
+            Self::Fingerprinting => "fingerprinting",
+        }
+    }
+}
@@ -0,0 +1,140 @@
+use std::collections::HashMap;
+
+use crynn_engine::TabId;
+
+use crate::category::TrackerCategory;
+use crate::classifier::{classify, host_from_url};
+use crate::strictness::StrictnessLevel;
+
+/// The outcome of evaluating one request against tracking protection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Allowed,
+    Blocked(TrackerCategory),
+}
+
+/// Owns the active [`StrictnessLevel`] and, per tab, how many requests it
+/// has blocked — the data the shield icon in the status bar renders
+/// from.
+#[derive(Debug, Default)]
+pub struct TrackingGuard {
+    strictness: StrictnessLevel,
+    blocked_counts: HashMap<TabId, u32>,
+}
+
+impl TrackingGuard {
+    pub fn new(strictness: StrictnessLevel) -> Self {
+        Self {
+            strictness,
+            blocked_counts: HashMap::new(),
+        }
+    }
+
+    pub fn strictness(&self) -> &StrictnessLevel {
+        &self.strictness
+    }
+
+    pub fn set_strictness(&mut self, strictness: StrictnessLevel) {
+        self.strictness = strictness;
+    }
+
+    /// Classifies `url`'s host and decides whether to block it for
+    /// `tab`, bumping that tab's blocked count when it does.
+    pub fn evaluate(&mut self, tab: TabId, url: &str) -> Verdict {
+        self.evaluate_with_override(tab, url, &[])
+    }
+
+    /// Like [`Self::evaluate`], but additionally blocks any category
+    /// listed in `force` regardless of what [`StrictnessLevel`] would
+    /// otherwise allow — resist-fingerprinting mode uses this to keep
+    /// blocking known fingerprinting scripts even under a
+    /// [`StrictnessLevel::Custom`] selection that leaves the category
+    /// unblocked.
+    pub fn evaluate_with_override(&mut self, tab: TabId, url: &str, force: &[TrackerCategory]) -> Verdict {
+        let Some(host) = host_from_url(url) else {
+            return Verdict::Allowed;
+        };
+        let Some(category) = classify(host) else {
+            return Verdict::Allowed;
+        };
+        if !self.strictness.blocks(category) && !force.contains(&category) {
+            return Verdict::Allowed;
+        }
+        *self.blocked_counts.entry(tab).or_insert(0) += 1;
+        tracing::debug!(?tab, %host, category = category.as_str(), "blocked tracker");
+        Verdict::Blocked(category)
+    }
+
+    pub fn blocked_count(&self, tab: TabId) -> u32 {
+        self.blocked_counts.get(&tab).copied().unwrap_or(0)
+    }
+
+    /// Clears a tab's count, e.g. when it navigates to a new page.
+    pub fn reset_tab(&mut self, tab: TabId) {
+        self.blocked_counts.remove(&tab);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crynn_engine::TabRegistry;
+
+    fn a_tab() -> TabId {
+        TabRegistry::new().open("https://example.com", "Example")
+    }
+
+    #[test]
+    fn evaluate_allows_unrecognized_domains() {
+        let mut guard = TrackingGuard::new(StrictnessLevel::Strict);
+        let tab = a_tab();
+        assert_eq!(guard.evaluate(tab, "https://example.com/page"), Verdict::Allowed);
+        assert_eq!(guard.blocked_count(tab), 0);
+    }
+
+    #[test]
+    fn standard_strictness_allows_social_trackers() {
+        let mut guard = TrackingGuard::new(StrictnessLevel::Standard);
+        let tab = a_tab();
+        assert_eq!(guard.evaluate(tab, "https://platform.twitter.com/widgets.js"), Verdict::Allowed);
+        assert_eq!(guard.blocked_count(tab), 0);
+    }
+
+    #[test]
+    fn strict_strictness_blocks_and_counts_social_trackers() {
+        let mut guard = TrackingGuard::new(StrictnessLevel::Strict);
+        let tab = a_tab();
+        let verdict = guard.evaluate(tab, "https://platform.twitter.com/widgets.js");
+        assert_eq!(verdict, Verdict::Blocked(TrackerCategory::Social));
+        assert_eq!(guard.blocked_count(tab), 1);
+    }
+
+    #[test]
+    fn blocked_counts_accumulate_per_tab_independently() {
+        let mut guard = TrackingGuard::new(StrictnessLevel::Strict);
+        let tab_a = a_tab();
+        guard.evaluate(tab_a, "https://doubleclick.net/ad.js");
+        guard.evaluate(tab_a, "https://google-analytics.com/ga.js");
+        assert_eq!(guard.blocked_count(tab_a), 2);
+    }
+
+    #[test]
+    fn evaluate_with_override_blocks_a_forced_category_under_a_custom_selection_that_excludes_it() {
+        let mut guard = TrackingGuard::new(StrictnessLevel::Custom(vec![TrackerCategory::Social]));
+        let tab = a_tab();
+        assert_eq!(guard.evaluate(tab, "https://fpjs.io/agent.js"), Verdict::Allowed);
+
+        let verdict = guard.evaluate_with_override(tab, "https://fpjs.io/agent.js", &[TrackerCategory::Fingerprinting]);
+        assert_eq!(verdict, Verdict::Blocked(TrackerCategory::Fingerprinting));
+        assert_eq!(guard.blocked_count(tab), 1);
+    }
+
+    #[test]
+    fn reset_tab_clears_its_count() {
+        let mut guard = TrackingGuard::new(StrictnessLevel::Strict);
+        let tab = a_tab();
+        guard.evaluate(tab, "https://doubleclick.net/ad.js");
+        guard.reset_tab(tab);
+        assert_eq!(guard.blocked_count(tab), 0);
+    }
+}
@@ -0,0 +1,20 @@
+//! Classifies request domains into tracker categories using a bundled
+//! list, decides whether to block them per a user-selected
+//! [`StrictnessLevel`], and counts blocked trackers per tab for the
+//! shield UI in the status bar.
+//!
+//! The bundled list in [`list`] is a small seed, not the full
+//! disconnect.me dataset — keeping classification and blocking as pure
+//! data lookups means swapping in a larger, periodically refreshed list
+//! later is a data change, not an architecture one.
+
+mod category;
+mod classifier;
+mod guard;
+mod list;
+mod strictness;
+
+pub use category::TrackerCategory;
+pub use classifier::{classify, host_from_url};
+pub use guard::{TrackingGuard, Verdict};
+pub use strictness::StrictnessLevel;
@@ -0,0 +1,20 @@
+use crate::category::TrackerCategory;
+
+/// Seed tracker list, grouped the way disconnect.me's category lists are:
+/// a handful of well-known domains per category, enough to exercise
+/// classification and blocking correctly. A real deployment would refresh
+/// this from a fetched list on a schedule; that update pipeline doesn't
+/// exist yet, so this ships as the bundled starting point.
+pub(crate) const BUNDLED_LIST: &[(&str, TrackerCategory)] = &[
+    ("doubleclick.net", TrackerCategory::Advertising),
+    ("googlesyndication.com", TrackerCategory::Advertising),
+    ("adnxs.com", TrackerCategory::Advertising),
+    ("googletagmanager.com", TrackerCategory::Analytics),
+    ("google-analytics.com", TrackerCategory::Analytics),
+    ("scorecardresearch.com", TrackerCategory::Analytics),
+    ("connect.facebook.net", TrackerCategory::Social),
+    ("platform.twitter.com", TrackerCategory::Social),
+    ("platform.linkedin.com", TrackerCategory::Social),
+    ("fingerprintjs.com", TrackerCategory::Fingerprinting),
+    ("fpjs.io", TrackerCategory::Fingerprinting),
+];
@@ -0,0 +1,81 @@
+use std::str::FromStr;
+
+use crate::category::TrackerCategory;
+
+/// How aggressively tracking protection blocks the categories it
+/// recognizes. Mirrors the standard/strict split real tracking-protection
+/// UIs expose, plus a custom level for users who pick categories
+/// themselves.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum StrictnessLevel {
+    #[default]
+    Standard,
+    Strict,
+    Custom(Vec<TrackerCategory>),
+}
+
+impl StrictnessLevel {
+    /// Whether this level blocks `category`. `Standard` leaves social
+    /// trackers unblocked, since blocking them breaks embedded share
+    /// buttons and login widgets more often than blocking ads/analytics
+    /// does; `Strict` blocks every recognized category.
+    pub fn blocks(&self, category: TrackerCategory) -> bool {
+        match self {
+            Self::Standard => matches!(
+                category,
+                TrackerCategory::Advertising | TrackerCategory::Analytics | TrackerCategory::Fingerprinting
+            ),
+            Self::Strict => true,
+            Self::Custom(categories) => categories.contains(&category),
+        }
+    }
+}
+
+impl FromStr for StrictnessLevel {
+    type Err = String;
+
+    /// Parses the `tracking.strictness` config value. Only the two named
+    /// levels are reachable from a plain string; `Custom` is built in
+    /// code by whatever UI lets a user pick categories individually.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "standard" => Ok(Self::Standard),
+            "strict" => Ok(Self::Strict),
+            other => Err(format!("unknown tracking-protection strictness level: {other}")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn standard_allows_social_but_blocks_the_rest() {
+        let level = StrictnessLevel::Standard;
+        assert!(!level.blocks(TrackerCategory::Social));
+        assert!(level.blocks(TrackerCategory::Advertising));
+        assert!(level.blocks(TrackerCategory::Analytics));
+        assert!(level.blocks(TrackerCategory::Fingerprinting));
+    }
+
+    #[test]
+    fn strict_blocks_every_category() {
+        let level = StrictnessLevel::Strict;
+        assert!(level.blocks(TrackerCategory::Social));
+        assert!(level.blocks(TrackerCategory::Advertising));
+    }
+
+    #[test]
+    fn custom_blocks_only_the_listed_categories() {
+        let level = StrictnessLevel::Custom(vec![TrackerCategory::Social]);
+        assert!(level.blocks(TrackerCategory::Social));
+        assert!(!level.blocks(TrackerCategory::Advertising));
+    }
+
+    #[test]
+    fn from_str_rejects_unknown_levels() {
+        assert_eq!(StrictnessLevel::from_str("standard"), Ok(StrictnessLevel::Standard));
+        assert!(StrictnessLevel::from_str("off").is_err());
+    }
+}
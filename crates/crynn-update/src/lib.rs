@@ -0,0 +1,25 @@
+//! Signed-manifest self-update. [`UpdateManifest`] is what a channel's
+//! release feed publishes for its newest build; [`verify_manifest`] and
+//! [`verify_package`] are how that manifest and the package it names
+//! earn trust; [`UpdateStager`] drives one check/download/verify
+//! attempt through to "staged, ready to install on next restart".
+//!
+//! There is no install step in this crate — swapping the running
+//! binary out from under itself is the embedding shell's job, not
+//! this crate's, the same way this crate has no download step either:
+//! [`UpdateStager::download_request`] hands back the request a
+//! caller's own transport runs (through `crynn-network`, respecting
+//! `updates.wifi_only` via [`UpdateStager::should_start_download`]).
+//!
+//! Channel selection and the wifi-only setting both live in
+//! `crynn-config`'s `updates` section, the same split tracking
+//! protection's strictness and the cookie policy have between "the
+//! setting" and "the crate that acts on it".
+
+mod manifest;
+mod signature;
+mod stager;
+
+pub use manifest::{Channel, UpdateManifest};
+pub use signature::{verify_manifest, verify_package};
+pub use stager::{UpdateStage, UpdateStager};
@@ -0,0 +1,190 @@
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+/// Release channel a manifest applies to. Mirrors the stable/beta split
+/// `updates.channel` in settings picks between.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Channel {
+    Stable,
+    Beta,
+}
+
+impl Channel {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Channel::Stable => "stable",
+            Channel::Beta => "beta",
+        }
+    }
+}
+
+impl FromStr for Channel {
+    type Err = String;
+
+    /// Parses the `updates.channel` config value.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "stable" => Ok(Self::Stable),
+            "beta" => Ok(Self::Beta),
+            other => Err(format!("unknown update channel: {other}")),
+        }
+    }
+}
+
+/// What a channel's release feed publishes for its newest build: where
+/// to fetch it, how big it is, and what it should hash and sign to.
+/// [`crate::verify_manifest`] checks `signature_hex` before any of this
+/// is trusted; [`crate::verify_package`] checks `sha256_hex` once the
+/// bytes are in hand.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UpdateManifest {
+    pub channel: Channel,
+    pub version: String,
+    pub full_url: String,
+    /// Smaller binary diff against the currently installed version, when
+    /// the release feed has one; [`crate::UpdateStager`] prefers this
+    /// over `full_url` when [`UpdateManifest::uses_delta`] is true.
+    pub delta_url: Option<String>,
+    /// The digest `delta_url`'s bytes must hash to. A diff's hash is
+    /// essentially never equal to `sha256_hex` (the full package's
+    /// digest), so a manifest that publishes `delta_url` without this
+    /// can never pass [`crate::verify_package`] — [`UpdateManifest::uses_delta`]
+    /// treats that combination as "no usable delta" and falls back to
+    /// `full_url` instead.
+    pub delta_sha256: Option<String>,
+    pub size_bytes: u64,
+    pub sha256_hex: String,
+    /// Hex-encoded ed25519 signature over [`UpdateManifest::signed_payload`].
+    pub signature_hex: String,
+}
+
+impl UpdateManifest {
+    /// The bytes the publisher's signature actually covers: every field
+    /// but the signature itself, so tampering with any of them
+    /// invalidates it.
+    pub fn signed_payload(&self) -> Vec<u8> {
+        format!(
+            "{}:{}:{}:{}:{}:{}:{}",
+            self.channel.as_str(),
+            self.version,
+            self.full_url,
+            self.delta_url.as_deref().unwrap_or(""),
+            self.delta_sha256.as_deref().unwrap_or(""),
+            self.size_bytes,
+            self.sha256_hex
+        )
+        .into_bytes()
+    }
+
+    /// Whether [`crate::UpdateStager::download_request`] will fetch
+    /// `delta_url` instead of `full_url`: only when the manifest also
+    /// published a `delta_sha256` to verify it against, so a delta
+    /// advertised without a digest for it is treated as if it weren't
+    /// offered at all rather than downloading bytes that can never pass
+    /// [`crate::verify_package`].
+    pub fn uses_delta(&self) -> bool {
+        self.delta_url.is_some() && self.delta_sha256.is_some()
+    }
+
+    /// The digest a downloaded package's bytes must hash to: `delta_sha256`
+    /// when [`UpdateManifest::uses_delta`], otherwise `sha256_hex`.
+    pub fn expected_sha256(&self) -> &str {
+        if self.uses_delta() {
+            self.delta_sha256.as_deref().unwrap()
+        } else {
+            &self.sha256_hex
+        }
+    }
+
+    /// Whether this manifest's version is newer than `current_version`.
+    /// Both are compared component-by-component as dotted integers
+    /// (`"1.4.0"` > `"1.3.9"`); a non-numeric component sorts as 0 so a
+    /// malformed version never panics the comparison.
+    pub fn is_newer_than(&self, current_version: &str) -> bool {
+        parse_version(&self.version) > parse_version(current_version)
+    }
+}
+
+fn parse_version(version: &str) -> Vec<u32> {
+    version.split('.').map(|part| part.parse().unwrap_or(0)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manifest(version: &str) -> UpdateManifest {
+        UpdateManifest {
+            channel: Channel::Stable,
+            version: version.to_string(),
+            full_url: "https://updates.crynn.example/crynn-1.4.0.pkg".to_string(),
+            delta_url: None,
+            delta_sha256: None,
+            size_bytes: 1024,
+            sha256_hex: "abc123".to_string(),
+            signature_hex: "deadbeef".to_string(),
+        }
+    }
+
+    #[test]
+    fn from_str_parses_known_channels() {
+        assert_eq!(Channel::from_str("stable"), Ok(Channel::Stable));
+        assert_eq!(Channel::from_str("beta"), Ok(Channel::Beta));
+    }
+
+    #[test]
+    fn from_str_rejects_unknown_channels() {
+        assert!(Channel::from_str("nightly").is_err());
+    }
+
+    #[test]
+    fn a_higher_patch_version_is_newer() {
+        assert!(manifest("1.4.1").is_newer_than("1.4.0"));
+        assert!(!manifest("1.4.0").is_newer_than("1.4.1"));
+    }
+
+    #[test]
+    fn equal_versions_are_not_newer() {
+        assert!(!manifest("1.4.0").is_newer_than("1.4.0"));
+    }
+
+    #[test]
+    fn a_malformed_component_compares_as_zero_rather_than_panicking() {
+        assert!(manifest("1.4.0").is_newer_than("1.x.0"));
+    }
+
+    #[test]
+    fn signed_payload_changes_when_any_covered_field_changes() {
+        let base = manifest("1.4.0");
+        let mut tampered = base.clone();
+        tampered.full_url = "https://evil.example/payload.pkg".to_string();
+        assert_ne!(base.signed_payload(), tampered.signed_payload());
+    }
+
+    #[test]
+    fn signed_payload_covers_the_delta_fields_too() {
+        let base = manifest("1.4.0");
+        let mut tampered = base.clone();
+        tampered.delta_url = Some("https://evil.example/payload.delta".to_string());
+        tampered.delta_sha256 = Some("def456".to_string());
+        assert_ne!(base.signed_payload(), tampered.signed_payload());
+    }
+
+    #[test]
+    fn a_delta_url_without_a_delta_digest_is_not_used() {
+        let mut m = manifest("1.4.0");
+        m.delta_url = Some("https://updates.crynn.example/crynn.delta".to_string());
+        assert!(!m.uses_delta());
+        assert_eq!(m.expected_sha256(), m.sha256_hex);
+    }
+
+    #[test]
+    fn a_delta_url_with_a_delta_digest_is_used() {
+        let mut m = manifest("1.4.0");
+        m.delta_url = Some("https://updates.crynn.example/crynn.delta".to_string());
+        m.delta_sha256 = Some("def456".to_string());
+        assert!(m.uses_delta());
+        assert_eq!(m.expected_sha256(), "def456");
+    }
+}
@@ -0,0 +1,116 @@
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+use sha2::{Digest, Sha256};
+
+use crynn_error::UpdateError;
+
+use crate::manifest::UpdateManifest;
+
+/// Verifies `manifest`'s signature against `public_key` — the key this
+/// build was compiled with, pinned to the release signing key. Nothing
+/// in the manifest should be trusted before this passes.
+pub fn verify_manifest(manifest: &UpdateManifest, public_key: &VerifyingKey) -> Result<(), UpdateError> {
+    let signature_bytes: [u8; 64] = hex_decode(&manifest.signature_hex)
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or(UpdateError::SignatureInvalid)?;
+    let signature = Signature::from_bytes(&signature_bytes);
+    public_key
+        .verify(&manifest.signed_payload(), &signature)
+        .map_err(|_| UpdateError::SignatureInvalid)
+}
+
+/// Verifies a downloaded package's bytes hash to the digest `manifest`
+/// promised — [`UpdateManifest::expected_sha256`], which is the delta's
+/// digest rather than the full package's when
+/// [`UpdateManifest::uses_delta`] — so a signed manifest can't be paired
+/// with a tampered or truncated download.
+pub fn verify_package(bytes: &[u8], manifest: &UpdateManifest) -> Result<(), UpdateError> {
+    let digest = hex_encode(&Sha256::digest(bytes));
+    if digest.eq_ignore_ascii_case(manifest.expected_sha256()) {
+        Ok(())
+    } else {
+        Err(UpdateError::DigestMismatch)
+    }
+}
+
+fn hex_encode(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+fn hex_decode(s: &str) -> Option<Vec<u8>> {
+    if !s.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..s.len()).step_by(2).map(|i| u8::from_str_radix(&s[i..i + 2], 16).ok()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use ed25519_dalek::{Signer, SigningKey};
+
+    use super::*;
+    use crate::manifest::Channel;
+
+    fn key(seed: u8) -> SigningKey {
+        SigningKey::from_bytes(&[seed; 32])
+    }
+
+    fn signed_manifest(signing_key: &SigningKey, version: &str, package: &[u8]) -> UpdateManifest {
+        let mut manifest = UpdateManifest {
+            channel: Channel::Stable,
+            version: version.to_string(),
+            full_url: "https://updates.crynn.example/crynn.pkg".to_string(),
+            delta_url: None,
+            delta_sha256: None,
+            size_bytes: package.len() as u64,
+            sha256_hex: hex_encode(&Sha256::digest(package)),
+            signature_hex: String::new(),
+        };
+        let signature = signing_key.sign(&manifest.signed_payload());
+        manifest.signature_hex = hex_encode(&signature.to_bytes());
+        manifest
+    }
+
+    #[test]
+    fn a_manifest_signed_with_the_matching_key_verifies() {
+        let signing_key = key(1);
+        let manifest = signed_manifest(&signing_key, "1.4.0", b"package bytes");
+
+        assert!(verify_manifest(&manifest, &signing_key.verifying_key()).is_ok());
+    }
+
+    #[test]
+    fn a_manifest_signed_with_a_different_key_does_not_verify() {
+        let signing_key = key(1);
+        let other_key = key(2);
+        let manifest = signed_manifest(&signing_key, "1.4.0", b"package bytes");
+
+        assert!(matches!(verify_manifest(&manifest, &other_key.verifying_key()), Err(UpdateError::SignatureInvalid)));
+    }
+
+    #[test]
+    fn tampering_with_a_signed_field_invalidates_the_signature() {
+        let signing_key = key(1);
+        let mut manifest = signed_manifest(&signing_key, "1.4.0", b"package bytes");
+        manifest.full_url = "https://evil.example/payload.pkg".to_string();
+
+        assert!(verify_manifest(&manifest, &signing_key.verifying_key()).is_err());
+    }
+
+    #[test]
+    fn verify_package_accepts_matching_bytes() {
+        let signing_key = key(1);
+        let package = b"package bytes";
+        let manifest = signed_manifest(&signing_key, "1.4.0", package);
+
+        assert!(verify_package(package, &manifest).is_ok());
+    }
+
+    #[test]
+    fn verify_package_rejects_a_truncated_download() {
+        let signing_key = key(1);
+        let package = b"package bytes";
+        let manifest = signed_manifest(&signing_key, "1.4.0", package);
+
+        assert!(matches!(verify_package(b"package byte", &manifest), Err(UpdateError::DigestMismatch)));
+    }
+}
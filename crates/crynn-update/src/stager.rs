@@ -0,0 +1,248 @@
+use ed25519_dalek::VerifyingKey;
+
+use crynn_error::UpdateError;
+use crynn_network::NetworkRequest;
+
+use crate::manifest::UpdateManifest;
+use crate::signature;
+
+/// Where one update attempt currently stands. The settings panel's
+/// updates section renders directly off this rather than keeping its
+/// own duplicate state.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum UpdateStage {
+    #[default]
+    Idle,
+    Checking,
+    UpToDate,
+    Downloading { manifest: UpdateManifest, bytes_so_far: u64 },
+    Verifying { manifest: UpdateManifest },
+    Staged { manifest: UpdateManifest },
+    Failed { reason: String },
+}
+
+/// Drives one update attempt from "check the manifest" through "staged,
+/// ready for the next restart to install". Doesn't perform the network
+/// fetch itself: [`UpdateStager::download_request`] is the request a
+/// caller's transport should run, the same split `crynn-network`'s own
+/// [`crynn_network::NetworkManager`] draws between deciding and sending.
+/// There is no install step — swapping the running binary out from
+/// under itself is the embedding shell's job, not this crate's.
+#[derive(Debug, Default)]
+pub struct UpdateStager {
+    stage: UpdateStage,
+}
+
+impl UpdateStager {
+    pub fn stage(&self) -> &UpdateStage {
+        &self.stage
+    }
+
+    pub fn start_check(&mut self) {
+        self.stage = UpdateStage::Checking;
+    }
+
+    /// Compares a fetched manifest against the running version and
+    /// moves to [`UpdateStage::Downloading`] if it's newer, or
+    /// [`UpdateStage::UpToDate`] otherwise.
+    pub fn receive_manifest(&mut self, manifest: UpdateManifest, current_version: &str) {
+        self.stage = if manifest.is_newer_than(current_version) {
+            UpdateStage::Downloading { manifest, bytes_so_far: 0 }
+        } else {
+            UpdateStage::UpToDate
+        };
+    }
+
+    /// The request a caller's transport should run to fetch the staged
+    /// manifest's package: the delta when [`UpdateManifest::uses_delta`],
+    /// otherwise the full package. `None` outside
+    /// [`UpdateStage::Downloading`].
+    pub fn download_request(&self) -> Option<NetworkRequest> {
+        let UpdateStage::Downloading { manifest, .. } = &self.stage else {
+            return None;
+        };
+        let url = if manifest.uses_delta() { manifest.delta_url.as_deref().unwrap() } else { &manifest.full_url };
+        Some(NetworkRequest::new("GET", url))
+    }
+
+    /// Whether a queued download should actually start now: on a
+    /// metered connection with `wifi_only` set, it holds off, the same
+    /// way `crynn-email`'s sync scheduler backs off rather than burning
+    /// a data plan behind the user's back.
+    pub fn should_start_download(&self, metered: bool, wifi_only: bool) -> bool {
+        matches!(self.stage, UpdateStage::Downloading { .. }) && !(metered && wifi_only)
+    }
+
+    pub fn record_progress(&mut self, bytes_so_far: u64) {
+        if let UpdateStage::Downloading { bytes_so_far: progress, .. } = &mut self.stage {
+            *progress = bytes_so_far;
+        }
+    }
+
+    /// Verifies the downloaded bytes against the manifest's signature
+    /// and digest, then stages the update for install on next restart.
+    /// Leaves the stage at [`UpdateStage::Failed`] rather than reverting
+    /// to [`UpdateStage::Downloading`] on a failure, since a corrupt or
+    /// forged package calls for the user re-triggering a check, not an
+    /// automatic retry of the same bytes.
+    pub fn verify_and_stage(&mut self, bytes: &[u8], public_key: &VerifyingKey) -> Result<(), UpdateError> {
+        let UpdateStage::Downloading { manifest, .. } = self.stage.clone() else {
+            return Err(UpdateError::NoManifestForChannel { channel: "none".to_string() });
+        };
+        self.stage = UpdateStage::Verifying { manifest: manifest.clone() };
+
+        if let Err(e) = signature::verify_manifest(&manifest, public_key).and_then(|_| signature::verify_package(bytes, &manifest)) {
+            self.stage = UpdateStage::Failed { reason: e.to_string() };
+            return Err(e);
+        }
+
+        self.stage = UpdateStage::Staged { manifest };
+        Ok(())
+    }
+
+    /// The manifest of a fully verified, staged update, for the
+    /// "restart to update" prompt. `None` until
+    /// [`UpdateStager::verify_and_stage`] has succeeded.
+    pub fn ready_to_install(&self) -> Option<&UpdateManifest> {
+        match &self.stage {
+            UpdateStage::Staged { manifest } => Some(manifest),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ed25519_dalek::{Signer, SigningKey};
+    use sha2::{Digest, Sha256};
+
+    use super::*;
+    use crate::manifest::Channel;
+
+    fn key() -> SigningKey {
+        SigningKey::from_bytes(&[9u8; 32])
+    }
+
+    fn signed_manifest(signing_key: &SigningKey, version: &str, package: &[u8]) -> UpdateManifest {
+        signed_manifest_with_delta(signing_key, version, package, None)
+    }
+
+    fn signed_manifest_with_delta(signing_key: &SigningKey, version: &str, package: &[u8], delta: Option<&[u8]>) -> UpdateManifest {
+        let mut manifest = UpdateManifest {
+            channel: Channel::Stable,
+            version: version.to_string(),
+            full_url: "https://updates.crynn.example/crynn.pkg".to_string(),
+            delta_url: delta.map(|_| "https://updates.crynn.example/crynn.delta".to_string()),
+            delta_sha256: delta.map(|d| Sha256::digest(d).iter().map(|b| format!("{b:02x}")).collect()),
+            size_bytes: package.len() as u64,
+            sha256_hex: Sha256::digest(package).iter().map(|b| format!("{b:02x}")).collect(),
+            signature_hex: String::new(),
+        };
+        let signature = signing_key.sign(&manifest.signed_payload());
+        manifest.signature_hex = signature.to_bytes().iter().map(|b| format!("{b:02x}")).collect();
+        manifest
+    }
+
+    #[test]
+    fn a_newer_manifest_moves_to_downloading() {
+        let mut stager = UpdateStager::default();
+        let manifest = signed_manifest(&key(), "1.4.0", b"pkg");
+        stager.receive_manifest(manifest.clone(), "1.3.0");
+        assert_eq!(stager.stage(), &UpdateStage::Downloading { manifest, bytes_so_far: 0 });
+    }
+
+    #[test]
+    fn a_manifest_that_is_not_newer_moves_to_up_to_date() {
+        let mut stager = UpdateStager::default();
+        let manifest = signed_manifest(&key(), "1.3.0", b"pkg");
+        stager.receive_manifest(manifest, "1.3.0");
+        assert_eq!(stager.stage(), &UpdateStage::UpToDate);
+    }
+
+    #[test]
+    fn download_request_prefers_the_delta_url() {
+        let mut stager = UpdateStager::default();
+        let manifest = signed_manifest_with_delta(&key(), "1.4.0", b"pkg", Some(b"diff"));
+        stager.receive_manifest(manifest.clone(), "1.3.0");
+
+        let request = stager.download_request().unwrap();
+        assert_eq!(request.url, manifest.delta_url.unwrap());
+    }
+
+    #[test]
+    fn download_request_falls_back_to_the_full_url_without_a_delta_digest() {
+        let mut stager = UpdateStager::default();
+        let signing_key = key();
+        let mut manifest = signed_manifest(&signing_key, "1.4.0", b"pkg");
+        manifest.delta_url = Some("https://updates.crynn.example/crynn.delta".to_string());
+        stager.receive_manifest(manifest.clone(), "1.3.0");
+
+        let request = stager.download_request().unwrap();
+        assert_eq!(request.url, manifest.full_url);
+    }
+
+    #[test]
+    fn a_verified_delta_download_stages_the_update() {
+        let mut stager = UpdateStager::default();
+        let signing_key = key();
+        let manifest = signed_manifest_with_delta(&signing_key, "1.4.0", b"pkg", Some(b"diff"));
+        stager.receive_manifest(manifest.clone(), "1.3.0");
+        assert_eq!(stager.download_request().unwrap().url, manifest.delta_url.clone().unwrap());
+
+        stager.verify_and_stage(b"diff", &signing_key.verifying_key()).unwrap();
+
+        assert_eq!(stager.ready_to_install(), Some(&manifest));
+    }
+
+    #[test]
+    fn a_delta_download_does_not_verify_against_the_full_packages_digest() {
+        let mut stager = UpdateStager::default();
+        let signing_key = key();
+        let manifest = signed_manifest_with_delta(&signing_key, "1.4.0", b"pkg", Some(b"diff"));
+        stager.receive_manifest(manifest, "1.3.0");
+
+        let result = stager.verify_and_stage(b"pkg", &signing_key.verifying_key());
+
+        assert!(matches!(result, Err(UpdateError::DigestMismatch)));
+    }
+
+    #[test]
+    fn download_request_is_none_outside_downloading() {
+        assert!(UpdateStager::default().download_request().is_none());
+    }
+
+    #[test]
+    fn metered_with_wifi_only_holds_off_the_download() {
+        let mut stager = UpdateStager::default();
+        stager.receive_manifest(signed_manifest(&key(), "1.4.0", b"pkg"), "1.3.0");
+
+        assert!(!stager.should_start_download(true, true));
+        assert!(stager.should_start_download(true, false));
+        assert!(stager.should_start_download(false, true));
+    }
+
+    #[test]
+    fn verified_bytes_stage_the_update() {
+        let mut stager = UpdateStager::default();
+        let signing_key = key();
+        let manifest = signed_manifest(&signing_key, "1.4.0", b"pkg");
+        stager.receive_manifest(manifest.clone(), "1.3.0");
+
+        stager.verify_and_stage(b"pkg", &signing_key.verifying_key()).unwrap();
+
+        assert_eq!(stager.ready_to_install(), Some(&manifest));
+    }
+
+    #[test]
+    fn a_digest_mismatch_fails_and_does_not_stage() {
+        let mut stager = UpdateStager::default();
+        let signing_key = key();
+        stager.receive_manifest(signed_manifest(&signing_key, "1.4.0", b"pkg"), "1.3.0");
+
+        let result = stager.verify_and_stage(b"tampered", &signing_key.verifying_key());
+
+        assert!(matches!(result, Err(UpdateError::DigestMismatch)));
+        assert!(stager.ready_to_install().is_none());
+        assert!(matches!(stager.stage(), UpdateStage::Failed { .. }));
+    }
+}
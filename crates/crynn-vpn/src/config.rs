@@ -0,0 +1,100 @@
+use serde::{Deserialize, Serialize};
+
+use crynn_error::VpnError;
+
+use crate::profile::VpnProfile;
+
+/// Every connection profile the user has saved, plus which one
+/// quick-connect should use. Serializes as-is, so [`crate::export_profiles`]/
+/// [`crate::import_profiles`] can move a whole config to another machine.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct VpnConfig {
+    profiles: Vec<VpnProfile>,
+    default_profile: Option<String>,
+}
+
+impl VpnConfig {
+    pub fn profiles(&self) -> &[VpnProfile] {
+        &self.profiles
+    }
+
+    pub fn profile(&self, name: &str) -> Option<&VpnProfile> {
+        self.profiles.iter().find(|p| p.name == name)
+    }
+
+    /// Adds `profile`, replacing any existing profile with the same name.
+    pub fn add_profile(&mut self, profile: VpnProfile) {
+        self.profiles.retain(|p| p.name != profile.name);
+        self.profiles.push(profile);
+    }
+
+    /// Removes the profile named `name`, clearing [`VpnConfig::default_profile`]
+    /// if it pointed there.
+    pub fn remove_profile(&mut self, name: &str) {
+        self.profiles.retain(|p| p.name != name);
+        if self.default_profile.as_deref() == Some(name) {
+            self.default_profile = None;
+        }
+    }
+
+    pub fn default_profile(&self) -> Option<&str> {
+        self.default_profile.as_deref()
+    }
+
+    /// Sets the quick-connect default. Errors with
+    /// [`VpnError::ProfileNotFound`] rather than silently pointing
+    /// quick-connect at a profile that doesn't exist.
+    pub fn set_default_profile(&mut self, name: &str) -> Result<(), VpnError> {
+        if self.profile(name).is_none() {
+            return Err(VpnError::ProfileNotFound { name: name.to_string() });
+        }
+        self.default_profile = Some(name.to_string());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::profile::VpnProtocol;
+
+    fn profile(name: &str) -> VpnProfile {
+        VpnProfile {
+            name: name.to_string(),
+            provider: "Example VPN".to_string(),
+            location: "nl-ams".to_string(),
+            protocol: VpnProtocol::WireGuard,
+            obfuscation: None,
+            fallback_protocol: None,
+            split_tunnel: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn add_profile_replaces_an_existing_profile_with_the_same_name() {
+        let mut config = VpnConfig::default();
+        config.add_profile(profile("home"));
+        config.add_profile(VpnProfile { location: "us-nyc".to_string(), ..profile("home") });
+
+        assert_eq!(config.profiles().len(), 1);
+        assert_eq!(config.profile("home").unwrap().location, "us-nyc");
+    }
+
+    #[test]
+    fn removing_the_default_profile_clears_the_default() {
+        let mut config = VpnConfig::default();
+        config.add_profile(profile("home"));
+        config.set_default_profile("home").unwrap();
+
+        config.remove_profile("home");
+
+        assert_eq!(config.default_profile(), None);
+    }
+
+    #[test]
+    fn set_default_profile_rejects_an_unknown_name() {
+        let mut config = VpnConfig::default();
+        let err = config.set_default_profile("missing").unwrap_err();
+        assert!(matches!(err, VpnError::ProfileNotFound { name } if name == "missing"));
+    }
+}
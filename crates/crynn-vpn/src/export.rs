@@ -0,0 +1,58 @@
+use std::fs;
+use std::path::Path;
+
+use crynn_error::VpnError;
+
+use crate::config::VpnConfig;
+
+/// Writes every saved profile (and the quick-connect default) to
+/// `path` as JSON, for moving them to another machine.
+pub fn export_profiles(config: &VpnConfig, path: impl AsRef<Path>) -> Result<(), VpnError> {
+    let contents = serde_json::to_string_pretty(config)?;
+    fs::write(path, contents)?;
+    Ok(())
+}
+
+/// Reads a [`VpnConfig`] previously written by [`export_profiles`].
+pub fn import_profiles(path: impl AsRef<Path>) -> Result<VpnConfig, VpnError> {
+    let contents = fs::read_to_string(path)?;
+    Ok(serde_json::from_str(&contents)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::profile::{VpnProfile, VpnProtocol};
+
+    #[test]
+    fn exported_profiles_round_trip_through_import() {
+        let dir = std::env::temp_dir().join(format!("crynn-vpn-export-test-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("profiles.json");
+
+        let mut config = VpnConfig::default();
+        config.add_profile(VpnProfile {
+            name: "home".to_string(),
+            provider: "Example VPN".to_string(),
+            location: "nl-ams".to_string(),
+            protocol: VpnProtocol::WireGuard,
+            obfuscation: None,
+            fallback_protocol: None,
+            split_tunnel: Vec::new(),
+        });
+        config.set_default_profile("home").unwrap();
+
+        export_profiles(&config, &path).unwrap();
+        let imported = import_profiles(&path).unwrap();
+
+        assert_eq!(imported, config);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn importing_a_missing_file_fails() {
+        let path = std::env::temp_dir().join(format!("crynn-vpn-missing-{}.json", std::process::id()));
+        assert!(import_profiles(&path).is_err());
+    }
+}
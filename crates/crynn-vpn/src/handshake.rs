@@ -0,0 +1,22 @@
+use crate::profile::{ObfuscationMethod, VpnProtocol};
+
+/// Decides whether a handshake with `protocol` (optionally wrapped in
+/// `obfuscation`) would succeed. No real network handshake happens in
+/// this build — the same split as `crynn_network::SuggestionsTransport`
+/// — so [`crate::VpnManager::connect_profile`] takes a probe rather
+/// than attempting one itself: a fake for tests, eventually a real one
+/// once a VPN helper process exists to ask.
+pub trait HandshakeProbe {
+    fn can_handshake(&mut self, protocol: VpnProtocol, obfuscation: Option<ObfuscationMethod>) -> bool;
+}
+
+/// A probe that always succeeds, for callers that don't need fallback
+/// behavior and just want `connect_profile` to proceed.
+#[derive(Debug, Default)]
+pub struct AlwaysHandshake;
+
+impl HandshakeProbe for AlwaysHandshake {
+    fn can_handshake(&mut self, _protocol: VpnProtocol, _obfuscation: Option<ObfuscationMethod>) -> bool {
+        true
+    }
+}
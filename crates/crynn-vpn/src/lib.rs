@@ -0,0 +1,39 @@
+//! Named VPN connection profiles and the decision contract for using
+//! them. No real tunnel exists in this build — establishing one is
+//! `crynn_ipc::Supervisor::spawn`'s job once this tree has a concrete
+//! VPN helper binary to hand it, the same split `crynn-shell`'s
+//! `VpnHelperHandle` already documents — so [`VpnManager::connect_profile`]
+//! only validates a profile and records that the connection is "on",
+//! the same contract a real tunnel-establishing implementation would
+//! fill in later without this API changing.
+//!
+//! [`VpnProfile`] bundles a provider, location, protocol, and
+//! split-tunnel rules under one name; [`VpnConfig`] is the saved set of
+//! profiles plus which one [`VpnManager::quick_connect`] uses.
+//! [`export_profiles`]/[`import_profiles`] move a [`VpnConfig`] to
+//! another machine as JSON.
+//!
+//! Every [`VpnManager::connect_profile`]/[`VpnManager::disconnect`] pair
+//! opens and closes a [`VpnSession`], kept in memory for
+//! [`VpnManager::session_history`]; `crynn-storage`'s own `VpnSession`
+//! is where a shell persists one across restarts.
+//!
+//! A profile's [`VpnProfile::protocol`] can be wrapped in an
+//! [`ObfuscationMethod`] for networks that block plain WireGuard/
+//! OpenVPN; [`VpnManager::connect_profile`] asks an injected
+//! [`HandshakeProbe`] whether that would succeed, and falls back to
+//! [`VpnProfile::fallback_protocol`] unobfuscated if not.
+
+mod config;
+mod export;
+mod handshake;
+mod manager;
+mod profile;
+mod session;
+
+pub use config::VpnConfig;
+pub use export::{export_profiles, import_profiles};
+pub use handshake::{AlwaysHandshake, HandshakeProbe};
+pub use manager::{VpnManager, VpnStatus};
+pub use profile::{ObfuscationMethod, SplitTunnelMode, SplitTunnelRule, VpnProfile, VpnProtocol};
+pub use session::VpnSession;
@@ -0,0 +1,234 @@
+use crynn_error::VpnError;
+
+use crate::config::VpnConfig;
+use crate::handshake::HandshakeProbe;
+use crate::session::VpnSession;
+
+/// Whether [`VpnManager`] currently believes it's tunneling traffic.
+/// Never backed by a real tunnel yet — see [`VpnManager::connect_profile`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum VpnStatus {
+    #[default]
+    Disconnected,
+    Connected { profile: String },
+}
+
+/// Drives connection profiles and keeps a record of the sessions they
+/// opened. [`VpnManager::connect_profile`] is only the decision half of
+/// connecting: validating the profile exists and recording [`VpnStatus`]
+/// plus a new [`VpnSession`]. Actually establishing a tunnel is the VPN
+/// helper process's job, once one exists to spawn; byte counts are
+/// supplied by the caller at [`VpnManager::disconnect`] for the same
+/// reason — this manager has no real traffic to count.
+#[derive(Debug, Clone, Default)]
+pub struct VpnManager {
+    config: VpnConfig,
+    status: VpnStatus,
+    sessions: Vec<VpnSession>,
+}
+
+impl VpnManager {
+    pub fn new(config: VpnConfig) -> Self {
+        Self { config, status: VpnStatus::Disconnected, sessions: Vec::new() }
+    }
+
+    pub fn config(&self) -> &VpnConfig {
+        &self.config
+    }
+
+    pub fn config_mut(&mut self) -> &mut VpnConfig {
+        &mut self.config
+    }
+
+    pub fn status(&self) -> &VpnStatus {
+        &self.status
+    }
+
+    /// Connects using the profile named `name` at time `at`, opening a
+    /// new [`VpnSession`]. Fails with [`VpnError::ProfileNotFound`]
+    /// rather than connecting to nothing.
+    ///
+    /// Asks `probe` whether the profile's protocol (wrapped in its
+    /// [`crate::ObfuscationMethod`], if any) can handshake; if not and
+    /// [`crate::VpnProfile::fallback_protocol`] is set, retries with
+    /// that protocol unobfuscated. Fails with
+    /// [`VpnError::HandshakeFailed`] if neither attempt succeeds.
+    pub fn connect_profile(&mut self, name: &str, at: u64, probe: &mut dyn HandshakeProbe) -> Result<(), VpnError> {
+        let profile = self.config.profile(name).ok_or_else(|| VpnError::ProfileNotFound { name: name.to_string() })?.clone();
+
+        let handshook = if probe.can_handshake(profile.protocol, profile.obfuscation) {
+            true
+        } else if let Some(fallback) = profile.fallback_protocol {
+            probe.can_handshake(fallback, None)
+        } else {
+            false
+        };
+        if !handshook {
+            return Err(VpnError::HandshakeFailed { server: profile.location.clone() });
+        }
+
+        self.sessions.push(VpnSession {
+            provider: profile.provider,
+            location: profile.location,
+            connected_at: at,
+            disconnected_at: None,
+            bytes_up: 0,
+            bytes_down: 0,
+        });
+        self.status = VpnStatus::Connected { profile: name.to_string() };
+        Ok(())
+    }
+
+    /// Connects using [`VpnConfig::default_profile`] at time `at`. Fails
+    /// with [`VpnError::NoDefaultProfile`] if quick-connect has nothing set.
+    pub fn quick_connect(&mut self, at: u64, probe: &mut dyn HandshakeProbe) -> Result<(), VpnError> {
+        let name = self.config.default_profile().ok_or(VpnError::NoDefaultProfile)?.to_string();
+        self.connect_profile(&name, at, probe)
+    }
+
+    /// Closes the current session, recording when it ended and how much
+    /// traffic it carried. A no-op if nothing is connected.
+    pub fn disconnect(&mut self, at: u64, bytes_up: u64, bytes_down: u64) {
+        if let Some(session) = self.sessions.last_mut() {
+            if session.disconnected_at.is_none() {
+                session.disconnected_at = Some(at);
+                session.bytes_up = bytes_up;
+                session.bytes_down = bytes_down;
+            }
+        }
+        self.status = VpnStatus::Disconnected;
+    }
+
+    /// Every session whose `connected_at` falls in `since..until`, for
+    /// the VPN panel's usage history and data-usage chart.
+    pub fn session_history(&self, since: u64, until: u64) -> Vec<&VpnSession> {
+        self.sessions.iter().filter(|s| s.connected_at >= since && s.connected_at < until).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::handshake::AlwaysHandshake;
+    use crate::profile::{ObfuscationMethod, VpnProfile, VpnProtocol};
+
+    fn manager_with_profile(name: &str) -> VpnManager {
+        let mut config = VpnConfig::default();
+        config.add_profile(VpnProfile {
+            name: name.to_string(),
+            provider: "Example VPN".to_string(),
+            location: "nl-ams".to_string(),
+            protocol: VpnProtocol::WireGuard,
+            obfuscation: None,
+            fallback_protocol: None,
+            split_tunnel: Vec::new(),
+        });
+        VpnManager::new(config)
+    }
+
+    /// Fails the protocols in `blocked`, succeeds on everything else —
+    /// a stand-in for a network that blocks plain WireGuard/OpenVPN.
+    struct BlockingNetwork {
+        blocked: Vec<VpnProtocol>,
+    }
+
+    impl HandshakeProbe for BlockingNetwork {
+        fn can_handshake(&mut self, protocol: VpnProtocol, _obfuscation: Option<ObfuscationMethod>) -> bool {
+            !self.blocked.contains(&protocol)
+        }
+    }
+
+    #[test]
+    fn connect_profile_records_status_and_opens_a_session() {
+        let mut manager = manager_with_profile("home");
+        manager.connect_profile("home", 100, &mut AlwaysHandshake).unwrap();
+
+        assert_eq!(manager.status(), &VpnStatus::Connected { profile: "home".to_string() });
+        let history = manager.session_history(0, u64::MAX);
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].connected_at, 100);
+        assert_eq!(history[0].disconnected_at, None);
+    }
+
+    #[test]
+    fn connect_profile_rejects_an_unknown_name() {
+        let mut manager = manager_with_profile("home");
+        let err = manager.connect_profile("office", 0, &mut AlwaysHandshake).unwrap_err();
+        assert!(matches!(err, VpnError::ProfileNotFound { name } if name == "office"));
+    }
+
+    #[test]
+    fn connect_profile_fails_with_no_fallback_when_the_probe_rejects_it() {
+        let mut manager = manager_with_profile("home");
+        let mut blocked = BlockingNetwork { blocked: vec![VpnProtocol::WireGuard] };
+
+        let err = manager.connect_profile("home", 0, &mut blocked).unwrap_err();
+
+        assert!(matches!(err, VpnError::HandshakeFailed { server } if server == "nl-ams"));
+        assert_eq!(manager.status(), &VpnStatus::Disconnected);
+    }
+
+    #[test]
+    fn connect_profile_falls_back_to_the_unobfuscated_fallback_protocol() {
+        let mut config = VpnConfig::default();
+        config.add_profile(VpnProfile {
+            name: "home".to_string(),
+            provider: "Example VPN".to_string(),
+            location: "nl-ams".to_string(),
+            protocol: VpnProtocol::WireGuard,
+            obfuscation: Some(ObfuscationMethod::Obfs4),
+            fallback_protocol: Some(VpnProtocol::OpenVpn),
+            split_tunnel: Vec::new(),
+        });
+        let mut manager = VpnManager::new(config);
+        let mut blocked = BlockingNetwork { blocked: vec![VpnProtocol::WireGuard] };
+
+        manager.connect_profile("home", 0, &mut blocked).unwrap();
+
+        assert_eq!(manager.status(), &VpnStatus::Connected { profile: "home".to_string() });
+    }
+
+    #[test]
+    fn quick_connect_uses_the_default_profile() {
+        let mut manager = manager_with_profile("home");
+        manager.config_mut().set_default_profile("home").unwrap();
+
+        manager.quick_connect(0, &mut AlwaysHandshake).unwrap();
+
+        assert_eq!(manager.status(), &VpnStatus::Connected { profile: "home".to_string() });
+    }
+
+    #[test]
+    fn quick_connect_without_a_default_profile_fails() {
+        let mut manager = manager_with_profile("home");
+        let err = manager.quick_connect(0, &mut AlwaysHandshake).unwrap_err();
+        assert!(matches!(err, VpnError::NoDefaultProfile));
+    }
+
+    #[test]
+    fn disconnect_closes_the_open_session_and_records_usage() {
+        let mut manager = manager_with_profile("home");
+        manager.connect_profile("home", 100, &mut AlwaysHandshake).unwrap();
+
+        manager.disconnect(160, 1024, 2048);
+
+        assert_eq!(manager.status(), &VpnStatus::Disconnected);
+        let history = manager.session_history(0, u64::MAX);
+        assert_eq!(history[0].disconnected_at, Some(160));
+        assert_eq!(history[0].bytes_up, 1024);
+        assert_eq!(history[0].bytes_down, 2048);
+    }
+
+    #[test]
+    fn session_history_excludes_sessions_outside_the_range() {
+        let mut manager = manager_with_profile("home");
+        manager.connect_profile("home", 5, &mut AlwaysHandshake).unwrap();
+        manager.disconnect(10, 0, 0);
+        manager.connect_profile("home", 500, &mut AlwaysHandshake).unwrap();
+
+        let history = manager.session_history(0, 100);
+
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].connected_at, 5);
+    }
+}
@@ -0,0 +1,52 @@
+use serde::{Deserialize, Serialize};
+
+/// Tunneling protocol a [`VpnProfile`] connects with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum VpnProtocol {
+    WireGuard,
+    OpenVpn,
+}
+
+/// Wraps a profile's chosen [`VpnProtocol`] so its traffic doesn't look
+/// like WireGuard/OpenVPN to a network that blocks those outright.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ObfuscationMethod {
+    Shadowsocks,
+    Obfs4,
+}
+
+/// Whether a [`SplitTunnelRule`]'s target is the thing that goes
+/// through the tunnel, or the thing that's carved out of it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SplitTunnelMode {
+    /// Only `target` goes through the tunnel; everything else is direct.
+    Include,
+    /// `target` goes direct; everything else goes through the tunnel.
+    Exclude,
+}
+
+/// One split-tunnel rule: an app name or CIDR range, and which side of
+/// the tunnel it belongs on.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SplitTunnelRule {
+    pub target: String,
+    pub mode: SplitTunnelMode,
+}
+
+/// A named, reusable connection setup: which server to use, how to
+/// reach it, and which traffic should bypass the tunnel.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VpnProfile {
+    pub name: String,
+    pub provider: String,
+    pub location: String,
+    pub protocol: VpnProtocol,
+    /// Wraps `protocol` in a stealth transport for networks that block
+    /// plain WireGuard/OpenVPN outright. `None` connects unwrapped.
+    pub obfuscation: Option<ObfuscationMethod>,
+    /// Protocol to retry, unobfuscated, if `protocol` can't handshake —
+    /// see [`crate::VpnManager::connect_profile`]. `None` means don't
+    /// retry; just report the failure.
+    pub fallback_protocol: Option<VpnProtocol>,
+    pub split_tunnel: Vec<SplitTunnelRule>,
+}
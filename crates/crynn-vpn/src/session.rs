@@ -0,0 +1,15 @@
+use serde::{Deserialize, Serialize};
+
+/// One VPN connection, from connect to disconnect, with how much
+/// traffic it carried. `disconnected_at`/`bytes_*` are filled in by
+/// [`crate::VpnManager::disconnect`]; a session still in progress has
+/// `disconnected_at` at `None`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VpnSession {
+    pub provider: String,
+    pub location: String,
+    pub connected_at: u64,
+    pub disconnected_at: Option<u64>,
+    pub bytes_up: u64,
+    pub bytes_down: u64,
+}